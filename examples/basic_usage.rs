@@ -25,6 +25,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             provider: Some("github".to_string()),
             use_ssh: false,
             priority: 0,
+            ca_cert_path: None,
+            insecure_skip_tls_verify: false,
+            token_expires_at: None,
+            groups: Vec::new(),
+            api_flavor: None,
         },
     );
 
@@ -40,6 +45,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             provider: Some("gitlab".to_string()),
             use_ssh: false,
             priority: 1,
+            ca_cert_path: None,
+            insecure_skip_tls_verify: false,
+            token_expires_at: None,
+            groups: Vec::new(),
+            api_flavor: None,
         },
     );
 
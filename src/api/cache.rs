@@ -0,0 +1,205 @@
+//! Short-lived response cache for API requests
+//!
+//! Repeated identical GET requests within one process (e.g. `create_branch`
+//! fetching the repository metadata again) reuse the cached response instead
+//! of spending API quota. Entries are scoped to the credentials that fetched
+//! them, and credential checks always go to the network.
+
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Default time-to-live for cached responses
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// In-memory TTL cache for JSON API responses
+///
+/// Cloning is cheap; clones share the same underlying storage.
+#[derive(Clone)]
+pub struct ResponseCache {
+    entries: Option<Arc<Mutex<HashMap<String, CacheEntry>>>>,
+    ttl: Duration,
+}
+
+struct CacheEntry {
+    value: Value,
+    inserted_at: Instant,
+}
+
+impl ResponseCache {
+    /// Create a new, empty cache with the given TTL
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            entries: Some(Arc::new(Mutex::new(HashMap::new()))),
+            ttl,
+        }
+    }
+
+    /// Create a cache that never stores anything (useful for tests)
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self {
+            entries: None,
+            ttl: Duration::ZERO,
+        }
+    }
+
+    /// Get the process-wide cache shared by all providers
+    #[must_use]
+    pub fn shared() -> Self {
+        static SHARED: OnceLock<ResponseCache> = OnceLock::new();
+        SHARED
+            .get_or_init(|| ResponseCache::new(DEFAULT_CACHE_TTL))
+            .clone()
+    }
+
+    /// Check whether caching is enabled
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.entries.is_some()
+    }
+
+    /// Look up a fresh entry, evicting it if it has expired
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<Value> {
+        let entries = self.entries.as_ref()?;
+        let mut entries = entries.lock().unwrap();
+
+        match entries.get(key) {
+            Some(entry) if entry.inserted_at.elapsed() < self.ttl => {
+                debug!("Cache hit: {}", key);
+                Some(entry.value.clone())
+            }
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Store a response
+    pub fn insert(&self, key: impl Into<String>, value: Value) {
+        if let Some(entries) = &self.entries {
+            entries.lock().unwrap().insert(
+                key.into(),
+                CacheEntry {
+                    value,
+                    inserted_at: Instant::now(),
+                },
+            );
+        }
+    }
+
+    /// Drop all entries whose key starts with `prefix`
+    ///
+    /// Called after mutating requests so later reads see fresh data.
+    pub fn invalidate_prefix(&self, prefix: &str) {
+        if let Some(entries) = &self.entries {
+            entries
+                .lock()
+                .unwrap()
+                .retain(|key, _| !key.starts_with(prefix));
+        }
+    }
+
+    /// Remove all entries
+    pub fn clear(&self) {
+        if let Some(entries) = &self.entries {
+            entries.lock().unwrap().clear();
+        }
+    }
+
+    /// Number of stored entries (including expired ones not yet evicted)
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries
+            .as_ref()
+            .map_or(0, |entries| entries.lock().unwrap().len())
+    }
+
+    /// Check if the cache holds no entries
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Key prefix for responses fetched with `token` as `username` on `provider`
+///
+/// Remotes with the same username but different tokens get separate
+/// entries. Only a hash of the token goes into the key, so it never shows up
+/// in debug logs.
+#[must_use]
+pub fn scope(provider: &str, username: &str, token: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    token.hash(&mut hasher);
+    format!("{provider}:{username}:{:016x}:", hasher.finish())
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_TTL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_cache_hit() {
+        let cache = ResponseCache::new(Duration::from_secs(60));
+        cache.insert("github:user:/repos/user/repo", json!({"name": "repo"}));
+
+        let value = cache.get("github:user:/repos/user/repo").unwrap();
+        assert_eq!(value["name"], "repo");
+        assert!(cache.get("github:user:/rate_limit").is_none());
+    }
+
+    #[test]
+    fn test_cache_expiry() {
+        let cache = ResponseCache::new(Duration::from_millis(10));
+        cache.insert("key", json!(1));
+
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(cache.get("key").is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_disabled_cache() {
+        let cache = ResponseCache::disabled();
+        cache.insert("key", json!(1));
+
+        assert!(!cache.is_enabled());
+        assert!(cache.get("key").is_none());
+    }
+
+    #[test]
+    fn test_invalidate_prefix() {
+        let cache = ResponseCache::new(Duration::from_secs(60));
+        cache.insert("github:a:/x", json!(1));
+        cache.insert("github:a:/y", json!(2));
+        cache.insert("gitlab:a:/x", json!(3));
+
+        cache.invalidate_prefix("github:a:");
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get("gitlab:a:/x").is_some());
+    }
+
+    #[test]
+    fn test_scope_separates_tokens() {
+        let first = scope("github", "alice", "token-one");
+        assert_eq!(first, scope("github", "alice", "token-one"));
+        assert_ne!(first, scope("github", "alice", "token-two"));
+        assert!(first.starts_with("github:alice:"));
+        assert!(!first.contains("token-one"));
+    }
+}
@@ -0,0 +1,327 @@
+//! Circuit breaker for API hosts
+//!
+//! Per-request retries cope with a flaky request, but during a provider outage
+//! every parallel call retries on its own and the command keeps hammering the
+//! host. The breaker keeps one failure budget per host for the whole process:
+//! after too many failures within a window the circuit opens and further calls
+//! to that host fail fast until a cooldown has passed. The first call after the
+//! cooldown is let through as a probe while the others keep failing fast; a
+//! success closes the circuit, a failure reopens it.
+
+use crate::utils::error::{MultiGitError, Result};
+use reqwest::{RequestBuilder, Response};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+use url::Url;
+
+/// Failures within [`DEFAULT_FAILURE_WINDOW`] that open a circuit
+pub const DEFAULT_FAILURE_THRESHOLD: usize = 8;
+
+/// Window in which failures count towards the threshold
+pub const DEFAULT_FAILURE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Time an open circuit fails fast before letting a call through again
+pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// When a circuit opens and for how long
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitPolicy {
+    /// Failures within `window` that open the circuit
+    pub failure_threshold: usize,
+    /// Window in which failures are counted
+    pub window: Duration,
+    /// Time the circuit stays open
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitPolicy {
+    fn default() -> Self {
+        Self {
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            window: DEFAULT_FAILURE_WINDOW,
+            cooldown: DEFAULT_COOLDOWN,
+        }
+    }
+}
+
+#[derive(Default)]
+struct CircuitState {
+    failures: VecDeque<Instant>,
+    opened_at: Option<Instant>,
+    /// When the half-open probe was let through, until it reports back
+    probe_started: Option<Instant>,
+}
+
+/// Failure budgets for API hosts
+///
+/// Cloning is cheap; clones share the same state.
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    circuits: Arc<Mutex<HashMap<String, CircuitState>>>,
+    policy: CircuitPolicy,
+}
+
+impl CircuitBreaker {
+    /// Create a breaker with its own state
+    #[must_use]
+    pub fn new(policy: CircuitPolicy) -> Self {
+        Self {
+            circuits: Arc::new(Mutex::new(HashMap::new())),
+            policy,
+        }
+    }
+
+    /// Get the process-wide breaker shared by all providers
+    #[must_use]
+    pub fn shared() -> Self {
+        static SHARED: OnceLock<CircuitBreaker> = OnceLock::new();
+        SHARED
+            .get_or_init(|| CircuitBreaker::new(CircuitPolicy::default()))
+            .clone()
+    }
+
+    /// Circuit for the host of `api_url`, reported as `provider`
+    #[must_use]
+    pub fn for_host(&self, provider: &str, api_url: &str) -> HostCircuit {
+        let host = Url::parse(api_url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_lowercase))
+            .unwrap_or_else(|| api_url.to_string());
+
+        // Name public hosts after the provider ("github"), others by host too
+        let public = crate::utils::git_url::provider_for_host(host.trim_start_matches("api."))
+            == Some(provider);
+        let label = if public {
+            provider.to_string()
+        } else {
+            format!("{provider} ({host})")
+        };
+
+        HostCircuit {
+            breaker: self.clone(),
+            host,
+            label,
+        }
+    }
+
+    /// Fail with [`MultiGitError::CircuitOpen`] while the circuit for `host` is open
+    ///
+    /// Once the cooldown has passed one call is let through as a probe; the
+    /// circuit stays open for the others until it succeeds or fails, or for
+    /// another cooldown if it never reports back.
+    pub fn check(&self, host: &str, label: &str) -> Result<()> {
+        let mut circuits = self.circuits.lock().unwrap();
+        let Some(state) = circuits.get_mut(host) else {
+            return Ok(());
+        };
+
+        if let Some(since) = state.opened_at.or(state.probe_started) {
+            let remaining = self.policy.cooldown.saturating_sub(since.elapsed());
+            if !remaining.is_zero() {
+                return Err(MultiGitError::circuit_open(
+                    label,
+                    remaining.as_secs().max(1),
+                ));
+            }
+
+            debug!("Circuit for {} half-open, letting a request through", label);
+            state.opened_at = None;
+            state.probe_started = Some(Instant::now());
+        }
+
+        Ok(())
+    }
+
+    /// Record a successful call to `host`, closing its circuit
+    pub fn record_success(&self, host: &str) {
+        let mut circuits = self.circuits.lock().unwrap();
+        if let Some(state) = circuits.get_mut(host) {
+            state.failures.clear();
+            state.probe_started = None;
+        }
+    }
+
+    /// Record a failed call to `host`, opening its circuit when the budget is spent
+    pub fn record_failure(&self, host: &str, label: &str) {
+        let mut circuits = self.circuits.lock().unwrap();
+        let state = circuits.entry(host.to_string()).or_default();
+        let now = Instant::now();
+
+        state.failures.push_back(now);
+        while state
+            .failures
+            .front()
+            .is_some_and(|failed| now.duration_since(*failed) > self.policy.window)
+        {
+            state.failures.pop_front();
+        }
+
+        if state.opened_at.is_none()
+            && (state.probe_started.is_some()
+                || state.failures.len() >= self.policy.failure_threshold)
+        {
+            warn!(
+                "Circuit open for {} after {} failed request(s); failing fast for {}s",
+                label,
+                state.failures.len(),
+                self.policy.cooldown.as_secs()
+            );
+            state.opened_at = Some(now);
+            state.probe_started = None;
+        }
+    }
+
+    /// Whether calls to `host` currently fail fast
+    #[must_use]
+    pub fn is_open(&self, host: &str) -> bool {
+        self.circuits
+            .lock()
+            .unwrap()
+            .get(host)
+            .and_then(|state| state.opened_at)
+            .is_some_and(|opened_at| opened_at.elapsed() < self.policy.cooldown)
+    }
+}
+
+/// The circuit of one API host, held by a provider
+#[derive(Clone)]
+pub struct HostCircuit {
+    breaker: CircuitBreaker,
+    host: String,
+    label: String,
+}
+
+impl HostCircuit {
+    /// Host the circuit guards
+    #[must_use]
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Send `request` unless the circuit is open
+    ///
+    /// Connection errors and 5xx responses count as failures; any other
+    /// response, including 4xx, shows the host is up.
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        self.breaker.check(&self.host, &self.label)?;
+
+        match request.send().await {
+            Ok(response) => {
+                if response.status().is_server_error() {
+                    self.breaker.record_failure(&self.host, &self.label);
+                } else {
+                    self.breaker.record_success(&self.host);
+                }
+                Ok(response)
+            }
+            Err(e) => {
+                self.breaker.record_failure(&self.host, &self.label);
+                Err(e.into())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(cooldown: Duration) -> CircuitPolicy {
+        CircuitPolicy {
+            failure_threshold: 3,
+            window: Duration::from_secs(60),
+            cooldown,
+        }
+    }
+
+    #[test]
+    fn test_opens_after_threshold() {
+        let breaker = CircuitBreaker::new(policy(Duration::from_secs(60)));
+
+        for _ in 0..2 {
+            breaker.record_failure("api.github.com", "github");
+        }
+        assert!(breaker.check("api.github.com", "github").is_ok());
+
+        breaker.record_failure("api.github.com", "github");
+        assert!(breaker.is_open("api.github.com"));
+        let err = breaker.check("api.github.com", "github").unwrap_err();
+        assert!(err.is_circuit_open());
+        assert!(err.to_string().contains("Circuit open for github"));
+
+        // Other hosts are unaffected
+        assert!(breaker.check("gitlab.com", "gitlab").is_ok());
+    }
+
+    #[test]
+    fn test_success_resets_budget() {
+        let breaker = CircuitBreaker::new(policy(Duration::from_secs(60)));
+
+        breaker.record_failure("gitlab.com", "gitlab");
+        breaker.record_failure("gitlab.com", "gitlab");
+        breaker.record_success("gitlab.com");
+        breaker.record_failure("gitlab.com", "gitlab");
+
+        assert!(!breaker.is_open("gitlab.com"));
+    }
+
+    #[test]
+    fn test_half_open_after_cooldown() {
+        let breaker = CircuitBreaker::new(policy(Duration::ZERO));
+
+        for _ in 0..3 {
+            breaker.record_failure("codeberg.org", "gitea");
+        }
+        // Cooldown over: one call is let through
+        assert!(breaker.check("codeberg.org", "gitea").is_ok());
+
+        // ...and a single failure reopens the circuit
+        breaker.record_failure("codeberg.org", "gitea");
+        let circuits = breaker.circuits.lock().unwrap();
+        assert!(circuits["codeberg.org"].opened_at.is_some());
+    }
+
+    #[test]
+    fn test_half_open_lets_one_probe_through() {
+        let breaker = CircuitBreaker::new(policy(Duration::from_secs(60)));
+
+        for _ in 0..3 {
+            breaker.record_failure("codeberg.org", "gitea");
+        }
+        // Pretend the cooldown has passed
+        let expired = Instant::now().checked_sub(Duration::from_secs(61));
+        breaker
+            .circuits
+            .lock()
+            .unwrap()
+            .get_mut("codeberg.org")
+            .unwrap()
+            .opened_at = expired;
+
+        assert!(breaker.check("codeberg.org", "gitea").is_ok());
+        // Concurrent callers keep failing fast while the probe is in flight
+        assert!(breaker
+            .check("codeberg.org", "gitea")
+            .unwrap_err()
+            .is_circuit_open());
+
+        breaker.record_success("codeberg.org");
+        assert!(breaker.check("codeberg.org", "gitea").is_ok());
+        assert!(breaker.check("codeberg.org", "gitea").is_ok());
+    }
+
+    #[test]
+    fn test_for_host_labels() {
+        let breaker = CircuitBreaker::new(CircuitPolicy::default());
+
+        let circuit = breaker.for_host("github", "https://api.github.com");
+        assert_eq!(circuit.host(), "api.github.com");
+        assert_eq!(circuit.label, "github");
+
+        let circuit = breaker.for_host("gitlab", "https://gitlab.example.com/api/v4");
+        assert_eq!(circuit.host(), "gitlab.example.com");
+        assert_eq!(circuit.label, "gitlab (gitlab.example.com)");
+    }
+}
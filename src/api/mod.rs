@@ -1,7 +1,10 @@
 //! API client utilities for communicating with Git hosting providers
 //!
-//! This module provides HTTP client functionality, rate limiting, and retry logic.
+//! This module provides HTTP client functionality, rate limiting, retry logic
+//! and per-host circuit breaking.
 
+pub mod cache;
+pub mod circuit_breaker;
 pub mod client;
 pub mod rate_limiter;
 pub mod retry;
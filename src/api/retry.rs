@@ -73,11 +73,43 @@ impl RetryConfig {
 ///     api_call().await
 /// }).await?;
 /// ```
-pub async fn retry_async<F, Fut, T, E>(config: RetryConfig, mut operation: F) -> Result<T, E>
+pub async fn retry_async<F, Fut, T, E>(config: RetryConfig, operation: F) -> Result<T, E>
 where
     F: FnMut() -> Fut,
     Fut: std::future::Future<Output = Result<T, E>>,
     E: std::fmt::Display,
+{
+    retry_async_if(config, |_| true, operation).await
+}
+
+/// Retry an API request, giving up at once when the host's circuit is open
+pub async fn retry_api<F, Fut, T>(
+    config: RetryConfig,
+    operation: F,
+) -> crate::utils::error::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = crate::utils::error::Result<T>>,
+{
+    retry_async_if(
+        config,
+        |e: &crate::utils::error::MultiGitError| !e.is_circuit_open(),
+        operation,
+    )
+    .await
+}
+
+/// Retry a fallible async operation while `should_retry` accepts the error
+pub async fn retry_async_if<F, Fut, T, E, P>(
+    config: RetryConfig,
+    should_retry: P,
+    mut operation: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, E>>,
+    E: std::fmt::Display,
+    P: Fn(&E) -> bool,
 {
     let mut attempt = 0;
 
@@ -92,6 +124,9 @@ where
                 return Ok(result);
             }
             Err(e) => {
+                if !should_retry(&e) {
+                    return Err(e);
+                }
                 if attempt >= config.max_attempts {
                     warn!("Operation failed after {} attempts: {}", attempt, e);
                     return Err(e);
@@ -206,4 +241,24 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_retry_api_stops_on_open_circuit() {
+        let attempts = Arc::new(Mutex::new(0));
+        let counter = attempts.clone();
+
+        let result = retry_api(RetryConfig::default(), || {
+            let counter = counter.clone();
+            async move {
+                *counter.lock().unwrap() += 1;
+                Err::<(), _>(crate::utils::error::MultiGitError::circuit_open(
+                    "github", 30,
+                ))
+            }
+        })
+        .await;
+
+        assert!(result.unwrap_err().is_circuit_open());
+        assert_eq!(*attempts.lock().unwrap(), 1);
+    }
 }
@@ -0,0 +1,138 @@
+//! Abort command implementation
+//!
+//! Abandon an in-progress merge, rebase, cherry-pick or revert and return the
+//! repository to where it was before the operation started.
+
+use crate::cli::interactive;
+use crate::git::operations::GitOperations;
+use crate::git::repo_state::OperationInProgress;
+use crate::ui::formatter::Status;
+use crate::utils::error::{MultiGitError, Result};
+use std::path::Path;
+use std::process::Command;
+use tracing::info;
+
+/// Abort whatever operation is in progress in the current repository
+///
+/// Asks for confirmation first unless `yes` is set, since resolutions made so
+/// far are discarded.
+pub fn execute(yes: bool) -> Result<()> {
+    let git_ops = GitOperations::open(".")?;
+    let operation = if let Some(operation) = git_ops.operation_in_progress()? {
+        operation
+    } else {
+        interactive::print_info("No merge, rebase or cherry-pick in progress");
+        return Ok(());
+    };
+
+    println!("\n{} {operation}", Status::Warning.icon());
+    if !operation.conflicted_files.is_empty() {
+        println!(
+            "  {} file(s) with conflicts; any resolutions so far will be discarded:",
+            operation.conflicted_files.len()
+        );
+        for file in &operation.conflicted_files {
+            println!("    {} {file}", Status::Error.icon());
+        }
+    }
+
+    if !yes && !interactive::confirm(&format!("Abort the {}?", operation.kind.as_str()))? {
+        println!("Cancelled.");
+        return Ok(());
+    }
+
+    abort_operation(git_ops.workdir()?, &operation)?;
+    interactive::print_success(&format!("Aborted {operation}"));
+
+    Ok(())
+}
+
+/// Run the git command that abandons `operation` in the repository at `workdir`
+///
+/// Fails if git reports an error or the repository is still mid-operation
+/// afterwards.
+pub fn abort_operation(workdir: &Path, operation: &OperationInProgress) -> Result<()> {
+    let args = operation.kind.abort_args();
+    info!("Running git {}", args.join(" "));
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(workdir)
+        .args(args)
+        .output()
+        .map_err(|e| MultiGitError::other(format!("Failed to execute git: {e}")))?;
+
+    if !output.status.success() {
+        return Err(MultiGitError::other(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    if let Some(still) = GitOperations::open(workdir)?.operation_in_progress()? {
+        return Err(MultiGitError::other(format!(
+            "Repository still reports {still} after git {}",
+            args.join(" ")
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::repo_state::OperationKind;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) -> bool {
+        Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status
+            .success()
+    }
+
+    #[test]
+    fn test_abort_conflicted_merge() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path();
+        assert!(git(path, &["init", "-q", "-b", "main"]));
+        std::fs::write(path.join("file.txt"), "base\n").unwrap();
+        assert!(git(path, &["add", "."]));
+        assert!(git(path, &["commit", "-q", "-m", "base"]));
+        assert!(git(path, &["checkout", "-q", "-b", "feature"]));
+        std::fs::write(path.join("file.txt"), "feature\n").unwrap();
+        assert!(git(path, &["commit", "-q", "-am", "feature"]));
+        assert!(git(path, &["checkout", "-q", "main"]));
+        std::fs::write(path.join("file.txt"), "main\n").unwrap();
+        assert!(git(path, &["commit", "-q", "-am", "main"]));
+        // Conflicts, so git exits non-zero
+        assert!(!git(path, &["merge", "feature"]));
+
+        let operation = GitOperations::open(path)
+            .unwrap()
+            .operation_in_progress()
+            .unwrap()
+            .unwrap();
+        assert_eq!(operation.kind, OperationKind::Merge);
+        assert_eq!(operation.conflicted_files, vec!["file.txt".to_string()]);
+
+        abort_operation(path, &operation).unwrap();
+
+        assert!(GitOperations::open(path)
+            .unwrap()
+            .operation_in_progress()
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            std::fs::read_to_string(path.join("file.txt")).unwrap(),
+            "main\n"
+        );
+    }
+}
@@ -1,15 +1,18 @@
 //! Interactive staging command
 //!
-//! Visual file selection for staging, similar to `git add -p` but easier
+//! Visual file selection for staging, and hunk-by-hunk staging like
+//! `git add -p` without needing the `git` binary
 
 use crate::cli::interactive;
 use crate::git::operations::GitOperations;
+use crate::git::patch::{self, Hunk, HunkLine, Selection};
+use crate::ui::formatter::{colors, Status};
 use crate::utils::error::{MultiGitError, Result};
 use dialoguer::{theme::ColorfulTheme, Confirm, MultiSelect, Select};
 use std::process::Command;
 
-/// Execute interactive add
-pub fn execute() -> Result<()> {
+/// Execute interactive add; with `patch`, go straight to hunk staging
+pub fn execute(patch: bool) -> Result<()> {
     println!("\n📦 Interactive Staging\n");
 
     // Check if we're in a git repository
@@ -17,6 +20,11 @@ pub fn execute() -> Result<()> {
         MultiGitError::other("Not in a git repository. Run this command from a git repository.")
     })?;
 
+    if patch {
+        stage_hunks(&git_ops)?;
+        return show_staged_files();
+    }
+
     // Get modified files
     let modified_files = get_modified_files(&git_ops)?;
 
@@ -32,6 +40,7 @@ pub fn execute() -> Result<()> {
         "📝 Stage all modified (exclude new files)",
         "✨ Stage all new files",
         "🔍 View diff before staging",
+        "🧩 Stage hunks (like git add -p)",
     ];
 
     let choice = Select::with_theme(&ColorfulTheme::default())
@@ -44,7 +53,7 @@ pub fn execute() -> Result<()> {
         0 => {
             // Stage all files
             stage_all_files(&modified_files)?;
-            interactive::print_success("✅ All files staged!");
+            interactive::print_success("All files staged!");
         }
         1 => {
             // Select files
@@ -53,7 +62,11 @@ pub fn execute() -> Result<()> {
                 interactive::print_info("No files selected.");
             } else {
                 stage_files(&selected)?;
-                interactive::print_success(&format!("✅ {} file(s) staged!", selected.len()));
+                interactive::print_success(&format!(
+                    "{} {} file(s) staged!",
+                    Status::Success.icon(),
+                    selected.len()
+                ));
             }
         }
         2 => {
@@ -69,7 +82,8 @@ pub fn execute() -> Result<()> {
             } else {
                 stage_files(&modified)?;
                 interactive::print_success(&format!(
-                    "✅ {} modified file(s) staged!",
+                    "{} {} modified file(s) staged!",
+                    Status::Success.icon(),
                     modified.len()
                 ));
             }
@@ -86,13 +100,20 @@ pub fn execute() -> Result<()> {
                 interactive::print_info("No new files found.");
             } else {
                 stage_files(&new_files)?;
-                interactive::print_success(&format!("✅ {} new file(s) staged!", new_files.len()));
+                interactive::print_success(&format!(
+                    "{} {} new file(s) staged!",
+                    Status::Success.icon(),
+                    new_files.len()
+                ));
             }
         }
         4 => {
             // View diff and stage
             view_diff_and_stage(&modified_files)?;
         }
+        5 => {
+            stage_hunks(&git_ops)?;
+        }
         _ => {}
     }
 
@@ -225,7 +246,7 @@ fn view_diff_and_stage(files: &[(String, String)]) -> Result<()> {
 
         if stage {
             stage_files(std::slice::from_ref(file))?;
-            println!("✅ Staged\n");
+            println!("{} Staged\n", Status::Success.icon());
         } else {
             println!("⏭️  Skipped\n");
         }
@@ -234,6 +255,134 @@ fn view_diff_and_stage(files: &[(String, String)]) -> Result<()> {
     Ok(())
 }
 
+/// What to do after reviewing a hunk
+enum Review {
+    Next,
+    Quit,
+}
+
+/// Offer every unstaged hunk of every modified file, then stage the accepted ones
+fn stage_hunks(git_ops: &GitOperations) -> Result<()> {
+    let repo = git_ops.inner();
+    let files = patch::unstaged_changes(repo)?;
+    if files.is_empty() {
+        interactive::print_info(
+            "No unstaged changes in modified files. New and deleted files are staged whole.",
+        );
+        return Ok(());
+    }
+
+    let total: usize = files.iter().map(|file| file.hunks.len()).sum();
+    let mut selections: Vec<Selection> = files
+        .iter()
+        .map(|file| {
+            file.hunks
+                .iter()
+                .map(|hunk| vec![false; hunk.pieces().len()])
+                .collect()
+        })
+        .collect();
+
+    let mut number = 0;
+    'files: for (file, selection) in files.iter().zip(&mut selections) {
+        for (hunk, selected) in file.hunks.iter().zip(selection.iter_mut()) {
+            number += 1;
+            println!("\n── {} (hunk {number}/{total}) ──", file.path);
+            if let Review::Quit = review_hunk(hunk, selected)? {
+                break 'files;
+            }
+        }
+    }
+
+    let mut staged = 0;
+    for (file, selection) in files.iter().zip(&selections) {
+        if patch::stage(repo, file, selection)? {
+            staged += 1;
+        }
+    }
+    if staged == 0 {
+        interactive::print_info("No hunks staged.");
+    } else {
+        let hunks = count_selected(&selections);
+        interactive::print_success(&format!(
+            "{} {hunks} change(s) in {staged} file(s) staged!",
+            Status::Success.icon()
+        ));
+    }
+    Ok(())
+}
+
+/// Ask whether to stage `hunk`, recording the answer in `selected` (one entry
+/// per piece)
+fn review_hunk(hunk: &Hunk, selected: &mut [bool]) -> Result<Review> {
+    println!(
+        "{}",
+        colors::colorize(&hunk.header, colors::CYAN, colors::enabled())
+    );
+    print_lines(&hunk.lines);
+
+    let pieces = hunk.pieces();
+    let mut options = vec!["Stage this hunk".to_string(), "Skip this hunk".to_string()];
+    let can_split = pieces.len() > 1;
+    if can_split {
+        options.push(format!("Split into {} smaller hunks", pieces.len()));
+    }
+    options.push("Quit (stage what was chosen so far)".to_string());
+
+    let choice = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Stage this hunk?")
+        .items(&options)
+        .default(0)
+        .interact()?;
+    match choice {
+        0 => selected.fill(true),
+        1 => {}
+        2 if can_split => {
+            for (index, piece) in pieces.iter().enumerate() {
+                println!("\n  piece {}/{}", index + 1, pieces.len());
+                let from = piece.start.saturating_sub(3);
+                let to = (piece.end + 3).min(hunk.lines.len());
+                print_lines(&hunk.lines[from..to]);
+
+                let choice = Select::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Stage this piece?")
+                    .items(&["Stage", "Skip", "Quit (stage what was chosen so far)"])
+                    .default(0)
+                    .interact()?;
+                match choice {
+                    0 => selected[index] = true,
+                    1 => {}
+                    _ => return Ok(Review::Quit),
+                }
+            }
+        }
+        _ => return Ok(Review::Quit),
+    }
+    Ok(Review::Next)
+}
+
+/// Print diff lines, additions green and removals red
+fn print_lines(lines: &[HunkLine]) {
+    let enabled = colors::enabled();
+    for line in lines {
+        let text = format!("{}{}", line.origin, line.content.trim_end_matches('\n'));
+        match line.origin {
+            '+' => println!("{}", colors::colorize(&text, colors::GREEN, enabled)),
+            '-' => println!("{}", colors::colorize(&text, colors::RED, enabled)),
+            _ => println!("{text}"),
+        }
+    }
+}
+
+/// Number of selected hunks and pieces
+fn count_selected(selections: &[Selection]) -> usize {
+    selections
+        .iter()
+        .flatten()
+        .map(|selected| selected.iter().filter(|&&s| s).count())
+        .sum()
+}
+
 /// Show what's currently staged
 fn show_staged_files() -> Result<()> {
     let output = Command::new("git")
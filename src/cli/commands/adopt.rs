@@ -0,0 +1,282 @@
+//! Adopt command implementation
+//!
+//! Bring an existing git clone under `MultiGit` management: every git remote on
+//! a known hosting service becomes a configured `MultiGit` remote, written to
+//! the repository's `.multigit/config.toml`.
+
+use crate::api::client::ClientOptions;
+use crate::cli::interactive;
+use crate::core::auth::{AuthBackend, AuthManager};
+use crate::core::config::{Config, RemoteConfig};
+use crate::git::operations::GitOperations;
+use crate::git::remote::RemoteManager;
+use crate::providers::factory::{create_provider_with_options, get_provider_host};
+use crate::utils::error::{MultiGitError, Result};
+use crate::utils::git_url;
+use std::collections::HashMap;
+use tracing::{debug, info};
+
+/// A git remote that can become a `MultiGit` remote
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdoptCandidate {
+    /// Git remote name, reused as the `MultiGit` remote name
+    pub remote: String,
+    /// Provider inferred from the host
+    pub provider: String,
+    /// Owner (user or organisation) from the URL path
+    pub owner: String,
+    /// Whether the remote uses SSH
+    pub use_ssh: bool,
+}
+
+/// A git remote that was left alone, and why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedRemote {
+    /// Git remote name
+    pub remote: String,
+    /// Reason it cannot be adopted automatically
+    pub reason: String,
+}
+
+/// Work out which `(name, url)` git remotes can be adopted
+#[must_use]
+pub fn plan(remotes: &[(String, String)]) -> (Vec<AdoptCandidate>, Vec<SkippedRemote>) {
+    let mut candidates = Vec::new();
+    let mut skipped = Vec::new();
+
+    for (remote, url) in remotes {
+        match infer_remote(url) {
+            Ok((provider, owner, use_ssh)) => candidates.push(AdoptCandidate {
+                remote: remote.clone(),
+                provider,
+                owner,
+                use_ssh,
+            }),
+            Err(reason) => skipped.push(SkippedRemote {
+                remote: remote.clone(),
+                reason,
+            }),
+        }
+    }
+
+    (candidates, skipped)
+}
+
+/// Provider, owner and SSH flag for a remote URL
+fn infer_remote(url: &str) -> std::result::Result<(String, String, bool), String> {
+    let parsed = git_url::parse(url).map_err(|e| e.to_string())?;
+    let provider = parsed.provider().ok_or_else(|| {
+        format!(
+            "unknown host {host}; add it with 'multigit remote add <provider> <user> --url https://{host}'",
+            host = parsed.host
+        )
+    })?;
+
+    Ok((provider.to_string(), parsed.owner.clone(), parsed.is_ssh()))
+}
+
+/// Adopt the git remotes of the repository in the current directory
+///
+/// Prompts for usernames and tokens in interactive mode; otherwise uses the
+/// URL owner as username and `MULTIGIT_<PROVIDER>_TOKEN` when set.
+pub async fn execute(interactive_mode: bool) -> Result<()> {
+    info!("Adopting git remotes");
+
+    let git_ops = GitOperations::open(".").map_err(|_| {
+        MultiGitError::other("Not a git repository. Run 'multigit adopt' inside a git clone.")
+    })?;
+    let remote_manager = RemoteManager::new(git_ops.inner());
+    let mut remotes = Vec::new();
+    for name in remote_manager.list()? {
+        let url = remote_manager.get_url(&name)?;
+        remotes.push((name, url));
+    }
+
+    if remotes.is_empty() {
+        interactive::print_info("No git remotes to adopt. Add one with 'git remote add'.");
+        return Ok(());
+    }
+
+    let (candidates, skipped) = plan(&remotes);
+    for skip in &skipped {
+        interactive::print_warning(&format!("Skipping '{}': {}", skip.remote, skip.reason));
+    }
+
+    let path = Config::repo_config_path();
+    let mut repo_config = if path.exists() {
+        Config::load_from_file(&path)?
+    } else {
+        Config::default()
+    };
+    let settings = Config::load().unwrap_or_default();
+
+    // Remotes on the same account share a token
+    let mut stored: HashMap<(String, String), bool> = HashMap::new();
+    let mut adopted = Vec::new();
+
+    for candidate in candidates {
+        println!(
+            "\n• {} → {} ({})",
+            candidate.remote, candidate.provider, candidate.owner
+        );
+
+        if repo_config.remotes.contains_key(&candidate.remote)
+            && !(interactive_mode
+                && interactive::confirm(&format!(
+                    "Remote '{}' is already configured. Overwrite?",
+                    candidate.remote
+                ))?)
+        {
+            interactive::print_info("Kept existing configuration");
+            continue;
+        }
+
+        let username = if interactive_mode {
+            interactive::prompt_text(
+                &format!("Username on {}", candidate.provider),
+                Some(&candidate.owner),
+            )?
+        } else {
+            candidate.owner.clone()
+        };
+
+        let key = (candidate.provider.clone(), username.clone());
+        if !stored.contains_key(&key) {
+            let ok =
+                store_token(&candidate.provider, &username, interactive_mode, &settings).await?;
+            stored.insert(key.clone(), ok);
+        }
+        if !stored[&key] {
+            interactive::print_warning(&format!(
+                "No credentials stored; run 'multigit remote update {}' to add a token",
+                candidate.remote
+            ));
+        }
+
+        repo_config.remotes.insert(
+            candidate.remote.clone(),
+            RemoteConfig {
+                username,
+                provider: Some(candidate.provider.clone()),
+                use_ssh: candidate.use_ssh,
+                ..Default::default()
+            },
+        );
+        adopted.push(candidate.remote);
+    }
+
+    if adopted.is_empty() {
+        interactive::print_info("Nothing adopted");
+        return Ok(());
+    }
+
+    repo_config.save_repo_config()?;
+    interactive::print_success(&format!(
+        "Adopted {} remote(s): {}",
+        adopted.len(),
+        adopted.join(", ")
+    ));
+    println!("Configuration written to {}", path.display());
+    println!("Check them with: multigit remote test");
+
+    Ok(())
+}
+
+/// Ask for (or read) a token, verify it and store it in the keyring
+///
+/// Returns `false` when no token was given or it did not authenticate.
+async fn store_token(
+    provider: &str,
+    username: &str,
+    interactive_mode: bool,
+    settings: &Config,
+) -> Result<bool> {
+    let token = if interactive_mode {
+        interactive::prompt_token(provider)?
+    } else {
+        let env_var = format!("MULTIGIT_{}_TOKEN", provider.to_uppercase());
+        if let Ok(token) = std::env::var(&env_var) {
+            token
+        } else {
+            debug!("{} not set", env_var);
+            return Ok(false);
+        }
+    };
+    if token.is_empty() {
+        return Ok(false);
+    }
+
+    let allow_insecure = settings.security.allow_insecure_http;
+    let client = create_provider_with_options(
+        provider,
+        username,
+        &token,
+        None,
+        allow_insecure,
+        &ClientOptions::from_settings(&settings.settings),
+    )?;
+    match client.test_connection().await {
+        Ok(true) => {}
+        Ok(false) => {
+            interactive::print_warning(&format!("Token for {username} on {provider} was rejected"));
+            return Ok(false);
+        }
+        Err(e) => {
+            interactive::print_warning(&format!("Could not verify token on {provider}: {e}"));
+            return Ok(false);
+        }
+    }
+
+    let host = get_provider_host(provider, None, allow_insecure)?;
+    AuthManager::new(AuthBackend::Keyring, settings.security.audit_log)
+        .store_credential(provider, &host, username, &token)?;
+    interactive::print_success(&format!("Credentials stored (bound to host: {host})"));
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote(name: &str, url: &str) -> (String, String) {
+        (name.to_string(), url.to_string())
+    }
+
+    #[test]
+    fn test_plan_infers_provider_and_owner() {
+        let (candidates, skipped) = plan(&[
+            remote("origin", "https://github.com/alice/project.git"),
+            remote("mirror", "git@gitlab.com:team/project.git"),
+            remote("backup", "ssh://git@codeberg.org/bob/project.git"),
+        ]);
+
+        assert!(skipped.is_empty());
+        assert_eq!(
+            candidates[0],
+            AdoptCandidate {
+                remote: "origin".to_string(),
+                provider: "github".to_string(),
+                owner: "alice".to_string(),
+                use_ssh: false,
+            }
+        );
+        assert_eq!(candidates[1].provider, "gitlab");
+        assert_eq!(candidates[1].owner, "team");
+        assert!(candidates[1].use_ssh);
+        assert_eq!(candidates[2].provider, "codeberg");
+        assert_eq!(candidates[2].owner, "bob");
+    }
+
+    #[test]
+    fn test_plan_skips_unknown_hosts() {
+        let (candidates, skipped) = plan(&[
+            remote("internal", "https://git.example.com/team/project.git"),
+            remote("local", "/srv/git/project.git"),
+        ]);
+
+        assert!(candidates.is_empty());
+        assert_eq!(skipped.len(), 2);
+        assert!(skipped[0].reason.contains("git.example.com"));
+    }
+}
@@ -1,16 +1,36 @@
 //! Quick amend last commit
 //!
-//! Easily amend the last commit with new changes or updated message
+//! Easily amend the last commit with new changes or updated message, and
+//! optionally replace the old commit on the remotes that already have it
 
+use crate::cli::commands::push::{audit_pushes, routed_remotes};
 use crate::cli::interactive;
+use crate::core::config::Config;
+use crate::core::repo_lock::RepoLock;
+use crate::core::sync_manager::SyncManager;
+use crate::ui::formatter::Status;
 use crate::utils::error::{MultiGitError, Result};
 use dialoguer::{theme::ColorfulTheme, Confirm, Editor, Select};
+use git2::Oid;
+use std::collections::HashMap;
 use std::process::Command;
 
 /// Execute amend
-pub fn execute(no_edit: bool) -> Result<()> {
+///
+/// With `push`, each enabled remote whose copy of the branch was at the old
+/// commit is force-pushed the amended one (see [`push_amended`]), with
+/// `concurrency` overriding `settings.max_parallel`.
+pub async fn execute(no_edit: bool, push: bool, concurrency: Option<usize>) -> Result<()> {
     println!("\n✏️  Amend Last Commit\n");
 
+    let before = if push {
+        let manager = SyncManager::new(".")?;
+        manager.ensure_no_operation_in_progress("amend --push")?;
+        Some((manager.current_branch()?, manager.head_commit_id()?))
+    } else {
+        None
+    };
+
     // Show last commit
     let output = Command::new("git")
         .args(["log", "-1", "--pretty=format:%h - %s"])
@@ -41,9 +61,151 @@ pub fn execute(no_edit: bool) -> Result<()> {
         _ => {}
     }
 
+    if let Some((branch, old)) = before {
+        let new = SyncManager::new(".")?.head_commit_id()?;
+        if new == old {
+            println!("\nThe commit is unchanged; nothing to push.");
+        } else {
+            push_amended(&branch, old, new, concurrency).await?;
+        }
+    }
+
     Ok(())
 }
 
+/// What `amend --push` does with one remote
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Plan {
+    /// The remote is at the old commit: replace it
+    Replace,
+    /// The remote already has the amended commit
+    UpToDate,
+    /// The remote doesn't have the branch
+    Missing,
+    /// The remote is somewhere else; overwriting it would lose commits
+    Diverged,
+}
+
+fn plan(tip: Option<Oid>, old: Oid, new: Oid) -> Plan {
+    match tip {
+        None => Plan::Missing,
+        Some(tip) if tip == old => Plan::Replace,
+        Some(tip) if tip == new => Plan::UpToDate,
+        Some(_) => Plan::Diverged,
+    }
+}
+
+/// Replace `old` with `new` on every enabled remote whose `branch` was at `old`
+///
+/// Each remote is fetched first so the decision uses its current tip, and
+/// the force push carries a lease on `old`: a remote that moves in between is
+/// rejected rather than overwritten. Remotes that diverged are skipped.
+async fn push_amended(branch: &str, old: Oid, new: Oid, concurrency: Option<usize>) -> Result<()> {
+    let config = Config::load().unwrap_or_default();
+    let mut remotes: Vec<String> = config.enabled_remotes().keys().cloned().collect();
+    if remotes.is_empty() {
+        interactive::print_info(
+            "No MultiGit remotes configured; push with 'git push --force-with-lease'",
+        );
+        return Ok(());
+    }
+    remotes.sort();
+    let remotes = routed_remotes(&config, branch, remotes)?;
+    // Keep a daemon sync from moving the remote-tracking refs underneath us
+    let _lock = RepoLock::acquire(".", "amend")?;
+
+    let manager = SyncManager::new(".")?
+        .with_max_parallel(concurrency.unwrap_or(config.settings.max_parallel))
+        .with_proxy(config.settings.proxy_url.clone())
+        .with_tls(config.remote_tls())
+        .with_retries(config.settings.network_attempts);
+
+    println!(
+        "\n📡 Fetching {} remote(s) to check their copy of '{branch}'...",
+        remotes.len()
+    );
+    let mut expected = HashMap::new();
+    let mut skipped = 0;
+    for fetch in manager.fetch_all(&remotes).await? {
+        let remote = fetch.remote;
+        if !fetch.success {
+            println!(
+                "{} {remote} - fetch failed, skipping: {}",
+                Status::Error.icon(),
+                fetch.message
+            );
+            skipped += 1;
+            continue;
+        }
+        match plan(manager.remote_branch_tip(&remote, branch)?, old, new) {
+            Plan::Replace => {
+                expected.insert(remote, old);
+            }
+            Plan::UpToDate => println!(
+                "{} {remote} - already has the amended commit",
+                Status::Success.icon()
+            ),
+            Plan::Missing => println!(
+                "{} {remote} - has no '{branch}' branch, skipping",
+                Status::Skipped.icon()
+            ),
+            Plan::Diverged => {
+                println!(
+                    "{} {remote} - '{branch}' has moved past the old commit, skipping \
+                     (pull and reconcile it first)",
+                    Status::Skipped.icon()
+                );
+                skipped += 1;
+            }
+        }
+    }
+
+    if expected.is_empty() {
+        println!("\nNo remote had the old commit at its tip; nothing pushed.");
+    } else {
+        println!(
+            "\n🚀 Replacing {} with {} on {} remote(s)...\n",
+            short(old),
+            short(new),
+            expected.len()
+        );
+        let results = manager.force_push_with_lease_all(branch, &expected).await?;
+        let forced: Vec<String> = expected.into_keys().collect();
+        audit_pushes(&config, branch, &forced, &results);
+        for result in &results {
+            if result.success {
+                println!("{} {} - replaced", Status::Success.icon(), result.remote);
+            } else if result.rejected {
+                println!(
+                    "{} {} - '{branch}' moved since it was fetched; left unchanged",
+                    Status::Error.icon(),
+                    result.remote
+                );
+                skipped += 1;
+            } else {
+                println!(
+                    "{} {} - {}",
+                    Status::Error.icon(),
+                    result.remote,
+                    result.message
+                );
+                skipped += 1;
+            }
+        }
+    }
+
+    if skipped > 0 {
+        return Err(MultiGitError::other(format!(
+            "{skipped} remote(s) were not updated with the amended commit"
+        )));
+    }
+    Ok(())
+}
+
+fn short(oid: Oid) -> String {
+    oid.to_string()[..7].to_string()
+}
+
 /// Amend without editing message
 fn amend_no_edit() -> Result<()> {
     let output = Command::new("git")
@@ -52,7 +214,7 @@ fn amend_no_edit() -> Result<()> {
         .map_err(|e| MultiGitError::other(format!("Failed to amend: {e}")))?;
 
     if output.status.success() {
-        interactive::print_success("✅ Commit amended!");
+        interactive::print_success("Commit amended!");
     } else {
         let error = String::from_utf8_lossy(&output.stderr);
         return Err(MultiGitError::other(format!("Failed to amend: {error}")));
@@ -78,7 +240,7 @@ fn amend_with_edit() -> Result<()> {
             .map_err(|e| MultiGitError::other(format!("Failed to amend: {e}")))?;
 
         if output.status.success() {
-            interactive::print_success("✅ Commit amended with new message!");
+            interactive::print_success("Commit amended with new message!");
         } else {
             let error = String::from_utf8_lossy(&output.stderr);
             return Err(MultiGitError::other(format!("Failed to amend: {error}")));
@@ -119,7 +281,7 @@ fn amend_all() -> Result<()> {
         .map_err(|e| MultiGitError::other(format!("Failed to amend: {e}")))?;
 
     if output.status.success() {
-        interactive::print_success("✅ All changes staged and commit amended!");
+        interactive::print_success("All changes staged and commit amended!");
     } else {
         let error = String::from_utf8_lossy(&output.stderr);
         return Err(MultiGitError::other(format!("Failed to amend: {error}")));
@@ -142,7 +304,7 @@ fn amend_author() -> Result<()> {
         .map_err(|e| MultiGitError::other(format!("Failed to amend: {e}")))?;
 
     if output.status.success() {
-        interactive::print_success("✅ Author updated!");
+        interactive::print_success("Author updated!");
     } else {
         let error = String::from_utf8_lossy(&output.stderr);
         return Err(MultiGitError::other(format!("Failed to amend: {error}")));
@@ -150,3 +312,20 @@ fn amend_author() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan() {
+        let old = Oid::from_str("1111111111111111111111111111111111111111").unwrap();
+        let new = Oid::from_str("2222222222222222222222222222222222222222").unwrap();
+        let other = Oid::from_str("3333333333333333333333333333333333333333").unwrap();
+
+        assert_eq!(plan(Some(old), old, new), Plan::Replace);
+        assert_eq!(plan(Some(new), old, new), Plan::UpToDate);
+        assert_eq!(plan(None, old, new), Plan::Missing);
+        assert_eq!(plan(Some(other), old, new), Plan::Diverged);
+    }
+}
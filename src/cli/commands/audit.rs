@@ -0,0 +1,69 @@
+//! Audit command implementation
+//!
+//! Query the audit log written when `security.audit_log` is on: credential
+//! changes, pushes, pulls, syncs and repository creation or deletion.
+
+use crate::core::config::Config;
+use crate::security::audit::{AuditLogger, AuditQuery};
+use crate::ui::formatter::{colors, Status, Table};
+use crate::utils::error::{MultiGitError, Result};
+
+/// Print the audit entries matching `query`, oldest first
+pub fn execute(query: &AuditQuery, json: bool) -> Result<()> {
+    let logger = AuditLogger::at_default_path(true);
+    let entries = logger
+        .query(query)
+        .map_err(|e| MultiGitError::other(format!("Failed to read the audit log: {e}")))?;
+
+    if json {
+        let output = serde_json::to_string_pretty(&entries)
+            .map_err(|e| MultiGitError::other(format!("Failed to serialize entries: {e}")))?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!(
+            "No matching audit entries in {}",
+            AuditLogger::default_path().display()
+        );
+        if !Config::load().unwrap_or_default().security.audit_log {
+            println!("Audit logging is off; enable it with security.audit_log = true");
+        }
+        return Ok(());
+    }
+
+    let mut table = Table::new(
+        ["", "Time", "Event", "Resource", "User", "Details"]
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+    );
+    for entry in &entries {
+        table.add_row(vec![
+            if entry.success {
+                Status::Success
+            } else {
+                Status::Error
+            }
+            .icon()
+            .to_string(),
+            entry
+                .timestamp
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            entry.event_type.to_string(),
+            entry.resource.clone(),
+            entry.user.clone().unwrap_or_default(),
+            entry.message.clone().unwrap_or_default(),
+        ]);
+    }
+    if colors::enabled() {
+        table.print();
+    } else {
+        table.no_colors().print();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,628 @@
+//! Auth command implementation
+//!
+//! Rotate the tokens of the configured remotes, and move stored credentials
+//! between backends. Remotes that share an account (same provider, host and
+//! user) and hold the same stored token are asked for a new token once,
+//! which is then stored under each remote's name. A token only replaces the
+//! stored ones after it authenticates against every remote of its account.
+
+use crate::cli::commands::remote::{
+    check_token_expiry, record_token_expiry, token_from_env, verify_token,
+};
+use crate::cli::interactive;
+use crate::core::auth::{remote_token_env_var, AuthBackend, AuthManager, CredentialKey};
+use crate::core::config::Config;
+use crate::providers::factory::get_provider_host;
+use crate::ui::formatter::{colors, Status, Table};
+use crate::utils::error::{MultiGitError, Result};
+use std::fmt;
+
+/// Environment variable holding the encrypted store's passphrase
+pub const PASSPHRASE_ENV_VAR: &str = "MULTIGIT_STORE_PASSPHRASE";
+
+/// The remotes that authenticate with one stored credential
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Account {
+    key: CredentialKey,
+    remotes: Vec<String>,
+}
+
+/// What happened to one account's token
+#[derive(Debug)]
+enum Outcome {
+    Rotated,
+    Skipped(String),
+    Failed(String),
+}
+
+/// Replace the token of every remote (or just `remote`) with a new, verified one
+pub async fn rotate(remote: Option<String>, interactive_mode: bool) -> Result<()> {
+    let config = Config::load()?;
+    let auth_manager = AuthManager::new(AuthBackend::Keyring, config.security.audit_log);
+    let accounts = split_by_stored_token(accounts(&config, remote.as_deref())?, |name| {
+        auth_manager.retrieve_remote_token(name).ok()
+    });
+    if accounts.is_empty() {
+        interactive::print_info("No remotes configured");
+        return Ok(());
+    }
+
+    let mut outcomes = Vec::with_capacity(accounts.len());
+    for account in &accounts {
+        let outcome = rotate_account(&config, account, interactive_mode).await;
+        outcomes.push(outcome);
+    }
+
+    report(&accounts, &outcomes);
+
+    let failed = outcomes
+        .iter()
+        .filter(|outcome| matches!(outcome, Outcome::Failed(_)))
+        .count();
+    if failed > 0 {
+        return Err(MultiGitError::auth(
+            "rotate",
+            format!("{failed} new token(s) were rejected; the old credentials were kept"),
+        ));
+    }
+    Ok(())
+}
+
+/// Group the configured remotes (or just `only`) by the credential they use
+fn accounts(config: &Config, only: Option<&str>) -> Result<Vec<Account>> {
+    let only = only.map(str::to_lowercase);
+    if let Some(name) = &only {
+        if !config.remotes.contains_key(name) {
+            return Err(MultiGitError::other(format!("Remote '{name}' not found")));
+        }
+    }
+
+    let mut accounts: Vec<Account> = Vec::new();
+    for (name, remote_config) in &config.remotes {
+        if only.as_ref().is_some_and(|only| only != name) {
+            continue;
+        }
+        let provider = remote_config
+            .provider
+            .clone()
+            .unwrap_or_else(|| name.clone())
+            .to_lowercase();
+        let host = get_provider_host(
+            &provider,
+            remote_config.api_url.as_deref(),
+            config.security.allow_insecure_http,
+        )?;
+
+        let key = CredentialKey::new(&provider, &host, &remote_config.username);
+        match accounts.iter_mut().find(|account| account.key == key) {
+            Some(account) => account.remotes.push(name.clone()),
+            None => accounts.push(Account {
+                key,
+                remotes: vec![name.clone()],
+            }),
+        }
+    }
+
+    for account in &mut accounts {
+        account.remotes.sort();
+    }
+    accounts.sort_by_key(|account| account.remotes[0].clone());
+    Ok(accounts)
+}
+
+/// Split each account so only remotes holding the same stored token are
+/// rotated together
+///
+/// Remotes of one account can still have tokens of their own (say, one
+/// scoped to each org), which a shared rotation would overwrite with one.
+fn split_by_stored_token(
+    accounts: Vec<Account>,
+    stored: impl Fn(&str) -> Option<String>,
+) -> Vec<Account> {
+    let mut split = Vec::with_capacity(accounts.len());
+    for account in accounts {
+        let mut groups: Vec<(Option<String>, Account)> = Vec::new();
+        for name in account.remotes {
+            let token = stored(&name);
+            match groups
+                .iter_mut()
+                .find(|(group_token, _)| *group_token == token)
+            {
+                Some((_, group)) => group.remotes.push(name),
+                None => groups.push((
+                    token,
+                    Account {
+                        key: account.key.clone(),
+                        remotes: vec![name],
+                    },
+                )),
+            }
+        }
+        split.extend(groups.into_iter().map(|(_, group)| group));
+    }
+    split.sort_by_key(|account| account.remotes[0].clone());
+    split
+}
+
+async fn rotate_account(config: &Config, account: &Account, interactive_mode: bool) -> Outcome {
+    let key = &account.key;
+    let first = &account.remotes[0];
+    let token = if interactive_mode {
+        println!(
+            "\nRotating the {} token of {}@{} (used by {})",
+            key.provider,
+            key.username,
+            key.host,
+            account.remotes.join(", ")
+        );
+        println!("Leave the token empty to skip this account");
+        match interactive::prompt_token(&key.provider) {
+            Ok(token) => token,
+            Err(_) => return Outcome::Skipped("no token entered".to_string()),
+        }
+    } else {
+        match token_from_env(first, &key.provider) {
+            Some(token) => token,
+            None => {
+                return Outcome::Skipped(format!(
+                    "set {} or MULTIGIT_{}_TOKEN",
+                    remote_token_env_var(first),
+                    key.provider.to_uppercase()
+                ))
+            }
+        }
+    };
+
+    // Every remote must accept the token before the shared credential changes
+    let mut providers = Vec::with_capacity(account.remotes.len());
+    for name in &account.remotes {
+        let remote_config = &config.remotes[name];
+        match verify_token(config, name, remote_config, &token).await {
+            Ok(provider) => providers.push((name, provider)),
+            Err(e) => return Outcome::Failed(format!("{name}: {e}")),
+        }
+    }
+
+    let auth_manager = AuthManager::new(AuthBackend::Keyring, config.security.audit_log);
+    for name in &account.remotes {
+        if let Err(e) = auth_manager.store_remote_credential(name, &token) {
+            return Outcome::Failed(format!("could not store the new token for {name}: {e}"));
+        }
+    }
+
+    // The old token's expiry no longer applies
+    let warn_days = config.settings.token_expiry_warning_days;
+    for (name, provider) in providers {
+        let token_info = check_token_expiry(name, provider.as_ref(), warn_days).await;
+        record_token_expiry(name, token_info.and_then(|token| token.expires_at));
+    }
+
+    Outcome::Rotated
+}
+
+fn report(accounts: &[Account], outcomes: &[Outcome]) {
+    println!("\n🔑 Token rotation\n");
+    let mut table = Table::new(
+        ["", "Account", "Remotes", "Result"]
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+    );
+    for (account, outcome) in accounts.iter().zip(outcomes) {
+        let (status, result) = match outcome {
+            Outcome::Rotated => (Status::Success, "rotated".to_string()),
+            Outcome::Skipped(reason) => (Status::Warning, format!("skipped: {reason}")),
+            Outcome::Failed(reason) => (Status::Error, format!("kept old token: {reason}")),
+        };
+        table.add_row(vec![
+            status.icon().to_string(),
+            format!("{}@{}", account.key.username, account.key.host),
+            account.remotes.join(", "),
+            result,
+        ]);
+    }
+    if colors::enabled() {
+        table.print();
+    } else {
+        table.no_colors().print();
+    }
+
+    let rotated = outcomes
+        .iter()
+        .filter(|outcome| matches!(outcome, Outcome::Rotated))
+        .count();
+    println!(
+        "\nRotated {rotated} of {} account(s), skipped {}",
+        outcomes.len(),
+        outcomes.len() - rotated
+    );
+}
+
+/// A stored credential: one shared by an account, or one remote's own
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Stored {
+    Account(CredentialKey),
+    Remote(String),
+}
+
+impl Stored {
+    fn retrieve(&self, manager: &AuthManager) -> Result<String> {
+        match self {
+            Self::Account(key) => {
+                manager.retrieve_credential(&key.provider, &key.host, &key.username, false)
+            }
+            Self::Remote(name) => manager.retrieve_remote_token(name),
+        }
+    }
+
+    fn store(&self, manager: &AuthManager, token: &str) -> Result<()> {
+        match self {
+            Self::Account(key) => {
+                manager.store_credential(&key.provider, &key.host, &key.username, token)
+            }
+            Self::Remote(name) => manager.store_remote_credential(name, token),
+        }
+    }
+
+    fn delete(&self, manager: &AuthManager) -> Result<()> {
+        match self {
+            Self::Account(key) => {
+                manager.delete_credential(&key.provider, &key.host, &key.username)
+            }
+            Self::Remote(name) => manager.delete_remote_credential(name),
+        }
+    }
+}
+
+impl fmt::Display for Stored {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Account(key) => key.fmt(f),
+            Self::Remote(name) => write!(f, "remote:{name}"),
+        }
+    }
+}
+
+/// One credential's trip from the source backend to the destination
+struct Migration {
+    key: Stored,
+    copied: Result<()>,
+    /// Set when the source copy was to be removed after a verified copy
+    purged: Option<Result<()>>,
+}
+
+/// Copy every credential from one backend to another, reading each copy back
+/// before (optionally) removing it from the source
+pub fn migrate(
+    from: AuthBackend,
+    to: AuthBackend,
+    purge: bool,
+    interactive_mode: bool,
+) -> Result<()> {
+    if from == to {
+        return Err(MultiGitError::invalid_input(format!(
+            "Credentials are already in the {from} backend"
+        )));
+    }
+    if from == AuthBackend::Environment || to == AuthBackend::Environment {
+        return Err(MultiGitError::invalid_input(
+            "Environment variables can't be migrated; choose keyring or encrypted",
+        ));
+    }
+
+    let config = Config::load()?;
+    let audit = config.security.audit_log;
+    let source = open_backend(from, false, interactive_mode, audit)?;
+    let destination = open_backend(to, true, interactive_mode, audit)?;
+
+    // The keyring can't be listed, so look up the accounts and remotes
+    // configured
+    let keys: Vec<Stored> = if from == AuthBackend::Keyring {
+        let mut remotes: Vec<&String> = config.remotes.keys().collect();
+        remotes.sort();
+        accounts(&config, None)?
+            .into_iter()
+            .map(|account| account.key)
+            .filter(|key| source.has_credential(&key.provider, &key.host, &key.username))
+            .map(Stored::Account)
+            .chain(
+                remotes
+                    .into_iter()
+                    .filter(|name| source.has_remote_credential(name))
+                    .map(|name| Stored::Remote(name.clone())),
+            )
+            .collect()
+    } else {
+        source
+            .list_credentials()?
+            .into_iter()
+            .map(Stored::Account)
+            .chain(
+                source
+                    .list_remote_credentials()?
+                    .into_iter()
+                    .map(Stored::Remote),
+            )
+            .collect()
+    };
+    if keys.is_empty() {
+        interactive::print_info(&format!("No credentials found in the {from} backend"));
+        return Ok(());
+    }
+
+    let migrations = migrate_credentials(&source, &destination, keys, purge);
+    report_migrations(&migrations, from, to);
+
+    let failed = migrations
+        .iter()
+        .filter(|migration| migration.copied.is_err())
+        .count();
+    if failed > 0 {
+        return Err(MultiGitError::other(format!(
+            "{failed} credential(s) could not be migrated; they are still in the {from} backend"
+        )));
+    }
+    Ok(())
+}
+
+/// An auth manager for `backend`, unlocking the encrypted store with a
+/// passphrase from the environment or a prompt
+fn open_backend(
+    backend: AuthBackend,
+    destination: bool,
+    interactive_mode: bool,
+    audit: bool,
+) -> Result<AuthManager> {
+    let mut manager = AuthManager::new(backend, audit);
+    if backend != AuthBackend::EncryptedFile {
+        return Ok(manager);
+    }
+
+    let path = AuthManager::default_encrypted_store_path();
+    let passphrase = match std::env::var(PASSPHRASE_ENV_VAR) {
+        Ok(passphrase) if !passphrase.is_empty() => passphrase,
+        _ if interactive_mode => {
+            let creating = destination && !path.exists();
+            let prompt = if creating {
+                "Choose a passphrase for the encrypted credential store"
+            } else {
+                "Passphrase for the encrypted credential store"
+            };
+            interactive::prompt_passphrase(prompt, creating)?
+        }
+        _ => {
+            return Err(MultiGitError::invalid_input(format!(
+                "Set {PASSPHRASE_ENV_VAR} to unlock the encrypted credential store"
+            )))
+        }
+    };
+    manager.init_encrypted_store(passphrase, path.clone());
+
+    // Fail on a wrong passphrase before anything is copied
+    manager
+        .list_credentials()
+        .map_err(|e| MultiGitError::other(format!("Could not unlock {}: {e}", path.display())))?;
+    Ok(manager)
+}
+
+fn migrate_credentials(
+    source: &AuthManager,
+    destination: &AuthManager,
+    keys: Vec<Stored>,
+    purge: bool,
+) -> Vec<Migration> {
+    keys.into_iter()
+        .map(|key| {
+            let copied = copy_credential(source, destination, &key);
+            let purged = (purge && copied.is_ok()).then(|| key.delete(source));
+            Migration {
+                key,
+                copied,
+                purged,
+            }
+        })
+        .collect()
+}
+
+fn copy_credential(source: &AuthManager, destination: &AuthManager, key: &Stored) -> Result<()> {
+    let token = key.retrieve(source)?;
+    key.store(destination, &token)?;
+
+    match key.retrieve(destination) {
+        Ok(stored) if stored == token => Ok(()),
+        Ok(_) => Err(MultiGitError::other("read-back returned a different token")),
+        Err(e) => Err(MultiGitError::other(format!("read-back failed: {e}"))),
+    }
+}
+
+fn report_migrations(migrations: &[Migration], from: AuthBackend, to: AuthBackend) {
+    println!("\n🔑 Credential migration: {from} → {to}\n");
+    let mut table = Table::new(
+        ["", "Credential", "Result"]
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+    );
+    for migration in migrations {
+        let (status, result) = match (&migration.copied, &migration.purged) {
+            (Err(e), _) => (Status::Error, format!("not migrated: {e}")),
+            (Ok(()), None) => (Status::Success, "migrated".to_string()),
+            (Ok(()), Some(Ok(()))) => (Status::Success, format!("migrated, removed from {from}")),
+            (Ok(()), Some(Err(e))) => (
+                Status::Warning,
+                format!("migrated, but could not remove from {from}: {e}"),
+            ),
+        };
+        table.add_row(vec![
+            status.icon().to_string(),
+            migration.key.to_string(),
+            result,
+        ]);
+    }
+    if colors::enabled() {
+        table.print();
+    } else {
+        table.no_colors().print();
+    }
+
+    let migrated = migrations
+        .iter()
+        .filter(|migration| migration.copied.is_ok())
+        .count();
+    println!(
+        "\nMigrated {migrated} of {} credential(s) to the {to} backend",
+        migrations.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::RemoteConfig;
+
+    fn remote(provider: &str, username: &str, api_url: Option<&str>) -> RemoteConfig {
+        RemoteConfig {
+            username: username.to_string(),
+            api_url: api_url.map(str::to_string),
+            provider: Some(provider.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn config() -> Config {
+        let mut config = Config::default();
+        config
+            .remotes
+            .insert("github".to_string(), remote("github", "alice", None));
+        config
+            .remotes
+            .insert("mirror".to_string(), remote("github", "alice", None));
+        config
+            .remotes
+            .insert("work".to_string(), remote("github", "alice-corp", None));
+        config.remotes.insert(
+            "gitea".to_string(),
+            remote("gitea", "alice", Some("https://git.example.com")),
+        );
+        config
+    }
+
+    #[test]
+    fn test_accounts_share_one_credential() {
+        let accounts = accounts(&config(), None).unwrap();
+
+        let remotes: Vec<Vec<String>> = accounts.iter().map(|a| a.remotes.clone()).collect();
+        assert_eq!(
+            remotes,
+            vec![
+                vec!["gitea".to_string()],
+                vec!["github".to_string(), "mirror".to_string()],
+                vec!["work".to_string()],
+            ]
+        );
+        assert_eq!(accounts[1].key.host, "github.com");
+    }
+
+    #[test]
+    fn test_accounts_for_one_remote() {
+        let accounts = accounts(&config(), Some("Mirror")).unwrap();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].remotes, vec!["mirror".to_string()]);
+
+        assert!(super::accounts(&config(), Some("missing")).is_err());
+    }
+
+    #[test]
+    fn test_split_by_stored_token() {
+        let mut config = config();
+        config
+            .remotes
+            .insert("backup".to_string(), remote("github", "alice", None));
+
+        // "mirror" has a token of its own, "backup" shares the one of "github"
+        let accounts = split_by_stored_token(accounts(&config, None).unwrap(), |name| match name {
+            "mirror" => Some("mirror-token".to_string()),
+            "gitea" => None,
+            _ => Some("shared-token".to_string()),
+        });
+
+        let remotes: Vec<Vec<String>> = accounts.iter().map(|a| a.remotes.clone()).collect();
+        assert_eq!(
+            remotes,
+            vec![
+                vec!["backup".to_string(), "github".to_string()],
+                vec!["gitea".to_string()],
+                vec!["mirror".to_string()],
+                vec!["work".to_string()],
+            ]
+        );
+        assert_eq!(accounts[0].key, accounts[2].key);
+    }
+
+    fn encrypted_store(path: &std::path::Path) -> AuthManager {
+        let mut manager = AuthManager::new(AuthBackend::EncryptedFile, false);
+        manager.init_encrypted_store("correct horse".to_string(), path.to_path_buf());
+        manager
+    }
+
+    #[test]
+    fn test_migrate_credentials_verifies_and_purges() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = encrypted_store(&temp_dir.path().join("old.enc"));
+        let destination = encrypted_store(&temp_dir.path().join("new.enc"));
+        source
+            .store_credential("github", "github.com", "alice", "gh-token")
+            .unwrap();
+        source
+            .store_credential("gitea", "git.example.com", "alice", "gitea-token")
+            .unwrap();
+        source
+            .store_remote_credential("work", "work-token")
+            .unwrap();
+
+        let accounts = source.list_credentials().unwrap();
+        let keys: Vec<Stored> = accounts
+            .iter()
+            .cloned()
+            .map(Stored::Account)
+            .chain([Stored::Remote("work".to_string())])
+            .collect();
+        let migrations = migrate_credentials(&source, &destination, keys, true);
+
+        assert_eq!(migrations.len(), 3);
+        assert!(migrations
+            .iter()
+            .all(|m| m.copied.is_ok() && matches!(m.purged, Some(Ok(())))));
+        assert_eq!(destination.list_credentials().unwrap(), accounts);
+        assert_eq!(
+            destination
+                .retrieve_credential("github", "github.com", "alice", false)
+                .unwrap(),
+            "gh-token"
+        );
+        assert_eq!(
+            destination.retrieve_remote_token("work").unwrap(),
+            "work-token"
+        );
+        assert!(source.list_credentials().unwrap().is_empty());
+        assert!(source.list_remote_credentials().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_migrate_credentials_keeps_source_on_failure() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = encrypted_store(&temp_dir.path().join("old.enc"));
+        let destination = AuthManager::new(AuthBackend::Environment, false);
+        source
+            .store_credential("github", "github.com", "alice", "gh-token")
+            .unwrap();
+
+        let accounts = source.list_credentials().unwrap();
+        let keys = accounts.iter().cloned().map(Stored::Account).collect();
+        let migrations = migrate_credentials(&source, &destination, keys, true);
+
+        assert!(migrations[0].copied.is_err());
+        assert!(migrations[0].purged.is_none());
+        assert_eq!(source.list_credentials().unwrap(), accounts);
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Configure and manage repository backups to multiple remotes
 
+use crate::ui::formatter::Status;
 use crate::utils::error::{MultiGitError, Result};
 use dialoguer::{theme::ColorfulTheme, Confirm};
 use std::process::Command;
@@ -61,9 +62,12 @@ fn perform_auto_backup() -> Result<()> {
             .map_err(|e| MultiGitError::other(format!("Failed to push: {e}")))?;
 
         if output.status.success() {
-            println!("  ✅ Backed up to {remote}");
+            println!("  {} Backed up to {remote}", Status::Success.icon());
         } else {
-            println!("  ⚠️  Warning: Backup to {remote} failed");
+            println!(
+                "  {} Warning: Backup to {remote} failed",
+                Status::Warning.icon()
+            );
         }
     }
 
@@ -76,13 +80,16 @@ fn perform_auto_backup() -> Result<()> {
             .map_err(|e| MultiGitError::other(format!("Failed to push tags: {e}")))?;
 
         if output.status.success() {
-            println!("  ✅ Tags backed up to {remote}");
+            println!("  {} Tags backed up to {remote}", Status::Success.icon());
         } else {
-            println!("  ⚠️  Warning: Tag backup to {remote} failed");
+            println!(
+                "  {} Warning: Tag backup to {remote} failed",
+                Status::Warning.icon()
+            );
         }
     }
 
-    println!("\n✅ Backup complete!");
+    println!("\n{} Backup complete!", Status::Success.icon());
 
     Ok(())
 }
@@ -0,0 +1,128 @@
+//! Bisect command implementation
+//!
+//! Wraps `git bisect` to find the commit that introduced a regression. The
+//! search itself is git's; after each step the state is read back so the
+//! remaining revisions are reported the same way `multigit status` does.
+
+use crate::cli::interactive;
+use crate::git::operations::GitOperations;
+use crate::git::repo_state::{bisect_bad_commit, OperationKind};
+use crate::ui::formatter::Status;
+use crate::utils::error::{MultiGitError, Result};
+use std::process::Command;
+use tracing::info;
+
+/// Start bisecting, optionally marking the bad and known-good commits
+pub fn start(bad: Option<&str>, good: &[String]) -> Result<()> {
+    let mut args = vec!["start".to_string()];
+    if let Some(bad) = bad {
+        args.push(bad.to_string());
+        args.extend(good.iter().cloned());
+    } else if !good.is_empty() {
+        return Err(MultiGitError::invalid_input(
+            "Give the bad commit before the good ones: multigit bisect start <bad> <good>...",
+        ));
+    }
+
+    run_git(&args)?;
+    print_state()
+}
+
+/// Mark `rev` (default: the checked-out commit) as good, bad or skipped
+pub fn mark(term: &str, rev: Option<&str>) -> Result<()> {
+    require_bisect()?;
+    let mut args = vec![term.to_string()];
+    args.extend(rev.map(str::to_string));
+
+    run_git(&args)?;
+    print_state()
+}
+
+/// Let git test each candidate with `command`, whose exit code says good (0),
+/// bad (1-127 except 125) or skip (125)
+pub fn run(command: Vec<String>) -> Result<()> {
+    let git_ops = require_bisect()?;
+    let ready = git_ops
+        .operation_in_progress()?
+        .is_some_and(|operation| operation.remaining.is_some());
+    if !ready {
+        return Err(MultiGitError::invalid_input(
+            "Mark a good and a bad commit before 'multigit bisect run'",
+        ));
+    }
+
+    let mut args = vec!["run".to_string()];
+    args.extend(command);
+    run_git(&args)?;
+    print_state()
+}
+
+/// End the bisect and return to the branch it started from
+pub fn reset() -> Result<()> {
+    require_bisect()?;
+    run_git(&["reset".to_string()])?;
+    interactive::print_success("Bisect finished");
+    Ok(())
+}
+
+fn require_bisect() -> Result<GitOperations> {
+    let git_ops = GitOperations::open(".")?;
+    let bisecting = git_ops
+        .operation_in_progress()?
+        .is_some_and(|operation| operation.kind == OperationKind::Bisect);
+    if bisecting {
+        Ok(git_ops)
+    } else {
+        Err(MultiGitError::invalid_input(
+            "No bisect in progress; start one with 'multigit bisect start <bad> <good>'",
+        ))
+    }
+}
+
+/// Report where the bisect stands, or the first bad commit once it is found
+fn print_state() -> Result<()> {
+    let git_ops = GitOperations::open(".")?;
+    let operation = match git_ops.operation_in_progress()? {
+        Some(operation) if operation.kind == OperationKind::Bisect => operation,
+        _ => return Ok(()),
+    };
+
+    match operation.remaining {
+        Some(0) => {
+            if let Some(oid) = bisect_bad_commit(git_ops.inner()) {
+                let commit = git_ops.find_commit(oid)?;
+                interactive::print_success(&format!(
+                    "First bad commit: {:.7} {}",
+                    oid,
+                    commit.summary().unwrap_or_default()
+                ));
+            }
+            println!("Return to your branch with: multigit bisect reset");
+        }
+        Some(_) => println!("\n{} {operation}", Status::Info.icon()),
+        None => println!(
+            "\n{} Bisecting: mark a good and a bad commit to begin",
+            Status::Info.icon()
+        ),
+    }
+
+    Ok(())
+}
+
+fn run_git(args: &[String]) -> Result<()> {
+    info!("Running git bisect {}", args.join(" "));
+    let status = Command::new("git")
+        .arg("bisect")
+        .args(args)
+        .status()
+        .map_err(|e| MultiGitError::other(format!("Failed to execute git: {e}")))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(MultiGitError::other(format!(
+            "git bisect {} failed",
+            args.first().map_or("", String::as_str)
+        )))
+    }
+}
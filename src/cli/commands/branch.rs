@@ -2,29 +2,47 @@
 //!
 //! Lists, creates, and deletes branches across multiple remotes.
 
+use crate::cli::interactive;
 use crate::core::auth::{AuthBackend, AuthManager};
-use crate::git::branch::BranchManager;
+use crate::git::branch::{BranchManager, DeletedBranch};
+use crate::git::branch_filter::BranchFilter;
 use crate::git::operations::GitOperations;
-use crate::utils::error::Result;
+use crate::ui::formatter::Status;
+use crate::utils::error::{MultiGitError, Result};
+use git2::Oid;
 use tracing::info;
 
 /// Branch subcommands
 pub mod commands {
     use super::{
-        create_on_github, create_on_gitlab, delete_on_github, delete_on_gitlab, info, AuthBackend,
-        AuthManager, BranchManager, GitOperations, Result,
+        create_on_github, create_on_gitlab, delete_on_github, delete_on_gitlab, info, list_deleted,
+        recover_named, AuthBackend, AuthManager, BranchFilter, BranchManager, GitOperations,
+        Result, Status,
     };
 
     /// List branches across all remotes
-    pub fn list(verbose: bool) -> Result<()> {
+    ///
+    /// With `matching`, only branches matching that glob are shown; the
+    /// configured `sync.exclude_branches` still apply, so the local list
+    /// previews what `sync --all-branches --include-branches <glob>` would push.
+    pub fn list(verbose: bool, matching: Option<String>) -> Result<()> {
         info!("Listing branches");
 
         let git_ops = GitOperations::open(".")?;
         let branch_manager = BranchManager::new(git_ops.inner());
 
+        let filter = match matching {
+            Some(ref pattern) => {
+                let config = crate::core::config::Config::load().unwrap_or_default();
+                BranchFilter::new(std::slice::from_ref(pattern), &config.sync.exclude_branches)?
+            }
+            None => BranchFilter::allow_all(),
+        };
+
         println!("\n🌿 Local Branches:\n");
 
-        let local_branches = branch_manager.list_local()?;
+        let mut local_branches = branch_manager.list_local()?;
+        local_branches.retain(|branch| filter.matches(&branch.name));
 
         for branch in &local_branches {
             let marker = if branch.is_head { "* " } else { "  " };
@@ -41,12 +59,25 @@ pub mod commands {
             println!("\n🌍 Remote Branches:\n");
             let remote_branches = branch_manager.list_remote()?;
 
+            // Remote branches are named `<remote>/<branch>`; match on the branch part
             for branch in &remote_branches {
-                println!("  {}", branch.name);
+                let short_name = branch
+                    .name
+                    .split_once('/')
+                    .map_or(branch.name.as_str(), |(_, name)| name);
+                if filter.matches(short_name) {
+                    println!("  {}", branch.name);
+                }
             }
         }
 
-        println!("\n📊 Total: {} local branch(es)", local_branches.len());
+        match matching {
+            Some(pattern) => println!(
+                "\n📊 Total: {} local branch(es) matching '{pattern}'",
+                local_branches.len()
+            ),
+            None => println!("\n📊 Total: {} local branch(es)", local_branches.len()),
+        }
 
         Ok(())
     }
@@ -63,26 +94,27 @@ pub mod commands {
         // Create locally first
         println!("📍 Creating local branch...");
         branch_manager.create(&name, None)?;
-        println!("✓ Local branch created");
+        println!("{} Local branch created", Status::Success.icon());
 
         // Load config to get configured remotes
         let config = crate::core::config::Config::load().unwrap_or_default();
 
         // Create on remotes via API
-        let auth_manager = AuthManager::new(AuthBackend::Keyring, false);
+        let auth_manager = AuthManager::new(AuthBackend::Keyring, config.security.audit_log);
         let allow_env = config.security.allow_env_tokens;
 
         // Try GitHub
         if let Some(github_config) = config.remotes.get("github") {
-            if let Ok(token) = auth_manager.retrieve_credential(
+            if let Ok(token) = auth_manager.retrieve_remote_credential(
+                "github",
                 "github",
                 "github.com",
                 &github_config.username,
                 allow_env,
             ) {
                 match create_on_github(&token, &name).await {
-                    Ok(()) => println!("✓ GitHub: Branch created"),
-                    Err(e) => println!("⚠ GitHub: {e}"),
+                    Ok(()) => println!("{} GitHub: Branch created", Status::Success.icon()),
+                    Err(e) => println!("{} GitHub: {e}", Status::Warning.icon()),
                 }
             }
         }
@@ -94,25 +126,46 @@ pub mod commands {
                 .as_deref()
                 .and_then(|url| crate::utils::validation::extract_host_from_url(url).ok())
                 .unwrap_or_else(|| "gitlab.com".to_string());
-            if let Ok(token) = auth_manager.retrieve_credential(
+            if let Ok(token) = auth_manager.retrieve_remote_credential(
+                "gitlab",
                 "gitlab",
                 &host,
                 &gitlab_config.username,
                 allow_env,
             ) {
                 match create_on_gitlab(&token, &name).await {
-                    Ok(()) => println!("✓ GitLab: Branch created"),
-                    Err(e) => println!("⚠ GitLab: {e}"),
+                    Ok(()) => println!("{} GitLab: Branch created", Status::Success.icon()),
+                    Err(e) => println!("{} GitLab: {e}", Status::Warning.icon()),
                 }
             }
         }
 
-        println!("\n✅ Branch '{name}' created successfully");
+        println!(
+            "\n{} Branch '{name}' created successfully",
+            Status::Success.icon()
+        );
         println!("💡 Switch to it with: git checkout {name}");
 
         Ok(())
     }
 
+    /// Recreate a deleted branch from the reflog
+    ///
+    /// Without `name`, lists the deleted branches that can be recovered. The
+    /// commit the branch will point to is shown for confirmation unless `yes`.
+    pub fn recover(name: Option<String>, yes: bool) -> Result<()> {
+        let git_ops = GitOperations::open(".")?;
+        let branch_manager = BranchManager::new(git_ops.inner());
+        let deleted = branch_manager.deleted()?;
+
+        if let Some(name) = name {
+            recover_named(&git_ops, &branch_manager, &deleted, &name, yes)
+        } else {
+            list_deleted(&git_ops, &deleted);
+            Ok(())
+        }
+    }
+
     /// Delete a branch from all remotes
     pub async fn delete(name: String, _force: bool) -> Result<()> {
         info!("Deleting branch: {}", name);
@@ -120,12 +173,13 @@ pub mod commands {
         let git_ops = GitOperations::open(".")?;
         let branch_manager = BranchManager::new(git_ops.inner());
 
+        branch_manager.require(&name)?;
         println!("\n🗑️  Deleting branch '{name}'\n");
 
         // Check if it's the current branch
         let current = branch_manager.current()?;
         if current == name {
-            println!("⚠️  Cannot delete current branch");
+            println!("{} Cannot delete current branch", Status::Warning.icon());
             println!("Please switch to another branch first");
             return Ok(());
         }
@@ -134,20 +188,21 @@ pub mod commands {
         let config = crate::core::config::Config::load().unwrap_or_default();
 
         // Delete from remotes first
-        let auth_manager = AuthManager::new(AuthBackend::Keyring, false);
+        let auth_manager = AuthManager::new(AuthBackend::Keyring, config.security.audit_log);
         let allow_env = config.security.allow_env_tokens;
 
         // Try GitHub
         if let Some(github_config) = config.remotes.get("github") {
-            if let Ok(token) = auth_manager.retrieve_credential(
+            if let Ok(token) = auth_manager.retrieve_remote_credential(
+                "github",
                 "github",
                 "github.com",
                 &github_config.username,
                 allow_env,
             ) {
                 match delete_on_github(&token, &name).await {
-                    Ok(()) => println!("✓ GitHub: Branch deleted"),
-                    Err(e) => println!("⚠ GitHub: {e}"),
+                    Ok(()) => println!("{} GitHub: Branch deleted", Status::Success.icon()),
+                    Err(e) => println!("{} GitHub: {e}", Status::Warning.icon()),
                 }
             }
         }
@@ -159,30 +214,121 @@ pub mod commands {
                 .as_deref()
                 .and_then(|url| crate::utils::validation::extract_host_from_url(url).ok())
                 .unwrap_or_else(|| "gitlab.com".to_string());
-            if let Ok(token) = auth_manager.retrieve_credential(
+            if let Ok(token) = auth_manager.retrieve_remote_credential(
+                "gitlab",
                 "gitlab",
                 &host,
                 &gitlab_config.username,
                 allow_env,
             ) {
                 match delete_on_gitlab(&token, &name).await {
-                    Ok(()) => println!("✓ GitLab: Branch deleted"),
-                    Err(e) => println!("⚠ GitLab: {e}"),
+                    Ok(()) => println!("{} GitLab: Branch deleted", Status::Success.icon()),
+                    Err(e) => println!("{} GitLab: {e}", Status::Warning.icon()),
                 }
             }
         }
 
         // Delete locally
         println!("\n📍 Deleting local branch...");
+        let tip = branch_manager.get_info(&name)?.target;
         branch_manager.delete(&name)?;
-        println!("✓ Local branch deleted");
+        match tip {
+            Some(tip) => println!(
+                "{} Local branch deleted (was {:.7})",
+                Status::Success.icon(),
+                tip.to_string()
+            ),
+            None => println!("{} Local branch deleted", Status::Success.icon()),
+        }
+        // `branch recover` only finds tips the HEAD reflog recorded
+        let recoverable = branch_manager
+            .deleted()?
+            .iter()
+            .any(|branch| branch.name == name);
+        if recoverable {
+            println!("   Undo with: multigit branch recover {name}");
+        } else if let Some(tip) = tip {
+            println!("   Undo with: git branch {name} {tip}");
+        }
 
-        println!("\n✅ Branch '{name}' deleted successfully");
+        println!(
+            "\n{} Branch '{name}' deleted successfully",
+            Status::Success.icon()
+        );
 
         Ok(())
     }
 }
 
+/// Recreate `name` from `deleted` after showing where it will point
+fn recover_named(
+    git_ops: &GitOperations,
+    branch_manager: &BranchManager<'_>,
+    deleted: &[DeletedBranch],
+    name: &str,
+    yes: bool,
+) -> Result<()> {
+    let branch = deleted
+        .iter()
+        .find(|branch| branch.name == name)
+        .ok_or_else(|| {
+            MultiGitError::invalid_input(format!(
+                "No deleted branch '{name}' in the reflog; see 'multigit branch recover'"
+            ))
+        })?;
+
+    println!(
+        "\n'{name}' will point to {}",
+        describe_commit(git_ops, branch.target, branch.seen_at)
+    );
+    if !yes && !interactive::confirm(&format!("Recreate branch '{name}'?"))? {
+        println!("Cancelled.");
+        return Ok(());
+    }
+
+    branch_manager.recover(name)?;
+    println!("{} Recovered branch '{name}'", Status::Success.icon());
+    Ok(())
+}
+
+/// Print the deleted branches that `branch recover` can bring back
+fn list_deleted(git_ops: &GitOperations, deleted: &[DeletedBranch]) {
+    if deleted.is_empty() {
+        println!("No deleted branches found in the reflog.");
+        return;
+    }
+
+    println!("\nRecently deleted branches:\n");
+    for branch in deleted {
+        println!(
+            "  {:<24} {}",
+            branch.name,
+            describe_commit(git_ops, branch.target, branch.seen_at)
+        );
+    }
+    println!("\nRecover one with: multigit branch recover <name>");
+}
+
+/// Short id, summary and author of a commit, with when its reflog entry was written
+fn describe_commit(git_ops: &GitOperations, oid: Oid, seen_at: i64) -> String {
+    let seen = chrono::DateTime::from_timestamp(seen_at, 0)
+        .map(|at| {
+            at.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default();
+    match git_ops.find_commit(oid) {
+        Ok(commit) => format!(
+            "{:.7} {} ({}, last seen {seen})",
+            oid.to_string(),
+            commit.summary().unwrap_or_default(),
+            commit.author().name().unwrap_or("unknown")
+        ),
+        Err(_) => format!("{:.7} (last seen {seen})", oid.to_string()),
+    }
+}
+
 /// Create branch on GitHub via API
 async fn create_on_github(_token: &str, _branch_name: &str) -> Result<()> {
     // Note: Branch creation typically happens on push, not via API
@@ -1,78 +1,308 @@
 //! Auto-generate changelog from conventional commits
 //!
-//! Parse conventional commits and create formatted CHANGELOG.md
+//! Parse conventional commits and create formatted CHANGELOG.md, either with
+//! a section per commit type or in the Keep a Changelog layout.
 
+use crate::ui::formatter::Status;
 use crate::utils::error::{MultiGitError, Result};
+use chrono::{DateTime, Local};
+use git2::{Oid, Repository, Sort};
 use std::collections::HashMap;
-use std::fmt::Write as _;
+use std::fmt::{self, Write as _};
 use std::fs;
-use std::process::Command;
+
+/// Header of a new Keep a Changelog file
+const KEEP_A_CHANGELOG_HEADER: &str = "# Changelog
+
+All notable changes to this project will be documented in this file.
+
+The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),
+and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).
+
+";
+
+/// Keep a Changelog sections, in the order they appear in a release
+const KEEP_A_CHANGELOG_SECTIONS: [&str; 4] = ["Added", "Changed", "Fixed", "Removed"];
+
+/// How `changelog` lays out each release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChangelogFormat {
+    /// A section per conventional commit type, appended to the file
+    #[default]
+    Conventional,
+    /// Keep a Changelog: Added, Changed, Fixed and Removed, inserted newest
+    /// first below the file's header
+    KeepAChangelog,
+}
+
+impl fmt::Display for ChangelogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Conventional => "conventional",
+            Self::KeepAChangelog => "keepachangelog",
+        })
+    }
+}
+
+impl std::str::FromStr for ChangelogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "conventional" => Ok(Self::Conventional),
+            "keepachangelog" | "keep-a-changelog" => Ok(Self::KeepAChangelog),
+            _ => Err(format!(
+                "Unknown changelog format '{s}': use conventional or keepachangelog"
+            )),
+        }
+    }
+}
+
+/// Commits of one release, or of the unreleased work on top of the last one
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Release {
+    /// The tag, or `None` for unreleased commits
+    pub tag: Option<String>,
+    /// Date of the tagged commit, or today for unreleased commits
+    pub date: String,
+    /// Commit summaries, newest first
+    pub commits: Vec<String>,
+}
 
 /// Generate changelog
-pub fn execute(since: Option<String>, output: Option<String>) -> Result<()> {
+///
+/// Covers the commits after `since` up to `until` (HEAD by default). Without
+/// `since`, it starts at the most recent tag before `until`, or covers the
+/// whole history when there is none. Tags inside the range split the output
+/// into one section per release.
+pub fn execute(
+    since: Option<String>,
+    until: Option<String>,
+    output: Option<String>,
+    format: ChangelogFormat,
+) -> Result<()> {
     println!("\n📋 Generating Changelog...\n");
 
-    let since_ref = since.unwrap_or_else(|| {
-        // Try to find last tag
-        get_last_tag().unwrap_or_else(|_| "HEAD~10".to_string())
-    });
+    let repo = Repository::open(".").map_err(MultiGitError::GitError)?;
+    let tags = tags_by_commit(&repo)?;
+    let until = resolve(&repo, until.as_deref().unwrap_or("HEAD"))?;
+    let since = match since {
+        Some(since) => Some(resolve(&repo, &since)?),
+        None => previous_tag(&repo, until, &tags)?,
+    };
 
-    let commits = get_commits_since(&since_ref)?;
-    let changelog = format_changelog(&commits);
+    let releases = releases(&repo, since, until, &tags)?;
+    if releases.is_empty() {
+        println!("No commits in range; changelog left unchanged");
+        return Ok(());
+    }
 
     let output_file = output.unwrap_or_else(|| "CHANGELOG.md".to_string());
+    write_changelog(&output_file, &releases, format)?;
 
-    // Append or create
-    let existing = fs::read_to_string(&output_file).unwrap_or_default();
+    println!(
+        "{} Changelog written to {output_file}",
+        Status::Success.icon()
+    );
 
-    let final_content = if existing.is_empty() {
-        format!("# Changelog\n\n{changelog}")
-    } else {
-        format!("{existing}\n\n{changelog}")
+    Ok(())
+}
+
+/// The notes of a release and the changelog they were written to
+#[derive(Debug, Clone)]
+pub(crate) struct ReleaseNotes {
+    /// The changelog file
+    pub path: String,
+    /// The release's section, heading included
+    pub notes: String,
+}
+
+/// Write the commits since the previous tag to the changelog as the release
+/// `version`, dated today
+///
+/// Fails when there are no commits since the previous tag.
+pub(crate) fn write_release_notes(
+    repo: &Repository,
+    version: &str,
+    output: Option<String>,
+    format: ChangelogFormat,
+) -> Result<ReleaseNotes> {
+    let tags = tags_by_commit(repo)?;
+    let head = resolve(repo, "HEAD")?;
+    let since = previous_tag(repo, head, &tags)?;
+
+    let commits: Vec<String> = releases(repo, since, head, &HashMap::new())?
+        .into_iter()
+        .flat_map(|release| release.commits)
+        .collect();
+    if commits.is_empty() {
+        let since = since.and_then(|oid| tags.get(&oid)).map_or_else(
+            || "the start of history".to_string(),
+            |tag| format!("'{tag}'"),
+        );
+        return Err(MultiGitError::other(format!(
+            "Nothing to release: no commits since {since}"
+        )));
+    }
+
+    let release = Release {
+        tag: Some(version.to_string()),
+        date: Local::now().format("%Y-%m-%d").to_string(),
+        commits,
     };
+    let path = output.unwrap_or_else(|| "CHANGELOG.md".to_string());
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| MultiGitError::other("Repository has no working directory"))?;
+    write_changelog(
+        &workdir.join(&path).to_string_lossy(),
+        std::slice::from_ref(&release),
+        format,
+    )?;
 
-    fs::write(&output_file, final_content)
-        .map_err(|e| MultiGitError::other(format!("Failed to write changelog: {e}")))?;
+    let notes = match format {
+        ChangelogFormat::Conventional => format_release(&release),
+        ChangelogFormat::KeepAChangelog => format_keep_a_changelog(&release),
+    };
+    Ok(ReleaseNotes { path, notes })
+}
 
-    println!("✅ Changelog written to {output_file}");
+/// Add `releases` to the changelog at `output_file` in `format`
+fn write_changelog(output_file: &str, releases: &[Release], format: ChangelogFormat) -> Result<()> {
+    let existing = fs::read_to_string(output_file).unwrap_or_default();
 
-    Ok(())
+    let final_content = match format {
+        ChangelogFormat::Conventional => {
+            // Append or create
+            let changelog = format_changelog(releases);
+            if existing.is_empty() {
+                format!("# Changelog\n\n{changelog}")
+            } else {
+                format!("{existing}\n\n{changelog}")
+            }
+        }
+        ChangelogFormat::KeepAChangelog => {
+            let sections: Vec<String> = releases.iter().map(format_keep_a_changelog).collect();
+            insert_sections(&existing, &sections)
+        }
+    };
+
+    fs::write(output_file, final_content)
+        .map_err(|e| MultiGitError::other(format!("Failed to write changelog: {e}")))
 }
 
-fn get_last_tag() -> Result<String> {
-    let output = Command::new("git")
-        .args(["describe", "--tags", "--abbrev=0"])
-        .output()
-        .map_err(|e| MultiGitError::other(format!("Failed to get last tag: {e}")))?;
+/// The commit a tag, branch or revision names
+fn resolve(repo: &Repository, spec: &str) -> Result<Oid> {
+    repo.revparse_single(spec)
+        .and_then(|object| object.peel_to_commit())
+        .map(|commit| commit.id())
+        .map_err(|e| {
+            MultiGitError::invalid_input(format!("Unknown revision '{spec}': {}", e.message()))
+        })
+}
 
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+/// Tag names by the commit they point at; of several tags on one commit,
+/// the greatest name is kept
+fn tags_by_commit(repo: &Repository) -> Result<HashMap<Oid, String>> {
+    let mut tags: HashMap<Oid, String> = HashMap::new();
+    let names = repo.tag_names(None).map_err(MultiGitError::GitError)?;
+    for name in names.iter().flatten() {
+        let Ok(commit) = repo
+            .revparse_single(&format!("refs/tags/{name}"))
+            .and_then(|object| object.peel_to_commit())
+        else {
+            continue;
+        };
+        let entry = tags.entry(commit.id()).or_insert_with(|| name.to_string());
+        if name > entry.as_str() {
+            *entry = name.to_string();
+        }
+    }
+    Ok(tags)
 }
 
-fn get_commits_since(since: &str) -> Result<Vec<String>> {
-    let output = Command::new("git")
-        .args(["log", &format!("{since}..HEAD"), "--pretty=format:%s"])
-        .output()
-        .map_err(|e| MultiGitError::other(format!("Failed to get commits: {e}")))?;
+/// A revwalk over `until` and its ancestors, newest first
+fn walk(repo: &Repository, until: Oid) -> Result<git2::Revwalk<'_>> {
+    let mut revwalk = repo.revwalk().map_err(MultiGitError::GitError)?;
+    revwalk
+        .set_sorting(Sort::TOPOLOGICAL | Sort::TIME)
+        .map_err(MultiGitError::GitError)?;
+    revwalk.push(until).map_err(MultiGitError::GitError)?;
+    Ok(revwalk)
+}
 
-    let commits: Vec<String> = String::from_utf8_lossy(&output.stdout)
-        .lines()
-        .map(std::string::ToString::to_string)
-        .collect();
+/// The most recent tagged commit reachable from `until`, not counting a tag
+/// on `until` itself, so a freshly tagged release gets its own changes
+fn previous_tag(repo: &Repository, until: Oid, tags: &HashMap<Oid, String>) -> Result<Option<Oid>> {
+    for oid in walk(repo, until)? {
+        let oid = oid.map_err(MultiGitError::GitError)?;
+        if oid != until && tags.contains_key(&oid) {
+            return Ok(Some(oid));
+        }
+    }
+    Ok(None)
+}
+
+/// The commits after `since` up to `until`, split at each tag into releases,
+/// newest first
+fn releases(
+    repo: &Repository,
+    since: Option<Oid>,
+    until: Oid,
+    tags: &HashMap<Oid, String>,
+) -> Result<Vec<Release>> {
+    let mut revwalk = walk(repo, until)?;
+    if let Some(since) = since {
+        revwalk.hide(since).map_err(MultiGitError::GitError)?;
+    }
+
+    let mut releases: Vec<Release> = Vec::new();
+    for oid in revwalk {
+        let commit = repo
+            .find_commit(oid.map_err(MultiGitError::GitError)?)
+            .map_err(MultiGitError::GitError)?;
+
+        if let Some(tag) = tags.get(&commit.id()) {
+            let date = DateTime::from_timestamp(commit.time().seconds(), 0)
+                .unwrap_or_default()
+                .format("%Y-%m-%d")
+                .to_string();
+            releases.push(Release {
+                tag: Some(tag.clone()),
+                date,
+                commits: Vec::new(),
+            });
+        } else if releases.is_empty() {
+            releases.push(Release {
+                tag: None,
+                date: Local::now().format("%Y-%m-%d").to_string(),
+                commits: Vec::new(),
+            });
+        }
+
+        let release = releases.last_mut().expect("a release was just pushed");
+        release
+            .commits
+            .push(commit.summary().unwrap_or_default().to_string());
+    }
+    Ok(releases)
+}
 
-    Ok(commits)
+fn format_changelog(releases: &[Release]) -> String {
+    releases.iter().map(format_release).collect()
 }
 
-fn format_changelog(commits: &[String]) -> String {
+fn format_release(release: &Release) -> String {
     let mut categorized: HashMap<String, Vec<String>> = HashMap::new();
 
-    for commit in commits {
+    for commit in &release.commits {
         let (category, message) = parse_conventional_commit(commit);
         categorized.entry(category).or_default().push(message);
     }
 
     let mut changelog = String::new();
-    let date = chrono::Local::now().format("%Y-%m-%d");
-    writeln!(changelog, "## [Unreleased] - {date}\n").unwrap();
+    let name = release.tag.as_deref().unwrap_or("Unreleased");
+    writeln!(changelog, "## [{name}] - {}\n", release.date).unwrap();
 
     // Order: feat, fix, docs, style, refactor, perf, test, build, ci, chore
     let order = vec![
@@ -108,6 +338,104 @@ fn format_changelog(commits: &[String]) -> String {
     changelog
 }
 
+/// A release in the Keep a Changelog layout; commit types without a
+/// matching section (docs, tests, chores) are left out
+fn format_keep_a_changelog(release: &Release) -> String {
+    let mut sections: HashMap<&str, Vec<String>> = HashMap::new();
+    for commit in &release.commits {
+        let (category, message) = parse_conventional_commit(commit);
+        if let Some(section) = keep_a_changelog_section(&category) {
+            sections.entry(section).or_default().push(message);
+        }
+    }
+
+    let mut changelog = String::new();
+    match &release.tag {
+        Some(tag) => writeln!(changelog, "## [{}] - {}\n", version(tag), release.date).unwrap(),
+        None => changelog.push_str("## [Unreleased]\n\n"),
+    }
+    for section in KEEP_A_CHANGELOG_SECTIONS {
+        if let Some(messages) = sections.get(section) {
+            writeln!(changelog, "### {section}\n").unwrap();
+            for msg in messages {
+                writeln!(changelog, "- {msg}").unwrap();
+            }
+            changelog.push('\n');
+        }
+    }
+    changelog
+}
+
+/// The Keep a Changelog section a conventional commit type belongs in
+fn keep_a_changelog_section(commit_type: &str) -> Option<&'static str> {
+    match commit_type.trim_end_matches('!') {
+        "feat" => Some("Added"),
+        "refactor" | "perf" | "style" => Some("Changed"),
+        "fix" => Some("Fixed"),
+        "remove" | "revert" => Some("Removed"),
+        _ => None,
+    }
+}
+
+/// The version a tag names, without a leading `v` (`v1.2.0` is `1.2.0`)
+fn version(tag: &str) -> &str {
+    tag.strip_prefix('v')
+        .filter(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+        .unwrap_or(tag)
+}
+
+/// `existing` with `sections` inserted after its header, before the releases
+/// already there
+///
+/// A release that already has a section (say `## [Unreleased]` from an
+/// earlier run) has it replaced. An empty file gets the standard header.
+fn insert_sections(existing: &str, sections: &[String]) -> String {
+    if existing.trim().is_empty() {
+        return format!("{KEEP_A_CHANGELOG_HEADER}{}", sections.concat());
+    }
+
+    let heading = |section: &str| section.lines().next().unwrap_or_default().to_string();
+    let new_headings: Vec<String> = sections.iter().map(|section| heading(section)).collect();
+
+    // Split into the header and one chunk per `## ` release heading
+    let mut header = String::new();
+    let mut old_sections: Vec<String> = Vec::new();
+    for line in existing.split_inclusive('\n') {
+        if line.starts_with("## ") {
+            old_sections.push(String::new());
+        }
+        match old_sections.last_mut() {
+            Some(section) => section.push_str(line),
+            None => header.push_str(line),
+        }
+    }
+    if !header.ends_with("\n\n") {
+        header.push_str(if header.ends_with('\n') { "\n" } else { "\n\n" });
+    }
+
+    let replaced = |section: &String| {
+        let old = heading(section);
+        new_headings.iter().any(|new| same_release(new, &old))
+    };
+    let mut content = header;
+    content.push_str(&sections.concat());
+    for section in old_sections.iter().filter(|section| !replaced(section)) {
+        content.push_str(section);
+    }
+    content
+}
+
+/// Whether two `## [name] ...` headings are for the same release
+fn same_release(a: &str, b: &str) -> bool {
+    let name = |heading: &str| {
+        heading
+            .strip_prefix("## [")
+            .and_then(|rest| rest.split_once(']'))
+            .map(|(name, _)| name.to_lowercase())
+    };
+    name(a).is_some() && name(a) == name(b)
+}
+
 fn parse_conventional_commit(commit: &str) -> (String, String) {
     // Parse: type(scope): message
     if let Some(colon_pos) = commit.find(':') {
@@ -125,3 +453,144 @@ fn parse_conventional_commit(commit: &str) -> (String, String) {
         ("other".to_string(), commit.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// A repository with one commit per summary, tagging those that have a tag
+    fn repo_with_history(history: &[(&str, Option<&str>)]) -> (TempDir, Repository) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        {
+            let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+            let tree = repo
+                .find_tree(repo.index().unwrap().write_tree().unwrap())
+                .unwrap();
+            let mut parent = None;
+            for (summary, tag) in history {
+                let parents: Vec<&git2::Commit<'_>> = parent.iter().collect();
+                let oid = repo
+                    .commit(Some("HEAD"), &sig, &sig, summary, &tree, &parents)
+                    .unwrap();
+                let commit = repo.find_commit(oid).unwrap();
+                if let Some(tag) = tag {
+                    repo.tag_lightweight(tag, commit.as_object(), false)
+                        .unwrap();
+                }
+                parent = Some(commit);
+            }
+        }
+        (temp_dir, repo)
+    }
+
+    fn head(repo: &Repository) -> Oid {
+        repo.head().unwrap().target().unwrap()
+    }
+
+    #[test]
+    fn test_releases_since_previous_tag() {
+        let (_dir, repo) = repo_with_history(&[
+            ("feat: start", Some("v1.0")),
+            ("fix: crash", None),
+            ("feat: export", None),
+        ]);
+        let tags = tags_by_commit(&repo).unwrap();
+        let since = previous_tag(&repo, head(&repo), &tags).unwrap();
+        assert_eq!(since, Some(resolve(&repo, "v1.0").unwrap()));
+
+        let releases = releases(&repo, since, head(&repo), &tags).unwrap();
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].tag, None);
+        assert_eq!(releases[0].commits, vec!["feat: export", "fix: crash"]);
+    }
+
+    #[test]
+    fn test_range_grouped_by_release() {
+        let (_dir, repo) = repo_with_history(&[
+            ("feat: start", Some("v1.0")),
+            ("fix: crash", Some("v1.1")),
+            ("feat: export", None),
+            ("docs: readme", Some("v1.2")),
+            ("chore: after", None),
+        ]);
+        let tags = tags_by_commit(&repo).unwrap();
+
+        let since = resolve(&repo, "v1.0").unwrap();
+        let until = resolve(&repo, "v1.2").unwrap();
+        let releases = releases(&repo, Some(since), until, &tags).unwrap();
+        let names: Vec<Option<&str>> = releases.iter().map(|r| r.tag.as_deref()).collect();
+        assert_eq!(names, vec![Some("v1.2"), Some("v1.1")]);
+        assert_eq!(releases[0].commits, vec!["docs: readme", "feat: export"]);
+        assert_eq!(releases[1].commits, vec!["fix: crash"]);
+
+        // A tag on the end of the range isn't its own starting point
+        assert_eq!(
+            previous_tag(&repo, until, &tags).unwrap(),
+            Some(resolve(&repo, "v1.1").unwrap())
+        );
+
+        let changelog = format_changelog(&releases);
+        assert!(changelog.starts_with("## [v1.2] - "));
+        assert!(changelog.contains("## [v1.1] - "));
+        assert!(changelog.contains("- export\n"));
+    }
+
+    #[test]
+    fn test_keep_a_changelog_release() {
+        let release = Release {
+            tag: Some("v1.2.0".to_string()),
+            date: "2024-05-01".to_string(),
+            commits: vec![
+                "fix: crash on empty config".to_string(),
+                "docs: readme".to_string(),
+                "feat(cli)!: new flag".to_string(),
+                "revert: old cache".to_string(),
+            ],
+        };
+        assert_eq!(
+            format_keep_a_changelog(&release),
+            "## [1.2.0] - 2024-05-01\n\n\
+             ### Added\n\n- new flag\n\n\
+             ### Fixed\n\n- crash on empty config\n\n\
+             ### Removed\n\n- old cache\n\n"
+        );
+        assert_eq!(version("release-1"), "release-1");
+        assert_eq!(version("version"), "version");
+    }
+
+    #[test]
+    fn test_insert_sections() {
+        let fresh = insert_sections("", &["## [Unreleased]\n\n".to_string()]);
+        assert!(fresh.starts_with(KEEP_A_CHANGELOG_HEADER));
+        assert!(fresh.ends_with("## [Unreleased]\n\n"));
+
+        let existing = "# Changelog\n\nNotes.\n\n## [Unreleased]\n\n### Added\n\n- stale\n\n\
+                        ## [1.0.0] - 2024-01-01\n\n### Added\n\n- first\n";
+        let updated = insert_sections(
+            existing,
+            &[
+                "## [Unreleased]\n\n### Fixed\n\n- fresh\n\n".to_string(),
+                "## [1.1.0] - 2024-03-01\n\n### Added\n\n- more\n\n".to_string(),
+            ],
+        );
+        assert_eq!(
+            updated,
+            "# Changelog\n\nNotes.\n\n\
+             ## [Unreleased]\n\n### Fixed\n\n- fresh\n\n\
+             ## [1.1.0] - 2024-03-01\n\n### Added\n\n- more\n\n\
+             ## [1.0.0] - 2024-01-01\n\n### Added\n\n- first\n"
+        );
+    }
+
+    #[test]
+    fn test_whole_history_without_tags() {
+        let (_dir, repo) = repo_with_history(&[("feat: one", None), ("fix: two", None)]);
+        let tags = tags_by_commit(&repo).unwrap();
+        assert!(tags.is_empty());
+        assert_eq!(previous_tag(&repo, head(&repo), &tags).unwrap(), None);
+        let releases = releases(&repo, None, head(&repo), &tags).unwrap();
+        assert_eq!(releases[0].commits.len(), 2);
+    }
+}
@@ -0,0 +1,88 @@
+//! Cherry-pick and mirror
+//!
+//! Apply commits on top of the current branch with libgit2, hand conflicts to
+//! the conflict resolver, then push the result to every remote.
+
+use crate::cli::commands::push;
+use crate::cli::interactive;
+use crate::core::config::Config;
+use crate::git::operations::{CherryPickOutcome, GitOperations};
+use crate::ui::conflict_resolver::run_conflict_resolver;
+use crate::ui::formatter::Status;
+use crate::utils::error::{MultiGitError, Result};
+use git2::Oid;
+use std::io::IsTerminal;
+use tracing::info;
+
+/// Cherry-pick `commits` in order, then push the current branch to all remotes
+///
+/// Stops at the first commit that conflicts and can't be resolved here.
+pub async fn execute(commits: Vec<String>) -> Result<()> {
+    if commits.is_empty() {
+        return Err(MultiGitError::invalid_input(
+            "Name at least one commit to cherry-pick",
+        ));
+    }
+
+    let git_ops = GitOperations::open(".")?;
+    if let Some(op) = git_ops.operation_in_progress()? {
+        return Err(op.refuse("cherry-pick"));
+    }
+    if !git_ops.is_clean()? {
+        return Err(MultiGitError::other(
+            "Working directory has uncommitted changes; commit or stash them before cherry-picking",
+        ));
+    }
+
+    println!("\n🍒 Cherry-picking {} commit(s)...\n", commits.len());
+    for rev in &commits {
+        let oid = match git_ops.cherry_pick(rev)? {
+            CherryPickOutcome::Committed(oid) => oid,
+            CherryPickOutcome::Conflicts(files) => resolve(&git_ops, rev, &files).await?,
+        };
+        println!(
+            "{} Picked {rev} as {:.7}",
+            Status::Success.icon(),
+            oid.to_string()
+        );
+    }
+
+    info!("Pushing cherry-picked commits");
+    push::execute(
+        None,
+        false,
+        Vec::new(),
+        None,
+        false,
+        false,
+        None,
+        push::TagMode::None,
+        None,
+    )
+    .await
+}
+
+/// Report the conflicts of `rev`, run the resolver and conclude the pick
+async fn resolve(git_ops: &GitOperations, rev: &str, files: &[String]) -> Result<Oid> {
+    println!(
+        "{} {rev} does not apply cleanly. Conflicted files:",
+        Status::Warning.icon()
+    );
+    for file in files {
+        println!("    {} {file}", Status::Error.icon());
+    }
+
+    let unresolved = || {
+        MultiGitError::conflict(
+            "Cherry-pick stopped on conflicts. Resolve them, run 'git cherry-pick --continue', \
+             then 'multigit push' (or 'multigit abort' to give up)",
+        )
+    };
+    let terminal = std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+    if !terminal || !interactive::confirm("Open the conflict resolver?")? {
+        return Err(unresolved());
+    }
+
+    run_conflict_resolver(Config::load().unwrap_or_default()).await?;
+    git_ops.conclude_cherry_pick().map_err(|_| unresolved())
+}
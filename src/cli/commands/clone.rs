@@ -2,12 +2,22 @@
 //!
 //! Clone a repository with multi-remote support.
 
+use crate::core::config::Config;
 use crate::git::operations::GitOperations;
+use crate::ui::formatter::Status;
 use crate::utils::error::{MultiGitError, Result};
 use tracing::info;
 
 /// Clone a repository with optional mirror remotes
-pub fn execute(url: String, path: Option<String>, mirrors: Vec<String>) -> Result<()> {
+///
+/// `depth` makes a shallow clone of the last N commits; without it
+/// `settings.fetch_depth` applies.
+pub fn execute(
+    url: String,
+    path: Option<String>,
+    mirrors: Vec<String>,
+    depth: Option<u32>,
+) -> Result<()> {
     info!("Cloning repository from: {}", url);
 
     // Determine clone path
@@ -20,11 +30,15 @@ pub fn execute(url: String, path: Option<String>, mirrors: Vec<String>) -> Resul
 
     println!("\n📥 Cloning repository...");
     println!("   Source: {url}");
+    let depth = depth.or_else(|| Config::load().ok().and_then(|c| c.settings.fetch_depth));
+    if let Some(depth) = depth {
+        println!("   Depth: {depth} commit(s)");
+    }
     println!("   Destination: {clone_path}\n");
 
     // Perform the clone
-    let git_ops = GitOperations::clone(&url, &clone_path)?;
-    println!("✓ Repository cloned successfully");
+    let git_ops = GitOperations::clone_with_depth(&url, &clone_path, depth)?;
+    println!("{} Repository cloned successfully", Status::Success.icon());
 
     // Add mirror remotes if specified
     if !mirrors.is_empty() {
@@ -32,13 +46,16 @@ pub fn execute(url: String, path: Option<String>, mirrors: Vec<String>) -> Resul
 
         for mirror in &mirrors {
             match add_mirror_remote(&git_ops, mirror, &extract_repo_name(&url)) {
-                Ok(()) => println!("✓ Added mirror: {mirror}"),
-                Err(e) => println!("⚠ Failed to add mirror {mirror}: {e}"),
+                Ok(()) => println!("{} Added mirror: {mirror}", Status::Success.icon()),
+                Err(e) => println!(
+                    "{} Failed to add mirror {mirror}: {e}",
+                    Status::Warning.icon()
+                ),
             }
         }
     }
 
-    println!("\n✅ Clone complete!");
+    println!("\n{} Clone complete!", Status::Success.icon());
     println!("\n💡 Next steps:");
     println!("   cd {clone_path}");
     println!("   multigit init");
@@ -92,5 +109,5 @@ pub fn execute_interactive(url: String) -> Result<()> {
         .map(|&i| providers[i].to_string())
         .collect();
 
-    execute(url, Some(path), mirrors)
+    execute(url, Some(path), mirrors, None)
 }
@@ -0,0 +1,244 @@
+//! Commit command implementation
+//!
+//! Stages and commits through git2, so committing works in minimal
+//! containers without the `git` binary. The binary is only used for what
+//! git2 can't do: signing, running commit hooks, opening an editor for the
+//! message, and extra `git commit` arguments. `--author` and `--date` are
+//! applied by git2 directly, or passed on when the binary is used. With
+//! `security.signing_key` set, commits are signed with that key.
+
+use crate::core::config::Config;
+
+use crate::cli::commands::git_passthrough::{self, check_git_available};
+use crate::cli::commands::status::warn_dirty_submodules;
+use crate::git::identity::CommitOverrides;
+use crate::git::operations::GitOperations;
+use crate::git::signing::{commit_sign_args, git_signing_key, remediation, SigningFormat};
+use crate::ui::formatter::Status;
+use crate::utils::error::{MultiGitError, Result};
+use std::fmt;
+use tracing::{debug, warn};
+
+/// Why a commit has to go through the `git` binary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NeedsGit {
+    /// `security.signing_key` or `commit.gpgsign` is set
+    Signing,
+    /// Commit hooks are installed and would otherwise be skipped
+    Hooks,
+    /// No message was given, so one has to be written in an editor
+    Editor,
+    /// Extra `git commit` arguments were given
+    Arguments,
+}
+
+impl NeedsGit {
+    /// Whether the commit can't be made without the binary; without it,
+    /// hooks are skipped with a warning instead
+    #[must_use]
+    pub fn is_required(self) -> bool {
+        self != Self::Hooks
+    }
+}
+
+impl fmt::Display for NeedsGit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Signing => write!(f, "signing commits"),
+            Self::Hooks => write!(f, "running commit hooks"),
+            Self::Editor => write!(f, "writing the message in an editor (pass -m instead)"),
+            Self::Arguments => write!(f, "extra git commit arguments"),
+        }
+    }
+}
+
+/// What, if anything, makes this commit need the `git` binary
+#[must_use]
+pub fn needs_git(
+    git_ops: &GitOperations,
+    has_message: bool,
+    args: &[String],
+    signing_key: Option<&str>,
+) -> Option<NeedsGit> {
+    if !args.is_empty() {
+        Some(NeedsGit::Arguments)
+    } else if !has_message {
+        Some(NeedsGit::Editor)
+    } else if signing_key.is_some() || git_ops.signs_commits() {
+        Some(NeedsGit::Signing)
+    } else if !git_ops.commit_hooks().is_empty() {
+        Some(NeedsGit::Hooks)
+    } else {
+        None
+    }
+}
+
+/// Commit the index (with `all`, every change to tracked files first), or
+/// with `amend` replace HEAD
+pub fn execute(
+    message: Option<String>,
+    amend: bool,
+    all: bool,
+    args: Vec<String>,
+    overrides: &CommitOverrides,
+) -> Result<()> {
+    overrides.validate()?;
+    warn_dirty_submodules();
+    let git_ops = GitOperations::open(".")?;
+    let config = Config::load()?;
+    let signing_key = config.security.signing_key();
+
+    if let Some(reason) = needs_git(&git_ops, message.is_some(), &args, signing_key) {
+        if check_git_available() {
+            check_signing_key(&git_ops, signing_key)?;
+            debug!("Committing with the git binary for {}", reason);
+            let mut git_args = vec!["commit".to_string()];
+            if let Some(msg) = message {
+                git_args.push("-m".to_string());
+                git_args.push(msg);
+            }
+            if amend {
+                git_args.push("--amend".to_string());
+            }
+            if all {
+                git_args.push("-a".to_string());
+            }
+            git_args.extend(overrides.git_args());
+            if let Some(key) = signing_key {
+                git_args.extend(commit_sign_args(key));
+            }
+            git_args.extend(args);
+            return git_passthrough::execute(git_args);
+        }
+        fallback(reason)?;
+    }
+
+    if all {
+        git_ops.stage_tracked()?;
+    }
+    let oid = if amend {
+        git_ops.amend_index(message.as_deref(), overrides)?
+    } else {
+        git_ops.commit_index(message.as_deref().unwrap_or_default(), overrides)?
+    };
+    report(&git_ops, oid);
+    Ok(())
+}
+
+/// Commit the already staged index with `message`, as the `cc` flow does
+pub fn commit_staged(message: &str, overrides: &CommitOverrides) -> Result<()> {
+    warn_dirty_submodules();
+    let git_ops = GitOperations::open(".")?;
+    let config = Config::load()?;
+    let signing_key = config.security.signing_key();
+
+    if let Some(reason) = needs_git(&git_ops, true, &[], signing_key) {
+        if check_git_available() {
+            check_signing_key(&git_ops, signing_key)?;
+            debug!("Committing with the git binary for {}", reason);
+            let mut git_args = vec!["commit".to_string(), "-m".to_string(), message.to_string()];
+            git_args.extend(overrides.git_args());
+            if let Some(key) = signing_key {
+                git_args.extend(commit_sign_args(key));
+            }
+            return git_passthrough::execute(git_args);
+        }
+        fallback(reason)?;
+    }
+
+    let oid = git_ops.commit_index(message, overrides)?;
+    report(&git_ops, oid);
+    Ok(())
+}
+
+/// Fail with how to set a key up when commits are to be signed with SSH but
+/// no key is configured, which git can't recover from; GPG falls back to the
+/// committer's identity
+fn check_signing_key(git_ops: &GitOperations, signing_key: Option<&str>) -> Result<()> {
+    if signing_key.is_none()
+        && git_ops.signs_commits()
+        && SigningFormat::of(git_ops.inner()) == SigningFormat::Ssh
+        && git_signing_key(git_ops.inner()).is_none()
+    {
+        return Err(MultiGitError::config(format!(
+            "commit.gpgsign asks for signed commits, but no SSH signing key is configured\n{}",
+            remediation()
+        )));
+    }
+    Ok(())
+}
+
+/// Carry on with git2 when `reason` allows it, as the binary is missing
+fn fallback(reason: NeedsGit) -> Result<()> {
+    if reason.is_required() {
+        return Err(MultiGitError::other(format!(
+            "The git binary is needed for {reason}, but it isn't installed"
+        )));
+    }
+    warn!("git is not installed; committing without {}", reason);
+    println!(
+        "{} git is not installed; committing without {reason}",
+        Status::Warning.icon()
+    );
+    Ok(())
+}
+
+/// Print the new commit the way `git commit` does
+fn report(git_ops: &GitOperations, oid: git2::Oid) {
+    let branch = git_ops
+        .current_branch()
+        .unwrap_or_else(|_| "detached HEAD".to_string());
+    let summary = git_ops
+        .find_commit(oid)
+        .ok()
+        .and_then(|commit| commit.summary().map(String::from))
+        .unwrap_or_default();
+    println!("[{branch} {}] {summary}", &oid.to_string()[..7]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_needs_git() {
+        let temp_dir = TempDir::new().unwrap();
+        let git_ops = GitOperations::init(temp_dir.path()).unwrap();
+
+        assert_eq!(needs_git(&git_ops, true, &[], None), None);
+        assert_eq!(
+            needs_git(&git_ops, false, &[], None),
+            Some(NeedsGit::Editor)
+        );
+        let args = vec!["--no-verify".to_string()];
+        assert_eq!(
+            needs_git(&git_ops, true, &args, None),
+            Some(NeedsGit::Arguments)
+        );
+        assert_eq!(
+            needs_git(&git_ops, true, &[], Some("ABCD")),
+            Some(NeedsGit::Signing)
+        );
+
+        let hooks = temp_dir.path().join(".git/hooks");
+        std::fs::create_dir_all(&hooks).unwrap();
+        std::fs::write(hooks.join("pre-commit"), "").unwrap();
+        assert_eq!(needs_git(&git_ops, true, &[], None), Some(NeedsGit::Hooks));
+        assert!(!NeedsGit::Hooks.is_required());
+
+        let mut config = git_ops.inner().config().unwrap();
+        config.set_bool("commit.gpgsign", true).unwrap();
+        assert_eq!(
+            needs_git(&git_ops, true, &[], None),
+            Some(NeedsGit::Signing)
+        );
+        assert!(fallback(NeedsGit::Signing).is_err());
+
+        assert!(check_signing_key(&git_ops, None).is_ok());
+        config.set_str("gpg.format", "ssh").unwrap();
+        let err = check_signing_key(&git_ops, None).unwrap_err().to_string();
+        assert!(err.contains("signing_key"));
+        assert!(check_signing_key(&git_ops, Some("~/.ssh/id_ed25519.pub")).is_ok());
+    }
+}
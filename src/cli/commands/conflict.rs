@@ -7,7 +7,9 @@ use crate::cli::interactive;
 use crate::core::config::Config;
 use crate::core::conflict_resolver::{Conflict, ConflictResolver, Resolution, ResolutionStrategy};
 use crate::git::operations::GitOperations;
+use crate::ui::formatter::{colors, Status};
 use crate::utils::error::{MultiGitError, Result};
+use git2::{IndexConflict, Repository};
 use std::path::Path;
 use tracing::{debug, info, warn};
 
@@ -49,14 +51,14 @@ pub fn detect_conflicts() -> Result<()> {
                 match git_ops.compare_with_remote(remote_name, &current_branch) {
                     Ok((ahead, behind)) => {
                         let status = if ahead == 0 && behind == 0 {
-                            "✓ In sync"
+                            format!("{} In sync", Status::Success.colored_icon())
                         } else if ahead > 0 && behind > 0 {
                             conflicts_found = true;
-                            "⚠ CONFLICT: Diverged"
+                            format!("{} CONFLICT: Diverged", Status::Warning.colored_icon())
                         } else if ahead > 0 {
-                            "↑ Ahead"
+                            format!("{} Ahead", colors::info("↑", colors::enabled()))
                         } else {
-                            "↓ Behind"
+                            format!("{} Behind", colors::info("↓", colors::enabled()))
                         };
 
                         println!("  {status} {remote_name}");
@@ -67,7 +69,8 @@ pub fn detect_conflicts() -> Result<()> {
 
                             if ahead > 0 && behind > 0 {
                                 println!(
-                                    "      ⚠️  Branches have diverged - manual resolution required"
+                                    "      {} Branches have diverged - manual resolution required",
+                                    Status::Warning.colored_icon()
                                 );
                             }
                             println!();
@@ -82,7 +85,10 @@ pub fn detect_conflicts() -> Result<()> {
                     }
                     Err(e) => {
                         warn!("Could not compare with remote {}: {}", remote_name, e);
-                        println!("  ✗ {remote_name} (error: {e})");
+                        println!(
+                            "  {} {remote_name} (error: {e})",
+                            Status::Error.colored_icon()
+                        );
                     }
                 }
             }
@@ -138,7 +144,10 @@ pub fn resolve_conflicts(strategy: ResolutionStrategy) -> Result<()> {
         if let Ok((ahead, behind)) = git_ops.compare_with_remote(remote_name, &current_branch) {
             if ahead > 0 && behind > 0 {
                 conflicts.push(Conflict::new(current_branch.clone(), ahead, behind));
-                println!("  ⚠ Conflict detected with '{remote_name}'");
+                println!(
+                    "  {} Conflict detected with '{remote_name}'",
+                    Status::Warning.colored_icon()
+                );
                 println!("      {ahead} commits ahead, {behind} commits behind");
             }
         }
@@ -202,6 +211,135 @@ pub fn resolve_conflicts(strategy: ResolutionStrategy) -> Result<()> {
     Ok(())
 }
 
+/// Side of a conflicted file kept by a non-interactive resolution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictSide {
+    /// The checked-out branch (index stage 2)
+    Ours,
+    /// The branch being merged in (index stage 3)
+    Theirs,
+}
+
+impl ConflictSide {
+    /// Side matching a strategy from [`parse_strategy`]
+    ///
+    /// `ours`/`fast-forward` and `force` keep the local side, `theirs` and
+    /// `primary` the incoming one. `manual` needs a person and is rejected.
+    pub fn for_strategy(strategy: ResolutionStrategy) -> Result<Self> {
+        match strategy {
+            ResolutionStrategy::FastForwardOnly | ResolutionStrategy::Force => Ok(Self::Ours),
+            ResolutionStrategy::PreferRemote => Ok(Self::Theirs),
+            ResolutionStrategy::Manual => Err(MultiGitError::conflict(
+                "The 'manual' strategy cannot be used with --non-interactive; \
+                 choose 'ours' or 'theirs', or run 'multigit conflict resolve' in a terminal",
+            )),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Ours => "ours",
+            Self::Theirs => "theirs",
+        }
+    }
+}
+
+/// A conflicted file resolved without user input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedFile {
+    /// Path relative to the repository root
+    pub path: String,
+    /// Whether the kept side deleted the file
+    pub deleted: bool,
+}
+
+/// Resolve every conflicted file in the index by keeping one side
+///
+/// The kept side's blob is written to the working tree and staged, replacing
+/// the conflict stages. Where that side deleted the file, it is removed.
+pub fn resolve_index_conflicts(repo: &Repository, side: ConflictSide) -> Result<Vec<ResolvedFile>> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| MultiGitError::other("Repository has no working directory"))?;
+    let mut index = repo.index()?;
+    let conflicts = index
+        .conflicts()?
+        .collect::<std::result::Result<Vec<IndexConflict>, _>>()?;
+
+    let mut resolved = Vec::new();
+    for conflict in conflicts {
+        let kept = match side {
+            ConflictSide::Ours => &conflict.our,
+            ConflictSide::Theirs => &conflict.their,
+        };
+        let path = match conflict
+            .our
+            .as_ref()
+            .or(conflict.their.as_ref())
+            .or(conflict.ancestor.as_ref())
+        {
+            Some(entry) => String::from_utf8_lossy(&entry.path).into_owned(),
+            None => continue,
+        };
+        let relative = Path::new(&path);
+
+        index.conflict_remove(relative)?;
+        if let Some(entry) = kept {
+            let blob = repo.find_blob(entry.id)?;
+            let target = workdir.join(relative);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&target, blob.content())?;
+            index.add_path(relative)?;
+        } else {
+            let target = workdir.join(relative);
+            if target.exists() {
+                std::fs::remove_file(&target)?;
+            }
+        }
+
+        debug!("Resolved {} with {}", path, side.as_str());
+        resolved.push(ResolvedFile {
+            path,
+            deleted: kept.is_none(),
+        });
+    }
+
+    index.write()?;
+    Ok(resolved)
+}
+
+/// Resolve conflicted files with a fixed strategy, without a terminal
+///
+/// Intended for CI: `multigit conflict resolve --strategy theirs --non-interactive`.
+pub fn resolve_non_interactive(strategy: ResolutionStrategy) -> Result<()> {
+    let side = ConflictSide::for_strategy(strategy)?;
+    let repo = Repository::open(".")?;
+
+    let resolved = resolve_index_conflicts(&repo, side)?;
+    if resolved.is_empty() {
+        interactive::print_info("No conflicted files");
+        return Ok(());
+    }
+
+    for file in &resolved {
+        let icon = Status::Success.colored_icon();
+        if file.deleted {
+            println!("  {icon} {} (deleted, {})", file.path, side.as_str());
+        } else {
+            println!("  {icon} {} ({})", file.path, side.as_str());
+        }
+    }
+    interactive::print_success(&format!(
+        "Resolved {} file(s) using '{}'. Run 'git commit' to conclude the merge.",
+        resolved.len(),
+        side.as_str()
+    ));
+
+    Ok(())
+}
+
 /// Set the primary remote for conflict resolution
 pub fn set_primary_remote(remote_name: String) -> Result<()> {
     info!("Setting primary remote: {}", remote_name);
@@ -293,6 +431,7 @@ struct RemoteState {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::git::test_support::{self, CONTENT_CONFLICT};
 
     #[test]
     fn test_parse_strategy() {
@@ -314,4 +453,64 @@ mod tests {
         ));
         assert!(parse_strategy("invalid").is_err());
     }
+
+    /// Repository with `file.txt` conflicted and `gone.txt` deleted on their side
+    fn conflicted_repo() -> (tempfile::TempDir, Repository) {
+        let mut entries = CONTENT_CONFLICT.to_vec();
+        entries.extend([("gone.txt", 1, "base\n"), ("gone.txt", 2, "kept\n")]);
+        test_support::conflicted_repo(
+            &entries,
+            &[("file.txt", "<<<<<<< conflicted\n"), ("gone.txt", "kept\n")],
+        )
+    }
+
+    #[test]
+    fn test_side_for_strategy() {
+        assert_eq!(
+            ConflictSide::for_strategy(parse_strategy("ours").unwrap()).unwrap(),
+            ConflictSide::Ours
+        );
+        assert_eq!(
+            ConflictSide::for_strategy(parse_strategy("theirs").unwrap()).unwrap(),
+            ConflictSide::Theirs
+        );
+        assert!(ConflictSide::for_strategy(ResolutionStrategy::Manual).is_err());
+    }
+
+    #[test]
+    fn test_resolve_index_conflicts_theirs() {
+        let (dir, repo) = conflicted_repo();
+
+        let resolved = resolve_index_conflicts(&repo, ConflictSide::Theirs).unwrap();
+        assert_eq!(resolved.len(), 2);
+        assert!(resolved
+            .iter()
+            .any(|file| file.path == "gone.txt" && file.deleted));
+
+        let mut index = repo.index().unwrap();
+        index.read(true).unwrap();
+        assert!(!index.has_conflicts());
+        assert!(index.get_path(Path::new("gone.txt"), 0).is_none());
+        assert!(!dir.path().join("gone.txt").exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("file.txt")).unwrap(),
+            "theirs\n"
+        );
+    }
+
+    #[test]
+    fn test_resolve_index_conflicts_ours() {
+        let (dir, repo) = conflicted_repo();
+
+        let resolved = resolve_index_conflicts(&repo, ConflictSide::Ours).unwrap();
+        assert!(resolved.iter().all(|file| !file.deleted));
+
+        let index = repo.index().unwrap();
+        let entry = index.get_path(Path::new("gone.txt"), 0).unwrap();
+        assert_eq!(repo.find_blob(entry.id).unwrap().content(), b"kept\n");
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("file.txt")).unwrap(),
+            "ours\n"
+        );
+    }
 }
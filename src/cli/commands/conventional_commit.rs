@@ -2,13 +2,15 @@
 //!
 //! Interactive tool for creating well-formatted conventional commits.
 
+use crate::cli::commands::commit;
 use crate::cli::interactive;
+use crate::git::identity::CommitOverrides;
 use crate::git::operations::GitOperations;
+use crate::ui::formatter::Status;
 use crate::utils::error::{MultiGitError, Result};
 use dialoguer::{theme::ColorfulTheme, Confirm, Editor, Input, MultiSelect, Select};
 use std::collections::HashSet;
-use std::path::PathBuf;
-use std::process::Command;
+use std::path::{Path, PathBuf};
 
 /// Conventional commit types
 const COMMIT_TYPES: &[(&str, &str)] = &[
@@ -53,8 +55,10 @@ const COMMON_SCOPES: &[&str] = &[
     "test",
 ];
 
-/// Execute conventional commit workflow
-pub fn execute() -> Result<()> {
+/// Execute conventional commit workflow, recording the author as
+/// `overrides` says
+pub fn execute(overrides: &CommitOverrides) -> Result<()> {
+    overrides.validate()?;
     println!("\n🎯 Conventional Commit Helper\n");
 
     // Check if we're in a git repository
@@ -79,7 +83,7 @@ pub fn execute() -> Result<()> {
     }
 
     // Stage the selected files
-    stage_files(&selected_files)?;
+    stage_files(&git_ops, &selected_files)?;
 
     // Step 2: Select commit type
     let commit_type = select_commit_type()?;
@@ -129,8 +133,8 @@ pub fn execute() -> Result<()> {
 
         if edit {
             if let Some(edited) = Editor::new().edit(&commit_message)? {
-                commit_with_message(&edited)?;
-                interactive::print_success("✅ Commit created successfully!");
+                commit::commit_staged(&edited, overrides)?;
+                interactive::print_success("Commit created successfully!");
                 return Ok(());
             }
             interactive::print_info("Commit cancelled.");
@@ -141,8 +145,8 @@ pub fn execute() -> Result<()> {
     }
 
     // Commit
-    commit_with_message(&commit_message)?;
-    interactive::print_success("✅ Commit created successfully!");
+    commit::commit_staged(&commit_message, overrides)?;
+    interactive::print_success("Commit created successfully!");
 
     Ok(())
 }
@@ -206,7 +210,11 @@ fn select_files_to_stage(files: &[(String, String)]) -> Result<Vec<String>> {
 
     if quick_choice == 0 {
         // All files selected
-        println!("\n✅ All {} file(s) will be committed\n", files.len());
+        println!(
+            "\n{} All {} file(s) will be committed\n",
+            Status::Success.icon(),
+            files.len()
+        );
         for (path, status) in files {
             println!("  [{status}] {path}");
         }
@@ -234,15 +242,10 @@ fn select_files_to_stage(files: &[(String, String)]) -> Result<Vec<String>> {
     Ok(selected)
 }
 
-/// Stage files using git add
-fn stage_files(files: &[String]) -> Result<()> {
-    for file in files {
-        Command::new("git")
-            .args(["add", file])
-            .output()
-            .map_err(|e| MultiGitError::other(format!("Failed to stage {file}: {e}")))?;
-    }
-    Ok(())
+/// Stage the selected files, as `git add` would
+fn stage_files(git_ops: &GitOperations, files: &[String]) -> Result<()> {
+    let paths: Vec<&Path> = files.iter().map(Path::new).collect();
+    git_ops.stage_paths(&paths)
 }
 
 /// Let user select commit type
@@ -485,18 +488,3 @@ fn build_commit_message(
 
     message
 }
-
-/// Commit with the given message
-fn commit_with_message(message: &str) -> Result<()> {
-    let output = Command::new("git")
-        .args(["commit", "-m", message])
-        .output()
-        .map_err(|e| MultiGitError::other(format!("Failed to commit: {e}")))?;
-
-    if !output.status.success() {
-        let error = String::from_utf8_lossy(&output.stderr);
-        return Err(MultiGitError::other(format!("Commit failed: {error}")));
-    }
-
-    Ok(())
-}
@@ -3,19 +3,76 @@
 //! Create repositories on all configured platforms.
 
 use crate::core::auth::{AuthBackend, AuthManager};
-use crate::providers::traits::{Provider, RepoConfig};
-use crate::providers::{
-    bitbucket::BitbucketProvider, github::GitHubProvider, gitlab::GitLabProvider,
-};
+use crate::core::config::Config;
+use crate::providers::factory::{create_provider_with_options, get_provider_host};
+use crate::providers::traits::{Protocol, Provider, RepoConfig};
+use crate::security::audit::{AuditEntry, AuditEventType, AuditLogger};
+use crate::ui::formatter::Status;
 use crate::utils::error::{MultiGitError, Result};
 use dialoguer::{Confirm, Input};
-use tracing::info;
+use futures::future::join_all;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Outcome of creating a repository on several providers
+#[derive(Debug, Default)]
+pub struct CreateReport {
+    /// Providers the repository now exists on
+    pub created: Vec<String>,
+    /// Providers where creation failed
+    pub failed: Vec<String>,
+    /// Providers where the repository was created and then deleted again
+    pub rolled_back: Vec<String>,
+}
+
+/// What `create` would do on one provider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlannedAction {
+    /// The repository doesn't exist yet and would be created
+    Create,
+    /// A repository with that name already exists; creating it would fail
+    AlreadyExists,
+    /// Whether the repository exists couldn't be determined
+    Unknown(String),
+}
+
+/// Dry-run result for one provider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreatePlan {
+    /// Remote name
+    pub remote: String,
+    /// What would happen
+    pub action: PlannedAction,
+    /// URL of the new (or existing) repository
+    pub url: String,
+    /// Visibility it would have (or has, if it already exists)
+    pub private: bool,
+}
 
 /// Create a repository on all configured platforms
-pub async fn execute(name: String, description: Option<String>, private: bool) -> Result<()> {
+///
+/// `private` is the visibility chosen with `--private`/`--public`; without
+/// either, `settings.default_private` decides. With `rollback`, a failure on
+/// any platform deletes the repositories already created by this run, and the
+/// command fails. With `dry_run`, only the per-provider plan is printed.
+pub async fn execute(
+    name: String,
+    description: Option<String>,
+    private: Option<bool>,
+    rollback: bool,
+    dry_run: bool,
+) -> Result<()> {
     info!("Creating repository: {}", name);
 
-    println!("\n📦 Creating repository '{name}' on all platforms...\n");
+    // Load config to get configured remotes
+    let config = Config::load().unwrap_or_default();
+    let private = private.unwrap_or(config.settings.default_private);
+
+    if dry_run {
+        println!("\n📋 Planning repository '{name}' on all platforms (dry run)...\n");
+    } else {
+        println!("\n📦 Creating repository '{name}' on all platforms...\n");
+    }
 
     // Get repository configuration
     let repo_config = RepoConfig {
@@ -24,103 +81,50 @@ pub async fn execute(name: String, description: Option<String>, private: bool) -
         private,
     };
 
-    // Load config to get configured remotes
-    let config = crate::core::config::Config::load().unwrap_or_default();
+    let providers = configured_providers(&config);
 
-    // Initialize auth manager
-    let auth_manager = AuthManager::new(AuthBackend::Keyring, false);
-    let allow_env = config.security.allow_env_tokens;
+    if providers.is_empty() {
+        println!(
+            "{} No remotes with usable credentials.",
+            Status::Warning.icon()
+        );
+        println!("Use 'multigit remote add <provider> <username>' to configure remotes.");
+        return Ok(());
+    }
 
-    // Track results
-    let mut results = Vec::new();
-
-    // Try to create on GitHub
-    if let Some(github_config) = config.remotes.get("github") {
-        if let Ok(token) = auth_manager.retrieve_credential(
-            "github",
-            "github.com",
-            &github_config.username,
-            allow_env,
-        ) {
-            match create_on_github(&token, &github_config.username, &repo_config).await {
-                Ok(url) => {
-                    println!("✓ GitHub: Created successfully");
-                    println!("  URL: {url}");
-                    results.push(("github", true));
-                }
-                Err(e) => {
-                    println!("✗ GitHub: Failed - {e}");
-                    results.push(("github", false));
-                }
-            }
-        } else {
-            println!("⊘ GitHub: Credentials not found");
-        }
-    } else {
-        println!("⊘ GitHub: Not configured (run 'multigit remote add github <username>')");
-    }
-
-    // Try to create on GitLab
-    if let Some(gitlab_config) = config.remotes.get("gitlab") {
-        let host = gitlab_config
-            .api_url
-            .as_deref()
-            .and_then(|url| crate::utils::validation::extract_host_from_url(url).ok())
-            .unwrap_or_else(|| "gitlab.com".to_string());
-        if let Ok(token) =
-            auth_manager.retrieve_credential("gitlab", &host, &gitlab_config.username, allow_env)
-        {
-            match create_on_gitlab(&token, &gitlab_config.username, &repo_config).await {
-                Ok(url) => {
-                    println!("✓ GitLab: Created successfully");
-                    println!("  URL: {url}");
-                    results.push(("gitlab", true));
-                }
-                Err(e) => {
-                    println!("✗ GitLab: Failed - {e}");
-                    results.push(("gitlab", false));
-                }
-            }
-        } else {
-            println!("⊘ GitLab: Credentials not found");
-        }
-    } else {
-        println!("⊘ GitLab: Not configured (run 'multigit remote add gitlab <username>')");
-    }
-
-    // Try to create on Bitbucket
-    if let Some(bitbucket_config) = config.remotes.get("bitbucket") {
-        if let Ok(password) = auth_manager.retrieve_credential(
-            "bitbucket",
-            "bitbucket.org",
-            &bitbucket_config.username,
-            allow_env,
-        ) {
-            match create_on_bitbucket(&bitbucket_config.username, &password, &repo_config).await {
-                Ok(url) => {
-                    println!("✓ Bitbucket: Created successfully");
-                    println!("  URL: {url}");
-                    results.push(("bitbucket", true));
-                }
-                Err(e) => {
-                    println!("✗ Bitbucket: Failed - {e}");
-                    results.push(("bitbucket", false));
-                }
-            }
-        } else {
-            println!("⊘ Bitbucket: Credentials not found");
-        }
-    } else {
-        println!("⊘ Bitbucket: Not configured (run 'multigit remote add bitbucket <username>')");
+    if dry_run {
+        let plans = plan_on_providers(&providers, &repo_config).await;
+        print_plan(&plans, &repo_config);
+        return Ok(());
     }
 
+    let audit = AuditLogger::at_default_path(config.security.audit_log);
+    let report = create_on_providers(&providers, &repo_config, rollback, &audit).await;
+
     // Summary
-    let success_count = results.iter().filter(|(_, success)| *success).count();
-    let total_count = results.len();
+    println!(
+        "\n📊 Summary: Created on {}/{} platforms",
+        report.created.len(),
+        providers.len()
+    );
 
-    println!("\n📊 Summary: Created on {success_count}/{total_count} platforms");
+    if rollback && !report.failed.is_empty() {
+        return Err(MultiGitError::other(format!(
+            "Creating '{name}' failed on {}; rolled back {}",
+            report.failed.join(", "),
+            if report.rolled_back.is_empty() {
+                "nothing".to_string()
+            } else {
+                report.rolled_back.join(", ")
+            }
+        )));
+    }
 
-    if success_count > 0 {
+    if private && !report.created.is_empty() {
+        mark_expect_private();
+    }
+
+    if !report.created.is_empty() {
         println!("\n💡 Next steps:");
         println!("  1. Add git remotes: multigit remote sync");
         println!("  2. Push your code: multigit push all");
@@ -129,38 +133,228 @@ pub async fn execute(name: String, description: Option<String>, private: bool) -
     Ok(())
 }
 
-/// Create repository on GitHub
-async fn create_on_github(token: &str, username: &str, config: &RepoConfig) -> Result<String> {
-    let provider = GitHubProvider::new(token.to_string(), username.to_string())?;
-    let repo = provider
-        .create_repo(config.clone())
-        .await
-        .map_err(|e| MultiGitError::Other(format!("GitHub API error: {e}")))?;
-    Ok(repo.html_url.unwrap_or(repo.url))
+/// Record in `.multigit/config.toml` that this repository must stay private
+///
+/// `status --detailed` and `doctor` then warn if a remote's copy turns public.
+/// Does nothing outside a `MultiGit` repository.
+fn mark_expect_private() {
+    let path = Config::repo_config_path();
+    if !path.exists() {
+        return;
+    }
+
+    let result = Config::load_from_file(&path).and_then(|mut repo_config| {
+        if repo_config.settings.expect_private {
+            return Ok(());
+        }
+        repo_config.settings.expect_private = true;
+        repo_config.save_repo_config()
+    });
+    if let Err(e) = result {
+        warn!(
+            "Could not record expect_private in {}: {}",
+            path.display(),
+            e
+        );
+    }
 }
 
-/// Create repository on GitLab
-async fn create_on_gitlab(token: &str, username: &str, config: &RepoConfig) -> Result<String> {
-    let provider = GitLabProvider::new(token.to_string(), username.to_string(), None)?;
-    let repo = provider
-        .create_repo(config.clone())
-        .await
-        .map_err(|e| MultiGitError::Other(format!("GitLab API error: {e}")))?;
-    Ok(repo.html_url.unwrap_or(repo.url))
+/// Build a provider for every enabled remote with stored credentials
+fn configured_providers(config: &Config) -> Vec<(String, Arc<dyn Provider>)> {
+    let auth_manager = AuthManager::new(AuthBackend::Keyring, config.security.audit_log);
+    let allow_insecure = config.security.allow_insecure_http;
+    let allow_env = config.security.allow_env_tokens;
+
+    let mut providers = Vec::new();
+    for (name, remote) in config.enabled_remotes() {
+        let provider_name = remote.provider_name(&name);
+        let api_url = remote.api_url.as_deref();
+
+        let credential =
+            get_provider_host(provider_name, api_url, allow_insecure).and_then(|host| {
+                auth_manager.retrieve_remote_credential(
+                    &name,
+                    provider_name,
+                    &host,
+                    &remote.username,
+                    allow_env,
+                )
+            });
+
+        if let Ok(token) = credential {
+            match create_provider_with_options(
+                provider_name,
+                &remote.username,
+                &token,
+                api_url,
+                allow_insecure,
+                &config.client_options_for(remote),
+            ) {
+                Ok(provider) => providers.push((name.clone(), provider)),
+                Err(e) => println!("{} {name}: {e}", Status::Skipped.icon()),
+            }
+        } else {
+            println!("{} {name}: Credentials not found", Status::Skipped.icon());
+        }
+    }
+
+    providers
 }
 
-/// Create repository on Bitbucket
-async fn create_on_bitbucket(
-    username: &str,
-    password: &str,
+/// Work out what creating the repository would do on each provider
+///
+/// Looks the repository up on all providers concurrently; nothing is changed.
+pub async fn plan_on_providers(
+    providers: &[(String, Arc<dyn Provider>)],
     config: &RepoConfig,
-) -> Result<String> {
-    let provider = BitbucketProvider::new(username.to_string(), password.to_string())?;
-    let repo = provider
-        .create_repo(config.clone())
-        .await
-        .map_err(|e| MultiGitError::Other(format!("Bitbucket API error: {e}")))?;
-    Ok(repo.html_url.unwrap_or(repo.url))
+) -> Vec<CreatePlan> {
+    let lookups = providers.iter().map(|(name, provider)| async move {
+        let new_url = provider.get_remote_url(&config.name, Protocol::Https);
+        let (action, url, private) = match provider.get_repo(&config.name).await {
+            Ok(existing) => (
+                PlannedAction::AlreadyExists,
+                existing.html_url.unwrap_or(existing.url),
+                existing.private,
+            ),
+            Err(e) if is_not_found(&e) => (PlannedAction::Create, new_url, config.private),
+            Err(e) => (
+                PlannedAction::Unknown(e.to_string()),
+                new_url,
+                config.private,
+            ),
+        };
+
+        CreatePlan {
+            remote: name.clone(),
+            action,
+            url,
+            private,
+        }
+    });
+
+    join_all(lookups).await
+}
+
+/// Whether a `get_repo` error means the repository doesn't exist
+fn is_not_found(error: &anyhow::Error) -> bool {
+    let message = error.to_string();
+    message.contains("404") || message.to_lowercase().contains("not found")
+}
+
+/// Print the dry-run plan, flagging anything that would end up public
+fn print_plan(plans: &[CreatePlan], config: &RepoConfig) {
+    let visibility = |private: bool| if private { "private" } else { "public" };
+
+    for plan in plans {
+        match &plan.action {
+            PlannedAction::Create => {
+                println!(
+                    "+ {}: would create ({})",
+                    plan.remote,
+                    visibility(plan.private)
+                );
+            }
+            PlannedAction::AlreadyExists => {
+                println!(
+                    "= {}: already exists ({}), creation would fail",
+                    plan.remote,
+                    visibility(plan.private)
+                );
+            }
+            PlannedAction::Unknown(reason) => {
+                println!("? {}: could not check ({reason})", plan.remote);
+            }
+        }
+        println!("  URL: {}", plan.url);
+    }
+
+    let creates = plans
+        .iter()
+        .filter(|plan| plan.action == PlannedAction::Create)
+        .count();
+    println!(
+        "\n📊 Plan: create on {creates}/{} platforms, visibility {}",
+        plans.len(),
+        visibility(config.private)
+    );
+    if !config.private && creates > 0 {
+        println!(
+            "{} The repository would be PUBLIC. Pass --private to keep it private.",
+            Status::Warning.icon()
+        );
+    }
+    println!("\n[DRY RUN] No changes were made.");
+}
+
+/// Create the repository on each provider in turn
+///
+/// With `rollback`, the repositories created so far are deleted (most recent
+/// first) if any provider fails. Each creation and deletion goes to `audit`.
+pub async fn create_on_providers(
+    providers: &[(String, Arc<dyn Provider>)],
+    config: &RepoConfig,
+    rollback: bool,
+    audit: &AuditLogger,
+) -> CreateReport {
+    let mut report = CreateReport::default();
+
+    for (name, provider) in providers {
+        let created = provider.create_repo(config.clone()).await;
+        audit_repo_change(audit, AuditEventType::RepoCreate, name, config, &created);
+        match created {
+            Ok(repo) => {
+                println!("{} {name}: Created successfully", Status::Success.icon());
+                println!("  URL: {}", repo.html_url.unwrap_or(repo.url));
+                report.created.push(name.clone());
+            }
+            Err(e) => {
+                println!("{} {name}: Failed - {e}", Status::Error.icon());
+                report.failed.push(name.clone());
+            }
+        }
+    }
+
+    if !rollback || report.failed.is_empty() || report.created.is_empty() {
+        return report;
+    }
+
+    println!("\n↩ Rolling back repositories created by this run...");
+    let created = std::mem::take(&mut report.created);
+    for (name, provider) in providers.iter().rev() {
+        if !created.contains(name) {
+            continue;
+        }
+        let deleted = provider.delete_repo(&config.name).await;
+        audit_repo_change(audit, AuditEventType::RepoDelete, name, config, &deleted);
+        match deleted {
+            Ok(()) => {
+                println!("  {} {name}: Deleted", Status::Success.icon());
+                report.rolled_back.push(name.clone());
+            }
+            Err(e) => {
+                warn!("Rollback failed on {}: {}", name, e);
+                println!("  {} {name}: Could not delete - {e}", Status::Error.icon());
+                report.created.push(name.clone());
+            }
+        }
+    }
+
+    report
+}
+
+fn audit_repo_change<T>(
+    audit: &AuditLogger,
+    event: AuditEventType,
+    remote: &str,
+    config: &RepoConfig,
+    outcome: &anyhow::Result<T>,
+) {
+    let entry = AuditEntry::new(event, format!("{remote}:{}", config.name), outcome.is_ok());
+    let entry = match outcome {
+        Ok(_) => entry,
+        Err(e) => entry.with_message(e.to_string()),
+    };
+    audit.log(entry);
 }
 
 /// Interactive repository creation
@@ -178,17 +372,162 @@ pub async fn execute_interactive() -> Result<()> {
         .interact_text()
         .map_err(|e| MultiGitError::Other(format!("Input error: {e}")))?;
 
+    let default_private = Config::load().unwrap_or_default().settings.default_private;
     let private = Confirm::new()
         .with_prompt("Make repository private?")
-        .default(true)
+        .default(default_private)
         .interact()
         .map_err(|e| MultiGitError::Other(format!("Input error: {e}")))?;
 
+    if !private {
+        let confirmed = Confirm::new()
+            .with_prompt("Create a PUBLIC repository? Anyone will be able to read its contents")
+            .default(false)
+            .interact()
+            .map_err(|e| MultiGitError::Other(format!("Input error: {e}")))?;
+        if !confirmed {
+            println!("Cancelled; nothing was created.");
+            return Ok(());
+        }
+    }
+
     let desc = if description.is_empty() {
         None
     } else {
         Some(description)
     };
 
-    execute(name, desc, private).await
+    execute(name, desc, Some(private), false, false).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::MockProvider;
+
+    fn repo_config() -> RepoConfig {
+        RepoConfig {
+            name: "demo".to_string(),
+            description: String::new(),
+            private: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_on_all_providers() {
+        let github = Arc::new(MockProvider::new("github"));
+        let gitlab = Arc::new(MockProvider::new("gitlab"));
+        let providers: Vec<(String, Arc<dyn Provider>)> = vec![
+            ("github".to_string(), github.clone()),
+            ("gitlab".to_string(), gitlab.clone()),
+        ];
+
+        let report =
+            create_on_providers(&providers, &repo_config(), true, &AuditLogger::default()).await;
+
+        assert_eq!(report.created, vec!["github", "gitlab"]);
+        assert!(report.failed.is_empty());
+        assert!(report.rolled_back.is_empty());
+        assert_eq!(github.calls(), vec!["create_repo:demo"]);
+    }
+
+    #[tokio::test]
+    async fn test_failure_rolls_back_created_repos() {
+        let github = Arc::new(MockProvider::new("github"));
+        let gitlab = Arc::new(MockProvider::new("gitlab"));
+        let gitea = Arc::new(MockProvider::new("gitea").with_create_error("name taken"));
+        let providers: Vec<(String, Arc<dyn Provider>)> = vec![
+            ("github".to_string(), github.clone()),
+            ("gitlab".to_string(), gitlab.clone()),
+            ("gitea".to_string(), gitea.clone()),
+        ];
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let audit = AuditLogger::new(temp_dir.path().join("audit.log"), true);
+        let report = create_on_providers(&providers, &repo_config(), true, &audit).await;
+
+        assert!(report.created.is_empty());
+        assert_eq!(report.failed, vec!["gitea"]);
+        assert_eq!(report.rolled_back, vec!["gitlab", "github"]);
+        assert_eq!(github.calls(), vec!["create_repo:demo", "delete_repo:demo"]);
+        assert_eq!(gitea.calls(), vec!["create_repo:demo"]);
+
+        let events: Vec<(AuditEventType, String, bool)> = audit
+            .read_entries()
+            .unwrap()
+            .into_iter()
+            .map(|entry| (entry.event_type, entry.resource, entry.success))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                (AuditEventType::RepoCreate, "github:demo".to_string(), true),
+                (AuditEventType::RepoCreate, "gitlab:demo".to_string(), true),
+                (AuditEventType::RepoCreate, "gitea:demo".to_string(), false),
+                (AuditEventType::RepoDelete, "gitlab:demo".to_string(), true),
+                (AuditEventType::RepoDelete, "github:demo".to_string(), true),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_rollback_keeps_repo_as_created() {
+        let github = Arc::new(MockProvider::new("github").with_delete_error("forbidden"));
+        let gitlab = Arc::new(MockProvider::new("gitlab").with_create_error("quota exceeded"));
+        let providers: Vec<(String, Arc<dyn Provider>)> = vec![
+            ("github".to_string(), github.clone()),
+            ("gitlab".to_string(), gitlab),
+        ];
+
+        let report =
+            create_on_providers(&providers, &repo_config(), true, &AuditLogger::default()).await;
+
+        assert_eq!(report.created, vec!["github"]);
+        assert!(report.rolled_back.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_plan_reports_new_and_existing_repos() {
+        let github = Arc::new(
+            MockProvider::new("github").with_get_repo_error("GitHub API error: 404 Not Found"),
+        );
+        let gitlab = Arc::new(MockProvider::new("gitlab"));
+        let gitea = Arc::new(MockProvider::new("gitea").with_get_repo_error("connection refused"));
+        let providers: Vec<(String, Arc<dyn Provider>)> = vec![
+            ("github".to_string(), github.clone()),
+            ("gitlab".to_string(), gitlab.clone()),
+            ("gitea".to_string(), gitea),
+        ];
+
+        let plans = plan_on_providers(&providers, &repo_config()).await;
+
+        assert_eq!(plans[0].action, PlannedAction::Create);
+        assert_eq!(plans[0].url, "https://github.example/mock/demo.git");
+        assert!(!plans[0].private);
+        assert_eq!(plans[1].action, PlannedAction::AlreadyExists);
+        assert!(
+            matches!(plans[2].action, PlannedAction::Unknown(ref reason) if reason.contains("refused"))
+        );
+
+        // Nothing was created
+        assert_eq!(github.calls(), vec!["get_repo:demo"]);
+        assert_eq!(gitlab.calls(), vec!["get_repo:demo"]);
+    }
+
+    #[tokio::test]
+    async fn test_no_rollback_without_flag() {
+        let github = Arc::new(MockProvider::new("github"));
+        let gitlab = Arc::new(MockProvider::new("gitlab").with_create_error("quota exceeded"));
+        let providers: Vec<(String, Arc<dyn Provider>)> = vec![
+            ("github".to_string(), github.clone()),
+            ("gitlab".to_string(), gitlab),
+        ];
+
+        let report =
+            create_on_providers(&providers, &repo_config(), false, &AuditLogger::default()).await;
+
+        assert_eq!(report.created, vec!["github"]);
+        assert_eq!(report.failed, vec!["gitlab"]);
+        assert_eq!(github.calls(), vec!["create_repo:demo"]);
+    }
 }
@@ -1,20 +1,53 @@
 //! Daemon management commands
 //!
-//! Start, stop, status, and logs for the background daemon service.
-
-use crate::daemon::service::DaemonService;
-use crate::ui::formatter::{colors, Status};
+//! Start, stop, status, and logs for the background daemon service, and
+//! single runs for external schedulers, with units to install them.
+
+use crate::cli::commands::sync::{self, SyncOptions};
+use crate::core::config::Config;
+use crate::daemon::scheduler::Schedule;
+use crate::daemon::service::{sync_cycle, DaemonService};
+use crate::daemon::state::DaemonState;
+use crate::daemon::unit::{self, ServiceManager, ServicePlan, UnitDirs};
+use crate::ui::formatter::{colors, format_duration, Status};
 use crate::utils::error::{MultiGitError, Result};
+use crate::utils::log_file::log_files_oldest_first;
+use crate::utils::redact::redact;
+use chrono::Utc;
+use serde::Serialize;
 use std::fs;
-use std::io::{BufRead, BufReader};
-use tracing::info;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// How often follow mode checks the log file for new content
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Resolve the daemon schedule from CLI flags, falling back to the `[daemon]` config
+///
+/// `--cron` and `--interval` are mutually exclusive; with neither given, the
+/// config's `cron` (if set) or `interval_seconds` is used.
+pub fn resolve_schedule(interval_minutes: Option<u64>, cron: Option<String>) -> Result<Schedule> {
+    let schedule = match (interval_minutes, cron) {
+        (Some(_), Some(_)) => {
+            return Err(MultiGitError::invalid_input(
+                "--interval and --cron cannot be used together",
+            ))
+        }
+        (None, Some(expression)) => Schedule::from_cron(&expression),
+        (Some(minutes), None) => Ok(Schedule::every_minutes(minutes)),
+        (None, None) => Config::load().unwrap_or_default().daemon.schedule(),
+    };
+
+    schedule.map_err(MultiGitError::invalid_input)
+}
 
 /// Start the daemon
-pub async fn start(interval_minutes: u64) -> Result<()> {
-    info!("Starting daemon with interval: {}m", interval_minutes);
+pub async fn start(schedule: Schedule) -> Result<()> {
+    info!("Starting daemon with schedule: {}", schedule);
 
-    let interval_seconds = interval_minutes * 60;
-    let daemon = DaemonService::new(interval_seconds);
+    let daemon = DaemonService::with_schedule(schedule.clone());
 
     // Check if already running
     if daemon.is_running()? {
@@ -33,7 +66,12 @@ pub async fn start(interval_minutes: u64) -> Result<()> {
     }
 
     println!("\n🚀 Starting MultiGit daemon...");
-    println!("  Interval: {interval_minutes}m ({interval_seconds}s)");
+    println!("  Schedule: {schedule}");
+    if let Some(next) = schedule.next_run_after(&chrono::Local::now()) {
+        if schedule.is_cron() {
+            println!("  Next run: {}", next.format("%Y-%m-%d %H:%M"));
+        }
+    }
 
     // In a real implementation, we'd fork/daemonize here
     // For now, we'll run in foreground (user can use nohup/systemd)
@@ -56,6 +94,195 @@ pub async fn start(interval_minutes: u64) -> Result<()> {
     Ok(())
 }
 
+/// Print what `daemon start` would do: the next `runs` run times and the
+/// remotes each run would sync, without starting anything
+pub fn dry_run(schedule: &Schedule, runs: usize) -> Result<()> {
+    info!("Previewing daemon schedule: {}", schedule);
+    let config = Config::load()?;
+    let state = DaemonState::load();
+
+    println!("\n🔎 Daemon dry run (nothing is started)\n");
+    println!("  Schedule: {schedule}");
+
+    println!("\n  Next {runs} run(s):");
+    for at in schedule.upcoming_runs(&chrono::Local::now(), runs) {
+        println!("    {}", at.format("%Y-%m-%d %H:%M:%S %Z"));
+    }
+
+    let mut remotes: Vec<String> = config.enabled_remotes().into_keys().collect();
+    remotes.sort();
+    println!("\n  Remotes to sync:");
+    if remotes.is_empty() {
+        println!(
+            "    {}",
+            colors::warning("none; add one with 'multigit remote add'", true)
+        );
+    }
+    let now = Utc::now();
+    for remote in &remotes {
+        match state.backoff.remotes.get(remote) {
+            Some(backoff) if backoff.is_backed_off(now) => {
+                let retry_in = backoff
+                    .next_attempt
+                    .map_or(0, |next| (next - now).num_seconds().unsigned_abs());
+                println!(
+                    "    {} {remote} (backed off, next attempt in {})",
+                    colors::warning("⏸", true),
+                    format_duration(retry_in)
+                );
+            }
+            _ => println!(
+                "    {} {remote}",
+                colors::success(Status::Success.icon(), true)
+            ),
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Sync once in the foreground, for an external scheduler such as a systemd
+/// timer
+///
+/// Runs the same cycle as the daemon loop (backoff, status file, log) but
+/// syncs each remote in-process, and fails if any remote failed.
+pub async fn run_once(schedule: Schedule) -> Result<()> {
+    info!("[Daemon] Running a single sync");
+    let started = std::time::Instant::now();
+
+    let result = sync_cycle(&schedule, false, |remote| async move {
+        sync::execute(SyncOptions {
+            remotes: vec![remote],
+            ..SyncOptions::default()
+        })
+        .await
+        .map_err(|e| redact(&e.to_string()))
+    })
+    .await;
+
+    let elapsed = format_duration(started.elapsed().as_secs());
+    match result {
+        Ok(()) => {
+            info!("[Daemon] Single sync finished in {}", elapsed);
+            println!(
+                "\n{}",
+                Status::Success.format(&format!("Daemon run finished in {elapsed}"), true)
+            );
+            Ok(())
+        }
+        Err(e) => {
+            warn!("[Daemon] Single sync failed: {}", e);
+            Err(MultiGitError::daemon(e.to_string()))
+        }
+    }
+}
+
+/// Install a unit that runs `daemon run-once` for this repository on
+/// `schedule` from the OS scheduler, or with `print_only` just show it
+pub fn install_service(schedule: &Schedule, print_only: bool) -> Result<()> {
+    let plan = service_plan(schedule)?;
+
+    if print_only {
+        print_plan(&plan, &plan.install);
+        return Ok(());
+    }
+
+    println!("\n⚙️  Installing {} unit '{}'\n", plan.manager, plan.name);
+    for file in &plan.files {
+        if let Some(parent) = file.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&file.path, &file.contents)?;
+        println!("  Wrote {}", file.path.display());
+    }
+    for command in &plan.install {
+        run_command(command)?;
+    }
+
+    println!(
+        "\n{}",
+        Status::Success.format(&format!("Syncing {schedule} via {}", plan.manager), true)
+    );
+    println!("  Remove it with: multigit daemon uninstall-service");
+    Ok(())
+}
+
+/// Disable and remove this repository's unit, or with `print_only` show what
+/// would be removed
+pub fn uninstall_service(print_only: bool) -> Result<()> {
+    let plan = service_plan(&Schedule::default())?;
+
+    if print_only {
+        print_plan(&plan, &plan.uninstall);
+        return Ok(());
+    }
+
+    println!("\n⚙️  Removing {} unit '{}'\n", plan.manager, plan.name);
+    for command in &plan.uninstall {
+        // Already stopped or never loaded: carry on and remove the files
+        if let Err(e) = run_command(command) {
+            println!("  {}", Status::Warning.format(&e.to_string(), true));
+        }
+    }
+    for file in &plan.files {
+        if file.path.exists() {
+            fs::remove_file(&file.path)?;
+            println!("  Removed {}", file.path.display());
+        }
+    }
+    if plan.manager == ServiceManager::Systemd {
+        run_command(&["systemctl", "--user", "daemon-reload"].map(String::from))?;
+    }
+
+    println!("\n{}", Status::Success.format("Service removed", true));
+    Ok(())
+}
+
+/// The unit for the repository in the current directory
+fn service_plan(schedule: &Schedule) -> Result<ServicePlan> {
+    let workdir = std::env::current_dir()?;
+    crate::git::operations::GitOperations::open(&workdir)
+        .map_err(|_| MultiGitError::daemon("Run this inside the repository to sync"))?;
+    let exe = std::env::current_exe()?;
+    unit::plan(
+        ServiceManager::current(),
+        &exe,
+        &workdir,
+        schedule,
+        &UnitDirs::for_user()?,
+    )
+}
+
+/// Show each file and the commands that would be run
+fn print_plan(plan: &ServicePlan, commands: &[Vec<String>]) {
+    for file in &plan.files {
+        println!("# {}", file.path.display());
+        println!("{}", file.contents);
+    }
+    println!("# {} commands", plan.manager);
+    for command in commands {
+        println!("{}", command.join(" "));
+    }
+}
+
+fn run_command(command: &[String]) -> Result<()> {
+    println!("  $ {}", command.join(" "));
+    let output = std::process::Command::new(&command[0])
+        .args(&command[1..])
+        .output()
+        .map_err(|e| MultiGitError::daemon(format!("Failed to run {}: {e}", command[0])))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(MultiGitError::daemon(format!(
+            "{} failed: {}",
+            command.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
 /// Stop the daemon
 pub fn stop() -> Result<()> {
     info!("Stopping daemon");
@@ -83,17 +310,44 @@ pub fn stop() -> Result<()> {
     Ok(())
 }
 
+/// Daemon status as printed by `daemon status --json`
+#[derive(Serialize)]
+struct StatusReport {
+    running: bool,
+    pid: Option<u32>,
+    log_file: Option<std::path::PathBuf>,
+    #[serde(flatten)]
+    state: DaemonState,
+}
+
 /// Show daemon status
-pub fn status() -> Result<()> {
+pub fn status(json: bool) -> Result<()> {
     info!("Checking daemon status");
 
     let daemon = DaemonService::new(300);
     let status = daemon.status()?;
+    let state = DaemonState::load();
+
+    if json {
+        let report = StatusReport {
+            running: status.running,
+            pid: status.pid,
+            log_file: status.log_file,
+            state,
+        };
+        let output = serde_json::to_string_pretty(&report)
+            .map_err(|e| MultiGitError::other(format!("Failed to serialize status: {e}")))?;
+        println!("{output}");
+        return Ok(());
+    }
 
     println!("\n📊 Daemon Status:\n");
 
     if status.running {
-        println!("  Status: {}", colors::success("✓ Running", true));
+        println!(
+            "  Status: {}",
+            colors::success(&format!("{} Running", Status::Success.icon()), true)
+        );
 
         if let Some(pid) = status.pid {
             println!("  PID: {}", colors::info(&pid.to_string(), true));
@@ -102,8 +356,14 @@ pub fn status() -> Result<()> {
         if let Some(log_file) = status.log_file {
             println!("  Log file: {}", log_file.display());
         }
+
+        show_run_times(&state);
+        show_backed_off_remotes(&state);
     } else {
-        println!("  Status: {}", colors::warning("✗ Not running", true));
+        println!(
+            "  Status: {}",
+            colors::warning(&format!("{} Not running", Status::Error.icon()), true)
+        );
         println!(
             "\n  Start with: {}",
             colors::dim("multigit daemon start", true)
@@ -115,8 +375,66 @@ pub fn status() -> Result<()> {
     Ok(())
 }
 
-/// Show daemon logs
-pub fn logs(lines: usize) -> Result<()> {
+/// Show when the daemon last synced and when it runs next
+fn show_run_times(state: &DaemonState) {
+    let now = Utc::now();
+
+    let last_sync = state.seconds_since_last_sync(now).map_or_else(
+        || "never".to_string(),
+        |secs| format!("{} ago", format_duration(secs.unsigned_abs())),
+    );
+    println!("  Last sync: {last_sync}");
+
+    if let Some(secs) = state.seconds_until_next_run(now) {
+        println!("  Next run: in {}", format_duration(secs.unsigned_abs()));
+    }
+
+    let failed: Vec<&str> = state
+        .remotes
+        .iter()
+        .filter(|(_, result)| !result.success)
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !failed.is_empty() {
+        let message = format!("failed for {}", failed.join(", "));
+        println!("  Last result: {}", colors::warning(&message, true));
+    } else if !state.remotes.is_empty() {
+        println!(
+            "  Last result: {}",
+            colors::success("all remotes synced", true)
+        );
+    }
+}
+
+/// List remotes the daemon is currently skipping because they keep failing
+fn show_backed_off_remotes(state: &DaemonState) {
+    let now = Utc::now();
+    let backed_off = state.backoff.backed_off(now);
+
+    if backed_off.is_empty() {
+        return;
+    }
+
+    println!("\n  Backed-off remotes:");
+    for (remote, state) in backed_off {
+        let retry_in = state
+            .next_attempt
+            .map_or(0, |next| (next - now).num_seconds().max(0));
+        println!(
+            "    {} {} - {} consecutive failure(s), retry in {}s",
+            colors::warning("⏸", true),
+            remote,
+            state.consecutive_failures,
+            retry_in
+        );
+        if let Some(error) = &state.last_error {
+            println!("      {}", colors::dim(error, true));
+        }
+    }
+}
+
+/// Show daemon logs, optionally streaming new lines as they are written
+pub fn logs(lines: usize, follow: bool) -> Result<()> {
     info!("Reading daemon logs (last {} lines)", lines);
 
     let daemon = DaemonService::new(300);
@@ -138,13 +456,18 @@ pub fn logs(lines: usize) -> Result<()> {
     println!("\n📜 Daemon Logs (last {lines} lines):\n");
     println!("{}", "=".repeat(60));
 
-    // Read last N lines
-    let file = fs::File::open(&log_file)
-        .map_err(|e| MultiGitError::other(format!("Failed to open log file: {e}")))?;
+    // Read last N lines across rolled files (daemon.log.N .. daemon.log)
+    let max_files = Config::load().unwrap_or_default().daemon.max_log_files;
+    let mut all_lines: Vec<String> = Vec::new();
 
-    let reader = BufReader::new(file);
-    #[allow(clippy::lines_filter_map_ok)]
-    let all_lines: Vec<String> = reader.lines().flatten().collect();
+    for path in log_files_oldest_first(&log_file, max_files) {
+        let file = fs::File::open(&path)
+            .map_err(|e| MultiGitError::other(format!("Failed to open log file: {e}")))?;
+
+        let reader = BufReader::new(file);
+        #[allow(clippy::lines_filter_map_ok)]
+        all_lines.extend(reader.lines().flatten());
+    }
 
     let start = if all_lines.len() > lines {
         all_lines.len() - lines
@@ -156,6 +479,14 @@ pub fn logs(lines: usize) -> Result<()> {
         println!("{line}");
     }
 
+    if follow {
+        println!(
+            "{}",
+            Status::Info.format("Following log (Ctrl+C to stop)", true)
+        );
+        return follow_log(&log_file);
+    }
+
     println!("{}", "=".repeat(60));
     println!("\nLog file: {}", log_file.display());
     println!();
@@ -163,8 +494,71 @@ pub fn logs(lines: usize) -> Result<()> {
     Ok(())
 }
 
+/// Print content appended to the log file until interrupted, like `tail -f`
+///
+/// Polls the file size. When the file is rotated (its inode changes) or
+/// truncated, the rest of the old file is drained and the new file is read
+/// from the start.
+fn follow_log(log_file: &Path) -> Result<()> {
+    let open_error =
+        |e: std::io::Error| MultiGitError::other(format!("Failed to open log file: {e}"));
+
+    let mut file = fs::File::open(log_file).map_err(open_error)?;
+    let mut position = file.seek(SeekFrom::End(0))?;
+    let mut identity = file_identity(&file.metadata()?);
+
+    loop {
+        std::thread::sleep(FOLLOW_POLL_INTERVAL);
+
+        // The file may briefly not exist while it is being rotated
+        let metadata = match fs::metadata(log_file) {
+            Ok(metadata) => metadata,
+            Err(_) => continue,
+        };
+
+        if file_identity(&metadata) != identity || metadata.len() < position {
+            debug!("Log file rotated or truncated, reopening");
+            print_new_content(&mut file)?;
+            file = fs::File::open(log_file).map_err(open_error)?;
+            identity = file_identity(&file.metadata()?);
+            position = 0;
+        }
+
+        position += print_new_content(&mut file)?;
+    }
+}
+
+/// Print everything from the current read position to EOF
+fn print_new_content(file: &mut fs::File) -> Result<u64> {
+    let mut buffer = Vec::new();
+    let read = file.read_to_end(&mut buffer)?;
+
+    if read > 0 {
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(&buffer)?;
+        stdout.flush()?;
+    }
+
+    Ok(read as u64)
+}
+
+/// Identify the underlying file so rotation can be detected
+#[cfg(unix)]
+fn file_identity(metadata: &fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.ino()
+}
+
+/// Identify the underlying file so rotation can be detected
+///
+/// Without inodes, rotation is only detected through the file shrinking.
+#[cfg(not(unix))]
+fn file_identity(_metadata: &fs::Metadata) -> u64 {
+    0
+}
+
 /// Restart the daemon
-pub async fn restart(interval_minutes: u64) -> Result<()> {
+pub async fn restart(schedule: Schedule) -> Result<()> {
     info!("Restarting daemon");
 
     println!("\n🔄 Restarting MultiGit daemon...\n");
@@ -182,7 +576,7 @@ pub async fn restart(interval_minutes: u64) -> Result<()> {
 
     // Start new daemon
     println!("  Starting daemon...");
-    start(interval_minutes).await?;
+    start(schedule).await?;
 
     Ok(())
 }
@@ -194,7 +588,7 @@ mod tests {
     #[test]
     fn test_daemon_status_check() {
         // Just test that status doesn't panic
-        let result = status();
+        let result = status(false);
         // It's ok if it errors (daemon not running)
         let _ = result;
     }
@@ -0,0 +1,163 @@
+//! Side-by-side diff command
+//!
+//! `multigit diff --side-by-side` renders the diff itself with git2 instead of
+//! handing off to `git diff`. Plain `multigit diff` stays a passthrough.
+
+use crate::ui::diff_view::SideBySideDiff;
+use crate::utils::error::{MultiGitError, Result};
+use crate::utils::pager;
+use git2::{Diff, DiffOptions, Repository, Tree};
+use std::io::IsTerminal;
+
+/// Terminal width assumed when it cannot be queried
+const DEFAULT_WIDTH: usize = 120;
+
+/// What to compare, parsed from `git diff`-style arguments
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiffTarget {
+    /// Compare against the index (`--cached`/`--staged`)
+    pub cached: bool,
+    /// Zero, one or two revisions
+    pub revisions: Vec<String>,
+    /// Limit to these paths (after `--`)
+    pub paths: Vec<String>,
+}
+
+impl DiffTarget {
+    /// Parse the supported subset of `git diff` arguments
+    ///
+    /// Accepts `--cached`/`--staged`, up to two revisions (or `a..b`) and
+    /// paths after `--`. Other options are rejected rather than ignored.
+    pub fn parse(args: &[String]) -> Result<Self> {
+        let mut target = Self::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--cached" | "--staged" => target.cached = true,
+                "--" => {
+                    target.paths.extend(args.by_ref().cloned());
+                }
+                option if option.starts_with('-') => {
+                    return Err(MultiGitError::InvalidInput(format!(
+                        "'{option}' is not supported with --side-by-side"
+                    )));
+                }
+                range if range.contains("...") => {
+                    return Err(MultiGitError::InvalidInput(format!(
+                        "'{range}' (merge-base range) is not supported with --side-by-side"
+                    )));
+                }
+                range if range.contains("..") => {
+                    let (from, to) = range.split_once("..").unwrap_or((range, ""));
+                    target.revisions.push(non_empty_or_head(from));
+                    target.revisions.push(non_empty_or_head(to));
+                }
+                revision => target.revisions.push(revision.to_string()),
+            }
+        }
+
+        if target.revisions.len() > 2 {
+            return Err(MultiGitError::InvalidInput(
+                "At most two revisions can be compared".to_string(),
+            ));
+        }
+        if target.cached && target.revisions.len() == 2 {
+            return Err(MultiGitError::InvalidInput(
+                "--cached takes at most one revision".to_string(),
+            ));
+        }
+
+        Ok(target)
+    }
+
+    /// Build the git2 diff this target describes
+    pub fn diff<'r>(&self, repo: &'r Repository) -> Result<Diff<'r>> {
+        let mut options = DiffOptions::new();
+        for path in &self.paths {
+            options.pathspec(path);
+        }
+
+        let diff = match (self.cached, self.revisions.as_slice()) {
+            (false, []) => repo.diff_index_to_workdir(None, Some(&mut options))?,
+            (false, [rev]) => {
+                let tree = tree_for(repo, rev)?;
+                repo.diff_tree_to_workdir_with_index(Some(&tree), Some(&mut options))?
+            }
+            (true, revs) => {
+                let rev = revs.first().map_or("HEAD", String::as_str);
+                // Unborn HEAD: everything staged is new
+                let tree = tree_for(repo, rev).ok();
+                repo.diff_tree_to_index(tree.as_ref(), None, Some(&mut options))?
+            }
+            (false, [from, to, ..]) => {
+                let old = tree_for(repo, from)?;
+                let new = tree_for(repo, to)?;
+                repo.diff_tree_to_tree(Some(&old), Some(&new), Some(&mut options))?
+            }
+        };
+
+        Ok(diff)
+    }
+}
+
+/// Render `git diff`-style `args` side by side and show them through a pager
+pub fn execute_side_by_side(args: &[String], no_color: bool) -> Result<()> {
+    let target = DiffTarget::parse(args)?;
+    let repo = Repository::open_from_env()?;
+    let layout = SideBySideDiff::from_diff(&target.diff(&repo)?)?;
+
+    if layout.is_empty() {
+        return Ok(());
+    }
+
+    let interactive = std::io::stdout().is_terminal();
+    let width =
+        crossterm::terminal::size().map_or(DEFAULT_WIDTH, |(columns, _)| usize::from(columns));
+    pager::page(&layout.render(width, interactive && !no_color))
+}
+
+fn tree_for<'r>(repo: &'r Repository, revision: &str) -> Result<Tree<'r>> {
+    Ok(repo.revparse_single(revision)?.peel_to_tree()?)
+}
+
+fn non_empty_or_head(revision: &str) -> String {
+    if revision.is_empty() {
+        "HEAD".to_string()
+    } else {
+        revision.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_parse_targets() {
+        assert_eq!(DiffTarget::parse(&[]).unwrap(), DiffTarget::default());
+
+        let target = DiffTarget::parse(&args(&["--staged", "HEAD~1", "--", "src"])).unwrap();
+        assert!(target.cached);
+        assert_eq!(target.revisions, vec!["HEAD~1"]);
+        assert_eq!(target.paths, vec!["src"]);
+
+        let target = DiffTarget::parse(&args(&["main..feature"])).unwrap();
+        assert_eq!(target.revisions, vec!["main", "feature"]);
+
+        let target = DiffTarget::parse(&args(&["main.."])).unwrap();
+        assert_eq!(target.revisions, vec!["main", "HEAD"]);
+    }
+
+    #[test]
+    fn test_parse_rejects_unsupported() {
+        assert!(DiffTarget::parse(&args(&["--stat"])).is_err());
+        assert!(DiffTarget::parse(&args(&["main...feature"])).is_err());
+        assert!(DiffTarget::parse(&args(&["a", "b", "c"])).is_err());
+        assert!(DiffTarget::parse(&args(&["--cached", "a", "b"])).is_err());
+    }
+}
@@ -2,52 +2,84 @@
 //!
 //! Run diagnostics and health checks.
 
-use crate::core::health_checker::HealthChecker;
+use crate::api::client::build_api_client_with;
+use crate::cli::commands::git_passthrough::git_version;
+use crate::cli::commands::push::remote_repositories;
+use crate::cli::commands::status::public_remotes;
+use crate::core::clock_skew::{describe_skew, measure_skew, MAX_CLOCK_SKEW_SECONDS};
+use crate::core::config::Config;
+use crate::core::health_checker::{HealthChecker, HealthReport};
+use crate::git::signing::{git_signing_key, remediation, verify_key, SigningFormat};
+use crate::providers::factory::get_provider_host;
+use crate::ui::formatter::Status;
 use crate::utils::error::Result;
-use tracing::info;
+use std::collections::BTreeMap;
+use tracing::{debug, info};
+
+/// What still runs the `git` binary; staging and committing use git2
+const NEEDS_GIT_BINARY: &[&str] = &[
+    "signed commits and tags",
+    "commit hooks",
+    "commit with an editor or extra git arguments",
+    "diff, show, reset and revert passthrough",
+    "stash, merge, undo, log and stats",
+    "Git LFS",
+];
 
 /// Run diagnostics and health checks
-pub fn execute(fix: bool) -> Result<()> {
+pub async fn execute(fix: bool) -> Result<()> {
     info!("Running diagnostics");
 
     println!("\n🔍 Running MultiGit Doctor...\n");
 
     let checker = HealthChecker::new(".")?;
-    let report = checker.check();
+    let mut report = checker.check();
 
     // Display repository status
     if report.repo_valid {
-        println!("✓ Repository: OK");
+        println!("{} Repository: OK", Status::Success.icon());
     } else {
-        println!("✗ Repository: FAILED");
+        println!("{} Repository: FAILED", Status::Error.icon());
     }
 
     if report.working_dir_clean {
-        println!("✓ Working directory: clean");
+        println!("{} Working directory: clean", Status::Success.icon());
     } else {
-        println!("⚠ Working directory: has uncommitted changes");
+        println!(
+            "{} Working directory: has uncommitted changes",
+            Status::Warning.icon()
+        );
     }
 
     if let Some(ref branch) = report.current_branch {
-        println!("✓ Current branch: {branch}");
+        println!("{} Current branch: {branch}", Status::Success.icon());
     } else {
-        println!("⚠ Current branch: DETACHED HEAD");
+        println!("{} Current branch: DETACHED HEAD", Status::Warning.icon());
     }
 
     // Display remote status
     println!("\nRemotes:");
     if report.remotes.is_empty() {
-        println!("  ⚠ No remotes configured");
+        println!("  {} No remotes configured", Status::Warning.icon());
     } else {
         for remote in &report.remotes {
-            let status = if remote.reachable { "✓" } else { "✗" };
+            let status = if remote.reachable {
+                Status::Success.icon()
+            } else {
+                Status::Error.icon()
+            };
             println!("  {} {} ({})", status, remote.name, remote.url);
         }
     }
 
+    check_git_binary(&mut report);
+    check_signing(&mut report);
+    check_clock(&mut report).await;
+    check_visibility(&mut report).await;
+
     // Display issues
     if !report.issues.is_empty() {
-        println!("\n⚠ Issues found:");
+        println!("\n{} Issues found:", Status::Warning.icon());
         for issue in &report.issues {
             println!("  • {issue}");
         }
@@ -62,11 +94,207 @@ pub fn execute(fix: bool) -> Result<()> {
     }
 
     if report.issues.is_empty() {
-        println!("\n✓ Everything looks good!");
+        println!("\n{} Everything looks good!", Status::Success.icon());
     } else if fix {
-        println!("\n⚠ Auto-fix is not yet implemented.");
+        println!(
+            "\n{} Auto-fix is not yet implemented.",
+            Status::Warning.icon()
+        );
         println!("Please follow the recommendations above.");
     }
 
     Ok(())
 }
+
+/// Report whether the `git` binary is installed, and what needs it when not
+fn check_git_binary(report: &mut HealthReport) {
+    println!("\nGit binary:");
+    if let Some(version) = git_version() {
+        println!("  {} {version}", Status::Success.icon());
+        return;
+    }
+
+    println!(
+        "  {} not found; staging and committing still work through libgit2",
+        Status::Warning.icon()
+    );
+    for feature in NEEDS_GIT_BINARY {
+        println!("    • needs git: {feature}");
+    }
+    report.issues.push(format!(
+        "The git binary is not installed; unavailable: {}",
+        NEEDS_GIT_BINARY.join(", ")
+    ));
+    report
+        .recommendations
+        .push("Install git to use every command (e.g. apt install git)".to_string());
+}
+
+/// Check the signing key: that `security.signing_key` (or `user.signingkey`)
+/// exists in gpg or on disk, and that `commit.gpgsign` agrees with it
+///
+/// Skipped when neither a key nor signing is configured.
+fn check_signing(report: &mut HealthReport) {
+    let Ok(repo) = git2::Repository::discover(".") else {
+        return;
+    };
+    let config = Config::load().unwrap_or_default();
+    let gpgsign = repo
+        .config()
+        .and_then(|config| config.get_bool("commit.gpgsign"))
+        .unwrap_or(false);
+    let key = config
+        .security
+        .signing_key()
+        .map(String::from)
+        .or_else(|| git_signing_key(&repo));
+
+    let Some(key) = key else {
+        if gpgsign {
+            println!("\nSigning:");
+            println!(
+                "  {} commit.gpgsign is set, but no signing key is configured",
+                Status::Error.icon()
+            );
+            report
+                .issues
+                .push("commit.gpgsign is set, but no signing key is configured".to_string());
+            report
+                .recommendations
+                .push(remediation().replace('\n', " "));
+        }
+        return;
+    };
+
+    println!("\nSigning:");
+    match verify_key(&repo, &key, SigningFormat::of(&repo)) {
+        Ok(found) => println!("  {} {found}", Status::Success.icon()),
+        Err(e) => {
+            println!("  {} {e}", Status::Error.icon());
+            report.issues.push(format!("Signing key: {e}"));
+            report.recommendations.push(
+                "Point security.signing_key at a key you have, or import the key into gpg"
+                    .to_string(),
+            );
+        }
+    }
+
+    if let (Some(configured), Some(git_key)) =
+        (config.security.signing_key(), git_signing_key(&repo))
+    {
+        if configured != git_key {
+            println!(
+                "  {} user.signingkey ({git_key}) differs; plain git commands sign with it",
+                Status::Warning.icon()
+            );
+        }
+    }
+
+    if gpgsign {
+        println!("  {} commit.gpgsign is set", Status::Success.icon());
+    } else if config.security.signing_key().is_some() {
+        println!(
+            "  {} commit.gpgsign is not set; only multigit signs commits",
+            Status::Warning.icon()
+        );
+        report.recommendations.push(
+            "Run 'git config commit.gpgsign true' so plain git commits are signed too".to_string(),
+        );
+    }
+}
+
+/// Flag remotes where the repository is public although it should be private
+///
+/// Only runs when `settings.expect_private` is set in the repository config.
+async fn check_visibility(report: &mut HealthReport) {
+    let config = Config::load().unwrap_or_default();
+    if !config.settings.expect_private {
+        return;
+    }
+
+    let remotes: Vec<String> = config.enabled_remotes().keys().cloned().collect();
+    let repositories = remote_repositories(&config, &remotes).await;
+
+    println!("\nVisibility (expected private):");
+    for (remote, repository) in &repositories {
+        if repository.private {
+            println!("  {} {remote}: private", Status::Success.icon());
+        } else {
+            println!("  {} {remote}: PUBLIC", Status::Error.icon());
+        }
+    }
+
+    let public = public_remotes(&repositories);
+    if !public.is_empty() {
+        report.issues.push(format!(
+            "Repository is public on {} but settings.expect_private is set",
+            public.join(", ")
+        ));
+        report.recommendations.push(
+            "Make the repository private in each provider's settings, or remove \
+             expect_private from .multigit/config.toml if it is meant to be public"
+                .to_string(),
+        );
+    }
+}
+
+/// Compare the local clock with each configured provider's `Date` header
+///
+/// Rate-limit reset times and token expiry are reported by the provider, so
+/// a skewed local clock makes them look wrong. Unreachable hosts are skipped;
+/// the remote checks above already cover connectivity.
+async fn check_clock(report: &mut HealthReport) {
+    let config = Config::load().unwrap_or_default();
+
+    // One probe per host, using that host's TLS/proxy settings
+    let mut probes = BTreeMap::new();
+    for (name, remote) in config.enabled_remotes() {
+        let provider = remote.provider_name(&name);
+        let url = match remote.api_url {
+            Some(ref url) => url.clone(),
+            None => match get_provider_host(provider, None, false) {
+                Ok(host) => format!("https://{host}"),
+                Err(_) => continue,
+            },
+        };
+        probes
+            .entry(url)
+            .or_insert_with(|| config.client_options_for(remote));
+    }
+
+    if probes.is_empty() {
+        return;
+    }
+
+    println!("\nClock:");
+    for (url, options) in &probes {
+        let skew = match build_api_client_with(options) {
+            Ok(client) => measure_skew(&client, url).await,
+            Err(e) => Err(e),
+        };
+
+        match skew {
+            Ok(skew) if skew.abs() > MAX_CLOCK_SKEW_SECONDS => {
+                println!("  {} {url}: {}", Status::Error.icon(), describe_skew(skew));
+                report
+                    .issues
+                    .push(format!("Clock skew against {url}: {}", describe_skew(skew)));
+                report.recommendations.push(
+                    "Synchronize the system clock (e.g. enable NTP); rate-limit resets and \
+                     token expiry times are shown relative to it"
+                        .to_string(),
+                );
+            }
+            Ok(skew) => println!(
+                "  {} {url}: {}",
+                Status::Success.icon(),
+                describe_skew(skew)
+            ),
+            Err(e) => {
+                debug!("Clock check against {} failed: {}", url, e);
+                println!("  {} {url}: could not check ({e})", Status::Warning.icon());
+            }
+        }
+    }
+    report.recommendations.dedup();
+}
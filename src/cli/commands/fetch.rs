@@ -2,17 +2,42 @@
 //!
 //! Fetch changes from remotes.
 
+use crate::cli::commands::push::group_or_remotes;
 use crate::core::config::Config;
 use crate::core::sync_manager::SyncManager;
-use crate::utils::error::Result;
+use crate::git::operations::GitOperations;
+use crate::git::remote::RemoteManager;
+use crate::ui::formatter::Status;
+use crate::utils::error::{MultiGitError, Result};
+use crate::utils::validation::validate_refspec;
 use tracing::info;
 
 /// Fetch from remotes
-pub async fn execute(remotes: Vec<String>, all: bool) -> Result<()> {
+///
+/// `args` are remotes, optionally followed by refspecs to fetch from a single
+/// remote. With `group`, fetches from the enabled remotes in that group. With
+/// `recurse_submodules`, every remote of each submodule is fetched as well.
+/// `concurrency` overrides `settings.max_parallel` for this fetch.
+pub async fn execute(
+    args: Vec<String>,
+    all: bool,
+    group: Option<String>,
+    recurse_submodules: bool,
+    concurrency: Option<usize>,
+) -> Result<()> {
     info!("Executing fetch command");
 
+    let known_remotes = GitOperations::open(".")
+        .and_then(|ops| RemoteManager::new(ops.inner()).list())
+        .unwrap_or_default();
+    let (remotes, refspecs) = split_refspecs(args, &known_remotes)?;
+
     // Load config to get settings
     let config = Config::load().unwrap_or_default();
+    let remotes = match group_or_remotes(&config, group, remotes)? {
+        Some(remotes) => remotes,
+        None => return Ok(()),
+    };
     let has_multigit_remotes = !config.enabled_remotes().is_empty();
 
     // Determine which remotes to fetch from
@@ -47,32 +72,172 @@ pub async fn execute(remotes: Vec<String>, all: bool) -> Result<()> {
         vec!["origin".to_string()]
     };
 
-    let manager = SyncManager::new(".")?.with_max_parallel(config.settings.max_parallel);
+    let manager = SyncManager::new(".")?
+        .with_max_parallel(concurrency.unwrap_or(config.settings.max_parallel))
+        .with_proxy(config.settings.proxy_url.clone())
+        .with_tls(config.remote_tls())
+        .with_fetch_depth(config.settings.fetch_depth)
+        .with_retries(config.settings.network_attempts);
 
     println!("\n📡 Fetching from {} remote(s)...\n", fetch_remotes.len());
 
     // Perform parallel fetch
-    let results = manager.fetch_all(&fetch_remotes).await?;
+    let results = manager.fetch_refspecs(&fetch_remotes, &refspecs).await?;
 
     // Display results
     let mut success_count = 0;
     let mut failed_count = 0;
+    let mut interrupted_count = 0;
 
     for result in &results {
         if result.success {
-            println!("✓ {} - fetched successfully", result.remote);
+            println!(
+                "{} {} - fetched successfully",
+                Status::Success.icon(),
+                result.remote
+            );
             success_count += 1;
+        } else if result.cancelled {
+            println!("{} {} - interrupted", Status::Skipped.icon(), result.remote);
+            interrupted_count += 1;
         } else {
-            println!("✗ {} - {}", result.remote, result.message);
+            println!(
+                "{} {} - {}",
+                Status::Error.icon(),
+                result.remote,
+                result.message
+            );
             failed_count += 1;
         }
     }
 
+    if recurse_submodules && !manager.is_cancelled() {
+        let (succeeded, failed) = fetch_submodules(&manager).await?;
+        success_count += succeeded;
+        failed_count += failed;
+    }
+
+    if interrupted_count > 0 {
+        println!(
+            "\n📊 Summary: {success_count} succeeded, {failed_count} failed, {interrupted_count} interrupted"
+        );
+        return Err(crate::utils::error::MultiGitError::Other(format!(
+            "Interrupted before {interrupted_count} fetch(es) finished"
+        )));
+    }
+
     println!("\n📊 Summary: {success_count} succeeded, {failed_count} failed");
 
-    if success_count > 0 {
+    if success_count > 0 && !refspecs.is_empty() {
+        println!("\n💡 Merge what was fetched with: multigit merge FETCH_HEAD");
+    } else if success_count > 0 {
         println!("\n💡 Use 'multigit status' to see changes");
     }
 
     Ok(())
 }
+
+/// Fetch every remote of each initialized submodule
+///
+/// Returns the number of fetches that succeeded and failed.
+async fn fetch_submodules(manager: &SyncManager) -> Result<(usize, usize)> {
+    let submodules = manager.submodule_managers()?;
+    if submodules.is_empty() {
+        return Ok((0, 0));
+    }
+
+    println!("\n📡 Fetching {} submodule(s)...\n", submodules.len());
+    let (mut succeeded, mut failed) = (0, 0);
+    for (submodule, sub_manager) in &submodules {
+        let path = submodule.path.display();
+        let remotes = sub_manager.remote_names()?;
+        for result in sub_manager.fetch_all(&remotes).await? {
+            if result.success {
+                println!(
+                    "{} {path}: {} - fetched successfully",
+                    Status::Success.icon(),
+                    result.remote
+                );
+                succeeded += 1;
+            } else {
+                println!(
+                    "{} {path}: {} - {}",
+                    Status::Error.icon(),
+                    result.remote,
+                    result.message
+                );
+                failed += 1;
+            }
+        }
+    }
+    Ok((succeeded, failed))
+}
+
+/// Split `fetch` arguments into remotes and the refspecs after them
+///
+/// Leading arguments naming a git remote are remotes and the rest are
+/// refspecs, so both `fetch github gitlab` and `fetch origin
+/// refs/pull/123/head` work. Refspecs need exactly one remote.
+fn split_refspecs(
+    args: Vec<String>,
+    known_remotes: &[String],
+) -> Result<(Vec<String>, Vec<String>)> {
+    let split = args
+        .iter()
+        .position(|arg| !known_remotes.contains(arg))
+        .unwrap_or(args.len());
+    let mut remotes = args;
+    let refspecs = remotes.split_off(split);
+
+    if refspecs.is_empty() {
+        return Ok((remotes, refspecs));
+    }
+    match remotes.len() {
+        0 => {
+            return Err(MultiGitError::invalid_input(format!(
+                "'{}' is not a remote of this repository (remotes: {})",
+                refspecs[0],
+                known_remotes.join(", ")
+            )))
+        }
+        1 => {}
+        _ => {
+            return Err(MultiGitError::invalid_input(format!(
+                "Refspecs are fetched from a single remote; got {}",
+                remotes.join(", ")
+            )))
+        }
+    }
+
+    for refspec in &refspecs {
+        validate_refspec(refspec)?;
+    }
+    Ok((remotes, refspecs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_split_refspecs() {
+        let known = args(&["origin", "github", "gitlab"]);
+
+        let (remotes, refspecs) = split_refspecs(args(&["github", "gitlab"]), &known).unwrap();
+        assert_eq!(remotes, vec!["github", "gitlab"]);
+        assert!(refspecs.is_empty());
+
+        let (remotes, refspecs) =
+            split_refspecs(args(&["origin", "refs/pull/123/head", "main"]), &known).unwrap();
+        assert_eq!(remotes, vec!["origin"]);
+        assert_eq!(refspecs, vec!["refs/pull/123/head", "main"]);
+
+        assert!(split_refspecs(args(&["github", "gitlab", "main"]), &known).is_err());
+        assert!(split_refspecs(args(&["upstream"]), &known).is_err());
+        assert!(split_refspecs(args(&["origin", "bad..ref"]), &known).is_err());
+    }
+}
@@ -25,5 +25,16 @@ pub fn execute(args: Vec<String>) -> Result<()> {
 /// Check if git is available
 #[must_use]
 pub fn check_git_available() -> bool {
-    Command::new("git").arg("--version").output().is_ok()
+    git_version().is_some()
+}
+
+/// The installed git's version line (e.g. `git version 2.43.0`), or `None`
+/// when the binary can't be run
+#[must_use]
+pub fn git_version() -> Option<String> {
+    let output = Command::new("git").arg("--version").output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
@@ -1,16 +1,115 @@
 //! Init command implementation
 //!
-//! Initialize a repository for `MultiGit` management.
+//! Initialize a repository for `MultiGit` management, optionally registering
+//! remotes (`--remotes`) or copying another repository's (`--from`) in the
+//! same step.
 
+use crate::cli::commands::remote;
+use crate::cli::interactive;
 use crate::core::config::Config;
 use crate::git::operations::GitOperations;
+use crate::providers::factory::connect_remote;
+use crate::providers::traits::Protocol;
+use crate::ui::formatter::Status;
 use crate::utils::error::{MultiGitError, Result};
 use std::fs;
-use std::path::Path;
-use tracing::info;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tracing::{info, warn};
+
+/// A `provider:username` pair given to `init --remotes`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteSpec {
+    /// Provider name, e.g. `github`
+    pub provider: String,
+    /// Account on that provider
+    pub username: String,
+}
+
+impl FromStr for RemoteSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().split_once(':') {
+            Some((provider, username)) if !provider.is_empty() && !username.is_empty() => {
+                Ok(Self {
+                    provider: provider.to_string(),
+                    username: username.to_string(),
+                })
+            }
+            _ => Err(format!("expected provider:username, got '{s}'")),
+        }
+    }
+}
 
 /// Initialize `MultiGit` for a repository
 pub fn execute(path: &str) -> Result<()> {
+    let config_path = create_config(path)?;
+
+    println!(
+        "\n{} MultiGit initialized successfully!",
+        Status::Success.icon()
+    );
+    println!("\nNext steps:");
+    println!(
+        "  1. Add remotes: multigit remote add <name> --provider <provider> --username <user>"
+    );
+    println!("  2. Configure credentials (stored securely in OS keyring)");
+    println!("  3. Push to all remotes: multigit push");
+    println!("\nConfiguration file: {}", config_path.display());
+
+    Ok(())
+}
+
+/// Initialize `MultiGit` and set up remotes in one go
+///
+/// Remotes from `from` (another repository, or a config file) are copied
+/// first, then each of `remotes` is added as `multigit remote add` would,
+/// prompting for tokens in interactive mode. Both land in the repository
+/// config created here. Must run from the repository root, like `remote add`.
+pub async fn bootstrap(
+    remotes: &[RemoteSpec],
+    from: Option<&Path>,
+    interactive_mode: bool,
+) -> Result<()> {
+    let config_path = create_config(".")?;
+    interactive::print_success("MultiGit initialized");
+
+    let mut failed = 0;
+    if let Some(from) = from {
+        copy_remotes(from, &config_path)?;
+    }
+
+    for spec in remotes {
+        println!("\n• {}:{}", spec.provider, spec.username);
+        if let Err(e) = remote::add_remote(
+            spec.provider.clone(),
+            spec.username.clone(),
+            None,
+            None,
+            interactive_mode,
+            Some(&config_path),
+        )
+        .await
+        {
+            interactive::print_error(&format!("Failed to add {}: {e}", spec.provider));
+            failed += 1;
+        }
+    }
+
+    println!("\nConfiguration file: {}", config_path.display());
+    if failed > 0 {
+        return Err(MultiGitError::other(format!(
+            "{failed} remote(s) could not be set up"
+        )));
+    }
+    println!("Push to all remotes with: multigit push");
+
+    Ok(())
+}
+
+/// Create `.multigit/config.toml` under `path` unless it exists, returning its path
+fn create_config(path: &str) -> Result<PathBuf> {
     info!("Initializing MultiGit at: {}", path);
 
     let repo_path = Path::new(path);
@@ -34,7 +133,15 @@ pub fn execute(path: &str) -> Result<()> {
     // Create config file if it doesn't exist
     let config_path = multigit_dir.join("config.toml");
     if !config_path.exists() {
-        let default_config = Config::default();
+        // Start from a snapshot of the template, so later template edits
+        // only reach this repository through inheritance
+        let default_config = match Config::load_template()? {
+            Some(template) => {
+                println!("Using template: {}", Config::template_path()?.display());
+                template
+            }
+            None => Config::default(),
+        };
         let config_str = toml::to_string_pretty(&default_config)
             .map_err(|e| MultiGitError::Other(format!("Failed to serialize config: {e}")))?;
 
@@ -44,14 +151,123 @@ pub fn execute(path: &str) -> Result<()> {
         info!("Created config file at: {}", config_path.display());
     }
 
-    println!("\n✓ MultiGit initialized successfully!");
-    println!("\nNext steps:");
-    println!(
-        "  1. Add remotes: multigit remote add <name> --provider <provider> --username <user>"
-    );
-    println!("  2. Configure credentials (stored securely in OS keyring)");
-    println!("  3. Push to all remotes: multigit push");
-    println!("\nConfiguration file: {}", config_path.display());
+    Ok(config_path)
+}
+
+/// The config file to copy remotes from: `from` itself, or the
+/// `.multigit/config.toml` of the repository at `from`
+fn source_config_path(from: &Path) -> PathBuf {
+    if from.is_dir() {
+        from.join(".multigit").join("config.toml")
+    } else {
+        from.to_path_buf()
+    }
+}
+
+/// Copy the remotes configured in `from` into `config_path` and add a git
+/// remote for each
+///
+/// Tokens are not copied: they are found in the keyring by provider, host and
+/// username, so remotes whose account is already stored work straight away.
+fn copy_remotes(from: &Path, config_path: &Path) -> Result<()> {
+    let source_path = source_config_path(from);
+    if !source_path.is_file() {
+        return Err(MultiGitError::invalid_input(format!(
+            "No MultiGit config found at {}",
+            source_path.display()
+        )));
+    }
+    let source = Config::load_from_file(&source_path)?;
+    if source.remotes.is_empty() {
+        return Err(MultiGitError::invalid_input(format!(
+            "{} has no remotes to copy",
+            source_path.display()
+        )));
+    }
+
+    let mut target = Config::load_from_file(config_path)?;
+    target.remotes.extend(source.remotes.clone());
+    target.save_to_file(config_path)?;
+
+    let mut names: Vec<&String> = source.remotes.keys().collect();
+    names.sort();
+    interactive::print_success(&format!(
+        "Copied {} remote(s) from {}: {}",
+        names.len(),
+        source_path.display(),
+        names
+            .iter()
+            .map(|name| name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+
+    let git_ops = GitOperations::open(".")?;
+    let repo_name = std::env::current_dir()
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "repo".to_string());
+    let settings = Config::load()?;
+
+    for name in names {
+        if git_ops.inner().find_remote(name).is_ok() {
+            continue;
+        }
+        let remote_config = &source.remotes[name];
+        let provider = match connect_remote(&settings, name, remote_config) {
+            Ok(provider) => provider,
+            Err(e) => {
+                warn!("Cannot connect remote {}: {}", name, e);
+                interactive::print_warning(&format!(
+                    "No git remote added for '{name}' ({e}); run 'multigit remote update {name}' to store a token"
+                ));
+                continue;
+            }
+        };
+        let protocol = if remote_config.use_ssh {
+            Protocol::Ssh
+        } else {
+            Protocol::Https
+        };
+        let url = provider.get_remote_url(&repo_name, protocol);
+        match git_ops.add_remote(name, &url) {
+            Ok(()) => interactive::print_success(&format!("Added git remote '{name}' -> {url}")),
+            Err(e) => {
+                interactive::print_warning(&format!("Failed to add git remote '{name}': {e}"));
+            }
+        }
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remote_spec_parse() {
+        assert_eq!(
+            "github:alice".parse::<RemoteSpec>().unwrap(),
+            RemoteSpec {
+                provider: "github".to_string(),
+                username: "alice".to_string(),
+            }
+        );
+        assert!("github".parse::<RemoteSpec>().is_err());
+        assert!("github:".parse::<RemoteSpec>().is_err());
+        assert!(":alice".parse::<RemoteSpec>().is_err());
+    }
+
+    #[test]
+    fn test_source_config_path() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert_eq!(
+            source_config_path(dir.path()),
+            dir.path().join(".multigit").join("config.toml")
+        );
+
+        let file = dir.path().join("team.toml");
+        assert_eq!(source_config_path(&file), file);
+    }
+}
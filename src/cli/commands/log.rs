@@ -2,13 +2,22 @@
 //!
 //! Beautiful, searchable commit history viewer
 
+use crate::git::history;
 use crate::git::operations::GitOperations;
 use crate::utils::error::{MultiGitError, Result};
+use crate::utils::pager;
 use dialoguer::{theme::ColorfulTheme, Select};
 use std::process::Command;
 
 /// Execute interactive log viewer
-pub fn execute(limit: Option<usize>, branch: Option<String>, author: Option<String>) -> Result<()> {
+///
+/// `since` is a date or an age such as `2w` (see [`history::parse_since`]).
+pub fn execute(
+    limit: Option<usize>,
+    branch: Option<String>,
+    author: Option<String>,
+    since: Option<String>,
+) -> Result<()> {
     println!("\n📜 Commit History Browser\n");
 
     let _git_ops =
@@ -31,6 +40,11 @@ pub fn execute(limit: Option<usize>, branch: Option<String>, author: Option<Stri
         args.push(format!("--author={a}"));
     }
 
+    if let Some(ref s) = since {
+        let since = history::parse_since(s, chrono::Utc::now())?;
+        args.push(format!("--since={}", since.to_rfc3339()));
+    }
+
     let output = Command::new("git")
         .args(&args)
         .output()
@@ -85,10 +99,6 @@ pub fn execute(limit: Option<usize>, branch: Option<String>, author: Option<Stri
 
 /// Show detailed commit information
 fn show_commit_details(hash: &str) -> Result<()> {
-    println!("\n═══════════════════════════════════════════════════════════════");
-    println!("Commit Details: {hash}");
-    println!("═══════════════════════════════════════════════════════════════\n");
-
     // Show full commit
     let output = Command::new("git")
         .args(["show", "--stat", "--pretty=fuller", hash])
@@ -96,15 +106,16 @@ fn show_commit_details(hash: &str) -> Result<()> {
         .map_err(|e| MultiGitError::other(format!("Failed to show commit: {e}")))?;
 
     let details = String::from_utf8_lossy(&output.stdout);
-    println!("{details}");
-
-    Ok(())
+    pager::page(&format!(
+        "\n═══════════════════════════════════════════════════════════════\n\
+         Commit Details: {hash}\n\
+         ═══════════════════════════════════════════════════════════════\n\n\
+         {details}\n"
+    ))
 }
 
 /// Show graphical log
 pub fn show_graph(limit: Option<usize>) -> Result<()> {
-    println!("\n🌳 Commit Graph\n");
-
     let limit_val = limit.unwrap_or(15);
 
     let output = Command::new("git")
@@ -120,7 +131,5 @@ pub fn show_graph(limit: Option<usize>) -> Result<()> {
         .map_err(|e| MultiGitError::other(format!("Failed to get graph: {e}")))?;
 
     let graph = String::from_utf8_lossy(&output.stdout);
-    println!("{graph}");
-
-    Ok(())
+    pager::page(&format!("\n🌳 Commit Graph\n\n{graph}\n"))
 }
@@ -2,15 +2,27 @@
 //!
 //! Merge changes from multiple remotes with conflict preview
 
+use crate::core::repo_lock::RepoLock;
+use crate::ui::formatter::Status;
 use crate::utils::error::{MultiGitError, Result};
 use dialoguer::{theme::ColorfulTheme, Confirm, Select};
 use std::process::Command;
 
 /// Execute smart merge
-pub fn execute(from_remote: Option<String>, branch: Option<String>) -> Result<()> {
+///
+/// A `target` such as `FETCH_HEAD` is merged as is, without fetching first.
+pub fn execute(
+    target: Option<String>,
+    from_remote: Option<String>,
+    branch: Option<String>,
+) -> Result<()> {
+    let _lock = RepoLock::acquire(".", "merge")?;
     println!("\n🔀 Smart Merge\n");
 
-    if let Some(remote) = from_remote {
+    if let Some(target) = target {
+        println!("Merging {target} into current branch...\n");
+        merge_target(&target)?;
+    } else if let Some(remote) = from_remote {
         let branch_name = branch.unwrap_or_else(|| "main".to_string());
         merge_from_remote(&remote, &branch_name)?;
     } else {
@@ -72,11 +84,13 @@ fn merge_from_remote(remote: &str, branch: &str) -> Result<()> {
         return Err(MultiGitError::other(format!("Fetch failed: {error}")));
     }
 
-    // Check for conflicts
-    let remote_branch = format!("{remote}/{branch}");
+    merge_target(&format!("{remote}/{branch}"))
+}
 
+/// Merge `target` into the current branch after confirming
+fn merge_target(target: &str) -> Result<()> {
     let confirm = Confirm::with_theme(&ColorfulTheme::default())
-        .with_prompt(format!("Proceed to merge {remote_branch}?"))
+        .with_prompt(format!("Proceed to merge {target}?"))
         .default(true)
         .interact()?;
 
@@ -87,16 +101,19 @@ fn merge_from_remote(remote: &str, branch: &str) -> Result<()> {
 
     // Merge
     let output = Command::new("git")
-        .args(["merge", &remote_branch])
+        .args(["merge", target])
         .output()
         .map_err(|e| MultiGitError::other(format!("Failed to merge: {e}")))?;
 
     if output.status.success() {
-        println!("✅ Merge successful!");
+        println!("{} Merge successful!", Status::Success.icon());
     } else {
         let error = String::from_utf8_lossy(&output.stderr);
         if error.contains("CONFLICT") {
-            println!("⚠️  Conflicts detected! Use 'mg conflict' to resolve.");
+            println!(
+                "{} Conflicts detected! Use 'mg conflict' to resolve.",
+                Status::Warning.icon()
+            );
         } else {
             return Err(MultiGitError::other(format!("Merge failed: {error}")));
         }
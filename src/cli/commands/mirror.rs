@@ -2,6 +2,7 @@
 //!
 //! Ensures all remotes have identical branches, tags, and history
 
+use crate::ui::formatter::Status;
 use crate::utils::error::{MultiGitError, Result};
 use dialoguer::{theme::ColorfulTheme, Confirm};
 use std::process::Command;
@@ -70,9 +71,9 @@ pub fn execute(force: bool, dry_run: bool) -> Result<()> {
             .map_err(|e| MultiGitError::other(format!("Failed to push: {e}")))?;
 
         if output.status.success() {
-            println!("  ✅ Branches mirrored");
+            println!("  {} Branches mirrored", Status::Success.icon());
         } else {
-            println!("  ⚠️  Warning: Branch mirror failed");
+            println!("  {} Warning: Branch mirror failed", Status::Warning.icon());
         }
 
         // Push all tags
@@ -87,16 +88,19 @@ pub fn execute(force: bool, dry_run: bool) -> Result<()> {
             .map_err(|e| MultiGitError::other(format!("Failed to push tags: {e}")))?;
 
         if output.status.success() {
-            println!("  ✅ Tags mirrored");
+            println!("  {} Tags mirrored", Status::Success.icon());
         } else {
-            println!("  ⚠️  Warning: Tag mirror failed");
+            println!("  {} Warning: Tag mirror failed", Status::Warning.icon());
         }
     }
 
     if dry_run {
         println!("\n🔍 DRY RUN COMPLETE - No changes were made");
     } else {
-        println!("\n✅ Mirror complete! All remotes are now in sync.");
+        println!(
+            "\n{} Mirror complete! All remotes are now in sync.",
+            Status::Success.icon()
+        );
     }
 
     Ok(())
@@ -1,16 +1,24 @@
 //! Command implementations
 
+pub mod abort;
 pub mod add;
+pub mod adopt;
 pub mod alias;
 pub mod amend;
+pub mod audit;
+pub mod auth;
 pub mod backup;
+pub mod bisect;
 pub mod branch;
 pub mod changelog;
+pub mod cherry_pick;
 pub mod clone;
+pub mod commit;
 pub mod conflict;
 pub mod conventional_commit;
 pub mod create;
 pub mod daemon;
+pub mod diff;
 pub mod doctor;
 pub mod fetch;
 pub mod git_passthrough;
@@ -19,9 +27,13 @@ pub mod init;
 pub mod log;
 pub mod merge;
 pub mod mirror;
+pub mod profile;
 pub mod pull;
 pub mod push;
+pub mod release;
 pub mod remote;
+pub mod routing;
+pub mod search;
 pub mod session;
 pub mod setup;
 pub mod stash;
@@ -32,3 +44,5 @@ pub mod sync;
 pub mod tag;
 pub mod template;
 pub mod undo;
+pub mod validate;
+pub mod worktree;
@@ -0,0 +1,54 @@
+//! Profile management commands
+//!
+//! List, create and switch between configuration profiles.
+
+use crate::cli::interactive;
+use crate::core::config::Config;
+use crate::core::profile::{self, DEFAULT_PROFILE};
+use crate::utils::error::Result;
+
+/// List profiles, marking the active one
+pub fn list() -> Result<()> {
+    let active = profile::active();
+    let default = profile::default_profile();
+
+    println!("\nProfiles:\n");
+    let mut names = vec![DEFAULT_PROFILE.to_string()];
+    names.extend(profile::list()?);
+
+    for name in &names {
+        let is_active = active.as_deref().unwrap_or(DEFAULT_PROFILE) == name;
+        let is_default = default.as_deref() == Some(name.as_str());
+        let marker = if is_active { "*" } else { " " };
+        let note = if is_default { " (default)" } else { "" };
+        println!("  {marker} {name}{note}");
+    }
+
+    if names.len() == 1 {
+        println!("\nCreate one with: multigit profile create <name>");
+    }
+
+    Ok(())
+}
+
+/// Create a profile with an empty configuration
+pub fn create(name: &str) -> Result<()> {
+    let path = profile::create(name)?;
+    interactive::print_success(&format!("Created profile '{name}'"));
+    println!("Configuration: {}", path.display());
+    println!("Add remotes with: multigit --profile {name} remote add <provider> <username>");
+    println!("Make it the default with: multigit profile switch {name}");
+
+    Ok(())
+}
+
+/// Make a profile the default for commands run without `--profile`
+pub fn switch(name: &str) -> Result<()> {
+    profile::switch(name)?;
+    // Show where the next command will read its config from
+    profile::set_override(Some(name.to_string()));
+    interactive::print_success(&format!("Switched to profile '{name}'"));
+    println!("Configuration: {}", Config::user_config_path()?.display());
+
+    Ok(())
+}
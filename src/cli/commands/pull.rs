@@ -2,15 +2,27 @@
 //!
 //! Pull changes from a specified remote.
 
-use crate::git::operations::GitOperations;
-use crate::utils::error::Result;
+use crate::core::config::Config;
+use crate::core::repo_lock::RepoLock;
+use crate::git::operations::{GitOperations, StashPop};
+use crate::security::audit::{AuditEntry, AuditEventType, AuditLogger};
+use crate::ui::formatter::Status;
+use crate::utils::error::{MultiGitError, Result};
 use tracing::info;
 
+/// Message of the stash entry `--autostash` creates
+pub(crate) const AUTOSTASH_MESSAGE: &str = "multigit autostash";
+
 /// Pull from a specified remote
-pub fn execute(remote: Option<String>, branch: Option<String>) -> Result<()> {
+///
+/// With `autostash` (or `sync.autostash`), uncommitted changes are stashed
+/// before the pull and restored after it instead of stopping the pull.
+pub fn execute(remote: Option<String>, branch: Option<String>, autostash: bool) -> Result<()> {
     info!("Executing pull command");
+    let _lock = RepoLock::acquire(".", "pull")?;
 
-    let git_ops = GitOperations::open(".")?;
+    let config = Config::load().unwrap_or_default();
+    let git_ops = GitOperations::open(".")?.with_retries(config.settings.network_attempts);
 
     // Get branch to pull
     let branch_name = match branch {
@@ -23,74 +35,122 @@ pub fn execute(remote: Option<String>, branch: Option<String>) -> Result<()> {
 
     println!("\n⬇️  Pulling '{branch_name}' from {remote_name}...\n");
 
+    let autostash = autostash || config.sync.autostash;
+
+    let outcome = if autostash && !git_ops.is_clean()? {
+        pull_with_autostash(&git_ops, &branch_name, &remote_name)
+    } else {
+        pull(&git_ops, &branch_name, &remote_name)
+    };
+
+    let entry = AuditEntry::new(
+        AuditEventType::Pull,
+        format!("{remote_name}:{branch_name}"),
+        outcome.is_ok(),
+    );
+    let entry = match &outcome {
+        Ok(summary) => entry.with_message(summary.clone()),
+        Err(e) => entry.with_message(e.to_string()),
+    };
+    AuditLogger::at_default_path(config.security.audit_log).log(entry);
+
+    outcome.map(|_| ())
+}
+
+/// [`pull`] with uncommitted changes stashed for the duration
+fn pull_with_autostash(
+    git_ops: &GitOperations,
+    branch_name: &str,
+    remote_name: &str,
+) -> Result<String> {
+    if git_ops.stash_save(AUTOSTASH_MESSAGE)?.is_none() {
+        return pull(git_ops, branch_name, remote_name);
+    }
+    println!("📦 Stashed local changes");
+
+    let outcome = pull(git_ops, branch_name, remote_name);
+    let restored = restore_autostash(git_ops.stash_pop());
+    let summary = outcome?;
+    restored?;
+    println!("📦 Restored stashed local changes");
+    Ok(format!("{summary}; local changes restored"))
+}
+
+/// Turn a conflicting autostash pop into an error
+///
+/// The changes stay in the stash for the user to resolve.
+pub(crate) fn restore_autostash(popped: Result<StashPop>) -> Result<()> {
+    match popped? {
+        StashPop::Restored => Ok(()),
+        StashPop::Conflicts(files) => {
+            let files = if files.is_empty() {
+                String::new()
+            } else {
+                format!(" in {}", files.join(", "))
+            };
+            Err(MultiGitError::other(format!(
+                "Restoring stashed local changes conflicted{files}; they are kept in \
+                 stash@{{0}}. Resolve the conflicts, then run 'git stash drop'"
+            )))
+        }
+    }
+}
+
+/// Fetch and fast-forward `branch_name`, returning what happened for the audit log
+fn pull(git_ops: &GitOperations, branch_name: &str, remote_name: &str) -> Result<String> {
     // Check if working directory is clean
     if !git_ops.is_clean()? {
-        println!("⚠️  Warning: Working directory has uncommitted changes");
-        println!("Please commit or stash your changes before pulling.\n");
-        return Ok(());
+        println!(
+            "{} Warning: Working directory has uncommitted changes",
+            Status::Warning.icon()
+        );
+        println!("Please commit or stash your changes before pulling, or pass --autostash.\n");
+        return Ok("Skipped: uncommitted changes".to_string());
     }
 
     // Fetch from the remote
     println!("📡 Fetching from {remote_name}...");
-    git_ops.fetch(&remote_name, &[])?;
-    println!("✓ Fetch complete");
+    git_ops.fetch(remote_name, &[])?;
+    println!("{} Fetch complete", Status::Success.icon());
 
     // Check if we're ahead, behind, or diverged
-    let (ahead, behind) = git_ops.get_ahead_behind(&branch_name, &remote_name)?;
+    let (ahead, behind) = git_ops.get_ahead_behind(branch_name, remote_name)?;
 
     if ahead > 0 && behind > 0 {
-        println!("\n⚠️  Divergence detected!");
+        println!("\n{} Divergence detected!", Status::Warning.icon());
         println!("   Local is {ahead} commits ahead");
         println!("   Remote is {behind} commits behind");
         println!("\n💡 Consider using 'multigit sync' to handle this situation");
-        return Ok(());
+        return Ok(format!("Diverged: {ahead} ahead, {behind} behind"));
     }
 
     if behind == 0 {
-        println!("\n✓ Already up to date!");
-        return Ok(());
+        println!("\n{} Already up to date!", Status::Success.icon());
+        return Ok("Already up to date".to_string());
     }
 
     println!("\n📥 Pulling {behind} commit(s)...");
 
     // Attempt fast-forward merge
     if ahead == 0 {
-        println!("✓ Fast-forward merge possible");
+        println!("{} Fast-forward merge possible", Status::Success.icon());
         println!("\nAttempting fast-forward merge...");
 
         // Fast-forward is safe since we have no local commits
-        let local_ref = format!("refs/heads/{branch_name}");
-        let remote_ref = format!("refs/remotes/{remote_name}/{branch_name}");
-
-        // Get the remote OID
-        let remote_oid = git_ops
-            .inner()
-            .refname_to_id(&remote_ref)
-            .map_err(crate::utils::error::MultiGitError::GitError)?;
-
-        // Update the local branch to point to remote
-        git_ops
-            .inner()
-            .reference(&local_ref, remote_oid, true, "fast-forward pull")
-            .map_err(crate::utils::error::MultiGitError::GitError)?;
-
-        // Update working tree if we're on this branch
-        if git_ops.current_branch()? == branch_name {
-            git_ops
-                .inner()
-                .checkout_head(Some(git2::build::CheckoutBuilder::default().force()))
-                .map_err(crate::utils::error::MultiGitError::GitError)?;
-        }
+        git_ops.fast_forward(branch_name, remote_name)?;
 
-        println!("✓ Fast-forward merge successful!");
+        println!("{} Fast-forward merge successful!", Status::Success.icon());
         println!("\n📊 Pulled {behind} commit(s) from {remote_name}");
+        Ok(format!("Fast-forwarded {behind} commit(s)"))
     } else {
-        println!("\n⚠️  Cannot fast-forward (you have {ahead} local commit(s))");
+        println!(
+            "\n{} Cannot fast-forward (you have {ahead} local commit(s))",
+            Status::Warning.icon()
+        );
         println!("\nTo merge, use one of:");
         println!("  1. Merge: git pull {remote_name} {branch_name}");
         println!("  2. Rebase: git pull --rebase {remote_name} {branch_name}");
         println!("  3. Sync all: multigit sync");
+        Ok(format!("Not fast-forwardable: {ahead} local commit(s)"))
     }
-
-    Ok(())
 }
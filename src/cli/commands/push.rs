@@ -2,79 +2,136 @@
 //!
 //! Push to all configured remotes in parallel.
 
+use crate::cli::interactive;
 use crate::core::config::Config;
-use crate::core::sync_manager::SyncManager;
-use crate::utils::error::Result;
-use tracing::info;
+use crate::core::repo_lock::RepoLock;
+use crate::core::routing::Routing;
+use crate::core::sync_manager::{PushResult, SyncManager};
+use crate::git::lfs;
+use crate::git::operations::{RefChange, RefUpdate};
+use crate::models::Repository;
+use crate::providers::factory::{connect_remote, remote_repo_name};
+use crate::providers::traits::Provider;
+use crate::security::audit::{AuditEntry, AuditEventType, AuditLogger};
+use crate::ui::formatter::Status;
+use crate::utils::atomic_write::write_atomic;
+use crate::utils::error::{MultiGitError, Result};
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use git2::Oid;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// Which tags a push sends along with (or instead of) the branch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TagMode {
+    /// No tags
+    #[default]
+    None,
+    /// Annotated tags on the pushed branch's history, like `git push --follow-tags`
+    Follow,
+    /// Every local tag, like `git push --tags`
+    All,
+    /// Every local tag, without pushing the branch
+    Only,
+}
 
 /// Push to all configured remotes
-pub async fn execute(branch: Option<String>, force: bool, remotes: Vec<String>) -> Result<()> {
+///
+/// Remotes whose repository is archived are skipped unless `include_archived`.
+/// With `group`, only the enabled remotes in that group are pushed to. With
+/// `recurse_submodules`, submodules are pushed first.
+///
+/// `set_upstream` decides whether the branch then tracks the primary remote
+/// (see [`upstream_remote`]); `None` does so only when it has no upstream yet.
+///
+/// `tags` selects tags to push once the branch is pushed, to each remote the
+/// branch reached; [`TagMode::Only`] pushes them without the branch.
+///
+/// `concurrency` overrides `settings.max_parallel` for this push.
+#[allow(clippy::too_many_arguments)]
+pub async fn execute(
+    branch: Option<String>,
+    force: bool,
+    remotes: Vec<String>,
+    group: Option<String>,
+    include_archived: bool,
+    recurse_submodules: bool,
+    set_upstream: Option<bool>,
+    tags: TagMode,
+    concurrency: Option<usize>,
+) -> Result<()> {
+    let warning = Status::Warning.icon();
     info!("Executing push command");
+    let _lock = RepoLock::acquire(".", "push")?;
 
     // Load config to get settings
     let config = Config::load().unwrap_or_default();
+    let remotes = match group_or_remotes(&config, group, remotes)? {
+        Some(remotes) => remotes,
+        None => return Ok(()),
+    };
 
     // Check if MultiGit is configured with remotes
     let has_multigit_remotes = !config.enabled_remotes().is_empty();
 
     // If no MultiGit remotes and no specific remotes requested, fallback to git push
     if !has_multigit_remotes && remotes.is_empty() {
-        info!("No MultiGit remotes configured, falling back to git push");
-        use std::process::Command;
+        return git_push(branch.as_deref(), force, tags);
+    }
 
-        let mut git_args = vec!["push".to_string()];
-        if let Some(ref b) = branch {
-            git_args.push(b.clone());
-        }
-        if force {
-            git_args.push("--force".to_string());
-        }
+    let manager = SyncManager::new(".")?
+        .with_max_parallel(concurrency.unwrap_or(config.settings.max_parallel))
+        .with_proxy(config.settings.proxy_url.clone())
+        .with_tls(config.remote_tls())
+        .with_retries(config.settings.network_attempts);
+    manager.ensure_no_operation_in_progress("push")?;
 
-        let output = Command::new("git")
-            .args(&git_args)
-            .stdin(std::process::Stdio::inherit())
-            .stdout(std::process::Stdio::inherit())
-            .stderr(std::process::Stdio::inherit())
-            .output()
-            .map_err(|e| {
-                crate::utils::error::MultiGitError::other(format!("Failed to execute git: {e}"))
-            })?;
+    // Get remotes to push to
+    let mut push_remotes = if remotes.is_empty() {
+        // Load all enabled remotes from config
+        let config = Config::load().unwrap_or_default();
+        let enabled: Vec<String> = config.enabled_remotes().keys().cloned().collect();
+        if enabled.is_empty() {
+            println!("{warning} No MultiGit remotes configured. Using standard git push behavior.");
+            return Ok(());
+        }
+        enabled
+    } else {
+        remotes
+    };
 
-        if !output.status.success() {
-            std::process::exit(output.status.code().unwrap_or(1));
+    if !include_archived {
+        skip_archived(&config, &mut push_remotes).await;
+        if push_remotes.is_empty() {
+            println!("\nNothing to push: every remote is archived.");
+            return Ok(());
         }
-        return Ok(());
     }
 
-    let manager = SyncManager::new(".")?.with_max_parallel(config.settings.max_parallel);
+    if tags == TagMode::Only {
+        return push_tags(&manager, &config, tags, None, force, &push_remotes).await;
+    }
 
     // Get branch to push
     let branch_name = match branch {
         Some(b) => b,
         None => manager.current_branch()?,
     };
+    let push_remotes = routed_remotes(&config, &branch_name, push_remotes)?;
 
     // Check if working directory is clean
     if !manager.is_clean()? {
-        println!("⚠ Warning: Working directory has uncommitted changes");
+        println!("{warning} Warning: Working directory has uncommitted changes");
     }
 
-    // Get remotes to push to
-    let push_remotes = if remotes.is_empty() {
-        // Load all enabled remotes from config
-        let config = Config::load().unwrap_or_default();
-        let enabled: Vec<String> = config.enabled_remotes().keys().cloned().collect();
-        if enabled.is_empty() {
-            println!("⚠ No MultiGit remotes configured. Using standard git push behavior.");
-            return Ok(());
-        }
-        enabled
-    } else {
-        remotes
-    };
-
     if force {
-        println!("⚠ Force push requested - this will overwrite remote history!");
+        println!("{warning} Force push requested - this will overwrite remote history!");
+        warn_default_branch_targets(&config, &branch_name, &push_remotes).await;
     }
 
     println!(
@@ -83,22 +140,231 @@ pub async fn execute(branch: Option<String>, force: bool, remotes: Vec<String>)
         push_remotes.len()
     );
 
+    if recurse_submodules {
+        push_submodules(&manager).await?;
+    }
+
+    let track = match set_upstream {
+        Some(track) => track,
+        None => !manager.has_upstream(&branch_name)?,
+    };
+
     // Perform parallel push
-    let results = manager.push_all(&branch_name, &push_remotes).await?;
+    let mut results = if force {
+        manager.force_push_all(&branch_name, &push_remotes).await?
+    } else {
+        manager.push_all(&branch_name, &push_remotes).await?
+    };
+    let forced: Vec<String> = if force {
+        push_remotes.clone()
+    } else {
+        force_rejected(&manager, &branch_name, &mut results).await?
+    };
+    if manager.uses_lfs() {
+        upload_lfs_objects(&manager, &branch_name, &mut results);
+    }
+    audit_pushes(&config, &branch_name, &forced, &results);
+    if track {
+        track_upstream(&manager, &config, &branch_name, &results);
+    }
+
+    let branch_report = report(&results);
+    if tags == TagMode::None {
+        return branch_report;
+    }
+
+    let pushed: Vec<String> = results
+        .iter()
+        .filter(|result| result.success)
+        .map(|result| result.remote.clone())
+        .collect();
+    let tag_report = if pushed.is_empty() {
+        Ok(())
+    } else {
+        push_tags(&manager, &config, tags, Some(&branch_name), force, &pushed).await
+    };
+    branch_report.and(tag_report)
+}
+
+/// Drop the remotes `[routing]` keeps `branch` away from, saying why
+///
+/// Fails when routing blocks every one of `remotes`.
+pub(crate) fn routed_remotes(
+    config: &Config,
+    branch: &str,
+    remotes: Vec<String>,
+) -> Result<Vec<String>> {
+    let routing = Routing::from_config(config)?;
+    let (allowed, blocked) = routing.partition(branch, &remotes);
+    for remote in &blocked {
+        println!(
+            "{} {remote} - skipping '{branch}': {} in [routing]",
+            Status::Skipped.icon(),
+            routing.route(branch, remote).describe()
+        );
+    }
+    if allowed.is_empty() && !blocked.is_empty() {
+        return Err(MultiGitError::invalid_input(format!(
+            "Routing rules keep '{branch}' from every selected remote; \
+             check with 'multigit routing test {branch}'"
+        )));
+    }
+    Ok(allowed)
+}
+
+/// Refspecs for the tags `mode` selects; `branch` is the branch whose
+/// history [`TagMode::Follow`] looks in
+fn tag_refspecs(
+    manager: &SyncManager,
+    mode: TagMode,
+    branch: Option<&str>,
+    force: bool,
+) -> Result<Vec<String>> {
+    let tags = match (mode, branch) {
+        (TagMode::None, _) => Vec::new(),
+        (TagMode::Follow, Some(branch)) => manager.annotated_tags_reachable_from(branch)?,
+        (TagMode::Follow, None) => {
+            manager.annotated_tags_reachable_from(&manager.current_branch()?)?
+        }
+        (TagMode::All | TagMode::Only, _) => manager.tags()?,
+    };
+    let plus = if force { "+" } else { "" };
+    Ok(tags
+        .iter()
+        .map(|tag| format!("{plus}refs/tags/{tag}:refs/tags/{tag}"))
+        .collect())
+}
+
+/// Push the tags `mode` selects to `remotes` and report, per remote, which
+/// were created and which were already there
+async fn push_tags(
+    manager: &SyncManager,
+    config: &Config,
+    mode: TagMode,
+    branch: Option<&str>,
+    force: bool,
+    remotes: &[String],
+) -> Result<()> {
+    let refspecs = tag_refspecs(manager, mode, branch, force)?;
+    if refspecs.is_empty() {
+        println!("\n{} No tags to push", Status::Info.icon());
+        return Ok(());
+    }
+
+    println!(
+        "\n🏷  Pushing {} tag(s) to {} remote(s)...\n",
+        refspecs.len(),
+        remotes.len()
+    );
+    let results = manager.push_refs_all(&refspecs, remotes).await?;
+    let forced = if force { remotes.to_vec() } else { Vec::new() };
+    audit_pushes(config, "tags", &forced, &results);
+    report_tags(&results)
+}
 
-    // Display results
+/// Names of the tags in `updates` that `change` describes
+fn tags_with(updates: &[RefUpdate], change: RefChange) -> Vec<&str> {
+    updates
+        .iter()
+        .filter(|update| update.change == change)
+        .filter_map(|update| update.refname.strip_prefix("refs/tags/"))
+        .collect()
+}
+
+/// Print which tags each remote gained, failing if any tag push failed
+fn report_tags(results: &[PushResult]) -> Result<()> {
+    let mut failed_count = 0;
+    for result in results {
+        if !result.success {
+            if result.cancelled {
+                println!(
+                    "{} {} - tags interrupted",
+                    Status::Skipped.icon(),
+                    result.remote
+                );
+            } else {
+                println!(
+                    "{} {} - tags: {}",
+                    Status::Error.icon(),
+                    result.remote,
+                    result.message
+                );
+            }
+            failed_count += 1;
+            continue;
+        }
+
+        let mut parts = Vec::new();
+        for (label, change) in [
+            ("created", RefChange::Created),
+            ("moved", RefChange::Updated),
+            ("already present", RefChange::UpToDate),
+        ] {
+            let tags = tags_with(&result.updates, change);
+            if !tags.is_empty() {
+                parts.push(format!("{label}: {}", tags.join(", ")));
+            }
+        }
+        println!(
+            "{} {} - {}",
+            Status::Success.icon(),
+            result.remote,
+            parts.join("; ")
+        );
+    }
+
+    if failed_count > 0 {
+        return Err(MultiGitError::other(format!(
+            "Tags could not be pushed to {failed_count} remote(s)"
+        )));
+    }
+    Ok(())
+}
+
+/// Print each push result and a summary, failing if any push did not succeed
+fn report(results: &[PushResult]) -> Result<()> {
     let mut success_count = 0;
     let mut failed_count = 0;
+    let mut interrupted_count = 0;
 
-    for result in &results {
+    for result in results {
         if result.success {
-            println!("✓ {} - pushed in {}ms", result.remote, result.duration_ms);
+            println!(
+                "{} {} - pushed in {}ms",
+                Status::Success.icon(),
+                result.remote,
+                result.duration_ms
+            );
             success_count += 1;
+        } else if result.cancelled {
+            println!("{} {} - interrupted", Status::Skipped.icon(), result.remote);
+            interrupted_count += 1;
         } else {
-            println!("✗ {} - {}", result.remote, result.message);
+            println!(
+                "{} {} - {}",
+                Status::Error.icon(),
+                result.remote,
+                result.message
+            );
             failed_count += 1;
         }
     }
+    if results.iter().any(|result| result.rejected) {
+        println!(
+            "\n{} Rejected remotes have commits this branch lacks: pull them first, \
+             or overwrite them with 'multigit push --force'",
+            Status::Info.icon()
+        );
+    }
+
+    if interrupted_count > 0 {
+        println!(
+            "\n📊 Summary: {success_count} succeeded, {failed_count} failed, {interrupted_count} interrupted"
+        );
+        return Err(crate::utils::error::MultiGitError::Other(format!(
+            "Interrupted before {interrupted_count} push(es) finished"
+        )));
+    }
 
     println!("\n📊 Summary: {success_count} succeeded, {failed_count} failed");
 
@@ -110,3 +376,547 @@ pub async fn execute(branch: Option<String>, force: bool, remotes: Vec<String>)
 
     Ok(())
 }
+
+/// Offer to force-push to each remote that rejected the push because it has
+/// diverged, returning the remotes force-pushed to
+///
+/// The remote branch is fetched first, and the force push only goes through
+/// while it is still at that commit, so nothing pushed there while the prompt
+/// was open is lost. Only asks on a terminal; elsewhere the rejections stand.
+async fn force_rejected(
+    manager: &SyncManager,
+    branch: &str,
+    results: &mut [PushResult],
+) -> Result<Vec<String>> {
+    let terminal = std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+    if !terminal {
+        return Ok(Vec::new());
+    }
+
+    let mut expected = HashMap::new();
+    for result in results.iter().filter(|result| result.rejected) {
+        let remote = &result.remote;
+        let Some(tip) = rejected_tip(manager, remote, branch).await else {
+            println!(
+                "{} {remote} - could not fetch '{branch}' to see what a force push would replace",
+                Status::Warning.icon()
+            );
+            continue;
+        };
+        if interactive::confirm(&format!(
+            "{remote} has diverged (at {}); force push? (this overwrites remote history)",
+            &tip.to_string()[..7]
+        ))? {
+            expected.insert(remote.clone(), tip);
+        }
+    }
+    if expected.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    for retry in manager.force_push_with_lease_all(branch, &expected).await? {
+        if let Some(result) = results.iter_mut().find(|r| r.remote == retry.remote) {
+            *result = retry;
+        }
+    }
+    let mut confirmed: Vec<String> = expected.into_keys().collect();
+    confirmed.sort();
+    Ok(confirmed)
+}
+
+/// Commit `branch` is at on `remote`, fetched into its remote-tracking branch
+async fn rejected_tip(manager: &SyncManager, remote: &str, branch: &str) -> Option<Oid> {
+    let refspec = format!("+refs/heads/{branch}:refs/remotes/{remote}/{branch}");
+    let fetched = manager
+        .fetch_refspecs(&[remote.to_string()], &[refspec])
+        .await
+        .ok()?;
+    if !fetched.iter().all(|result| result.success) {
+        return None;
+    }
+    manager.remote_branch_tip(remote, branch).ok().flatten()
+}
+
+/// Record each push in the audit log, as a force push for the remotes in `forced`
+pub(crate) fn audit_pushes(
+    config: &Config,
+    branch: &str,
+    forced: &[String],
+    results: &[PushResult],
+) {
+    let logger = AuditLogger::at_default_path(config.security.audit_log);
+    for result in results {
+        let event = if forced.contains(&result.remote) {
+            AuditEventType::ForcePush
+        } else {
+            AuditEventType::Push
+        };
+        let mut entry =
+            AuditEntry::new(event, format!("{}:{branch}", result.remote), result.success)
+                .with_message(result.message.clone());
+        if let Some(remote) = config.remotes.get(&result.remote) {
+            entry = entry.with_user(remote.username.clone());
+        }
+        logger.log(entry);
+    }
+}
+
+/// The remote a branch should track among `remotes`: `sync.primary_source`
+/// when it is one of them, otherwise the highest-priority one (ties by name)
+fn upstream_remote<'a>(config: &Config, remotes: &'a [String]) -> Option<&'a String> {
+    if let Some(primary) = &config.sync.primary_source {
+        if let Some(remote) = remotes.iter().find(|remote| *remote == primary) {
+            return Some(remote);
+        }
+    }
+
+    let priority = |name: &String| config.remotes.get(name).map_or(0, |r| r.priority);
+    remotes
+        .iter()
+        .min_by(|a, b| priority(b).cmp(&priority(a)).then_with(|| a.cmp(b)))
+}
+
+/// Set the upstream of `branch` to the primary remote once the push to it succeeded
+fn track_upstream(manager: &SyncManager, config: &Config, branch: &str, results: &[PushResult]) {
+    let pushed: Vec<String> = results.iter().map(|r| r.remote.clone()).collect();
+    let remote = match upstream_remote(config, &pushed) {
+        Some(remote) => remote,
+        None => return,
+    };
+    if !results.iter().any(|r| &r.remote == remote && r.success) {
+        return;
+    }
+
+    match manager.set_upstream(branch, remote) {
+        Ok(()) => println!("↪ '{branch}' now tracks {remote}/{branch}"),
+        Err(e) => println!(
+            "{} Could not set upstream of '{branch}' to {remote}: {e}",
+            Status::Warning.icon()
+        ),
+    }
+}
+
+/// Push the checked-out branch of each initialized submodule to its remotes
+///
+/// Runs before the superproject push so the commits it records exist
+/// remotely; any failure stops the push.
+async fn push_submodules(manager: &SyncManager) -> Result<()> {
+    let submodules = manager.submodule_managers()?;
+    if submodules.is_empty() {
+        return Ok(());
+    }
+
+    println!("\n📦 Pushing {} submodule(s)...\n", submodules.len());
+    let mut failed = 0;
+    for (submodule, sub_manager) in &submodules {
+        let path = submodule.path.display();
+        if let Ok(branch) = sub_manager.current_branch() {
+            let remotes = sub_manager.remote_names()?;
+            let mut results = sub_manager.push_all(&branch, &remotes).await?;
+            if sub_manager.uses_lfs() {
+                upload_lfs_objects(sub_manager, &branch, &mut results);
+            }
+            for result in &results {
+                if result.success {
+                    println!(
+                        "{} {path}: {} - pushed '{branch}'",
+                        Status::Success.icon(),
+                        result.remote
+                    );
+                } else {
+                    println!(
+                        "{} {path}: {} - {}",
+                        Status::Error.icon(),
+                        result.remote,
+                        result.message
+                    );
+                    failed += 1;
+                }
+            }
+        } else {
+            println!(
+                "{} {path} - detached HEAD, nothing to push",
+                Status::Skipped.icon()
+            );
+        }
+    }
+
+    if failed > 0 {
+        return Err(MultiGitError::other(format!(
+            "{failed} submodule push(es) failed; the superproject was not pushed"
+        )));
+    }
+    Ok(())
+}
+
+/// Upload LFS objects to every remote in `results` whose push succeeded
+///
+/// A remote whose LFS upload fails is marked failed. Without `git lfs` the
+/// remotes keep pointer files only, which is warned about on stderr so it
+/// shows even with `--json`.
+pub(crate) fn upload_lfs_objects(manager: &SyncManager, branch: &str, results: &mut [PushResult]) {
+    let pushed: Vec<&str> = results
+        .iter()
+        .filter(|result| result.success)
+        .map(|result| result.remote.as_str())
+        .collect();
+    if pushed.is_empty() {
+        return;
+    }
+
+    if !lfs::is_installed() {
+        eprintln!(
+            "\n{} This repository uses Git LFS, but git-lfs is not installed.",
+            Status::Warning.icon()
+        );
+        eprintln!("{} LFS objects were NOT uploaded to: {}. Those remotes now hold pointer files without content.", Status::Warning.icon(), pushed.join(", "));
+        eprintln!(
+            "  Install git-lfs, then run 'git lfs push <remote> {branch}' for each of them.\n"
+        );
+        return;
+    }
+
+    info!(
+        "Uploading LFS objects for '{}' to {} remote(s)",
+        branch,
+        pushed.len()
+    );
+    for result in results.iter_mut().filter(|result| result.success) {
+        if let Err(e) = manager.push_lfs(&result.remote, branch) {
+            result.success = false;
+            result.message = format!("commits pushed, but LFS objects were not: {e}");
+        }
+    }
+}
+
+/// Plain `git push`, for repositories without `MultiGit` remotes
+fn git_push(branch: Option<&str>, force: bool, tags: TagMode) -> Result<()> {
+    use std::process::Command;
+
+    info!("No MultiGit remotes configured, falling back to git push");
+
+    let mut git_args = vec!["push".to_string()];
+    if let Some(b) = branch {
+        git_args.push(b.to_string());
+    }
+    if force {
+        git_args.push("--force".to_string());
+    }
+    match tags {
+        TagMode::None => {}
+        TagMode::Follow => git_args.push("--follow-tags".to_string()),
+        TagMode::All | TagMode::Only => git_args.push("--tags".to_string()),
+    }
+
+    let output = Command::new("git")
+        .args(&git_args)
+        .stdin(std::process::Stdio::inherit())
+        .stdout(std::process::Stdio::inherit())
+        .stderr(std::process::Stdio::inherit())
+        .output()
+        .map_err(|e| {
+            crate::utils::error::MultiGitError::other(format!("Failed to execute git: {e}"))
+        })?;
+
+    if !output.status.success() {
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+    Ok(())
+}
+
+/// The enabled remotes in `group`, or `remotes` when no group is given
+///
+/// `None` when every remote in the group is disabled, after saying so.
+pub(crate) fn group_or_remotes(
+    config: &Config,
+    group: Option<String>,
+    remotes: Vec<String>,
+) -> Result<Option<Vec<String>>> {
+    let group = match group {
+        Some(group) => group,
+        None => return Ok(Some(remotes)),
+    };
+
+    let members = config.enabled_remotes_in_group(&group)?;
+    if members.is_empty() {
+        println!(
+            "{} Every remote in group '{group}' is disabled.",
+            Status::Warning.icon()
+        );
+        return Ok(None);
+    }
+    Ok(Some(members))
+}
+
+/// Warn about remotes where `branch` is the repository's default branch
+///
+/// The default branch is looked up from each provider rather than assumed to
+/// be `main`. Remotes that can't be queried are skipped.
+async fn warn_default_branch_targets(config: &Config, branch: &str, remotes: &[String]) {
+    let targets = remotes.iter().filter_map(|remote| {
+        Some((
+            remote,
+            config.remotes.get(remote)?,
+            remote_repo_name(remote)?,
+        ))
+    });
+
+    for (remote, remote_config, repo_name) in targets {
+        match connect_remote(config, remote, remote_config) {
+            Ok(provider) => {
+                if is_default_branch(provider.as_ref(), &repo_name, branch).await {
+                    println!(
+                        "{} '{branch}' is the default branch on {remote}; \
+                         force-pushing rewrites its shared history",
+                        Status::Warning.icon()
+                    );
+                }
+            }
+            Err(e) => debug!("Skipping default branch check for '{}': {}", remote, e),
+        }
+    }
+}
+
+/// Drop remotes whose repository is archived, noting each one
+async fn skip_archived(config: &Config, remotes: &mut Vec<String>) {
+    let archived = archived_remotes(config, remotes, true).await;
+    for remote in &archived {
+        println!(
+            "{} {remote} - remote is archived, skipping (use --include-archived to try anyway)",
+            Status::Skipped.icon()
+        );
+    }
+    remotes.retain(|remote| !archived.contains(remote));
+}
+
+/// How long an archived flag looked up from a provider is trusted
+const ARCHIVED_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// File in the repository's git directory remembering which remotes are archived
+const ARCHIVED_CACHE_FILE: &str = "multigit-archived.json";
+
+/// Whether a remote's repository was archived when last looked up
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ArchivedFlag {
+    archived: bool,
+    checked_at: DateTime<Utc>,
+}
+
+/// Remotes among `remotes` whose repository is archived (read-only) on the provider
+///
+/// Flags looked up within [`ARCHIVED_CACHE_TTL`] are reused, so a push only
+/// queries the providers once in a while. Without `lookup` only those cached
+/// flags are consulted and no provider is contacted.
+pub(crate) async fn archived_remotes(
+    config: &Config,
+    remotes: &[String],
+    lookup: bool,
+) -> Vec<String> {
+    let cache = archived_cache_path();
+    let flags = cache
+        .as_deref()
+        .map(load_archived_flags)
+        .unwrap_or_default();
+    let (mut archived, stale) = cached_archived(&flags, remotes, Utc::now());
+    if lookup && !stale.is_empty() {
+        archived.extend(
+            remote_repositories(config, &stale)
+                .await
+                .into_iter()
+                .filter(|(_, repository)| repository.archived == Some(true))
+                .map(|(remote, _)| remote),
+        );
+    }
+    remotes
+        .iter()
+        .filter(|remote| archived.contains(remote))
+        .cloned()
+        .collect()
+}
+
+/// Split `remotes` into those `flags` knows to be archived as of `now`, and
+/// those whose flag is missing or older than [`ARCHIVED_CACHE_TTL`]
+fn cached_archived(
+    flags: &HashMap<String, ArchivedFlag>,
+    remotes: &[String],
+    now: DateTime<Utc>,
+) -> (Vec<String>, Vec<String>) {
+    let mut archived = Vec::new();
+    let mut stale = Vec::new();
+    for remote in remotes {
+        let fresh = flags.get(remote).filter(|flag| {
+            (now - flag.checked_at)
+                .to_std()
+                .is_ok_and(|age| age < ARCHIVED_CACHE_TTL)
+        });
+        match fresh {
+            Some(flag) if flag.archived => archived.push(remote.clone()),
+            Some(_) => {}
+            None => stale.push(remote.clone()),
+        }
+    }
+    (archived, stale)
+}
+
+fn archived_cache_path() -> Option<PathBuf> {
+    let repo = git2::Repository::open(".").ok()?;
+    Some(repo.path().join(ARCHIVED_CACHE_FILE))
+}
+
+fn load_archived_flags(path: &Path) -> HashMap<String, ArchivedFlag> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Remember the archived flag of each of `repositories` in the cache at `path`
+fn record_archived_flags(path: &Path, repositories: &[(String, Repository)]) {
+    let mut flags = load_archived_flags(path);
+    let now = Utc::now();
+    for (remote, repository) in repositories {
+        if let Some(archived) = repository.archived {
+            flags.insert(
+                remote.clone(),
+                ArchivedFlag {
+                    archived,
+                    checked_at: now,
+                },
+            );
+        }
+    }
+    let result = serde_json::to_string_pretty(&flags)
+        .map_err(std::io::Error::from)
+        .and_then(|content| write_atomic(path, content));
+    if let Err(e) = result {
+        debug!("Could not cache archived flags: {}", e);
+    }
+}
+
+/// This repository as each of `remotes` reports it
+///
+/// Looked up concurrently. Remotes that can't be queried (no token, API
+/// unreachable) are left out, so callers never block on them. The archived
+/// flags found are cached for [`archived_remotes`].
+pub(crate) async fn remote_repositories(
+    config: &Config,
+    remotes: &[String],
+) -> Vec<(String, Repository)> {
+    let lookups = remotes.iter().map(|remote| async move {
+        let remote_config = config.remotes.get(remote)?;
+        let repo_name = remote_repo_name(remote)?;
+        let provider = connect_remote(config, remote, remote_config)
+            .map_err(|e| debug!("Skipping repository lookup on '{}': {}", remote, e))
+            .ok()?;
+
+        match provider.get_repo(&repo_name).await {
+            Ok(repository) => Some((remote.clone(), repository)),
+            Err(e) => {
+                debug!("Could not fetch '{}' from '{}': {}", repo_name, remote, e);
+                None
+            }
+        }
+    });
+
+    let repositories: Vec<(String, Repository)> =
+        join_all(lookups).await.into_iter().flatten().collect();
+    if let Some(cache) = archived_cache_path() {
+        record_archived_flags(&cache, &repositories);
+    }
+    repositories
+}
+
+/// Whether `branch` is the default branch of `repo` on `provider`
+async fn is_default_branch(provider: &dyn Provider, repo: &str, branch: &str) -> bool {
+    match provider.default_branch(repo).await {
+        Ok(default_branch) => default_branch == branch,
+        Err(e) => {
+            debug!("Could not look up default branch of '{}': {}", repo, e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Repository;
+    use crate::providers::mock::MockProvider;
+
+    #[test]
+    fn test_cached_archived_flags() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = temp_dir.path().join(ARCHIVED_CACHE_FILE);
+        let mut archived = Repository::new(
+            "demo",
+            "https://x/demo.git",
+            "git@x:demo.git",
+            false,
+            "main",
+        );
+        archived.archived = Some(true);
+        let mut active = archived.clone();
+        active.archived = Some(false);
+        record_archived_flags(
+            &cache,
+            &[
+                ("github".to_string(), archived),
+                ("gitlab".to_string(), active),
+            ],
+        );
+
+        let flags = load_archived_flags(&cache);
+        let remotes = ["github", "gitlab", "gitea"].map(String::from);
+        let (found, stale) = cached_archived(&flags, &remotes, Utc::now());
+        assert_eq!(found, vec!["github".to_string()]);
+        assert_eq!(stale, vec!["gitea".to_string()]);
+
+        // Once the TTL has passed every remote is looked up again
+        let later = Utc::now() + chrono::Duration::hours(2);
+        let (found, stale) = cached_archived(&flags, &remotes, later);
+        assert!(found.is_empty());
+        assert_eq!(stale.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_is_default_branch_uses_provider() {
+        let repo = Repository::new(
+            "demo",
+            "https://x/demo.git",
+            "git@x:demo.git",
+            false,
+            "trunk",
+        );
+        let provider = MockProvider::new("gitlab").with_repository(repo);
+
+        assert!(is_default_branch(&provider, "demo", "trunk").await);
+        assert!(!is_default_branch(&provider, "demo", "main").await);
+        assert_eq!(provider.calls(), vec!["get_repo:demo", "get_repo:demo"]);
+    }
+
+    #[test]
+    fn test_upstream_remote() {
+        let mut config = Config::default();
+        for (name, priority) in [("github", 0), ("gitlab", 5), ("codeberg", 5)] {
+            config.remotes.insert(
+                name.to_string(),
+                crate::core::config::RemoteConfig {
+                    username: "ada".to_string(),
+                    priority,
+                    ..Default::default()
+                },
+            );
+        }
+        let remotes: Vec<String> = ["github", "gitlab", "codeberg"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(upstream_remote(&config, &remotes).unwrap(), "codeberg");
+
+        config.sync.primary_source = Some("github".to_string());
+        assert_eq!(upstream_remote(&config, &remotes).unwrap(), "github");
+        assert_eq!(upstream_remote(&config, &remotes[1..]).unwrap(), "codeberg");
+        assert!(upstream_remote(&config, &[]).is_none());
+    }
+}
@@ -0,0 +1,547 @@
+//! Release command implementation
+//!
+//! Cuts a release in one go: checks the working tree, records the changes
+//! since the previous tag in the changelog and commits it, tags the result,
+//! pushes the branch and tag to every remote, and with `--publish` creates a
+//! release from the notes on every host that supports them. Each step after
+//! the checks can be skipped, and a summary of the steps taken is printed at
+//! the end, also when one of them fails.
+
+use crate::cli::commands::changelog::{write_release_notes, ChangelogFormat};
+use crate::cli::commands::push::{self, TagMode};
+use crate::cli::commands::tag::create_signed;
+use crate::core::config::Config;
+use crate::git::operations::GitOperations;
+use crate::models::{Release, ReleaseAsset};
+use crate::providers::factory::{connect_remote, remote_repo_name};
+use crate::providers::traits::Provider;
+use crate::ui::formatter::{colors, format_bytes, Status, Table};
+use crate::utils::error::{MultiGitError, Result};
+use futures::future::join_all;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Options for the release command
+#[derive(Debug, Clone, Default)]
+pub struct ReleaseOptions {
+    /// Tag message (default: `Release <version>`)
+    pub message: Option<String>,
+    /// Sign the tag with `security.signing_key`, or git's GPG or SSH setup
+    pub sign: bool,
+    /// Changelog file (default: `CHANGELOG.md`)
+    pub changelog: Option<String>,
+    /// Layout of the changelog section
+    pub format: ChangelogFormat,
+    /// Leave the changelog alone
+    pub no_changelog: bool,
+    /// Don't create the tag
+    pub no_tag: bool,
+    /// Don't push to the remotes
+    pub no_push: bool,
+    /// Create a release on every remote whose host supports them
+    pub publish: bool,
+    /// Mark the published releases as pre-releases
+    pub prerelease: bool,
+    /// Files to attach to the published releases
+    pub assets: Vec<PathBuf>,
+}
+
+/// How a release step turned out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Done,
+    Skipped,
+    Failed,
+}
+
+/// One step of the release, for the summary
+#[derive(Debug, Clone)]
+struct Step {
+    name: &'static str,
+    outcome: Outcome,
+    detail: String,
+}
+
+/// Record the result of a step, passing a failure on
+fn record(steps: &mut Vec<Step>, name: &'static str, result: Result<String>) -> Result<()> {
+    match result {
+        Ok(detail) => {
+            steps.push(Step {
+                name,
+                outcome: Outcome::Done,
+                detail,
+            });
+            Ok(())
+        }
+        Err(e) => {
+            steps.push(Step {
+                name,
+                outcome: Outcome::Failed,
+                detail: e.to_string(),
+            });
+            Err(e)
+        }
+    }
+}
+
+fn skip(steps: &mut Vec<Step>, name: &'static str, reason: &str) {
+    steps.push(Step {
+        name,
+        outcome: Outcome::Skipped,
+        detail: format!("skipped ({reason})"),
+    });
+}
+
+/// Release `version`, which is also the tag name
+pub async fn execute(version: String, options: ReleaseOptions) -> Result<()> {
+    info!("Releasing {}", version);
+    println!("\n🚀 Releasing {version}\n");
+
+    let mut steps = Vec::new();
+    let result = run(&version, &options, &mut steps).await;
+    print_summary(&steps);
+
+    if result.is_ok() {
+        println!("\n{} Released {version}", Status::Success.colored_icon());
+    }
+    result
+}
+
+async fn run(version: &str, options: &ReleaseOptions, steps: &mut Vec<Step>) -> Result<()> {
+    let git_ops = GitOperations::open(".")?;
+    let checks = check(&git_ops, version, options.no_tag)
+        .and_then(|detail| check_assets(&options.assets).map(|()| detail));
+    record(steps, "checks", checks)?;
+
+    let message = options
+        .message
+        .clone()
+        .unwrap_or_else(|| format!("Release {version}"));
+    let mut release_notes = message.clone();
+
+    if options.no_changelog {
+        skip(steps, "changelog", "--no-changelog");
+    } else {
+        let result = write_release_notes(
+            git_ops.inner(),
+            version,
+            options.changelog.clone(),
+            options.format,
+        )
+        .and_then(|notes| {
+            println!("{}", notes.notes.trim_end());
+            release_notes.clone_from(&notes.notes);
+            let oid = git_ops.commit_paths(
+                &[Path::new(&notes.path)],
+                &format!("chore(release): {version}"),
+            )?;
+            Ok(format!("{} updated in {}", notes.path, short(oid)))
+        });
+        record(steps, "changelog", result)?;
+    }
+
+    if options.no_tag {
+        skip(steps, "tag", "--no-tag");
+    } else {
+        record(
+            steps,
+            "tag",
+            create_tag(&git_ops, version, &message, options.sign),
+        )?;
+    }
+
+    if options.no_push {
+        skip(steps, "push", "--no-push");
+    } else {
+        let tags = if options.no_tag {
+            TagMode::None
+        } else {
+            TagMode::Follow
+        };
+        let result = push::execute(
+            None,
+            false,
+            Vec::new(),
+            None,
+            false,
+            false,
+            None,
+            tags,
+            None,
+        )
+        .await
+        .map(|()| {
+            if options.no_tag {
+                "branch pushed".to_string()
+            } else {
+                "branch and tag pushed".to_string()
+            }
+        });
+        record(steps, "push", result)?;
+    }
+
+    if options.publish {
+        let result = publish(version, &release_notes, options).await;
+        record(steps, "releases", result)?;
+    } else {
+        skip(steps, "releases", "use --publish to create them");
+    }
+
+    Ok(())
+}
+
+/// What publishing did on one remote
+#[derive(Debug)]
+struct Published {
+    release: Release,
+    /// Each asset's file name and upload result, in the order given
+    assets: Vec<(String, std::result::Result<ReleaseAsset, String>)>,
+}
+
+/// The outcome on one remote: published, `None` without release support, or
+/// why the release couldn't be created
+type PublishResult = std::result::Result<Option<Published>, String>;
+
+/// Create the release for `version` with its assets on every enabled remote
+/// at once, skipping hosts without release support
+async fn publish(version: &str, notes: &str, options: &ReleaseOptions) -> Result<String> {
+    let config = Config::load()?;
+    let mut names: Vec<String> = config.enabled_remotes().into_keys().collect();
+    names.sort();
+
+    let releases = names.iter().map(|name| async {
+        let provider =
+            connect_remote(&config, name, &config.remotes[name]).map_err(|e| e.to_string())?;
+        let repo = remote_repo_name(name)
+            .ok_or_else(|| "can't tell the repository from its URL".to_string())?;
+        publish_on(
+            provider.as_ref(),
+            &repo,
+            version,
+            notes,
+            options.prerelease,
+            &options.assets,
+        )
+        .await
+    });
+    let results = names
+        .iter()
+        .cloned()
+        .zip(join_all(releases).await)
+        .collect::<Vec<_>>();
+
+    print_published(&results);
+    summarize_releases(&results)
+}
+
+/// Create the release on `provider` and upload `assets` to it one after the
+/// other, or `None` when it has no release support
+async fn publish_on(
+    provider: &dyn Provider,
+    repo: &str,
+    version: &str,
+    notes: &str,
+    prerelease: bool,
+    assets: &[PathBuf],
+) -> PublishResult {
+    if !provider.supports_releases() {
+        return Ok(None);
+    }
+    let release = provider
+        .create_release(repo, version, version, notes, prerelease)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut uploads = Vec::with_capacity(assets.len());
+    for path in assets {
+        let result = provider
+            .upload_release_asset(repo, &release.id, path)
+            .await
+            .map_err(|e| e.to_string());
+        uploads.push((path.display().to_string(), result));
+    }
+    Ok(Some(Published {
+        release,
+        assets: uploads,
+    }))
+}
+
+/// Each remote's release, and below it each asset
+fn print_published(results: &[(String, PublishResult)]) {
+    for (name, result) in results {
+        match result {
+            Ok(Some(published)) => {
+                println!(
+                    "  {} {name}: {}",
+                    Status::Success.colored_icon(),
+                    published.release.url
+                );
+                for (file, upload) in &published.assets {
+                    match upload {
+                        Ok(asset) => println!(
+                            "    {} {} ({})",
+                            Status::Success.colored_icon(),
+                            asset.name,
+                            format_bytes(asset.size)
+                        ),
+                        Err(e) => println!("    {} {file}: {e}", Status::Error.colored_icon()),
+                    }
+                }
+            }
+            Ok(None) => println!(
+                "  {} {name}: no release support",
+                Status::Info.colored_icon()
+            ),
+            Err(e) => println!("  {} {name}: {e}", Status::Error.colored_icon()),
+        }
+    }
+}
+
+/// One line for the summary, failing if a release or an upload failed
+fn summarize_releases(results: &[(String, PublishResult)]) -> Result<String> {
+    let (mut created, mut unsupported, mut failed) = (Vec::new(), Vec::new(), Vec::new());
+    for (name, result) in results {
+        match result {
+            Ok(Some(published)) => {
+                let uploaded = published.assets.iter().filter(|(_, r)| r.is_ok()).count();
+                if published.assets.is_empty() {
+                    created.push(name.clone());
+                } else {
+                    created.push(format!(
+                        "{name} ({uploaded}/{} assets)",
+                        published.assets.len()
+                    ));
+                }
+                for (file, upload) in &published.assets {
+                    if let Err(e) = upload {
+                        failed.push(format!("{name}: {file}: {e}"));
+                    }
+                }
+            }
+            Ok(None) => unsupported.push(name.clone()),
+            Err(e) => failed.push(format!("{name}: {e}")),
+        }
+    }
+
+    let mut parts = Vec::new();
+    if !created.is_empty() {
+        parts.push(format!("created on {}", created.join(", ")));
+    }
+    if !unsupported.is_empty() {
+        parts.push(format!(
+            "skipped {} (no release support)",
+            unsupported.join(", ")
+        ));
+    }
+    if !failed.is_empty() {
+        parts.push(format!("failed on {}", failed.join("; ")));
+        return Err(MultiGitError::other(parts.join("; ")));
+    }
+    if parts.is_empty() {
+        return Ok("no enabled remotes".to_string());
+    }
+    Ok(parts.join("; "))
+}
+
+/// That every asset to upload is a file
+fn check_assets(assets: &[PathBuf]) -> Result<()> {
+    match assets.iter().find(|path| !path.is_file()) {
+        Some(path) => Err(MultiGitError::invalid_input(format!(
+            "Asset '{}' is not a file",
+            path.display()
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// That the repository is ready for a release: on a branch, not mid-operation,
+/// with a clean working tree, and (when tagging) without the tag yet
+fn check(git_ops: &GitOperations, version: &str, no_tag: bool) -> Result<String> {
+    let branch = git_ops.current_branch()?;
+    if let Some(operation) = git_ops.operation_in_progress()? {
+        return Err(MultiGitError::other(format!(
+            "{operation}. {}",
+            operation.guidance()
+        )));
+    }
+    if !git_ops.is_clean()? {
+        return Err(MultiGitError::other(
+            "Working tree has uncommitted changes; commit or stash them first",
+        ));
+    }
+
+    if !no_tag {
+        let refname = format!("refs/tags/{version}");
+        if !git2::Reference::is_valid_name(&refname) {
+            return Err(MultiGitError::invalid_input(format!(
+                "'{version}' is not a valid tag name"
+            )));
+        }
+        if git_ops.inner().find_reference(&refname).is_ok() {
+            return Err(MultiGitError::other(format!(
+                "Tag '{version}' already exists"
+            )));
+        }
+    }
+    Ok(format!("clean working tree on '{branch}'"))
+}
+
+/// Create the annotated (or, with `sign`, signed) tag `name` on HEAD
+fn create_tag(git_ops: &GitOperations, name: &str, message: &str, sign: bool) -> Result<String> {
+    let head = git_ops.head_commit()?;
+    if sign {
+        let config = Config::load()?;
+        create_signed(
+            git_ops.workdir()?,
+            name,
+            message,
+            config.security.signing_key(),
+        )?;
+        return Ok(format!("signed tag on {}", short(head.id())));
+    }
+
+    let repo = git_ops.inner();
+    let tagger = repo
+        .signature()
+        .or_else(|_| git2::Signature::now("multigit", "multigit@localhost"))
+        .map_err(MultiGitError::GitError)?;
+    repo.tag(name, head.as_object(), &tagger, message, false)
+        .map_err(MultiGitError::GitError)?;
+    Ok(format!("annotated tag on {}", short(head.id())))
+}
+
+fn short(oid: git2::Oid) -> String {
+    oid.to_string()[..7].to_string()
+}
+
+fn print_summary(steps: &[Step]) {
+    println!("\n📋 Release summary\n");
+    let mut table = Table::new(
+        ["", "Step", "Result"]
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+    );
+    for step in steps {
+        let status = match step.outcome {
+            Outcome::Done => Status::Success,
+            Outcome::Skipped => Status::Info,
+            Outcome::Failed => Status::Error,
+        };
+        table.add_row(vec![
+            status.icon().to_string(),
+            step.name.to_string(),
+            step.detail.clone(),
+        ]);
+    }
+    if colors::enabled() {
+        table.print();
+    } else {
+        table.no_colors().print();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::MockProvider;
+    use tempfile::TempDir;
+
+    fn repo() -> (TempDir, GitOperations) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        {
+            let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+            let tree = repo
+                .find_tree(repo.index().unwrap().write_tree().unwrap())
+                .unwrap();
+            repo.commit(Some("HEAD"), &sig, &sig, "feat: start", &tree, &[])
+                .unwrap();
+        }
+        let git_ops = GitOperations::open(temp_dir.path()).unwrap();
+        (temp_dir, git_ops)
+    }
+
+    #[test]
+    fn test_check() {
+        let (temp_dir, git_ops) = repo();
+        assert!(check(&git_ops, "v1.0.0", false).is_ok());
+        assert!(check(&git_ops, "not a tag", false).is_err());
+
+        create_tag(&git_ops, "v1.0.0", "Release v1.0.0", false).unwrap();
+        let exists = check(&git_ops, "v1.0.0", false).unwrap_err();
+        assert!(exists.to_string().contains("already exists"));
+        assert!(check(&git_ops, "v1.0.0", true).is_ok());
+
+        std::fs::write(temp_dir.path().join("dirty.txt"), "x").unwrap();
+        let dirty = check(&git_ops, "v1.1.0", false).unwrap_err();
+        assert!(dirty.to_string().contains("uncommitted changes"));
+    }
+
+    #[tokio::test]
+    async fn test_publish_skips_unsupported_hosts() {
+        let gitea = MockProvider::new("gitea");
+        let github = MockProvider::new("github").with_releases();
+
+        let skipped = publish_on(&gitea, "demo", "v1.0.0", "notes", false, &[]).await;
+        assert!(matches!(skipped, Ok(None)));
+        assert!(gitea.calls().is_empty());
+
+        let created = publish_on(&github, "demo", "v1.0.0", "notes", false, &[]).await;
+        assert_eq!(
+            created.as_ref().unwrap().as_ref().unwrap().release.tag,
+            "v1.0.0"
+        );
+        assert_eq!(github.calls(), vec!["create_release:demo/v1.0.0"]);
+
+        let results = vec![
+            ("gitea".to_string(), skipped),
+            ("github".to_string(), created),
+        ];
+        assert_eq!(
+            summarize_releases(&results).unwrap(),
+            "created on github; skipped gitea (no release support)"
+        );
+
+        let failed = vec![("gitlab".to_string(), Err("403 Forbidden".to_string()))];
+        let err = summarize_releases(&failed).unwrap_err();
+        assert!(err.to_string().contains("failed on gitlab: 403 Forbidden"));
+    }
+
+    #[tokio::test]
+    async fn test_publish_uploads_assets() {
+        let temp_dir = TempDir::new().unwrap();
+        let binary = temp_dir.path().join("bin.tar.gz");
+        std::fs::write(&binary, b"artifact").unwrap();
+        let missing = temp_dir.path().join("missing.zip");
+        assert!(check_assets(std::slice::from_ref(&binary)).is_ok());
+        assert!(check_assets(&[binary.clone(), missing.clone()]).is_err());
+
+        let github = MockProvider::new("github").with_releases();
+        let result = publish_on(
+            &github,
+            "demo",
+            "v1.0.0",
+            "notes",
+            false,
+            &[binary, missing],
+        )
+        .await;
+        let published = result.as_ref().unwrap().as_ref().unwrap();
+        assert_eq!(published.assets[0].1.as_ref().unwrap().size, 8);
+        assert!(published.assets[1].1.is_err());
+        assert_eq!(
+            github.calls(),
+            vec![
+                "create_release:demo/v1.0.0",
+                "upload_release_asset:demo/v1.0.0/bin.tar.gz",
+                "upload_release_asset:demo/v1.0.0/missing.zip",
+            ]
+        );
+
+        let err = summarize_releases(&[("github".to_string(), result)]).unwrap_err();
+        let summary = err.to_string();
+        assert!(summary.contains("created on github (1/2 assets)"));
+        assert!(summary.contains("missing.zip"));
+    }
+}
@@ -3,32 +3,45 @@
 //! Handles adding, removing, listing, testing, and updating Git hosting remotes.
 //! Integrates with the authentication manager for secure credential storage.
 
+use crate::api::client::ClientOptions;
 use crate::cli::interactive;
-use crate::core::auth::{AuthBackend, AuthManager};
+use crate::core::auth::{remote_token_env_var, AuthBackend, AuthManager};
 use crate::core::config::{Config, RemoteConfig};
 use crate::git::operations::GitOperations;
-use crate::providers::factory::{create_provider, get_provider_host, is_supported_provider};
-use crate::providers::traits::Protocol;
+use crate::models::{Repository, TokenInfo};
+use crate::providers::factory::{
+    connect_remote, create_provider_with_options, get_provider_host, is_supported_provider,
+    remote_repo_name, supported_providers, CUSTOM_FLAVORS, CUSTOM_PROVIDER,
+};
+use crate::providers::traits::{Protocol, Provider};
+use crate::ui::formatter::{colors, Status, Table};
 use crate::utils::error::{MultiGitError, Result};
-use tracing::{info, warn};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
 
 /// Add a new remote provider
+///
+/// The remote is saved to `config_file`, or to the user config when `None`.
 #[allow(clippy::too_many_lines)]
 pub async fn add_remote(
     provider_name: String,
     username: String,
     api_url: Option<String>,
+    api_flavor: Option<String>,
     interactive_mode: bool,
+    config_file: Option<&Path>,
 ) -> Result<()> {
     info!("Adding remote: {} for user {}", provider_name, username);
 
     // Validate provider name
     let provider_lower = provider_name.to_lowercase();
-    if !is_supported_provider(&provider_lower) {
-        return Err(MultiGitError::other(format!(
-            "Unsupported provider '{provider_name}'. Supported providers: github, gitlab, bitbucket, codeberg, gitea"
-        )));
-    }
+    let api_flavor = api_flavor.map(|flavor| flavor.to_lowercase());
+    let implementation =
+        implementation_for(&provider_lower, api_flavor.as_deref(), api_url.as_deref())?;
 
     // Load config
     let mut config = Config::load()?;
@@ -69,16 +82,17 @@ pub async fn add_remote(
 
     // Get host for credential binding
     let allow_insecure = config.security.allow_insecure_http;
-    let host = get_provider_host(&provider_lower, api_url.as_deref(), allow_insecure)?;
+    let host = get_provider_host(implementation, api_url.as_deref(), allow_insecure)?;
 
     // Test connection before saving
     interactive::print_info(&format!("Testing connection to {provider_name}..."));
-    let provider = create_provider(
-        &provider_lower,
+    let provider = create_provider_with_options(
+        implementation,
         &username,
         &token,
         api_url.as_deref(),
         allow_insecure,
+        &ClientOptions::from_settings(&config.settings),
     )?;
 
     match provider.test_connection().await {
@@ -101,31 +115,41 @@ pub async fn add_remote(
     // Store credentials with host binding
     let auth_manager = AuthManager::new(AuthBackend::Keyring, config.security.audit_log);
 
-    auth_manager.store_credential(&provider_lower, &host, &username, &token)?;
+    auth_manager.store_remote_credential(&provider_lower, &token)?;
     interactive::print_success(&format!(
-        "Credentials stored securely (bound to host: {host})"
+        "Credentials stored securely for remote '{provider_lower}' (host: {host})"
     ));
 
     // Update config
     let remote_config = RemoteConfig {
         username: username.clone(),
         api_url,
-        enabled: true,
         provider: Some(provider_lower.clone()),
-        use_ssh: false,
-        priority: 0,
+        api_flavor,
+        ..Default::default()
     };
 
-    config.remotes.insert(provider_lower.clone(), remote_config);
-    config.save()?;
+    if let Some(path) = config_file {
+        let mut target = Config::load_from_file(path)?;
+        target.remotes.insert(provider_lower.clone(), remote_config);
+        target.save_to_file(path)?;
+    } else {
+        config.remotes.insert(provider_lower.clone(), remote_config);
+        config.save()?;
+    }
 
     // Add the actual git remote to .git/config
     if let Ok(git_ops) = GitOperations::open(".") {
         // Determine repository name from current directory or remote repo
-        let repo_name = std::env::current_dir()
+        let dir_name = std::env::current_dir()
             .ok()
             .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
             .unwrap_or_else(|| "repo".to_string());
+        let repo_name = if interactive_mode {
+            choose_repository(provider.as_ref(), &dir_name).await?
+        } else {
+            dir_name
+        };
 
         // Get the remote URL from provider
         let remote_url = provider.get_remote_url(&repo_name, Protocol::Https);
@@ -170,10 +194,128 @@ pub async fn add_remote(
     Ok(())
 }
 
+/// The provider implementation serving a new remote: `provider` itself, or
+/// for a `custom` provider the `api_flavor` it is compatible with
+fn implementation_for<'a>(
+    provider: &'a str,
+    api_flavor: Option<&'a str>,
+    api_url: Option<&str>,
+) -> Result<&'a str> {
+    if provider != CUSTOM_PROVIDER {
+        if api_flavor.is_some() {
+            return Err(MultiGitError::invalid_input(format!(
+                "--flavor only applies to the '{CUSTOM_PROVIDER}' provider"
+            )));
+        }
+        if !is_supported_provider(provider) {
+            return Err(MultiGitError::other(format!(
+                "Unsupported provider '{provider}'. Supported providers: {}, {CUSTOM_PROVIDER}",
+                supported_providers().join(", ")
+            )));
+        }
+        return Ok(provider);
+    }
+
+    let flavor = api_flavor
+        .filter(|flavor| CUSTOM_FLAVORS.contains(flavor))
+        .ok_or_else(|| {
+            MultiGitError::invalid_input(format!(
+                "A custom provider needs --flavor set to one of: {}",
+                CUSTOM_FLAVORS.join(", ")
+            ))
+        })?;
+    if api_url.is_none() {
+        return Err(MultiGitError::invalid_input(
+            "A custom provider needs its API base URL; use --url",
+        ));
+    }
+    Ok(flavor)
+}
+
+/// Ask which of the user's repositories on `provider` to link, falling back to
+/// `dir_name` when the provider can't list them or the picker is dismissed
+async fn choose_repository(provider: &dyn Provider, dir_name: &str) -> Result<String> {
+    let repos = match provider.list_repos().await {
+        Ok(repos) if !repos.is_empty() => repos,
+        Ok(_) => return Ok(dir_name.to_string()),
+        Err(e) => {
+            debug!("Could not list repositories on {}: {e}", provider.name());
+            return Ok(dir_name.to_string());
+        }
+    };
+
+    let labels: Vec<String> = repos.iter().map(repository_label).collect();
+    let items: Vec<&str> = labels.iter().map(String::as_str).collect();
+    let default = default_repository_index(&repos, dir_name);
+    let choice = interactive::select_option("Repository to link", &items, default)?;
+
+    Ok(choice.map_or_else(|| dir_name.to_string(), |i| repos[i].slug().to_string()))
+}
+
+/// The repository named like the current directory, otherwise the most
+/// recently updated one (the first)
+fn default_repository_index(repos: &[Repository], dir_name: &str) -> usize {
+    repos
+        .iter()
+        .position(|repo| repo.slug().eq_ignore_ascii_case(dir_name))
+        .unwrap_or(0)
+}
+
+fn repository_label(repo: &Repository) -> String {
+    let mut label = repo.slug().to_string();
+    if repo.private {
+        label.push_str(" (private)");
+    }
+    if let Some(description) = repo.description.as_deref().filter(|d| !d.is_empty()) {
+        label.push_str(" - ");
+        label.push_str(description);
+    }
+    label
+}
+
+/// A remote as printed by `remote list --json`; the token is never included
+#[derive(Debug, Serialize)]
+struct RemoteListing {
+    name: String,
+    provider: String,
+    username: String,
+    api_url: Option<String>,
+    enabled: bool,
+    use_ssh: bool,
+    priority: i32,
+    credential_stored: bool,
+}
+
+impl RemoteListing {
+    fn new(name: &str, remote_config: &RemoteConfig, credential_stored: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            provider: remote_config
+                .provider
+                .clone()
+                .unwrap_or_else(|| name.to_string()),
+            username: remote_config.username.clone(),
+            api_url: remote_config.api_url.clone(),
+            enabled: remote_config.enabled,
+            use_ssh: remote_config.use_ssh,
+            priority: remote_config.priority,
+            credential_stored,
+        }
+    }
+}
+
 /// List all configured remotes
-pub fn list_remotes(detailed: bool) -> Result<()> {
+///
+/// With `detailed`, also looks up this repository on each enabled remote and
+/// shows the stars, forks, open issues and archived state it reports. With
+/// `json`, prints them as a JSON array sorted by name instead.
+pub async fn list_remotes(detailed: bool, json: bool) -> Result<()> {
     let config = Config::load()?;
 
+    if json {
+        return print_remotes_json(&config);
+    }
+
     if config.remotes.is_empty() {
         interactive::print_info("No remotes configured.");
         interactive::print_info("Add a remote with: multigit remote add <provider> <username>");
@@ -182,32 +324,26 @@ pub fn list_remotes(detailed: bool) -> Result<()> {
 
     println!("\n📋 Configured Remotes:\n");
 
+    if detailed {
+        print_remotes_table(&config).await;
+        println!();
+        return Ok(());
+    }
+
     for (name, remote_config) in &config.remotes {
-        let status = if remote_config.enabled { "✓" } else { "✗" };
+        let status = Status::of_enabled(remote_config.enabled).colored_icon();
         let provider_display = remote_config
             .provider
             .as_ref()
             .map_or_else(|| name.clone(), std::string::ToString::to_string);
 
-        println!("  {status} {name} ({provider_display})");
-
-        if detailed {
-            println!("      Username: {}", remote_config.username);
-
-            if let Some(url) = &remote_config.api_url {
-                println!("      API URL: {url}");
-            }
-
-            println!("      Enabled: {}", remote_config.enabled);
+        if remote_config.groups.is_empty() {
+            println!("  {status} {name} ({provider_display})");
+        } else {
             println!(
-                "      Protocol: {}",
-                if remote_config.use_ssh {
-                    "SSH"
-                } else {
-                    "HTTPS"
-                }
+                "  {status} {name} ({provider_display}) [{}]",
+                remote_config.groups.join(", ")
             );
-            println!();
         }
     }
 
@@ -215,6 +351,143 @@ pub fn list_remotes(detailed: bool) -> Result<()> {
     Ok(())
 }
 
+/// `remote list --detailed`: one table row per remote, sorted by name
+async fn print_remotes_table(config: &Config) {
+    let mut table = Table::new(
+        [
+            "",
+            "Name",
+            "Provider",
+            "Username",
+            "Protocol",
+            "Groups",
+            "API URL",
+            "Repository",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect(),
+    );
+    if !colors::enabled() {
+        table = table.no_colors();
+    }
+
+    let mut names: Vec<&String> = config.remotes.keys().collect();
+    names.sort();
+    for name in names {
+        let remote_config = &config.remotes[name];
+        let repository = if remote_config.enabled {
+            fetch_repository(config, name, remote_config).await
+        } else {
+            None
+        };
+
+        table.add_row(vec![
+            Status::of_enabled(remote_config.enabled).colored_icon(),
+            name.clone(),
+            remote_config
+                .provider
+                .clone()
+                .unwrap_or_else(|| name.clone()),
+            remote_config.username.clone(),
+            if remote_config.use_ssh {
+                "SSH"
+            } else {
+                "HTTPS"
+            }
+            .to_string(),
+            or_dash(remote_config.groups.join(", ")),
+            or_dash(remote_config.api_url.clone().unwrap_or_default()),
+            or_dash(
+                repository
+                    .as_ref()
+                    .map(repository_summary)
+                    .unwrap_or_default(),
+            ),
+        ]);
+    }
+
+    table.print();
+}
+
+fn or_dash(value: String) -> String {
+    if value.is_empty() {
+        "-".to_string()
+    } else {
+        value
+    }
+}
+
+fn print_remotes_json(config: &Config) -> Result<()> {
+    let auth_manager = AuthManager::new(AuthBackend::Keyring, false);
+    let mut names: Vec<&String> = config.remotes.keys().collect();
+    names.sort();
+
+    let listings: Vec<RemoteListing> = names
+        .into_iter()
+        .map(|name| {
+            let remote_config = &config.remotes[name];
+            let provider = remote_config.provider_name(name);
+            let credential_stored = get_provider_host(
+                provider,
+                remote_config.api_url.as_deref(),
+                config.security.allow_insecure_http,
+            )
+            .is_ok_and(|host| {
+                auth_manager.has_remote_credential(name)
+                    || auth_manager.has_credential(provider, &host, &remote_config.username)
+            });
+            RemoteListing::new(name, remote_config, credential_stored)
+        })
+        .collect();
+
+    let output = serde_json::to_string_pretty(&listings)
+        .map_err(|e| MultiGitError::other(format!("Failed to serialize remotes: {e}")))?;
+    println!("{output}");
+    Ok(())
+}
+
+/// This repository on `name`, found through the git remote of the same name
+async fn fetch_repository(
+    config: &Config,
+    name: &str,
+    remote_config: &RemoteConfig,
+) -> Option<Repository> {
+    let repo_name = remote_repo_name(name)?;
+    let provider = connect_remote(config, name, remote_config)
+        .map_err(|e| debug!("Skipping repository lookup on '{}': {}", name, e))
+        .ok()?;
+
+    provider
+        .get_repo(&repo_name)
+        .await
+        .map_err(|e| debug!("Could not fetch '{}' from '{}': {}", repo_name, name, e))
+        .ok()
+}
+
+/// One-line summary of a repository's metadata, leaving out what isn't reported
+pub(crate) fn repository_summary(repository: &Repository) -> String {
+    let mut parts = vec![repository
+        .full_name
+        .clone()
+        .unwrap_or_else(|| repository.name.clone())];
+
+    if let Some(stars) = repository.stars {
+        parts.push(format!("★ {stars}"));
+    }
+    if let Some(forks) = repository.forks {
+        parts.push(format!("{forks} fork(s)"));
+    }
+    if let Some(open_issues) = repository.open_issues {
+        parts.push(format!("{open_issues} open issue(s)"));
+    }
+    if repository.archived == Some(true) {
+        parts.push("archived".to_string());
+    }
+
+    parts.join("  ")
+}
+
 /// Remove a remote from configuration
 pub fn remove_remote(name: String, force: bool) -> Result<()> {
     let mut config = Config::load()?;
@@ -258,13 +531,31 @@ pub fn remove_remote(name: String, force: bool) -> Result<()> {
     // Remove credentials
     let auth_manager = AuthManager::new(AuthBackend::Keyring, config.security.audit_log);
 
-    // Get host for credential deletion (best effort)
-    if let Ok(host) = get_provider_host(&name_lower, remote_config.api_url.as_deref(), false) {
-        // Attempt to remove credentials (don't fail if they don't exist)
-        if let Err(e) = auth_manager.remove_credential(&name_lower, &host, &remote_config.username)
+    // Attempt to remove credentials (don't fail if they don't exist)
+    if let Err(e) = auth_manager.delete_remote_credential(&name_lower) {
+        debug!("No remote-keyed credential to remove: {}", e);
+    }
+
+    // The provider-keyed credential predates per-remote tokens and may be
+    // shared, so it only goes once no remaining remote uses the same account
+    let provider_name = remote_config.provider_name(&name_lower);
+    let shared = config.remotes.iter().any(|(other_name, other)| {
+        other.provider_name(other_name) == provider_name
+            && other.username == remote_config.username
+            && other.api_url == remote_config.api_url
+    });
+    if !shared {
+        // Get host for credential deletion (best effort)
+        if let Ok(host) = get_provider_host(provider_name, remote_config.api_url.as_deref(), false)
         {
-            warn!("Failed to remove credentials: {}", e);
-            interactive::print_warning("Could not remove stored credentials (they may not exist)");
+            if let Err(e) =
+                auth_manager.remove_credential(provider_name, &host, &remote_config.username)
+            {
+                warn!("Failed to remove credentials: {}", e);
+                interactive::print_warning(
+                    "Could not remove stored credentials (they may not exist)",
+                );
+            }
         }
     }
 
@@ -273,8 +564,82 @@ pub fn remove_remote(name: String, force: bool) -> Result<()> {
     Ok(())
 }
 
+/// Enable or disable a remote, or every remote in `group`
+pub fn set_enabled(name: Option<String>, group: Option<String>, enabled: bool) -> Result<()> {
+    let mut config = Config::load()?;
+
+    let names: Vec<String> = match (name, group) {
+        (_, Some(group)) => {
+            let mut names: Vec<String> = config.remotes_in_group(&group).into_keys().collect();
+            if names.is_empty() {
+                return Err(MultiGitError::InvalidInput(format!(
+                    "No remotes in group '{group}'"
+                )));
+            }
+            names.sort();
+            names
+        }
+        (Some(name), None) => {
+            let name_lower = name.to_lowercase();
+            if !config.remotes.contains_key(&name_lower) {
+                return Err(MultiGitError::other(format!("Remote '{name}' not found")));
+            }
+            vec![name_lower]
+        }
+        (None, None) => {
+            return Err(MultiGitError::InvalidInput(
+                "Give a remote name or --group".to_string(),
+            ))
+        }
+    };
+
+    for name in &names {
+        if let Some(remote) = config.remotes.get_mut(name) {
+            remote.enabled = enabled;
+        }
+    }
+    config.save()?;
+
+    let action = if enabled { "Enabled" } else { "Disabled" };
+    interactive::print_success(&format!("{action} {}", names.join(", ")));
+
+    Ok(())
+}
+
+/// Replace the groups of a remote; an empty list clears them
+pub fn set_groups(name: String, groups: Vec<String>) -> Result<()> {
+    let mut config = Config::load()?;
+    let name_lower = name.to_lowercase();
+
+    let remote = config
+        .remotes
+        .get_mut(&name_lower)
+        .ok_or_else(|| MultiGitError::other(format!("Remote '{name}' not found")))?;
+
+    let mut groups: Vec<String> = groups
+        .iter()
+        .map(|group| group.trim().to_string())
+        .filter(|group| !group.is_empty())
+        .collect();
+    groups.sort();
+    groups.dedup();
+    remote.groups = groups;
+    let summary = if remote.groups.is_empty() {
+        format!("Cleared groups of '{name_lower}'")
+    } else {
+        format!("'{name_lower}' is in: {}", remote.groups.join(", "))
+    };
+    config.save()?;
+
+    interactive::print_success(&summary);
+
+    Ok(())
+}
+
 /// Test connection to a remote
-pub async fn test_remote(name: String) -> Result<()> {
+///
+/// `timeout_secs` overrides `settings.api_timeout_seconds` for this test.
+pub async fn test_remote(name: String, timeout_secs: Option<u64>) -> Result<()> {
     let config = Config::load()?;
 
     let name_lower = name.to_lowercase();
@@ -289,10 +654,11 @@ pub async fn test_remote(name: String) -> Result<()> {
     }
 
     // Get host for credential retrieval
+    let provider_name = remote_config.provider_name(&name_lower);
     let allow_insecure = config.security.allow_insecure_http;
     let allow_env = config.security.allow_env_tokens;
     let host = get_provider_host(
-        &name_lower,
+        provider_name,
         remote_config.api_url.as_deref(),
         allow_insecure,
     )?;
@@ -301,7 +667,13 @@ pub async fn test_remote(name: String) -> Result<()> {
     let auth_manager = AuthManager::new(AuthBackend::Keyring, config.security.audit_log);
 
     let token = auth_manager
-        .retrieve_credential(&name_lower, &host, &remote_config.username, allow_env)
+        .retrieve_remote_credential(
+            &name_lower,
+            provider_name,
+            &host,
+            &remote_config.username,
+            allow_env,
+        )
         .map_err(|e| {
             MultiGitError::auth(
                 name_lower.clone(),
@@ -312,17 +684,49 @@ pub async fn test_remote(name: String) -> Result<()> {
     // Create provider and test
     interactive::print_info(&format!("Testing connection to {name_lower}..."));
 
-    let provider = create_provider(
-        &name_lower,
+    let mut options = config.client_options_for(remote_config);
+    if let Some(secs) = timeout_secs {
+        options = options.with_timeout(Duration::from_secs(secs.max(1)));
+    }
+    let timeout = options.timeout;
+
+    let provider = create_provider_with_options(
+        provider_name,
         &remote_config.username,
         &token,
         remote_config.api_url.as_deref(),
         allow_insecure,
+        &options,
     )?;
 
-    match provider.test_connection().await {
+    test_provider(&name_lower, provider.as_ref(), timeout).await?;
+
+    let warn_days = config.settings.token_expiry_warning_days;
+    if let Some(token) = check_token_expiry(&name_lower, provider.as_ref(), warn_days).await {
+        record_token_expiry(&name_lower, token.expires_at);
+    }
+
+    Ok(())
+}
+
+/// Check that `provider` is reachable and accepts its credentials
+///
+/// Gives up with a "connection timed out" error after `timeout`, retries
+/// included. Prints rate limit information on success when the provider
+/// reports it.
+pub async fn test_provider(name: &str, provider: &dyn Provider, timeout: Duration) -> Result<()> {
+    let result = tokio::time::timeout(timeout, provider.test_connection())
+        .await
+        .map_err(|_| {
+            MultiGitError::network(format!(
+                "Connection to {name} timed out after {}s",
+                timeout.as_secs()
+            ))
+        })?;
+
+    match result {
         Ok(true) => {
-            interactive::print_success(&format!("✓ {name_lower} connection successful"));
+            interactive::print_success(&format!("{name} connection successful"));
 
             // Try to get rate limit info
             if let Ok(rate_limit) = provider.get_rate_limit().await {
@@ -335,7 +739,7 @@ pub async fn test_remote(name: String) -> Result<()> {
             Ok(())
         }
         Ok(false) => Err(MultiGitError::auth(
-            name_lower,
+            name.to_string(),
             "Authentication failed. Credentials may be invalid or expired".to_string(),
         )),
         Err(e) => Err(MultiGitError::network(format!(
@@ -344,6 +748,91 @@ pub async fn test_remote(name: String) -> Result<()> {
     }
 }
 
+/// Report when the token behind `provider` expires
+///
+/// Warns if that is within `warn_days`. Returns `None` when the provider
+/// can't describe its tokens or the lookup fails.
+pub async fn check_token_expiry(
+    name: &str,
+    provider: &dyn Provider,
+    warn_days: u32,
+) -> Option<TokenInfo> {
+    let token = match provider.token_info().await {
+        Ok(Some(token)) => token,
+        Ok(None) => return None,
+        Err(e) => {
+            debug!("Could not fetch token details for {}: {}", name, e);
+            return None;
+        }
+    };
+
+    let now = Utc::now();
+    let description = token.describe_expiry(now);
+    if token.expires_within(warn_days, now) {
+        interactive::print_warning(&format!(
+            "Token for {name} {description}. Renew it, then run 'multigit remote update {name}'"
+        ));
+    } else {
+        println!("\n  Token: {description}");
+    }
+
+    Some(token)
+}
+
+/// Remember the token expiry so `status` and the daemon can warn offline
+pub(crate) fn record_token_expiry(name: &str, expires_at: Option<DateTime<Utc>>) {
+    let result = Config::load().and_then(|mut config| match config.remotes.get_mut(name) {
+        Some(remote) if remote.token_expires_at != expires_at => {
+            remote.token_expires_at = expires_at;
+            config.save()
+        }
+        _ => Ok(()),
+    });
+
+    if let Err(e) = result {
+        warn!("Could not record token expiry for {}: {}", name, e);
+    }
+}
+
+/// A new token for remote `name` from its own `MULTIGIT_<REMOTE>_TOKEN`, or
+/// else the provider-level `MULTIGIT_<PROVIDER>_TOKEN`
+pub(crate) fn token_from_env(name: &str, provider_name: &str) -> Option<String> {
+    std::env::var(remote_token_env_var(name))
+        .or_else(|_| std::env::var(format!("MULTIGIT_{}_TOKEN", provider_name.to_uppercase())))
+        .ok()
+        .filter(|token| !token.trim().is_empty())
+}
+
+/// Check that `token` authenticates as remote `name`'s user, returning the
+/// connected provider
+pub(crate) async fn verify_token(
+    config: &Config,
+    name: &str,
+    remote_config: &RemoteConfig,
+    token: &str,
+) -> Result<Arc<dyn Provider>> {
+    let provider_name = remote_config.provider_name(name);
+    let provider = create_provider_with_options(
+        provider_name,
+        &remote_config.username,
+        token,
+        remote_config.api_url.as_deref(),
+        config.security.allow_insecure_http,
+        &config.client_options_for(remote_config),
+    )?;
+
+    match provider.test_connection().await {
+        Ok(true) => Ok(provider),
+        Ok(false) => Err(MultiGitError::auth(
+            name,
+            "Authentication failed with new credentials".to_string(),
+        )),
+        Err(e) => Err(MultiGitError::network(format!(
+            "Failed to test new credentials: {e}"
+        ))),
+    }
+}
+
 /// Update remote credentials
 pub async fn update_remote(name: String, interactive_mode: bool) -> Result<()> {
     let config = Config::load()?;
@@ -355,17 +844,20 @@ pub async fn update_remote(name: String, interactive_mode: bool) -> Result<()> {
         .get(&name_lower)
         .ok_or_else(|| MultiGitError::other(format!("Remote '{name}' not found")))?;
 
+    let provider_name = remote_config.provider_name(&name_lower);
+
     // Get new token
     let token = if interactive_mode {
         println!("\nUpdating credentials for '{name_lower}'\n");
-        interactive::prompt_token(&name_lower)?
+        interactive::prompt_token(provider_name)?
     } else {
-        let env_var = format!("MULTIGIT_{}_TOKEN", name_lower.to_uppercase());
-        std::env::var(&env_var).map_err(|_| {
+        token_from_env(&name_lower, provider_name).ok_or_else(|| {
             MultiGitError::auth(
                 name_lower.clone(),
                 format!(
-                    "Token not provided. Set {env_var} environment variable or use interactive mode"
+                    "Token not provided. Set {} or MULTIGIT_{}_TOKEN environment variable or use interactive mode",
+                    remote_token_env_var(&name_lower),
+                    provider_name.to_uppercase()
                 ),
             )
         })?
@@ -374,53 +866,35 @@ pub async fn update_remote(name: String, interactive_mode: bool) -> Result<()> {
     // Get host for credential binding
     let allow_insecure = config.security.allow_insecure_http;
     let host = get_provider_host(
-        &name_lower,
+        provider_name,
         remote_config.api_url.as_deref(),
         allow_insecure,
     )?;
 
     // Test new credentials
     interactive::print_info("Testing new credentials...");
-
-    let provider = create_provider(
-        &name_lower,
-        &remote_config.username,
-        &token,
-        remote_config.api_url.as_deref(),
-        allow_insecure,
-    )?;
-
-    match provider.test_connection().await {
-        Ok(true) => {
-            interactive::print_success("New credentials are valid");
-        }
-        Ok(false) => {
-            return Err(MultiGitError::auth(
-                name_lower,
-                "Authentication failed with new credentials".to_string(),
-            ));
-        }
-        Err(e) => {
-            return Err(MultiGitError::network(format!(
-                "Failed to test new credentials: {e}"
-            )));
-        }
-    }
+    let provider = verify_token(&config, &name_lower, remote_config, &token).await?;
+    interactive::print_success("New credentials are valid");
 
     // Update credentials with host binding
     let auth_manager = AuthManager::new(AuthBackend::Keyring, config.security.audit_log);
 
-    auth_manager.store_credential(&name_lower, &host, &remote_config.username, &token)?;
+    auth_manager.store_remote_credential(&name_lower, &token)?;
 
     interactive::print_success(&format!(
-        "Credentials for '{name_lower}' updated successfully"
+        "Credentials for '{name_lower}' updated successfully (host: {host})"
     ));
 
+    // Replace the old token's expiry, which no longer applies
+    let warn_days = config.settings.token_expiry_warning_days;
+    let token_info = check_token_expiry(&name_lower, provider.as_ref(), warn_days).await;
+    record_token_expiry(&name_lower, token_info.and_then(|token| token.expires_at));
+
     Ok(())
 }
 
 /// Test all configured remotes
-pub async fn test_all_remotes() -> Result<()> {
+pub async fn test_all_remotes(timeout_secs: Option<u64>) -> Result<()> {
     let config = Config::load()?;
 
     if config.remotes.is_empty() {
@@ -434,10 +908,10 @@ pub async fn test_all_remotes() -> Result<()> {
     let mut fail_count = 0;
 
     for name in config.remotes.keys() {
-        match test_remote(name.clone()).await {
+        match test_remote(name.clone(), timeout_secs).await {
             Ok(()) => success_count += 1,
             Err(e) => {
-                interactive::print_error(&format!("✗ {name}: {e}"));
+                interactive::print_error(&format!("{name}: {e}"));
                 fail_count += 1;
             }
         }
@@ -445,8 +919,11 @@ pub async fn test_all_remotes() -> Result<()> {
     }
 
     println!("\nTest Results:");
-    println!("  ✓ Success: {success_count}");
-    println!("  ✗ Failed: {fail_count}");
+    println!(
+        "  {} Success: {success_count}",
+        Status::Success.colored_icon()
+    );
+    println!("  {} Failed: {fail_count}", Status::Error.colored_icon());
 
     if fail_count > 0 {
         println!(
@@ -462,6 +939,101 @@ pub async fn test_all_remotes() -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::RateLimit;
+    use crate::providers::mock::MockProvider;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn test_repository_summary() {
+        let mut repository =
+            Repository::new("project", "", "", false, "main").with_full_name("alice/project");
+        assert_eq!(repository_summary(&repository), "alice/project");
+
+        repository.stars = Some(42);
+        repository.forks = Some(3);
+        repository.open_issues = Some(0);
+        repository.archived = Some(true);
+        assert_eq!(
+            repository_summary(&repository),
+            "alice/project  ★ 42  3 fork(s)  0 open issue(s)  archived"
+        );
+    }
+
+    #[test]
+    fn test_implementation_for_custom_provider() {
+        let url = Some("https://forge.example.com");
+        assert_eq!(
+            implementation_for("custom", Some("gitea"), url).unwrap(),
+            "gitea"
+        );
+        assert_eq!(
+            implementation_for("custom", Some("gitlab"), url).unwrap(),
+            "gitlab"
+        );
+        assert_eq!(implementation_for("github", None, None).unwrap(), "github");
+
+        assert!(implementation_for("custom", None, url).is_err());
+        assert!(implementation_for("custom", Some("bitbucket"), url).is_err());
+        assert!(implementation_for("custom", Some("gitea"), None).is_err());
+        assert!(implementation_for("github", Some("gitea"), None).is_err());
+        assert!(implementation_for("sourcehut", None, None).is_err());
+    }
+
+    #[test]
+    fn test_default_repository_index() {
+        let repos = vec![
+            Repository::new("recent", "", "", false, "main"),
+            Repository::new("My Project", "", "", true, "main").with_full_name("ada/my-project"),
+        ];
+        assert_eq!(default_repository_index(&repos, "My-Project"), 1);
+        assert_eq!(default_repository_index(&repos, "elsewhere"), 0);
+
+        let mut described = repos[1].clone();
+        described.description = Some("Tools".to_string());
+        assert_eq!(repository_label(&repos[0]), "recent");
+        assert_eq!(repository_label(&described), "my-project (private) - Tools");
+    }
+
+    #[tokio::test]
+    async fn test_choose_repository_falls_back_to_directory_name() {
+        let unsupported = MockProvider::new("mock");
+        assert_eq!(
+            choose_repository(&unsupported, "local").await.unwrap(),
+            "local"
+        );
+
+        let empty = MockProvider::new("mock").with_repositories(Vec::new());
+        assert_eq!(choose_repository(&empty, "local").await.unwrap(), "local");
+        assert_eq!(empty.calls(), vec!["list_repos"]);
+    }
+
+    #[test]
+    fn test_remote_listing_json() {
+        let remote_config = RemoteConfig {
+            username: "ada".to_string(),
+            api_url: Some("https://git.example.com".to_string()),
+            provider: Some("gitea".to_string()),
+            use_ssh: true,
+            priority: 2,
+            ..Default::default()
+        };
+
+        let value = serde_json::to_value(RemoteListing::new("work", &remote_config, true)).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "name": "work",
+                "provider": "gitea",
+                "username": "ada",
+                "api_url": "https://git.example.com",
+                "enabled": true,
+                "use_ssh": true,
+                "priority": 2,
+                "credential_stored": true,
+            })
+        );
+    }
 
     #[test]
     fn test_supported_providers() {
@@ -472,4 +1044,67 @@ mod tests {
         assert!(is_supported_provider("gitea"));
         assert!(!is_supported_provider("invalid"));
     }
+
+    #[tokio::test]
+    async fn test_provider_success_queries_rate_limit() {
+        let provider = MockProvider::new("github").with_rate_limit(RateLimit {
+            limit: 5000,
+            remaining: 4999,
+            reset_at: chrono::Utc::now(),
+        });
+
+        test_provider("github", &provider, TIMEOUT).await.unwrap();
+        assert_eq!(provider.calls(), vec!["test_connection", "get_rate_limit"]);
+    }
+
+    #[tokio::test]
+    async fn test_provider_rejected_credentials() {
+        let provider = MockProvider::new("gitlab").with_connection(false);
+
+        let err = test_provider("gitlab", &provider, TIMEOUT)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, MultiGitError::AuthError { .. }));
+        assert_eq!(provider.calls(), vec!["test_connection"]);
+    }
+
+    #[tokio::test]
+    async fn test_provider_connection_error() {
+        let provider = MockProvider::new("gitea").with_connection_error("connection refused");
+
+        let err = test_provider("gitea", &provider, TIMEOUT)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, MultiGitError::NetworkMessage(_)));
+        assert!(err.to_string().contains("connection refused"));
+    }
+
+    #[tokio::test]
+    async fn test_provider_times_out() {
+        let provider = MockProvider::new("gitea").with_latency(Duration::from_secs(60));
+
+        let err = test_provider("gitea", &provider, Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, MultiGitError::NetworkMessage(_)));
+        assert!(err.to_string().contains("timed out"));
+    }
+
+    #[tokio::test]
+    async fn test_check_token_expiry() {
+        let expires_at = chrono::Utc::now() + chrono::Duration::days(3);
+        let provider = MockProvider::new("gitlab").with_token_info(TokenInfo {
+            name: Some("ci".to_string()),
+            expires_at: Some(expires_at),
+        });
+
+        let token = check_token_expiry("gitlab", &provider, 14).await.unwrap();
+        assert_eq!(token.expires_at, Some(expires_at));
+
+        // Providers that can't describe tokens report nothing
+        let provider = MockProvider::new("bitbucket");
+        assert!(check_token_expiry("bitbucket", &provider, 14)
+            .await
+            .is_none());
+    }
 }
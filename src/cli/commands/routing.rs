@@ -0,0 +1,53 @@
+//! Routing command implementation
+//!
+//! Preview which remotes the `[routing]` rules let a branch be pushed to.
+
+use crate::core::config::Config;
+use crate::core::routing::Routing;
+use crate::ui::formatter::{colors, Status, Table};
+use crate::utils::error::Result;
+
+/// Show, for every configured remote, whether `branch` would be pushed to it
+pub fn test(branch: &str) -> Result<()> {
+    let config = Config::load()?;
+    let routing = Routing::from_config(&config)?;
+
+    let mut names: Vec<&String> = config.remotes.keys().collect();
+    names.sort();
+    if names.is_empty() {
+        println!("{} No remotes configured yet.", Status::Warning.icon());
+        return Ok(());
+    }
+
+    println!("\n🧭 Routing for '{branch}'\n");
+    let mut table = Table::new(
+        ["", "Remote", "Reason"]
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+    );
+    let mut targets = 0;
+    for name in names {
+        let route = routing.route(branch, name);
+        let (status, reason) = if !route.permits() {
+            (Status::Error, route.describe())
+        } else if config.remotes[name].enabled {
+            targets += 1;
+            (Status::Success, route.describe())
+        } else {
+            (Status::Warning, "remote is disabled".to_string())
+        };
+        table.add_row(vec![status.icon().to_string(), name.clone(), reason]);
+    }
+    if colors::enabled() {
+        table.print();
+    } else {
+        table.no_colors().print();
+    }
+
+    if routing.is_empty() {
+        println!("\nNo [routing] rules are configured.");
+    }
+    println!("\n'{branch}' would be pushed to {targets} remote(s)");
+    Ok(())
+}
@@ -0,0 +1,113 @@
+//! Commit search
+//!
+//! Search commit messages and diffs across history.
+
+use crate::git::history::{self, CommitFilter, SearchMatch, SearchScope};
+use crate::git::operations::GitOperations;
+use crate::utils::error::{MultiGitError, Result};
+use crate::utils::pager;
+use chrono::Utc;
+use tracing::info;
+
+/// Diff lines shown per commit before the rest are summarized
+const MAX_DIFF_LINES: usize = 5;
+
+/// Search history for `query`, newest first
+///
+/// `since` takes a date or an age, as `log --since` does.
+pub fn execute(
+    query: &str,
+    scope: SearchScope,
+    author: Option<String>,
+    since: Option<String>,
+    limit: usize,
+) -> Result<()> {
+    info!("Searching {} for '{}'", scope, query);
+
+    let git_ops =
+        GitOperations::open(".").map_err(|_| MultiGitError::other("Not in a git repository."))?;
+    let filter = CommitFilter {
+        author,
+        since: since
+            .map(|since| history::parse_since(&since, Utc::now()))
+            .transpose()?,
+    };
+
+    let matches = history::search(git_ops.inner(), query, scope, &filter, limit)?;
+    if matches.is_empty() {
+        println!("No commits matching '{query}' in {scope}.");
+        return Ok(());
+    }
+
+    let mut out = vec![format!(
+        "\n🔎 {} commit(s) matching '{query}' in {scope}:\n",
+        matches.len()
+    )];
+    for found in &matches {
+        out.extend(match_lines(found));
+    }
+    if matches.len() == limit {
+        out.push(format!(
+            "Stopped after {limit} commits; raise --limit to see more."
+        ));
+    }
+
+    pager::page_lines(&out)
+}
+
+/// A matching commit and the lines that matched
+fn match_lines(found: &SearchMatch) -> Vec<String> {
+    let date = chrono::DateTime::from_timestamp(found.time, 0)
+        .map(|at| at.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    let mut lines = vec![format!(
+        "{:.7} {} ({}, {date})",
+        found.oid.to_string(),
+        found.summary,
+        found.author
+    )];
+
+    for line in &found.message_lines {
+        lines.push(format!("    message: {line}"));
+    }
+    for hit in found.diff_hits.iter().take(MAX_DIFF_LINES) {
+        lines.push(format!("    {}: {}{}", hit.path, hit.origin, hit.line));
+    }
+    if found.diff_hits.len() > MAX_DIFF_LINES {
+        lines.push(format!(
+            "    … {} more matching line(s)",
+            found.diff_hits.len() - MAX_DIFF_LINES
+        ));
+    }
+    lines.push(String::new());
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::history::DiffHit;
+
+    #[test]
+    fn test_match_lines_truncates_diff() {
+        let hit = |n: usize| DiffHit {
+            path: "src/lib.rs".to_string(),
+            origin: '+',
+            line: format!("let timeout_{n} = 5;"),
+        };
+        let found = SearchMatch {
+            oid: git2::Oid::from_str("abcdef1234567890abcdef1234567890abcdef12").unwrap(),
+            summary: "Tune timeouts".to_string(),
+            author: "Ada".to_string(),
+            time: 1_717_200_000,
+            message_lines: vec!["Tune timeouts".to_string()],
+            diff_hits: (0..7).map(hit).collect(),
+        };
+
+        let lines = match_lines(&found);
+        assert_eq!(lines[0], "abcdef1 Tune timeouts (Ada, 2024-06-01)");
+        assert_eq!(lines[1], "    message: Tune timeouts");
+        assert_eq!(lines[2], "    src/lib.rs: +let timeout_0 = 5;");
+        assert_eq!(lines[7], "    … 2 more matching line(s)");
+    }
+}
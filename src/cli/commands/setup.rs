@@ -2,12 +2,14 @@
 //!
 //! Provides an interactive, user-friendly setup experience.
 
+use crate::api::client::ClientOptions;
 use crate::cli::interactive;
 use crate::core::auth::{AuthBackend, AuthManager};
 use crate::core::config::{Config, RemoteConfig};
 use crate::git::operations::GitOperations;
-use crate::providers::factory::{create_provider, get_provider_host};
+use crate::providers::factory::{create_provider_with_options, get_provider_host};
 use crate::providers::traits::{Protocol, Provider};
+use crate::ui::formatter::Status;
 use crate::utils::error::{MultiGitError, Result};
 use dialoguer::{theme::ColorfulTheme, Confirm, MultiSelect, Select};
 use std::sync::Arc;
@@ -23,6 +25,8 @@ const PROVIDERS: &[(&str, &str)] = &[
 
 /// Run the interactive setup wizard
 pub async fn run_wizard() -> Result<()> {
+    let ok = Status::Success.icon();
+    let warning = Status::Warning.icon();
     println!("\n╔══════════════════════════════════════════════╗");
     println!("║                                              ║");
     println!("║     🚀 Welcome to MultiGit Setup Wizard     ║");
@@ -51,10 +55,10 @@ pub async fn run_wizard() -> Result<()> {
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
 
     if Config::is_initialized() {
-        println!("✅ MultiGit is already initialized in this repository.");
+        println!("{ok} MultiGit is already initialized in this repository.");
     } else {
         Config::initialize()?;
-        println!("✅ MultiGit initialized successfully!");
+        println!("{ok} MultiGit initialized successfully!");
     }
 
     // Step 2: Add providers
@@ -73,7 +77,7 @@ pub async fn run_wizard() -> Result<()> {
         .map_err(|e| MultiGitError::other(format!("Selection failed: {e}")))?;
 
     if selections.is_empty() {
-        println!("\n⚠️  No providers selected. You can add them later with:");
+        println!("\n{warning} No providers selected. You can add them later with:");
         println!("   multigit remote add <provider> <username>");
         return Ok(());
     }
@@ -89,7 +93,7 @@ pub async fn run_wizard() -> Result<()> {
 
         // Add provider with guided setup
         if let Err(e) = add_provider_guided(&mut config, provider_id).await {
-            println!("⚠️  Failed to set up {provider_display}: {e}");
+            println!("{warning} Failed to set up {provider_display}: {e}");
             println!(
                 "   You can try again later with: multigit remote add {provider_id} <username>"
             );
@@ -113,7 +117,7 @@ pub async fn run_wizard() -> Result<()> {
         configure_preferences(&mut config)?;
         config.save()?;
     } else {
-        println!("✅ Using default settings (you can change these anytime)");
+        println!("{ok} Using default settings (you can change these anytime)");
     }
 
     // Done!
@@ -141,6 +145,8 @@ pub async fn run_wizard() -> Result<()> {
 
 /// Add a provider with guided setup
 async fn add_provider_guided(config: &mut Config, provider: &str) -> Result<()> {
+    let ok = Status::Success.icon();
+    let warning = Status::Warning.icon();
     // Get username
     let username = interactive::prompt_text(&format!("Enter your {provider} username"), None)?;
 
@@ -178,6 +184,7 @@ async fn add_provider_guided(config: &mut Config, provider: &str) -> Result<()>
     // Load config to get security settings
     let current_config = Config::load().unwrap_or_default();
     let allow_insecure = current_config.security.allow_insecure_http;
+    let options = ClientOptions::from_settings(&current_config.settings);
 
     // Get host for credential binding
     let host = get_provider_host(provider, api_url.as_deref(), allow_insecure)?;
@@ -191,11 +198,12 @@ async fn add_provider_guided(config: &mut Config, provider: &str) -> Result<()>
         &token,
         api_url.as_deref(),
         allow_insecure,
+        &options,
     )?;
 
     match test_provider.test_connection().await {
         Ok(true) => {
-            println!("✅ Connection successful!");
+            println!("{ok} Connection successful!");
         }
         Ok(false) | Err(_) => {
             return Err(MultiGitError::auth(
@@ -206,22 +214,20 @@ async fn add_provider_guided(config: &mut Config, provider: &str) -> Result<()>
     }
 
     // Store credentials with host binding
-    let auth_manager = AuthManager::new(AuthBackend::Keyring, false);
+    let auth_manager = AuthManager::new(AuthBackend::Keyring, current_config.security.audit_log);
     auth_manager.store_credential(provider, &host, &username, &token)?;
-    println!("✅ Credentials stored securely (bound to host: {host})");
+    println!("{ok} Credentials stored securely (bound to host: {host})");
 
     // Add to config
     let remote_config = RemoteConfig {
         username: username.clone(),
         api_url: api_url.clone(),
-        enabled: true,
         provider: Some(provider.to_string()),
-        use_ssh: false,
-        priority: 0,
+        ..Default::default()
     };
 
     config.add_remote(provider.to_string(), remote_config);
-    println!("✅ {provider} added to configuration");
+    println!("{ok} {provider} added to configuration");
 
     // Add the actual git remote to .git/config
     if let Ok(git_ops) = GitOperations::open(".") {
@@ -238,21 +244,22 @@ async fn add_provider_guided(config: &mut Config, provider: &str) -> Result<()>
             &token,
             api_url.as_deref(),
             allow_insecure,
+            &options,
         )?;
         let remote_url = test_provider.get_remote_url(&repo_name, Protocol::Https);
 
         // Add git remote
         match git_ops.add_remote(provider, &remote_url) {
             Ok(()) => {
-                println!("✅ Git remote added: {provider} -> {remote_url}");
+                println!("{ok} Git remote added: {provider} -> {remote_url}");
 
                 // Optionally fetch to create tracking refs
                 if let Err(e) = git_ops.fetch(provider, &[]) {
-                    println!("⚠️  Initial fetch failed (normal for new repos): {e}");
+                    println!("{warning} Initial fetch failed (normal for new repos): {e}");
                 }
             }
             Err(e) => {
-                println!("⚠️  Failed to add git remote: {e}");
+                println!("{warning} Failed to add git remote: {e}");
                 println!("   You can manually add it with: git remote add {provider} {remote_url}");
             }
         }
@@ -312,8 +319,9 @@ fn create_test_provider(
     token: &str,
     api_url: Option<&str>,
     allow_insecure: bool,
+    options: &ClientOptions,
 ) -> Result<Arc<dyn Provider>> {
-    create_provider(provider, username, token, api_url, allow_insecure)
+    create_provider_with_options(provider, username, token, api_url, allow_insecure, options)
 }
 
 /// Configure advanced preferences
@@ -364,7 +372,7 @@ fn configure_preferences(config: &mut Config) -> Result<()> {
 
     config.sync.detect_conflicts = detect_conflicts;
 
-    println!("\n✅ Preferences configured!");
+    println!("\n{} Preferences configured!", Status::Success.icon());
 
     Ok(())
 }
@@ -384,7 +392,7 @@ pub async fn quick_setup(provider: &str, _username: String) -> Result<()> {
     add_provider_guided(&mut config, provider).await?;
     config.save()?;
 
-    println!("\n✅ {provider} setup complete!");
+    println!("\n{} {provider} setup complete!", Status::Success.icon());
     println!("💡 Run 'multigit status' to see your configuration");
 
     Ok(())
@@ -3,6 +3,7 @@
 //! Save, apply, and manage stashed changes
 
 use crate::cli::interactive;
+use crate::ui::formatter::Status;
 use crate::utils::error::{MultiGitError, Result};
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
 use std::process::Command;
@@ -60,7 +61,7 @@ fn save_stash() -> Result<()> {
         .map_err(|e| MultiGitError::other(format!("Failed to stash: {e}")))?;
 
     if output.status.success() {
-        interactive::print_success("✅ Changes stashed successfully!");
+        interactive::print_success("Changes stashed successfully!");
     } else {
         let error = String::from_utf8_lossy(&output.stderr);
         return Err(MultiGitError::other(format!("Failed to stash: {error}")));
@@ -104,7 +105,7 @@ fn apply_stash(stash_id: Option<String>) -> Result<()> {
         .map_err(|e| MultiGitError::other(format!("Failed to apply stash: {e}")))?;
 
     if output.status.success() {
-        interactive::print_success("✅ Stash applied successfully!");
+        interactive::print_success("Stash applied successfully!");
     } else {
         let error = String::from_utf8_lossy(&output.stderr);
         return Err(MultiGitError::other(format!(
@@ -123,7 +124,7 @@ fn pop_stash() -> Result<()> {
         .map_err(|e| MultiGitError::other(format!("Failed to pop stash: {e}")))?;
 
     if output.status.success() {
-        interactive::print_success("✅ Stash popped successfully!");
+        interactive::print_success("Stash popped successfully!");
     } else {
         let error = String::from_utf8_lossy(&output.stderr);
         return Err(MultiGitError::other(format!(
@@ -208,7 +209,7 @@ fn drop_stash() -> Result<()> {
             .map_err(|e| MultiGitError::other(format!("Failed to drop stash: {e}")))?;
 
         if output.status.success() {
-            interactive::print_success(&format!("✅ Dropped {stash_id}"));
+            interactive::print_success(&format!("{} Dropped {stash_id}", Status::Success.icon()));
         } else {
             let error = String::from_utf8_lossy(&output.stderr);
             return Err(MultiGitError::other(format!(
@@ -234,7 +235,7 @@ fn clear_stashes() -> Result<()> {
             .map_err(|e| MultiGitError::other(format!("Failed to clear stashes: {e}")))?;
 
         if output.status.success() {
-            interactive::print_success("✅ All stashes cleared!");
+            interactive::print_success("All stashes cleared!");
         } else {
             let error = String::from_utf8_lossy(&output.stderr);
             return Err(MultiGitError::other(format!(
@@ -3,21 +3,22 @@
 //! Show contribution graphs, commit frequency, and more
 
 use crate::utils::error::{MultiGitError, Result};
+use crate::utils::pager::{self, Destination};
 use std::process::Command;
 
-/// Execute stats viewer
-pub fn execute() -> Result<()> {
-    println!("\n📊 Repository Statistics\n");
+/// Execute stats viewer, writing the report to `output`
+pub fn execute(output: &Destination) -> Result<()> {
+    let mut out = vec!["\n📊 Repository Statistics\n".to_string()];
 
-    show_commit_stats()?;
-    show_contributor_stats()?;
-    show_file_stats()?;
+    show_commit_stats(&mut out)?;
+    show_contributor_stats(&mut out)?;
+    show_file_stats(&mut out)?;
 
-    Ok(())
+    pager::emit_lines(&out, output)
 }
 
-fn show_commit_stats() -> Result<()> {
-    println!("📈 Commit Activity\n");
+fn show_commit_stats(out: &mut Vec<String>) -> Result<()> {
+    out.push("📈 Commit Activity\n".to_string());
 
     // Total commits
     let output = Command::new("git")
@@ -26,7 +27,7 @@ fn show_commit_stats() -> Result<()> {
         .map_err(|e| MultiGitError::other(format!("Failed to count commits: {e}")))?;
 
     let total_commits = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    println!("  Total commits: {total_commits}");
+    out.push(format!("  Total commits: {total_commits}"));
 
     // Commits this week
     let output = Command::new("git")
@@ -35,7 +36,7 @@ fn show_commit_stats() -> Result<()> {
         .map_err(|e| MultiGitError::other(format!("Failed to count commits: {e}")))?;
 
     let week_commits = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    println!("  Commits this week: {week_commits}");
+    out.push(format!("  Commits this week: {week_commits}"));
 
     // Commits today
     let output = Command::new("git")
@@ -44,13 +45,13 @@ fn show_commit_stats() -> Result<()> {
         .map_err(|e| MultiGitError::other(format!("Failed to count commits: {e}")))?;
 
     let today_commits = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    println!("  Commits today: {today_commits}\n");
+    out.push(format!("  Commits today: {today_commits}\n"));
 
     Ok(())
 }
 
-fn show_contributor_stats() -> Result<()> {
-    println!("👥 Top Contributors\n");
+fn show_contributor_stats(out: &mut Vec<String>) -> Result<()> {
+    out.push("👥 Top Contributors\n".to_string());
 
     let output = Command::new("git")
         .args(["shortlog", "-sn", "--all", "--no-merges"])
@@ -60,15 +61,15 @@ fn show_contributor_stats() -> Result<()> {
     let contributors = String::from_utf8_lossy(&output.stdout);
 
     for (i, line) in contributors.lines().take(5).enumerate() {
-        println!("  {}. {}", i + 1, line.trim());
+        out.push(format!("  {}. {}", i + 1, line.trim()));
     }
-    println!();
+    out.push(String::new());
 
     Ok(())
 }
 
-fn show_file_stats() -> Result<()> {
-    println!("📁 Repository Size\n");
+fn show_file_stats(out: &mut Vec<String>) -> Result<()> {
+    out.push("📁 Repository Size\n".to_string());
 
     // Count files
     let output = Command::new("git")
@@ -77,7 +78,7 @@ fn show_file_stats() -> Result<()> {
         .map_err(|e| MultiGitError::other(format!("Failed to list files: {e}")))?;
 
     let file_count = String::from_utf8_lossy(&output.stdout).lines().count();
-    println!("  Tracked files: {file_count}");
+    out.push(format!("  Tracked files: {file_count}"));
 
     // Count branches
     let output = Command::new("git")
@@ -86,7 +87,7 @@ fn show_file_stats() -> Result<()> {
         .map_err(|e| MultiGitError::other(format!("Failed to count branches: {e}")))?;
 
     let branch_count = String::from_utf8_lossy(&output.stdout).lines().count();
-    println!("  Branches: {branch_count}");
+    out.push(format!("  Branches: {branch_count}"));
 
     // Count tags
     let output = Command::new("git")
@@ -95,7 +96,7 @@ fn show_file_stats() -> Result<()> {
         .map_err(|e| MultiGitError::other(format!("Failed to count tags: {e}")))?;
 
     let tag_count = String::from_utf8_lossy(&output.stdout).lines().count();
-    println!("  Tags: {tag_count}\n");
+    out.push(format!("  Tags: {tag_count}\n"));
 
     Ok(())
 }
@@ -2,47 +2,814 @@
 //!
 //! Display sync status across all remotes.
 
-use crate::core::config::Config;
+use crate::cli::commands::push::remote_repositories;
+use crate::cli::commands::remote::repository_summary;
+use crate::cli::interactive;
+use crate::core::config::{Config, RemoteConfig};
+use crate::core::repo_lock::RepoLock;
+use crate::core::routing::Routing;
 use crate::core::sync_manager::SyncManager;
+use crate::git::operations::GitOperations;
+use crate::git::submodule::{self, SubmoduleInfo, SubmoduleState};
+use crate::git::working_tree::WorkingTreeSummary;
+use crate::models::{PullRequestState, RemoteState, RemoteSyncStatus, Repository, SyncState};
+use crate::providers::factory::connect_remote;
+use crate::providers::traits::Provider;
+use crate::ui::formatter::{colors, terminal_width, Status, Table, FALLBACK_WIDTH};
+use crate::ui::progress::Spinner;
 use crate::utils::error::Result;
-use tracing::info;
+use crate::utils::pager::{self, Destination};
+use chrono::Utc;
+use futures::future::join_all;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info};
 
-/// Show sync status
-pub fn execute(verbose: bool) -> Result<()> {
+/// How long `status` reuses cached ahead/behind counts while HEAD stays put
+pub const STATUS_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// File in the repository's git directory holding the cached counts
+const STATUS_CACHE_FILE: &str = "multigit-status.json";
+
+/// Show sync status, writing the report to `output`
+///
+/// With `interactive`, remotes that are ahead of or behind the current branch
+/// are then offered a push or pull.
+pub async fn execute(verbose: bool, interactive: bool, output: &Destination) -> Result<()> {
     info!("Checking sync status");
 
     let manager = SyncManager::new(".")?;
+    let operation = manager.operation_in_progress()?;
 
-    // Get current branch
-    let branch = manager.current_branch()?;
+    // Get current branch; a rebase leaves HEAD detached
+    let branch = match manager.current_branch() {
+        Ok(branch) => branch,
+        Err(_) if operation.is_some() => "(detached HEAD)".to_string(),
+        Err(e) => return Err(e),
+    };
     let is_clean = manager.is_clean()?;
 
-    println!("\nℹ MultiGit Status");
-    println!("\nCurrent branch: {branch}");
-    println!(
-        "Working directory: {}",
-        if is_clean { "clean" } else { "has changes" }
-    );
+    let mut out = vec![format!("\n{} MultiGit Status", Status::Info.icon())];
+
+    if let Some(ref op) = operation {
+        out.push(format!("\n{} {op}", Status::Warning.icon()));
+        if !op.conflicted_files.is_empty() {
+            out.push("  Files with conflicts:".to_string());
+            for file in &op.conflicted_files {
+                out.push(format!("    {} {file}", Status::Error.icon()));
+            }
+        }
+        out.push(format!("  {}", op.guidance()));
+        out.push("  push and sync are disabled until it is concluded.".to_string());
+    }
+
+    out.push(format!("\nCurrent branch: {branch}"));
+    if let Some(line) = worktree_line(&manager) {
+        out.push(line);
+    }
+    let mut changes = manager.working_tree()?;
+    if operation.is_some() {
+        // Already listed with the operation in progress
+        changes.conflicted.clear();
+    }
+    out.extend(working_tree_lines(&changes, is_clean, verbose));
+    out.extend(submodule_lines(&manager.submodules().unwrap_or_default()));
 
     // Get configured remotes from config
-    println!("\nRemote status:");
+    out.push("\nRemote status:".to_string());
     let config = Config::load().unwrap_or_default();
     let remotes = config.enabled_remotes();
+    let mut out_of_sync = Vec::new();
+    let mut pull_requests = Vec::new();
 
     if remotes.is_empty() {
-        println!("  No remotes configured.");
-        println!("  Configure remotes with: multigit remote add");
+        out.push("  No remotes configured.".to_string());
+        out.push("  Configure remotes with: multigit remote add".to_string());
     } else {
-        for (name, remote_config) in remotes {
-            println!("  ✓ {} (@{})", name, remote_config.username);
+        // Repositories and pull requests are only looked up in verbose mode,
+        // which talks to the providers
+        let repositories = if verbose {
+            let names: Vec<String> = remotes.keys().cloned().collect();
+            let spinner = Spinner::for_terminal(&format!("Checking {} remote(s)...", names.len()));
+            let (repositories, lines) = tokio::join!(
+                remote_repositories(&config, &names),
+                open_pull_request_lines(&config)
+            );
+            if let Some(spinner) = spinner {
+                spinner.finish_and_clear();
+            }
+            pull_requests = lines;
+            repositories
+        } else {
+            Vec::new()
+        };
+        // Compared with the last fetch, unless an operation left HEAD detached
+        let names: Vec<&String> = remotes.keys().collect();
+        let counts = if operation.is_none() {
+            let cache = manager.repo_path().join(STATUS_CACHE_FILE);
+            cached_ahead_behind(&manager, &branch, &names, &cache)
+        } else {
+            HashMap::new()
+        };
+        let (lines, divergences) =
+            remote_lines(&counts, &remotes, &repositories, verbose.then_some(output));
+        out.extend(lines);
+        out_of_sync = divergences;
+        out_of_sync.sort_by(|a, b| a.remote.cmp(&b.remote));
+
+        if config.settings.expect_private {
+            for remote in public_remotes(&repositories) {
+                out.push(format!(
+                    "  {} {remote}: repository is public, but settings.expect_private is set",
+                    Status::Warning.icon()
+                ));
+            }
         }
     }
 
+    // Last-known expiry, as recorded by `remote test`
+    let now = Utc::now();
+    for (name, token) in config.expiring_tokens(now) {
+        out.push(format!(
+            "  {} Token for {name} {}",
+            Status::Warning.icon(),
+            token.describe_expiry(now)
+        ));
+        out.push(format!(
+            "    Renew it, then run: multigit remote update {name}"
+        ));
+    }
+
     if verbose {
-        println!("\nVerbose mode - additional details:");
-        println!("  Repository path: .");
-        println!("  Config: .multigit/config.toml");
+        out.push("\nVerbose mode - additional details:".to_string());
+        out.push("  Repository path: .".to_string());
+        out.push("  Config: .multigit/config.toml".to_string());
+        out.extend(pull_requests);
+    }
+
+    pager::emit_lines(&out, output)?;
+
+    if interactive && !out_of_sync.is_empty() {
+        offer_quick_sync(&config, &branch, is_clean, &out_of_sync).await?;
     }
 
     Ok(())
 }
+
+/// Status of each remote, sorted by name, and those out of sync
+///
+/// With `table` (the detailed view) the remotes are a table fitted for that
+/// destination instead of one line each.
+fn remote_lines(
+    counts: &HashMap<String, (usize, usize)>,
+    remotes: &HashMap<String, &RemoteConfig>,
+    repositories: &[(String, Repository)],
+    table: Option<&Destination>,
+) -> (Vec<String>, Vec<OutOfSync>) {
+    let mut out = Vec::new();
+    let mut out_of_sync = Vec::new();
+    let archived = |name: &String| {
+        repositories
+            .iter()
+            .any(|(remote, repository)| remote == name && repository.archived == Some(true))
+    };
+
+    let mut names: Vec<&String> = remotes.keys().collect();
+    names.sort();
+    let mut rows = Vec::new();
+    for name in names {
+        let remote_config = remotes[name];
+        let (status, state, divergence) = if archived(name) {
+            (
+                Status::Skipped.icon(),
+                "archived, read-only; push and sync skip it".to_string(),
+                None,
+            )
+        } else {
+            remote_state(name, counts.get(name).copied())
+        };
+        out_of_sync.extend(divergence);
+
+        if table.is_some() {
+            let repository = repositories
+                .iter()
+                .find(|(remote, _)| remote == name)
+                .map_or_else(|| "-".to_string(), |(_, r)| repository_summary(r));
+            rows.push(vec![
+                status.to_string(),
+                name.clone(),
+                format!("@{}", remote_config.username),
+                if state.is_empty() {
+                    "ok".to_string()
+                } else {
+                    state
+                },
+                repository,
+            ]);
+        } else if state.is_empty() {
+            out.push(format!("  {status} {name} (@{})", remote_config.username));
+        } else {
+            out.push(format!(
+                "  {status} {name} (@{}) - {state}",
+                remote_config.username
+            ));
+        }
+    }
+    if let Some(output) = table {
+        out.extend(remote_table(rows, output));
+    }
+    (out, out_of_sync)
+}
+
+/// Icon and state of a remote, and how far it is out of sync if it is
+///
+/// The state is empty when there is nothing to report. A remote that wasn't
+/// compared, or has no copy of the branch yet, is shown as fine.
+fn remote_state(
+    name: &str,
+    divergence: Option<(usize, usize)>,
+) -> (&'static str, String, Option<OutOfSync>) {
+    match divergence {
+        Some((ahead, behind)) if ahead > 0 || behind > 0 => (
+            Status::Warning.icon(),
+            describe_divergence(ahead, behind),
+            Some(OutOfSync {
+                remote: name.to_string(),
+                ahead,
+                behind,
+            }),
+        ),
+        _ => (Status::Success.icon(), String::new(), None),
+    }
+}
+
+/// Commits `branch` is ahead of and behind each of `remotes`, as of the last
+/// fetch, for those that have a copy of it
+///
+/// Reuses the counts in the sync state cache at `cache` while HEAD and every
+/// remote-tracking branch are where they were computed and they are younger
+/// than [`STATUS_CACHE_TTL`], so a commit, push or fetch between two `status`
+/// calls is always counted.
+fn cached_ahead_behind(
+    manager: &SyncManager,
+    branch: &str,
+    remotes: &[&String],
+    cache: &Path,
+) -> HashMap<String, (usize, usize)> {
+    let repo_path = manager.repo_path().display().to_string();
+    let Ok(head) = manager.head_commit_id().map(|oid| oid.to_string()) else {
+        return HashMap::new();
+    };
+
+    let tips: HashMap<&str, Option<String>> = remotes
+        .iter()
+        .map(|name| {
+            let tip = manager.remote_branch_tip(name, branch).ok().flatten();
+            (name.as_str(), tip.map(|oid| oid.to_string()))
+        })
+        .collect();
+
+    if let Some(state) = SyncState::load_from(cache) {
+        if state.is_fresh(&repo_path, branch, &head, STATUS_CACHE_TTL)
+            && remotes.iter().all(|name| {
+                state
+                    .remotes
+                    .get(*name)
+                    .is_some_and(|remote| remote.tracking_oid == tips[name.as_str()])
+            })
+        {
+            debug!("Reusing cached ahead/behind counts for {}", head);
+            return remotes
+                .iter()
+                .filter_map(|name| {
+                    let remote = &state.remotes[*name];
+                    (remote.status != RemoteSyncStatus::Unknown)
+                        .then(|| ((*name).clone(), (remote.ahead, remote.behind)))
+                })
+                .collect();
+        }
+    }
+
+    let mut state = SyncState::new(repo_path, branch);
+    let mut counts = HashMap::new();
+    for name in remotes {
+        let mut remote = RemoteState::new(name.as_str());
+        remote.tracking_oid.clone_from(&tips[name.as_str()]);
+        if let Ok((ahead, behind)) = manager.ahead_behind(branch, name) {
+            remote.ahead = ahead;
+            remote.behind = behind;
+            remote.status = if ahead == 0 && behind == 0 {
+                RemoteSyncStatus::Synced
+            } else {
+                RemoteSyncStatus::Dirty
+            };
+            counts.insert((*name).clone(), (ahead, behind));
+        }
+        state.add_remote(name.as_str(), remote);
+    }
+    state.update_status();
+    state.head_oid = Some(head);
+    state.computed_at = Some(Utc::now());
+    if let Err(e) = state.save_to(cache) {
+        debug!("Could not cache ahead/behind counts: {}", e);
+    }
+    counts
+}
+
+/// `status --detailed` remotes as a table, fitted to the terminal when
+/// printing there
+fn remote_table(rows: Vec<Vec<String>>, output: &Destination) -> Vec<String> {
+    let mut table = Table::new(
+        ["", "Remote", "User", "State", "Repository"]
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+    );
+    if !(output.is_stdout() && colors::enabled()) {
+        table = table.no_colors();
+    }
+    for row in rows {
+        table.add_row(row);
+    }
+
+    let width = if output.is_stdout() {
+        terminal_width()
+    } else {
+        FALLBACK_WIDTH
+    };
+    table.render(width)
+}
+
+/// The worktree the command runs in, when the repository has linked worktrees
+fn worktree_line(manager: &SyncManager) -> Option<String> {
+    let worktrees = manager.worktrees().ok()?;
+    if worktrees.len() < 2 {
+        return None;
+    }
+    let current = worktrees.iter().find(|worktree| worktree.is_current)?;
+    Some(format!(
+        "Worktree: {} ({}) - {} worktree(s), see 'multigit worktree list'",
+        current.label(),
+        current.path.display(),
+        worktrees.len()
+    ))
+}
+
+/// Submodules section of the status, empty without submodules
+fn submodule_lines(submodules: &[SubmoduleInfo]) -> Vec<String> {
+    if submodules.is_empty() {
+        return Vec::new();
+    }
+
+    let mut lines = vec!["\nSubmodules:".to_string()];
+    for submodule in submodules {
+        let path = submodule.path.display();
+        if submodule.state == SubmoduleState::UpToDate {
+            lines.push(format!("  {} {path}", Status::Success.icon()));
+        } else {
+            lines.push(format!(
+                "  {} {path} - {}",
+                Status::Warning.icon(),
+                submodule.state
+            ));
+        }
+    }
+    lines
+}
+
+/// Warn about submodules with uncommitted changes before a commit
+///
+/// The commit only records each submodule's checked-out commit, not the
+/// changes inside it.
+pub fn warn_dirty_submodules() {
+    let dirty = GitOperations::open(".")
+        .and_then(|ops| submodule::dirty(ops.inner()))
+        .unwrap_or_default();
+    for submodule in dirty {
+        interactive::print_warning(&format!(
+            "Submodule {} has uncommitted changes, which this commit will not include; \
+             commit them inside the submodule first",
+            submodule.path.display()
+        ));
+    }
+}
+
+/// Files listed per working-tree category before the rest are counted
+const LISTED_FILES: usize = 5;
+
+/// Counts of uncommitted changes, then each category with its first few
+/// files (all of them when `verbose`)
+fn working_tree_lines(changes: &WorkingTreeSummary, is_clean: bool, verbose: bool) -> Vec<String> {
+    let categories = changes.categories();
+    if categories.is_empty() {
+        let state = if is_clean { "clean" } else { "has changes" };
+        return vec![format!("Working directory: {state}")];
+    }
+
+    let counts: Vec<String> = categories
+        .iter()
+        .map(|(label, files)| format!("{} {}", files.len(), label.to_lowercase()))
+        .collect();
+    let mut lines = vec![format!("Working directory: {}", counts.join(", "))];
+    for (label, files) in categories {
+        lines.push(format!("  {label} ({}):", files.len()));
+        let shown = if verbose { files.len() } else { LISTED_FILES };
+        for file in files.iter().take(shown) {
+            lines.push(format!("    {file}"));
+        }
+        if files.len() > shown {
+            lines.push(format!("    … and {} more", files.len() - shown));
+        }
+    }
+    lines
+}
+
+/// A remote whose copy of the current branch differs from the local one
+struct OutOfSync {
+    remote: String,
+    ahead: usize,
+    behind: usize,
+}
+
+/// Follow-up actions for a remote that is out of sync
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QuickAction {
+    Push,
+    Pull,
+    Skip,
+}
+
+impl QuickAction {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Push => "Push",
+            Self::Pull => "Pull (fast-forward)",
+            Self::Skip => "Skip",
+        }
+    }
+}
+
+/// Actions that make sense for a remote `ahead`/`behind` the local branch
+///
+/// A pull is only offered on a clean working tree, and a push only when
+/// `[routing]` lets the branch reach the remote (`push_allowed`); a diverged
+/// branch gets no action since neither direction is a fast-forward.
+fn quick_actions(
+    ahead: usize,
+    behind: usize,
+    is_clean: bool,
+    push_allowed: bool,
+) -> Vec<QuickAction> {
+    let mut actions = Vec::new();
+    if ahead > 0 && behind == 0 && push_allowed {
+        actions.push(QuickAction::Push);
+    }
+    if behind > 0 && ahead == 0 && is_clean {
+        actions.push(QuickAction::Pull);
+    }
+    if !actions.is_empty() {
+        actions.push(QuickAction::Skip);
+    }
+    actions
+}
+
+fn describe_divergence(ahead: usize, behind: usize) -> String {
+    match (ahead, behind) {
+        (0, behind) => format!("{behind} commit(s) behind"),
+        (ahead, 0) => format!("{ahead} commit(s) ahead"),
+        (ahead, behind) => format!("diverged: {ahead} ahead, {behind} behind"),
+    }
+}
+
+/// Ask what to do about each out-of-sync remote and do it
+async fn offer_quick_sync(
+    config: &Config,
+    branch: &str,
+    is_clean: bool,
+    out_of_sync: &[OutOfSync],
+) -> Result<()> {
+    let manager = SyncManager::new(".")?
+        .with_proxy(config.settings.proxy_url.clone())
+        .with_tls(config.remote_tls())
+        .with_retries(config.settings.network_attempts);
+    let routing = Routing::from_config(config)?;
+
+    println!();
+    for entry in out_of_sync {
+        let remote = &entry.remote;
+        let route = routing.route(branch, remote);
+        let actions = quick_actions(entry.ahead, entry.behind, is_clean, route.permits());
+        if actions.is_empty() {
+            let hint = if entry.ahead > 0 && !route.permits() {
+                format!("not pushed: {} in [routing]", route.describe())
+            } else if entry.ahead > 0 {
+                "run 'multigit sync' to reconcile".to_string()
+            } else {
+                "commit or stash your changes to pull".to_string()
+            };
+            interactive::print_info(&format!(
+                "{remote}: {}; {hint}",
+                describe_divergence(entry.ahead, entry.behind)
+            ));
+            continue;
+        }
+
+        let labels: Vec<&str> = actions.iter().map(|action| action.label()).collect();
+        let prompt = format!(
+            "{remote} is {}",
+            describe_divergence(entry.ahead, entry.behind)
+        );
+        let action = match interactive::select_option(&prompt, &labels, 0)? {
+            Some(index) => actions[index],
+            // Esc ends the follow-up
+            None => break,
+        };
+
+        match action {
+            QuickAction::Push => {
+                let _lock = RepoLock::acquire(".", "status")?;
+                let results = manager
+                    .push_all(branch, std::slice::from_ref(remote))
+                    .await?;
+                for result in results {
+                    if result.success {
+                        interactive::print_success(&format!("Pushed '{branch}' to {remote}"));
+                    } else {
+                        interactive::print_error(&format!("{remote}: {}", result.message));
+                    }
+                }
+            }
+            QuickAction::Pull => {
+                let _lock = RepoLock::acquire(".", "status")?;
+                let fetched = manager.fetch_all(std::slice::from_ref(remote)).await?;
+                if let Some(failed) = fetched.iter().find(|result| !result.success) {
+                    interactive::print_error(&format!("{remote}: {}", failed.message));
+                    continue;
+                }
+                match manager.fast_forward(branch, remote) {
+                    Ok(pulled) => interactive::print_success(&format!(
+                        "Pulled {pulled} commit(s) from {remote}"
+                    )),
+                    Err(e) => interactive::print_error(&e.to_string()),
+                }
+            }
+            QuickAction::Skip => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Open pull/merge requests grouped by remote, looked up concurrently
+///
+/// Remotes whose credentials can't be loaded, or whose provider has no pull
+/// request support, are skipped.
+async fn open_pull_request_lines(config: &Config) -> Vec<String> {
+    let remotes = config.enabled_remotes();
+    if remotes.is_empty() {
+        return Vec::new();
+    }
+
+    let repo_name = std::env::current_dir()
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "repo".to_string());
+
+    let mut names: Vec<&String> = remotes.keys().collect();
+    names.sort();
+
+    let lookups = names.into_iter().map(|name| {
+        let provider = connect_remote(config, name, remotes[name]);
+        let repo_name = &repo_name;
+        async move { pull_request_lines(name, provider, repo_name).await }
+    });
+
+    let mut out = vec!["\nOpen pull requests:".to_string()];
+    out.extend(join_all(lookups).await.into_iter().flatten());
+    out
+}
+
+/// The open pull requests of one remote
+async fn pull_request_lines(
+    name: &str,
+    provider: Result<Arc<dyn Provider>>,
+    repo_name: &str,
+) -> Vec<String> {
+    let provider = match provider {
+        Ok(provider) => provider,
+        Err(e) => return vec![format!("  {name}: skipped ({e})")],
+    };
+
+    if !provider.supports_pull_requests() {
+        debug!("Provider for '{}' has no pull request support", name);
+        return Vec::new();
+    }
+
+    match provider
+        .list_pull_requests(repo_name, PullRequestState::Open)
+        .await
+    {
+        Ok(prs) if prs.is_empty() => vec![format!("  {name}: none")],
+        Ok(prs) => {
+            let mut out = vec![format!("  {name}:")];
+            for pr in prs {
+                out.push(format!("    #{} {} (@{})", pr.number, pr.title, pr.author));
+                out.push(format!("      {}", pr.url));
+            }
+            out
+        }
+        Err(e) => vec![format!("  {name}: failed to list pull requests ({e})")],
+    }
+}
+
+/// Remotes whose repository is public, for checking `settings.expect_private`
+pub(crate) fn public_remotes(repositories: &[(String, Repository)]) -> Vec<&str> {
+    repositories
+        .iter()
+        .filter(|(_, repository)| !repository.private)
+        .map(|(remote, _)| remote.as_str())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RouteRule;
+    use crate::providers::mock::MockProvider;
+    use crate::utils::error::MultiGitError;
+    use tempfile::TempDir;
+
+    fn commit(repo: &git2::Repository, message: &str) -> git2::Oid {
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let parents: Vec<git2::Commit<'_>> = repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok())
+            .into_iter()
+            .collect();
+        let parents: Vec<&git2::Commit<'_>> = parents.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_cached_ahead_behind_recomputed_after_commit() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        let base = commit(&repo, "initial");
+        let manager = SyncManager::new(temp_dir.path()).unwrap();
+        let branch = manager.current_branch().unwrap();
+        repo.reference(&format!("refs/remotes/github/{branch}"), base, true, "test")
+            .unwrap();
+        let github = "github".to_string();
+        let cache = temp_dir.path().join("cache/sync_state.json");
+
+        let counts = cached_ahead_behind(&manager, &branch, &[&github], &cache);
+        assert_eq!(counts.get("github"), Some(&(0, 0)));
+
+        // An unchanged HEAD reuses what is cached
+        let mut state = SyncState::load_from(&cache).unwrap();
+        assert_eq!(state.head_oid, Some(base.to_string()));
+        state.remotes.get_mut("github").unwrap().behind = 7;
+        state.save_to(&cache).unwrap();
+        let counts = cached_ahead_behind(&manager, &branch, &[&github], &cache);
+        assert_eq!(counts.get("github"), Some(&(0, 7)));
+
+        // A new commit moves HEAD, so the counts are computed again
+        let head = commit(&repo, "second");
+        let counts = cached_ahead_behind(&manager, &branch, &[&github], &cache);
+        assert_eq!(counts.get("github"), Some(&(1, 0)));
+        let state = SyncState::load_from(&cache).unwrap();
+        assert_eq!(state.head_oid, Some(head.to_string()));
+
+        // A push moves the remote-tracking branch, which also invalidates them
+        repo.reference(&format!("refs/remotes/github/{branch}"), head, true, "test")
+            .unwrap();
+        let counts = cached_ahead_behind(&manager, &branch, &[&github], &cache);
+        assert_eq!(counts.get("github"), Some(&(0, 0)));
+        let state = SyncState::load_from(&cache).unwrap();
+        assert_eq!(state.remotes["github"].tracking_oid, Some(head.to_string()));
+    }
+
+    #[test]
+    fn test_quick_actions() {
+        use QuickAction::{Pull, Push, Skip};
+
+        assert_eq!(quick_actions(2, 0, true, true), vec![Push, Skip]);
+        assert_eq!(quick_actions(0, 3, true, true), vec![Pull, Skip]);
+        // Pulling would overwrite local changes
+        assert!(quick_actions(0, 3, false, true).is_empty());
+        // Neither direction fast-forwards
+        assert!(quick_actions(1, 1, true, true).is_empty());
+    }
+
+    #[test]
+    fn test_quick_push_respects_routing() {
+        let mut config = Config::default();
+        config.routing.insert(
+            "internal/**".to_string(),
+            RouteRule {
+                allow: Vec::new(),
+                deny: vec!["public".to_string()],
+            },
+        );
+        let routing = Routing::from_config(&config).unwrap();
+
+        let denied = routing.route("internal/plan", "public").permits();
+        assert!(quick_actions(2, 0, true, denied).is_empty());
+        let allowed = routing.route("internal/plan", "origin").permits();
+        assert_eq!(
+            quick_actions(2, 0, true, allowed),
+            vec![QuickAction::Push, QuickAction::Skip]
+        );
+    }
+
+    #[test]
+    fn test_describe_divergence() {
+        assert_eq!(describe_divergence(0, 3), "3 commit(s) behind");
+        assert_eq!(describe_divergence(2, 0), "2 commit(s) ahead");
+        assert_eq!(describe_divergence(2, 3), "diverged: 2 ahead, 3 behind");
+    }
+
+    #[test]
+    fn test_submodule_lines() {
+        let submodule = |path: &str, state| SubmoduleInfo {
+            name: path.to_string(),
+            path: path.into(),
+            workdir: path.into(),
+            state,
+        };
+
+        assert!(submodule_lines(&[]).is_empty());
+        assert_eq!(
+            submodule_lines(&[
+                submodule("lib/core", SubmoduleState::UpToDate),
+                submodule("vendor/ui", SubmoduleState::OutOfDate),
+            ]),
+            vec![
+                "\nSubmodules:",
+                "  ✓ lib/core",
+                "  ⚠ vendor/ui - checked out at a different commit than recorded",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pull_request_lines() {
+        let missing = Err(MultiGitError::auth("github", "credentials not found"));
+        let lines = pull_request_lines("github", missing, "repo").await;
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].starts_with("  github: skipped"));
+
+        // Providers without pull request support are left out
+        let provider: Arc<dyn Provider> = Arc::new(MockProvider::new("bitbucket"));
+        assert!(pull_request_lines("bitbucket", Ok(provider), "repo")
+            .await
+            .is_empty());
+    }
+
+    #[test]
+    fn test_working_tree_lines() {
+        use crate::git::working_tree::ChangedFile;
+
+        let files = |code, count: usize| -> Vec<ChangedFile> {
+            (0..count)
+                .map(|i| ChangedFile {
+                    path: format!("f{i}.rs"),
+                    code,
+                })
+                .collect()
+        };
+        assert_eq!(
+            working_tree_lines(&WorkingTreeSummary::default(), true, false),
+            vec!["Working directory: clean"]
+        );
+
+        let changes = WorkingTreeSummary {
+            staged: files('A', 1),
+            untracked: files('?', LISTED_FILES + 2),
+            ..WorkingTreeSummary::default()
+        };
+        let lines = working_tree_lines(&changes, false, false);
+        assert_eq!(lines[0], "Working directory: 1 staged, 7 untracked");
+        assert_eq!(lines[1], "  Staged (1):");
+        assert_eq!(lines[2], "    A f0.rs");
+        assert_eq!(lines[3], "  Untracked (7):");
+        assert_eq!(lines.last().unwrap(), "    … and 2 more");
+
+        let lines = working_tree_lines(&changes, false, true);
+        assert_eq!(lines.last().unwrap(), "    ? f6.rs");
+    }
+}
@@ -1,50 +1,90 @@
 //! Interactive branch switcher
 //!
-//! Fuzzy-searchable branch switching
+//! Fuzzy-searchable branch switching. The picker lists the most recently
+//! used branches first, each with its last commit subject and how far it is
+//! ahead of or behind its upstream; `--recent` narrows it to the last few.
+//! A branch named on the command line must exist (locally or on a remote); a
+//! typo gets the closest branch suggested. `-` names the previous branch.
 
 use crate::cli::interactive;
+use crate::git::branch::BranchManager;
 use crate::git::operations::GitOperations;
+use crate::ui::formatter::Status;
 use crate::utils::error::{MultiGitError, Result};
 use dialoguer::{theme::ColorfulTheme, Confirm, FuzzySelect, Input};
+use git2::{BranchType, Repository};
 use std::process::Command;
 
-/// Execute interactive branch switch
-pub fn execute(new_branch: Option<String>) -> Result<()> {
-    let _git_ops =
+/// A local branch as the picker shows it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchChoice {
+    /// Branch name
+    pub name: String,
+    /// Subject line of the branch's last commit
+    pub subject: String,
+    /// Commits ahead of and behind the upstream, when there is one
+    pub ahead_behind: Option<(usize, usize)>,
+    /// Whether the branch is checked out
+    pub is_current: bool,
+}
+
+impl BranchChoice {
+    /// One picker line, with the name padded to `width`
+    #[must_use]
+    pub fn label(&self, width: usize) -> String {
+        let marker = if self.is_current { "→" } else { " " };
+        let tracking = match self.ahead_behind {
+            Some((0, 0)) => "=".to_string(),
+            Some((ahead, 0)) => format!("↑{ahead}"),
+            Some((0, behind)) => format!("↓{behind}"),
+            Some((ahead, behind)) => format!("↑{ahead} ↓{behind}"),
+            None => String::new(),
+        };
+        format!(
+            "{marker} {:width$}  {tracking:7}  {}",
+            self.name, self.subject
+        )
+        .trim_end()
+        .to_string()
+    }
+}
+
+/// Switch to `new_branch`, to the previous branch when it is `-`, or pick
+/// one: from every branch, or with `recent` from the last `recent` used
+pub fn execute(new_branch: Option<String>, recent: Option<usize>) -> Result<()> {
+    let git_ops =
         GitOperations::open(".").map_err(|_| MultiGitError::other("Not in a git repository."))?;
 
     if let Some(branch_name) = new_branch {
-        // Direct switch
+        let branch_name = if branch_name == "-" {
+            previous_branch(git_ops.inner())?
+        } else {
+            check_target(git_ops.inner(), &branch_name)?;
+            branch_name
+        };
         switch_to_branch(&branch_name)?;
-        interactive::print_success(&format!("✅ Switched to branch '{branch_name}'"));
+        interactive::print_success(&format!(
+            "{} Switched to branch '{branch_name}'",
+            Status::Success.icon()
+        ));
         return Ok(());
     }
 
-    println!("\n🔀 Branch Switcher\n");
-
-    // Get all branches
-    let branches = get_branches()?;
+    let branches = if let Some(limit) = recent {
+        println!("\n🕘 Recent Branches\n");
+        recent_choices(git_ops.inner(), limit)?
+    } else {
+        println!("\n🔀 Branch Switcher\n");
+        branch_choices(git_ops.inner())?
+    };
 
     if branches.is_empty() {
         println!("No branches found.");
         return Ok(());
     }
 
-    // Current branch
-    let current = get_current_branch()?;
-    println!("Current branch: {current}\n");
-
-    // Format branches with indicators
-    let branch_options: Vec<String> = branches
-        .iter()
-        .map(|b| {
-            if b == &current {
-                format!("→ {b} (current)")
-            } else {
-                format!("  {b}")
-            }
-        })
-        .collect();
+    let width = branches.iter().map(|b| b.name.len()).max().unwrap_or(0);
+    let branch_options: Vec<String> = branches.iter().map(|b| b.label(width)).collect();
 
     let selection = FuzzySelect::with_theme(&ColorfulTheme::default())
         .with_prompt("Select branch to switch to (type to search)")
@@ -53,14 +93,14 @@ pub fn execute(new_branch: Option<String>) -> Result<()> {
         .interact_opt()?;
 
     if let Some(idx) = selection {
-        let selected_branch = &branches[idx];
+        let selected = &branches[idx];
 
-        if selected_branch == &current {
-            println!("Already on branch '{current}'");
+        if selected.is_current {
+            println!("Already on branch '{}'", selected.name);
         } else {
             // Check for uncommitted changes
-            if has_uncommitted_changes()? {
-                println!("\n⚠️  You have uncommitted changes.");
+            if !git_ops.is_clean()? {
+                println!("\n{} You have uncommitted changes.", Status::Warning.icon());
                 let proceed = Confirm::with_theme(&ColorfulTheme::default())
                     .with_prompt("Switch anyway? (changes will be preserved)")
                     .default(false)
@@ -72,14 +112,105 @@ pub fn execute(new_branch: Option<String>) -> Result<()> {
                 }
             }
 
-            switch_to_branch(selected_branch)?;
-            interactive::print_success(&format!("✅ Switched to branch '{selected_branch}'"));
+            switch_to_branch(&selected.name)?;
+            interactive::print_success(&format!(
+                "{} Switched to branch '{}'",
+                Status::Success.icon(),
+                selected.name
+            ));
         }
     }
 
     Ok(())
 }
 
+/// The branch checked out before the current one, like `git switch -`
+fn previous_branch(repo: &Repository) -> Result<String> {
+    let manager = BranchManager::new(repo);
+    let previous = manager.previous()?.ok_or_else(|| {
+        MultiGitError::invalid_input("No previous branch: nothing else was checked out before")
+    })?;
+    if !manager.exists(&previous)? {
+        return Err(MultiGitError::invalid_input(format!(
+            "The previous checkout '{previous}' is not a branch (anymore)"
+        )));
+    }
+    Ok(previous)
+}
+
+/// The last `limit` branches used before the current one, most recent first
+pub fn recent_choices(repo: &Repository, limit: usize) -> Result<Vec<BranchChoice>> {
+    let recent = BranchManager::new(repo).recently_used()?;
+    Ok(branch_choices(repo)?
+        .into_iter()
+        .filter(|choice| !choice.is_current && recent.contains(&choice.name))
+        .take(limit)
+        .collect())
+}
+
+/// Local branches in picker order: most recently used first (from the HEAD
+/// reflog), then the others by last commit, newest first
+pub fn branch_choices(repo: &Repository) -> Result<Vec<BranchChoice>> {
+    let manager = BranchManager::new(repo);
+    let recent = manager.recently_used()?;
+
+    let mut choices = Vec::new();
+    for info in manager.list_local()? {
+        let commit = info.target.and_then(|oid| repo.find_commit(oid).ok());
+        let ahead_behind = info
+            .target
+            .zip(
+                info.upstream
+                    .as_deref()
+                    .and_then(|upstream| repo.find_branch(upstream, BranchType::Remote).ok())
+                    .and_then(|upstream| upstream.get().target()),
+            )
+            .and_then(|(local, upstream)| repo.graph_ahead_behind(local, upstream).ok());
+        let time = commit.as_ref().map_or(0, |c| c.time().seconds());
+        let subject = commit
+            .as_ref()
+            .and_then(|c| c.summary().map(String::from))
+            .unwrap_or_default();
+        choices.push((
+            BranchChoice {
+                name: info.name,
+                subject,
+                ahead_behind,
+                is_current: info.is_head,
+            },
+            time,
+        ));
+    }
+
+    choices.sort_by(|(a, a_time), (b, b_time)| {
+        let rank = |name: &str| recent.iter().position(|r| r == name).unwrap_or(usize::MAX);
+        rank(&a.name)
+            .cmp(&rank(&b.name))
+            .then(b_time.cmp(a_time))
+            .then(a.name.cmp(&b.name))
+    });
+    Ok(choices.into_iter().map(|(choice, _)| choice).collect())
+}
+
+/// That `name` can be switched to: a local branch, or one on a remote for
+/// `git checkout` to track
+fn check_target(repo: &Repository, name: &str) -> Result<()> {
+    let manager = BranchManager::new(repo);
+    if manager.exists(name)? {
+        return Ok(());
+    }
+    let on_remote = repo.remotes().is_ok_and(|remotes| {
+        remotes.iter().flatten().any(|remote| {
+            repo.find_reference(&format!("refs/remotes/{remote}/{name}"))
+                .is_ok()
+        })
+    });
+    if on_remote {
+        return Ok(());
+    }
+    manager.require(name)
+}
+
 /// Create and switch to new branch
 pub fn create_and_switch(from: Option<String>) -> Result<()> {
     println!("\n🌱 Create New Branch\n");
@@ -100,7 +231,7 @@ pub fn create_and_switch(from: Option<String>) -> Result<()> {
     let base_branch = if let Some(b) = from {
         b
     } else {
-        get_current_branch()?
+        GitOperations::open(".")?.current_branch()?
     };
 
     // Create and switch
@@ -117,48 +248,13 @@ pub fn create_and_switch(from: Option<String>) -> Result<()> {
     }
 
     interactive::print_success(&format!(
-        "✅ Created and switched to branch '{branch_name}'"
+        "{} Created and switched to branch '{branch_name}'",
+        Status::Success.icon()
     ));
 
     Ok(())
 }
 
-/// Get all branches
-fn get_branches() -> Result<Vec<String>> {
-    let output = Command::new("git")
-        .args(["branch", "--format=%(refname:short)"])
-        .output()
-        .map_err(|e| MultiGitError::other(format!("Failed to get branches: {e}")))?;
-
-    let branches: Vec<String> = String::from_utf8_lossy(&output.stdout)
-        .lines()
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-        .collect();
-
-    Ok(branches)
-}
-
-/// Get current branch
-fn get_current_branch() -> Result<String> {
-    let output = Command::new("git")
-        .args(["rev-parse", "--abbrev-ref", "HEAD"])
-        .output()
-        .map_err(|e| MultiGitError::other(format!("Failed to get current branch: {e}")))?;
-
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
-}
-
-/// Check for uncommitted changes
-fn has_uncommitted_changes() -> Result<bool> {
-    let output = Command::new("git")
-        .args(["status", "--porcelain"])
-        .output()
-        .map_err(|e| MultiGitError::other(format!("Failed to check status: {e}")))?;
-
-    Ok(!output.stdout.is_empty())
-}
-
 /// Switch to branch
 fn switch_to_branch(branch: &str) -> Result<()> {
     let output = Command::new("git")
@@ -173,3 +269,74 @@ fn switch_to_branch(branch: &str) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn repo() -> (TempDir, Repository) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        {
+            let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+            let tree = repo
+                .find_tree(repo.index().unwrap().write_tree().unwrap())
+                .unwrap();
+            repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+                .unwrap();
+        }
+        (temp_dir, repo)
+    }
+
+    #[test]
+    fn test_branch_choices_put_recent_first() {
+        let (_temp_dir, repo) = repo();
+        let manager = BranchManager::new(&repo);
+        let main = manager.current().unwrap();
+        manager.create("aaa-untouched", None).unwrap();
+        manager.create("feature", None).unwrap();
+        manager.checkout("feature").unwrap();
+        manager.checkout(&main).unwrap();
+
+        let choices = branch_choices(&repo).unwrap();
+        let names: Vec<&str> = choices.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec![main.as_str(), "feature", "aaa-untouched"]);
+        assert!(choices[0].is_current);
+
+        let recent = recent_choices(&repo, 5).unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].name, "feature");
+        assert_eq!(previous_branch(&repo).unwrap(), "feature");
+        assert_eq!(choices[1].subject, "Initial commit");
+        assert_eq!(choices[1].ahead_behind, None);
+    }
+
+    #[test]
+    fn test_label() {
+        let choice = BranchChoice {
+            name: "feature".to_string(),
+            subject: "Add login".to_string(),
+            ahead_behind: Some((2, 1)),
+            is_current: false,
+        };
+        assert_eq!(choice.label(10), "  feature     ↑2 ↓1    Add login");
+
+        let current = BranchChoice {
+            is_current: true,
+            ahead_behind: None,
+            ..choice
+        };
+        assert!(current.label(7).starts_with("→ feature"));
+    }
+
+    #[test]
+    fn test_check_target_suggests_closest() {
+        let (_temp_dir, repo) = repo();
+        BranchManager::new(&repo).create("feature", None).unwrap();
+
+        assert!(check_target(&repo, "feature").is_ok());
+        let err = check_target(&repo, "featur").unwrap_err();
+        assert!(err.to_string().contains("Did you mean 'feature'?"), "{err}");
+    }
+}
@@ -2,87 +2,631 @@
 //!
 //! Synchronize across all remotes (fetch + push).
 
+use crate::cli::commands::pull::{restore_autostash, AUTOSTASH_MESSAGE};
+use crate::cli::commands::push::{archived_remotes, upload_lfs_objects};
 use crate::core::config::Config;
-use crate::core::sync_manager::SyncManager;
-use crate::utils::error::Result;
+use crate::core::repo_lock::RepoLock;
+use crate::core::routing::Routing;
+use crate::core::sync_manager::{FetchResult, PushResult, SyncManager};
+use crate::git::branch_filter::BranchFilter;
+use crate::security::audit::{AuditEntry, AuditEventType, AuditLogger};
+use crate::ui::formatter::{Status, Table};
+use crate::utils::error::{MultiGitError, Result};
+use serde::Serialize;
+use std::time::Instant;
 use tracing::info;
 
-/// Synchronize across all remotes
-pub async fn execute(branch: Option<String>, dry_run: bool) -> Result<()> {
+/// Options for the sync command
+#[derive(Debug, Clone, Default)]
+pub struct SyncOptions {
+    /// Branch to sync (default: current branch)
+    pub branch: Option<String>,
+    /// Show what would be done without doing it
+    pub dry_run: bool,
+    /// Only sync with these remotes (default: all enabled remotes)
+    pub remotes: Vec<String>,
+    /// Only sync with the enabled remotes in this group
+    pub group: Option<String>,
+    /// Sync every local branch selected by the include/exclude globs
+    pub all_branches: bool,
+    /// Branch globs to include; replaces `sync.include_branches` when given
+    pub include_branches: Vec<String>,
+    /// Branch globs to exclude, in addition to `sync.exclude_branches`
+    pub exclude_branches: Vec<String>,
+    /// Print the summary report as JSON instead of progress and a table
+    pub json: bool,
+    /// Push to remotes whose repository is archived instead of skipping them
+    pub include_archived: bool,
+    /// Stash uncommitted changes for the duration of the sync (also `sync.autostash`)
+    pub autostash: bool,
+    /// Parallel remote operations; overrides `settings.max_parallel`
+    pub concurrency: Option<usize>,
+}
+
+/// Outcome and timing of one remote in a sync run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RemoteSyncTiming {
+    /// Remote name
+    pub remote: String,
+    /// Whether the remote's repository is archived, so nothing was pushed to it
+    pub archived: bool,
+    /// Whether the fetch succeeded
+    pub fetched: bool,
+    /// Time spent fetching, in milliseconds
+    pub fetch_ms: u64,
+    /// Branches pushed successfully
+    pub branches_pushed: usize,
+    /// Branches whose push failed
+    pub branches_failed: usize,
+    /// Time spent pushing all branches, in milliseconds
+    pub push_ms: u64,
+    /// Fetch plus push time, in milliseconds
+    pub total_ms: u64,
+    /// Failure messages, in the order they happened
+    pub errors: Vec<String>,
+}
+
+impl RemoteSyncTiming {
+    /// Whether every operation against this remote succeeded
+    #[must_use]
+    pub fn succeeded(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Per-phase timings of a sync run, shown as a summary at the end
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncReport {
+    /// Branches that were pushed
+    pub branches: Vec<String>,
+    /// One entry per remote, in sync order
+    pub remotes: Vec<RemoteSyncTiming>,
+    /// Wall-clock time of the fetch phase, in milliseconds
+    pub fetch_ms: u64,
+    /// Wall-clock time of the push phase, in milliseconds
+    pub push_ms: u64,
+    /// Wall-clock time of the whole sync, in milliseconds
+    pub total_ms: u64,
+    /// Whether Ctrl-C stopped the sync before every operation finished
+    pub interrupted: bool,
+}
+
+impl SyncReport {
+    /// Start an empty report for `branches` on `remotes`
+    #[must_use]
+    pub fn new(branches: &[String], remotes: &[String]) -> Self {
+        Self {
+            branches: branches.to_vec(),
+            remotes: remotes
+                .iter()
+                .map(|remote| RemoteSyncTiming {
+                    remote: remote.clone(),
+                    ..RemoteSyncTiming::default()
+                })
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Record the fetch from one remote
+    pub fn record_fetch(&mut self, result: &FetchResult) {
+        let entry = self.entry(&result.remote);
+        entry.fetched = result.success;
+        entry.fetch_ms += result.duration_ms;
+        entry.total_ms += result.duration_ms;
+        if !result.success {
+            entry.errors.push(result.message.clone());
+        }
+    }
+
+    /// Mark `remote` as archived; it is fetched from but not pushed to
+    pub fn record_archived(&mut self, remote: &str) {
+        self.entry(remote).archived = true;
+    }
+
+    /// Record one branch push to one remote
+    pub fn record_push(&mut self, result: &PushResult) {
+        let entry = self.entry(&result.remote);
+        entry.push_ms += result.duration_ms;
+        entry.total_ms += result.duration_ms;
+        if result.success {
+            entry.branches_pushed += 1;
+        } else {
+            entry.branches_failed += 1;
+            entry.errors.push(result.message.clone());
+        }
+    }
+
+    /// Number of failed fetches and pushes across all remotes
+    #[must_use]
+    pub fn failed_operations(&self) -> usize {
+        self.remotes.iter().map(|r| r.errors.len()).sum()
+    }
+
+    /// Summary table: one row per remote, slowest first
+    #[must_use]
+    pub fn table(&self) -> Table {
+        let mut table = Table::new(
+            [
+                "remote", "fetched", "pushed", "fetch ms", "push ms", "total ms", "result",
+            ]
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        );
+
+        let mut remotes: Vec<&RemoteSyncTiming> = self.remotes.iter().collect();
+        remotes.sort_by_key(|timing| std::cmp::Reverse(timing.total_ms));
+
+        for timing in remotes {
+            let pushes = timing.branches_pushed + timing.branches_failed;
+            table.add_row(vec![
+                timing.remote.clone(),
+                if timing.fetched { "yes" } else { "no" }.to_string(),
+                format!("{}/{pushes}", timing.branches_pushed),
+                timing.fetch_ms.to_string(),
+                timing.push_ms.to_string(),
+                timing.total_ms.to_string(),
+                if !timing.succeeded() {
+                    "failed"
+                } else if timing.archived {
+                    "archived"
+                } else {
+                    "ok"
+                }
+                .to_string(),
+            ]);
+        }
+
+        table
+    }
+
+    fn entry(&mut self, remote: &str) -> &mut RemoteSyncTiming {
+        if let Some(index) = self.remotes.iter().position(|r| r.remote == remote) {
+            &mut self.remotes[index]
+        } else {
+            self.remotes.push(RemoteSyncTiming {
+                remote: remote.to_string(),
+                ..RemoteSyncTiming::default()
+            });
+            self.remotes.last_mut().expect("entry was just pushed")
+        }
+    }
+}
+
+/// Synchronize across all remotes (or only `options.remotes`, if given)
+///
+/// Returns an error if fetching from or pushing to any remote failed.
+pub async fn execute(options: SyncOptions) -> Result<()> {
     info!("Executing sync command");
+    let _lock = RepoLock::acquire(".", "sync")?;
 
     // Load config to get settings
     let config = Config::load().unwrap_or_default();
 
-    let manager = SyncManager::new(".")?.with_max_parallel(config.settings.max_parallel);
+    let manager = SyncManager::new(".")?
+        .with_max_parallel(options.concurrency.unwrap_or(config.settings.max_parallel))
+        .with_proxy(config.settings.proxy_url.clone())
+        .with_tls(config.remote_tls())
+        .with_fetch_depth(config.settings.fetch_depth)
+        .with_retries(config.settings.network_attempts);
+    manager.ensure_no_operation_in_progress("sync")?;
 
-    // Get branch to sync
-    let branch_name = match branch {
-        Some(b) => b,
-        None => manager.current_branch()?,
+    // Get branches to sync
+    let branches = if options.all_branches {
+        let filter = branch_filter(&config, &options)?;
+        filter.filter(manager.local_branches()?)
+    } else {
+        match options.branch {
+            Some(ref b) => vec![b.clone()],
+            None => vec![manager.current_branch()?],
+        }
     };
 
-    // Check if working directory is clean
-    if !manager.is_clean()? {
-        println!("⚠ Warning: Working directory has uncommitted changes");
-        println!("Commit or stash changes before syncing.\n");
+    if branches.is_empty() {
+        println!(
+            "{} No branches match the include/exclude patterns.",
+            Status::Warning.icon()
+        );
+        println!("Preview matches with: multigit branch list --matching <glob>");
         return Ok(());
     }
 
-    // Load configured remotes from config
-    let config = Config::load().unwrap_or_default();
-    let enabled: Vec<String> = config.enabled_remotes().keys().cloned().collect();
+    // Check if working directory is clean
+    let autostash = options.autostash || config.sync.autostash;
+    let dirty = !manager.is_clean()?;
+    if dirty && !autostash {
+        println!(
+            "{} Warning: Working directory has uncommitted changes",
+            Status::Warning.icon()
+        );
+        println!("Commit or stash changes before syncing, or pass --autostash.\n");
+        return Ok(());
+    }
 
+    let enabled = selected_remotes(&config, &options)?;
     if enabled.is_empty() {
-        println!("⚠ No remotes configured yet.");
+        println!("{} No remotes configured yet.", Status::Warning.icon());
         println!("Use 'multigit remote add' to configure remotes.");
         println!("\nExample:");
         println!("  multigit remote add github your-username");
         return Ok(());
     }
 
-    if dry_run {
+    let branch_label = describe_branches(&branches);
+    let routing = Routing::from_config(&config)?;
+
+    let archived = if options.include_archived {
+        Vec::new()
+    } else {
+        archived_remotes(&config, &enabled, !options.dry_run).await
+    };
+
+    if options.dry_run {
+        print_dry_run(&branch_label, &branches, &enabled, &archived, &routing);
+        return Ok(());
+    }
+
+    let quiet = options.json;
+    if !quiet {
         println!(
-            "\n[DRY RUN] Would sync branch '{}' with {} remote(s):",
-            branch_name,
+            "\n🔄 Syncing {} with {} remote(s)...\n",
+            branch_label,
             enabled.len()
         );
-        for remote in &enabled {
-            println!("  - {remote}");
+    }
+
+    let stashed = dirty && manager.stash_save(AUTOSTASH_MESSAGE)?.is_some();
+    if stashed && !quiet {
+        println!("📦 Stashed local changes\n");
+    }
+
+    let report = run_sync(&manager, &branches, &enabled, &archived, &routing, quiet).await;
+    let restored = if stashed {
+        restore_autostash(manager.stash_pop())
+    } else {
+        Ok(())
+    };
+    let report = report?;
+    audit_sync(&config, &report);
+
+    print_summary(&config, &report, options.json)?;
+
+    restored?;
+    if stashed && !quiet {
+        println!("\n📦 Restored stashed local changes");
+    }
+
+    if report.interrupted {
+        return Err(MultiGitError::other(
+            "Sync interrupted; the summary shows what finished",
+        ));
+    }
+
+    let failed = report.failed_operations();
+    if failed > 0 {
+        return Err(MultiGitError::other(format!(
+            "Sync finished with {failed} failed operation(s)"
+        )));
+    }
+
+    if !quiet {
+        println!("\n{} Sync complete!", Status::Success.icon());
+    }
+
+    Ok(())
+}
+
+/// The enabled remotes to sync, narrowed to `options.remotes` and
+/// `options.group` when given
+fn selected_remotes(config: &Config, options: &SyncOptions) -> Result<Vec<String>> {
+    let mut enabled: Vec<String> = config.enabled_remotes().keys().cloned().collect();
+    if !options.remotes.is_empty() {
+        if let Some(unknown) = options.remotes.iter().find(|r| !enabled.contains(r)) {
+            return Err(MultiGitError::RemoteNotFound(unknown.clone()));
         }
-        println!("\n[DRY RUN] No changes were made.");
-        return Ok(());
+        enabled.retain(|r| options.remotes.contains(r));
+    }
+    if let Some(group) = &options.group {
+        let members = config.enabled_remotes_in_group(group)?;
+        enabled.retain(|r| members.contains(r));
+    }
+    Ok(enabled)
+}
+
+/// Print the timing summary as a table, or as JSON with `json`
+fn print_summary(config: &Config, report: &SyncReport, json: bool) -> Result<()> {
+    if json {
+        let output = serde_json::to_string_pretty(report)
+            .map_err(|e| MultiGitError::other(format!("Failed to serialize report: {e}")))?;
+        println!("{output}");
+    } else {
+        println!("\n⏱  Summary ({}ms total):", report.total_ms);
+        let table = report.table();
+        if config.settings.colored_output {
+            table.print();
+        } else {
+            table.no_colors().print();
+        }
+    }
+    Ok(())
+}
+
+/// Record one audit entry per remote with what the sync did there
+fn audit_sync(config: &Config, report: &SyncReport) {
+    let logger = AuditLogger::at_default_path(config.security.audit_log);
+    let branches = report.branches.join(",");
+    for remote in &report.remotes {
+        let mut message = format!(
+            "{} branch(es) pushed, {} failed",
+            remote.branches_pushed, remote.branches_failed
+        );
+        if remote.archived {
+            message.push_str(" (archived: fetch only)");
+        }
+        for error in &remote.errors {
+            message.push_str("; ");
+            message.push_str(error);
+        }
+        let mut entry = AuditEntry::new(
+            AuditEventType::Sync,
+            format!("{}:{branches}", remote.remote),
+            remote.succeeded(),
+        )
+        .with_message(message);
+        if let Some(remote_config) = config.remotes.get(&remote.remote) {
+            entry = entry.with_user(remote_config.username.clone());
+        }
+        logger.log(entry);
     }
+}
 
+/// Describe what a sync would do without touching any remote
+fn print_dry_run(
+    branch_label: &str,
+    branches: &[String],
+    remotes: &[String],
+    archived: &[String],
+    routing: &Routing,
+) {
     println!(
-        "\n🔄 Syncing branch '{}' with {} remote(s)...\n",
-        branch_name,
-        enabled.len()
+        "\n[DRY RUN] Would sync {} with {} remote(s):",
+        branch_label,
+        remotes.len()
     );
+    for remote in remotes {
+        if archived.contains(remote) {
+            println!("  - {remote} (archived: fetch only, push skipped)");
+        } else {
+            println!("  - {remote}");
+        }
+    }
+    if branches.len() > 1 {
+        println!("\nBranches:");
+        for branch in branches {
+            println!("  - {branch}");
+        }
+    }
+    for branch in branches {
+        let (_, blocked) = routing.partition(branch, remotes);
+        if !blocked.is_empty() {
+            println!(
+                "\n'{branch}' would not be pushed to {} ([routing])",
+                blocked.join(", ")
+            );
+        }
+    }
+    println!("\n[DRY RUN] No changes were made.");
+}
+
+/// Fetch from and push `branches` to `remotes`, timing each phase
+///
+/// `archived` remotes are fetched from but not pushed to, and a branch skips
+/// the remotes `routing` keeps it away from. Progress lines are printed
+/// unless `quiet`.
+async fn run_sync(
+    manager: &SyncManager,
+    branches: &[String],
+    remotes: &[String],
+    archived: &[String],
+    routing: &Routing,
+    quiet: bool,
+) -> Result<SyncReport> {
+    let ok = Status::Success.icon();
+    let error = Status::Error.icon();
+    let skipped = Status::Skipped.icon();
+    let started = Instant::now();
+    let mut report = SyncReport::new(branches, remotes);
+    for remote in archived {
+        report.record_archived(remote);
+    }
+    let push_remotes: Vec<String> = remotes
+        .iter()
+        .filter(|remote| !archived.contains(remote))
+        .cloned()
+        .collect();
 
     // Fetch from all remotes
-    println!("📥 Fetching updates...");
-    let fetch_results = manager.fetch_all(&enabled).await?;
+    if !quiet {
+        println!("📥 Fetching updates...");
+    }
+    let phase = Instant::now();
+    let fetch_results = manager.fetch_all(remotes).await?;
+    report.fetch_ms = elapsed_ms(phase);
     for result in &fetch_results {
+        report.record_fetch(result);
+        if quiet {
+            continue;
+        }
         if result.success {
-            println!("  ✓ {} - fetched", result.remote);
+            println!("  {ok} {} - fetched", result.remote);
+        } else if result.cancelled {
+            println!("  {skipped} {} - interrupted", result.remote);
         } else {
-            println!("  ✗ {} - {}", result.remote, result.message);
+            println!("  {error} {} - {}", result.remote, result.message);
         }
     }
 
     // Push to all remotes
-    println!("\n📤 Pushing changes...");
-    let push_results = manager.push_all(&branch_name, &enabled).await?;
-    for result in &push_results {
-        if result.success {
-            println!("  ✓ {} - pushed", result.remote);
-        } else {
-            println!("  ✗ {} - {}", result.remote, result.message);
+    if !quiet {
+        for remote in archived {
+            println!("\n{skipped} {remote} - remote is archived, skipping push (use --include-archived to try anyway)");
+        }
+    }
+    let phase = Instant::now();
+    let uses_lfs = manager.uses_lfs();
+    for branch_name in branches {
+        if push_remotes.is_empty() || manager.is_cancelled() {
+            break;
+        }
+        if !quiet {
+            if branches.len() > 1 {
+                println!("\n📤 Pushing '{branch_name}'...");
+            } else {
+                println!("\n📤 Pushing changes...");
+            }
+        }
+
+        let (branch_remotes, blocked) = routing.partition(branch_name, &push_remotes);
+        if !quiet {
+            for remote in &blocked {
+                println!(
+                    "  {skipped} {remote} - {} in [routing]",
+                    routing.route(branch_name, remote).describe()
+                );
+            }
+        }
+        if branch_remotes.is_empty() {
+            continue;
+        }
+
+        let mut push_results = manager.push_all(branch_name, &branch_remotes).await?;
+        if uses_lfs {
+            upload_lfs_objects(manager, branch_name, &mut push_results);
+        }
+        for result in &push_results {
+            report.record_push(result);
+            if quiet {
+                continue;
+            }
+            if result.success {
+                println!("  {ok} {} - pushed", result.remote);
+            } else if result.cancelled {
+                println!("  {skipped} {} - interrupted", result.remote);
+            } else {
+                println!("  {error} {} - {}", result.remote, result.message);
+            }
         }
     }
+    report.push_ms = elapsed_ms(phase);
+    report.total_ms = elapsed_ms(started);
+    report.interrupted = manager.is_cancelled();
 
-    println!("\n✅ Sync complete!");
+    Ok(report)
+}
 
-    Ok(())
+fn elapsed_ms(since: Instant) -> u64 {
+    u64::try_from(since.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Combine CLI patterns with the `[sync]` config
+///
+/// CLI includes replace the configured ones; CLI excludes add to them.
+fn branch_filter(config: &Config, options: &SyncOptions) -> Result<BranchFilter> {
+    let include = if options.include_branches.is_empty() {
+        &config.sync.include_branches
+    } else {
+        &options.include_branches
+    };
+
+    let mut exclude = config.sync.exclude_branches.clone();
+    exclude.extend(options.exclude_branches.iter().cloned());
+
+    BranchFilter::new(include, &exclude)
+}
+
+fn describe_branches(branches: &[String]) -> String {
+    match branches {
+        [branch] => format!("branch '{branch}'"),
+        _ => format!("{} branches", branches.len()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(remote: &str, success: bool, duration_ms: u64) -> PushResult {
+        PushResult {
+            remote: remote.to_string(),
+            success,
+            message: if success { "ok" } else { "rejected" }.to_string(),
+            duration_ms,
+            cancelled: false,
+            rejected: false,
+            updates: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_report_accumulates_phases_per_remote() {
+        let branches = vec!["main".to_string(), "dev".to_string()];
+        let remotes = vec!["github".to_string(), "gitlab".to_string()];
+        let mut report = SyncReport::new(&branches, &remotes);
+
+        for (remote, duration_ms) in [("github", 100), ("gitlab", 900)] {
+            report.record_fetch(&FetchResult {
+                remote: remote.to_string(),
+                success: true,
+                message: String::new(),
+                commits_fetched: 0,
+                duration_ms,
+                cancelled: false,
+            });
+        }
+        report.record_push(&push("github", true, 50));
+        report.record_push(&push("github", true, 70));
+        report.record_push(&push("gitlab", true, 300));
+        report.record_push(&push("gitlab", false, 20));
+
+        let github = &report.remotes[0];
+        assert_eq!(
+            (github.fetch_ms, github.push_ms, github.total_ms),
+            (100, 120, 220)
+        );
+        assert_eq!(github.branches_pushed, 2);
+        assert!(github.succeeded());
+
+        let gitlab = &report.remotes[1];
+        assert_eq!(gitlab.total_ms, 1220);
+        assert_eq!((gitlab.branches_pushed, gitlab.branches_failed), (1, 1));
+        assert!(!gitlab.succeeded());
+        assert_eq!(report.failed_operations(), 1);
+    }
+
+    #[test]
+    fn test_report_serializes_timings() {
+        let mut report = SyncReport::new(&["main".to_string()], &["github".to_string()]);
+        report.record_push(&push("github", true, 42));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["remotes"][0]["remote"], "github");
+        assert_eq!(json["remotes"][0]["push_ms"], 42);
+        assert_eq!(json["branches"][0], "main");
+    }
+
+    #[test]
+    fn test_archived_remote_is_not_a_failure() {
+        let remotes = vec!["github".to_string(), "gitlab".to_string()];
+        let mut report = SyncReport::new(&["main".to_string()], &remotes);
+        report.record_archived("gitlab");
+        report.record_push(&push("github", true, 10));
+
+        assert!(report.remotes[1].archived);
+        assert!(report.remotes[1].succeeded());
+        assert_eq!(report.failed_operations(), 0);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["remotes"][1]["archived"], true);
+    }
 }
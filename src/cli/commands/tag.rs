@@ -1,15 +1,27 @@
 //! Tag command implementation
 //!
 //! Manage tags across all remotes.
+//!
+//! Lightweight and annotated tags are created through git2. git2 can't sign,
+//! so signed tags shell out to `git tag -s`, with `-u` for the key in
+//! `security.signing_key` or else whatever GPG or SSH setup git has.
 
+use crate::core::config::Config;
 use crate::git::operations::GitOperations;
+use crate::git::signing::{git_signing_key, remediation, tag_sign_args};
+use crate::ui::formatter::Status;
 use crate::utils::error::{MultiGitError, Result};
 use git2::{ObjectType, Signature};
+use std::path::Path;
+use std::process::Command;
 use tracing::info;
 
 /// Tag subcommands
 pub mod commands {
-    use super::{info, GitOperations, MultiGitError, ObjectType, Result, Signature};
+    use super::{
+        create_signed, info, verify_signed, Config, GitOperations, MultiGitError, ObjectType,
+        Result, Signature, Status,
+    };
 
     /// List tags
     pub fn list(_remote: Option<String>) -> Result<()> {
@@ -38,7 +50,11 @@ pub mod commands {
     }
 
     /// Create a tag locally and push to all remotes
-    pub fn create(name: String, message: Option<String>, _sign: bool) -> Result<()> {
+    ///
+    /// With `sign`, an annotated tag signed with `security.signing_key` (or
+    /// git's own GPG or SSH setup) is created by `git tag -s` and its
+    /// signature verified afterwards.
+    pub fn create(name: String, message: Option<String>, sign: bool) -> Result<()> {
         info!("Creating tag: {}", name);
 
         let git_ops = GitOperations::open(".")?;
@@ -50,7 +66,21 @@ pub mod commands {
         let target_oid = head_commit.id();
 
         // Create the tag
-        if let Some(msg) = message {
+        if sign {
+            println!("🔏 Creating signed tag...");
+
+            // Signed tags are always annotated; default the message to the tag name
+            let msg = message.unwrap_or_else(|| name.clone());
+            let workdir = git_ops.workdir()?;
+            let config = Config::load()?;
+            create_signed(workdir, &name, &msg, config.security.signing_key())?;
+            println!("{} Signed tag created", Status::Success.icon());
+
+            match verify_signed(workdir, &name) {
+                Ok(()) => println!("{} Signature verified", Status::Success.icon()),
+                Err(e) => println!("{} Could not verify signature: {e}", Status::Warning.icon()),
+            }
+        } else if let Some(msg) = message {
             // Annotated tag
             println!("📝 Creating annotated tag...");
 
@@ -62,7 +92,7 @@ pub mod commands {
                 .tag(&name, head_commit.as_object(), &sig, &msg, false)
                 .map_err(MultiGitError::GitError)?;
 
-            println!("✓ Annotated tag created");
+            println!("{} Annotated tag created", Status::Success.icon());
         } else {
             // Lightweight tag
             println!("📌 Creating lightweight tag...");
@@ -77,7 +107,7 @@ pub mod commands {
                 .tag_lightweight(&name, &obj, false)
                 .map_err(MultiGitError::GitError)?;
 
-            println!("✓ Lightweight tag created");
+            println!("{} Lightweight tag created", Status::Success.icon());
         }
 
         println!("\n💡 To push tags to all remotes, use:");
@@ -87,6 +117,20 @@ pub mod commands {
         Ok(())
     }
 
+    /// Verify the signature of a tag
+    pub fn verify(name: String) -> Result<()> {
+        info!("Verifying tag: {}", name);
+
+        let git_ops = GitOperations::open(".")?;
+        verify_signed(git_ops.workdir()?, &name)?;
+
+        println!(
+            "{} Tag '{name}' has a good signature",
+            Status::Success.icon()
+        );
+        Ok(())
+    }
+
     /// Delete a tag from local and all remotes
     pub fn delete(name: String) -> Result<()> {
         info!("Deleting tag: {}", name);
@@ -101,10 +145,10 @@ pub mod commands {
         match git_ops.inner().find_reference(&tag_ref) {
             Ok(mut tag_reference) => {
                 tag_reference.delete().map_err(MultiGitError::GitError)?;
-                println!("✓ Local tag deleted");
+                println!("{} Local tag deleted", Status::Success.icon());
             }
             Err(_) => {
-                println!("⚠️  Tag '{name}' not found locally");
+                println!("{} Tag '{name}' not found locally", Status::Warning.icon());
             }
         }
 
@@ -115,3 +159,85 @@ pub mod commands {
         Ok(())
     }
 }
+
+/// Create a signed annotated tag on HEAD with `git tag -s`, using `key`
+/// (`security.signing_key`) when given and git's own setup otherwise
+pub(crate) fn create_signed(
+    workdir: &Path,
+    name: &str,
+    message: &str,
+    key: Option<&str>,
+) -> Result<()> {
+    let output = Command::new("git")
+        .arg("tag")
+        .args(tag_sign_args(key))
+        .args([name, "-m", message])
+        .current_dir(workdir)
+        .output()
+        .map_err(|e| MultiGitError::other(format!("Failed to execute git: {e}")))?;
+
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let has_key = key.is_some() || has_signing_key(workdir);
+    Err(signing_error(stderr.trim(), has_key))
+}
+
+/// Verify a tag's signature with `git tag -v`
+fn verify_signed(workdir: &Path, name: &str) -> Result<()> {
+    let output = Command::new("git")
+        .args(["tag", "-v", name])
+        .current_dir(workdir)
+        .output()
+        .map_err(|e| MultiGitError::other(format!("Failed to execute git: {e}")))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(MultiGitError::other(format!(
+            "Signature verification failed for tag '{name}': {}",
+            stderr.trim()
+        )))
+    }
+}
+
+/// Whether `user.signingkey` is set for the repository
+fn has_signing_key(workdir: &Path) -> bool {
+    git2::Repository::open(workdir).is_ok_and(|repo| git_signing_key(&repo).is_some())
+}
+
+/// Explain a failed `git tag -s`, pointing at the missing key if there is none
+fn signing_error(stderr: &str, has_key: bool) -> MultiGitError {
+    if has_key {
+        MultiGitError::other(format!("Failed to create signed tag: {stderr}"))
+    } else {
+        MultiGitError::other(format!(
+            "Failed to create signed tag: no signing key configured ({stderr})\n{}",
+            remediation()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signing_error_without_key_explains_setup() {
+        let err = signing_error("gpg: skipped \"Alice\": No secret key", false).to_string();
+        assert!(err.contains("no signing key configured"));
+        assert!(err.contains("signing_key"));
+        assert!(err.contains("git config user.signingkey"));
+        assert!(err.contains("No secret key"));
+    }
+
+    #[test]
+    fn test_signing_error_with_key_passes_git_output_through() {
+        let err = signing_error("gpg failed to sign the data", true).to_string();
+        assert!(err.contains("gpg failed to sign the data"));
+        assert!(!err.contains("no signing key configured"));
+    }
+}
@@ -3,6 +3,7 @@
 //! Undo commits, unstage files, or discard changes safely
 
 use crate::cli::interactive;
+use crate::ui::formatter::Status;
 use crate::utils::error::{MultiGitError, Result};
 use dialoguer::{theme::ColorfulTheme, Confirm, Select};
 use std::process::Command;
@@ -77,9 +78,9 @@ fn undo_last_commit(keep_changes: bool) -> Result<()> {
 
     if output.status.success() {
         if keep_changes {
-            interactive::print_success("✅ Commit undone, changes preserved!");
+            interactive::print_success("Commit undone, changes preserved!");
         } else {
-            interactive::print_success("✅ Commit undone and changes discarded!");
+            interactive::print_success("Commit undone and changes discarded!");
         }
     } else {
         let error = String::from_utf8_lossy(&output.stderr);
@@ -97,7 +98,7 @@ fn unstage_all() -> Result<()> {
         .map_err(|e| MultiGitError::other(format!("Failed to unstage: {e}")))?;
 
     if output.status.success() {
-        interactive::print_success("✅ All files unstaged!");
+        interactive::print_success("All files unstaged!");
     } else {
         let error = String::from_utf8_lossy(&output.stderr);
         return Err(MultiGitError::other(format!("Failed to unstage: {error}")));
@@ -108,7 +109,10 @@ fn unstage_all() -> Result<()> {
 
 /// Discard all uncommitted changes
 fn discard_all_changes() -> Result<()> {
-    println!("\n⚠️  WARNING: This will discard ALL uncommitted changes!");
+    println!(
+        "\n{} WARNING: This will discard ALL uncommitted changes!",
+        Status::Warning.icon()
+    );
 
     let confirm = Confirm::with_theme(&ColorfulTheme::default())
         .with_prompt("Are you absolutely sure?")
@@ -146,7 +150,7 @@ fn discard_all_changes() -> Result<()> {
             .map_err(|e| MultiGitError::other(format!("Failed to clean: {e}")))?;
     }
 
-    interactive::print_success("✅ Changes discarded!");
+    interactive::print_success("Changes discarded!");
 
     Ok(())
 }
@@ -198,7 +202,8 @@ fn discard_file_changes() -> Result<()> {
     }
 
     interactive::print_success(&format!(
-        "✅ Discarded changes in {} file(s)!",
+        "{} Discarded changes in {} file(s)!",
+        Status::Success.icon(),
         selections.len()
     ));
 
@@ -262,7 +267,10 @@ fn reset_to_commit() -> Result<()> {
         .map_err(|e| MultiGitError::other(format!("Failed to reset: {e}")))?;
 
     if output.status.success() {
-        interactive::print_success(&format!("✅ Reset to {commit_hash} ({reset_type})"));
+        interactive::print_success(&format!(
+            "{} Reset to {commit_hash} ({reset_type})",
+            Status::Success.icon()
+        ));
     } else {
         let error = String::from_utf8_lossy(&output.stderr);
         return Err(MultiGitError::other(format!("Failed to reset: {error}")));
@@ -0,0 +1,347 @@
+//! Validate command implementation
+//!
+//! A read-only preflight before a big operation: the config, the git
+//! repository, and every enabled remote's credentials and connectivity are
+//! checked in one go. Unlike `doctor` nothing is ever changed, and any failed
+//! check makes the command exit non-zero so it can gate a CI job.
+
+use crate::core::auth::{AuthBackend, AuthManager};
+use crate::core::config::Config;
+use crate::git::operations::GitOperations;
+use crate::providers::factory::{create_provider_with_options, get_provider_host};
+use crate::providers::traits::Provider;
+use crate::ui::formatter::{colors, Status, Table};
+use crate::utils::error::{MultiGitError, Result};
+use futures::future::join_all;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How one check turned out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    /// Nothing to worry about
+    Pass,
+    /// Worth a look, but only fails the run with `--strict`
+    Warn,
+    /// Fails the run
+    Fail,
+}
+
+impl Outcome {
+    fn status(self) -> Status {
+        match self {
+            Self::Pass => Status::Success,
+            Self::Warn => Status::Warning,
+            Self::Fail => Status::Error,
+        }
+    }
+}
+
+/// The result of one check
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    /// What was checked, e.g. `config`, `repository` or `remote github`
+    pub subject: String,
+    /// How it turned out
+    pub outcome: Outcome,
+    /// What was found
+    pub detail: String,
+}
+
+impl Check {
+    fn new(subject: impl Into<String>, outcome: Outcome, detail: impl Into<String>) -> Self {
+        Self {
+            subject: subject.into(),
+            outcome,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// The JSON report
+#[derive(Serialize)]
+struct Report<'a> {
+    passed: bool,
+    checks: &'a [Check],
+}
+
+/// Check the config, repository and remotes, failing if any check fails (or,
+/// with `strict`, warns)
+pub async fn execute(strict: bool, timeout_secs: Option<u64>, json: bool) -> Result<()> {
+    let mut checks = Vec::new();
+
+    let config = match Config::load() {
+        Ok(config) => {
+            checks.extend(config_checks(&config));
+            Some(config)
+        }
+        Err(e) => {
+            checks.push(Check::new("config", Outcome::Fail, e.to_string()));
+            None
+        }
+    };
+
+    checks.extend(repository_checks("."));
+
+    if let Some(config) = &config {
+        checks.extend(remote_checks(config, timeout_secs).await);
+    }
+
+    let passed = passed(&checks, strict);
+    if json {
+        let output = serde_json::to_string_pretty(&Report {
+            passed,
+            checks: &checks,
+        })
+        .map_err(|e| MultiGitError::other(format!("Failed to serialize report: {e}")))?;
+        println!("{output}");
+    } else {
+        print_report(&checks);
+    }
+
+    if passed {
+        if !json {
+            println!("\n{} Validation passed", Status::Success.colored_icon());
+        }
+        Ok(())
+    } else {
+        let failed = checks
+            .iter()
+            .filter(|check| counts_as_failure(check.outcome, strict))
+            .count();
+        Err(MultiGitError::other(format!(
+            "Validation failed: {failed} check(s) did not pass"
+        )))
+    }
+}
+
+fn counts_as_failure(outcome: Outcome, strict: bool) -> bool {
+    match outcome {
+        Outcome::Pass => false,
+        Outcome::Warn => strict,
+        Outcome::Fail => true,
+    }
+}
+
+/// Whether the run passes: nothing failed, and with `strict` nothing warned
+#[must_use]
+pub fn passed(checks: &[Check], strict: bool) -> bool {
+    !checks
+        .iter()
+        .any(|check| counts_as_failure(check.outcome, strict))
+}
+
+/// One failure per problem [`Config::validate`] finds, or a single pass
+#[must_use]
+pub fn config_checks(config: &Config) -> Vec<Check> {
+    let problems = config.validate();
+    if problems.is_empty() {
+        return vec![Check::new("config", Outcome::Pass, "valid")];
+    }
+    problems
+        .into_iter()
+        .map(|problem| Check::new("config", Outcome::Fail, problem))
+        .collect()
+}
+
+/// The repository at `path`: that it opens, has a branch checked out, isn't
+/// mid-operation, and whether the working tree is clean
+#[must_use]
+pub fn repository_checks(path: &str) -> Vec<Check> {
+    let git_ops = match GitOperations::open(path) {
+        Ok(git_ops) => git_ops,
+        Err(e) => return vec![Check::new("repository", Outcome::Fail, e.to_string())],
+    };
+
+    let mut checks = Vec::new();
+    checks.push(match git_ops.current_branch() {
+        Ok(branch) => Check::new("branch", Outcome::Pass, format!("on '{branch}'")),
+        Err(_) => Check::new("branch", Outcome::Fail, "detached HEAD or no commits"),
+    });
+
+    match git_ops.operation_in_progress() {
+        Ok(Some(operation)) => checks.push(Check::new(
+            "repository",
+            Outcome::Fail,
+            format!("{operation}. {}", operation.guidance()),
+        )),
+        Ok(None) => {}
+        Err(e) => checks.push(Check::new("repository", Outcome::Fail, e.to_string())),
+    }
+
+    checks.push(match git_ops.is_clean() {
+        Ok(true) => Check::new("working tree", Outcome::Pass, "clean"),
+        Ok(false) => Check::new("working tree", Outcome::Warn, "has uncommitted changes"),
+        Err(e) => Check::new("working tree", Outcome::Fail, e.to_string()),
+    });
+    checks
+}
+
+/// Credentials and connectivity of every enabled remote, tested concurrently
+async fn remote_checks(config: &Config, timeout_secs: Option<u64>) -> Vec<Check> {
+    let mut names: Vec<String> = config.enabled_remotes().into_keys().collect();
+    if names.is_empty() {
+        return vec![Check::new(
+            "remotes",
+            Outcome::Warn,
+            "no enabled remotes configured",
+        )];
+    }
+    names.sort();
+
+    let auth_manager = AuthManager::new(AuthBackend::Keyring, config.security.audit_log);
+    let checks = names.iter().map(|name| {
+        let subject = format!("remote {name}");
+        let provider = connect(config, &auth_manager, name, timeout_secs);
+        async move {
+            match provider {
+                Ok((provider, timeout)) => {
+                    connection_check(subject, provider.as_ref(), timeout).await
+                }
+                Err(e) => Check::new(subject, Outcome::Fail, e.to_string()),
+            }
+        }
+    });
+    join_all(checks).await
+}
+
+/// A provider for `name` built from its stored credentials, and the timeout to
+/// test it with
+fn connect(
+    config: &Config,
+    auth_manager: &AuthManager,
+    name: &str,
+    timeout_secs: Option<u64>,
+) -> Result<(Arc<dyn Provider>, Duration)> {
+    let remote_config = &config.remotes[name];
+    let provider_name = remote_config.provider_name(name);
+    let allow_insecure = config.security.allow_insecure_http;
+    let host = get_provider_host(
+        provider_name,
+        remote_config.api_url.as_deref(),
+        allow_insecure,
+    )?;
+
+    let token = auth_manager
+        .retrieve_remote_credential(
+            name,
+            provider_name,
+            &host,
+            &remote_config.username,
+            config.security.allow_env_tokens,
+        )
+        .map_err(|_| {
+            MultiGitError::auth(
+                name,
+                format!("no credentials stored; run 'multigit remote update {name}'"),
+            )
+        })?;
+
+    let mut options = config.client_options_for(remote_config);
+    if let Some(secs) = timeout_secs {
+        options = options.with_timeout(Duration::from_secs(secs.max(1)));
+    }
+    let timeout = options.timeout;
+    let provider = create_provider_with_options(
+        provider_name,
+        &remote_config.username,
+        &token,
+        remote_config.api_url.as_deref(),
+        allow_insecure,
+        &options,
+    )?;
+    Ok((provider, timeout))
+}
+
+/// Whether `provider` accepts its credentials within `timeout`
+pub async fn connection_check(
+    subject: String,
+    provider: &dyn Provider,
+    timeout: Duration,
+) -> Check {
+    match tokio::time::timeout(timeout, provider.test_connection()).await {
+        Ok(Ok(true)) => Check::new(subject, Outcome::Pass, "credentials accepted"),
+        Ok(Ok(false)) => Check::new(
+            subject,
+            Outcome::Fail,
+            "credentials rejected; they may be invalid or expired",
+        ),
+        Ok(Err(e)) => Check::new(subject, Outcome::Fail, format!("connection failed: {e}")),
+        Err(_) => Check::new(
+            subject,
+            Outcome::Fail,
+            format!("connection timed out after {}s", timeout.as_secs()),
+        ),
+    }
+}
+
+fn print_report(checks: &[Check]) {
+    println!("\n🔍 Validation\n");
+    let mut table = Table::new(
+        ["", "Check", "Result"]
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+    );
+    for check in checks {
+        table.add_row(vec![
+            check.outcome.status().icon().to_string(),
+            check.subject.clone(),
+            check.detail.clone(),
+        ]);
+    }
+    if colors::enabled() {
+        table.print();
+    } else {
+        table.no_colors().print();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock::MockProvider;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn test_warnings_fail_only_when_strict() {
+        let checks = vec![
+            Check::new("config", Outcome::Pass, "valid"),
+            Check::new("working tree", Outcome::Warn, "has uncommitted changes"),
+        ];
+        assert!(passed(&checks, false));
+        assert!(!passed(&checks, true));
+
+        let failed = vec![Check::new("remote github", Outcome::Fail, "rejected")];
+        assert!(!passed(&failed, false));
+    }
+
+    #[test]
+    fn test_repository_checks_outside_a_repository() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let checks = repository_checks(temp_dir.path().to_str().unwrap());
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].outcome, Outcome::Fail);
+    }
+
+    #[tokio::test]
+    async fn test_connection_check() {
+        let accepted = MockProvider::new("github");
+        let check = connection_check("remote github".to_string(), &accepted, TIMEOUT).await;
+        assert_eq!(check.outcome, Outcome::Pass);
+
+        let rejected = MockProvider::new("gitlab").with_connection(false);
+        let check = connection_check("remote gitlab".to_string(), &rejected, TIMEOUT).await;
+        assert_eq!(check.outcome, Outcome::Fail);
+        assert!(check.detail.contains("rejected"));
+
+        let slow = MockProvider::new("gitea").with_latency(Duration::from_secs(5));
+        let check =
+            connection_check("remote gitea".to_string(), &slow, Duration::from_millis(10)).await;
+        assert_eq!(check.outcome, Outcome::Fail);
+        assert!(check.detail.contains("timed out"));
+    }
+}
@@ -0,0 +1,61 @@
+//! Worktree commands
+//!
+//! List, add and remove linked worktrees of the current repository.
+
+use crate::cli::interactive;
+use crate::git::operations::GitOperations;
+use crate::git::worktree;
+use crate::utils::error::Result;
+use std::path::Path;
+
+/// List worktrees, marking the current one
+pub fn list() -> Result<()> {
+    let git_ops = GitOperations::open(".")?;
+    let worktrees = worktree::list(git_ops.inner())?;
+
+    println!("\nWorktrees:\n");
+    for worktree in &worktrees {
+        let marker = if worktree.is_current { "*" } else { " " };
+        let branch = worktree.branch.as_deref().unwrap_or("(detached HEAD)");
+        let locked = if worktree.locked { " [locked]" } else { "" };
+        println!(
+            "  {marker} {:<16} {:<24} {}{locked}",
+            worktree.label(),
+            branch,
+            worktree.path.display()
+        );
+    }
+
+    if worktrees.len() == 1 {
+        println!("\nAdd one with: multigit worktree add <path> --branch <branch>");
+    }
+
+    Ok(())
+}
+
+/// Add a linked worktree at `path`
+pub fn add(path: &str, branch: Option<&str>) -> Result<()> {
+    let git_ops = GitOperations::open(".")?;
+    let added = worktree::add(git_ops.inner(), Path::new(path), branch)?;
+
+    interactive::print_success(&format!(
+        "Added worktree '{}' at {}",
+        added.label(),
+        added.path.display()
+    ));
+    if let Some(branch) = &added.branch {
+        println!("Branch: {branch}");
+    }
+    println!("MultiGit commands run there use this repository's .multigit config.");
+
+    Ok(())
+}
+
+/// Remove the linked worktree `name`
+pub fn remove(name: &str, force: bool) -> Result<()> {
+    let git_ops = GitOperations::open(".")?;
+    worktree::remove(git_ops.inner(), name, force)?;
+    interactive::print_success(&format!("Removed worktree '{name}'"));
+
+    Ok(())
+}
@@ -3,6 +3,7 @@
 //! Provides interactive UI components for user interactions using dialoguer.
 //! Includes token input with masking, provider selection, and confirmation prompts.
 
+use crate::ui::formatter::Status;
 use crate::utils::error::{MultiGitError, Result};
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, Password, Select};
 
@@ -78,6 +79,26 @@ pub fn prompt_token(provider: &str) -> Result<String> {
     Ok(token.trim().to_string())
 }
 
+/// Prompt for a passphrase with masked input, asking twice when `confirm` is set
+pub fn prompt_passphrase(prompt: &str, confirm: bool) -> Result<String> {
+    let theme = ColorfulTheme::default();
+    let mut password = Password::with_theme(&theme).with_prompt(prompt);
+    if confirm {
+        password = password.with_confirmation("Repeat the passphrase", "Passphrases don't match");
+    }
+    let passphrase = password
+        .interact()
+        .map_err(|e| MultiGitError::other(format!("Passphrase input failed: {e}")))?;
+
+    if passphrase.is_empty() {
+        return Err(MultiGitError::other(
+            "Passphrase cannot be empty".to_string(),
+        ));
+    }
+
+    Ok(passphrase)
+}
+
 /// Prompt for custom API URL (for self-hosted instances)
 pub fn prompt_api_url(provider: &str) -> Result<Option<String>> {
     if provider != "gitea" && provider != "gitlab" {
@@ -215,6 +236,16 @@ pub fn select_resolution_strategy() -> Result<String> {
     Ok(strategy.to_string())
 }
 
+/// Select one of `items`, or `None` if the prompt is dismissed with Esc
+pub fn select_option(prompt: &str, items: &[&str], default: usize) -> Result<Option<usize>> {
+    Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .items(items)
+        .default(default)
+        .interact_opt()
+        .map_err(|e| MultiGitError::other(format!("Selection failed: {e}")))
+}
+
 /// Select a remote from a list
 pub fn select_remote(remotes: &[String]) -> Result<String> {
     if remotes.is_empty() {
@@ -263,22 +294,22 @@ fn is_valid_repo_name(name: &str) -> bool {
 
 /// Print a warning message
 pub fn print_warning(message: &str) {
-    eprintln!("⚠️  {message}");
+    eprintln!("{} {message}", Status::Warning.icon());
 }
 
 /// Print an error message
 pub fn print_error(message: &str) {
-    eprintln!("❌ {message}");
+    eprintln!("{} {message}", Status::Error.icon());
 }
 
 /// Print a success message
 pub fn print_success(message: &str) {
-    println!("✅ {message}");
+    println!("{} {message}", Status::Success.icon());
 }
 
 /// Print an info message
 pub fn print_info(message: &str) {
-    println!("ℹ️  {message}");
+    println!("{} {message}", Status::Info.icon());
 }
 
 #[cfg(test)]
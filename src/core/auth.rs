@@ -7,9 +7,12 @@ use crate::security::{
     encryption,
     keyring::KeyringManager,
 };
+use crate::utils::atomic_write::write_atomic;
 use crate::utils::error::{MultiGitError, Result};
 use std::collections::HashMap;
+use std::fmt;
 use std::path::PathBuf;
+use std::str::FromStr;
 use tracing::{debug, info};
 
 /// Authentication backend
@@ -23,6 +26,97 @@ pub enum AuthBackend {
     Environment,
 }
 
+impl fmt::Display for AuthBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Keyring => "keyring",
+            Self::EncryptedFile => "encrypted",
+            Self::Environment => "environment",
+        })
+    }
+}
+
+impl FromStr for AuthBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "keyring" => Ok(Self::Keyring),
+            "encrypted" | "encrypted-file" => Ok(Self::EncryptedFile),
+            "environment" | "env" => Ok(Self::Environment),
+            _ => Err(format!(
+                "unknown credential backend '{s}' (expected one of: keyring, encrypted, environment)"
+            )),
+        }
+    }
+}
+
+/// The provider, host and username a credential is stored under
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CredentialKey {
+    /// Provider name (github, gitlab, ...)
+    pub provider: String,
+    /// Host the credential is bound to
+    pub host: String,
+    /// Username on the provider
+    pub username: String,
+}
+
+impl CredentialKey {
+    /// Create a credential key
+    #[must_use]
+    pub fn new(provider: &str, host: &str, username: &str) -> Self {
+        Self {
+            provider: provider.to_string(),
+            host: host.to_string(),
+            username: username.to_string(),
+        }
+    }
+
+    /// Parse a `provider:host:username` store key; the host may carry a port
+    fn parse(key: &str) -> Option<Self> {
+        let (provider, rest) = key.split_once(':')?;
+        let (host, username) = rest.rsplit_once(':')?;
+        Some(Self::new(provider, host, username))
+    }
+}
+
+impl fmt::Display for CredentialKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.provider, self.host, self.username)
+    }
+}
+
+/// Environment variable holding the token for one configured remote
+///
+/// The remote name is upper-cased and anything but ASCII letters and digits
+/// becomes `_`, so remote `gitea-work` reads `MULTIGIT_GITEA_WORK_TOKEN`.
+#[must_use]
+pub fn remote_token_env_var(remote: &str) -> String {
+    let name: String = remote
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("MULTIGIT_{name}_TOKEN")
+}
+
+/// Token from the provider-level `MULTIGIT_<PROVIDER>_TOKEN`, if set
+fn provider_env_token(provider: &str, host: &str) -> Option<String> {
+    let env_var = format!("MULTIGIT_{}_TOKEN", provider.to_uppercase());
+    let token = std::env::var(&env_var).ok()?;
+    info!(
+        "Using token from environment variable: {} (provider: {}, host: {})",
+        env_var, provider, host
+    );
+    Some(token)
+}
+
 /// Authentication manager
 pub struct AuthManager {
     keyring: Option<KeyringManager>,
@@ -115,41 +209,194 @@ impl AuthManager {
 
         // Try environment variables if allowed
         if allow_env {
-            let env_var = format!("MULTIGIT_{}_TOKEN", provider.to_uppercase());
+            if let Some(token) = provider_env_token(provider, host) {
+                return Ok(token);
+            }
+        }
+
+        let result = self.lookup_account(provider, host, username);
+
+        // Log audit event
+        if let Some(ref logger) = self.audit_logger {
+            let entry = AuditEntry::new(
+                AuditEventType::CredentialRetrieve,
+                format!("{provider}:{host}:{username}"),
+                result.is_ok(),
+            );
+            logger.log(entry);
+        }
+
+        result
+    }
+
+    /// Store the token of a configured remote
+    ///
+    /// The token is keyed by the remote's config name rather than by provider,
+    /// host and username, so two remotes on the same provider and username
+    /// (say two GitHub orgs) keep their own tokens.
+    pub fn store_remote_credential(&self, remote: &str, token: &str) -> Result<()> {
+        info!("Storing credential for remote {}", remote);
+
+        let result = match self.preferred_backend {
+            AuthBackend::Keyring => {
+                if let Some(ref keyring) = self.keyring {
+                    keyring.store_remote_token(remote, token)
+                } else {
+                    Err(MultiGitError::Other("Keyring not initialized".to_string()))
+                }
+            }
+            AuthBackend::EncryptedFile => {
+                if let Some(ref store) = self.encrypted_store {
+                    store.store_remote(remote, token)
+                } else {
+                    Err(MultiGitError::Other(
+                        "Encrypted store not initialized".to_string(),
+                    ))
+                }
+            }
+            AuthBackend::Environment => Err(MultiGitError::Other(
+                "Cannot store credentials in environment variables".to_string(),
+            )),
+        };
+
+        // Log audit event
+        if let Some(ref logger) = self.audit_logger {
+            let entry = AuditEntry::new(
+                AuditEventType::CredentialStore,
+                format!("remote:{remote}"),
+                result.is_ok(),
+            );
+            logger.log(entry);
+        }
+
+        result
+    }
+
+    /// Retrieve the credential for a configured remote
+    ///
+    /// When `allow_env` is set the remote's own `MULTIGIT_<REMOTE>_TOKEN` is
+    /// checked first, then the provider-level variable. After that the token
+    /// stored under the remote's name is used; failing that, a token stored
+    /// under the old provider, host and username key is copied to the
+    /// remote's name and returned. The provider-keyed entry is kept, since
+    /// other remotes on the same account may still rely on it.
+    pub fn retrieve_remote_credential(
+        &self,
+        remote: &str,
+        provider: &str,
+        host: &str,
+        username: &str,
+        allow_env: bool,
+    ) -> Result<String> {
+        debug!("Retrieving credential for remote {}", remote);
+
+        if allow_env {
+            let env_var = remote_token_env_var(remote);
             if let Ok(token) = std::env::var(&env_var) {
                 info!(
-                    "Using token from environment variable: {} (provider: {}, host: {})",
-                    env_var, provider, host
+                    "Using token from environment variable: {} (remote: {}, host: {})",
+                    env_var, remote, host
                 );
                 return Ok(token);
             }
+            if let Some(token) = provider_env_token(provider, host) {
+                return Ok(token);
+            }
+        }
+
+        let result = self.lookup_remote(remote).or_else(|_| {
+            let token = self.lookup_account(provider, host, username)?;
+            debug!("Migrating provider-keyed credential to remote '{}'", remote);
+            if let Err(e) = self.store_remote_credential(remote, &token) {
+                debug!("Failed to migrate credential: {}", e);
+            } else {
+                info!("Migrated credential of {provider}:{host}:{username} to remote '{remote}'");
+            }
+            Ok(token)
+        });
+
+        // Log audit event
+        if let Some(ref logger) = self.audit_logger {
+            let entry = AuditEntry::new(
+                AuditEventType::CredentialRetrieve,
+                format!("remote:{remote}"),
+                result.is_ok(),
+            );
+            logger.log(entry);
         }
 
-        // Try preferred backend
+        result
+    }
+
+    /// The token stored under a remote's name
+    ///
+    /// Unlike [`Self::retrieve_remote_credential`] neither environment
+    /// variables nor provider-keyed entries are considered.
+    pub fn retrieve_remote_token(&self, remote: &str) -> Result<String> {
+        let result = self.lookup_remote(remote);
+
+        // Log audit event
+        if let Some(ref logger) = self.audit_logger {
+            let entry = AuditEntry::new(
+                AuditEventType::CredentialRetrieve,
+                format!("remote:{remote}"),
+                result.is_ok(),
+            );
+            logger.log(entry);
+        }
+
+        result
+    }
+
+    /// Whether a token is stored for this provider, host and username
+    ///
+    /// Only the storage backend is checked, not environment variables, and no
+    /// audit entry is written since the token isn't handed out.
+    #[must_use]
+    pub fn has_credential(&self, provider: &str, host: &str, username: &str) -> bool {
+        self.lookup_account(provider, host, username).is_ok()
+    }
+
+    /// Whether a token is stored under a remote's name
+    ///
+    /// Like [`Self::has_credential`], provider-keyed entries the remote would
+    /// migrate from aren't considered.
+    #[must_use]
+    pub fn has_remote_credential(&self, remote: &str) -> bool {
+        self.lookup_remote(remote).is_ok()
+    }
+
+    /// Delete the token stored under a remote's name
+    pub fn delete_remote_credential(&self, remote: &str) -> Result<()> {
+        info!("Deleting credential for remote {}", remote);
+
         let result = match self.preferred_backend {
-            AuthBackend::Keyring | AuthBackend::Environment => {
+            AuthBackend::Keyring => {
                 if let Some(ref keyring) = self.keyring {
-                    keyring.retrieve_provider_token(provider, host, username)
+                    keyring.delete_remote_token(remote)
                 } else {
                     Err(MultiGitError::Other("Keyring not initialized".to_string()))
                 }
             }
             AuthBackend::EncryptedFile => {
                 if let Some(ref store) = self.encrypted_store {
-                    store.retrieve(provider, host, username)
+                    store.delete_remote(remote)
                 } else {
                     Err(MultiGitError::Other(
                         "Encrypted store not initialized".to_string(),
                     ))
                 }
             }
+            AuthBackend::Environment => Err(MultiGitError::Other(
+                "Cannot delete environment variables".to_string(),
+            )),
         };
 
         // Log audit event
         if let Some(ref logger) = self.audit_logger {
             let entry = AuditEntry::new(
-                AuditEventType::CredentialRetrieve,
-                format!("{provider}:{host}:{username}"),
+                AuditEventType::CredentialDelete,
+                format!("remote:{remote}"),
                 result.is_ok(),
             );
             logger.log(entry);
@@ -158,6 +405,50 @@ impl AuthManager {
         result
     }
 
+    /// The provider-keyed token in the preferred backend, without auditing
+    fn lookup_account(&self, provider: &str, host: &str, username: &str) -> Result<String> {
+        match self.preferred_backend {
+            AuthBackend::Keyring | AuthBackend::Environment => {
+                if let Some(ref keyring) = self.keyring {
+                    keyring.retrieve_provider_token(provider, host, username)
+                } else {
+                    Err(MultiGitError::Other("Keyring not initialized".to_string()))
+                }
+            }
+            AuthBackend::EncryptedFile => {
+                if let Some(ref store) = self.encrypted_store {
+                    store.retrieve(provider, host, username)
+                } else {
+                    Err(MultiGitError::Other(
+                        "Encrypted store not initialized".to_string(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// The remote-keyed token in the preferred backend, without auditing
+    fn lookup_remote(&self, remote: &str) -> Result<String> {
+        match self.preferred_backend {
+            AuthBackend::Keyring | AuthBackend::Environment => {
+                if let Some(ref keyring) = self.keyring {
+                    keyring.retrieve_remote_token(remote)
+                } else {
+                    Err(MultiGitError::Other("Keyring not initialized".to_string()))
+                }
+            }
+            AuthBackend::EncryptedFile => {
+                if let Some(ref store) = self.encrypted_store {
+                    store.retrieve_remote(remote)
+                } else {
+                    Err(MultiGitError::Other(
+                        "Encrypted store not initialized".to_string(),
+                    ))
+                }
+            }
+        }
+    }
+
     /// Remove a credential (alias for `delete_credential`)
     pub fn remove_credential(&self, provider: &str, host: &str, username: &str) -> Result<()> {
         self.delete_credential(provider, host, username)
@@ -206,6 +497,57 @@ impl AuthManager {
     pub fn init_encrypted_store(&mut self, passphrase: String, path: PathBuf) {
         self.encrypted_store = Some(EncryptedCredentialStore::new(passphrase, path));
     }
+
+    /// Default location of the encrypted credential store
+    #[must_use]
+    pub fn default_encrypted_store_path() -> PathBuf {
+        let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+        path.push("multigit");
+        path.push("credentials.enc");
+        path
+    }
+
+    /// Every credential in the preferred backend
+    ///
+    /// Only the encrypted store can be listed; the OS keyring has no way to
+    /// enumerate its entries, so callers must know the keys to look up.
+    /// Legacy entries without a host and remote-keyed entries are left out.
+    pub fn list_credentials(&self) -> Result<Vec<CredentialKey>> {
+        match self.preferred_backend {
+            AuthBackend::EncryptedFile => match self.encrypted_store {
+                Some(ref store) => store.list(),
+                None => Err(MultiGitError::Other(
+                    "Encrypted store not initialized".to_string(),
+                )),
+            },
+            AuthBackend::Keyring => Err(MultiGitError::Other(
+                "The OS keyring cannot list its entries".to_string(),
+            )),
+            AuthBackend::Environment => Err(MultiGitError::Other(
+                "Cannot list environment variables".to_string(),
+            )),
+        }
+    }
+
+    /// Names of the remotes with a token of their own in the preferred backend
+    ///
+    /// Like [`Self::list_credentials`], only the encrypted store can be listed.
+    pub fn list_remote_credentials(&self) -> Result<Vec<String>> {
+        match self.preferred_backend {
+            AuthBackend::EncryptedFile => match self.encrypted_store {
+                Some(ref store) => store.list_remotes(),
+                None => Err(MultiGitError::Other(
+                    "Encrypted store not initialized".to_string(),
+                )),
+            },
+            AuthBackend::Keyring => Err(MultiGitError::Other(
+                "The OS keyring cannot list its entries".to_string(),
+            )),
+            AuthBackend::Environment => Err(MultiGitError::Other(
+                "Cannot list environment variables".to_string(),
+            )),
+        }
+    }
 }
 
 impl Default for AuthManager {
@@ -214,6 +556,9 @@ impl Default for AuthManager {
     }
 }
 
+/// Prefix of the encrypted store's remote-keyed entries
+const REMOTE_KEY_PREFIX: &str = "remote:";
+
 /// Encrypted credential store (fallback when keyring unavailable)
 struct EncryptedCredentialStore {
     passphrase: String,
@@ -241,18 +586,13 @@ impl EncryptedCredentialStore {
     }
 
     fn save_store(&self, store: &HashMap<String, String>) -> Result<()> {
-        // Ensure parent directory exists
-        if let Some(parent) = self.path.parent() {
-            std::fs::create_dir_all(parent)
-                .map_err(|e| MultiGitError::Other(format!("Failed to create dir: {e}")))?;
-        }
-
         let json = serde_json::to_string(store)
             .map_err(|e| MultiGitError::Other(format!("JSON serialize failed: {e}")))?;
 
         let encrypted = encryption::encrypt_with_passphrase(json.as_bytes(), &self.passphrase)?;
 
-        std::fs::write(&self.path, encrypted)
+        // Atomic so a crash can't leave a truncated, undecryptable store
+        write_atomic(&self.path, encrypted)
             .map_err(|e| MultiGitError::Other(format!("Failed to write store: {e}")))?;
 
         Ok(())
@@ -296,6 +636,47 @@ impl EncryptedCredentialStore {
         Err(MultiGitError::Other(format!("Credential not found: {key}")))
     }
 
+    fn list(&self) -> Result<Vec<CredentialKey>> {
+        let mut keys: Vec<CredentialKey> = self
+            .load_store()?
+            .keys()
+            .filter(|key| !key.starts_with(REMOTE_KEY_PREFIX))
+            .filter_map(|key| CredentialKey::parse(key))
+            .collect();
+        keys.sort();
+        Ok(keys)
+    }
+
+    fn store_remote(&self, remote: &str, token: &str) -> Result<()> {
+        let mut store = self.load_store()?;
+        store.insert(format!("{REMOTE_KEY_PREFIX}{remote}"), token.to_string());
+        self.save_store(&store)
+    }
+
+    fn retrieve_remote(&self, remote: &str) -> Result<String> {
+        let key = format!("{REMOTE_KEY_PREFIX}{remote}");
+        self.load_store()?
+            .remove(&key)
+            .ok_or_else(|| MultiGitError::Other(format!("Credential not found: {key}")))
+    }
+
+    fn list_remotes(&self) -> Result<Vec<String>> {
+        let mut remotes: Vec<String> = self
+            .load_store()?
+            .keys()
+            .filter_map(|key| key.strip_prefix(REMOTE_KEY_PREFIX))
+            .map(str::to_string)
+            .collect();
+        remotes.sort();
+        Ok(remotes)
+    }
+
+    fn delete_remote(&self, remote: &str) -> Result<()> {
+        let mut store = self.load_store()?;
+        store.remove(&format!("{REMOTE_KEY_PREFIX}{remote}"));
+        self.save_store(&store)
+    }
+
     fn delete(&self, provider: &str, host: &str, username: &str) -> Result<()> {
         let mut store = self.load_store()?;
         let key = format!("{provider}:{host}:{username}");
@@ -319,6 +700,88 @@ mod tests {
         assert!(manager.keyring.is_some());
     }
 
+    #[test]
+    fn test_backend_names() {
+        for backend in [
+            AuthBackend::Keyring,
+            AuthBackend::EncryptedFile,
+            AuthBackend::Environment,
+        ] {
+            assert_eq!(backend.to_string().parse::<AuthBackend>(), Ok(backend));
+        }
+        assert_eq!(
+            "encrypted-file".parse::<AuthBackend>(),
+            Ok(AuthBackend::EncryptedFile)
+        );
+        assert!("vault".parse::<AuthBackend>().is_err());
+    }
+
+    #[test]
+    fn test_list_encrypted_credentials() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut manager = AuthManager::new(AuthBackend::EncryptedFile, false);
+        manager.init_encrypted_store(
+            "correct horse".to_string(),
+            temp_dir.path().join("credentials.enc"),
+        );
+        assert!(manager.list_credentials().unwrap().is_empty());
+
+        manager
+            .store_credential("gitea", "git.example.com:3000", "alice", "t1")
+            .unwrap();
+        manager
+            .store_credential("github", "github.com", "alice", "t2")
+            .unwrap();
+
+        assert_eq!(
+            manager.list_credentials().unwrap(),
+            vec![
+                CredentialKey::new("gitea", "git.example.com:3000", "alice"),
+                CredentialKey::new("github", "github.com", "alice"),
+            ]
+        );
+        assert!(AuthManager::new(AuthBackend::Keyring, false)
+            .list_credentials()
+            .is_err());
+    }
+
+    #[test]
+    fn test_remote_credentials_are_separate_and_migrate() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut manager = AuthManager::new(AuthBackend::EncryptedFile, false);
+        manager.init_encrypted_store(
+            "correct horse".to_string(),
+            temp_dir.path().join("credentials.enc"),
+        );
+
+        // Two orgs on the same provider and username keep their own tokens
+        manager.store_remote_credential("org-a", "token-a").unwrap();
+        manager.store_remote_credential("org-b", "token-b").unwrap();
+        let retrieve = |remote: &str| {
+            manager.retrieve_remote_credential(remote, "github", "github.com", "alice", false)
+        };
+        assert_eq!(retrieve("org-a").unwrap(), "token-a");
+        assert_eq!(retrieve("org-b").unwrap(), "token-b");
+
+        // A provider-keyed token is copied to the remote's name on first use
+        manager
+            .store_credential("github", "github.com", "alice", "shared")
+            .unwrap();
+        assert!(!manager.has_remote_credential("org-c"));
+        assert_eq!(retrieve("org-c").unwrap(), "shared");
+        assert_eq!(manager.retrieve_remote_token("org-c").unwrap(), "shared");
+        assert!(manager.has_credential("github", "github.com", "alice"));
+
+        assert_eq!(
+            manager.list_remote_credentials().unwrap(),
+            vec!["org-a", "org-b", "org-c"]
+        );
+        assert_eq!(manager.list_credentials().unwrap().len(), 1);
+
+        manager.delete_remote_credential("org-a").unwrap();
+        assert!(!manager.has_remote_credential("org-a"));
+    }
+
     #[test]
     fn test_env_var_credential() {
         std::env::set_var("MULTIGIT_GITHUB_TOKEN", "test_token_123");
@@ -336,4 +799,38 @@ mod tests {
 
         std::env::remove_var("MULTIGIT_GITHUB_TOKEN");
     }
+
+    #[test]
+    fn test_remote_env_var_credential() {
+        assert_eq!(
+            remote_token_env_var("gitea-work"),
+            "MULTIGIT_GITEA_WORK_TOKEN"
+        );
+
+        std::env::set_var("MULTIGIT_GITEA_TOKEN", "provider_token");
+        std::env::set_var("MULTIGIT_GITEA_HOME_TOKEN", "home_token");
+
+        let manager = AuthManager::new(AuthBackend::Environment, false);
+        let home = manager.retrieve_remote_credential(
+            "gitea-home",
+            "gitea",
+            "git.home.example",
+            "alice",
+            true,
+        );
+        assert_eq!(home.unwrap(), "home_token");
+
+        // No remote-specific variable: falls back to the provider's
+        let work = manager.retrieve_remote_credential(
+            "gitea-work",
+            "gitea",
+            "git.work.example",
+            "alice",
+            true,
+        );
+        assert_eq!(work.unwrap(), "provider_token");
+
+        std::env::remove_var("MULTIGIT_GITEA_TOKEN");
+        std::env::remove_var("MULTIGIT_GITEA_HOME_TOKEN");
+    }
 }
@@ -0,0 +1,104 @@
+//! Local clock skew detection
+//!
+//! Rate-limit reset times and token expiry are absolute timestamps from the
+//! provider, so a badly set local clock makes them misleading. The skew is
+//! estimated by comparing the `Date` header of a lightweight HEAD request
+//! with the local time at the midpoint of the request.
+
+use crate::utils::error::{MultiGitError, Result};
+use chrono::{DateTime, Utc};
+use reqwest::{header, Client};
+use tracing::debug;
+
+/// Skew (in seconds, either direction) beyond which `doctor` warns
+pub const MAX_CLOCK_SKEW_SECONDS: i64 = 120;
+
+/// Server time minus local time, in seconds, from an HTTP `Date` header
+///
+/// Positive means the local clock is behind. Returns `None` if the header
+/// isn't a valid HTTP date.
+#[must_use]
+pub fn skew_from_date_header(date: &str, local: DateTime<Utc>) -> Option<i64> {
+    let server = DateTime::parse_from_rfc2822(date.trim()).ok()?;
+    Some((server.with_timezone(&Utc) - local).num_seconds())
+}
+
+/// Measure the skew against the server at `url`
+pub async fn measure_skew(client: &Client, url: &str) -> Result<i64> {
+    let sent = Utc::now();
+    let response = client.head(url).send().await?;
+    let received = Utc::now();
+
+    // The server stamped the response somewhere between send and receive
+    let local = sent + (received - sent) / 2;
+
+    let date = response
+        .headers()
+        .get(header::DATE)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| MultiGitError::network(format!("{url} sent no Date header")))?;
+    debug!("Date header from {}: {}", url, date);
+
+    skew_from_date_header(date, local)
+        .ok_or_else(|| MultiGitError::network(format!("{url} sent an invalid Date header")))
+}
+
+/// Human-readable description of a skew, e.g. `local clock is 3m 5s behind`
+#[must_use]
+pub fn describe_skew(skew_seconds: i64) -> String {
+    let magnitude = skew_seconds.unsigned_abs();
+    let amount = if magnitude >= 60 {
+        format!("{}m {}s", magnitude / 60, magnitude % 60)
+    } else {
+        format!("{magnitude}s")
+    };
+
+    match skew_seconds {
+        0 => "local clock is in sync".to_string(),
+        s if s > 0 => format!("local clock is {amount} behind"),
+        _ => format!("local clock is {amount} ahead"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_skew_from_date_header() {
+        let local = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+
+        assert_eq!(
+            skew_from_date_header("Fri, 01 Mar 2024 12:05:00 GMT", local),
+            Some(300)
+        );
+        assert_eq!(
+            skew_from_date_header("Fri, 01 Mar 2024 11:59:30 GMT", local),
+            Some(-30)
+        );
+        assert_eq!(skew_from_date_header("yesterday", local), None);
+    }
+
+    #[test]
+    fn test_describe_skew() {
+        assert_eq!(describe_skew(0), "local clock is in sync");
+        assert_eq!(describe_skew(185), "local clock is 3m 5s behind");
+        assert_eq!(describe_skew(-42), "local clock is 42s ahead");
+    }
+
+    #[tokio::test]
+    async fn test_measure_skew_reads_date_header() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("HEAD", "/")
+            .with_header("date", "Mon, 01 Jan 2001 00:00:00 GMT")
+            .create_async()
+            .await;
+
+        let skew = measure_skew(&Client::new(), &server.url()).await.unwrap();
+
+        mock.assert_async().await;
+        assert!(skew < -MAX_CLOCK_SKEW_SECONDS);
+    }
+}
@@ -3,19 +3,40 @@
 //! Implements hierarchical configuration loading from:
 //! 1. CLI flags (highest priority)
 //! 2. Repository config (.multigit/config.toml)
-//! 3. User config (~/.config/multigit/config.toml)
-//! 4. Default values (lowest priority)
-
-use crate::models::{DaemonConfig, SecurityConfig, Settings, SyncConfig};
+//! 3. Template (~/.config/multigit/template.toml), inherited by every repository
+//! 4. User config (~/.config/multigit/config.toml, or the active profile's)
+//! 5. Default values (lowest priority)
+
+use crate::api::client::ClientOptions;
+use crate::core::config_migration::{self, Migration, CONFIG_VERSION};
+use crate::core::profile;
+use crate::core::routing::Routing;
+use crate::git::worktree;
+use crate::models::{DaemonConfig, RouteRule, SecurityConfig, Settings, SyncConfig, TokenInfo};
+use crate::providers::factory::{
+    get_provider_host, is_supported_provider, CUSTOM_FLAVORS, CUSTOM_PROVIDER,
+};
+use crate::utils::atomic_write::write_atomic;
 use crate::utils::error::{MultiGitError, Result};
+use crate::utils::tls::TlsOptions;
+use crate::utils::validation::{validate_branch_name, validate_url};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// `security.audit_log` given on the command line, applied by [`Config::load`]
+static AUDIT_OVERRIDE: RwLock<Option<bool>> = RwLock::new(None);
 
 /// Main configuration structure
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    /// Config schema version (files without it are version 0)
+    #[serde(default)]
+    pub version: u32,
+
     /// General settings
     #[serde(default)]
     pub settings: Settings,
@@ -35,6 +56,21 @@ pub struct Config {
     /// Configured remotes
     #[serde(default)]
     pub remotes: HashMap<String, RemoteConfig>,
+
+    /// Which remotes each branch glob may be pushed to
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub routing: BTreeMap<String, RouteRule>,
+
+    /// Top-level keys and tables this version doesn't recognize (e.g. from a
+    /// newer release or a plugin), preserved on save
+    #[serde(flatten)]
+    pub extra: toml::Table,
+
+    /// What [`Config::load`] returned, before any changes; saving writes only
+    /// what differs from it, so values from the template, the repository
+    /// config and command-line flags stay out of the saved file
+    #[serde(skip)]
+    loaded: Option<toml::Table>,
 }
 
 /// Remote configuration stored in config file
@@ -60,21 +96,104 @@ pub struct RemoteConfig {
     /// Priority for conflict resolution
     #[serde(default)]
     pub priority: i32,
+
+    /// Extra PEM root certificate for this remote (overrides `settings.ca_cert_path`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_cert_path: Option<PathBuf>,
+
+    /// Accept any TLS certificate from this remote. Dangerous: anyone on
+    /// the network path can impersonate the server. Lab environments only.
+    #[serde(default)]
+    pub insecure_skip_tls_verify: bool,
+
+    /// Token expiry last reported by the provider, recorded by `remote test`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_expires_at: Option<DateTime<Utc>>,
+
+    /// Group labels (e.g. "mirrors") for selecting remotes with `--group`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<String>,
+
+    /// Which provider's API a `custom` provider speaks (`gitea` or `gitlab`);
+    /// `api_url` is then required
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_flavor: Option<String>,
+}
+
+/// An enabled remote with no username, provider or other settings; fill in
+/// the fields that matter and take the rest with `..Default::default()`
+impl Default for RemoteConfig {
+    fn default() -> Self {
+        Self {
+            username: String::new(),
+            api_url: None,
+            enabled: true,
+            provider: None,
+            use_ssh: false,
+            priority: 0,
+            ca_cert_path: None,
+            insecure_skip_tls_verify: false,
+            token_expires_at: None,
+            groups: Vec::new(),
+            api_flavor: None,
+        }
+    }
+}
+
+impl RemoteConfig {
+    /// The provider implementation serving the remote called `name`: its
+    /// configured provider or, when none is set, its name. A `custom`
+    /// provider resolves to its `api_flavor`.
+    #[must_use]
+    pub fn provider_name<'a>(&'a self, name: &'a str) -> &'a str {
+        match self.provider.as_deref() {
+            Some(CUSTOM_PROVIDER) => self.api_flavor.as_deref().unwrap_or(CUSTOM_PROVIDER),
+            Some(provider) => provider,
+            None => name,
+        }
+    }
+
+    /// Whether the remote is labelled with `group`
+    #[must_use]
+    pub fn in_group(&self, group: &str) -> bool {
+        self.groups.iter().any(|g| g == group)
+    }
 }
 
 fn default_true() -> bool {
     true
 }
 
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            settings: Settings::default(),
+            sync: SyncConfig::default(),
+            security: SecurityConfig::default(),
+            daemon: DaemonConfig::default(),
+            remotes: HashMap::new(),
+            routing: BTreeMap::new(),
+            extra: toml::Table::new(),
+            loaded: None,
+        }
+    }
+}
+
 impl Config {
     /// Load configuration from all sources with hierarchical priority
     ///
     /// Configuration is loaded and merged in the following order (later overrides earlier):
     /// 1. Default values (hardcoded)
     /// 2. User config (`~/.config/multigit/config.toml`)
-    /// 3. Repository config (`.multigit/config.toml`) - highest priority
+    /// 3. Template (`~/.config/multigit/template.toml`)
+    /// 4. Repository config (`.multigit/config.toml`) - highest priority
     ///
-    /// This allows global defaults with per-repository overrides.
+    /// This allows global defaults with per-repository overrides; a repository
+    /// without a config of its own still sees the template's remotes. Only the
+    /// keys the template file declares override the user config. Saving
+    /// the result writes back only what was changed after loading, so the
+    /// template is never copied into the user config.
     pub fn load() -> Result<Self> {
         // Start with defaults
         let mut config = Self::default();
@@ -84,14 +203,41 @@ impl Config {
             config = config.merge(user_config);
         }
 
+        // Template shared by every repository; only the keys it declares apply
+        if let Some(template) = Self::load_template_table()? {
+            config = config.with_template(template, &Self::template_path()?)?;
+        }
+
         // Load repo config if it exists (highest priority)
         if let Some(repo_config) = Self::load_repo_config()? {
             config = config.merge(repo_config);
         }
 
+        // CLI flags override every file
+        if let Some(audit) = Self::audit_override() {
+            config.security.audit_log = audit;
+        }
+
+        config.loaded = Some(config.to_table()?);
         Ok(config)
     }
 
+    /// Override `security.audit_log` for every later [`Config::load`] in this
+    /// process, from `--audit` or `--no-audit`
+    ///
+    /// The override is never saved back to a config file.
+    pub fn set_audit_override(audit: Option<bool>) {
+        if let Ok(mut current) = AUDIT_OVERRIDE.write() {
+            *current = audit;
+        }
+    }
+
+    /// The `--audit`/`--no-audit` override, if one is set
+    #[must_use]
+    pub fn audit_override() -> Option<bool> {
+        AUDIT_OVERRIDE.read().ok().and_then(|current| *current)
+    }
+
     /// Load user-level configuration from ~/.config/multigit/config.toml
     fn load_user_config() -> Result<Option<Self>> {
         let config_path = Self::user_config_path()?;
@@ -102,16 +248,45 @@ impl Config {
         }
 
         tracing::debug!("Loading user config from: {}", config_path.display());
-        let content = fs::read_to_string(&config_path)?;
-        let config: Config = toml::from_str(&content)
-            .map_err(|e| MultiGitError::config(format!("Failed to parse user config: {e}")))?;
+        Self::load_from_file(&config_path).map(Some)
+    }
+
+    /// Load the repository template from ~/.config/multigit/template.toml
+    pub fn load_template() -> Result<Option<Self>> {
+        let path = Self::template_path()?;
+        Self::load_template_table()?
+            .map(|table| Self::from_table(table, &path))
+            .transpose()
+    }
+
+    /// The keys the template file declares, without defaults filled in
+    fn load_template_table() -> Result<Option<toml::Table>> {
+        let template_path = Self::template_path()?;
 
-        Ok(Some(config))
+        if !template_path.exists() {
+            tracing::debug!("Template not found at: {}", template_path.display());
+            return Ok(None);
+        }
+
+        tracing::debug!("Loading template from: {}", template_path.display());
+        Self::read_table(&template_path).map(Some)
+    }
+
+    /// Layer the keys of `template`, read from `path`, over this config
+    ///
+    /// Unlike [`Config::merge`], settings the template doesn't mention keep
+    /// their current value instead of falling back to the defaults.
+    fn with_template(self, template: toml::Table, path: &Path) -> Result<Self> {
+        let mut table = self.to_table()?;
+        overlay(&mut table, template);
+        let mut config = Self::from_table(table, path)?;
+        config.version = CONFIG_VERSION;
+        Ok(config)
     }
 
     /// Load repository-level configuration from .multigit/config.toml
     fn load_repo_config() -> Result<Option<Self>> {
-        let config_path = PathBuf::from(".multigit/config.toml");
+        let config_path = Self::repo_config_path();
 
         if !config_path.exists() {
             tracing::debug!("Repo config not found at: {}", config_path.display());
@@ -119,15 +294,65 @@ impl Config {
         }
 
         tracing::debug!("Loading repo config from: {}", config_path.display());
-        let content = fs::read_to_string(&config_path)?;
-        let config: Config = toml::from_str(&content)
-            .map_err(|e| MultiGitError::config(format!("Failed to parse repo config: {e}")))?;
+        Self::load_from_file(&config_path).map(Some)
+    }
+
+    /// Load a single config file, migrating it to the current schema version
+    ///
+    /// Files from an older version are upgraded and rewritten in place.
+    pub fn load_from_file(path: &Path) -> Result<Self> {
+        let table = Self::read_table(path)?;
+        Self::from_table(table, path)
+    }
+
+    /// Parse a config table read from `path`
+    fn from_table(table: toml::Table, path: &Path) -> Result<Self> {
+        toml::Value::Table(table)
+            .try_into()
+            .map_err(|e| MultiGitError::config(format!("Failed to parse {}: {e}", path.display())))
+    }
 
-        Ok(Some(config))
+    /// Read the table in a config file, migrating it to the current schema
+    /// version and rewriting the file if it was older
+    fn read_table(path: &Path) -> Result<toml::Table> {
+        let content = fs::read_to_string(path)?;
+        let mut table: toml::Table = toml::from_str(&content).map_err(|e| {
+            MultiGitError::config(format!("Failed to parse {}: {e}", path.display()))
+        })?;
+
+        let migration = config_migration::migrate(&mut table);
+
+        if let Migration::Upgraded { from } = migration {
+            // Rewrite the migrated table rather than `config` so keys this
+            // release doesn't model survive the upgrade
+            let migrated = toml::to_string_pretty(&table)
+                .map_err(|e| MultiGitError::config(format!("Failed to serialize config: {e}")))?;
+            write_atomic(path, migrated)?;
+            tracing::info!(
+                "Upgraded {} from config version {} to {}",
+                path.display(),
+                from,
+                CONFIG_VERSION
+            );
+        }
+
+        Ok(table)
     }
 
     /// Get the user config directory path
+    ///
+    /// With a profile active this is the profile's directory under
+    /// `profiles/`; see [`crate::core::profile`].
     pub fn user_config_dir() -> Result<PathBuf> {
+        let base = Self::base_config_dir()?;
+        Ok(match profile::active() {
+            Some(name) => base.join(profile::PROFILES_DIR).join(name),
+            None => base,
+        })
+    }
+
+    /// Get the user config directory path, ignoring profiles
+    pub fn base_config_dir() -> Result<PathBuf> {
         let config_dir = dirs::config_dir()
             .ok_or_else(|| MultiGitError::config("Cannot determine config directory"))?;
         Ok(config_dir.join("multigit"))
@@ -138,10 +363,27 @@ impl Config {
         Ok(Self::user_config_dir()?.join("config.toml"))
     }
 
+    /// Get the template path, whose remotes and settings every repository
+    /// inherits and `init` copies into new repository configs
+    pub fn template_path() -> Result<PathBuf> {
+        Ok(Self::user_config_dir()?.join("template.toml"))
+    }
+
     /// Get the repo config directory path
+    ///
+    /// In a linked worktree without a config of its own, this is the one of
+    /// the main worktree. A `.multigit` holding only the repository lock
+    /// doesn't count.
     #[must_use]
     pub fn repo_config_dir() -> PathBuf {
-        PathBuf::from(".multigit")
+        let local = PathBuf::from(".multigit");
+        if local.join("config.toml").exists() {
+            return local;
+        }
+        worktree::main_workdir_of_linked(Path::new("."))
+            .map(|main| main.join(".multigit"))
+            .filter(|dir| dir.join("config.toml").exists())
+            .unwrap_or(local)
     }
 
     /// Get the repo config file path
@@ -151,21 +393,41 @@ impl Config {
     }
 
     /// Save configuration to a file
+    ///
+    /// A config from [`Config::load`] only writes what the caller changed
+    /// into the file, keeping the rest of the file as it is; any other config
+    /// replaces the file. The write is atomic: a crash mid-save leaves the
+    /// previous file intact.
     pub fn save_to_file(&self, path: &Path) -> Result<()> {
-        // Ensure parent directory exists
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-
-        let content = toml::to_string_pretty(self)
+        let table = match &self.loaded {
+            Some(loaded) => {
+                let mut file = if path.exists() {
+                    toml::from_str(&fs::read_to_string(path)?).map_err(|e| {
+                        MultiGitError::config(format!("Failed to parse {}: {e}", path.display()))
+                    })?
+                } else {
+                    toml::Table::new()
+                };
+                apply_changes(&mut file, loaded, &self.to_table()?, true);
+                file.insert("version".to_string(), toml::Value::from(CONFIG_VERSION));
+                file
+            }
+            None => self.to_table()?,
+        };
+        let content = toml::to_string_pretty(&table)
             .map_err(|e| MultiGitError::config(format!("Failed to serialize config: {e}")))?;
 
-        fs::write(path, content)?;
+        write_atomic(path, content)?;
         tracing::info!("Saved configuration to: {}", path.display());
 
         Ok(())
     }
 
+    fn to_table(&self) -> Result<toml::Table> {
+        toml::Table::try_from(self)
+            .map_err(|e| MultiGitError::config(format!("Failed to serialize config: {e}")))
+    }
+
     /// Save to user config file
     pub fn save_user_config(&self) -> Result<()> {
         let path = Self::user_config_path()?;
@@ -203,10 +465,27 @@ impl Config {
         self.save_user_config()
     }
 
+    /// The config files that declare remote `name`: the user config, the
+    /// repository config, or both
+    ///
+    /// Changes to a remote belong in the file it comes from; a remote from
+    /// neither (e.g. the template's) yields an empty list.
+    pub fn files_defining_remote(name: &str) -> Result<Vec<PathBuf>> {
+        let paths = [Self::user_config_path()?, Self::repo_config_path()];
+        Ok(paths
+            .into_iter()
+            .filter(|path| defines_remote(path, name))
+            .collect())
+    }
+
     /// Merge another config into this one (other has priority)
     fn merge(mut self, other: Config) -> Self {
-        // Merge remotes (other overwrites)
+        // A merged config is always in the current schema
+        self.version = CONFIG_VERSION;
+
+        // Merge remotes and routing rules (other overwrites)
         self.remotes.extend(other.remotes);
+        self.routing.extend(other.routing);
 
         // For other fields, we take from 'other' if they're not default
         // This is a simplified merge - in production you might want more sophisticated merging
@@ -217,12 +496,41 @@ impl Config {
         self.settings.parallel_push = other.settings.parallel_push;
         self.settings.max_parallel = other.settings.max_parallel;
         self.settings.colored_output = other.settings.colored_output;
+        if other.settings.icons.is_some() {
+            self.settings.icons = other.settings.icons;
+        }
         self.settings.verbosity = other.settings.verbosity;
+        self.settings.api_timeout_seconds = other.settings.api_timeout_seconds;
+        self.settings.token_expiry_warning_days = other.settings.token_expiry_warning_days;
+        if other.settings.proxy_url.is_some() {
+            self.settings.proxy_url = other.settings.proxy_url;
+        }
+        if other.settings.ca_cert_path.is_some() {
+            self.settings.ca_cert_path = other.settings.ca_cert_path;
+        }
+        if other.settings.log_file.is_some() {
+            self.settings.log_file = other.settings.log_file;
+        }
+        if other.settings.log_filter.is_some() {
+            self.settings.log_filter = other.settings.log_filter;
+        }
+        if other.settings.fetch_depth.is_some() {
+            self.settings.fetch_depth = other.settings.fetch_depth;
+        }
+        self.settings.network_attempts = other.settings.network_attempts;
+        self.settings.default_private = other.settings.default_private;
+        if other.settings.expect_private {
+            self.settings.expect_private = true;
+        }
+        self.settings.extra.extend(other.settings.extra);
 
         // Merge sync config
         if other.sync.auto_sync {
             self.sync.auto_sync = true;
         }
+        if other.sync.autostash {
+            self.sync.autostash = true;
+        }
         if other.sync.primary_source.is_some() {
             self.sync.primary_source = other.sync.primary_source;
         }
@@ -232,6 +540,7 @@ impl Config {
         if !other.sync.exclude_branches.is_empty() {
             self.sync.exclude_branches = other.sync.exclude_branches;
         }
+        self.sync.extra.extend(other.sync.extra);
 
         // Merge security config
         self.security = other.security;
@@ -241,6 +550,9 @@ impl Config {
             self.daemon = other.daemon;
         }
 
+        // Unknown keys (other overwrites)
+        self.extra.extend(other.extra);
+
         self
     }
 
@@ -270,17 +582,194 @@ impl Config {
             .collect()
     }
 
+    /// Get all remotes labelled with `group`, enabled or not
+    #[must_use]
+    pub fn remotes_in_group(&self, group: &str) -> HashMap<String, &RemoteConfig> {
+        self.remotes
+            .iter()
+            .filter(|(_, config)| config.in_group(group))
+            .map(|(name, config)| (name.clone(), config))
+            .collect()
+    }
+
+    /// Names of the enabled remotes in `group`, sorted
+    ///
+    /// Fails if no remote carries the label at all, so a mistyped group isn't
+    /// silently treated as empty.
+    pub fn enabled_remotes_in_group(&self, group: &str) -> Result<Vec<String>> {
+        let members = self.remotes_in_group(group);
+        if members.is_empty() {
+            return Err(MultiGitError::InvalidInput(format!(
+                "No remotes in group '{group}'. Add one with 'multigit remote group <remote> {group}'"
+            )));
+        }
+
+        let mut names: Vec<String> = members
+            .into_iter()
+            .filter(|(_, config)| config.enabled)
+            .map(|(name, _)| name)
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// TLS settings for `remote`; its own CA overrides `settings.ca_cert_path`
+    #[must_use]
+    pub fn tls_for(&self, remote: &RemoteConfig) -> TlsOptions {
+        TlsOptions {
+            ca_cert_path: remote
+                .ca_cert_path
+                .clone()
+                .or_else(|| self.settings.ca_cert_path.clone()),
+            insecure_skip_verify: remote.insecure_skip_tls_verify,
+        }
+    }
+
+    /// TLS settings of every enabled remote, keyed by remote name
+    #[must_use]
+    pub fn remote_tls(&self) -> HashMap<String, TlsOptions> {
+        self.enabled_remotes()
+            .into_iter()
+            .map(|(name, remote)| {
+                let tls = self.tls_for(remote);
+                (name, tls)
+            })
+            .collect()
+    }
+
+    /// API client options for requests to `remote`
+    #[must_use]
+    pub fn client_options_for(&self, remote: &RemoteConfig) -> ClientOptions {
+        ClientOptions::from_settings(&self.settings).with_tls(self.tls_for(remote))
+    }
+
+    /// Enabled remotes whose last-known token expiry falls within
+    /// `settings.token_expiry_warning_days` of `now`, sorted by name
+    #[must_use]
+    pub fn expiring_tokens(&self, now: DateTime<Utc>) -> Vec<(String, TokenInfo)> {
+        let mut expiring: Vec<(String, TokenInfo)> = self
+            .enabled_remotes()
+            .into_iter()
+            .map(|(name, remote)| {
+                let token = TokenInfo {
+                    name: None,
+                    expires_at: remote.token_expires_at,
+                };
+                (name, token)
+            })
+            .filter(|(_, token)| token.expires_within(self.settings.token_expiry_warning_days, now))
+            .collect();
+        expiring.sort_by(|a, b| a.0.cmp(&b.0));
+        expiring
+    }
+
+    /// Problems that would make operations fail, one message per problem
+    ///
+    /// Only the configuration itself is checked: nothing is contacted and no
+    /// credentials are looked up. An empty list means the config is usable.
+    #[must_use]
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        if let Err(e) = validate_branch_name(&self.settings.default_branch) {
+            problems.push(format!("settings.default_branch: {e}"));
+        }
+        if self.settings.max_parallel == 0 {
+            problems.push("settings.max_parallel must be at least 1".to_string());
+        }
+        if self.settings.api_timeout_seconds == 0 {
+            problems.push("settings.api_timeout_seconds must be at least 1".to_string());
+        }
+        if let Some(proxy) = &self.settings.proxy_url {
+            if let Err(e) = validate_url(proxy) {
+                problems.push(format!("settings.proxy_url: {e}"));
+            }
+        }
+        if let Some(path) = &self.settings.ca_cert_path {
+            if !path.is_file() {
+                problems.push(format!(
+                    "settings.ca_cert_path: {} does not exist",
+                    path.display()
+                ));
+            }
+        }
+        if let Some(source) = &self.sync.primary_source {
+            if !self.remotes.contains_key(source) {
+                problems.push(format!(
+                    "sync.primary_source: remote '{source}' is not configured"
+                ));
+            }
+        }
+
+        let mut names: Vec<&String> = self.remotes.keys().collect();
+        names.sort();
+        for name in names {
+            let remote = &self.remotes[name];
+            let provider = remote.provider_name(name);
+            if remote.provider.as_deref() == Some(CUSTOM_PROVIDER) {
+                if !CUSTOM_FLAVORS.contains(&provider) {
+                    problems.push(format!(
+                        "remotes.{name}: a custom provider needs api_flavor set to one of: {}",
+                        CUSTOM_FLAVORS.join(", ")
+                    ));
+                    continue;
+                }
+                if remote.api_url.is_none() {
+                    problems.push(format!("remotes.{name}: a custom provider needs api_url"));
+                    continue;
+                }
+            }
+            if !is_supported_provider(provider) {
+                problems.push(format!("remotes.{name}: unsupported provider '{provider}'"));
+                continue;
+            }
+            if remote.username.trim().is_empty() {
+                problems.push(format!("remotes.{name}: username is empty"));
+            }
+            if let Err(e) = get_provider_host(
+                provider,
+                remote.api_url.as_deref(),
+                self.security.allow_insecure_http,
+            ) {
+                problems.push(format!("remotes.{name}: {e}"));
+            }
+            if let Some(path) = &remote.ca_cert_path {
+                if !path.is_file() {
+                    problems.push(format!(
+                        "remotes.{name}.ca_cert_path: {} does not exist",
+                        path.display()
+                    ));
+                }
+            }
+        }
+
+        if let Err(e) = Routing::from_config(self) {
+            problems.push(format!("routing: {e}"));
+        }
+        for (pattern, rule) in &self.routing {
+            for remote in rule.allow.iter().chain(&rule.deny) {
+                if !self.remotes.contains_key(remote) {
+                    problems.push(format!(
+                        "routing.\"{pattern}\": remote '{remote}' is not configured"
+                    ));
+                }
+            }
+        }
+
+        problems
+    }
+
     /// Check if `MultiGit` is initialized in the current directory
     #[must_use]
     pub fn is_initialized() -> bool {
-        Self::repo_config_dir().exists()
+        Self::repo_config_path().exists()
     }
 
     /// Initialize `MultiGit` in the current directory
     pub fn initialize() -> Result<()> {
         let config_dir = Self::repo_config_dir();
 
-        if config_dir.exists() {
+        if config_dir.join("config.toml").exists() {
             return Err(MultiGitError::AlreadyInitialized);
         }
 
@@ -296,6 +785,70 @@ impl Config {
     }
 }
 
+/// Write into `file` every key that differs between `before` and `after`,
+/// and drop the ones `after` no longer has
+///
+/// At the top level a section missing from `file` is started empty, so only
+/// the changed settings land in it; deeper down (a remote, a routing rule) a
+/// changed entry the file doesn't have is copied whole.
+fn apply_changes(file: &mut toml::Table, before: &toml::Table, after: &toml::Table, top: bool) {
+    for key in before.keys() {
+        if !after.contains_key(key) {
+            file.remove(key);
+        }
+    }
+    for (key, value) in after {
+        let old = before.get(key);
+        if old == Some(value) {
+            continue;
+        }
+        match (old, value, file.get_mut(key)) {
+            (
+                Some(toml::Value::Table(old)),
+                toml::Value::Table(new),
+                Some(toml::Value::Table(section)),
+            ) => {
+                apply_changes(section, old, new, false);
+            }
+            (Some(toml::Value::Table(old)), toml::Value::Table(new), None) if top => {
+                let mut section = toml::Table::new();
+                apply_changes(&mut section, old, new, false);
+                file.insert(key.clone(), toml::Value::Table(section));
+            }
+            _ => {
+                file.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Whether the config file at `path` has a `[remotes.<name>]` table
+fn defines_remote(path: &Path, name: &str) -> bool {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| content.parse::<toml::Table>().ok())
+        .is_some_and(|table| {
+            table
+                .get("remotes")
+                .and_then(toml::Value::as_table)
+                .is_some_and(|remotes| remotes.contains_key(name))
+        })
+}
+
+/// Copy every key of `over` into `base`, descending into tables both have
+fn overlay(base: &mut toml::Table, over: toml::Table) {
+    for (key, value) in over {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(section)), toml::Value::Table(value)) => {
+                overlay(section, value);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -323,17 +876,182 @@ mod tests {
         assert_eq!(merged.settings.max_parallel, 8);
     }
 
+    #[test]
+    fn test_merge_keeps_expect_private_marker() {
+        let mut repo = Config::default();
+        repo.settings.expect_private = true;
+        repo.settings.default_private = false;
+
+        let merged = Config::default().merge(repo);
+        assert!(merged.settings.expect_private);
+        assert!(!merged.settings.default_private);
+
+        // A later config without the marker doesn't clear it
+        let merged = merged.merge(Config::default());
+        assert!(merged.settings.expect_private);
+    }
+
+    #[test]
+    fn test_defines_remote() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        fs::write(&path, "[remotes.github]\nusername = \"me\"\n").unwrap();
+
+        assert!(defines_remote(&path, "github"));
+        assert!(!defines_remote(&path, "gitlab"));
+        assert!(!defines_remote(
+            &temp_dir.path().join("missing.toml"),
+            "github"
+        ));
+    }
+
+    #[test]
+    fn test_template_keeps_settings_it_does_not_declare() {
+        let user: Config = toml::from_str(
+            r#"
+            [settings]
+            max_parallel = 8
+            api_timeout_seconds = 60
+
+            [security]
+            auth_backend = "encrypted-file"
+            audit_log = true
+            allow_insecure_http = true
+
+            [remotes.github]
+            username = "alice"
+            "#,
+        )
+        .unwrap();
+
+        let template: toml::Table = toml::from_str(
+            r#"
+            [remotes.gitlab]
+            username = "alice"
+            provider = "gitlab"
+            "#,
+        )
+        .unwrap();
+        let config = user
+            .with_template(template, Path::new("template.toml"))
+            .unwrap();
+
+        assert_eq!(config.settings.max_parallel, 8);
+        assert_eq!(config.settings.api_timeout_seconds, 60);
+        assert!(config.security.audit_log);
+        assert!(config.security.allow_insecure_http);
+        assert_eq!(
+            config.security.auth_backend,
+            crate::models::AuthBackend::EncryptedFile
+        );
+        assert!(config.remotes.contains_key("github"));
+        assert_eq!(config.remotes["gitlab"].username, "alice");
+    }
+
+    #[test]
+    fn test_save_writes_only_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "version = 1\n[settings]\nmax_parallel = 8\n\n[remotes.github]\nusername = \"me\"\n",
+        )
+        .unwrap();
+
+        // As loaded: a template remote and setting, and `--no-audit`, on top
+        // of the file
+        let mut config = Config::load_from_file(&path).unwrap();
+        let mut template_remote = config.remotes["github"].clone();
+        template_remote.provider = Some("gitlab".to_string());
+        config.remotes.insert("gitlab".to_string(), template_remote);
+        config.settings.max_parallel = 2;
+        config.security.audit_log = !config.security.audit_log;
+        config.loaded = Some(config.to_table().unwrap());
+
+        config.settings.fetch_depth = Some(5);
+        config.remotes.get_mut("github").unwrap().priority = 3;
+        config.save_to_file(&path).unwrap();
+
+        let saved = Config::load_from_file(&path).unwrap();
+        assert_eq!(saved.settings.max_parallel, 8);
+        assert_eq!(saved.settings.fetch_depth, Some(5));
+        assert_eq!(
+            saved.security.audit_log,
+            SecurityConfig::default().audit_log
+        );
+        assert_eq!(saved.remotes.len(), 1);
+        assert_eq!(saved.remotes["github"].priority, 3);
+
+        let mut config = saved;
+        config.loaded = Some(config.to_table().unwrap());
+        config.remotes.remove("github");
+        config.save_to_file(&path).unwrap();
+        assert!(Config::load_from_file(&path).unwrap().remotes.is_empty());
+    }
+
+    #[test]
+    fn test_validate_reports_each_problem() {
+        let mut config = Config::default();
+        assert!(config.validate().is_empty());
+
+        config.settings.max_parallel = 0;
+        config.sync.primary_source = Some("origin".to_string());
+        config.remotes.insert(
+            "gitea".to_string(),
+            RemoteConfig {
+                username: "alice".to_string(),
+                provider: Some("gitea".to_string()),
+                ..Default::default()
+            },
+        );
+        config.routing.insert(
+            "internal/**".to_string(),
+            RouteRule {
+                allow: Vec::new(),
+                deny: vec!["public".to_string()],
+            },
+        );
+
+        let problems = config.validate();
+        assert_eq!(problems.len(), 4, "{problems:?}");
+        assert!(problems[0].contains("max_parallel"));
+        assert!(problems[1].contains("'origin' is not configured"));
+        assert!(problems[2].starts_with("remotes.gitea:"));
+        assert!(problems[2].contains("Gitea requires an API URL"));
+        assert!(problems[3].contains("remote 'public' is not configured"));
+    }
+
+    #[test]
+    fn test_custom_provider() {
+        let toml = r#"
+            [remotes.forge]
+            username = "alice"
+            provider = "custom"
+            api_flavor = "gitea"
+            api_url = "https://forge.example.com"
+        "#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.remotes["forge"].provider_name("forge"), "gitea");
+        assert!(config.validate().is_empty(), "{:?}", config.validate());
+
+        let forge = config.remotes.get_mut("forge").unwrap();
+        forge.api_flavor = Some("bitbucket".to_string());
+        assert!(config.validate()[0].contains("needs api_flavor"));
+
+        let forge = config.remotes.get_mut("forge").unwrap();
+        forge.api_flavor = Some("gitlab".to_string());
+        forge.api_url = None;
+        assert!(config.validate()[0].contains("needs api_url"));
+    }
+
     #[test]
     fn test_add_remove_remote() {
         let mut config = Config::default();
 
         let remote_config = RemoteConfig {
             username: "testuser".to_string(),
-            api_url: None,
-            enabled: true,
             provider: Some("github".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         };
 
         config.add_remote("github".to_string(), remote_config);
@@ -349,20 +1067,15 @@ mod tests {
 
         let enabled = RemoteConfig {
             username: "user1".to_string(),
-            api_url: None,
-            enabled: true,
             provider: Some("github".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         };
 
         let disabled = RemoteConfig {
             username: "user2".to_string(),
-            api_url: None,
             enabled: false,
             provider: Some("gitlab".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         };
 
         config.add_remote("github".to_string(), enabled);
@@ -373,6 +1086,65 @@ mod tests {
         assert!(enabled_remotes.contains_key("github"));
     }
 
+    #[test]
+    fn test_remotes_in_group() {
+        let mut config = Config::default();
+        let remote = |enabled: bool, groups: &[&str]| RemoteConfig {
+            username: "user".to_string(),
+            enabled,
+            groups: groups.iter().map(ToString::to_string).collect(),
+            ..Default::default()
+        };
+
+        config.add_remote("github".to_string(), remote(true, &[]));
+        config.add_remote("gitlab".to_string(), remote(true, &["mirrors"]));
+        config.add_remote("codeberg".to_string(), remote(true, &["mirrors", "backup"]));
+        config.add_remote("gitea".to_string(), remote(false, &["mirrors"]));
+
+        assert_eq!(config.remotes_in_group("mirrors").len(), 3);
+        assert_eq!(
+            config.enabled_remotes_in_group("mirrors").unwrap(),
+            vec!["codeberg", "gitlab"]
+        );
+        assert_eq!(
+            config.enabled_remotes_in_group("backup").unwrap(),
+            vec!["codeberg"]
+        );
+        assert!(config.enabled_remotes_in_group("mirorrs").is_err());
+    }
+
+    #[test]
+    fn test_audit_override() {
+        Config::set_audit_override(Some(false));
+        assert_eq!(Config::audit_override(), Some(false));
+
+        Config::set_audit_override(None);
+        assert_eq!(Config::audit_override(), None);
+    }
+
+    #[test]
+    fn test_expiring_tokens() {
+        let now = chrono::Utc::now();
+        let mut config = Config::default();
+        config.settings.token_expiry_warning_days = 7;
+
+        for (name, days) in [("github", Some(3)), ("gitlab", Some(30)), ("gitea", None)] {
+            config.add_remote(
+                name.to_string(),
+                RemoteConfig {
+                    username: "user".to_string(),
+                    provider: Some(name.to_string()),
+                    token_expires_at: days.map(|d| now + chrono::Duration::days(d)),
+                    ..Default::default()
+                },
+            );
+        }
+
+        let expiring = config.expiring_tokens(now);
+        assert_eq!(expiring.len(), 1);
+        assert_eq!(expiring[0].0, "github");
+    }
+
     #[test]
     fn test_save_and_load_config() -> Result<()> {
         let temp_dir = TempDir::new().unwrap();
@@ -386,6 +1158,122 @@ mod tests {
 
         let content = fs::read_to_string(&config_path)?;
         assert!(content.contains("develop"));
+        assert!(content.contains("version = 1"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_migrate_v0_config() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.toml");
+
+        fs::write(
+            &config_path,
+            r#"[settings]
+default_branch = "develop"
+verbose = true
+
+[daemon]
+sync_interval = "1h"
+
+[remotes.github]
+username = "octocat"
+enabled = true
+"#,
+        )?;
+
+        let config = Config::load_from_file(&config_path)?;
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.settings.default_branch, "develop");
+        assert_eq!(config.settings.verbosity, 2);
+        assert_eq!(config.daemon.interval_seconds, 3600);
+        assert_eq!(config.remotes["github"].username, "octocat");
+        // Fields added since v0 get their defaults
+        assert_eq!(config.daemon.max_log_files, 5);
+
+        // The file is rewritten in the current schema
+        let content = fs::read_to_string(&config_path)?;
+        assert!(content.contains("version = 1"));
+        assert!(!content.contains("sync_interval"));
+        assert_eq!(
+            Config::load_from_file(&config_path)?.version,
+            CONFIG_VERSION
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_keys_survive_round_trip() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.toml");
+
+        fs::write(
+            &config_path,
+            r#"version = 1
+
+[settings]
+default_branch = "main"
+future_setting = "kept"
+
+[experimental]
+turbo = true
+level = 3
+
+[remotes.github]
+username = "octocat"
+"#,
+        )?;
+
+        let config = Config::load_from_file(&config_path)?;
+        assert_eq!(config.extra["experimental"]["turbo"].as_bool(), Some(true));
+        config.save_to_file(&config_path)?;
+
+        let reloaded = Config::load_from_file(&config_path)?;
+        assert_eq!(
+            reloaded.extra["experimental"]["level"].as_integer(),
+            Some(3)
+        );
+        assert_eq!(
+            reloaded.settings.extra["future_setting"].as_str(),
+            Some("kept")
+        );
+        assert_eq!(reloaded.remotes["github"].username, "octocat");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remote_tls_overrides_global_ca() -> Result<()> {
+        let config: Config = toml::from_str(
+            r#"
+[settings]
+ca_cert_path = "/etc/ssl/corp-ca.pem"
+
+[remotes.gitea]
+username = "me"
+ca_cert_path = "/etc/ssl/lab-ca.pem"
+insecure_skip_tls_verify = true
+
+[remotes.github]
+username = "octocat"
+"#,
+        )?;
+
+        let gitea = config.tls_for(&config.remotes["gitea"]);
+        assert_eq!(
+            gitea.ca_cert_path.as_deref(),
+            Some(Path::new("/etc/ssl/lab-ca.pem"))
+        );
+        assert!(gitea.insecure_skip_verify);
+
+        let github = config.tls_for(&config.remotes["github"]);
+        assert_eq!(
+            github.ca_cert_path.as_deref(),
+            Some(Path::new("/etc/ssl/corp-ca.pem"))
+        );
+        assert!(!github.insecure_skip_verify);
 
         Ok(())
     }
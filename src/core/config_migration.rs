@@ -0,0 +1,146 @@
+//! Config schema versioning and migration
+//!
+//! Every config file carries a `version` field. Files written by older
+//! releases (no `version` at all counts as version 0) are upgraded step by
+//! step on load: moved or renamed keys are translated and the file is
+//! rewritten. Files from a newer release are loaded as-is with a warning.
+
+use crate::daemon::Schedule;
+use toml::{Table, Value};
+use tracing::{info, warn};
+
+/// Config schema version written by this release
+pub const CONFIG_VERSION: u32 = 1;
+
+/// Result of migrating a parsed config table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Migration {
+    /// Already at [`CONFIG_VERSION`]
+    UpToDate,
+    /// Upgraded from the given version; the file should be rewritten
+    Upgraded {
+        /// Version found in the file
+        from: u32,
+    },
+    /// Written by a newer release; loaded as-is
+    Newer {
+        /// Version found in the file
+        found: u32,
+    },
+}
+
+/// Schema version of a parsed config table (missing = 0)
+#[must_use]
+pub fn config_version(table: &Table) -> u32 {
+    table
+        .get("version")
+        .and_then(Value::as_integer)
+        .and_then(|v| u32::try_from(v).ok())
+        .unwrap_or(0)
+}
+
+/// Upgrade `table` in place to [`CONFIG_VERSION`]
+pub fn migrate(table: &mut Table) -> Migration {
+    let from = config_version(table);
+
+    if from > CONFIG_VERSION {
+        warn!(
+            "Config version {} is newer than supported version {}; \
+             settings this release doesn't know about will be ignored",
+            from, CONFIG_VERSION
+        );
+        return Migration::Newer { found: from };
+    }
+
+    if from == CONFIG_VERSION {
+        return Migration::UpToDate;
+    }
+
+    let mut version = from;
+    while version < CONFIG_VERSION {
+        match version {
+            0 => migrate_v0_to_v1(table),
+            _ => unreachable!("no migration from config version {version}"),
+        }
+        version += 1;
+    }
+
+    table.insert("version".to_string(), Value::Integer(i64::from(version)));
+    info!("Migrated config from version {} to {}", from, version);
+    Migration::Upgraded { from }
+}
+
+/// v0 -> v1: `settings.verbose` became `settings.verbosity`, and
+/// `daemon.sync_interval` ("1h") became `daemon.interval_seconds`
+fn migrate_v0_to_v1(table: &mut Table) {
+    if let Some(settings) = section(table, "settings") {
+        if let Some(verbose) = settings.remove("verbose") {
+            if !settings.contains_key("verbosity") {
+                let level = if verbose.as_bool().unwrap_or(false) {
+                    2
+                } else {
+                    1
+                };
+                settings.insert("verbosity".to_string(), Value::Integer(level));
+            }
+        }
+    }
+
+    if let Some(daemon) = section(table, "daemon") {
+        if let Some(interval) = daemon.remove("sync_interval") {
+            let seconds = interval
+                .as_str()
+                .and_then(|s| Schedule::from_duration_str(s).ok())
+                .map(|schedule| schedule.interval_seconds())
+                .or_else(|| interval.as_integer().and_then(|v| u64::try_from(v).ok()));
+
+            match seconds {
+                Some(seconds) if !daemon.contains_key("interval_seconds") => {
+                    daemon.insert(
+                        "interval_seconds".to_string(),
+                        Value::Integer(i64::try_from(seconds).unwrap_or(i64::MAX)),
+                    );
+                }
+                Some(_) => {}
+                None => warn!("Dropping invalid daemon.sync_interval: {}", interval),
+            }
+        }
+    }
+}
+
+fn section<'a>(table: &'a mut Table, name: &str) -> Option<&'a mut Table> {
+    table.get_mut(name).and_then(Value::as_table_mut)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_version_is_up_to_date() {
+        let mut table: Table = toml::from_str("version = 1\n").unwrap();
+        assert_eq!(migrate(&mut table), Migration::UpToDate);
+    }
+
+    #[test]
+    fn test_newer_version_is_left_alone() {
+        let mut table: Table = toml::from_str("version = 99\n[future]\nkey = 1\n").unwrap();
+        assert_eq!(migrate(&mut table), Migration::Newer { found: 99 });
+        assert!(table.contains_key("future"));
+        assert_eq!(config_version(&table), 99);
+    }
+
+    #[test]
+    fn test_v0_renamed_keys() {
+        let mut table: Table =
+            toml::from_str("[settings]\nverbose = true\n\n[daemon]\nsync_interval = \"1h\"\n")
+                .unwrap();
+
+        assert_eq!(migrate(&mut table), Migration::Upgraded { from: 0 });
+        assert_eq!(config_version(&table), CONFIG_VERSION);
+        assert_eq!(table["settings"]["verbosity"].as_integer(), Some(2));
+        assert!(table["settings"].get("verbose").is_none());
+        assert_eq!(table["daemon"]["interval_seconds"].as_integer(), Some(3600));
+        assert!(table["daemon"].get("sync_interval").is_none());
+    }
+}
@@ -4,9 +4,14 @@
 //! authentication, sync operations, and conflict resolution.
 
 pub mod auth;
+pub mod clock_skew;
 pub mod config;
+pub mod config_migration;
 pub mod conflict_resolver;
 pub mod health_checker;
+pub mod profile;
+pub mod repo_lock;
+pub mod routing;
 pub mod sync_manager;
 
 pub use config::Config;
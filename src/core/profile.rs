@@ -0,0 +1,213 @@
+//! Configuration profiles
+//!
+//! A profile is a separate user config at
+//! `~/.config/multigit/profiles/<name>/config.toml` with its own keyring
+//! namespace, so work and personal setups don't share remotes or tokens.
+//! The profile comes from `--profile`, else `settings.default_profile` in the
+//! plain user config; with neither, `MultiGit` uses the plain user config and
+//! keyring service as before.
+
+use crate::core::config::Config;
+use crate::utils::error::{MultiGitError, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Directory under the user config dir holding one directory per profile
+pub const PROFILES_DIR: &str = "profiles";
+
+/// Name that selects the plain user config
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Keyring service used without a profile
+const BASE_KEYRING_SERVICE: &str = "multigit";
+
+/// Profile given with `--profile`
+static PROFILE_OVERRIDE: RwLock<Option<String>> = RwLock::new(None);
+
+/// Use `profile` for the rest of the process, e.g. from `--profile`
+pub fn set_override(profile: Option<String>) {
+    if let Ok(mut current) = PROFILE_OVERRIDE.write() {
+        *current = profile;
+    }
+}
+
+/// Use an existing profile for the rest of the process (`--profile`)
+pub fn select(name: &str) -> Result<()> {
+    validate_name(name)?;
+    if !exists(name) {
+        return Err(missing(name));
+    }
+    set_override(Some(name.to_string()));
+    Ok(())
+}
+
+/// The profile in effect, or `None` for the plain user config
+#[must_use]
+pub fn active() -> Option<String> {
+    let selected = PROFILE_OVERRIDE
+        .read()
+        .ok()
+        .and_then(|current| current.clone())
+        .or_else(default_profile);
+
+    selected.filter(|name| name != DEFAULT_PROFILE)
+}
+
+/// `settings.default_profile` from the plain user config
+#[must_use]
+pub fn default_profile() -> Option<String> {
+    let path = Config::base_config_dir().ok()?.join("config.toml");
+    if !path.exists() {
+        return None;
+    }
+    Config::load_from_file(&path).ok()?.settings.default_profile
+}
+
+/// Check that `name` can be used as a profile (and directory) name
+pub fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(MultiGitError::InvalidInput(format!(
+            "Invalid profile name '{name}': use letters, digits, '-' and '_'"
+        )))
+    }
+}
+
+/// Config directory of a profile
+pub fn profile_dir(name: &str) -> Result<PathBuf> {
+    validate_name(name)?;
+    Ok(Config::base_config_dir()?.join(PROFILES_DIR).join(name))
+}
+
+/// Whether a profile has been created
+#[must_use]
+pub fn exists(name: &str) -> bool {
+    name == DEFAULT_PROFILE || profile_dir(name).is_ok_and(|dir| dir.join("config.toml").exists())
+}
+
+/// Names of all created profiles, sorted
+pub fn list() -> Result<Vec<String>> {
+    list_in(&Config::base_config_dir()?.join(PROFILES_DIR))
+}
+
+/// Create a profile with a default config, returning its config path
+pub fn create(name: &str) -> Result<PathBuf> {
+    if name == DEFAULT_PROFILE {
+        return Err(MultiGitError::InvalidInput(format!(
+            "'{DEFAULT_PROFILE}' is the plain user config and always exists"
+        )));
+    }
+    create_in(&Config::base_config_dir()?.join(PROFILES_DIR), name)
+}
+
+/// Make `name` the profile used when `--profile` is not given
+///
+/// `default` goes back to the plain user config.
+pub fn switch(name: &str) -> Result<()> {
+    validate_name(name)?;
+    if !exists(name) {
+        return Err(missing(name));
+    }
+
+    let path = Config::base_config_dir()?.join("config.toml");
+    let mut config = if path.exists() {
+        Config::load_from_file(&path)?
+    } else {
+        Config::default()
+    };
+    config.settings.default_profile = (name != DEFAULT_PROFILE).then(|| name.to_string());
+    config.save_to_file(&path)
+}
+
+/// Keyring service for the active profile
+#[must_use]
+pub fn keyring_service() -> String {
+    keyring_service_for(active().as_deref())
+}
+
+/// Keyring service for `profile`; no profile keeps the original service name
+#[must_use]
+pub fn keyring_service_for(profile: Option<&str>) -> String {
+    match profile {
+        Some(name) => format!("{BASE_KEYRING_SERVICE}:{name}"),
+        None => BASE_KEYRING_SERVICE.to_string(),
+    }
+}
+
+fn missing(name: &str) -> MultiGitError {
+    MultiGitError::config(format!(
+        "Profile '{name}' does not exist. Create it with 'multigit profile create {name}'"
+    ))
+}
+
+fn list_in(profiles_dir: &Path) -> Result<Vec<String>> {
+    if !profiles_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut names: Vec<String> = fs::read_dir(profiles_dir)?
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| entry.path().join("config.toml").exists())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| validate_name(name).is_ok())
+        .collect();
+    names.sort();
+
+    Ok(names)
+}
+
+fn create_in(profiles_dir: &Path, name: &str) -> Result<PathBuf> {
+    validate_name(name)?;
+    let path = profiles_dir.join(name).join("config.toml");
+    if path.exists() {
+        return Err(MultiGitError::config(format!(
+            "Profile '{name}' already exists"
+        )));
+    }
+
+    Config::default().save_to_file(&path)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("work").is_ok());
+        assert!(validate_name("client_a-2").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("../etc").is_err());
+        assert!(validate_name("my profile").is_err());
+    }
+
+    #[test]
+    fn test_keyring_service_for() {
+        assert_eq!(keyring_service_for(None), "multigit");
+        assert_eq!(keyring_service_for(Some("work")), "multigit:work");
+    }
+
+    #[test]
+    fn test_create_and_list() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join(PROFILES_DIR);
+        assert!(list_in(&dir).unwrap().is_empty());
+
+        let path = create_in(&dir, "work").unwrap();
+        assert!(path.ends_with("profiles/work/config.toml"));
+        create_in(&dir, "personal").unwrap();
+        // A stray directory without a config is not a profile
+        fs::create_dir_all(dir.join("scratch")).unwrap();
+
+        assert_eq!(list_in(&dir).unwrap(), vec!["personal", "work"]);
+        assert!(create_in(&dir, "work").is_err());
+    }
+}
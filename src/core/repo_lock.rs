@@ -0,0 +1,244 @@
+//! Repository lock for mutating commands
+//!
+//! Commands that move refs (push, sync, pull, merge) take an advisory lock
+//! on `.multigit/lock` so that, say, a daemon sync and a manual push don't
+//! update the same refs at once. Read-only commands never take it. The lock
+//! is released when the holder exits, even if it crashes, so a stale file is
+//! harmless; it also records who holds it, for the error the next command
+//! shows.
+//!
+//! By default a held lock fails fast; `--lock-timeout` waits for it instead.
+
+use crate::utils::error::{MultiGitError, Result};
+use fs2::FileExt;
+use git2::Repository;
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// How long to wait for a held lock, from `--lock-timeout`
+static WAIT: RwLock<Duration> = RwLock::new(Duration::ZERO);
+
+/// Locks this process already holds, so nested commands don't block on them
+static HELD: Mutex<Option<HashSet<PathBuf>>> = Mutex::new(None);
+
+/// How often a waiting command retries the lock
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// An acquired repository lock, released on drop
+#[derive(Debug)]
+pub struct RepoLock {
+    /// The locked file, or `None` when an outer guard in this process holds it
+    file: Option<File>,
+    path: PathBuf,
+}
+
+impl RepoLock {
+    /// Lock the repository containing `path` for `command`, waiting as long as
+    /// `--lock-timeout` allows
+    ///
+    /// Outside a repository there is nothing to protect, and the guard does
+    /// nothing.
+    pub fn acquire(path: impl AsRef<Path>, command: &str) -> Result<Self> {
+        let Ok(repo) = Repository::discover(path.as_ref()) else {
+            return Ok(Self {
+                file: None,
+                path: PathBuf::new(),
+            });
+        };
+        Self::acquire_in(&repo, command, wait())
+    }
+
+    /// Lock `repo` for `command`, waiting up to `wait` for another process to
+    /// release it
+    pub fn acquire_in(repo: &Repository, command: &str, wait: Duration) -> Result<Self> {
+        let path = lock_path(repo)?;
+        if !mark_held(&path) {
+            debug!("Repository lock already held by this process");
+            return Ok(Self { file: None, path });
+        }
+
+        match open_and_lock(&path, command, wait) {
+            Ok(file) => {
+                debug!("Acquired repository lock {}", path.display());
+                Ok(Self {
+                    file: Some(file),
+                    path,
+                })
+            }
+            Err(e) => {
+                unmark_held(&path);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl Drop for RepoLock {
+    fn drop(&mut self) {
+        if let Some(file) = self.file.take() {
+            let _ = file.set_len(0);
+            let _ = FileExt::unlock(&file);
+            unmark_held(&self.path);
+        }
+    }
+}
+
+/// Wait up to `wait` for a held lock in every later [`RepoLock::acquire`] in
+/// this process, from `--lock-timeout`
+pub fn set_wait(wait: Duration) {
+    if let Ok(mut current) = WAIT.write() {
+        *current = wait;
+    }
+}
+
+fn wait() -> Duration {
+    WAIT.read().map(|wait| *wait).unwrap_or_default()
+}
+
+/// `.multigit/lock` in the working tree (kept out of `git status`), or
+/// `multigit.lock` in the git directory of a bare repository
+fn lock_path(repo: &Repository) -> Result<PathBuf> {
+    let Some(workdir) = repo.workdir() else {
+        return Ok(repo.path().join("multigit.lock"));
+    };
+    let dir = workdir.join(".multigit");
+    fs::create_dir_all(&dir)?;
+    let path = dir.join("lock");
+    if !repo
+        .is_path_ignored(Path::new(".multigit/lock"))
+        .unwrap_or(false)
+    {
+        exclude(repo)?;
+    }
+    Ok(path)
+}
+
+/// Add the lock file to `.git/info/exclude`, so it never shows as untracked
+fn exclude(repo: &Repository) -> Result<()> {
+    let info = repo.path().join("info");
+    fs::create_dir_all(&info)?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(info.join("exclude"))?;
+    writeln!(file, "/.multigit/lock")?;
+    Ok(())
+}
+
+fn open_and_lock(path: &Path, command: &str, wait: Duration) -> Result<File> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)?;
+
+    let started = Instant::now();
+    loop {
+        match file.try_lock_exclusive() {
+            Ok(()) => break,
+            Err(e) if e.kind() == fs2::lock_contended_error().kind() => {
+                if started.elapsed() >= wait {
+                    return Err(locked_error(&mut file, wait));
+                }
+                std::thread::sleep(POLL_INTERVAL);
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    file.set_len(0)?;
+    file.rewind()?;
+    writeln!(file, "{} multigit {command}", std::process::id())?;
+    file.flush()?;
+    Ok(file)
+}
+
+/// Who holds the lock, from what the holder wrote into it
+fn locked_error(file: &mut File, wait: Duration) -> MultiGitError {
+    let mut holder = String::new();
+    let _ = file
+        .rewind()
+        .and_then(|()| file.read_to_string(&mut holder));
+    let holder = match holder.trim().split_once(' ') {
+        Some((pid, command)) => format!("'{command}' (pid {pid})"),
+        None => "another multigit process".to_string(),
+    };
+    let waited = if wait.is_zero() {
+        "; pass --lock-timeout <secs> to wait for it".to_string()
+    } else {
+        format!(" after waiting {}s", wait.as_secs())
+    };
+    MultiGitError::other(format!("The repository is locked by {holder}{waited}"))
+}
+
+fn mark_held(path: &Path) -> bool {
+    let mut held = HELD
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    held.get_or_insert_with(HashSet::new)
+        .insert(path.to_path_buf())
+}
+
+fn unmark_held(path: &Path) {
+    let mut held = HELD
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if let Some(held) = held.as_mut() {
+        held.remove(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_lock_is_reentrant_and_excluded() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+
+        let outer = RepoLock::acquire_in(&repo, "sync", Duration::ZERO).unwrap();
+        let inner = RepoLock::acquire_in(&repo, "push", Duration::ZERO).unwrap();
+        assert!(inner.file.is_none());
+        drop(inner);
+
+        let lock = temp_dir.path().join(".multigit/lock");
+        let contents = fs::read_to_string(&lock).unwrap();
+        assert!(contents.ends_with("multigit sync\n"));
+        let mut options = git2::StatusOptions::new();
+        options.include_untracked(true).include_ignored(false);
+        assert!(repo.statuses(Some(&mut options)).unwrap().is_empty());
+
+        drop(outer);
+        assert_eq!(fs::read_to_string(&lock).unwrap(), "");
+        let again = RepoLock::acquire_in(&repo, "pull", Duration::ZERO).unwrap();
+        assert!(again.file.is_some());
+    }
+
+    #[test]
+    fn test_held_lock_fails_with_the_holder() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let path = lock_path(&repo).unwrap();
+
+        // Another process: a separate open file holding the lock
+        let other = open_and_lock(&path, "push", Duration::ZERO).unwrap();
+        let err = open_and_lock(&path, "sync", Duration::ZERO).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("'multigit push'"), "{message}");
+        assert!(message.contains("--lock-timeout"), "{message}");
+
+        let err = open_and_lock(&path, "sync", Duration::from_millis(200)).unwrap_err();
+        assert!(err.to_string().contains("after waiting"));
+
+        FileExt::unlock(&other).unwrap();
+        assert!(open_and_lock(&path, "sync", Duration::ZERO).is_ok());
+    }
+}
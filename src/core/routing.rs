@@ -0,0 +1,194 @@
+//! Branch-specific remote routing
+//!
+//! The `[routing]` config maps branch globs to the remotes those branches may
+//! be pushed to, so that e.g. `internal/*` never reaches a public mirror.
+//! Every rule whose glob matches a branch applies: a remote denied by any of
+//! them is blocked, and when any of them has an `allow` list the remote must
+//! be on one. Deny rules win over allow rules.
+
+use crate::core::config::Config;
+use crate::git::branch_filter::branch_glob;
+use crate::models::RouteRule;
+use crate::utils::error::Result;
+use globset::GlobMatcher;
+
+/// Why a remote may or may not receive a branch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    /// No rule restricts the remote
+    Unrestricted,
+    /// Allowed by the rule for this glob
+    Allowed(String),
+    /// Denied by the rule for this glob
+    Denied(String),
+    /// Not on the allow list of the rules for these globs
+    NotAllowed(Vec<String>),
+}
+
+impl Route {
+    /// Whether the branch may be pushed to the remote
+    #[must_use]
+    pub fn permits(&self) -> bool {
+        matches!(self, Self::Unrestricted | Self::Allowed(_))
+    }
+
+    /// One-line reason, e.g. `denied by "internal/**"`
+    #[must_use]
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Unrestricted => "no routing rule applies".to_string(),
+            Self::Allowed(pattern) => format!("allowed by \"{pattern}\""),
+            Self::Denied(pattern) => format!("denied by \"{pattern}\""),
+            Self::NotAllowed(patterns) => {
+                let patterns: Vec<String> = patterns.iter().map(|p| format!("\"{p}\"")).collect();
+                format!("not allowed by {}", patterns.join(", "))
+            }
+        }
+    }
+}
+
+/// Compiled `[routing]` rules
+#[derive(Debug, Clone, Default)]
+pub struct Routing {
+    rules: Vec<(String, GlobMatcher, RouteRule)>,
+}
+
+impl Routing {
+    /// Compile the `[routing]` section of `config`
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let rules = config
+            .routing
+            .iter()
+            .map(|(pattern, rule)| {
+                Ok((
+                    pattern.clone(),
+                    branch_glob(pattern)?.compile_matcher(),
+                    rule.clone(),
+                ))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Whether no rules are configured
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Decide whether `branch` may be pushed to `remote`
+    #[must_use]
+    pub fn route(&self, branch: &str, remote: &str) -> Route {
+        let matching: Vec<&(String, GlobMatcher, RouteRule)> = self
+            .rules
+            .iter()
+            .filter(|(_, matcher, _)| matcher.is_match(branch))
+            .collect();
+
+        if let Some((pattern, _, _)) = matching
+            .iter()
+            .find(|(_, _, rule)| rule.deny.iter().any(|r| r == remote))
+        {
+            return Route::Denied(pattern.clone());
+        }
+
+        let restricting: Vec<&&(String, GlobMatcher, RouteRule)> = matching
+            .iter()
+            .filter(|(_, _, rule)| !rule.allow.is_empty())
+            .collect();
+        if restricting.is_empty() {
+            return Route::Unrestricted;
+        }
+        match restricting
+            .iter()
+            .find(|(_, _, rule)| rule.allow.iter().any(|r| r == remote))
+        {
+            Some((pattern, _, _)) => Route::Allowed(pattern.clone()),
+            None => Route::NotAllowed(
+                restricting
+                    .iter()
+                    .map(|(pattern, _, _)| pattern.clone())
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Split `remotes` into those `branch` may be pushed to and those it may not
+    #[must_use]
+    pub fn partition(&self, branch: &str, remotes: &[String]) -> (Vec<String>, Vec<String>) {
+        remotes
+            .iter()
+            .cloned()
+            .partition(|remote| self.route(branch, remote).permits())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn routing(rules: &[(&str, &[&str], &[&str])]) -> Routing {
+        let mut config = Config::default();
+        for (pattern, allow, deny) in rules {
+            config.routing.insert(
+                (*pattern).to_string(),
+                RouteRule {
+                    allow: allow.iter().map(ToString::to_string).collect(),
+                    deny: deny.iter().map(ToString::to_string).collect(),
+                },
+            );
+        }
+        Routing::from_config(&config).unwrap()
+    }
+
+    #[test]
+    fn test_unmatched_branches_go_everywhere() {
+        let routing = routing(&[("internal/**", &[], &["public"])]);
+        assert_eq!(routing.route("main", "public"), Route::Unrestricted);
+        assert!(Routing::default().route("internal/x", "public").permits());
+    }
+
+    #[test]
+    fn test_deny_wins_over_allow() {
+        let routing = routing(&[
+            ("internal/**", &[], &["public"]),
+            ("**", &["public", "private"], &[]),
+        ]);
+        assert_eq!(
+            routing.route("internal/secret", "public"),
+            Route::Denied("internal/**".to_string())
+        );
+        assert_eq!(
+            routing.route("internal/secret", "private"),
+            Route::Allowed("**".to_string())
+        );
+        assert_eq!(
+            routing.route("main", "public"),
+            Route::Allowed("**".to_string())
+        );
+    }
+
+    #[test]
+    fn test_allow_list_restricts_matching_branches() {
+        let routing = routing(&[("release/*", &["github"], &[])]);
+        let remotes = vec!["github".to_string(), "gitlab".to_string()];
+        let (allowed, blocked) = routing.partition("release/1.0", &remotes);
+        assert_eq!(allowed, vec!["github"]);
+        assert_eq!(blocked, vec!["gitlab"]);
+        assert_eq!(
+            routing.route("release/1.0", "gitlab"),
+            Route::NotAllowed(vec!["release/*".to_string()])
+        );
+        // `*` stays within one segment
+        assert!(routing.route("release/1.x/hotfix", "gitlab").permits());
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let mut config = Config::default();
+        config
+            .routing
+            .insert("internal/[a".to_string(), RouteRule::default());
+        assert!(Routing::from_config(&config).is_err());
+    }
+}
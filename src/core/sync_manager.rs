@@ -2,12 +2,23 @@
 //!
 //! Coordinates push/pull/sync operations across multiple Git remotes.
 
-use crate::git::operations::GitOperations;
-use crate::utils::error::Result;
+use crate::git::branch::BranchManager;
+use crate::git::lfs;
+use crate::git::operations::{GitOperations, RefUpdate, StashPop, DEFAULT_NETWORK_ATTEMPTS};
+use crate::git::remote::RemoteManager;
+use crate::git::repo_state::OperationInProgress;
+use crate::git::submodule::SubmoduleInfo;
+use crate::git::working_tree::{self, WorkingTreeSummary};
+use crate::git::worktree::{self, WorktreeInfo};
+use crate::utils::error::{MultiGitError, Result};
+use crate::utils::interrupt::{self, Interruptible};
+use crate::utils::tls::TlsOptions;
+use git2::Oid;
 use std::collections::HashMap;
 use std::path::Path;
 use tokio::task::JoinHandle;
-use tracing::{debug, error, info, warn};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, instrument, warn};
 
 /// Result of a push operation to a single remote
 #[derive(Debug, Clone)]
@@ -20,6 +31,74 @@ pub struct PushResult {
     pub message: String,
     /// Duration of the operation in milliseconds
     pub duration_ms: u64,
+    /// Stopped by Ctrl-C before it finished
+    pub cancelled: bool,
+    /// Rejected because the remote branch has diverged (not a fast-forward)
+    pub rejected: bool,
+    /// How each pushed ref changed on the remote (empty unless it succeeded)
+    pub updates: Vec<RefUpdate>,
+}
+
+impl PushResult {
+    fn from_outcome(
+        remote: String,
+        outcome: Result<Vec<RefUpdate>>,
+        cancelled: bool,
+        duration_ms: u64,
+    ) -> Self {
+        match outcome {
+            Ok(updates) => {
+                info!("Successfully pushed to {}", remote);
+                Self {
+                    remote,
+                    success: true,
+                    message: "Push successful".to_string(),
+                    duration_ms,
+                    cancelled: false,
+                    rejected: false,
+                    updates,
+                }
+            }
+            Err(_) if cancelled => Self::interrupted(remote, duration_ms),
+            Err(e) if e.is_non_fast_forward() => {
+                warn!("Push to {} rejected: {}", remote, e);
+                Self::failed(
+                    remote,
+                    "Rejected: the remote branch has diverged (non-fast-forward)".to_string(),
+                    duration_ms,
+                    true,
+                )
+            }
+            Err(e) => {
+                warn!("Failed to push to {}: {}", remote, e);
+                Self::failed(remote, format!("Push failed: {e}"), duration_ms, false)
+            }
+        }
+    }
+
+    fn failed(remote: String, message: String, duration_ms: u64, rejected: bool) -> Self {
+        Self {
+            remote,
+            success: false,
+            message,
+            duration_ms,
+            cancelled: false,
+            rejected,
+            updates: Vec::new(),
+        }
+    }
+
+    fn interrupted(remote: String, duration_ms: u64) -> Self {
+        Self {
+            remote,
+            success: false,
+            message: "Interrupted".to_string(),
+            duration_ms,
+            cancelled: true,
+            rejected: false,
+            updates: Vec::new(),
+        }
+    }
 }
 
 /// Result of a fetch operation from a single remote
@@ -33,12 +112,45 @@ pub struct FetchResult {
     pub message: String,
     /// Number of commits fetched
     pub commits_fetched: usize,
+    /// Duration of the operation in milliseconds
+    pub duration_ms: u64,
+    /// Stopped by Ctrl-C before it finished
+    pub cancelled: bool,
+}
+
+impl FetchResult {
+    fn failed(remote: String, message: String, duration_ms: u64) -> Self {
+        Self {
+            remote,
+            success: false,
+            message,
+            commits_fetched: 0,
+            duration_ms,
+            cancelled: false,
+        }
+    }
+
+    fn interrupted(remote: String, duration_ms: u64) -> Self {
+        Self {
+            remote,
+            success: false,
+            message: "Interrupted".to_string(),
+            commits_fetched: 0,
+            duration_ms,
+            cancelled: true,
+        }
+    }
 }
 
 /// Synchronization manager
 pub struct SyncManager {
     git_ops: GitOperations,
     max_parallel: usize,
+    proxy_url: Option<String>,
+    tls: HashMap<String, TlsOptions>,
+    cancel: CancellationToken,
+    fetch_depth: Option<u32>,
+    network_attempts: u32,
 }
 
 impl SyncManager {
@@ -49,6 +161,11 @@ impl SyncManager {
         Ok(Self {
             git_ops,
             max_parallel: 4, // Default parallel operations
+            proxy_url: None,
+            tls: HashMap::new(),
+            cancel: interrupt::token(),
+            fetch_depth: None,
+            network_attempts: DEFAULT_NETWORK_ATTEMPTS,
         })
     }
 
@@ -59,63 +176,197 @@ impl SyncManager {
         self
     }
 
+    /// Route fetch/push through `proxy_url` (see [`GitOperations::with_proxy`])
+    #[must_use]
+    pub fn with_proxy(mut self, proxy_url: Option<String>) -> Self {
+        self.proxy_url = proxy_url;
+        self
+    }
+
+    /// Per-remote certificate trust settings (see `Config::remote_tls`)
+    #[must_use]
+    pub fn with_tls(mut self, tls: HashMap<String, TlsOptions>) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    /// Token that stops pushes and fetches (default: cancelled by Ctrl-C)
+    #[must_use]
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Shallow fetches of at most `depth` commits (see [`GitOperations::with_fetch_depth`])
+    #[must_use]
+    pub fn with_fetch_depth(mut self, depth: Option<u32>) -> Self {
+        self.fetch_depth = depth;
+        self
+    }
+
+    /// Retry each push and fetch up to `attempts` times on transient network
+    /// errors (see [`GitOperations::with_retries`])
+    #[must_use]
+    pub fn with_retries(mut self, attempts: u32) -> Self {
+        self.network_attempts = attempts;
+        self
+    }
+
+    /// Whether pushes and fetches have been cancelled
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Trust settings for `remote`, with its CA registered before any transfer starts
+    fn prepare_tls(&self, remote: &str) -> TlsOptions {
+        let tls = self.tls.get(remote).cloned().unwrap_or_default();
+        if let Err(e) = tls.trust_ca_for_git() {
+            warn!("Could not load CA certificate for {}: {}", remote, e);
+        }
+        tls
+    }
+
     /// Push to all configured remotes in parallel
+    #[instrument(level = "debug", skip_all, fields(branch = %branch, remotes = remotes.len()))]
     pub async fn push_all(&self, branch: &str, remotes: &[String]) -> Result<Vec<PushResult>> {
         info!("Pushing branch '{}' to {} remotes", branch, remotes.len());
+        self.push_refspecs(
+            vec![format!("refs/heads/{branch}:refs/heads/{branch}")],
+            remotes,
+            &HashMap::new(),
+        )
+        .await
+    }
+
+    /// Push to all given remotes in parallel, overwriting diverged remote branches
+    #[instrument(level = "debug", skip_all, fields(branch = %branch, remotes = remotes.len()))]
+    pub async fn force_push_all(
+        &self,
+        branch: &str,
+        remotes: &[String],
+    ) -> Result<Vec<PushResult>> {
+        warn!(
+            "Force pushing branch '{}' to {} remotes",
+            branch,
+            remotes.len()
+        );
+        self.push_refspecs(
+            vec![format!("+refs/heads/{branch}:refs/heads/{branch}")],
+            remotes,
+            &HashMap::new(),
+        )
+        .await
+    }
 
-        let refspec = format!("refs/heads/{branch}:refs/heads/{branch}");
+    /// Force push `branch` to each remote in `expected`, but only while that
+    /// remote's copy is still at the commit given for it
+    ///
+    /// A remote whose branch has moved is reported as rejected and left as is.
+    #[instrument(level = "debug", skip_all, fields(branch = %branch, remotes = expected.len()))]
+    pub async fn force_push_with_lease_all(
+        &self,
+        branch: &str,
+        expected: &HashMap<String, Oid>,
+    ) -> Result<Vec<PushResult>> {
+        let refname = format!("refs/heads/{branch}");
+        let mut remotes: Vec<String> = expected.keys().cloned().collect();
+        remotes.sort();
+        let leases = expected
+            .iter()
+            .map(|(remote, oid)| (remote.clone(), vec![(refname.clone(), *oid)]))
+            .collect();
+        info!(
+            "Force pushing branch '{}' with lease to {} remotes",
+            branch,
+            remotes.len()
+        );
+        self.push_refspecs(vec![format!("+{refname}:{refname}")], &remotes, &leases)
+            .await
+    }
+
+    /// Push `refspecs` to all given remotes in parallel, in one push per remote
+    #[instrument(level = "debug", skip_all, fields(refspecs = refspecs.len(), remotes = remotes.len()))]
+    pub async fn push_refs_all(
+        &self,
+        refspecs: &[String],
+        remotes: &[String],
+    ) -> Result<Vec<PushResult>> {
+        info!(
+            "Pushing {} ref(s) to {} remotes",
+            refspecs.len(),
+            remotes.len()
+        );
+        self.push_refspecs(refspecs.to_vec(), remotes, &HashMap::new())
+            .await
+    }
+
+    /// Push `refspecs` to each remote; `leases` lists, per remote, refs that
+    /// must still be at the given commit (see [`GitOperations::push_with_lease`])
+    async fn push_refspecs(
+        &self,
+        refspecs: Vec<String>,
+        remotes: &[String],
+        leases: &HashMap<String, Vec<(String, Oid)>>,
+    ) -> Result<Vec<PushResult>> {
+        let _interruptible = Interruptible::new();
         let mut tasks: Vec<JoinHandle<PushResult>> = Vec::new();
         let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(self.max_parallel));
 
         // Create tasks for each remote
-        for remote_name in remotes {
+        // Register CAs up front: libgit2 options can't change mid-transfer
+        let tls_settings: Vec<TlsOptions> = remotes.iter().map(|r| self.prepare_tls(r)).collect();
+
+        for (remote_name, tls) in remotes.iter().zip(tls_settings) {
             let remote = remote_name.clone();
-            let refspec = refspec.clone();
+            let refspecs = refspecs.clone();
+            let lease = leases.get(remote_name).cloned().unwrap_or_default();
             let repo_path = self.git_ops.workdir()?.to_path_buf();
+            let proxy_url = self.proxy_url.clone();
             let permit = semaphore.clone();
+            let cancel = self.cancel.clone();
+            let network_attempts = self.network_attempts;
 
             let task = tokio::spawn(async move {
                 // Acquire semaphore permit to limit concurrency
-                let _permit = permit
-                    .acquire()
-                    .await
-                    .expect("Semaphore should not be closed");
-                let start = std::time::Instant::now();
-
-                // Open a new GitOperations instance for this task
-                let ops = match GitOperations::open(&repo_path) {
-                    Ok(ops) => ops,
-                    Err(e) => {
-                        return PushResult {
-                            remote,
-                            success: false,
-                            message: format!("Failed to open repo: {e}"),
-                            duration_ms: start.elapsed().as_millis() as u64,
-                        };
-                    }
+                let _permit = tokio::select! {
+                    biased;
+                    () = cancel.cancelled() => return PushResult::interrupted(remote, 0),
+                    permit = permit.acquire() => permit.expect("Semaphore should not be closed"),
                 };
-
-                // Perform the push
-                match ops.push(&remote, &[&refspec]) {
-                    Ok(()) => {
-                        info!("Successfully pushed to {}", remote);
-                        PushResult {
-                            remote,
-                            success: true,
-                            message: "Push successful".to_string(),
-                            duration_ms: start.elapsed().as_millis() as u64,
+                // git2 transfers and retry backoff block, so keep them off the runtime workers
+                let name = remote.clone();
+                tokio::task::spawn_blocking(move || {
+                    let start = std::time::Instant::now();
+
+                    // Open a new GitOperations instance for this task
+                    let ops = match GitOperations::open(&repo_path).map(|ops| {
+                        ops.with_proxy(proxy_url)
+                            .with_tls(tls)
+                            .with_cancellation(cancel.clone())
+                            .with_retries(network_attempts)
+                    }) {
+                        Ok(ops) => ops,
+                        Err(e) => {
+                            return PushResult::failed(
+                                remote,
+                                format!("Failed to open repo: {e}"),
+                                start.elapsed().as_millis() as u64,
+                                false,
+                            );
                         }
-                    }
-                    Err(e) => {
-                        warn!("Failed to push to {}: {}", remote, e);
-                        PushResult {
-                            remote,
-                            success: false,
-                            message: format!("Push failed: {e}"),
-                            duration_ms: start.elapsed().as_millis() as u64,
-                        }
-                    }
-                }
+                    };
+
+                    // Perform the push
+                    let refspecs: Vec<&str> = refspecs.iter().map(String::as_str).collect();
+                    let outcome = ops.push_with_lease(&remote, &refspecs, &lease);
+                    let duration_ms = start.elapsed().as_millis() as u64;
+                    PushResult::from_outcome(remote, outcome, cancel.is_cancelled(), duration_ms)
+                })
+                .await
+                .unwrap_or_else(|e| {
+                    PushResult::failed(name, format!("Push task failed: {e}"), 0, false)
+                })
             });
 
             tasks.push(task);
@@ -144,92 +395,135 @@ impl SyncManager {
 
     /// Fetch from all configured remotes in parallel
     pub async fn fetch_all(&self, remotes: &[String]) -> Result<Vec<FetchResult>> {
+        self.fetch_refspecs(remotes, &[]).await
+    }
+
+    /// Fetch `refspecs` from each remote in parallel
+    ///
+    /// An empty list fetches the remotes' configured refspecs. Whatever is
+    /// fetched is recorded in `FETCH_HEAD`.
+    #[instrument(level = "debug", skip_all, fields(remotes = remotes.len()))]
+    pub async fn fetch_refspecs(
+        &self,
+        remotes: &[String],
+        refspecs: &[String],
+    ) -> Result<Vec<FetchResult>> {
         info!("Fetching from {} remotes", remotes.len());
 
+        let _interruptible = Interruptible::new();
         let mut tasks: Vec<JoinHandle<FetchResult>> = Vec::new();
         let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(self.max_parallel));
 
-        for remote_name in remotes {
+        // Register CAs up front: libgit2 options can't change mid-transfer
+        let tls_settings: Vec<TlsOptions> = remotes.iter().map(|r| self.prepare_tls(r)).collect();
+
+        for (remote_name, tls) in remotes.iter().zip(tls_settings) {
             let remote = remote_name.clone();
             let repo_path = self.git_ops.workdir()?.to_path_buf();
+            let proxy_url = self.proxy_url.clone();
             let permit = semaphore.clone();
+            let cancel = self.cancel.clone();
+            let network_attempts = self.network_attempts;
+            let refspecs = refspecs.to_vec();
+            let fetch_depth = self.fetch_depth;
 
             let task = tokio::spawn(async move {
                 // Acquire semaphore permit to limit concurrency
-                let _permit = permit
-                    .acquire()
-                    .await
-                    .expect("Semaphore should not be closed");
-
-                let ops = match GitOperations::open(&repo_path) {
-                    Ok(ops) => ops,
-                    Err(e) => {
-                        return FetchResult {
-                            remote,
-                            success: false,
-                            message: format!("Failed to open repo: {e}"),
-                            commits_fetched: 0,
-                        };
-                    }
+                let _permit = tokio::select! {
+                    biased;
+                    () = cancel.cancelled() => return FetchResult::interrupted(remote, 0),
+                    permit = permit.acquire() => permit.expect("Semaphore should not be closed"),
                 };
-
-                // Get all remote refs before fetch to compare
-                let remote_refs_before: std::collections::HashSet<_> = ops
-                    .inner()
-                    .references_glob(&format!("refs/remotes/{remote}/*"))
-                    .ok()
-                    .map(|refs| {
-                        refs.filter_map(std::result::Result::ok)
-                            .filter_map(|r| r.target())
-                            .collect()
-                    })
-                    .unwrap_or_default();
-
-                // Fetch all refs from the remote
-                match ops.fetch(&remote, &[]) {
-                    Ok(()) => {
-                        info!("Successfully fetched from {}", remote);
-
-                        // Count new/updated refs by comparing OIDs
-                        let remote_refs_after: std::collections::HashSet<_> = ops
-                            .inner()
-                            .references_glob(&format!("refs/remotes/{remote}/*"))
-                            .ok()
-                            .map(|refs| {
-                                refs.filter_map(std::result::Result::ok)
-                                    .filter_map(|r| r.target())
-                                    .collect()
-                            })
-                            .unwrap_or_default();
-
-                        // Count new OIDs that weren't in the before set
-                        let new_refs = remote_refs_after.difference(&remote_refs_before).count();
-
-                        // Use new refs as a proxy for fetched commits
-                        // Note: This counts updated refs, not individual commits
-                        let commits_fetched = new_refs;
-
-                        FetchResult {
-                            remote,
-                            success: true,
-                            message: if commits_fetched > 0 {
-                                format!("Fetch successful ({new_refs} ref(s) updated)")
-                            } else {
-                                "Fetch successful (already up to date)".to_string()
-                            },
-                            commits_fetched,
+                // git2 transfers and retry backoff block, so keep them off the runtime workers
+                let name = remote.clone();
+                tokio::task::spawn_blocking(move || {
+                    let start = std::time::Instant::now();
+
+                    let ops = match GitOperations::open(&repo_path).map(|ops| {
+                        ops.with_proxy(proxy_url)
+                            .with_tls(tls)
+                            .with_cancellation(cancel.clone())
+                            .with_fetch_depth(fetch_depth)
+                            .with_retries(network_attempts)
+                    }) {
+                        Ok(ops) => ops,
+                        Err(e) => {
+                            return FetchResult::failed(
+                                remote,
+                                format!("Failed to open repo: {e}"),
+                                start.elapsed().as_millis() as u64,
+                            );
                         }
-                    }
-                    Err(e) => {
-                        warn!("Failed to fetch from {}: {}", remote, e);
-                        FetchResult {
-                            remote,
-                            success: false,
-                            message: format!("Fetch failed: {e}"),
-                            commits_fetched: 0,
+                    };
+
+                    // Get all remote refs before fetch to compare
+                    let remote_refs_before: std::collections::HashSet<_> = ops
+                        .inner()
+                        .references_glob(&format!("refs/remotes/{remote}/*"))
+                        .ok()
+                        .map(|refs| {
+                            refs.filter_map(std::result::Result::ok)
+                                .filter_map(|r| r.target())
+                                .collect()
+                        })
+                        .unwrap_or_default();
+
+                    // Fetch the requested refspecs, or all refs from the remote
+                    let specs: Vec<&str> = refspecs.iter().map(String::as_str).collect();
+                    match ops.fetch(&remote, &specs) {
+                        Ok(()) => {
+                            info!("Successfully fetched from {}", remote);
+
+                            // Count new/updated refs by comparing OIDs
+                            let remote_refs_after: std::collections::HashSet<_> = ops
+                                .inner()
+                                .references_glob(&format!("refs/remotes/{remote}/*"))
+                                .ok()
+                                .map(|refs| {
+                                    refs.filter_map(std::result::Result::ok)
+                                        .filter_map(|r| r.target())
+                                        .collect()
+                                })
+                                .unwrap_or_default();
+
+                            // Count new OIDs that weren't in the before set
+                            let new_refs =
+                                remote_refs_after.difference(&remote_refs_before).count();
+
+                            // Use new refs as a proxy for fetched commits
+                            // Note: This counts updated refs, not individual commits
+                            let commits_fetched = new_refs;
+
+                            FetchResult {
+                                remote,
+                                success: true,
+                                message: if commits_fetched > 0 {
+                                    format!("Fetch successful ({new_refs} ref(s) updated)")
+                                } else if !refspecs.is_empty() {
+                                    "Fetch successful (see FETCH_HEAD)".to_string()
+                                } else {
+                                    "Fetch successful (already up to date)".to_string()
+                                },
+                                commits_fetched,
+                                duration_ms: start.elapsed().as_millis() as u64,
+                                cancelled: false,
+                            }
+                        }
+                        Err(_) if cancel.is_cancelled() => {
+                            FetchResult::interrupted(remote, start.elapsed().as_millis() as u64)
+                        }
+                        Err(e) => {
+                            warn!("Failed to fetch from {}: {}", remote, e);
+                            FetchResult::failed(
+                                remote,
+                                format!("Fetch failed: {e}"),
+                                start.elapsed().as_millis() as u64,
+                            )
                         }
                     }
-                }
+                })
+                .await
+                .unwrap_or_else(|e| FetchResult::failed(name, format!("Fetch task failed: {e}"), 0))
             });
 
             tasks.push(task);
@@ -291,15 +585,160 @@ impl SyncManager {
         Ok(statuses)
     }
 
+    /// Path of the repository's git directory
+    #[must_use]
+    pub fn repo_path(&self) -> &Path {
+        self.git_ops.path()
+    }
+
+    /// Commit at HEAD
+    pub fn head_commit_id(&self) -> Result<Oid> {
+        self.git_ops.head_commit().map(|commit| commit.id())
+    }
+
+    /// Commit `branch` is at on `remote`, as of the last fetch
+    pub fn remote_branch_tip(&self, remote: &str, branch: &str) -> Result<Option<Oid>> {
+        self.git_ops.remote_branch_tip(remote, branch)
+    }
+
+    /// Commits `branch` is ahead of and behind its copy on `remote`, as of the last fetch
+    pub fn ahead_behind(&self, branch: &str, remote: &str) -> Result<(usize, usize)> {
+        self.git_ops.get_ahead_behind(branch, remote)
+    }
+
+    /// Fast-forward `branch` to its copy on `remote`, returning the commits pulled
+    ///
+    /// Works from the remote-tracking ref of the last fetch. Fails when
+    /// `branch` has commits the remote lacks.
+    pub fn fast_forward(&self, branch: &str, remote: &str) -> Result<usize> {
+        let (ahead, behind) = self.git_ops.get_ahead_behind(branch, remote)?;
+        if ahead > 0 {
+            return Err(MultiGitError::other(format!(
+                "Cannot fast-forward '{branch}' from {remote}: it has {ahead} local commit(s)"
+            )));
+        }
+
+        if behind > 0 {
+            self.git_ops.fast_forward(branch, remote)?;
+        }
+        Ok(behind)
+    }
+
     /// Check if the working directory is clean
     pub fn is_clean(&self) -> Result<bool> {
         self.git_ops.is_clean()
     }
 
+    /// Stash uncommitted changes; `None` when there was nothing to stash
+    pub fn stash_save(&self, message: &str) -> Result<Option<Oid>> {
+        self.git_ops.stash_save(message)
+    }
+
+    /// Reapply and drop the latest stash, keeping it if it conflicts
+    pub fn stash_pop(&self) -> Result<StashPop> {
+        self.git_ops.stash_pop()
+    }
+
+    /// Merge, rebase or similar operation the repository is in the middle of
+    pub fn operation_in_progress(&self) -> Result<Option<OperationInProgress>> {
+        self.git_ops.operation_in_progress()
+    }
+
+    /// Fail with guidance if an operation is in progress, so `action` does
+    /// not run against a half-merged tree
+    pub fn ensure_no_operation_in_progress(&self, action: &str) -> Result<()> {
+        match self.operation_in_progress()? {
+            Some(op) => Err(op.refuse(action)),
+            None => Ok(()),
+        }
+    }
+
     /// Get the current branch
     pub fn current_branch(&self) -> Result<String> {
         self.git_ops.current_branch()
     }
+
+    /// Whether `branch` tracks a remote branch
+    pub fn has_upstream(&self, branch: &str) -> Result<bool> {
+        Ok(BranchManager::new(self.git_ops.inner())
+            .get_upstream(branch)?
+            .is_some())
+    }
+
+    /// Make `branch` track its copy on `remote`
+    pub fn set_upstream(&self, branch: &str, remote: &str) -> Result<()> {
+        BranchManager::new(self.git_ops.inner()).set_upstream(branch, remote)
+    }
+
+    /// Get the names of all local branches
+    pub fn local_branches(&self) -> Result<Vec<String>> {
+        let branches = BranchManager::new(self.git_ops.inner()).list_local()?;
+        Ok(branches.into_iter().map(|b| b.name).collect())
+    }
+
+    /// Names of the local tags, sorted
+    pub fn tags(&self) -> Result<Vec<String>> {
+        self.git_ops.tags()
+    }
+
+    /// Annotated tags reachable from `branch` (see
+    /// [`GitOperations::annotated_tags_reachable_from`])
+    pub fn annotated_tags_reachable_from(&self, branch: &str) -> Result<Vec<String>> {
+        self.git_ops.annotated_tags_reachable_from(branch)
+    }
+
+    /// Names of the git remotes configured in the repository
+    pub fn remote_names(&self) -> Result<Vec<String>> {
+        RemoteManager::new(self.git_ops.inner()).list()
+    }
+
+    /// Submodules of the repository, sorted by path
+    pub fn submodules(&self) -> Result<Vec<SubmoduleInfo>> {
+        self.git_ops.submodules()
+    }
+
+    /// Uncommitted changes grouped like `git status -s`
+    pub fn working_tree(&self) -> Result<WorkingTreeSummary> {
+        working_tree::summarize(self.git_ops.inner())
+    }
+
+    /// Worktrees of the repository, the main one first
+    pub fn worktrees(&self) -> Result<Vec<WorktreeInfo>> {
+        worktree::list(self.git_ops.inner())
+    }
+
+    /// Managers for the initialized submodules, sharing this manager's settings
+    pub fn submodule_managers(&self) -> Result<Vec<(SubmoduleInfo, SyncManager)>> {
+        let mut managers = Vec::new();
+        for submodule in self.submodules()? {
+            if !submodule.is_initialized() {
+                continue;
+            }
+            let manager = SyncManager::new(&submodule.workdir)?
+                .with_max_parallel(self.max_parallel)
+                .with_proxy(self.proxy_url.clone())
+                .with_tls(self.tls.clone())
+                .with_cancellation(self.cancel.clone())
+                .with_fetch_depth(self.fetch_depth)
+                .with_retries(self.network_attempts);
+            managers.push((submodule, manager));
+        }
+        Ok(managers)
+    }
+
+    /// Whether the repository tracks files with Git LFS
+    #[must_use]
+    pub fn uses_lfs(&self) -> bool {
+        lfs::uses_lfs(self.git_ops.inner())
+    }
+
+    /// Upload the LFS objects of `branch` to `remote`
+    ///
+    /// [`push_all`](Self::push_all) only sends commits, so LFS content
+    /// needs this extra step.
+    pub fn push_lfs(&self, remote: &str, branch: &str) -> Result<()> {
+        lfs::push(self.git_ops.workdir()?, remote, branch)
+    }
 }
 
 /// Sync status for a single remote
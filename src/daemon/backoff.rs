@@ -0,0 +1,130 @@
+//! Per-remote exponential backoff for the daemon
+//!
+//! When a remote keeps failing (e.g. a self-hosted instance is down), the
+//! daemon stops retrying it on every run. Each consecutive failure doubles
+//! the remote's effective sync interval up to a cap; a success resets it.
+//! The state is persisted with the rest of the daemon state (see
+//! [`DaemonState`](crate::daemon::state::DaemonState)) so `daemon status`
+//! can report backed-off remotes.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Default upper bound for a remote's backed-off interval (6 hours)
+pub const DEFAULT_MAX_BACKOFF_SECONDS: u64 = 6 * 3600;
+
+/// Backoff state of a single remote
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteBackoff {
+    /// Number of failures since the last success
+    pub consecutive_failures: u32,
+    /// Earliest time the remote will be synced again
+    pub next_attempt: Option<DateTime<Utc>>,
+    /// Error from the most recent failure
+    pub last_error: Option<String>,
+}
+
+impl RemoteBackoff {
+    /// Check if the remote is currently being skipped
+    #[must_use]
+    pub fn is_backed_off(&self, now: DateTime<Utc>) -> bool {
+        self.next_attempt.is_some_and(|next| next > now)
+    }
+}
+
+/// Backoff state for all remotes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackoffTracker {
+    /// Backoff state keyed by remote name
+    #[serde(default)]
+    pub remotes: BTreeMap<String, RemoteBackoff>,
+}
+
+impl BackoffTracker {
+    /// Check whether `remote` is due for a sync
+    #[must_use]
+    pub fn should_sync(&self, remote: &str, now: DateTime<Utc>) -> bool {
+        self.remotes
+            .get(remote)
+            .map_or(true, |state| !state.is_backed_off(now))
+    }
+
+    /// Reset a remote after a successful sync
+    pub fn record_success(&mut self, remote: &str) {
+        self.remotes.remove(remote);
+    }
+
+    /// Record a failed sync and schedule the next attempt
+    ///
+    /// The delay is `base_seconds * 2^(failures - 1)`, capped at `max_seconds`,
+    /// so the first failure keeps the normal interval.
+    pub fn record_failure(
+        &mut self,
+        remote: &str,
+        error: impl Into<String>,
+        now: DateTime<Utc>,
+        base_seconds: u64,
+        max_seconds: u64,
+    ) -> u64 {
+        let state = self.remotes.entry(remote.to_string()).or_default();
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        state.last_error = Some(error.into());
+
+        let delay = backoff_delay(state.consecutive_failures, base_seconds, max_seconds);
+        state.next_attempt =
+            Some(now + Duration::seconds(i64::try_from(delay).unwrap_or(i64::MAX)));
+        delay
+    }
+
+    /// Remotes currently being skipped, by name
+    #[must_use]
+    pub fn backed_off(&self, now: DateTime<Utc>) -> Vec<(&str, &RemoteBackoff)> {
+        self.remotes
+            .iter()
+            .filter(|(_, state)| state.is_backed_off(now))
+            .map(|(name, state)| (name.as_str(), state))
+            .collect()
+    }
+}
+
+/// Backed-off delay after `failures` consecutive failures
+#[must_use]
+pub fn backoff_delay(failures: u32, base_seconds: u64, max_seconds: u64) -> u64 {
+    let exponent = failures.saturating_sub(1).min(32);
+    base_seconds
+        .max(1)
+        .saturating_mul(1u64 << exponent)
+        .min(max_seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_delay_doubles_and_caps() {
+        assert_eq!(backoff_delay(1, 300, 3600), 300);
+        assert_eq!(backoff_delay(2, 300, 3600), 600);
+        assert_eq!(backoff_delay(3, 300, 3600), 1200);
+        assert_eq!(backoff_delay(5, 300, 3600), 3600);
+        assert_eq!(backoff_delay(100, 300, 3600), 3600);
+    }
+
+    #[test]
+    fn test_failure_then_success_resets() {
+        let now = Utc::now();
+        let mut tracker = BackoffTracker::default();
+
+        tracker.record_failure("gitlab", "connection refused", now, 300, 3600);
+        tracker.record_failure("gitlab", "connection refused", now, 300, 3600);
+        assert!(!tracker.should_sync("gitlab", now));
+        assert!(tracker.should_sync("gitlab", now + Duration::seconds(601)));
+        assert!(tracker.should_sync("github", now));
+        assert_eq!(tracker.backed_off(now).len(), 1);
+
+        tracker.record_success("gitlab");
+        assert!(tracker.should_sync("gitlab", now));
+        assert!(tracker.backed_off(now).is_empty());
+    }
+}
@@ -2,9 +2,15 @@
 //!
 //! Provides daemon service for background sync operations with scheduling.
 
+pub mod backoff;
 pub mod scheduler;
 pub mod service;
+pub mod state;
+pub mod unit;
 
 // Re-export key types
+pub use backoff::{BackoffTracker, RemoteBackoff};
 pub use scheduler::{Schedule, Scheduler, SchedulerHandle};
 pub use service::{DaemonService, DaemonStatus};
+pub use state::{DaemonState, RemoteRunResult};
+pub use unit::{ServiceManager, ServicePlan};
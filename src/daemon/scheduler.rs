@@ -1,17 +1,20 @@
 //! Task scheduler for periodic sync operations
 //!
-//! Provides interval-based scheduling for automatic sync operations.
-//! Supports configurable intervals and graceful shutdown.
+//! Provides interval- and cron-based scheduling for automatic sync operations.
+//! Supports configurable intervals, cron expressions, and graceful shutdown.
 
+use chrono::{DateTime, Local, TimeZone};
+use std::fmt;
+use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::time::interval;
+use tokio::time::{interval, sleep};
 use tracing::{debug, error, info};
 
 /// Task scheduler for periodic operations
 pub struct Scheduler {
-    interval_seconds: u64,
+    schedule: Schedule,
     running: Arc<AtomicBool>,
 }
 
@@ -19,8 +22,14 @@ impl Scheduler {
     /// Create a new scheduler with the given interval
     #[must_use]
     pub fn new(interval_seconds: u64) -> Self {
+        Self::with_schedule(Schedule::every_seconds(interval_seconds))
+    }
+
+    /// Create a new scheduler from an interval or cron schedule
+    #[must_use]
+    pub fn with_schedule(schedule: Schedule) -> Self {
         Self {
-            interval_seconds,
+            schedule,
             running: Arc::new(AtomicBool::new(false)),
         }
     }
@@ -37,15 +46,21 @@ impl Scheduler {
             + 'static,
     {
         self.running.store(true, Ordering::SeqCst);
-        info!(
-            "Scheduler started with interval: {}s",
-            self.interval_seconds
-        );
+        info!("Scheduler started: {}", self.schedule);
 
-        let mut ticker = interval(Duration::from_secs(self.interval_seconds));
+        let mut ticker = match self.schedule {
+            Schedule::Interval { seconds } => Some(interval(Duration::from_secs(seconds))),
+            Schedule::Cron { .. } => None,
+        };
 
         while self.running.load(Ordering::SeqCst) {
-            ticker.tick().await;
+            if let Some(ticker) = ticker.as_mut() {
+                ticker.tick().await;
+            } else {
+                let delay = self.schedule.delay_until_next(&Local::now());
+                debug!("Next cron run in {:?}", delay);
+                sleep(delay).await;
+            }
 
             if !self.running.load(Ordering::SeqCst) {
                 break;
@@ -81,9 +96,17 @@ impl Scheduler {
     }
 
     /// Get the interval in seconds
+    ///
+    /// For cron schedules this is the time until the next run.
     #[must_use]
     pub fn interval_seconds(&self) -> u64 {
-        self.interval_seconds
+        self.schedule.interval_seconds()
+    }
+
+    /// Get the schedule this scheduler runs on
+    #[must_use]
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
     }
 
     /// Get a handle to stop the scheduler
@@ -115,10 +138,23 @@ impl SchedulerHandle {
     }
 }
 
-/// Simple cron-like schedule parser (simplified version)
+/// When a scheduled task should run
+///
+/// Either a fixed interval or a cron expression; the two are mutually exclusive.
 #[derive(Debug, Clone)]
-pub struct Schedule {
-    interval_seconds: u64,
+pub enum Schedule {
+    /// Run every `seconds` seconds
+    Interval {
+        /// Interval between runs in seconds
+        seconds: u64,
+    },
+    /// Run at the times matched by a cron expression (evaluated in local time)
+    Cron {
+        /// The expression as written by the user
+        expression: String,
+        /// Parsed schedule
+        schedule: Box<cron::Schedule>,
+    },
 }
 
 impl Schedule {
@@ -126,68 +162,129 @@ impl Schedule {
     pub fn from_duration_str(s: &str) -> Result<Self, String> {
         let s = s.trim().to_lowercase();
 
-        if s.ends_with('s') {
-            let num = s
-                .trim_end_matches('s')
+        let seconds = if s.ends_with('s') {
+            s.trim_end_matches('s')
                 .parse::<u64>()
-                .map_err(|e| format!("Invalid seconds: {e}"))?;
-            Ok(Self {
-                interval_seconds: num,
-            })
+                .map_err(|e| format!("Invalid seconds: {e}"))?
         } else if s.ends_with('m') {
-            let num = s
-                .trim_end_matches('m')
+            s.trim_end_matches('m')
                 .parse::<u64>()
-                .map_err(|e| format!("Invalid minutes: {e}"))?;
-            Ok(Self {
-                interval_seconds: num * 60,
-            })
+                .map_err(|e| format!("Invalid minutes: {e}"))?
+                * 60
         } else if s.ends_with('h') {
-            let num = s
-                .trim_end_matches('h')
+            s.trim_end_matches('h')
                 .parse::<u64>()
-                .map_err(|e| format!("Invalid hours: {e}"))?;
-            Ok(Self {
-                interval_seconds: num * 3600,
-            })
+                .map_err(|e| format!("Invalid hours: {e}"))?
+                * 3600
         } else {
             // Default to seconds if no suffix
-            let num = s
-                .parse::<u64>()
-                .map_err(|e| format!("Invalid number: {e}"))?;
-            Ok(Self {
-                interval_seconds: num,
-            })
-        }
+            s.parse::<u64>()
+                .map_err(|e| format!("Invalid number: {e}"))?
+        };
+
+        Ok(Self::every_seconds(seconds))
+    }
+
+    /// Create a schedule from a cron expression
+    ///
+    /// Accepts the standard 5-field form (`"0 2,14 * * *"`: minute, hour,
+    /// day of month, month, day of week) as well as the 6/7-field form with
+    /// leading seconds and trailing year.
+    pub fn from_cron(expression: &str) -> Result<Self, String> {
+        let expression = expression.trim();
+        let normalized = if expression.split_whitespace().count() == 5 {
+            format!("0 {expression}")
+        } else {
+            expression.to_string()
+        };
+
+        let schedule = cron::Schedule::from_str(&normalized)
+            .map_err(|e| format!("Invalid cron expression '{expression}': {e}"))?;
+
+        Ok(Self::Cron {
+            expression: expression.to_string(),
+            schedule: Box::new(schedule),
+        })
     }
 
     /// Get the interval in seconds
+    ///
+    /// For cron schedules this is the time until the next run.
     #[must_use]
     pub fn interval_seconds(&self) -> u64 {
-        self.interval_seconds
+        match self {
+            Self::Interval { seconds } => *seconds,
+            Self::Cron { .. } => self.delay_until_next(&Local::now()).as_secs(),
+        }
+    }
+
+    /// Check if this is a cron schedule
+    #[must_use]
+    pub fn is_cron(&self) -> bool {
+        matches!(self, Self::Cron { .. })
+    }
+
+    /// Compute the next run time strictly after `after`
+    #[must_use]
+    pub fn next_run_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        match self {
+            Self::Interval { seconds } => {
+                Some(after.clone() + chrono::Duration::seconds(i64::try_from(*seconds).ok()?))
+            }
+            Self::Cron { schedule, .. } => schedule.after(after).next(),
+        }
+    }
+
+    /// The first `count` runs of a scheduler started at `start`: interval
+    /// schedules run straight away and then every interval, cron schedules
+    /// at each matching time
+    #[must_use]
+    pub fn upcoming_runs<Tz: TimeZone>(
+        &self,
+        start: &DateTime<Tz>,
+        count: usize,
+    ) -> Vec<DateTime<Tz>> {
+        let first = match self {
+            Self::Interval { .. } => Some(start.clone()),
+            Self::Cron { .. } => self.next_run_after(start),
+        };
+        std::iter::successors(first, |previous| self.next_run_after(previous))
+            .take(count)
+            .collect()
+    }
+
+    /// Time to wait from `now` until the next run
+    #[must_use]
+    pub fn delay_until_next<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> Duration {
+        self.next_run_after(now)
+            .and_then(|next| (next - now.clone()).to_std().ok())
+            .unwrap_or(Duration::ZERO)
     }
 
     /// Create a schedule for every N seconds
     #[must_use]
     pub fn every_seconds(seconds: u64) -> Self {
-        Self {
-            interval_seconds: seconds,
-        }
+        Self::Interval { seconds }
     }
 
     /// Create a schedule for every N minutes
     #[must_use]
     pub fn every_minutes(minutes: u64) -> Self {
-        Self {
-            interval_seconds: minutes * 60,
-        }
+        Self::every_seconds(minutes * 60)
     }
 
     /// Create a schedule for every N hours
     #[must_use]
     pub fn every_hours(hours: u64) -> Self {
-        Self {
-            interval_seconds: hours * 3600,
+        Self::every_seconds(hours * 3600)
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Interval { seconds } => write!(f, "every {seconds}s"),
+            Self::Cron { expression, .. } => write!(f, "cron '{expression}'"),
         }
     }
 }
@@ -237,6 +334,61 @@ mod tests {
         assert_eq!(Schedule::every_hours(1).interval_seconds(), 3600);
     }
 
+    #[test]
+    fn test_cron_schedule_parsing() {
+        let schedule = Schedule::from_cron("0 2,14 * * *").unwrap();
+        assert!(schedule.is_cron());
+        assert_eq!(schedule.to_string(), "cron '0 2,14 * * *'");
+
+        assert!(Schedule::from_cron("0 0 2 * * *").is_ok());
+        assert!(Schedule::from_cron("not a cron").is_err());
+    }
+
+    #[test]
+    fn test_cron_next_run() {
+        let schedule = Schedule::from_cron("0 2,14 * * *").unwrap();
+        let now = chrono::Utc.with_ymd_and_hms(2024, 5, 1, 3, 0, 0).unwrap();
+
+        let next = schedule.next_run_after(&now).unwrap();
+        assert_eq!(
+            next,
+            chrono::Utc.with_ymd_and_hms(2024, 5, 1, 14, 0, 0).unwrap()
+        );
+        assert_eq!(
+            schedule.delay_until_next(&now),
+            Duration::from_secs(11 * 3600)
+        );
+    }
+
+    #[test]
+    fn test_interval_next_run() {
+        let schedule = Schedule::every_minutes(5);
+        let now = chrono::Utc.with_ymd_and_hms(2024, 5, 1, 3, 0, 0).unwrap();
+
+        assert_eq!(schedule.delay_until_next(&now), Duration::from_secs(300));
+    }
+
+    #[test]
+    fn test_upcoming_runs() {
+        let now = chrono::Utc.with_ymd_and_hms(2024, 5, 1, 3, 0, 0).unwrap();
+
+        let runs = Schedule::every_minutes(5).upcoming_runs(&now, 3);
+        assert_eq!(runs[0], now);
+        assert_eq!(runs[2], now + chrono::Duration::minutes(10));
+
+        let runs = Schedule::from_cron("0 2,14 * * *")
+            .unwrap()
+            .upcoming_runs(&now, 3);
+        assert_eq!(
+            runs,
+            vec![
+                chrono::Utc.with_ymd_and_hms(2024, 5, 1, 14, 0, 0).unwrap(),
+                chrono::Utc.with_ymd_and_hms(2024, 5, 2, 2, 0, 0).unwrap(),
+                chrono::Utc.with_ymd_and_hms(2024, 5, 2, 14, 0, 0).unwrap(),
+            ]
+        );
+    }
+
     #[test]
     fn test_scheduler_creation() {
         let scheduler = Scheduler::new(300);
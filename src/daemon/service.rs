@@ -4,38 +4,62 @@
 //! Handles PID files, signal handling, and graceful shutdown.
 
 use crate::core::config::Config;
-use crate::daemon::scheduler::Scheduler;
+use crate::daemon::scheduler::{Schedule, Scheduler};
+use crate::daemon::state::DaemonState;
 use crate::utils::error::{MultiGitError, Result};
+use crate::utils::paths;
 use crate::utils::redact::redact;
+use chrono::Utc;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process;
 use tokio::signal;
 use tracing::{debug, error, info, warn};
 
+/// How long a daemon sync waits for the repository lock
+const LOCK_TIMEOUT_SECS: u64 = 300;
+
 /// Daemon service for background operations
 pub struct DaemonService {
     pid_file: PathBuf,
     log_file: Option<PathBuf>,
-    interval_seconds: u64,
+    schedule: Schedule,
 }
 
 impl DaemonService {
     /// Create a new daemon service
     #[must_use]
     pub fn new(interval_seconds: u64) -> Self {
-        let config_dir = dirs::config_dir().unwrap_or_else(|| PathBuf::from(".multigit"));
+        Self::with_schedule(Schedule::every_seconds(interval_seconds))
+    }
 
-        let pid_file = config_dir.join("multigit").join("daemon.pid");
-        let log_file = Some(config_dir.join("multigit").join("daemon.log"));
+    /// Create a new daemon service running on an interval or cron schedule
+    #[must_use]
+    pub fn with_schedule(schedule: Schedule) -> Self {
+        let pid_file = paths::runtime_dir().join("daemon.pid");
+        let log_file = Self::default_log_path();
+
+        // Earlier versions kept these files in the config directory
+        if let Some(legacy_dir) = paths::legacy_dir() {
+            paths::migrate_legacy_file(&legacy_dir.join("daemon.pid"), &pid_file);
+            paths::migrate_legacy_file(&legacy_dir.join("daemon.log"), &log_file);
+        }
+
+        let log_file = Some(log_file);
 
         Self {
             pid_file,
             log_file,
-            interval_seconds,
+            schedule,
         }
     }
 
+    /// Default location of the daemon log file
+    #[must_use]
+    pub fn default_log_path() -> PathBuf {
+        paths::state_dir().join("daemon.log")
+    }
+
     /// Start the daemon service
     pub async fn start(&self) -> Result<()> {
         // Check if daemon is already running
@@ -46,7 +70,7 @@ impl DaemonService {
         }
 
         info!("Starting MultiGit daemon service...");
-        info!("Sync interval: {}s", self.interval_seconds);
+        info!("Sync schedule: {}", self.schedule);
 
         // Write PID file
         self.write_pid_file()?;
@@ -67,13 +91,26 @@ impl DaemonService {
             }
         });
 
+        // Record the new run in the persisted state (keeping backoff history)
+        let mut state = DaemonState::load();
+        state.schedule = Some(self.schedule.to_string());
+        state.started_at = Some(Utc::now());
+        state.next_run = Some(Utc::now());
+        if let Err(e) = state.save() {
+            warn!("Failed to save daemon state: {}", e);
+        }
+
         // Create scheduler
-        let scheduler = Scheduler::new(self.interval_seconds);
+        let scheduler = Scheduler::with_schedule(self.schedule.clone());
 
         // Define the sync task
-        let sync_task = || async move {
-            info!("[Daemon] Running scheduled sync...");
-            perform_sync().await
+        let schedule = self.schedule.clone();
+        let sync_task = move || {
+            let schedule = schedule.clone();
+            async move {
+                info!("[Daemon] Running scheduled sync...");
+                perform_sync(&schedule).await
+            }
         };
 
         // Run the scheduler
@@ -248,60 +285,166 @@ pub struct DaemonStatus {
 
 /// Perform a sync operation using the CLI command
 ///
+/// Each enabled remote is synced separately so that a persistently failing
+/// remote can be backed off without holding up the others.
+///
 /// Note: We use `tokio::process::Command` to invoke the CLI binary directly
 /// since `libgit2` Repository doesn't implement Send, which is required for
 /// async daemon operations. This approach allows full sync functionality.
-async fn perform_sync() -> std::result::Result<(), Box<dyn std::error::Error + Send + Sync>> {
+async fn perform_sync(
+    schedule: &Schedule,
+) -> std::result::Result<(), Box<dyn std::error::Error + Send + Sync>> {
     debug!("Performing background sync...");
 
+    // Get the current executable path to invoke multigit CLI
+    let current_exe = std::env::current_exe()
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+    sync_cycle(schedule, true, |remote| {
+        let current_exe = current_exe.clone();
+        async move { sync_remote(&current_exe, &remote).await }
+    })
+    .await
+}
+
+/// One daemon run: sync every enabled remote that isn't backed off with
+/// `sync`, one after the other, then record the results in the daemon state
+///
+/// `schedule` sets the backoff base; with `scheduled` it also gives the next
+/// run time, which is left unset for runs triggered from outside.
+pub async fn sync_cycle<F, Fut>(
+    schedule: &Schedule,
+    scheduled: bool,
+    mut sync: F,
+) -> std::result::Result<(), Box<dyn std::error::Error + Send + Sync>>
+where
+    F: FnMut(String) -> Fut,
+    Fut: std::future::Future<Output = std::result::Result<(), String>>,
+{
     // Load config
     let config =
         Config::load().map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+    let next_run = scheduled.then_some(schedule);
 
     // Get enabled remotes
-    let enabled: Vec<String> = config.enabled_remotes().keys().cloned().collect();
+    let mut enabled: Vec<String> = config.enabled_remotes().keys().cloned().collect();
     if enabled.is_empty() {
         info!("[Daemon] No remotes configured");
         return Ok(());
     }
+    enabled.sort();
+
+    let mut state = DaemonState::load();
+    let now = Utc::now();
+
+    for (remote, token) in config.expiring_tokens(now) {
+        warn!(
+            "[Daemon] Token for '{}' {}; renew it and run 'multigit remote update {}'",
+            remote,
+            token.describe_expiry(now),
+            remote
+        );
+    }
+    // Failing remotes back off in multiples of the normal interval
+    let base_seconds = schedule.interval_seconds();
+
+    let (due, skipped): (Vec<String>, Vec<String>) = enabled
+        .into_iter()
+        .partition(|remote| state.backoff.should_sync(remote, now));
+
+    for remote in &skipped {
+        debug!("[Daemon] Skipping backed-off remote: {}", remote);
+    }
+
+    if due.is_empty() {
+        info!("[Daemon] All remotes are backed off, skipping this run");
+        save_state(&mut state, next_run);
+        return Ok(());
+    }
 
     info!(
-        "[Daemon] Starting sync with {} enabled remotes: {:?}",
-        enabled.len(),
-        enabled
+        "[Daemon] Starting sync with {} remotes: {:?}",
+        due.len(),
+        due
     );
 
-    // Get the current executable path to invoke multigit CLI
-    let current_exe = std::env::current_exe()
-        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+    let mut failed = Vec::new();
+    for remote in &due {
+        match sync(remote.clone()).await {
+            Ok(()) => {
+                if state.backoff.remotes.contains_key(remote) {
+                    info!("[Daemon] Remote '{}' recovered", remote);
+                }
+                state.backoff.record_success(remote);
+                state.record_result(remote, true, None, Utc::now());
+            }
+            Err(message) => {
+                state.record_result(remote, false, Some(message.clone()), Utc::now());
+                let delay = state.backoff.record_failure(
+                    remote,
+                    message.clone(),
+                    now,
+                    base_seconds,
+                    config.daemon.max_backoff_seconds,
+                );
+                warn!(
+                    "[Daemon] Sync with '{}' failed: {} (next attempt in {}s)",
+                    remote, message, delay
+                );
+                failed.push(remote.clone());
+            }
+        }
+    }
+
+    save_state(&mut state, next_run);
+
+    if failed.is_empty() {
+        info!("[Daemon] Sync completed successfully");
+        Ok(())
+    } else {
+        Err(Box::new(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("Sync failed for: {}", failed.join(", ")),
+        )) as Box<dyn std::error::Error + Send + Sync>)
+    }
+}
+
+/// Stamp the finished run and persist the daemon state; `schedule` gives the
+/// next run, if there is one
+fn save_state(state: &mut DaemonState, schedule: Option<&Schedule>) {
+    let now = Utc::now();
+    state.last_sync = Some(now);
+    state.next_run = schedule.and_then(|schedule| schedule.next_run_after(&now));
+
+    if let Err(e) = state.save() {
+        warn!("[Daemon] Failed to save daemon state: {}", e);
+    }
+}
 
-    // Run multigit sync command (no --no-interaction flag needed)
-    let output = tokio::process::Command::new(&current_exe)
-        .args(["sync"])
+/// Run `multigit sync --remotes <remote>`, returning the redacted error output on failure
+///
+/// The sync waits for a manual push or pull holding the repository lock
+/// rather than failing the run.
+async fn sync_remote(current_exe: &Path, remote: &str) -> std::result::Result<(), String> {
+    let lock_timeout = LOCK_TIMEOUT_SECS.to_string();
+    let output = tokio::process::Command::new(current_exe)
+        .args(["--lock-timeout", &lock_timeout, "sync", "--remotes", remote])
         .current_dir(".")
         .output()
         .await
-        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+        .map_err(|e| e.to_string())?;
 
     if output.status.success() {
-        info!("[Daemon] Sync completed successfully");
         // Log stdout if available (with secret redaction)
         if !output.stdout.is_empty() {
             let stdout = String::from_utf8_lossy(&output.stdout);
-            let redacted_stdout = redact(&stdout);
-            debug!("[Daemon] Sync output: {}", redacted_stdout);
+            debug!("[Daemon] Sync output for '{}': {}", remote, redact(&stdout));
         }
+        Ok(())
     } else {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        let redacted_stderr = redact(&stderr);
-        warn!("[Daemon] Sync failed: {}", redacted_stderr);
-        return Err(Box::new(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            format!("Sync command failed: {redacted_stderr}"),
-        )) as Box<dyn std::error::Error + Send + Sync>);
+        Err(redact(stderr.trim()))
     }
-
-    Ok(())
 }
 
 impl Drop for DaemonService {
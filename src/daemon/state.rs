@@ -0,0 +1,152 @@
+//! Persisted daemon run state
+//!
+//! After each scheduled run the daemon writes `daemon_status.json` to the
+//! state directory with the last sync time, the next scheduled run, the
+//! result for each remote, and the backoff state of failing remotes.
+//! `daemon status` reads it back, since the daemon runs in another process.
+
+use crate::daemon::backoff::BackoffTracker;
+use crate::utils::atomic_write::write_atomic;
+use crate::utils::paths;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Outcome of the last sync attempt with one remote
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteRunResult {
+    /// Whether the sync succeeded
+    pub success: bool,
+    /// Error message for failed syncs
+    pub message: Option<String>,
+    /// When the attempt finished
+    pub finished_at: DateTime<Utc>,
+}
+
+/// State the daemon persists between runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DaemonState {
+    /// Human-readable schedule (e.g. `every 300s`)
+    pub schedule: Option<String>,
+    /// When the daemon process started
+    pub started_at: Option<DateTime<Utc>>,
+    /// When the last scheduled run finished
+    pub last_sync: Option<DateTime<Utc>>,
+    /// When the next run is scheduled
+    pub next_run: Option<DateTime<Utc>>,
+    /// Last result per remote (backed-off remotes keep their previous result)
+    #[serde(default)]
+    pub remotes: BTreeMap<String, RemoteRunResult>,
+    /// Backoff state of failing remotes
+    #[serde(default)]
+    pub backoff: BackoffTracker,
+}
+
+impl DaemonState {
+    /// Default location of the persisted state
+    #[must_use]
+    pub fn default_path() -> PathBuf {
+        paths::state_dir().join("daemon_status.json")
+    }
+
+    /// Load the state from the default location
+    #[must_use]
+    pub fn load() -> Self {
+        Self::load_from(&Self::default_path())
+    }
+
+    /// Load the state from `path`, starting fresh if missing or unreadable
+    #[must_use]
+    pub fn load_from(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Persist the state to the default location
+    pub fn save(&self) -> io::Result<()> {
+        self.save_to(&Self::default_path())
+    }
+
+    /// Persist the state to `path`
+    pub fn save_to(&self, path: &Path) -> io::Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        write_atomic(path, content)
+    }
+
+    /// Record the result of syncing `remote`
+    pub fn record_result(
+        &mut self,
+        remote: &str,
+        success: bool,
+        message: Option<String>,
+        now: DateTime<Utc>,
+    ) {
+        self.remotes.insert(
+            remote.to_string(),
+            RemoteRunResult {
+                success,
+                message,
+                finished_at: now,
+            },
+        );
+    }
+
+    /// Seconds since the last sync, if any
+    #[must_use]
+    pub fn seconds_since_last_sync(&self, now: DateTime<Utc>) -> Option<i64> {
+        self.last_sync.map(|last| (now - last).num_seconds().max(0))
+    }
+
+    /// Seconds until the next scheduled run, if known
+    #[must_use]
+    pub fn seconds_until_next_run(&self, now: DateTime<Utc>) -> Option<i64> {
+        self.next_run.map(|next| (next - now).num_seconds().max(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_state_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("daemon_status.json");
+        let now = Utc::now();
+
+        let mut state = DaemonState {
+            schedule: Some("every 300s".to_string()),
+            last_sync: Some(now),
+            next_run: Some(now + Duration::seconds(300)),
+            ..DaemonState::default()
+        };
+        state.record_result("github", true, None, now);
+        state.record_result("gitea", false, Some("timeout".to_string()), now);
+        state
+            .backoff
+            .record_failure("gitea", "timeout", now, 300, 3600);
+        state.save_to(&path).unwrap();
+
+        let loaded = DaemonState::load_from(&path);
+        assert_eq!(loaded.schedule.as_deref(), Some("every 300s"));
+        assert!(loaded.remotes["github"].success);
+        assert_eq!(loaded.remotes["gitea"].message.as_deref(), Some("timeout"));
+        assert_eq!(loaded.backoff.remotes["gitea"].consecutive_failures, 1);
+        assert_eq!(loaded.seconds_until_next_run(now), Some(300));
+    }
+
+    #[test]
+    fn test_missing_state_is_default() {
+        let temp = TempDir::new().unwrap();
+        let state = DaemonState::load_from(&temp.path().join("missing.json"));
+        assert!(state.last_sync.is_none());
+        assert!(state.remotes.is_empty());
+    }
+}
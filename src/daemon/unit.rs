@@ -0,0 +1,520 @@
+//! Service units for running the daemon from the OS scheduler
+//!
+//! Instead of keeping `multigit daemon start` running, the OS can run
+//! `multigit daemon run-once` on a timer: a systemd user timer on Linux, a
+//! launchd agent on macOS, or a scheduled task on Windows. Units are named
+//! after the repository directory, so each repository gets its own.
+//!
+//! Generation is pure; [`ServicePlan`] lists the files to write and the
+//! commands that enable or disable the unit, for the caller to carry out or
+//! print.
+
+use crate::daemon::scheduler::Schedule;
+use crate::utils::error::{MultiGitError, Result};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Which OS scheduler runs the unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceManager {
+    /// A systemd user service and timer
+    Systemd,
+    /// A launchd user agent
+    Launchd,
+    /// A Windows scheduled task
+    TaskScheduler,
+}
+
+impl ServiceManager {
+    /// The scheduler of the OS this was built for
+    #[must_use]
+    pub fn current() -> Self {
+        if cfg!(target_os = "macos") {
+            Self::Launchd
+        } else if cfg!(windows) {
+            Self::TaskScheduler
+        } else {
+            Self::Systemd
+        }
+    }
+}
+
+impl fmt::Display for ServiceManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Systemd => write!(f, "systemd"),
+            Self::Launchd => write!(f, "launchd"),
+            Self::TaskScheduler => write!(f, "Task Scheduler"),
+        }
+    }
+}
+
+/// A file a unit is made of
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitFile {
+    /// Where it is installed
+    pub path: PathBuf,
+    /// What it contains
+    pub contents: String,
+}
+
+/// Everything needed to install or remove one repository's unit
+#[derive(Debug, Clone)]
+pub struct ServicePlan {
+    /// The scheduler it is for
+    pub manager: ServiceManager,
+    /// Unit (or task) name
+    pub name: String,
+    /// Files to write on install and remove on uninstall
+    pub files: Vec<UnitFile>,
+    /// Commands to run after writing the files
+    pub install: Vec<Vec<String>>,
+    /// Commands to run before removing the files
+    pub uninstall: Vec<Vec<String>>,
+}
+
+/// Where units go: the systemd user unit directory and the home directory
+#[derive(Debug, Clone)]
+pub struct UnitDirs {
+    /// `~/.config/systemd/user`
+    pub systemd: PathBuf,
+    /// `~/Library/LaunchAgents`
+    pub launch_agents: PathBuf,
+}
+
+impl UnitDirs {
+    /// The current user's unit directories
+    pub fn for_user() -> Result<Self> {
+        let home = dirs::home_dir()
+            .ok_or_else(|| MultiGitError::daemon("Could not determine the home directory"))?;
+        let config = dirs::config_dir().unwrap_or_else(|| home.join(".config"));
+        Ok(Self {
+            systemd: config.join("systemd").join("user"),
+            launch_agents: home.join("Library").join("LaunchAgents"),
+        })
+    }
+}
+
+/// The unit name for the repository at `workdir`, e.g.
+/// `multigit-sync-myapp-1a2b3c4d`
+///
+/// The suffix hashes the full path, so repositories with the same directory
+/// name in different places get their own units.
+#[must_use]
+pub fn unit_name(workdir: &Path) -> String {
+    let full = std::fs::canonicalize(workdir).unwrap_or_else(|_| workdir.to_path_buf());
+    let hash = git2::Oid::hash_object(git2::ObjectType::Blob, full.to_string_lossy().as_bytes())
+        .map(|oid| oid.to_string()[..8].to_string())
+        .unwrap_or_default();
+    let repo = workdir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let repo: String = repo
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    [repo, hash]
+        .iter()
+        .filter(|part| !part.is_empty())
+        .fold("multigit-sync".to_string(), |name, part| {
+            format!("{name}-{part}")
+        })
+}
+
+/// Plan a unit running `exe daemon run-once` in `workdir` on `schedule`
+pub fn plan(
+    manager: ServiceManager,
+    exe: &Path,
+    workdir: &Path,
+    schedule: &Schedule,
+    dirs: &UnitDirs,
+) -> Result<ServicePlan> {
+    let name = unit_name(workdir);
+    match manager {
+        ServiceManager::Systemd => systemd(name, exe, workdir, schedule, dirs),
+        ServiceManager::Launchd => launchd(name, exe, workdir, schedule, dirs),
+        ServiceManager::TaskScheduler => task_scheduler(name, exe, workdir, schedule),
+    }
+}
+
+fn systemd(
+    name: String,
+    exe: &Path,
+    workdir: &Path,
+    schedule: &Schedule,
+    dirs: &UnitDirs,
+) -> Result<ServicePlan> {
+    let trigger = match schedule {
+        Schedule::Interval { seconds } => {
+            format!("OnActiveSec=1min\nOnUnitActiveSec={}s", (*seconds).max(1))
+        }
+        Schedule::Cron { expression, .. } => format!(
+            "OnCalendar={}\nPersistent=true",
+            cron_to_on_calendar(expression)?
+        ),
+    };
+    let service = format!(
+        "[Unit]\n\
+         Description=MultiGit sync for {workdir}\n\
+         \n\
+         [Service]\n\
+         Type=oneshot\n\
+         WorkingDirectory={workdir}\n\
+         ExecStart=\"{exe}\" daemon run-once\n",
+        workdir = workdir.display(),
+        exe = exe.display(),
+    );
+    let timer = format!(
+        "[Unit]\n\
+         Description=MultiGit sync for {workdir} ({schedule})\n\
+         \n\
+         [Timer]\n\
+         {trigger}\n\
+         \n\
+         [Install]\n\
+         WantedBy=timers.target\n",
+        workdir = workdir.display(),
+    );
+    let systemctl = |args: &[&str]| {
+        std::iter::once("systemctl")
+            .chain(std::iter::once("--user"))
+            .chain(args.iter().copied())
+            .map(String::from)
+            .collect::<Vec<_>>()
+    };
+    let timer_unit = format!("{name}.timer");
+
+    Ok(ServicePlan {
+        manager: ServiceManager::Systemd,
+        files: vec![
+            UnitFile {
+                path: dirs.systemd.join(format!("{name}.service")),
+                contents: service,
+            },
+            UnitFile {
+                path: dirs.systemd.join(&timer_unit),
+                contents: timer,
+            },
+        ],
+        install: vec![
+            systemctl(&["daemon-reload"]),
+            systemctl(&["enable", "--now", &timer_unit]),
+        ],
+        uninstall: vec![systemctl(&["disable", "--now", &timer_unit])],
+        name,
+    })
+}
+
+fn launchd(
+    name: String,
+    exe: &Path,
+    workdir: &Path,
+    schedule: &Schedule,
+    dirs: &UnitDirs,
+) -> Result<ServicePlan> {
+    let Schedule::Interval { seconds } = schedule else {
+        return Err(MultiGitError::invalid_input(
+            "launchd units only support interval schedules; use --interval",
+        ));
+    };
+    let label = format!("dev.multigit.{name}");
+    let plist = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \
+         \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+         <plist version=\"1.0\">\n\
+         <dict>\n\
+         \x20   <key>Label</key>\n\
+         \x20   <string>{label}</string>\n\
+         \x20   <key>ProgramArguments</key>\n\
+         \x20   <array>\n\
+         \x20       <string>{exe}</string>\n\
+         \x20       <string>daemon</string>\n\
+         \x20       <string>run-once</string>\n\
+         \x20   </array>\n\
+         \x20   <key>WorkingDirectory</key>\n\
+         \x20   <string>{workdir}</string>\n\
+         \x20   <key>StartInterval</key>\n\
+         \x20   <integer>{seconds}</integer>\n\
+         \x20   <key>RunAtLoad</key>\n\
+         \x20   <true/>\n\
+         </dict>\n\
+         </plist>\n",
+        exe = xml_escape(&exe.display().to_string()),
+        workdir = xml_escape(&workdir.display().to_string()),
+        seconds = (*seconds).max(1),
+    );
+    let path = dirs.launch_agents.join(format!("{label}.plist"));
+    let launchctl = |action: &str| {
+        vec![
+            "launchctl".to_string(),
+            action.to_string(),
+            "-w".to_string(),
+            path.display().to_string(),
+        ]
+    };
+
+    Ok(ServicePlan {
+        manager: ServiceManager::Launchd,
+        install: vec![launchctl("load")],
+        uninstall: vec![launchctl("unload")],
+        files: vec![UnitFile {
+            path: path.clone(),
+            contents: plist,
+        }],
+        name,
+    })
+}
+
+fn task_scheduler(
+    name: String,
+    exe: &Path,
+    workdir: &Path,
+    schedule: &Schedule,
+) -> Result<ServicePlan> {
+    let Schedule::Interval { seconds } = schedule else {
+        return Err(MultiGitError::invalid_input(
+            "scheduled tasks only support interval schedules; use --interval",
+        ));
+    };
+    // schtasks repeats every 1 to 1439 minutes
+    let minutes = seconds.saturating_add(59) / 60;
+    let minutes = minutes.clamp(1, 1439);
+    let command = format!(
+        "cmd /c cd /d \"{}\" && \"{}\" daemon run-once",
+        workdir.display(),
+        exe.display()
+    );
+
+    Ok(ServicePlan {
+        manager: ServiceManager::TaskScheduler,
+        files: Vec::new(),
+        install: vec![[
+            "schtasks",
+            "/Create",
+            "/F",
+            "/TN",
+            &name,
+            "/SC",
+            "MINUTE",
+            "/MO",
+            &minutes.to_string(),
+            "/TR",
+            &command,
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect()],
+        uninstall: vec![["schtasks", "/Delete", "/F", "/TN", &name]
+            .iter()
+            .map(ToString::to_string)
+            .collect()],
+        name,
+    })
+}
+
+/// Translate a 5- or 6-field cron expression into a systemd `OnCalendar`
+/// value, e.g. `0 2,14 * * 1-5` into `Mon..Fri *-*-* 02,14:00:00`
+pub fn cron_to_on_calendar(expression: &str) -> Result<String> {
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+    let (second, rest) = match fields.len() {
+        5 => ("0", &fields[..]),
+        6 => (fields[0], &fields[1..]),
+        _ => {
+            return Err(MultiGitError::invalid_input(format!(
+                "Can't convert cron expression '{expression}' for systemd; \
+                 use 5 fields (or 6 with seconds)"
+            )))
+        }
+    };
+    let invalid = |field: &str| {
+        MultiGitError::invalid_input(format!(
+            "Can't convert cron field '{field}' of '{expression}' for systemd"
+        ))
+    };
+    let convert = |field: &str, pad: bool| -> Result<String> {
+        field
+            .split(',')
+            .map(|part| {
+                if part == "*" {
+                    return Ok("*".to_string());
+                }
+                if let Some(step) = part.strip_prefix("*/") {
+                    return step
+                        .parse::<u32>()
+                        .map(|step| format!("0/{step}"))
+                        .map_err(|_| invalid(field));
+                }
+                let number = |value: &str| {
+                    value
+                        .parse::<u32>()
+                        .map(|n| {
+                            if pad {
+                                format!("{n:02}")
+                            } else {
+                                n.to_string()
+                            }
+                        })
+                        .map_err(|_| invalid(field))
+                };
+                match part.split_once('-') {
+                    Some((from, to)) => Ok(format!("{}..{}", number(from)?, number(to)?)),
+                    None => number(part),
+                }
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(|parts| parts.join(","))
+    };
+
+    let weekday = |field: &str| -> Result<String> {
+        const DAYS: [&str; 8] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+        let day = |value: &str| -> Result<String> {
+            if let Ok(n) = value.parse::<usize>() {
+                return DAYS
+                    .get(n)
+                    .map(ToString::to_string)
+                    .ok_or_else(|| invalid(field));
+            }
+            DAYS.iter()
+                .find(|day| day.eq_ignore_ascii_case(value))
+                .map(ToString::to_string)
+                .ok_or_else(|| invalid(field))
+        };
+        field
+            .split(',')
+            .map(|part| match part.split_once('-') {
+                Some((from, to)) => Ok(format!("{}..{}", day(from)?, day(to)?)),
+                None => day(part),
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(|parts| parts.join(","))
+    };
+
+    let [minute, hour, day, month, dow] = [rest[0], rest[1], rest[2], rest[3], rest[4]];
+    let date = format!("*-{}-{}", convert(month, true)?, convert(day, true)?);
+    let time = format!(
+        "{}:{}:{}",
+        convert(hour, true)?,
+        convert(minute, true)?,
+        convert(second, true)?
+    );
+    if dow == "*" || dow == "?" {
+        Ok(format!("{date} {time}"))
+    } else {
+        Ok(format!("{} {date} {time}", weekday(dow)?))
+    }
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dirs() -> UnitDirs {
+        UnitDirs {
+            systemd: PathBuf::from("/home/me/.config/systemd/user"),
+            launch_agents: PathBuf::from("/Users/me/Library/LaunchAgents"),
+        }
+    }
+
+    #[test]
+    fn test_cron_to_on_calendar() {
+        assert_eq!(
+            cron_to_on_calendar("0 2,14 * * *").unwrap(),
+            "*-*-* 02,14:00:00"
+        );
+        assert_eq!(
+            cron_to_on_calendar("*/15 9-17 * * 1-5").unwrap(),
+            "Mon..Fri *-*-* 09..17:0/15:00"
+        );
+        assert_eq!(
+            cron_to_on_calendar("30 0 3 1 * sun").unwrap(),
+            "Sun *-*-01 03:00:30"
+        );
+        assert!(cron_to_on_calendar("0 2 * * * * 2030").is_err());
+        assert!(cron_to_on_calendar("0 2 L * *").is_err());
+    }
+
+    #[test]
+    fn test_systemd_timer() {
+        let exe = Path::new("/usr/bin/multigit");
+        let workdir = Path::new("/src/my app");
+        assert_eq!(unit_name(workdir), "multigit-sync-my-app-eb8c87b9");
+        assert_ne!(unit_name(workdir), unit_name(Path::new("/oss/my app")));
+
+        let plan = plan(
+            ServiceManager::Systemd,
+            exe,
+            workdir,
+            &Schedule::every_minutes(10),
+            &dirs(),
+        )
+        .unwrap();
+        assert_eq!(
+            plan.files[0].path,
+            Path::new("/home/me/.config/systemd/user/multigit-sync-my-app-eb8c87b9.service")
+        );
+        assert!(plan.files[0]
+            .contents
+            .contains("ExecStart=\"/usr/bin/multigit\" daemon run-once"));
+        assert!(plan.files[1].contents.contains("OnUnitActiveSec=600s"));
+        assert_eq!(
+            plan.install[1],
+            vec![
+                "systemctl",
+                "--user",
+                "enable",
+                "--now",
+                "multigit-sync-my-app-eb8c87b9.timer"
+            ]
+        );
+
+        let cron = Schedule::from_cron("0 2 * * *").unwrap();
+        let plan = super::plan(ServiceManager::Systemd, exe, workdir, &cron, &dirs()).unwrap();
+        assert!(plan.files[1].contents.contains("OnCalendar=*-*-* 02:00:00"));
+    }
+
+    #[test]
+    fn test_launchd_and_task_scheduler() {
+        let exe = Path::new("/usr/local/bin/multigit");
+        let workdir = Path::new("/src/app");
+        let every_5 = Schedule::every_minutes(5);
+
+        let plan = plan(ServiceManager::Launchd, exe, workdir, &every_5, &dirs()).unwrap();
+        assert_eq!(
+            plan.files[0].path,
+            Path::new(
+                "/Users/me/Library/LaunchAgents/dev.multigit.multigit-sync-app-3c0db052.plist"
+            )
+        );
+        assert!(plan.files[0].contents.contains("<integer>300</integer>"));
+
+        let plan = super::plan(
+            ServiceManager::TaskScheduler,
+            exe,
+            workdir,
+            &every_5,
+            &dirs(),
+        )
+        .unwrap();
+        assert!(plan.files.is_empty());
+        assert!(plan.install[0].contains(&"5".to_string()));
+
+        let cron = Schedule::from_cron("0 2 * * *").unwrap();
+        assert!(super::plan(ServiceManager::Launchd, exe, workdir, &cron, &dirs()).is_err());
+    }
+}
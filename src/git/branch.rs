@@ -119,6 +119,131 @@ impl<'repo> BranchManager<'repo> {
         }
     }
 
+    /// Branches that no longer exist, newest first, as last seen in the HEAD reflog
+    ///
+    /// Deleting a branch removes its own reflog, but HEAD's reflog still
+    /// records where each branch pointed when it was checked out or left.
+    /// Tips whose commit has since been garbage-collected are skipped.
+    pub fn deleted(&self) -> Result<Vec<DeletedBranch>> {
+        let reflog = match self.repo.reflog("HEAD") {
+            Ok(reflog) => reflog,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(MultiGitError::GitError(e)),
+        };
+
+        let mut deleted: Vec<DeletedBranch> = Vec::new();
+        let mut seen = Vec::new();
+        for entry in reflog.iter() {
+            let message = entry.message().unwrap_or_default();
+            for (name, target) in reflog_branch_tips(message, entry.id_old(), entry.id_new()) {
+                if seen.contains(&name) {
+                    continue;
+                }
+                seen.push(name.clone());
+                if target.is_zero()
+                    || !Branch::name_is_valid(&name).unwrap_or(false)
+                    || self.exists(&name)?
+                    || self.repo.find_commit(target).is_err()
+                {
+                    continue;
+                }
+                deleted.push(DeletedBranch {
+                    name,
+                    target,
+                    seen_at: entry.committer().when().seconds(),
+                });
+            }
+        }
+
+        debug!("Found {} deleted branch(es) in the reflog", deleted.len());
+        Ok(deleted)
+    }
+
+    /// Existing local branches, most recently checked out first, as the HEAD
+    /// reflog records them
+    ///
+    /// Branches that were never checked out (or whose reflog entries have
+    /// expired) are not included.
+    pub fn recently_used(&self) -> Result<Vec<String>> {
+        let reflog = match self.repo.reflog("HEAD") {
+            Ok(reflog) => reflog,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(MultiGitError::GitError(e)),
+        };
+
+        let mut recent: Vec<String> = Vec::new();
+        for entry in reflog.iter() {
+            let message = entry.message().unwrap_or_default();
+            // The branch moved to is more recent than the one moved from
+            for (name, _) in reflog_branch_tips(message, entry.id_old(), entry.id_new())
+                .into_iter()
+                .rev()
+            {
+                if !recent.contains(&name)
+                    && Branch::name_is_valid(&name).unwrap_or(false)
+                    && self.exists(&name)?
+                {
+                    recent.push(name);
+                }
+            }
+        }
+        Ok(recent)
+    }
+
+    /// What was checked out before the current branch (`@{-1}`), as the HEAD
+    /// reflog records it
+    ///
+    /// This may be a branch that has since been deleted, or a commit id when
+    /// HEAD was detached.
+    pub fn previous(&self) -> Result<Option<String>> {
+        let reflog = match self.repo.reflog("HEAD") {
+            Ok(reflog) => reflog,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(None),
+            Err(e) => return Err(MultiGitError::GitError(e)),
+        };
+        Ok(reflog.iter().find_map(|entry| {
+            entry
+                .message()
+                .and_then(|message| message.strip_prefix("checkout: moving from "))
+                .and_then(|rest| rest.split_once(" to "))
+                .map(|(from, _)| from.to_string())
+        }))
+    }
+
+    /// Fail with a "did you mean" hint unless local branch `name` exists
+    pub fn require(&self, name: &str) -> Result<()> {
+        if self.exists(name)? {
+            return Ok(());
+        }
+        let names: Vec<String> = self.list_local()?.into_iter().map(|b| b.name).collect();
+        let message = match closest(name, names.iter().map(String::as_str)) {
+            Some(suggestion) => format!("Branch '{name}' not found. Did you mean '{suggestion}'?"),
+            None => format!("Branch '{name}' not found"),
+        };
+        Err(MultiGitError::invalid_input(message))
+    }
+
+    /// Recreate the deleted branch `name` at its last known tip
+    pub fn recover(&self, name: &str) -> Result<DeletedBranch> {
+        if self.exists(name)? {
+            return Err(MultiGitError::invalid_input(format!(
+                "Branch '{name}' already exists"
+            )));
+        }
+
+        let branch = self
+            .deleted()?
+            .into_iter()
+            .find(|branch| branch.name == name)
+            .ok_or_else(|| {
+                MultiGitError::invalid_input(format!(
+                    "No trace of branch '{name}' in the reflog; it may never have been checked out"
+                ))
+            })?;
+        self.create(name, Some(branch.target))?;
+        Ok(branch)
+    }
+
     /// Rename a branch
     pub fn rename(&self, old_name: &str, new_name: &str, force: bool) -> Result<()> {
         info!("Renaming branch '{}' to '{}'", old_name, new_name);
@@ -233,6 +358,34 @@ impl<'repo> BranchManager<'repo> {
         }
     }
 
+    /// Make local branch `name` track its copy on `remote`, as `git push -u` does
+    ///
+    /// The remote-tracking ref is created at the local tip when the push did
+    /// not write one (e.g. the remote has no fetch refspec).
+    pub fn set_upstream(&self, name: &str, remote: &str) -> Result<()> {
+        let mut branch = self
+            .repo
+            .find_branch(name, BranchType::Local)
+            .map_err(|_| MultiGitError::Other(format!("Branch '{name}' not found")))?;
+
+        let tracking = format!("refs/remotes/{remote}/{name}");
+        if self.repo.find_reference(&tracking).is_err() {
+            let tip = branch
+                .get()
+                .target()
+                .ok_or_else(|| MultiGitError::Other(format!("Branch '{name}' has no target")))?;
+            self.repo
+                .reference(&tracking, tip, false, "multigit: push -u")
+                .map_err(MultiGitError::GitError)?;
+        }
+
+        branch
+            .set_upstream(Some(&format!("{remote}/{name}")))
+            .map_err(MultiGitError::GitError)?;
+        info!("Branch '{}' now tracks {}/{}", name, remote, name);
+        Ok(())
+    }
+
     /// Helper to convert a `git2::Branch` to `BranchInfo`
     fn branch_to_info(&self, branch: Branch) -> Result<Option<BranchInfo>> {
         let name = match branch.name().map_err(MultiGitError::GitError)? {
@@ -275,6 +428,66 @@ pub struct BranchInfo {
     pub upstream: Option<String>,
 }
 
+/// A deleted branch and where it last pointed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeletedBranch {
+    /// Branch name
+    pub name: String,
+    /// Last known tip
+    pub target: Oid,
+    /// When the reflog entry was written, in seconds since the epoch
+    pub seen_at: i64,
+}
+
+/// The candidate closest to `name` by edit distance, if close enough to be
+/// a likely typo
+pub fn closest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(2);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between `a` and `b`, in characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Branches named by a HEAD reflog message, with the commit each pointed to
+///
+/// Checkouts of a detached HEAD name a commit id rather than a branch; those
+/// are left out.
+fn reflog_branch_tips(message: &str, old: Oid, new: Oid) -> Vec<(String, Oid)> {
+    let mut tips = Vec::new();
+    if let Some(rest) = message.strip_prefix("checkout: moving from ") {
+        if let Some((from, to)) = rest.split_once(" to ") {
+            tips = vec![(from.to_string(), old), (to.to_string(), new)];
+        }
+    } else if let Some(rest) = message.strip_prefix("rebase (finish): returning to refs/heads/") {
+        tips = vec![(rest.to_string(), new)];
+    }
+    tips.retain(|(name, _)| !is_commit_id(name));
+    tips
+}
+
+/// Whether `name` is a full commit id, as a detached HEAD is recorded
+fn is_commit_id(name: &str) -> bool {
+    Oid::from_str(name).is_ok_and(|oid| oid.to_string() == name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -341,4 +554,115 @@ mod tests {
         let current = manager.current().unwrap();
         assert!(!current.is_empty());
     }
+
+    #[test]
+    fn test_set_upstream() {
+        let (_temp_dir, repo) = create_test_repo();
+        let manager = BranchManager::new(&repo);
+        let current = manager.current().unwrap();
+        repo.remote("origin", "https://example.com/repo.git")
+            .unwrap();
+
+        assert_eq!(manager.get_upstream(&current).unwrap(), None);
+        manager.set_upstream(&current, "origin").unwrap();
+        assert_eq!(
+            manager.get_upstream(&current).unwrap(),
+            Some(format!("origin/{current}"))
+        );
+    }
+
+    #[test]
+    fn test_reflog_branch_tips() {
+        let old = Oid::from_str("1111111111111111111111111111111111111111").unwrap();
+        let new = Oid::from_str("2222222222222222222222222222222222222222").unwrap();
+
+        assert_eq!(
+            reflog_branch_tips("checkout: moving from feature to main", old, new),
+            vec![("feature".to_string(), old), ("main".to_string(), new)]
+        );
+        assert_eq!(
+            reflog_branch_tips("rebase (finish): returning to refs/heads/topic", old, new),
+            vec![("topic".to_string(), new)]
+        );
+        assert!(reflog_branch_tips("commit: Add feature", old, new).is_empty());
+
+        let detached = format!("checkout: moving from feature to {new}");
+        assert_eq!(
+            reflog_branch_tips(&detached, old, new),
+            vec![("feature".to_string(), old)]
+        );
+        let reattached = format!("checkout: moving from {old} to main");
+        assert_eq!(
+            reflog_branch_tips(&reattached, old, new),
+            vec![("main".to_string(), new)]
+        );
+        assert!(!is_commit_id("cafe"));
+    }
+
+    #[test]
+    fn test_recover_deleted_branch() {
+        let (_temp_dir, repo) = create_test_repo();
+        let manager = BranchManager::new(&repo);
+        let main = manager.current().unwrap();
+
+        manager.create("feature", None).unwrap();
+        manager.checkout("feature").unwrap();
+        let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let tip = repo
+            .commit(
+                Some("HEAD"),
+                &sig,
+                &sig,
+                "Work on feature",
+                &head.tree().unwrap(),
+                &[&head],
+            )
+            .unwrap();
+        manager.checkout(&main).unwrap();
+        manager.delete("feature").unwrap();
+
+        let deleted = manager.deleted().unwrap();
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0].name, "feature");
+        assert_eq!(deleted[0].target, tip);
+
+        manager.recover("feature").unwrap();
+        assert_eq!(manager.get_info("feature").unwrap().target, Some(tip));
+        assert!(manager.deleted().unwrap().is_empty());
+        assert!(manager.recover("feature").is_err());
+    }
+
+    #[test]
+    fn test_closest_branch() {
+        let names = ["main", "feature/login", "release-1.0"];
+        assert_eq!(closest("mian", names), Some("main"));
+        assert_eq!(closest("feature/logn", names), Some("feature/login"));
+        assert_eq!(closest("hotfix", names), None);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_recently_used_and_require() {
+        let (_temp_dir, repo) = create_test_repo();
+        let manager = BranchManager::new(&repo);
+        let main = manager.current().unwrap();
+
+        manager.create("feature", None).unwrap();
+        manager.create("docs", None).unwrap();
+        manager.create("never-used", None).unwrap();
+        manager.checkout("feature").unwrap();
+        manager.checkout("docs").unwrap();
+        manager.checkout(&main).unwrap();
+
+        assert_eq!(manager.previous().unwrap().as_deref(), Some("docs"));
+        assert_eq!(
+            manager.recently_used().unwrap(),
+            vec![main, "docs".to_string(), "feature".to_string()]
+        );
+
+        assert!(manager.require("docs").is_ok());
+        let err = manager.require("featrue").unwrap_err();
+        assert!(err.to_string().contains("Did you mean 'feature'?"), "{err}");
+    }
 }
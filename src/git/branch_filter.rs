@@ -0,0 +1,151 @@
+//! Glob-based branch selection
+//!
+//! Interprets the `include_branches` / `exclude_branches` sync settings as
+//! glob patterns: `*` matches within one path segment (`feature/*` matches
+//! `feature/login` but not `feature/ui/login`), `**` matches across segments
+//! (`release/**`). Exclusions take precedence over inclusions, and an empty
+//! include list selects every branch.
+
+use crate::models::SyncConfig;
+use crate::utils::error::{MultiGitError, Result};
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+
+/// Include/exclude filter for branch names
+#[derive(Debug, Clone)]
+pub struct BranchFilter {
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+}
+
+impl BranchFilter {
+    /// Build a filter from include and exclude glob patterns
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        Ok(Self {
+            include: build_set(include)?,
+            exclude: build_set(exclude)?,
+        })
+    }
+
+    /// Build a filter from the `[sync]` config section
+    pub fn from_sync_config(config: &SyncConfig) -> Result<Self> {
+        Self::new(&config.include_branches, &config.exclude_branches)
+    }
+
+    /// A filter that selects every branch
+    #[must_use]
+    pub fn allow_all() -> Self {
+        Self {
+            include: None,
+            exclude: None,
+        }
+    }
+
+    /// Check whether `branch` is selected
+    #[must_use]
+    pub fn matches(&self, branch: &str) -> bool {
+        if self
+            .exclude
+            .as_ref()
+            .is_some_and(|set| set.is_match(branch))
+        {
+            return false;
+        }
+
+        self.include
+            .as_ref()
+            .map_or(true, |set| set.is_match(branch))
+    }
+
+    /// Keep only the selected branches, preserving order
+    #[must_use]
+    pub fn filter<I, S>(&self, branches: I) -> Vec<S>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        branches
+            .into_iter()
+            .filter(|branch| self.matches(branch.as_ref()))
+            .collect()
+    }
+}
+
+impl Default for BranchFilter {
+    fn default() -> Self {
+        Self::allow_all()
+    }
+}
+
+fn build_set(patterns: &[String]) -> Result<Option<GlobSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(branch_glob(pattern)?);
+    }
+
+    builder
+        .build()
+        .map(Some)
+        .map_err(|e| MultiGitError::invalid_input(format!("Invalid branch patterns: {e}")))
+}
+
+/// Compile one branch glob, where `*` stays within a path segment
+pub fn branch_glob(pattern: &str) -> Result<Glob> {
+    GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()
+        .map_err(|e| {
+            MultiGitError::invalid_input(format!("Invalid branch pattern '{pattern}': {e}"))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(list: &[&str]) -> Vec<String> {
+        list.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_empty_filter_matches_everything() {
+        let filter = BranchFilter::new(&[], &[]).unwrap();
+        assert!(filter.matches("main"));
+        assert!(filter.matches("feature/login"));
+    }
+
+    #[test]
+    fn test_single_and_double_star() {
+        let filter = BranchFilter::new(&patterns(&["feature/*", "release/**"]), &[]).unwrap();
+
+        assert!(filter.matches("feature/login"));
+        assert!(!filter.matches("feature/ui/login"));
+        assert!(filter.matches("release/1.0"));
+        assert!(filter.matches("release/1.x/hotfix"));
+        assert!(!filter.matches("main"));
+    }
+
+    #[test]
+    fn test_exclude_takes_precedence() {
+        let filter = BranchFilter::new(
+            &patterns(&["feature/**", "main"]),
+            &patterns(&["feature/wip-*"]),
+        )
+        .unwrap();
+
+        assert!(filter.matches("main"));
+        assert!(filter.matches("feature/login"));
+        assert!(!filter.matches("feature/wip-refactor"));
+
+        let selected = filter.filter(vec!["main", "feature/wip-x", "feature/a", "dev"]);
+        assert_eq!(selected, vec!["main", "feature/a"]);
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        assert!(BranchFilter::new(&patterns(&["feature/[a"]), &[]).is_err());
+    }
+}
@@ -0,0 +1,322 @@
+//! Conflict marker parsing
+//!
+//! Splits a file left behind by a failed merge into the text git merged
+//! cleanly and the conflicted regions between `<<<<<<<`, `|||||||` (diff3
+//! style), `=======` and `>>>>>>>` markers, so each region ("hunk") can be
+//! resolved on its own and the file reassembled.
+
+use crate::utils::error::{MultiGitError, Result};
+
+/// One side-by-side conflicted region
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictHunk {
+    /// Our side, between `<<<<<<<` and `|||||||`/`=======`
+    pub ours: String,
+    /// Common ancestor, present with `merge.conflictStyle = diff3`
+    pub base: Option<String>,
+    /// Their side, between `=======` and `>>>>>>>`
+    pub theirs: String,
+    /// Label after `<<<<<<<`, usually `HEAD`
+    pub ours_label: String,
+    /// Label after `>>>>>>>`, usually the merged branch
+    pub theirs_label: String,
+}
+
+/// How to resolve one hunk
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HunkChoice {
+    /// Not decided yet
+    #[default]
+    Unresolved,
+    /// Keep our side
+    Ours,
+    /// Keep their side
+    Theirs,
+    /// Keep our side followed by their side
+    Both,
+}
+
+impl ConflictHunk {
+    /// Text this hunk resolves to under `choice`, or `None` if unresolved
+    #[must_use]
+    pub fn resolve(&self, choice: HunkChoice) -> Option<String> {
+        match choice {
+            HunkChoice::Unresolved => None,
+            HunkChoice::Ours => Some(self.ours.clone()),
+            HunkChoice::Theirs => Some(self.theirs.clone()),
+            HunkChoice::Both => Some(format!("{}{}", self.ours, self.theirs)),
+        }
+    }
+
+    /// The hunk with its conflict markers, as git wrote it
+    #[must_use]
+    pub fn to_marked(&self) -> String {
+        let mut out = marker_line("<<<<<<<", &self.ours_label);
+        out.push_str(&self.ours);
+        if let Some(ref base) = self.base {
+            out.push_str("|||||||\n");
+            out.push_str(base);
+        }
+        out.push_str("=======\n");
+        out.push_str(&self.theirs);
+        out.push_str(&marker_line(">>>>>>>", &self.theirs_label));
+        out
+    }
+}
+
+/// Part of a conflicted file
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Clean(String),
+    Conflict(ConflictHunk),
+}
+
+/// A file with conflict markers, split into clean text and hunks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictedFile {
+    segments: Vec<Segment>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Clean,
+    Ours,
+    Base,
+    Theirs,
+}
+
+impl ConflictedFile {
+    /// Parse file content containing conflict markers
+    ///
+    /// Fails if a conflict region is left open or markers appear out of order.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut clean = String::new();
+        let mut hunk = empty_hunk();
+        let mut section = Section::Clean;
+
+        for (number, line) in content.split_inclusive('\n').enumerate() {
+            let marker_error = |marker: &str| {
+                MultiGitError::conflict(format!(
+                    "Unexpected '{marker}' marker on line {}",
+                    number + 1
+                ))
+            };
+
+            if let Some(label) = marker_label(line, "<<<<<<<") {
+                if section != Section::Clean {
+                    return Err(marker_error("<<<<<<<"));
+                }
+                if !clean.is_empty() {
+                    segments.push(Segment::Clean(std::mem::take(&mut clean)));
+                }
+                hunk.ours_label = label;
+                section = Section::Ours;
+            } else if marker_label(line, "|||||||").is_some() && section == Section::Ours {
+                hunk.base = Some(String::new());
+                section = Section::Base;
+            } else if is_separator(line) && matches!(section, Section::Ours | Section::Base) {
+                section = Section::Theirs;
+            } else if let Some(label) = marker_label(line, ">>>>>>>") {
+                if section != Section::Theirs {
+                    return Err(marker_error(">>>>>>>"));
+                }
+                hunk.theirs_label = label;
+                segments.push(Segment::Conflict(std::mem::replace(
+                    &mut hunk,
+                    empty_hunk(),
+                )));
+                section = Section::Clean;
+            } else {
+                match section {
+                    Section::Clean => clean.push_str(line),
+                    Section::Ours => hunk.ours.push_str(line),
+                    Section::Base => hunk.base.get_or_insert_with(String::new).push_str(line),
+                    Section::Theirs => hunk.theirs.push_str(line),
+                }
+            }
+        }
+
+        if section != Section::Clean {
+            return Err(MultiGitError::conflict(
+                "Conflict region is not closed with '>>>>>>>'",
+            ));
+        }
+        if !clean.is_empty() {
+            segments.push(Segment::Clean(clean));
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Conflicted regions, in file order
+    pub fn hunks(&self) -> impl Iterator<Item = &ConflictHunk> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Conflict(hunk) => Some(hunk),
+            Segment::Clean(_) => None,
+        })
+    }
+
+    /// Number of conflicted regions
+    #[must_use]
+    pub fn hunk_count(&self) -> usize {
+        self.hunks().count()
+    }
+
+    /// Reassemble the file, resolving hunk `i` with `choices[i]`
+    ///
+    /// Returns `None` unless every hunk has a choice other than
+    /// [`HunkChoice::Unresolved`].
+    #[must_use]
+    pub fn resolve(&self, choices: &[HunkChoice]) -> Option<String> {
+        if choices.len() != self.hunk_count() {
+            return None;
+        }
+
+        let mut choices = choices.iter();
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Clean(text) => out.push_str(text),
+                Segment::Conflict(hunk) => out.push_str(&hunk.resolve(*choices.next()?)?),
+            }
+        }
+        Some(out)
+    }
+
+    /// Reassemble the file, keeping markers around hunks still unresolved
+    ///
+    /// Useful as a starting point for editing by hand.
+    #[must_use]
+    pub fn render_partial(&self, choices: &[HunkChoice]) -> String {
+        let mut hunk_index = 0;
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Clean(text) => out.push_str(text),
+                Segment::Conflict(hunk) => {
+                    let choice = choices.get(hunk_index).copied().unwrap_or_default();
+                    match hunk.resolve(choice) {
+                        Some(text) => out.push_str(&text),
+                        None => out.push_str(&hunk.to_marked()),
+                    }
+                    hunk_index += 1;
+                }
+            }
+        }
+        out
+    }
+}
+
+fn empty_hunk() -> ConflictHunk {
+    ConflictHunk {
+        ours: String::new(),
+        base: None,
+        theirs: String::new(),
+        ours_label: String::new(),
+        theirs_label: String::new(),
+    }
+}
+
+/// Label after a 7-character marker, if `line` starts with that marker
+fn marker_label(line: &str, marker: &str) -> Option<String> {
+    let rest = line.strip_prefix(marker)?;
+    let rest = rest.trim_end_matches(['\r', '\n']);
+    if rest.is_empty() {
+        Some(String::new())
+    } else {
+        rest.strip_prefix(' ').map(String::from)
+    }
+}
+
+fn is_separator(line: &str) -> bool {
+    line.trim_end_matches(['\r', '\n']) == "======="
+}
+
+fn marker_line(marker: &str, label: &str) -> String {
+    if label.is_empty() {
+        format!("{marker}\n")
+    } else {
+        format!("{marker} {label}\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TWO_HUNKS: &str = concat!(
+        "fn main() {\n",
+        "<<<<<<< HEAD\n    println!(\"ours\");\n=======\n    println!(\"theirs\");\n>>>>>>> feature\n",
+        "    let x = 1;\n",
+        "<<<<<<< HEAD\n    a();\n=======\n    b();\n>>>>>>> feature\n",
+        "}\n",
+    );
+
+    #[test]
+    fn test_parse_hunks() {
+        let file = ConflictedFile::parse(TWO_HUNKS).unwrap();
+        assert_eq!(file.hunk_count(), 2);
+
+        let first = file.hunks().next().unwrap();
+        assert_eq!(first.ours, "    println!(\"ours\");\n");
+        assert_eq!(first.theirs, "    println!(\"theirs\");\n");
+        assert_eq!(first.ours_label, "HEAD");
+        assert_eq!(first.theirs_label, "feature");
+        assert_eq!(first.base, None);
+    }
+
+    #[test]
+    fn test_resolve_per_hunk() {
+        let file = ConflictedFile::parse(TWO_HUNKS).unwrap();
+
+        assert_eq!(file.resolve(&[HunkChoice::Ours]), None);
+        assert_eq!(
+            file.resolve(&[HunkChoice::Ours, HunkChoice::Unresolved]),
+            None
+        );
+
+        let resolved = file
+            .resolve(&[HunkChoice::Theirs, HunkChoice::Both])
+            .unwrap();
+        assert_eq!(
+            resolved,
+            "fn main() {\n    println!(\"theirs\");\n    let x = 1;\n    a();\n    b();\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_render_partial_keeps_unresolved_markers() {
+        let file = ConflictedFile::parse(TWO_HUNKS).unwrap();
+        let partial = file.render_partial(&[HunkChoice::Ours]);
+
+        assert!(partial.starts_with("fn main() {\n    println!(\"ours\");\n"));
+        assert!(partial.contains("<<<<<<< HEAD\n    a();\n=======\n    b();\n>>>>>>> feature\n"));
+
+        // Nothing chosen reproduces the input
+        assert_eq!(file.render_partial(&[]), TWO_HUNKS);
+    }
+
+    #[test]
+    fn test_parse_diff3_base() {
+        let content = "<<<<<<< HEAD\nours\n|||||||\nbase\n=======\ntheirs\n>>>>>>> main\n";
+        let file = ConflictedFile::parse(content).unwrap();
+        let hunk = file.hunks().next().unwrap();
+
+        assert_eq!(hunk.base.as_deref(), Some("base\n"));
+        assert_eq!(hunk.to_marked(), content);
+    }
+
+    #[test]
+    fn test_parse_rejects_broken_markers() {
+        assert!(ConflictedFile::parse("<<<<<<< HEAD\nours\n=======\ntheirs\n").is_err());
+        assert!(ConflictedFile::parse("text\n>>>>>>> main\n").is_err());
+    }
+
+    #[test]
+    fn test_clean_file_has_no_hunks() {
+        let file = ConflictedFile::parse("just text\n").unwrap();
+        assert_eq!(file.hunk_count(), 0);
+        assert_eq!(file.resolve(&[]).as_deref(), Some("just text\n"));
+    }
+}
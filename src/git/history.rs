@@ -0,0 +1,335 @@
+//! Commit history filtering and search
+//!
+//! Shared by `log` and `search`: commits can be narrowed down by author and
+//! date, and searched by message and diff content.
+
+use crate::utils::error::{MultiGitError, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use git2::{Commit, DiffFormat, Oid, Repository, Sort};
+use std::fmt;
+
+/// Commits to consider: by author and how recent they are
+#[derive(Debug, Clone, Default)]
+pub struct CommitFilter {
+    /// Substring of the author's name or email, case-insensitive
+    pub author: Option<String>,
+    /// Only commits made at or after this time
+    pub since: Option<DateTime<Utc>>,
+}
+
+impl CommitFilter {
+    /// Whether `commit` passes the filter
+    #[must_use]
+    pub fn matches(&self, commit: &Commit<'_>) -> bool {
+        if let Some(since) = self.since {
+            if commit.time().seconds() < since.timestamp() {
+                return false;
+            }
+        }
+        if let Some(author) = &self.author {
+            let author = author.to_lowercase();
+            let signature = commit.author();
+            let name = signature.name().unwrap_or_default().to_lowercase();
+            let email = signature.email().unwrap_or_default().to_lowercase();
+            if !name.contains(&author) && !email.contains(&author) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Parse a `--since` value: a date (`2024-05-01`) or an age (`3d`, `2w`, `6m`, `1y`)
+pub fn parse_since(value: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+
+    let invalid = || {
+        MultiGitError::invalid_input(format!(
+            "Invalid --since '{value}': use a date like 2024-05-01 or an age like 3d, 2w, 6m, 1y"
+        ))
+    };
+    let Some((index, unit)) = value.char_indices().last() else {
+        return Err(invalid());
+    };
+    let count: i64 = value[..index].parse().map_err(|_| invalid())?;
+    let days_per_unit = match unit {
+        'd' => 1,
+        'w' => 7,
+        'm' => 30,
+        'y' => 365,
+        _ => return Err(invalid()),
+    };
+    count
+        .checked_mul(days_per_unit)
+        .and_then(Duration::try_days)
+        .and_then(|age| now.checked_sub_signed(age))
+        .ok_or_else(invalid)
+}
+
+/// What `search` looks at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchScope {
+    /// Commit summaries and bodies
+    Messages,
+    /// Added and removed lines
+    Diffs,
+    /// Both messages and diffs
+    #[default]
+    Both,
+}
+
+impl SearchScope {
+    fn messages(self) -> bool {
+        matches!(self, Self::Messages | Self::Both)
+    }
+
+    fn diffs(self) -> bool {
+        matches!(self, Self::Diffs | Self::Both)
+    }
+}
+
+impl fmt::Display for SearchScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Messages => "messages",
+            Self::Diffs => "diffs",
+            Self::Both => "both",
+        })
+    }
+}
+
+impl std::str::FromStr for SearchScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "messages" | "message" => Ok(Self::Messages),
+            "diffs" | "diff" => Ok(Self::Diffs),
+            "both" => Ok(Self::Both),
+            _ => Err(format!(
+                "Unknown search scope '{s}': use messages, diffs or both"
+            )),
+        }
+    }
+}
+
+/// A line of a commit's diff that matched, with the file it belongs to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffHit {
+    /// Path of the changed file
+    pub path: String,
+    /// `+` for an added line, `-` for a removed one
+    pub origin: char,
+    /// The line, without its newline
+    pub line: String,
+}
+
+/// A commit that matched a search
+#[derive(Debug, Clone)]
+pub struct SearchMatch {
+    /// Commit id
+    pub oid: Oid,
+    /// First line of the message
+    pub summary: String,
+    /// Author name
+    pub author: String,
+    /// Commit time, in seconds since the epoch
+    pub time: i64,
+    /// Message lines containing the query
+    pub message_lines: Vec<String>,
+    /// Diff lines containing the query
+    pub diff_hits: Vec<DiffHit>,
+}
+
+/// Search history reachable from HEAD for `query`, newest first
+///
+/// Matching is case-insensitive. Stops after `limit` matching commits.
+pub fn search(
+    repo: &Repository,
+    query: &str,
+    scope: SearchScope,
+    filter: &CommitFilter,
+    limit: usize,
+) -> Result<Vec<SearchMatch>> {
+    let needle = query.to_lowercase();
+    let mut revwalk = repo.revwalk().map_err(MultiGitError::GitError)?;
+    revwalk
+        .set_sorting(Sort::TIME)
+        .map_err(MultiGitError::GitError)?;
+    revwalk.push_head().map_err(MultiGitError::GitError)?;
+
+    let mut matches = Vec::new();
+    for oid in revwalk {
+        if matches.len() >= limit {
+            break;
+        }
+        let commit = repo
+            .find_commit(oid.map_err(MultiGitError::GitError)?)
+            .map_err(MultiGitError::GitError)?;
+        if !filter.matches(&commit) {
+            continue;
+        }
+
+        let message_lines = if scope.messages() {
+            matching_message_lines(commit.message().unwrap_or_default(), &needle)
+        } else {
+            Vec::new()
+        };
+        let diff_hits = if scope.diffs() {
+            matching_diff_lines(repo, &commit, &needle)?
+        } else {
+            Vec::new()
+        };
+
+        if !message_lines.is_empty() || !diff_hits.is_empty() {
+            matches.push(SearchMatch {
+                oid: commit.id(),
+                summary: commit.summary().unwrap_or_default().to_string(),
+                author: commit.author().name().unwrap_or("unknown").to_string(),
+                time: commit.time().seconds(),
+                message_lines,
+                diff_hits,
+            });
+        }
+    }
+
+    Ok(matches)
+}
+
+fn matching_message_lines(message: &str, needle: &str) -> Vec<String> {
+    message
+        .lines()
+        .filter(|line| line.to_lowercase().contains(needle))
+        .map(|line| line.trim().to_string())
+        .collect()
+}
+
+/// Added or removed lines of `commit` against its first parent that contain `needle`
+fn matching_diff_lines(
+    repo: &Repository,
+    commit: &Commit<'_>,
+    needle: &str,
+) -> Result<Vec<DiffHit>> {
+    let tree = commit.tree().map_err(MultiGitError::GitError)?;
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree().map_err(MultiGitError::GitError)?),
+        Err(_) => None,
+    };
+    let diff = repo
+        .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+        .map_err(MultiGitError::GitError)?;
+
+    let mut hits = Vec::new();
+    diff.print(DiffFormat::Patch, |delta, _hunk, line| {
+        let origin = line.origin();
+        if origin == '+' || origin == '-' {
+            let content = String::from_utf8_lossy(line.content());
+            if content.to_lowercase().contains(needle) {
+                let path = delta
+                    .new_file()
+                    .path()
+                    .or_else(|| delta.old_file().path())
+                    .map(|path| path.display().to_string())
+                    .unwrap_or_default();
+                hits.push(DiffHit {
+                    path,
+                    origin,
+                    line: content.trim_end().to_string(),
+                });
+            }
+        }
+        true
+    })
+    .map_err(MultiGitError::GitError)?;
+
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn commit_file(repo: &Repository, path: &str, content: &str, message: &str) {
+        std::fs::write(repo.workdir().unwrap().join(path), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Ada", "ada@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&Commit<'_>> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_parse_since() {
+        let now = DateTime::parse_from_rfc3339("2024-06-15T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            parse_since("2024-05-01", now).unwrap().to_rfc3339(),
+            "2024-05-01T00:00:00+00:00"
+        );
+        assert_eq!(parse_since("2w", now).unwrap(), now - Duration::days(14));
+        assert!(parse_since("soon", now).is_err());
+        assert!(parse_since("3x", now).is_err());
+        assert!(parse_since("3é", now).is_err());
+        assert!(parse_since("", now).is_err());
+        assert!(parse_since("99999999999999y", now).is_err());
+        assert!(parse_since(&format!("{}d", i64::MAX), now).is_err());
+    }
+
+    #[test]
+    fn test_search_scopes() {
+        let temp = TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+        commit_file(&repo, "a.txt", "hello\n", "Add greeting");
+        commit_file(
+            &repo,
+            "a.txt",
+            "hello\nretry_limit = 3\n",
+            "Tune network settings",
+        );
+
+        let filter = CommitFilter::default();
+        let found = search(&repo, "RETRY_LIMIT", SearchScope::Diffs, &filter, 10).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].summary, "Tune network settings");
+        assert_eq!(found[0].diff_hits[0].origin, '+');
+        assert_eq!(found[0].diff_hits[0].path, "a.txt");
+
+        assert!(
+            search(&repo, "retry_limit", SearchScope::Messages, &filter, 10)
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            search(&repo, "greeting", SearchScope::Both, &filter, 10)
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let other_author = CommitFilter {
+            author: Some("grace".to_string()),
+            since: None,
+        };
+        assert!(search(&repo, "hello", SearchScope::Both, &other_author, 10)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,186 @@
+//! Commit identity overrides
+//!
+//! `--author "Name <email>"` and `--date` replace the author recorded on a
+//! commit made through git2, as they do for `git commit`. The committer is
+//! always the configured identity at the current time.
+
+use crate::utils::error::{MultiGitError, Result};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone};
+use git2::{Signature, Time};
+
+/// Author overrides for a commit, kept as given so they can also be passed
+/// on to `git commit`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitOverrides {
+    /// `Name <email>` to record as the author instead of the configured identity
+    pub author: Option<String>,
+    /// When the change was authored, instead of now
+    pub date: Option<String>,
+}
+
+impl CommitOverrides {
+    /// Check the overrides parse, before anything is staged or asked
+    pub fn validate(&self) -> Result<()> {
+        if let Some(ref author) = self.author {
+            parse_author(author)?;
+        }
+        if let Some(ref date) = self.date {
+            parse_date(date)?;
+        }
+        Ok(())
+    }
+
+    /// The author to record: `base` (the configured identity, or the author
+    /// of an amended commit) with the overrides applied
+    pub fn author(&self, base: &Signature<'_>) -> Result<Signature<'static>> {
+        let (name, email) = match self.author {
+            Some(ref author) => parse_author(author)?,
+            None => (
+                base.name().unwrap_or_default().to_string(),
+                base.email().unwrap_or_default().to_string(),
+            ),
+        };
+        let time = match self.date {
+            Some(ref date) => parse_date(date)?,
+            None if self.author.is_some() => Time::new(Local::now().timestamp(), local_offset()),
+            None => base.when(),
+        };
+        Signature::new(&name, &email, &time).map_err(MultiGitError::GitError)
+    }
+
+    /// The overrides as `git commit` arguments
+    #[must_use]
+    pub fn git_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(ref author) = self.author {
+            args.push(format!("--author={author}"));
+        }
+        if let Some(ref date) = self.date {
+            args.push(format!("--date={date}"));
+        }
+        args
+    }
+}
+
+/// Split `Name <email>` into its name and email
+pub fn parse_author(value: &str) -> Result<(String, String)> {
+    let invalid =
+        || MultiGitError::invalid_input(format!("Invalid author '{value}'; use \"Name <email>\""));
+    let (name, rest) = value.split_once('<').ok_or_else(invalid)?;
+    let email = rest.strip_suffix('>').ok_or_else(invalid)?.trim();
+    let name = name.trim();
+    if name.is_empty() || email.is_empty() {
+        return Err(invalid());
+    }
+    Ok((name.to_string(), email.to_string()))
+}
+
+/// Parse a commit date: RFC 3339, `YYYY-MM-DD HH:MM:SS [+zzzz]`, a plain
+/// `YYYY-MM-DD` (local midnight) or `@<unix seconds>`
+///
+/// Dates without an offset are taken as local time.
+pub fn parse_date(value: &str) -> Result<Time> {
+    let value = value.trim();
+    if let Some(seconds) = value.strip_prefix('@') {
+        if let Ok(seconds) = seconds.parse::<i64>() {
+            return Ok(Time::new(seconds, 0));
+        }
+    }
+
+    let with_offset = DateTime::parse_from_rfc3339(value)
+        .or_else(|_| DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S %z"));
+    if let Ok(at) = with_offset {
+        return Ok(Time::new(
+            at.timestamp(),
+            at.offset().local_minus_utc() / 60,
+        ));
+    }
+
+    let naive = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S"))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        });
+    if let Some(at) = naive.and_then(|naive| Local.from_local_datetime(&naive).earliest()) {
+        return Ok(Time::new(
+            at.timestamp(),
+            at.offset().local_minus_utc() / 60,
+        ));
+    }
+
+    Err(MultiGitError::invalid_input(format!(
+        "Invalid date '{value}'; use e.g. 2024-05-01T12:00:00+02:00, 2024-05-01 or @1714557600"
+    )))
+}
+
+/// The local UTC offset in minutes
+fn local_offset() -> i32 {
+    Local::now().offset().local_minus_utc() / 60
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_author() {
+        assert_eq!(
+            parse_author("Jane Doe <jane@example.com>").unwrap(),
+            ("Jane Doe".to_string(), "jane@example.com".to_string())
+        );
+        assert!(parse_author("Jane Doe").is_err());
+        assert!(parse_author("<jane@example.com>").is_err());
+        assert!(parse_author("Jane <>").is_err());
+    }
+
+    #[test]
+    fn test_parse_date() {
+        let time = parse_date("2024-05-01T12:00:00+02:00").unwrap();
+        assert_eq!(time.seconds(), 1_714_557_600);
+        assert_eq!(time.offset_minutes(), 120);
+
+        let time = parse_date("2024-05-01 10:00:00 +0000").unwrap();
+        assert_eq!(time.seconds(), 1_714_557_600);
+        assert_eq!(parse_date("@1714557600").unwrap().seconds(), 1_714_557_600);
+        assert!(parse_date("2024-05-01").is_ok());
+        assert!(parse_date("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_overrides_apply_to_base() {
+        let base = Signature::new("Config", "config@example.com", &Time::new(100, 0)).unwrap();
+
+        let none = CommitOverrides::default();
+        let author = none.author(&base).unwrap();
+        assert_eq!(author.name(), Some("Config"));
+        assert_eq!(author.when().seconds(), 100);
+
+        let invalid = CommitOverrides {
+            author: None,
+            date: Some("last week".to_string()),
+        };
+        assert!(invalid.validate().is_err());
+
+        let dated = CommitOverrides {
+            author: None,
+            date: Some("@1714557600".to_string()),
+        };
+        let author = dated.author(&base).unwrap();
+        assert_eq!(author.email(), Some("config@example.com"));
+        assert_eq!(author.when().seconds(), 1_714_557_600);
+
+        let both = CommitOverrides {
+            author: Some("Jane <jane@example.com>".to_string()),
+            date: Some("@1714557600".to_string()),
+        };
+        assert_eq!(both.author(&base).unwrap().name(), Some("Jane"));
+        assert!(both.validate().is_ok());
+        assert_eq!(
+            both.git_args(),
+            vec!["--author=Jane <jane@example.com>", "--date=@1714557600"]
+        );
+    }
+}
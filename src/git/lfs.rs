@@ -0,0 +1,108 @@
+//! Git LFS detection
+//!
+//! libgit2 pushes commits but runs no LFS hooks, so files tracked with
+//! `filter=lfs` reach a remote as pointer files without their content. A
+//! repository that uses LFS needs `git lfs push` after every git2 push.
+
+use crate::utils::error::{MultiGitError, Result};
+use git2::Repository;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tracing::debug;
+
+/// Whether any attributes file of `repo` routes paths through the LFS filter
+///
+/// Reads the top-level `.gitattributes`, every `.gitattributes` in the index
+/// and `.git/info/attributes`.
+#[must_use]
+pub fn uses_lfs(repo: &Repository) -> bool {
+    let mut files = vec![repo.path().join("info").join("attributes")];
+    if let Some(workdir) = repo.workdir() {
+        files.push(workdir.join(".gitattributes"));
+        if let Ok(index) = repo.index() {
+            files.extend(
+                index
+                    .iter()
+                    .filter_map(|entry| String::from_utf8(entry.path).ok())
+                    .filter(|path| path.ends_with(".gitattributes") && path.contains('/'))
+                    .map(|path| workdir.join(path)),
+            );
+        }
+    }
+
+    files
+        .iter()
+        .any(|file| fs::read_to_string(file).is_ok_and(|contents| declares_lfs(&contents)))
+}
+
+/// Whether attributes file `contents` set `filter=lfs` on any pattern
+#[must_use]
+pub fn declares_lfs(contents: &str) -> bool {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .any(|line| {
+            line.split_whitespace()
+                .skip(1)
+                .any(|attr| attr == "filter=lfs")
+        })
+}
+
+/// Whether the `git lfs` command is available
+#[must_use]
+pub fn is_installed() -> bool {
+    Command::new("git")
+        .args(["lfs", "version"])
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Upload the LFS objects referenced by `branch` to `remote`
+pub fn push(workdir: &Path, remote: &str, branch: &str) -> Result<()> {
+    debug!("Running git lfs push {} {}", remote, branch);
+    let output = Command::new("git")
+        .args(["lfs", "push", remote, branch])
+        .current_dir(workdir)
+        .output()
+        .map_err(|e| MultiGitError::other(format!("Failed to run git lfs: {e}")))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(MultiGitError::other(format!(
+            "git lfs push failed: {}",
+            stderr.trim()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_declares_lfs() {
+        assert!(declares_lfs("*.psd filter=lfs diff=lfs merge=lfs -text\n"));
+        assert!(!declares_lfs("*.sh text eol=lf\n"));
+        assert!(!declares_lfs("# *.bin filter=lfs\n"));
+        assert!(!declares_lfs("filter=lfs\n"));
+    }
+
+    #[test]
+    fn test_uses_lfs_reads_gitattributes() {
+        let temp = TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+        assert!(!uses_lfs(&repo));
+
+        fs::write(
+            temp.path().join(".gitattributes"),
+            "*.bin filter=lfs diff=lfs merge=lfs -text\n",
+        )
+        .unwrap();
+        assert!(uses_lfs(&repo));
+    }
+}
@@ -1,8 +1,21 @@
 //! Git operations wrapper using libgit2
 
 pub mod branch;
+pub mod branch_filter;
+pub mod conflict_markers;
+pub mod history;
+pub mod identity;
+pub mod lfs;
 pub mod operations;
+pub mod patch;
 pub mod remote;
+pub mod repo_state;
+pub mod signing;
+pub mod submodule;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod working_tree;
+pub mod worktree;
 
 // TODO: Implement Git wrapper in Phase 2
 // TODO: Wrap git2-rs for common operations
@@ -2,19 +2,77 @@
 //!
 //! Provides a wrapper around git2 for common repository operations.
 
+use crate::api::retry::RetryConfig;
+use crate::git::identity::CommitOverrides;
+use crate::git::repo_state::OperationInProgress;
+use crate::git::submodule::{self, SubmoduleInfo};
 use crate::utils::error::{MultiGitError, Result};
-use git2::{BranchType, Commit, Oid, Repository, StatusOptions};
+use crate::utils::redact::redact;
+use crate::utils::tls::TlsOptions;
+use git2::{
+    BranchType, Commit, ErrorClass, ErrorCode, Oid, ProxyOptions, Repository, StatusOptions,
+};
 use std::path::Path;
-use std::time::Duration;
-use tracing::{debug, info, warn};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, instrument, warn};
 
 /// Default timeout for network operations (5 minutes)
 const DEFAULT_NETWORK_TIMEOUT_SECS: u64 = 300;
 
+/// Default attempts at a push or fetch that fails on a transient network error
+pub const DEFAULT_NETWORK_ATTEMPTS: u32 = 3;
+
+/// Oldest libgit2 that can fetch with a depth limit
+pub const MIN_SHALLOW_LIBGIT2: (u32, u32, u32) = (1, 7, 0);
+
+/// Result of [`GitOperations::cherry_pick`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CherryPickOutcome {
+    /// Applied cleanly and committed as this commit
+    Committed(Oid),
+    /// Stopped with these files conflicted
+    Conflicts(Vec<String>),
+}
+
+/// Result of [`GitOperations::stash_pop`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StashPop {
+    /// Reapplied cleanly and dropped from the stash list
+    Restored,
+    /// Conflicted in these files; the stash entry was kept
+    Conflicts(Vec<String>),
+}
+
+/// How a push changed one remote ref
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefChange {
+    /// The remote didn't have the ref
+    Created,
+    /// The remote ref pointed elsewhere and was moved
+    Updated,
+    /// The remote ref already pointed at the pushed object
+    UpToDate,
+}
+
+/// One ref offered to the remote by [`GitOperations::push_with_updates`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefUpdate {
+    /// Full name of the remote ref, e.g. `refs/tags/v1.0`
+    pub refname: String,
+    /// What the push did to it
+    pub change: RefChange,
+}
+
 /// Wrapper for Git operations using libgit2
 pub struct GitOperations {
     repo: Repository,
     network_timeout: Duration,
+    proxy_url: Option<String>,
+    tls: TlsOptions,
+    cancel: CancellationToken,
+    fetch_depth: Option<u32>,
+    retry: RetryConfig,
 }
 
 impl GitOperations {
@@ -26,10 +84,7 @@ impl GitOperations {
         let repo = Repository::open(path).map_err(MultiGitError::GitError)?;
 
         info!("Successfully opened repository at {}", path.display());
-        Ok(Self {
-            repo,
-            network_timeout: Duration::from_secs(DEFAULT_NETWORK_TIMEOUT_SECS),
-        })
+        Ok(Self::from_repo(repo))
     }
 
     /// Initialize a new repository at the given path
@@ -40,10 +95,22 @@ impl GitOperations {
         let repo = Repository::init(path).map_err(MultiGitError::GitError)?;
 
         info!("Successfully initialized repository at {}", path.display());
-        Ok(Self {
+        Ok(Self::from_repo(repo))
+    }
+
+    fn from_repo(repo: Repository) -> Self {
+        Self {
             repo,
             network_timeout: Duration::from_secs(DEFAULT_NETWORK_TIMEOUT_SECS),
-        })
+            proxy_url: None,
+            tls: TlsOptions::default(),
+            cancel: CancellationToken::new(),
+            fetch_depth: None,
+            retry: RetryConfig {
+                max_attempts: DEFAULT_NETWORK_ATTEMPTS as usize,
+                ..RetryConfig::for_network()
+            },
+        }
     }
 
     /// Set the network timeout for fetch/push/clone operations
@@ -53,6 +120,119 @@ impl GitOperations {
         self
     }
 
+    /// Route fetch/push through `proxy_url`
+    ///
+    /// Without one, the proxy is detected from git's `http.proxy` config and
+    /// the `HTTPS_PROXY`/`HTTP_PROXY`/`NO_PROXY` environment variables.
+    #[must_use]
+    pub fn with_proxy(mut self, proxy_url: Option<String>) -> Self {
+        self.proxy_url = proxy_url;
+        self
+    }
+
+    /// Certificate trust settings for fetch/push
+    ///
+    /// A custom CA must also be registered with [`TlsOptions::trust_ca_for_git`]
+    /// before transfers start; this only controls per-transfer verification.
+    #[must_use]
+    pub fn with_tls(mut self, tls: TlsOptions) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    /// Abort fetch/push transfers once `cancel` is cancelled
+    ///
+    /// Checked from libgit2's callbacks, so a transfer stops at the next
+    /// progress report rather than instantly.
+    #[must_use]
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Fetch at most `depth` commits of history per ref (`None` fetches everything)
+    #[must_use]
+    pub fn with_fetch_depth(mut self, depth: Option<u32>) -> Self {
+        self.fetch_depth = depth;
+        self
+    }
+
+    /// Try a fetch or push up to `attempts` times while it fails on a
+    /// transient network error (1 never retries)
+    ///
+    /// All attempts together stay within the network timeout.
+    #[must_use]
+    pub fn with_retries(mut self, attempts: u32) -> Self {
+        self.retry.max_attempts = attempts.max(1) as usize;
+        self
+    }
+
+    /// Backoff between retries (see [`Self::with_retries`])
+    #[must_use]
+    pub fn with_retry_config(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Run `transfer` with what is left of the network timeout, again after a
+    /// backoff while it fails on a transient network error
+    ///
+    /// Gives up early when cancelled or when the backoff would outlast the
+    /// timeout.
+    fn with_network_retries<T>(
+        &self,
+        operation: &str,
+        remote_name: &str,
+        mut transfer: impl FnMut(Duration) -> Result<T>,
+    ) -> Result<T> {
+        let deadline = Instant::now() + self.network_timeout;
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let e = match transfer(remaining) {
+                Err(MultiGitError::GitError(e))
+                    if attempt < self.retry.max_attempts && is_transient_network_error(&e) =>
+                {
+                    e
+                }
+                result => return result,
+            };
+
+            let backoff = self.retry.backoff_duration(attempt - 1);
+            if self.cancel.is_cancelled()
+                || backoff >= deadline.saturating_duration_since(Instant::now())
+            {
+                return Err(MultiGitError::GitError(e));
+            }
+            warn!(
+                "{} {} failed (attempt {}/{}): {}. Retrying in {:?}...",
+                operation,
+                remote_name,
+                attempt,
+                self.retry.max_attempts,
+                e.message(),
+                backoff
+            );
+            std::thread::sleep(backoff);
+        }
+    }
+
+    /// Proxy options for fetch/push
+    fn proxy_options(&self) -> ProxyOptions<'_> {
+        let mut proxy = ProxyOptions::new();
+        match self.proxy_url {
+            Some(ref url) => {
+                debug!("Using proxy {} for git transfers", redact(url));
+                proxy.url(url);
+            }
+            None => {
+                proxy.auto();
+            }
+        }
+        proxy
+    }
+
     /// Get the current branch name
     pub fn current_branch(&self) -> Result<String> {
         let head = self.repo.head().map_err(MultiGitError::GitError)?;
@@ -78,6 +258,10 @@ impl GitOperations {
     }
 
     /// Check if the working directory is clean (no uncommitted changes)
+    ///
+    /// A submodule checked out at another commit than recorded, or with
+    /// changes of its own, makes the tree dirty, unless its `ignore` setting
+    /// in `.gitmodules` says otherwise.
     pub fn is_clean(&self) -> Result<bool> {
         let statuses = self
             .repo
@@ -93,6 +277,11 @@ impl GitOperations {
         Ok(is_clean)
     }
 
+    /// Submodules of the repository, sorted by path
+    pub fn submodules(&self) -> Result<Vec<SubmoduleInfo>> {
+        submodule::list(&self.repo)
+    }
+
     /// Get the repository path
     #[must_use]
     pub fn path(&self) -> &Path {
@@ -107,6 +296,10 @@ impl GitOperations {
     }
 
     /// Fetch from a remote
+    ///
+    /// Stops at the next transfer callback once the cancellation token is cancelled.
+    /// Connection resets and timeouts are retried (see [`Self::with_retries`]).
+    #[instrument(name = "git.fetch", level = "debug", skip_all, fields(remote = remote_name))]
     pub fn fetch(&self, remote_name: &str, refspecs: &[&str]) -> Result<()> {
         info!(
             "Fetching from remote: {} (timeout: {}s)",
@@ -114,6 +307,15 @@ impl GitOperations {
             self.network_timeout.as_secs()
         );
 
+        self.with_network_retries("Fetch from", remote_name, |timeout| {
+            self.fetch_once(remote_name, refspecs, timeout)
+        })?;
+
+        info!("Successfully fetched from {}", remote_name);
+        Ok(())
+    }
+
+    fn fetch_once(&self, remote_name: &str, refspecs: &[&str], timeout: Duration) -> Result<()> {
         let mut remote = self
             .repo
             .find_remote(remote_name)
@@ -124,10 +326,14 @@ impl GitOperations {
         let mut callbacks = git2::RemoteCallbacks::new();
 
         // Track start time for timeout checking
-        let start_time = std::time::Instant::now();
-        let timeout = self.network_timeout;
+        let start_time = Instant::now();
 
+        let cancel = self.cancel.clone();
         callbacks.transfer_progress(move |stats| {
+            if cancel.is_cancelled() {
+                return false; // Abort the transfer
+            }
+
             // Check for timeout
             if start_time.elapsed() > timeout {
                 warn!("Fetch operation timed out after {}s", timeout.as_secs());
@@ -150,20 +356,29 @@ impl GitOperations {
             true
         });
 
+        let tls = self.tls.clone();
+        callbacks.certificate_check(move |_cert, host| Ok(tls.certificate_check(host)));
+
         fetch_options.remote_callbacks(callbacks);
+        fetch_options.proxy_options(self.proxy_options());
+        // Record what was fetched so `merge FETCH_HEAD` works afterwards
+        fetch_options.update_fetchhead(true);
+        if let Some(depth) = self.fetch_depth {
+            ensure_shallow_supported()?;
+            fetch_options.depth(i32::try_from(depth).unwrap_or(i32::MAX));
+        }
 
         remote
             .fetch(refspecs, Some(&mut fetch_options), None)
             .map_err(|e| {
-                if start_time.elapsed() > timeout {
+                if self.cancel.is_cancelled() {
+                    MultiGitError::Other("Fetch interrupted".to_string())
+                } else if start_time.elapsed() > timeout {
                     MultiGitError::Other(format!("Fetch timed out after {}s", timeout.as_secs()))
                 } else {
                     MultiGitError::GitError(e)
                 }
-            })?;
-
-        info!("Successfully fetched from {}", remote_name);
-        Ok(())
+            })
     }
 
     /// Push to a remote with timeout protection
@@ -171,16 +386,63 @@ impl GitOperations {
     /// Uses multiple callbacks to enforce timeout:
     /// - `pack_progress`: Aborts pack generation if timeout exceeded
     /// - `push_transfer_progress`: Monitors transfer progress
+    /// - `push_negotiation` and `sideband_progress`: Abort once the
+    ///   cancellation token is cancelled
     ///
     /// **Note**: Timeout enforcement is best-effort. Some libgit2 operations
     /// may not check callbacks frequently enough for immediate cancellation.
     pub fn push(&self, remote_name: &str, refspecs: &[&str]) -> Result<()> {
+        self.push_with_updates(remote_name, refspecs).map(|_| ())
+    }
+
+    /// Push like [`Self::push`], returning how each remote ref was changed
+    ///
+    /// Refs the remote already had at the pushed object are reported as
+    /// [`RefChange::UpToDate`] rather than left out.
+    pub fn push_with_updates(
+        &self,
+        remote_name: &str,
+        refspecs: &[&str],
+    ) -> Result<Vec<RefUpdate>> {
+        self.push_with_lease(remote_name, refspecs, &[])
+    }
+
+    /// Push like [`Self::push_with_updates`], but only while each remote ref
+    /// in `expected` is still at the given commit, like
+    /// `git push --force-with-lease`
+    ///
+    /// The check happens during negotiation, against what the server reports,
+    /// so a ref moved since the last fetch fails the push as non-fast-forward
+    /// without changing anything. Connection resets and timeouts are retried
+    /// (see [`Self::with_retries`]); rejections are not.
+    #[instrument(name = "git.push", level = "debug", skip_all, fields(remote = remote_name))]
+    pub fn push_with_lease(
+        &self,
+        remote_name: &str,
+        refspecs: &[&str],
+        expected: &[(String, Oid)],
+    ) -> Result<Vec<RefUpdate>> {
         info!(
             "Pushing to remote: {} (timeout: {}s)",
             remote_name,
             self.network_timeout.as_secs()
         );
 
+        let updates = self.with_network_retries("Push to", remote_name, |timeout| {
+            self.push_once(remote_name, refspecs, expected, timeout)
+        })?;
+
+        info!("Successfully pushed to {}", remote_name);
+        Ok(updates)
+    }
+
+    fn push_once(
+        &self,
+        remote_name: &str,
+        refspecs: &[&str],
+        expected: &[(String, Oid)],
+        timeout: Duration,
+    ) -> Result<Vec<RefUpdate>> {
         let mut remote = self
             .repo
             .find_remote(remote_name)
@@ -190,8 +452,7 @@ impl GitOperations {
         let mut callbacks = git2::RemoteCallbacks::new();
 
         // Track start time for timeout checking
-        let start_time = std::time::Instant::now();
-        let timeout = self.network_timeout;
+        let start_time = Instant::now();
 
         // Clone for pack progress callback
         let start_time_pack = start_time;
@@ -218,20 +479,122 @@ impl GitOperations {
             debug!("Push progress: {}/{} ({} bytes)", current, total, bytes);
         });
 
+        // The only push callbacks that can abort: before the pack is sent,
+        // and on each progress message from the server
+        let cancel = self.cancel.clone();
+        let updates = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let negotiated = updates.clone();
+        let expected = expected.to_vec();
+        callbacks.push_negotiation(move |offered| {
+            if cancel.is_cancelled() {
+                return Err(git2::Error::from_str("push interrupted"));
+            }
+            for update in offered {
+                let Some(refname) = update.dst_refname() else {
+                    continue;
+                };
+                let lease = expected.iter().find(|(name, _)| name == refname);
+                if let Some((_, oid)) = lease {
+                    if update.src() != *oid {
+                        return Err(rejected_ref_error(refname, "stale info"));
+                    }
+                }
+            }
+            let mut negotiated = negotiated.lock().expect("push updates lock poisoned");
+            negotiated.extend(offered.iter().filter_map(|update| {
+                let change = if update.src().is_zero() {
+                    RefChange::Created
+                } else if update.src() == update.dst() {
+                    RefChange::UpToDate
+                } else {
+                    RefChange::Updated
+                };
+                Some(RefUpdate {
+                    refname: update.dst_refname()?.to_string(),
+                    change,
+                })
+            }));
+            Ok(())
+        });
+        let cancel = self.cancel.clone();
+        callbacks.sideband_progress(move |_| !cancel.is_cancelled());
+
+        let tls = self.tls.clone();
+        callbacks.certificate_check(move |_cert, host| Ok(tls.certificate_check(host)));
+
+        // Servers report rejected refs here; without an error the push
+        // would look successful
+        callbacks.push_update_reference(|refname, status| match status {
+            Some(status) => Err(rejected_ref_error(refname, status)),
+            None => Ok(()),
+        });
+
         push_options.remote_callbacks(callbacks);
+        push_options.proxy_options(self.proxy_options());
 
         remote
             .push(refspecs, Some(&mut push_options))
             .map_err(|e| {
-                if start_time.elapsed() > timeout {
+                if self.cancel.is_cancelled() {
+                    MultiGitError::Other("Push interrupted".to_string())
+                } else if start_time.elapsed() > timeout {
                     MultiGitError::Other(format!("Push timed out after {}s", timeout.as_secs()))
                 } else {
                     MultiGitError::GitError(e)
                 }
             })?;
 
-        info!("Successfully pushed to {}", remote_name);
-        Ok(())
+        let updates = std::mem::take(&mut *updates.lock().expect("push updates lock poisoned"));
+        Ok(updates)
+    }
+
+    /// Commit `refs/remotes/<remote>/<branch>` points at, as of the last
+    /// fetch; `None` when the remote has no such branch
+    pub fn remote_branch_tip(&self, remote: &str, branch: &str) -> Result<Option<Oid>> {
+        match self
+            .repo
+            .refname_to_id(&format!("refs/remotes/{remote}/{branch}"))
+        {
+            Ok(oid) => Ok(Some(oid)),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(MultiGitError::GitError(e)),
+        }
+    }
+
+    /// Names of the local tags, sorted
+    pub fn tags(&self) -> Result<Vec<String>> {
+        let names = self.repo.tag_names(None)?;
+        let mut tags: Vec<String> = names.iter().flatten().map(str::to_string).collect();
+        tags.sort();
+        Ok(tags)
+    }
+
+    /// Annotated tags pointing at `branch`'s tip or one of its ancestors,
+    /// the tags `git push --follow-tags` sends along
+    pub fn annotated_tags_reachable_from(&self, branch: &str) -> Result<Vec<String>> {
+        let tip = self
+            .repo
+            .find_branch(branch, BranchType::Local)?
+            .get()
+            .peel_to_commit()?
+            .id();
+
+        let mut tags = Vec::new();
+        for name in self.tags()? {
+            let reference = self.repo.find_reference(&format!("refs/tags/{name}"))?;
+            // Lightweight tags point straight at a commit, not a tag object
+            if reference.peel_to_tag().is_err() {
+                continue;
+            }
+            let commit = match reference.peel_to_commit() {
+                Ok(commit) => commit.id(),
+                Err(_) => continue,
+            };
+            if commit == tip || self.repo.graph_descendant_of(tip, commit)? {
+                tags.push(name);
+            }
+        }
+        Ok(tags)
     }
 
     /// Get the number of commits ahead and behind compared to a remote branch
@@ -265,6 +628,31 @@ impl GitOperations {
         Ok((ahead, behind))
     }
 
+    /// Move `branch` to `refs/remotes/<remote>/<branch>`
+    ///
+    /// Callers check the move is a fast-forward with [`Self::get_ahead_behind`].
+    /// The working tree is updated when `branch` is checked out.
+    pub fn fast_forward(&self, branch: &str, remote: &str) -> Result<()> {
+        let local_ref = format!("refs/heads/{branch}");
+        let remote_ref = format!("refs/remotes/{remote}/{branch}");
+
+        let remote_oid = self
+            .repo
+            .refname_to_id(&remote_ref)
+            .map_err(MultiGitError::GitError)?;
+        self.repo
+            .reference(&local_ref, remote_oid, true, "fast-forward pull")
+            .map_err(MultiGitError::GitError)?;
+
+        if self.current_branch()? == branch {
+            self.repo
+                .checkout_head(Some(git2::build::CheckoutBuilder::default().force()))
+                .map_err(MultiGitError::GitError)?;
+        }
+
+        Ok(())
+    }
+
     /// Get the commit at HEAD
     pub fn head_commit(&self) -> Result<Commit<'_>> {
         let head = self.repo.head().map_err(MultiGitError::GitError)?;
@@ -275,6 +663,79 @@ impl GitOperations {
         self.repo.find_commit(oid).map_err(MultiGitError::GitError)
     }
 
+    /// Apply the commit `rev` names on top of HEAD and commit it
+    ///
+    /// The new commit keeps the original author and message. When the
+    /// changes conflict, the index and working tree are left mid-cherry-pick
+    /// for [`conclude_cherry_pick`](Self::conclude_cherry_pick).
+    pub fn cherry_pick(&self, rev: &str) -> Result<CherryPickOutcome> {
+        let commit = self
+            .repo
+            .revparse_single(rev)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|_| MultiGitError::invalid_input(format!("Unknown commit '{rev}'")))?;
+        if commit.parent_count() > 1 {
+            return Err(MultiGitError::invalid_input(format!(
+                "{rev} is a merge commit; pick it with 'git cherry-pick -m <parent> {rev}'"
+            )));
+        }
+
+        info!("Cherry-picking {}", commit.id());
+        self.repo
+            .cherrypick(&commit, None)
+            .map_err(MultiGitError::GitError)?;
+
+        let index = self.repo.index().map_err(MultiGitError::GitError)?;
+        if index.has_conflicts() {
+            let files = OperationInProgress::detect(&self.repo)?
+                .map(|op| op.conflicted_files)
+                .unwrap_or_default();
+            return Ok(CherryPickOutcome::Conflicts(files));
+        }
+
+        self.conclude_cherry_pick()
+            .map(CherryPickOutcome::Committed)
+    }
+
+    /// Commit a cherry-pick whose conflicts have been resolved and staged
+    pub fn conclude_cherry_pick(&self) -> Result<Oid> {
+        let picked = std::fs::read_to_string(self.repo.path().join("CHERRY_PICK_HEAD"))
+            .ok()
+            .and_then(|oid| Oid::from_str(oid.trim()).ok())
+            .ok_or_else(|| MultiGitError::other("No cherry-pick in progress"))?;
+        let original = self.find_commit(picked)?;
+
+        let mut index = self.repo.index().map_err(MultiGitError::GitError)?;
+        if index.has_conflicts() {
+            return Err(MultiGitError::conflict(
+                "Resolve the remaining conflicts before concluding the cherry-pick",
+            ));
+        }
+        let tree_id = index.write_tree().map_err(MultiGitError::GitError)?;
+        let tree = self
+            .repo
+            .find_tree(tree_id)
+            .map_err(MultiGitError::GitError)?;
+        let head = self.head_commit()?;
+        let committer = self.repo.signature().map_err(MultiGitError::GitError)?;
+
+        let oid = self
+            .repo
+            .commit(
+                Some("HEAD"),
+                &original.author(),
+                &committer,
+                original.message().unwrap_or_default(),
+                &tree,
+                &[&head],
+            )
+            .map_err(MultiGitError::GitError)?;
+        self.repo.cleanup_state().map_err(MultiGitError::GitError)?;
+
+        info!("Cherry-picked {} as {}", picked, oid);
+        Ok(oid)
+    }
+
     /// Get a commit by OID
     pub fn find_commit(&self, oid: Oid) -> Result<Commit<'_>> {
         self.repo.find_commit(oid).map_err(MultiGitError::GitError)
@@ -305,6 +766,200 @@ impl GitOperations {
         self.repo.is_bare()
     }
 
+    /// Merge, rebase or similar operation the repository is in the middle of
+    pub fn operation_in_progress(&self) -> Result<Option<OperationInProgress>> {
+        OperationInProgress::detect(&self.repo)
+    }
+
+    /// Stage `paths` (relative to the working directory) and commit them on
+    /// top of HEAD with the configured identity
+    pub fn commit_paths(&self, paths: &[&Path], message: &str) -> Result<Oid> {
+        self.stage_paths(paths)?;
+        let oid = self.commit_index(message, &CommitOverrides::default())?;
+        info!("Committed {} path(s) as {}", paths.len(), oid);
+        Ok(oid)
+    }
+
+    /// Stage `paths` (relative to the working directory) like `git add`,
+    /// removing the ones deleted from the working tree
+    pub fn stage_paths(&self, paths: &[&Path]) -> Result<()> {
+        let workdir = self.workdir()?;
+        let mut index = self.repo.index().map_err(MultiGitError::GitError)?;
+        for path in paths {
+            if workdir.join(path).exists() {
+                index.add_path(path).map_err(MultiGitError::GitError)?;
+            } else {
+                index.remove_path(path).map_err(MultiGitError::GitError)?;
+            }
+        }
+        index.write().map_err(MultiGitError::GitError)
+    }
+
+    /// Stage every change to tracked files, like `git commit -a`
+    pub fn stage_tracked(&self) -> Result<()> {
+        let mut index = self.repo.index().map_err(MultiGitError::GitError)?;
+        index
+            .update_all(["*"].iter(), None)
+            .map_err(MultiGitError::GitError)?;
+        index.write().map_err(MultiGitError::GitError)
+    }
+
+    /// Commit the index on top of HEAD (or as the first commit) with the
+    /// configured identity, and the author as `overrides` says
+    ///
+    /// Like `git commit`, fails when nothing is staged.
+    pub fn commit_index(&self, message: &str, overrides: &CommitOverrides) -> Result<Oid> {
+        let tree = self.index_tree()?;
+        let parent = match self.repo.head() {
+            Ok(_) => Some(self.head_commit()?),
+            Err(e) if e.code() == git2::ErrorCode::UnbornBranch => None,
+            Err(e) => return Err(MultiGitError::GitError(e)),
+        };
+        if parent
+            .as_ref()
+            .is_some_and(|parent| parent.tree_id() == tree.id())
+        {
+            return Err(MultiGitError::other(
+                "Nothing to commit: no changes are staged",
+            ));
+        }
+
+        let committer = self.signature()?;
+        let author = overrides.author(&committer)?;
+        let parents: Vec<&Commit<'_>> = parent.iter().collect();
+        self.repo
+            .commit(Some("HEAD"), &author, &committer, message, &tree, &parents)
+            .map_err(MultiGitError::GitError)
+    }
+
+    /// Replace HEAD with a commit of the index, keeping its message unless
+    /// `message` is given, and its author unless `overrides` replace it
+    pub fn amend_index(&self, message: Option<&str>, overrides: &CommitOverrides) -> Result<Oid> {
+        let tree = self.index_tree()?;
+        let head = self.head_commit()?;
+        let committer = self.signature()?;
+        let author = overrides.author(&head.author())?;
+        head.amend(
+            Some("HEAD"),
+            Some(&author),
+            Some(&committer),
+            None,
+            message,
+            Some(&tree),
+        )
+        .map_err(MultiGitError::GitError)
+    }
+
+    /// Whether `commit.gpgsign` asks for commits to be signed, which git2
+    /// can't do
+    #[must_use]
+    pub fn signs_commits(&self) -> bool {
+        self.repo
+            .config()
+            .and_then(|config| config.get_bool("commit.gpgsign"))
+            .unwrap_or(false)
+    }
+
+    /// Installed hooks `git commit` would run, which git2 doesn't
+    #[must_use]
+    pub fn commit_hooks(&self) -> Vec<&'static str> {
+        let hooks_dir = self
+            .repo
+            .config()
+            .and_then(|config| config.get_path("core.hooksPath"))
+            .map_or_else(
+                |_| self.repo.path().join("hooks"),
+                |path| match self.repo.workdir() {
+                    Some(workdir) if path.is_relative() => workdir.join(path),
+                    _ => path,
+                },
+            );
+        [
+            "pre-commit",
+            "prepare-commit-msg",
+            "commit-msg",
+            "post-commit",
+        ]
+        .into_iter()
+        .filter(|hook| hooks_dir.join(hook).is_file())
+        .collect()
+    }
+
+    fn index_tree(&self) -> Result<git2::Tree<'_>> {
+        let mut index = self.repo.index().map_err(MultiGitError::GitError)?;
+        let tree_id = index.write_tree().map_err(MultiGitError::GitError)?;
+        self.repo
+            .find_tree(tree_id)
+            .map_err(MultiGitError::GitError)
+    }
+
+    /// The configured identity, or a placeholder when none is set
+    fn signature(&self) -> Result<git2::Signature<'static>> {
+        self.repo
+            .signature()
+            .or_else(|_| git2::Signature::now("multigit", "multigit@localhost"))
+            .map_err(MultiGitError::GitError)
+    }
+
+    /// Stash uncommitted changes, untracked files included
+    ///
+    /// Returns `None` when there was nothing to stash.
+    pub fn stash_save(&self, message: &str) -> Result<Option<Oid>> {
+        // Stashing needs a mutable repository; a second handle avoids making
+        // every operation take `&mut self`
+        let mut repo = Repository::open(self.repo.path()).map_err(MultiGitError::GitError)?;
+        let stasher = repo
+            .signature()
+            .or_else(|_| git2::Signature::now("multigit", "multigit@localhost"))
+            .map_err(MultiGitError::GitError)?;
+        match repo.stash_save(&stasher, message, Some(git2::StashFlags::INCLUDE_UNTRACKED)) {
+            Ok(oid) => {
+                info!("Stashed local changes as {}", oid);
+                Ok(Some(oid))
+            }
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(MultiGitError::GitError(e)),
+        }
+    }
+
+    /// Reapply the latest stash and drop it
+    ///
+    /// When the changes conflict with the working tree the stash is kept, so
+    /// nothing is lost, and the conflicted files are returned.
+    pub fn stash_pop(&self) -> Result<StashPop> {
+        let mut repo = Repository::open(self.repo.path()).map_err(MultiGitError::GitError)?;
+        let mut options = git2::StashApplyOptions::new();
+        options.reinstantiate_index();
+        match repo.stash_apply(0, Some(&mut options)) {
+            Ok(()) => {}
+            Err(e)
+                if matches!(
+                    e.code(),
+                    git2::ErrorCode::Conflict | git2::ErrorCode::MergeConflict
+                ) =>
+            {
+                warn!("Stash could not be reapplied: {}", e.message());
+                return Ok(StashPop::Conflicts(Vec::new()));
+            }
+            Err(e) => return Err(MultiGitError::GitError(e)),
+        }
+
+        let index = repo.index().map_err(MultiGitError::GitError)?;
+        if index.has_conflicts() {
+            let mut files = Vec::new();
+            for conflict in index.conflicts().map_err(MultiGitError::GitError)? {
+                let conflict = conflict.map_err(MultiGitError::GitError)?;
+                if let Some(entry) = conflict.our.or(conflict.their).or(conflict.ancestor) {
+                    files.push(String::from_utf8_lossy(&entry.path).into_owned());
+                }
+            }
+            return Ok(StashPop::Conflicts(files));
+        }
+
+        repo.stash_drop(0).map_err(MultiGitError::GitError)?;
+        Ok(StashPop::Restored)
+    }
+
     /// Get the underlying `git2::Repository` reference
     #[must_use]
     pub fn inner(&self) -> &Repository {
@@ -344,16 +999,31 @@ impl GitOperations {
 
     /// Clone a repository from a URL to a path
     pub fn clone<P: AsRef<Path>>(url: &str, path: P) -> Result<Self> {
+        Self::clone_with_depth(url, path, None)
+    }
+
+    /// Clone a repository, keeping only the last `depth` commits when given
+    pub fn clone_with_depth<P: AsRef<Path>>(
+        url: &str,
+        path: P,
+        depth: Option<u32>,
+    ) -> Result<Self> {
         let path = path.as_ref();
         info!("Cloning repository from {} to {}", url, path.display());
 
-        let repo = Repository::clone(url, path).map_err(MultiGitError::GitError)?;
+        let mut fetch_options = git2::FetchOptions::new();
+        if let Some(depth) = depth {
+            ensure_shallow_supported()?;
+            fetch_options.depth(i32::try_from(depth).unwrap_or(i32::MAX));
+        }
+
+        let repo = git2::build::RepoBuilder::new()
+            .fetch_options(fetch_options)
+            .clone(url, path)
+            .map_err(MultiGitError::GitError)?;
 
         info!("Successfully cloned repository to {}", path.display());
-        Ok(Self {
-            repo,
-            network_timeout: Duration::from_secs(DEFAULT_NETWORK_TIMEOUT_SECS),
-        })
+        Ok(Self::from_repo(repo))
     }
 
     /// Add a remote to the repository
@@ -381,6 +1051,83 @@ impl GitOperations {
     }
 }
 
+/// Whether the linked libgit2 can fetch with a depth limit
+#[must_use]
+pub fn shallow_supported() -> bool {
+    git2::Version::get().libgit2_version() >= MIN_SHALLOW_LIBGIT2
+}
+
+/// Fail clearly instead of silently fetching full history on an old libgit2
+fn ensure_shallow_supported() -> Result<()> {
+    if shallow_supported() {
+        return Ok(());
+    }
+
+    let (major, minor, patch) = git2::Version::get().libgit2_version();
+    let (min_major, min_minor, min_patch) = MIN_SHALLOW_LIBGIT2;
+    Err(MultiGitError::other(format!(
+        "Shallow fetches need libgit2 {min_major}.{min_minor}.{min_patch} or newer, \
+         but this build uses {major}.{minor}.{patch}. Drop --depth/settings.fetch_depth \
+         or rebuild with the vendored libgit2"
+    )))
+}
+
+/// Error for a ref the server refused, flagged as not-fast-forward when the
+/// server's status says the remote branch has diverged
+fn rejected_ref_error(refname: &str, status: &str) -> git2::Error {
+    let diverged = ["non-fast-forward", "fetch first", "stale info"]
+        .iter()
+        .any(|reason| status.contains(reason));
+    let code = if diverged {
+        git2::ErrorCode::NotFastForward
+    } else {
+        git2::ErrorCode::GenericError
+    };
+    git2::Error::new(
+        code,
+        git2::ErrorClass::Reference,
+        format!("{refname} rejected: {status}"),
+    )
+}
+
+/// Whether a failed transfer is worth retrying: the connection dropped or
+/// timed out, as opposed to the remote refusing it
+///
+/// Authentication and certificate failures and rejected refs never are.
+#[must_use]
+pub fn is_transient_network_error(e: &git2::Error) -> bool {
+    match e.code() {
+        ErrorCode::Auth | ErrorCode::Certificate | ErrorCode::NotFastForward => return false,
+        ErrorCode::Timeout => return true,
+        _ => {}
+    }
+    if !matches!(
+        e.class(),
+        ErrorClass::Net | ErrorClass::Os | ErrorClass::Ssh | ErrorClass::Http
+    ) {
+        return false;
+    }
+    let message = e.message().to_ascii_lowercase();
+    [
+        "timed out",
+        "timeout",
+        "connection reset",
+        "reset by peer",
+        "connection refused",
+        "connection aborted",
+        "broken pipe",
+        "early eof",
+        "unexpected eof",
+        "network is unreachable",
+        "temporary failure in name resolution",
+        "status code: 502",
+        "status code: 503",
+        "status code: 504",
+    ]
+    .iter()
+    .any(|pattern| message.contains(pattern))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -412,4 +1159,360 @@ mod tests {
         // New repos don't have a current branch until first commit
         assert!(ops.current_branch().is_err());
     }
+
+    #[test]
+    fn test_stage_and_commit_without_git_binary() {
+        let temp_dir = TempDir::new().unwrap();
+        let ops = GitOperations::init(temp_dir.path()).unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+        std::fs::write(temp_dir.path().join("b.txt"), "b").unwrap();
+
+        ops.stage_paths(&[Path::new("a.txt"), Path::new("b.txt")])
+            .unwrap();
+        let first = ops
+            .commit_index("first", &CommitOverrides::default())
+            .unwrap();
+        assert!(ops.is_clean().unwrap());
+        assert!(ops
+            .commit_index("empty", &CommitOverrides::default())
+            .is_err());
+
+        std::fs::remove_file(temp_dir.path().join("b.txt")).unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "changed").unwrap();
+        ops.stage_tracked().unwrap();
+        let second = ops
+            .commit_index("second", &CommitOverrides::default())
+            .unwrap();
+        let head = ops.head_commit().unwrap();
+        assert_eq!(head.id(), second);
+        assert_eq!(head.parent_id(0).unwrap(), first);
+        assert!(head.tree().unwrap().get_name("b.txt").is_none());
+
+        let original_author = head.author().name().map(String::from);
+        let amended = ops
+            .amend_index(Some("second, reworded"), &CommitOverrides::default())
+            .unwrap();
+        let head = ops.head_commit().unwrap();
+        assert_eq!(head.id(), amended);
+        assert_eq!(head.message(), Some("second, reworded"));
+        assert_eq!(head.parent_id(0).unwrap(), first);
+        assert_eq!(head.author().name().map(String::from), original_author);
+
+        let overrides = CommitOverrides {
+            author: Some("Jane Doe <jane@example.com>".to_string()),
+            date: Some("2024-05-01T12:00:00+02:00".to_string()),
+        };
+        ops.amend_index(None, &overrides).unwrap();
+        let head = ops.head_commit().unwrap();
+        assert_eq!(head.message(), Some("second, reworded"));
+        assert_eq!(head.author().email(), Some("jane@example.com"));
+        assert_eq!(head.author().when().seconds(), 1_714_557_600);
+        assert_ne!(head.committer().email(), Some("jane@example.com"));
+    }
+
+    #[test]
+    fn test_commit_hooks_and_signing() {
+        let temp_dir = TempDir::new().unwrap();
+        let ops = GitOperations::init(temp_dir.path()).unwrap();
+        assert!(ops.commit_hooks().is_empty());
+        assert!(!ops.signs_commits());
+
+        let hooks = temp_dir.path().join(".git/hooks");
+        std::fs::create_dir_all(&hooks).unwrap();
+        std::fs::write(hooks.join("pre-commit.sample"), "").unwrap();
+        std::fs::write(hooks.join("commit-msg"), "").unwrap();
+        assert_eq!(ops.commit_hooks(), vec!["commit-msg"]);
+
+        let mut config = ops.inner().config().unwrap();
+        config.set_bool("commit.gpgsign", true).unwrap();
+        assert!(ops.signs_commits());
+    }
+
+    #[test]
+    fn test_vendored_libgit2_supports_shallow() {
+        assert!(shallow_supported());
+        assert!(ensure_shallow_supported().is_ok());
+    }
+
+    #[test]
+    fn test_tag_push_reports_created_and_up_to_date() {
+        let temp_dir = TempDir::new().unwrap();
+        let bare = temp_dir.path().join("mirror.git");
+        Repository::init_bare(&bare).unwrap();
+        let ops = GitOperations::init(temp_dir.path().join("work")).unwrap();
+        let repo = ops.inner();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let first = repo
+            .commit(Some("HEAD"), &sig, &sig, "first", &tree, &[])
+            .unwrap();
+        let first = repo.find_commit(first).unwrap();
+        repo.tag("v1", first.as_object(), &sig, "Release 1", false)
+            .unwrap();
+        repo.tag_lightweight("light", first.as_object(), false)
+            .unwrap();
+        let second = repo
+            .commit(Some("HEAD"), &sig, &sig, "second", &tree, &[&first])
+            .unwrap();
+        let second = repo.find_commit(second).unwrap();
+        repo.tag("v2", second.as_object(), &sig, "Release 2", false)
+            .unwrap();
+        repo.remote("mirror", bare.to_str().unwrap()).unwrap();
+
+        assert_eq!(ops.tags().unwrap(), vec!["light", "v1", "v2"]);
+        let branch = ops.current_branch().unwrap();
+        assert_eq!(
+            ops.annotated_tags_reachable_from(&branch).unwrap(),
+            vec!["v1", "v2"]
+        );
+
+        let updates = ops
+            .push_with_updates("mirror", &["refs/tags/v1:refs/tags/v1"])
+            .unwrap();
+        assert_eq!(
+            updates,
+            vec![RefUpdate {
+                refname: "refs/tags/v1".to_string(),
+                change: RefChange::Created,
+            }]
+        );
+
+        let updates = ops
+            .push_with_updates(
+                "mirror",
+                &["refs/tags/v1:refs/tags/v1", "refs/tags/v2:refs/tags/v2"],
+            )
+            .unwrap();
+        let changes: Vec<RefChange> = updates.iter().map(|update| update.change).collect();
+        assert_eq!(changes, vec![RefChange::UpToDate, RefChange::Created]);
+
+        // The annotated tag object itself reached the mirror
+        let mirror = Repository::open_bare(&bare).unwrap();
+        let tag = mirror
+            .find_reference("refs/tags/v2")
+            .unwrap()
+            .peel_to_tag()
+            .unwrap();
+        assert_eq!(tag.message(), Some("Release 2"));
+    }
+
+    #[test]
+    fn test_is_clean_with_dirty_submodule() {
+        let temp_dir = TempDir::new().unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let commit_all = |repo: &Repository, message: &str| {
+            let mut index = repo.index().unwrap();
+            index
+                .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+                .unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parents: Vec<Commit> = repo
+                .head()
+                .ok()
+                .and_then(|h| h.peel_to_commit().ok())
+                .into_iter()
+                .collect();
+            let parents: Vec<&Commit> = parents.iter().collect();
+            repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+                .unwrap();
+        };
+
+        let lib_path = temp_dir.path().join("lib");
+        let lib = Repository::init(&lib_path).unwrap();
+        std::fs::write(lib_path.join("lib.txt"), "lib\n").unwrap();
+        commit_all(&lib, "lib");
+
+        let app_path = temp_dir.path().join("app");
+        let ops = GitOperations::init(&app_path).unwrap();
+        let mut submodule = ops
+            .inner()
+            .submodule(lib_path.to_str().unwrap(), Path::new("lib"), true)
+            .unwrap();
+        submodule.clone(None).unwrap();
+        submodule.add_finalize().unwrap();
+        commit_all(ops.inner(), "add lib");
+        assert!(ops.is_clean().unwrap());
+
+        std::fs::write(app_path.join("lib").join("lib.txt"), "changed\n").unwrap();
+        assert!(!ops.is_clean().unwrap());
+    }
+
+    #[test]
+    fn test_stash_save_and_pop() {
+        let temp_dir = TempDir::new().unwrap();
+        let ops = GitOperations::init(temp_dir.path()).unwrap();
+        let repo = ops.inner();
+        let file = temp_dir.path().join("notes.txt");
+        let commit_file = |content: &str, message: &str| {
+            std::fs::write(&file, content).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new("notes.txt")).unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+            let parents: Vec<Commit> = repo
+                .head()
+                .ok()
+                .and_then(|h| h.peel_to_commit().ok())
+                .into_iter()
+                .collect();
+            let parents: Vec<&Commit> = parents.iter().collect();
+            repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+                .unwrap();
+        };
+        commit_file("base\n", "base");
+
+        assert_eq!(ops.stash_save("autostash").unwrap(), None);
+
+        std::fs::write(&file, "local\n").unwrap();
+        std::fs::write(temp_dir.path().join("untracked.txt"), "new\n").unwrap();
+        assert!(ops.stash_save("autostash").unwrap().is_some());
+        assert!(ops.is_clean().unwrap());
+
+        assert_eq!(ops.stash_pop().unwrap(), StashPop::Restored);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "local\n");
+        assert!(temp_dir.path().join("untracked.txt").exists());
+
+        // Upstream changes the same line while the local edit is stashed
+        std::fs::remove_file(temp_dir.path().join("untracked.txt")).unwrap();
+        ops.stash_save("autostash").unwrap();
+        commit_file("upstream\n", "upstream");
+        assert!(matches!(ops.stash_pop().unwrap(), StashPop::Conflicts(_)));
+        let mut repo = Repository::open(temp_dir.path()).unwrap();
+        let mut stashes = 0;
+        repo.stash_foreach(|_, _, _| {
+            stashes += 1;
+            true
+        })
+        .unwrap();
+        assert_eq!(stashes, 1, "a conflicted pop keeps the stash");
+    }
+
+    #[test]
+    fn test_push_with_lease_refuses_a_moved_branch() {
+        let temp_dir = TempDir::new().unwrap();
+        let bare = temp_dir.path().join("mirror.git");
+        Repository::init_bare(&bare).unwrap();
+        let ops = GitOperations::init(temp_dir.path().join("work")).unwrap();
+        let repo = ops.inner();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let old = repo
+            .commit(Some("HEAD"), &sig, &sig, "first", &tree, &[])
+            .unwrap();
+        repo.remote("mirror", bare.to_str().unwrap()).unwrap();
+        let branch = ops.current_branch().unwrap();
+        let refname = format!("refs/heads/{branch}");
+        ops.push("mirror", &[&format!("{refname}:{refname}")])
+            .unwrap();
+
+        // "Amend": a new commit replacing the pushed one
+        let amended = repo
+            .commit(None, &sig, &sig, "amended", &tree, &[])
+            .unwrap();
+        repo.reference(&refname, amended, true, "amend").unwrap();
+        let refspec = format!("+{refname}:{refname}");
+
+        let stale = vec![(refname.clone(), amended)];
+        let err = ops
+            .push_with_lease("mirror", &[&refspec], &stale)
+            .unwrap_err();
+        assert!(err.is_non_fast_forward(), "{err}");
+        let mirror = Repository::open_bare(&bare).unwrap();
+        assert_eq!(mirror.refname_to_id(&refname).unwrap(), old);
+
+        ops.push_with_lease("mirror", &[&refspec], &[(refname.clone(), old)])
+            .unwrap();
+        assert_eq!(mirror.refname_to_id(&refname).unwrap(), amended);
+    }
+
+    #[test]
+    fn test_rejected_ref_error() {
+        let err = MultiGitError::GitError(rejected_ref_error("refs/heads/main", "fetch first"));
+        assert!(err.is_non_fast_forward());
+
+        let err = rejected_ref_error("refs/heads/main", "pre-receive hook declined");
+        assert_eq!(err.code(), git2::ErrorCode::GenericError);
+        assert!(err.message().contains("hook declined"));
+    }
+
+    #[test]
+    fn test_transient_network_errors() {
+        let net = |message| git2::Error::new(ErrorCode::GenericError, ErrorClass::Net, message);
+        assert!(is_transient_network_error(&net("connection reset by peer")));
+        assert!(is_transient_network_error(&net("early EOF")));
+        assert!(is_transient_network_error(&git2::Error::new(
+            ErrorCode::Timeout,
+            ErrorClass::Os,
+            "operation timed out"
+        )));
+
+        assert!(!is_transient_network_error(&net(
+            "unexpected http status code: 404"
+        )));
+        assert!(!is_transient_network_error(&git2::Error::new(
+            ErrorCode::Auth,
+            ErrorClass::Http,
+            "connection reset while authenticating"
+        )));
+        assert!(!is_transient_network_error(&rejected_ref_error(
+            "refs/heads/main",
+            "non-fast-forward"
+        )));
+    }
+
+    #[test]
+    fn test_network_retries_stop_at_permanent_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let ops = GitOperations::init(temp_dir.path())
+            .unwrap()
+            .with_retry_config(RetryConfig {
+                max_attempts: 3,
+                initial_backoff: Duration::ZERO,
+                max_backoff: Duration::ZERO,
+                backoff_multiplier: 1.0,
+            });
+        let reset = || {
+            MultiGitError::GitError(git2::Error::new(
+                ErrorCode::GenericError,
+                ErrorClass::Net,
+                "connection reset by peer",
+            ))
+        };
+
+        let mut attempts = 0;
+        let result = ops.with_network_retries("Fetch from", "origin", |_| {
+            attempts += 1;
+            if attempts < 3 {
+                Err(reset())
+            } else {
+                Ok(attempts)
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+
+        attempts = 0;
+        let result: Result<()> = ops.with_network_retries("Fetch from", "origin", |_| {
+            attempts += 1;
+            Err(reset())
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 3);
+
+        attempts = 0;
+        let result: Result<()> = ops.with_network_retries("Push to", "origin", |_| {
+            attempts += 1;
+            Err(MultiGitError::GitError(rejected_ref_error(
+                "refs/heads/main",
+                "fetch first",
+            )))
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
 }
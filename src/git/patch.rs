@@ -0,0 +1,303 @@
+//! Hunk-level staging
+//!
+//! Reads the unstaged changes of modified files as hunks, lets a caller
+//! select whole hunks or the smaller pieces they split into, and stages the
+//! selection by applying a patch to the index. Unselected removals in a
+//! partly selected hunk become context and unselected additions are dropped,
+//! so the working tree is never touched.
+
+use crate::utils::error::{MultiGitError, Result};
+use git2::{ApplyLocation, Delta, Diff, DiffOptions, Patch, Repository};
+use std::fmt::Write as _;
+use std::ops::Range;
+
+/// One line of a hunk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HunkLine {
+    /// `' '` for context, `'+'` for an addition, `'-'` for a removal
+    pub origin: char,
+    /// The line, with its newline unless it ends the file without one
+    pub content: String,
+}
+
+impl HunkLine {
+    fn is_change(&self) -> bool {
+        self.origin != ' '
+    }
+}
+
+/// A hunk of the index-to-working-tree diff of one file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    /// The `@@ -a,b +c,d @@` header as git2 reports it
+    pub header: String,
+    /// First line of the hunk in the index version
+    pub old_start: u32,
+    /// Context, removed and added lines, in order
+    pub lines: Vec<HunkLine>,
+}
+
+impl Hunk {
+    /// Ranges of `lines` holding each run of consecutive changes
+    ///
+    /// A hunk with more than one piece can be split: each piece is then
+    /// staged or skipped on its own.
+    #[must_use]
+    pub fn pieces(&self) -> Vec<Range<usize>> {
+        let mut pieces = Vec::new();
+        let mut start = None;
+        for (index, line) in self.lines.iter().enumerate() {
+            match (line.is_change(), start) {
+                (true, None) => start = Some(index),
+                (false, Some(from)) => {
+                    pieces.push(from..index);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(from) = start {
+            pieces.push(from..self.lines.len());
+        }
+        pieces
+    }
+}
+
+/// The unstaged hunks of one modified file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilePatch {
+    /// Path relative to the working directory
+    pub path: String,
+    /// Hunks in file order
+    pub hunks: Vec<Hunk>,
+}
+
+/// Which pieces (see [`Hunk::pieces`]) of each hunk of a file to stage
+pub type Selection = Vec<Vec<bool>>;
+
+/// Unstaged hunks of every modified text file, sorted by path
+///
+/// New, deleted and binary files have no hunks to pick from and are left to
+/// whole-file staging.
+pub fn unstaged_changes(repo: &Repository) -> Result<Vec<FilePatch>> {
+    let diff = repo
+        .diff_index_to_workdir(None, Some(DiffOptions::new().context_lines(3)))
+        .map_err(MultiGitError::GitError)?;
+
+    let mut files = Vec::new();
+    for index in 0..diff.deltas().len() {
+        let delta = diff.get_delta(index).expect("delta index is in range");
+        if delta.status() != Delta::Modified || delta.flags().is_binary() {
+            continue;
+        }
+        let Some(file_patch) = Patch::from_diff(&diff, index).map_err(MultiGitError::GitError)?
+        else {
+            continue;
+        };
+        let Some(path) = delta.new_file().path().and_then(|p| p.to_str()) else {
+            continue;
+        };
+
+        let mut hunks = Vec::new();
+        for hunk_index in 0..file_patch.num_hunks() {
+            let (hunk, line_count) = file_patch
+                .hunk(hunk_index)
+                .map_err(MultiGitError::GitError)?;
+            let mut lines = Vec::with_capacity(line_count);
+            for line_index in 0..line_count {
+                let line = file_patch
+                    .line_in_hunk(hunk_index, line_index)
+                    .map_err(MultiGitError::GitError)?;
+                // '=', '>' and '<' only mark a missing newline at end of file,
+                // which the content already shows
+                if matches!(line.origin(), ' ' | '+' | '-') {
+                    lines.push(HunkLine {
+                        origin: line.origin(),
+                        content: String::from_utf8_lossy(line.content()).into_owned(),
+                    });
+                }
+            }
+            hunks.push(Hunk {
+                header: String::from_utf8_lossy(hunk.header())
+                    .trim_end()
+                    .to_string(),
+                old_start: hunk.old_start(),
+                lines,
+            });
+        }
+        if !hunks.is_empty() {
+            files.push(FilePatch {
+                path: path.to_string(),
+                hunks,
+            });
+        }
+    }
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// A unified diff staging the selected pieces of `file`, or `None` when
+/// nothing is selected
+#[must_use]
+pub fn patch_text(file: &FilePatch, selection: &[Vec<bool>]) -> Option<String> {
+    let mut body = String::new();
+    let mut offset: i64 = 0;
+
+    for (hunk, selected) in file.hunks.iter().zip(selection) {
+        if !selected.iter().any(|&s| s) {
+            continue;
+        }
+
+        let pieces = hunk.pieces();
+        let chosen = |line: usize| {
+            pieces
+                .iter()
+                .zip(selected)
+                .any(|(piece, &s)| s && piece.contains(&line))
+        };
+
+        let mut lines = String::new();
+        let (mut old_lines, mut new_lines) = (0_i64, 0_i64);
+        for (index, line) in hunk.lines.iter().enumerate() {
+            let origin = match (line.origin, chosen(index)) {
+                ('+', false) => continue,
+                ('-', false) => ' ',
+                (origin, _) => origin,
+            };
+            if origin != '+' {
+                old_lines += 1;
+            }
+            if origin != '-' {
+                new_lines += 1;
+            }
+            lines.push(origin);
+            lines.push_str(&line.content);
+            if !line.content.ends_with('\n') {
+                lines.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+
+        let old_start = i64::from(hunk.old_start);
+        let new_start = old_start + offset;
+        let _ = writeln!(
+            body,
+            "@@ -{old_start},{old_lines} +{new_start},{new_lines} @@"
+        );
+        body.push_str(&lines);
+        offset += new_lines - old_lines;
+    }
+
+    if body.is_empty() {
+        return None;
+    }
+    let path = &file.path;
+    Some(format!(
+        "diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n{body}"
+    ))
+}
+
+/// Stage the selected pieces of `file` into the index, returning whether
+/// anything was staged
+pub fn stage(repo: &Repository, file: &FilePatch, selection: &[Vec<bool>]) -> Result<bool> {
+    let Some(text) = patch_text(file, selection) else {
+        return Ok(false);
+    };
+    let diff = Diff::from_buffer(text.as_bytes()).map_err(MultiGitError::GitError)?;
+    repo.apply(&diff, ApplyLocation::Index, None).map_err(|e| {
+        MultiGitError::other(format!(
+            "Failed to stage hunks of {}: {}",
+            file.path,
+            e.message()
+        ))
+    })?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    /// A repository with `file.txt` holding lines `1`..=`20` committed
+    fn repo_with_numbers() -> (TempDir, Repository) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        edit(temp_dir.path(), &[]);
+        {
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new("file.txt")).unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+            repo.commit(Some("HEAD"), &sig, &sig, "Initial", &tree, &[])
+                .unwrap();
+        }
+        (temp_dir, repo)
+    }
+
+    /// Rewrite lines of `file.txt`, 1-based
+    fn edit(dir: &Path, edits: &[(usize, &str)]) {
+        let mut lines: Vec<String> = (1..=20).map(|n| n.to_string()).collect();
+        for (number, text) in edits {
+            lines[number - 1] = (*text).to_string();
+        }
+        std::fs::write(dir.join("file.txt"), lines.join("\n") + "\n").unwrap();
+    }
+
+    fn staged(repo: &Repository) -> String {
+        let mut index = repo.index().unwrap();
+        index.read(true).unwrap();
+        let entry = index.get_path(Path::new("file.txt"), 0).unwrap();
+        String::from_utf8(repo.find_blob(entry.id).unwrap().content().to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_stage_one_of_two_hunks() {
+        let (temp_dir, repo) = repo_with_numbers();
+        edit(temp_dir.path(), &[(2, "two"), (15, "fifteen")]);
+
+        let files = unstaged_changes(&repo).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].hunks.len(), 2);
+
+        assert!(stage(&repo, &files[0], &[vec![false], vec![true]]).unwrap());
+        let index = staged(&repo);
+        assert!(index.contains("\nfifteen\n"));
+        assert!(index.contains("\n2\n"));
+
+        // The working tree keeps both edits; only the skipped hunk is left
+        let remaining = unstaged_changes(&repo).unwrap();
+        assert_eq!(remaining[0].hunks.len(), 1);
+        assert!(remaining[0].hunks[0]
+            .lines
+            .iter()
+            .any(|line| line.content == "two\n"));
+    }
+
+    #[test]
+    fn test_split_hunk_stages_one_piece() {
+        let (temp_dir, repo) = repo_with_numbers();
+        edit(temp_dir.path(), &[(5, "five"), (9, "nine")]);
+
+        let files = unstaged_changes(&repo).unwrap();
+        let hunk = &files[0].hunks[0];
+        assert_eq!(files[0].hunks.len(), 1);
+        assert_eq!(hunk.pieces().len(), 2);
+
+        assert!(stage(&repo, &files[0], &[vec![false, true]]).unwrap());
+        let index = staged(&repo);
+        assert!(index.contains("\n5\n"));
+        assert!(index.contains("\nnine\n"));
+    }
+
+    #[test]
+    fn test_nothing_selected() {
+        let (temp_dir, repo) = repo_with_numbers();
+        edit(temp_dir.path(), &[(2, "two")]);
+        let files = unstaged_changes(&repo).unwrap();
+        assert_eq!(patch_text(&files[0], &[vec![false]]), None);
+        assert!(!stage(&repo, &files[0], &[vec![false]]).unwrap());
+    }
+}
@@ -0,0 +1,324 @@
+//! In-progress operation detection
+//!
+//! Recognises a repository left mid-way through a merge, rebase, cherry-pick
+//! or revert, so commands can explain the situation instead of failing on a
+//! detached HEAD or conflicted index.
+
+use crate::utils::error::{MultiGitError, Result};
+use git2::{Oid, Repository, RepositoryState};
+use std::fmt;
+use std::path::Path;
+
+/// Kind of operation git is in the middle of
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    /// `git merge` stopped (`MERGE_HEAD`)
+    Merge,
+    /// `git rebase` stopped (`rebase-merge/` or `rebase-apply/`)
+    Rebase,
+    /// `git am` stopped (`rebase-apply/applying`)
+    ApplyMailbox,
+    /// `git cherry-pick` stopped (`CHERRY_PICK_HEAD`)
+    CherryPick,
+    /// `git revert` stopped (`REVERT_HEAD`)
+    Revert,
+    /// `git bisect` session (`BISECT_LOG`)
+    Bisect,
+}
+
+impl OperationKind {
+    /// Name as used on the git command line
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Merge => "merge",
+            Self::Rebase => "rebase",
+            Self::ApplyMailbox => "am",
+            Self::CherryPick => "cherry-pick",
+            Self::Revert => "revert",
+            Self::Bisect => "bisect",
+        }
+    }
+
+    /// git arguments that abandon this operation and restore the prior state
+    #[must_use]
+    pub fn abort_args(self) -> [&'static str; 2] {
+        match self {
+            Self::Bisect => ["bisect", "reset"],
+            _ => [self.as_str(), "--abort"],
+        }
+    }
+
+    fn from_state(state: RepositoryState) -> Option<Self> {
+        match state {
+            RepositoryState::Clean => None,
+            RepositoryState::Merge => Some(Self::Merge),
+            RepositoryState::Rebase
+            | RepositoryState::RebaseInteractive
+            | RepositoryState::RebaseMerge => Some(Self::Rebase),
+            RepositoryState::ApplyMailbox | RepositoryState::ApplyMailboxOrRebase => {
+                Some(Self::ApplyMailbox)
+            }
+            RepositoryState::CherryPick | RepositoryState::CherryPickSequence => {
+                Some(Self::CherryPick)
+            }
+            RepositoryState::Revert | RepositoryState::RevertSequence => Some(Self::Revert),
+            RepositoryState::Bisect => Some(Self::Bisect),
+        }
+    }
+}
+
+/// An operation the repository is in the middle of
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationInProgress {
+    /// What is in progress
+    pub kind: OperationKind,
+    /// Current and total step, for rebases that record them
+    pub step: Option<(usize, usize)>,
+    /// Revisions still to test, for a bisect with good and bad commits marked
+    pub remaining: Option<usize>,
+    /// Files with unresolved conflicts in the index
+    pub conflicted_files: Vec<String>,
+}
+
+impl OperationInProgress {
+    /// Detect the operation in progress, if any
+    pub fn detect(repo: &Repository) -> Result<Option<Self>> {
+        let kind = match OperationKind::from_state(repo.state()) {
+            Some(kind) => kind,
+            None => return Ok(None),
+        };
+
+        let step = match kind {
+            OperationKind::Rebase | OperationKind::ApplyMailbox => rebase_step(repo.path()),
+            _ => None,
+        };
+        let remaining = match kind {
+            OperationKind::Bisect => bisect_remaining(repo),
+            _ => None,
+        };
+
+        let mut conflicted_files = Vec::new();
+        let index = repo.index()?;
+        if index.has_conflicts() {
+            for conflict in index.conflicts()? {
+                let conflict = conflict?;
+                if let Some(entry) = conflict
+                    .our
+                    .as_ref()
+                    .or(conflict.their.as_ref())
+                    .or(conflict.ancestor.as_ref())
+                {
+                    conflicted_files.push(String::from_utf8_lossy(&entry.path).into_owned());
+                }
+            }
+        }
+
+        Ok(Some(Self {
+            kind,
+            step,
+            remaining,
+            conflicted_files,
+        }))
+    }
+
+    /// How to finish or abandon the operation
+    #[must_use]
+    pub fn guidance(&self) -> String {
+        let command = self.kind.as_str();
+        match self.kind {
+            OperationKind::Bisect => {
+                "Mark commits with 'multigit bisect good|bad', or finish with 'multigit bisect reset'"
+                    .to_string()
+            }
+            OperationKind::Merge => {
+                "Resolve conflicts and run 'git commit', or abort with 'multigit abort'".to_string()
+            }
+            _ => format!(
+                "Resolve conflicts and run 'git {command} --continue', or abort with 'multigit abort'"
+            ),
+        }
+    }
+
+    /// Error for a command that must not run during this operation
+    #[must_use]
+    pub fn refuse(&self, action: &str) -> MultiGitError {
+        MultiGitError::other(format!("Cannot {action}: {self}. {}", self.guidance()))
+    }
+}
+
+impl fmt::Display for OperationInProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(remaining) = self.remaining {
+            let plural = if remaining == 1 { "" } else { "s" };
+            return write!(f, "bisecting: {remaining} revision{plural} left");
+        }
+        write!(f, "{} in progress", self.kind.as_str())?;
+        if let Some((current, total)) = self.step {
+            write!(f, " ({current}/{total})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Names git uses for bad and good commits in this bisect
+///
+/// `git bisect start --term-new/--term-old` records custom ones in `BISECT_TERMS`.
+fn bisect_terms(git_dir: &Path) -> (String, String) {
+    let terms = std::fs::read_to_string(git_dir.join("BISECT_TERMS")).unwrap_or_default();
+    let mut lines = terms.lines().map(str::trim).filter(|line| !line.is_empty());
+    match (lines.next(), lines.next()) {
+        (Some(bad), Some(good)) => (bad.to_string(), good.to_string()),
+        _ => ("bad".to_string(), "good".to_string()),
+    }
+}
+
+/// The commit currently marked bad in a bisect
+///
+/// Once no revisions are left this is the first bad commit.
+#[must_use]
+pub fn bisect_bad_commit(repo: &Repository) -> Option<Oid> {
+    let (bad, _) = bisect_terms(repo.path());
+    repo.refname_to_id(&format!("refs/bisect/{bad}")).ok()
+}
+
+/// Commits that may still be the first bad one, other than the bad commit itself
+///
+/// `None` until both a bad and at least one good commit are marked.
+fn bisect_remaining(repo: &Repository) -> Option<usize> {
+    let (_, good) = bisect_terms(repo.path());
+    let bad = bisect_bad_commit(repo)?;
+    let goods: Vec<Oid> = repo
+        .references_glob(&format!("refs/bisect/{good}-*"))
+        .ok()?
+        .flatten()
+        .filter_map(|reference| reference.target())
+        .collect();
+    if goods.is_empty() {
+        return None;
+    }
+
+    let mut revwalk = repo.revwalk().ok()?;
+    revwalk.push(bad).ok()?;
+    for oid in goods {
+        revwalk.hide(oid).ok()?;
+    }
+    Some(revwalk.count().saturating_sub(1))
+}
+
+/// Current and total step of a rebase, from the files git keeps in `.git`
+fn rebase_step(git_dir: &Path) -> Option<(usize, usize)> {
+    let read_number =
+        |path: &Path| -> Option<usize> { std::fs::read_to_string(path).ok()?.trim().parse().ok() };
+
+    let merge_dir = git_dir.join("rebase-merge");
+    if merge_dir.is_dir() {
+        return Some((
+            read_number(&merge_dir.join("msgnum"))?,
+            read_number(&merge_dir.join("end"))?,
+        ));
+    }
+
+    let apply_dir = git_dir.join("rebase-apply");
+    Some((
+        read_number(&apply_dir.join("next"))?,
+        read_number(&apply_dir.join("last"))?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn repo_with_commit() -> (TempDir, Repository) {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        {
+            let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+            let tree_id = repo.index().unwrap().write_tree().unwrap();
+            let tree = repo.find_tree(tree_id).unwrap();
+            repo.commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[])
+                .unwrap();
+        }
+        (dir, repo)
+    }
+
+    #[test]
+    fn test_clean_repository() {
+        let (_dir, repo) = repo_with_commit();
+        assert_eq!(OperationInProgress::detect(&repo).unwrap(), None);
+    }
+
+    #[test]
+    fn test_detect_merge() {
+        let (_dir, repo) = repo_with_commit();
+        let head = repo.head().unwrap().target().unwrap();
+        std::fs::write(repo.path().join("MERGE_HEAD"), format!("{head}\n")).unwrap();
+
+        let op = OperationInProgress::detect(&repo).unwrap().unwrap();
+        assert_eq!(op.kind, OperationKind::Merge);
+        assert_eq!(op.to_string(), "merge in progress");
+        assert!(op.guidance().contains("multigit abort"));
+        assert_eq!(op.kind.abort_args(), ["merge", "--abort"]);
+    }
+
+    #[test]
+    fn test_detect_rebase_progress() {
+        let (_dir, repo) = repo_with_commit();
+        let rebase_dir = repo.path().join("rebase-merge");
+        std::fs::create_dir(&rebase_dir).unwrap();
+        std::fs::write(rebase_dir.join("interactive"), "").unwrap();
+        std::fs::write(rebase_dir.join("msgnum"), "2\n").unwrap();
+        std::fs::write(rebase_dir.join("end"), "5\n").unwrap();
+
+        let op = OperationInProgress::detect(&repo).unwrap().unwrap();
+        assert_eq!(op.kind, OperationKind::Rebase);
+        assert_eq!(op.to_string(), "rebase in progress (2/5)");
+        assert!(op
+            .refuse("push")
+            .to_string()
+            .starts_with("Cannot push: rebase in progress (2/5)."));
+    }
+
+    #[test]
+    fn test_detect_bisect_remaining() {
+        let (_dir, repo) = repo_with_commit();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let good = repo.head().unwrap().target().unwrap();
+        for message in ["second", "third", "fourth", "fifth"] {
+            let parent = repo.head().unwrap().peel_to_commit().unwrap();
+            let tree = parent.tree().unwrap();
+            repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &[&parent])
+                .unwrap();
+        }
+        let bad = repo.head().unwrap().target().unwrap();
+        std::fs::write(repo.path().join("BISECT_LOG"), "").unwrap();
+
+        // Nothing marked yet
+        let op = OperationInProgress::detect(&repo).unwrap().unwrap();
+        assert_eq!(op.kind, OperationKind::Bisect);
+        assert_eq!(op.to_string(), "bisect in progress");
+
+        repo.reference("refs/bisect/bad", bad, false, "bisect")
+            .unwrap();
+        repo.reference(&format!("refs/bisect/good-{good}"), good, false, "bisect")
+            .unwrap();
+        let op = OperationInProgress::detect(&repo).unwrap().unwrap();
+        assert_eq!(op.remaining, Some(3));
+        assert_eq!(op.to_string(), "bisecting: 3 revisions left");
+        assert_eq!(bisect_bad_commit(&repo), Some(bad));
+        assert_eq!(op.kind.abort_args(), ["bisect", "reset"]);
+    }
+
+    #[test]
+    fn test_detect_cherry_pick() {
+        let (_dir, repo) = repo_with_commit();
+        let head = repo.head().unwrap().target().unwrap();
+        std::fs::write(repo.path().join("CHERRY_PICK_HEAD"), format!("{head}\n")).unwrap();
+
+        let op = OperationInProgress::detect(&repo).unwrap().unwrap();
+        assert_eq!(op.kind, OperationKind::CherryPick);
+        assert_eq!(op.step, None);
+    }
+}
@@ -0,0 +1,143 @@
+//! Commit and tag signing keys
+//!
+//! `security.signing_key` names the key commits and tags are signed with. It
+//! is passed to git explicitly (`git tag -u <key>`, `git commit
+//! --gpg-sign=<key>`), so it wins over `user.signingkey`. git2 can't sign, so
+//! all signing goes through the `git` binary.
+
+use git2::Repository;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// How git signs, from `gpg.format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigningFormat {
+    /// GPG (the default)
+    OpenPgp,
+    /// SSH keys
+    Ssh,
+    /// X.509 certificates via gpgsm
+    X509,
+}
+
+impl SigningFormat {
+    /// The format `repo` (or the global git config) is set up for
+    #[must_use]
+    pub fn of(repo: &Repository) -> Self {
+        let format = repo
+            .config()
+            .and_then(|config| config.get_string("gpg.format"))
+            .unwrap_or_default();
+        match format.as_str() {
+            "ssh" => Self::Ssh,
+            "x509" => Self::X509,
+            _ => Self::OpenPgp,
+        }
+    }
+}
+
+/// `user.signingkey` from the git config, if set
+#[must_use]
+pub fn git_signing_key(repo: &Repository) -> Option<String> {
+    repo.config()
+        .and_then(|config| config.get_string("user.signingkey"))
+        .ok()
+        .filter(|key| !key.trim().is_empty())
+}
+
+/// `git commit` arguments to sign with `key`
+#[must_use]
+pub fn commit_sign_args(key: &str) -> Vec<String> {
+    vec![format!("--gpg-sign={key}")]
+}
+
+/// `git tag` arguments to create a tag signed with `key`, or with git's own
+/// signing setup when there is none
+#[must_use]
+pub fn tag_sign_args(key: Option<&str>) -> Vec<String> {
+    match key {
+        Some(key) => vec!["-s".to_string(), "-u".to_string(), key.to_string()],
+        None => vec!["-s".to_string()],
+    }
+}
+
+/// How to configure a signing key
+#[must_use]
+pub fn remediation() -> &'static str {
+    "Set one under [security] in the multigit config: signing_key = \"<key-id>\"\n\
+     (or for git alone: git config user.signingkey <key-id>)\n\
+     For SSH keys also run: git config gpg.format ssh"
+}
+
+/// Check that `key` can sign in `format`: a GPG secret key gpg knows about,
+/// or an SSH key file that exists
+///
+/// Returns what was found, or why the key can't be used.
+pub fn verify_key(repo: &Repository, key: &str, format: SigningFormat) -> Result<String, String> {
+    match format {
+        SigningFormat::OpenPgp => {
+            let program = repo
+                .config()
+                .and_then(|config| config.get_string("gpg.program"))
+                .unwrap_or_else(|_| "gpg".to_string());
+            let output = Command::new(&program)
+                .args(["--list-secret-keys", "--with-colons", key])
+                .output()
+                .map_err(|e| format!("can't run {program}: {e}"))?;
+            if output.status.success() {
+                Ok(format!("GPG secret key '{key}' found"))
+            } else {
+                Err(format!("gpg has no secret key '{key}'"))
+            }
+        }
+        SigningFormat::Ssh => {
+            if key.starts_with("key::") {
+                return Ok("literal SSH public key".to_string());
+            }
+            let path = expand_home(key);
+            if path.is_file() {
+                Ok(format!("SSH key {}", path.display()))
+            } else {
+                Err(format!("SSH key file {} does not exist", path.display()))
+            }
+        }
+        SigningFormat::X509 => Ok(format!("X.509 key '{key}' (not checked)")),
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().map_or_else(|| PathBuf::from(path), |home| home.join(rest)),
+        None => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_ssh_key_verification() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        assert_eq!(SigningFormat::of(&repo), SigningFormat::OpenPgp);
+
+        repo.config().unwrap().set_str("gpg.format", "ssh").unwrap();
+        assert_eq!(SigningFormat::of(&repo), SigningFormat::Ssh);
+
+        let key = temp_dir.path().join("id_ed25519.pub");
+        let key = key.to_str().unwrap();
+        assert!(verify_key(&repo, key, SigningFormat::Ssh).is_err());
+        std::fs::write(key, "ssh-ed25519 AAAA test").unwrap();
+        assert!(verify_key(&repo, key, SigningFormat::Ssh).is_ok());
+        assert!(verify_key(&repo, "key::ssh-ed25519 AAAA", SigningFormat::Ssh).is_ok());
+    }
+
+    #[test]
+    fn test_sign_args() {
+        assert_eq!(commit_sign_args("ABCD"), vec!["--gpg-sign=ABCD"]);
+        assert_eq!(tag_sign_args(Some("ABCD")), vec!["-s", "-u", "ABCD"]);
+        assert_eq!(tag_sign_args(None), vec!["-s"]);
+    }
+}
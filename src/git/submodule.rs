@@ -0,0 +1,142 @@
+//! Submodule inspection
+//!
+//! Reports each submodule's state relative to the commit the superproject
+//! records for it, so status, fetch, push and commit can account for them.
+
+use crate::utils::error::{MultiGitError, Result};
+use git2::{Repository, SubmoduleIgnore, SubmoduleStatus};
+use std::fmt;
+use std::path::PathBuf;
+
+/// State of a submodule's checkout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmoduleState {
+    /// Checked out at the recorded commit with no local changes
+    UpToDate,
+    /// Not cloned yet (`git submodule update --init`)
+    Uninitialized,
+    /// Checked out at a different commit than the superproject records
+    OutOfDate,
+    /// Has uncommitted changes or untracked files
+    Modified,
+}
+
+impl SubmoduleState {
+    fn from_status(status: SubmoduleStatus) -> Self {
+        if status.contains(SubmoduleStatus::WD_UNINITIALIZED) {
+            Self::Uninitialized
+        } else if status.intersects(
+            SubmoduleStatus::WD_INDEX_MODIFIED
+                | SubmoduleStatus::WD_WD_MODIFIED
+                | SubmoduleStatus::WD_UNTRACKED,
+        ) {
+            Self::Modified
+        } else if status.intersects(SubmoduleStatus::WD_MODIFIED | SubmoduleStatus::INDEX_MODIFIED)
+        {
+            Self::OutOfDate
+        } else {
+            Self::UpToDate
+        }
+    }
+}
+
+impl fmt::Display for SubmoduleState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Self::UpToDate => "up to date",
+            Self::Uninitialized => "not initialized",
+            Self::OutOfDate => "checked out at a different commit than recorded",
+            Self::Modified => "has uncommitted changes",
+        };
+        f.write_str(text)
+    }
+}
+
+/// A submodule of a repository
+#[derive(Debug, Clone)]
+pub struct SubmoduleInfo {
+    /// Name from `.gitmodules`
+    pub name: String,
+    /// Path relative to the superproject's working directory
+    pub path: PathBuf,
+    /// Absolute path of the checkout
+    pub workdir: PathBuf,
+    /// State of the checkout
+    pub state: SubmoduleState,
+}
+
+impl SubmoduleInfo {
+    /// Whether the checkout exists and can be fetched or pushed
+    #[must_use]
+    pub fn is_initialized(&self) -> bool {
+        self.state != SubmoduleState::Uninitialized
+    }
+}
+
+/// Submodules of `repo`, sorted by path
+pub fn list(repo: &Repository) -> Result<Vec<SubmoduleInfo>> {
+    let workdir = match repo.workdir() {
+        Some(workdir) => workdir,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut submodules = Vec::new();
+    for submodule in repo.submodules().map_err(MultiGitError::GitError)? {
+        let name = submodule.name().unwrap_or_default().to_string();
+        let status = repo
+            .submodule_status(&name, SubmoduleIgnore::None)
+            .map_err(MultiGitError::GitError)?;
+        submodules.push(SubmoduleInfo {
+            path: submodule.path().to_path_buf(),
+            workdir: workdir.join(submodule.path()),
+            name,
+            state: SubmoduleState::from_status(status),
+        });
+    }
+    submodules.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(submodules)
+}
+
+/// Submodules with uncommitted changes, which a commit would not include
+pub fn dirty(repo: &Repository) -> Result<Vec<SubmoduleInfo>> {
+    Ok(list(repo)?
+        .into_iter()
+        .filter(|submodule| submodule.state == SubmoduleState::Modified)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_from_status() {
+        assert_eq!(
+            SubmoduleState::from_status(SubmoduleStatus::IN_HEAD | SubmoduleStatus::IN_WD),
+            SubmoduleState::UpToDate
+        );
+        assert_eq!(
+            SubmoduleState::from_status(SubmoduleStatus::WD_UNINITIALIZED),
+            SubmoduleState::Uninitialized
+        );
+        assert_eq!(
+            SubmoduleState::from_status(SubmoduleStatus::WD_MODIFIED),
+            SubmoduleState::OutOfDate
+        );
+        // Local changes matter more than the commit being off
+        assert_eq!(
+            SubmoduleState::from_status(
+                SubmoduleStatus::WD_MODIFIED | SubmoduleStatus::WD_UNTRACKED
+            ),
+            SubmoduleState::Modified
+        );
+    }
+
+    #[test]
+    fn test_list_without_submodules() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+        assert!(list(&repo).unwrap().is_empty());
+    }
+}
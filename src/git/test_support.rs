@@ -0,0 +1,47 @@
+//! Repository fixtures shared by unit tests
+
+use git2::{IndexEntry, IndexTime, Repository};
+use tempfile::TempDir;
+
+/// `file.txt` conflicted at all three stages
+pub(crate) const CONTENT_CONFLICT: [(&str, u16, &str); 3] = [
+    ("file.txt", 1, "base\n"),
+    ("file.txt", 2, "ours\n"),
+    ("file.txt", 3, "theirs\n"),
+];
+
+/// Fresh repository whose index holds the given `(path, stage, content)`
+/// entries, with `worktree` `(path, content)` files written next to it
+pub(crate) fn conflicted_repo(
+    entries: &[(&str, u16, &str)],
+    worktree: &[(&str, &str)],
+) -> (TempDir, Repository) {
+    let dir = TempDir::new().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    {
+        let mut index = repo.index().unwrap();
+        for &(path, stage, content) in entries {
+            index
+                .add(&IndexEntry {
+                    ctime: IndexTime::new(0, 0),
+                    mtime: IndexTime::new(0, 0),
+                    dev: 0,
+                    ino: 0,
+                    mode: 0o100_644,
+                    uid: 0,
+                    gid: 0,
+                    file_size: 0,
+                    id: repo.blob(content.as_bytes()).unwrap(),
+                    flags: (stage << 12) | u16::try_from(path.len()).unwrap(),
+                    flags_extended: 0,
+                    path: path.as_bytes().to_vec(),
+                })
+                .unwrap();
+        }
+        index.write().unwrap();
+    }
+    for (path, content) in worktree {
+        std::fs::write(dir.path().join(path), content).unwrap();
+    }
+    (dir, repo)
+}
@@ -0,0 +1,201 @@
+//! Working-tree summary
+//!
+//! Groups uncommitted changes the way `git status -s` does: staged changes
+//! (index vs HEAD), unstaged changes (working tree vs index), untracked files
+//! and unresolved conflicts. A file staged and then edited again shows up as
+//! both staged and unstaged.
+
+use crate::utils::error::{MultiGitError, Result};
+use git2::{Repository, Status, StatusOptions};
+
+/// One changed file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedFile {
+    /// Path relative to the working directory
+    pub path: String,
+    /// `git status -s` letter: `A`dded, `M`odified, `D`eleted, `R`enamed,
+    /// `T`ype changed, `U`nmerged or `?` untracked
+    pub code: char,
+}
+
+impl std::fmt::Display for ChangedFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.code, self.path)
+    }
+}
+
+/// Uncommitted changes, by category, each sorted by path
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkingTreeSummary {
+    /// Changes in the index, ready to commit
+    pub staged: Vec<ChangedFile>,
+    /// Changes to tracked files not yet staged
+    pub unstaged: Vec<ChangedFile>,
+    /// Files git doesn't track
+    pub untracked: Vec<ChangedFile>,
+    /// Files with unresolved merge conflicts
+    pub conflicted: Vec<ChangedFile>,
+}
+
+impl WorkingTreeSummary {
+    /// Whether there are no changes of any kind
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.staged.is_empty()
+            && self.unstaged.is_empty()
+            && self.untracked.is_empty()
+            && self.conflicted.is_empty()
+    }
+
+    /// Non-empty categories with their label, in display order
+    #[must_use]
+    pub fn categories(&self) -> Vec<(&'static str, &[ChangedFile])> {
+        [
+            ("Conflicted", self.conflicted.as_slice()),
+            ("Staged", self.staged.as_slice()),
+            ("Unstaged", self.unstaged.as_slice()),
+            ("Untracked", self.untracked.as_slice()),
+        ]
+        .into_iter()
+        .filter(|(_, files)| !files.is_empty())
+        .collect()
+    }
+}
+
+/// Summarize the uncommitted changes in `repo`'s working directory
+pub fn summarize(repo: &Repository) -> Result<WorkingTreeSummary> {
+    let statuses = repo
+        .statuses(Some(
+            StatusOptions::new()
+                .include_untracked(true)
+                .recurse_untracked_dirs(true)
+                .include_ignored(false)
+                .renames_head_to_index(true),
+        ))
+        .map_err(MultiGitError::GitError)?;
+
+    let mut summary = WorkingTreeSummary::default();
+    for entry in statuses.iter() {
+        let Some(path) = entry.path() else {
+            continue;
+        };
+        let status = entry.status();
+        let file = |code| ChangedFile {
+            path: path.to_string(),
+            code,
+        };
+
+        if status.is_conflicted() {
+            summary.conflicted.push(file('U'));
+            continue;
+        }
+        if status.is_wt_new() {
+            summary.untracked.push(file('?'));
+            continue;
+        }
+        if let Some(code) = staged_code(status) {
+            summary.staged.push(file(code));
+        }
+        if let Some(code) = unstaged_code(status) {
+            summary.unstaged.push(file(code));
+        }
+    }
+
+    for files in [
+        &mut summary.staged,
+        &mut summary.unstaged,
+        &mut summary.untracked,
+        &mut summary.conflicted,
+    ] {
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+    Ok(summary)
+}
+
+fn staged_code(status: Status) -> Option<char> {
+    if status.is_index_new() {
+        Some('A')
+    } else if status.is_index_modified() {
+        Some('M')
+    } else if status.is_index_deleted() {
+        Some('D')
+    } else if status.is_index_renamed() {
+        Some('R')
+    } else if status.is_index_typechange() {
+        Some('T')
+    } else {
+        None
+    }
+}
+
+fn unstaged_code(status: Status) -> Option<char> {
+    if status.is_wt_modified() {
+        Some('M')
+    } else if status.is_wt_deleted() {
+        Some('D')
+    } else if status.is_wt_renamed() {
+        Some('R')
+    } else if status.is_wt_typechange() {
+        Some('T')
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_summarize_groups_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let write = |name: &str, content: &str| {
+            std::fs::write(temp_dir.path().join(name), content).unwrap();
+        };
+        let stage = |name: &str| {
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new(name)).unwrap();
+            index.write().unwrap();
+        };
+
+        write("tracked.txt", "one\n");
+        write("removed.txt", "gone\n");
+        stage("tracked.txt");
+        stage("removed.txt");
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial", &tree, &[])
+            .unwrap();
+        assert!(summarize(&repo).unwrap().is_clean());
+
+        write("added.txt", "new\n");
+        stage("added.txt");
+        write("tracked.txt", "two\n");
+        stage("tracked.txt");
+        write("tracked.txt", "three\n");
+        std::fs::remove_file(temp_dir.path().join("removed.txt")).unwrap();
+        write("scratch.txt", "?\n");
+
+        let summary = summarize(&repo).unwrap();
+        let render = |files: &[ChangedFile]| -> Vec<String> {
+            files.iter().map(ToString::to_string).collect()
+        };
+        assert_eq!(
+            render(&summary.staged),
+            vec!["A added.txt", "M tracked.txt"]
+        );
+        assert_eq!(
+            render(&summary.unstaged),
+            vec!["D removed.txt", "M tracked.txt"]
+        );
+        assert_eq!(render(&summary.untracked), vec!["? scratch.txt"]);
+        assert!(summary.conflicted.is_empty());
+        let labels: Vec<&str> = summary.categories().iter().map(|(l, _)| *l).collect();
+        assert_eq!(labels, vec!["Staged", "Unstaged", "Untracked"]);
+    }
+}
@@ -0,0 +1,235 @@
+//! Worktree management
+//!
+//! A repository can have linked worktrees next to its main working directory,
+//! each with its own checked-out branch. They share one `.git` directory, so
+//! `MultiGit` resolves the repository config through the main worktree when a
+//! linked one has none of its own.
+
+use crate::utils::error::{MultiGitError, Result};
+use git2::{BranchType, Repository, StatusOptions, WorktreeAddOptions, WorktreePruneOptions};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// A worktree of a repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeInfo {
+    /// Name of a linked worktree; `None` for the main worktree
+    pub name: Option<String>,
+    /// Working directory
+    pub path: PathBuf,
+    /// Checked-out branch; `None` for a detached HEAD
+    pub branch: Option<String>,
+    /// Whether the command runs in this worktree
+    pub is_current: bool,
+    /// Locked against pruning (`git worktree lock`)
+    pub locked: bool,
+}
+
+impl WorktreeInfo {
+    /// Name to show: the linked worktree's name, or "main"
+    #[must_use]
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or("main")
+    }
+}
+
+/// The main repository of `repo`, which is `repo` itself unless it is a linked worktree
+pub fn main_repository(repo: &Repository) -> Result<Repository> {
+    if repo.is_worktree() {
+        Repository::open(repo.commondir()).map_err(MultiGitError::GitError)
+    } else {
+        Repository::open(repo.path()).map_err(MultiGitError::GitError)
+    }
+}
+
+/// Working directory of the main worktree when `path` is inside a linked worktree
+#[must_use]
+pub fn main_workdir_of_linked(path: &Path) -> Option<PathBuf> {
+    let repo = Repository::discover(path).ok()?;
+    if !repo.is_worktree() {
+        return None;
+    }
+    main_repository(&repo)
+        .ok()?
+        .workdir()
+        .map(Path::to_path_buf)
+}
+
+/// Every worktree of `repo`: the main one first, then linked ones by name
+pub fn list(repo: &Repository) -> Result<Vec<WorktreeInfo>> {
+    let main = main_repository(repo)?;
+    let current = repo.workdir().map(canonical);
+    let is_current = |path: &Path| current.as_deref() == Some(canonical(path).as_path());
+
+    let mut worktrees = Vec::new();
+    if let Some(path) = main.workdir() {
+        worktrees.push(WorktreeInfo {
+            name: None,
+            path: path.to_path_buf(),
+            branch: head_branch(&main),
+            is_current: is_current(path),
+            locked: false,
+        });
+    }
+
+    let names = main.worktrees().map_err(MultiGitError::GitError)?;
+    let mut names: Vec<&str> = names.iter().flatten().collect();
+    names.sort_unstable();
+    for name in names {
+        let worktree = main.find_worktree(name).map_err(MultiGitError::GitError)?;
+        let path = worktree.path().to_path_buf();
+        let branch = Repository::open_from_worktree(&worktree)
+            .ok()
+            .and_then(|repo| head_branch(&repo));
+        worktrees.push(WorktreeInfo {
+            name: Some(name.to_string()),
+            is_current: is_current(&path),
+            path,
+            branch,
+            locked: !matches!(worktree.is_locked(), Ok(git2::WorktreeLockStatus::Unlocked)),
+        });
+    }
+
+    Ok(worktrees)
+}
+
+/// The worktree the command runs in
+pub fn current(repo: &Repository) -> Result<Option<WorktreeInfo>> {
+    Ok(list(repo)?.into_iter().find(|worktree| worktree.is_current))
+}
+
+/// Add a linked worktree at `path`, named after its last path component
+///
+/// Checks out `branch`, creating it from HEAD if it doesn't exist; without
+/// `branch`, a new branch named after the worktree is created.
+pub fn add(repo: &Repository, path: &Path, branch: Option<&str>) -> Result<WorktreeInfo> {
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| {
+            MultiGitError::invalid_input(format!("Invalid worktree path: {}", path.display()))
+        })?
+        .to_string();
+
+    let main = main_repository(repo)?;
+    let reference = match branch {
+        Some(branch) => {
+            let local = main
+                .find_branch(branch, BranchType::Local)
+                .or_else(|_| {
+                    let head = main.head()?.peel_to_commit()?;
+                    main.branch(branch, &head, false)
+                })
+                .map_err(MultiGitError::GitError)?;
+            Some(local.into_reference())
+        }
+        None => None,
+    };
+
+    let mut options = WorktreeAddOptions::new();
+    options.reference(reference.as_ref());
+    main.worktree(&name, path, Some(&options))
+        .map_err(MultiGitError::GitError)?;
+    info!("Added worktree '{}' at {}", name, path.display());
+
+    list(&main)?
+        .into_iter()
+        .find(|worktree| worktree.name.as_deref() == Some(name.as_str()))
+        .ok_or_else(|| MultiGitError::other(format!("Worktree '{name}' was not registered")))
+}
+
+/// Remove the linked worktree `name` and its working directory
+///
+/// Refuses when it has uncommitted changes or is locked, unless `force`.
+pub fn remove(repo: &Repository, name: &str, force: bool) -> Result<()> {
+    let main = main_repository(repo)?;
+    let worktree = main
+        .find_worktree(name)
+        .map_err(|_| MultiGitError::invalid_input(format!("No worktree named '{name}'")))?;
+
+    if !force {
+        if let Ok(linked) = Repository::open_from_worktree(&worktree) {
+            let statuses = linked
+                .statuses(Some(StatusOptions::new().include_untracked(true)))
+                .map_err(MultiGitError::GitError)?;
+            if !statuses.is_empty() {
+                return Err(MultiGitError::invalid_input(format!(
+                    "Worktree '{name}' has uncommitted changes; commit them or use --force"
+                )));
+            }
+        }
+    }
+
+    worktree
+        .prune(Some(
+            WorktreePruneOptions::new()
+                .valid(true)
+                .locked(force)
+                .working_tree(true),
+        ))
+        .map_err(MultiGitError::GitError)?;
+    info!("Removed worktree '{}'", name);
+    Ok(())
+}
+
+fn head_branch(repo: &Repository) -> Option<String> {
+    let head = repo.head().ok()?;
+    if head.is_branch() {
+        head.shorthand().map(str::to_string)
+    } else {
+        None
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn repo_with_commit(path: &Path) -> Repository {
+        let repo = Repository::init(path).unwrap();
+        {
+            let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+            let tree_id = repo.index().unwrap().write_tree().unwrap();
+            let tree = repo.find_tree(tree_id).unwrap();
+            repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+                .unwrap();
+        }
+        repo
+    }
+
+    #[test]
+    fn test_add_list_remove() {
+        let temp = TempDir::new().unwrap();
+        let repo = repo_with_commit(&temp.path().join("main"));
+
+        let added = add(&repo, &temp.path().join("feature-wt"), Some("feature")).unwrap();
+        assert_eq!(added.name.as_deref(), Some("feature-wt"));
+        assert_eq!(added.branch.as_deref(), Some("feature"));
+
+        let worktrees = list(&repo).unwrap();
+        assert_eq!(worktrees.len(), 2);
+        assert_eq!(worktrees[0].label(), "main");
+        assert!(worktrees[0].is_current);
+        assert!(!worktrees[1].is_current);
+
+        // Seen from the linked worktree, the main repository is found
+        let linked = Repository::open(temp.path().join("feature-wt")).unwrap();
+        assert_eq!(
+            current(&linked).unwrap().unwrap().name.as_deref(),
+            Some("feature-wt")
+        );
+        assert_eq!(
+            canonical(&main_workdir_of_linked(&temp.path().join("feature-wt")).unwrap()),
+            canonical(&temp.path().join("main"))
+        );
+
+        remove(&repo, "feature-wt", false).unwrap();
+        assert_eq!(list(&repo).unwrap().len(), 1);
+        assert!(!temp.path().join("feature-wt").exists());
+    }
+}
@@ -3,7 +3,7 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use multigit::core::config::Config;
-use multigit::utils::logger::{init_logger, LogLevel, LoggerConfig};
+use multigit::utils::logger::{init_logger, LogFormat, LogLevel, LoggerConfig};
 
 #[derive(Parser)]
 #[command(name = env!("CARGO_BIN_NAME"))]
@@ -17,14 +17,55 @@ struct Cli {
     #[arg(short, long, action = clap::ArgAction::Count)]
     verbose: u8,
 
-    /// Output in JSON format
+    /// Output in JSON format (logs too, as with --log-format json)
     #[arg(long)]
     json: bool,
 
+    /// Console log format: plain, json, compact, pretty or logfmt
+    #[arg(long, global = true, value_name = "FORMAT")]
+    log_format: Option<LogFormat>,
+
     /// Disable colored output
     #[arg(long)]
     no_color: bool,
 
+    /// Draw status icons in plain ASCII, e.g. [ok] and [!] (overrides settings.icons)
+    #[arg(long, global = true)]
+    ascii: bool,
+
+    /// Print long output directly instead of through $GIT_PAGER/$PAGER
+    #[arg(long, global = true)]
+    no_pager: bool,
+
+    /// Configuration profile to use (overrides settings.default_profile)
+    #[arg(long, global = true, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Also write a debug log, as JSON lines with secrets redacted, to this file
+    #[arg(long, global = true, value_name = "PATH")]
+    log_file: Option<std::path::PathBuf>,
+
+    /// Print time spent in pushes, fetches and API calls when the command ends
+    #[arg(long, global = true)]
+    debug_timings: bool,
+
+    /// Parallel remote operations for this run (overrides settings.max_parallel)
+    #[arg(long, global = true, value_parser = clap::value_parser!(u64).range(1..))]
+    concurrency: Option<u64>,
+
+    /// Write to the audit log for this run, whatever security.audit_log says
+    #[arg(long, global = true, conflicts_with = "no_audit")]
+    audit: bool,
+
+    /// Keep this run out of the audit log, whatever security.audit_log says
+    #[arg(long, global = true)]
+    no_audit: bool,
+
+    /// Wait up to this many seconds for another multigit process to release
+    /// the repository lock (default: fail straight away)
+    #[arg(long, global = true, value_name = "SECS")]
+    lock_timeout: Option<u64>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -36,6 +77,38 @@ enum Commands {
         /// Skip interactive setup
         #[arg(short, long)]
         no_interactive: bool,
+
+        /// Remotes to add, as provider:username (comma-separated)
+        #[arg(long, value_delimiter = ',', value_name = "PROVIDER:USER")]
+        remotes: Vec<multigit::cli::commands::init::RemoteSpec>,
+
+        /// Copy the remotes of another repository (or a config file)
+        #[arg(long, value_name = "PATH")]
+        from: Option<std::path::PathBuf>,
+    },
+
+    /// Clone a repository, optionally adding mirror remotes
+    Clone {
+        /// URL of the repository to clone
+        url: String,
+
+        /// Directory to clone into (default: the repository name)
+        path: Option<String>,
+
+        /// Provider to add as a mirror remote (repeatable)
+        #[arg(long = "mirror")]
+        mirrors: Vec<String>,
+
+        /// Only fetch the last N commits (default: settings.fetch_depth)
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        depth: Option<u32>,
+    },
+
+    /// Configure MultiGit from the remotes of an existing git clone
+    Adopt {
+        /// Use URL owners as usernames and MULTIGIT_<PROVIDER>_TOKEN for tokens
+        #[arg(short, long)]
+        no_interactive: bool,
     },
 
     /// Interactive setup wizard (easiest way to get started)
@@ -50,7 +123,11 @@ enum Commands {
     },
 
     /// Interactive staging - visually select files to stage
-    Add,
+    Add {
+        /// Pick individual hunks to stage, like `git add -p`
+        #[arg(short, long)]
+        patch: bool,
+    },
 
     /// Create repository on all configured platforms
     Create {
@@ -61,13 +138,25 @@ enum Commands {
         #[arg(short, long)]
         description: Option<String>,
 
-        /// Make repository private
-        #[arg(short, long)]
+        /// Make repository private (the default unless settings.default_private = false)
+        #[arg(short, long, conflicts_with = "public")]
         private: bool,
 
+        /// Make repository public
+        #[arg(long)]
+        public: bool,
+
         /// Interactive mode
         #[arg(short, long)]
         interactive: bool,
+
+        /// Delete repositories created by this run if any platform fails
+        #[arg(long)]
+        rollback: bool,
+
+        /// Show what would be created on each platform without creating anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Manage remotes
@@ -89,6 +178,39 @@ enum Commands {
         /// Specific remotes to push to
         #[arg(long)]
         remotes: Vec<String>,
+
+        /// Push to the enabled remotes in this group
+        #[arg(long, conflicts_with = "remotes")]
+        group: Option<String>,
+
+        /// Also push to remotes whose repository is archived
+        #[arg(long)]
+        include_archived: bool,
+
+        /// First push the checked-out branch of each submodule to its remotes
+        #[arg(long)]
+        recurse_submodules: bool,
+
+        /// Make the branch track the primary remote (default for branches
+        /// without an upstream)
+        #[arg(short = 'u', long)]
+        set_upstream: bool,
+
+        /// Leave the branch's upstream alone
+        #[arg(long, conflicts_with = "set_upstream")]
+        no_set_upstream: bool,
+
+        /// Also push every local tag
+        #[arg(long)]
+        tags: bool,
+
+        /// Also push annotated tags that point into the pushed branch
+        #[arg(long, conflicts_with = "tags")]
+        follow_tags: bool,
+
+        /// Push every local tag but not the branch
+        #[arg(long, conflicts_with_all = ["tags", "follow_tags", "branch", "set_upstream", "recurse_submodules"])]
+        tags_only: bool,
     },
 
     /// Pull from primary remote
@@ -96,16 +218,29 @@ enum Commands {
         /// Remote to pull from
         #[arg(long, default_value = "origin")]
         from: String,
+
+        /// Stash uncommitted changes before pulling and restore them after
+        #[arg(long)]
+        autostash: bool,
     },
 
     /// Fetch from remotes
     Fetch {
-        /// Specific remotes to fetch from
+        /// Remotes to fetch from, optionally followed by refspecs for a single
+        /// remote (e.g. `origin refs/pull/123/head`)
         remotes: Vec<String>,
 
         /// Fetch from all configured remotes
         #[arg(short, long)]
         all: bool,
+
+        /// Fetch from the enabled remotes in this group
+        #[arg(long, conflicts_with_all = ["remotes", "all"])]
+        group: Option<String>,
+
+        /// Also fetch every remote of each submodule
+        #[arg(long)]
+        recurse_submodules: bool,
     },
 
     /// Synchronize all remotes
@@ -117,6 +252,38 @@ enum Commands {
         /// Dry run - show what would be done
         #[arg(long)]
         dry_run: bool,
+
+        /// Specific remotes to sync with
+        #[arg(long)]
+        remotes: Vec<String>,
+
+        /// Sync with the enabled remotes in this group
+        #[arg(long)]
+        group: Option<String>,
+
+        /// Sync all local branches matching the include/exclude globs
+        #[arg(long, conflicts_with = "branch")]
+        all_branches: bool,
+
+        /// Branch globs to include (e.g. "feature/*"); overrides sync.include_branches
+        #[arg(long, requires = "all_branches")]
+        include_branches: Vec<String>,
+
+        /// Branch globs to exclude (e.g. "release/**"); added to sync.exclude_branches
+        #[arg(long, requires = "all_branches")]
+        exclude_branches: Vec<String>,
+
+        /// Print the per-remote timing summary as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Also push to remotes whose repository is archived
+        #[arg(long)]
+        include_archived: bool,
+
+        /// Stash uncommitted changes before syncing and restore them after
+        #[arg(long)]
+        autostash: bool,
     },
 
     /// Show sync status
@@ -124,6 +291,14 @@ enum Commands {
         /// Show detailed information
         #[arg(short, long)]
         detailed: bool,
+
+        /// Only report; don't offer to push or pull out-of-sync remotes
+        #[arg(long)]
+        no_interactive: bool,
+
+        /// Write the report to this file instead of stdout (`-` for stdout)
+        #[arg(short, long)]
+        output: Option<multigit::utils::pager::Destination>,
     },
 
     /// Branch operations
@@ -144,12 +319,51 @@ enum Commands {
         action: ConflictCommands,
     },
 
+    /// Manage configuration profiles
+    Profile {
+        #[command(subcommand)]
+        action: ProfileCommands,
+    },
+
+    /// Inspect branch-to-remote routing rules
+    Routing {
+        #[command(subcommand)]
+        action: RoutingCommands,
+    },
+
+    /// Manage linked worktrees
+    Worktree {
+        #[command(subcommand)]
+        action: WorktreeCommands,
+    },
+
+    /// Find the commit that introduced a regression
+    Bisect {
+        #[command(subcommand)]
+        action: BisectCommands,
+    },
+
     /// Daemon operations
     Daemon {
         #[command(subcommand)]
         action: DaemonCommands,
     },
 
+    /// Check config, credentials, connectivity and the repository without changing anything
+    Validate {
+        /// Fail on warnings too, such as uncommitted changes
+        #[arg(long)]
+        strict: bool,
+
+        /// Seconds to wait for each remote (default: settings.api_timeout_seconds)
+        #[arg(long)]
+        timeout: Option<u64>,
+
+        /// Print the report as JSON (also enabled by the global --json)
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Run diagnostics and auto-fix issues
     Doctor {
         /// Automatically fix issues without prompting
@@ -159,7 +373,15 @@ enum Commands {
 
     /// Interactive conventional commit helper
     #[command(name = "cc")]
-    Cc,
+    Cc {
+        /// Record this author instead of the configured identity ("Name <email>")
+        #[arg(long)]
+        author: Option<String>,
+
+        /// Record this author date instead of now (e.g. 2024-05-01T12:00:00+02:00)
+        #[arg(long)]
+        date: Option<String>,
+    },
 
     /// Interactive commit history browser
     Log {
@@ -175,16 +397,52 @@ enum Commands {
         #[arg(short, long)]
         author: Option<String>,
 
+        /// Only commits since a date (2024-05-01) or age (3d, 2w, 6m, 1y)
+        #[arg(long)]
+        since: Option<String>,
+
         /// Show graph view
         #[arg(short, long)]
         graph: bool,
     },
 
+    /// Search commit messages and diffs across history
+    Search {
+        /// Text to look for (case-insensitive)
+        query: String,
+
+        /// Where to look: messages, diffs or both
+        #[arg(long = "in", default_value = "both")]
+        scope: multigit::git::history::SearchScope,
+
+        /// Only commits whose author name or email contains this
+        #[arg(short, long)]
+        author: Option<String>,
+
+        /// Only commits since a date (2024-05-01) or age (3d, 2w, 6m, 1y)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Maximum number of matching commits to show
+        #[arg(short, long, default_value_t = 50)]
+        limit: usize,
+    },
+
     /// Interactive branch switcher
     Switch {
-        /// Branch name to switch to
+        /// Branch name to switch to ("-" for the previous branch)
         branch: Option<String>,
 
+        /// Pick from the N most recently used branches (default 10)
+        #[arg(
+            long,
+            value_name = "N",
+            num_args = 0..=1,
+            default_missing_value = "10",
+            conflicts_with = "branch"
+        )]
+        recent: Option<usize>,
+
         /// Create new branch
         #[arg(short, long)]
         create: bool,
@@ -200,29 +458,135 @@ enum Commands {
     /// Undo operations (commits, changes, staging)
     Undo,
 
+    /// Abort an in-progress merge, rebase, cherry-pick or revert
+    Abort {
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+
     /// Amend last commit
     Amend {
         /// Amend without editing message
         #[arg(long)]
         no_edit: bool,
+
+        /// Then force-push (with lease) to every remote that had the old commit
+        #[arg(long)]
+        push: bool,
     },
 
     /// Generate changelog from conventional commits
     Changelog {
-        /// Generate since this tag/commit
+        /// Generate since this tag/commit (default: the previous tag)
         #[arg(short, long)]
         since: Option<String>,
 
+        /// Generate up to this tag/commit (default: HEAD)
+        #[arg(short, long)]
+        until: Option<String>,
+
         /// Output file
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Layout: conventional or keepachangelog
+        #[arg(long, default_value = "conventional")]
+        format: multigit::cli::commands::changelog::ChangelogFormat,
+    },
+
+    /// Cut a release: update the changelog, tag, and push to every remote
+    Release {
+        /// Version to release, also used as the tag name (e.g. v1.2.0)
+        version: String,
+
+        /// Tag message (default: "Release <version>")
+        #[arg(short, long)]
+        message: Option<String>,
+
+        /// Sign the tag with the configured GPG or SSH key (via `git tag -s`)
+        #[arg(short, long)]
+        sign: bool,
+
+        /// Changelog file (default: CHANGELOG.md)
+        #[arg(long)]
+        changelog: Option<String>,
+
+        /// Changelog layout: conventional or keepachangelog
+        #[arg(long, default_value = "conventional")]
+        format: multigit::cli::commands::changelog::ChangelogFormat,
+
+        /// Don't update the changelog
+        #[arg(long)]
+        no_changelog: bool,
+
+        /// Don't create the tag
+        #[arg(long)]
+        no_tag: bool,
+
+        /// Don't push to the remotes
+        #[arg(long)]
+        no_push: bool,
+
+        /// Also create a release with the notes on every remote whose host supports them (GitHub, GitLab)
+        #[arg(long)]
+        publish: bool,
+
+        /// Mark the published releases as pre-releases
+        #[arg(long)]
+        prerelease: bool,
+
+        /// Attach a file to the published releases (repeatable; implies --publish)
+        #[arg(long = "asset", value_name = "PATH")]
+        assets: Vec<std::path::PathBuf>,
+    },
+
+    /// Manage provider credentials
+    Auth {
+        #[command(subcommand)]
+        action: AuthCommands,
+    },
+
+    /// Query the audit log of credential, push, pull, sync and repository events
+    Audit {
+        /// Only this event type (e.g. push, force-push, sync, repo-create)
+        #[arg(long)]
+        event: Option<multigit::security::audit::AuditEventType>,
+
+        /// Only entries whose resource (remote:branch, remote:repo) contains this
+        #[arg(long)]
+        resource: Option<String>,
+
+        /// Only entries since a date (2024-05-01) or age (3d, 2w, 6m, 1y)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only failed operations
+        #[arg(long)]
+        failed: bool,
+
+        /// Show at most this many of the most recent entries
+        #[arg(short = 'n', long, default_value = "50")]
+        limit: usize,
+
+        /// Print the entries as JSON (also enabled by the global --json)
+        #[arg(long)]
+        json: bool,
     },
 
     /// Repository statistics
-    Stats,
+    Stats {
+        /// Write the report to this file instead of stdout (`-` for stdout)
+        #[arg(short, long)]
+        output: Option<multigit::utils::pager::Destination>,
+    },
 
     /// Smart merge from remotes
     Merge {
+        /// Commit or ref to merge as is, e.g. FETCH_HEAD after `fetch <remote> <refspec>`
+        #[arg(conflicts_with_all = ["from", "branch"])]
+        target: Option<String>,
+
         /// Remote to merge from
         #[arg(short, long)]
         from: Option<String>,
@@ -273,13 +637,21 @@ enum Commands {
         message: Option<String>,
 
         /// Amend previous commit
-        #[arg(short, long)]
+        #[arg(long)]
         amend: bool,
 
         /// All changes
         #[arg(short, long)]
         all: bool,
 
+        /// Record this author instead of the configured identity ("Name <email>")
+        #[arg(long)]
+        author: Option<String>,
+
+        /// Record this author date instead of now (e.g. 2024-05-01T12:00:00+02:00)
+        #[arg(long)]
+        date: Option<String>,
+
         /// Additional git commit arguments
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
@@ -287,6 +659,10 @@ enum Commands {
 
     /// Git diff (standard git diff)
     Diff {
+        /// Render old and new side by side instead of running `git diff`
+        #[arg(long)]
+        side_by_side: bool,
+
         /// Files or commits to diff
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
@@ -316,6 +692,10 @@ enum Commands {
     /// Git cherry-pick
     #[command(name = "cherry-pick")]
     CherryPick {
+        /// Apply the commits here and push the result to all remotes
+        #[arg(long)]
+        push: bool,
+
         /// Commits to cherry-pick
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
@@ -363,6 +743,10 @@ enum RemoteCommands {
         /// Custom API URL for self-hosted instances
         #[arg(long)]
         url: Option<String>,
+
+        /// API a `custom` provider is compatible with (gitea or gitlab)
+        #[arg(long)]
+        flavor: Option<String>,
     },
 
     /// List configured remotes
@@ -370,6 +754,10 @@ enum RemoteCommands {
         /// Show detailed information
         #[arg(short, long)]
         detailed: bool,
+
+        /// Print the remotes as a JSON array (also enabled by the global --json)
+        #[arg(long)]
+        json: bool,
     },
 
     /// Remove a remote
@@ -382,6 +770,10 @@ enum RemoteCommands {
     Test {
         /// Remote name to test (omit to test all)
         name: Option<String>,
+
+        /// Seconds to wait before giving up (default: settings.api_timeout_seconds)
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        timeout: Option<u64>,
     },
 
     /// Update remote credentials
@@ -389,6 +781,37 @@ enum RemoteCommands {
         /// Remote name to update
         name: String,
     },
+
+    /// Enable a remote, or every remote in a group
+    Enable {
+        /// Remote name
+        #[arg(required_unless_present = "group", conflicts_with = "group")]
+        name: Option<String>,
+
+        /// Enable every remote in this group
+        #[arg(long)]
+        group: Option<String>,
+    },
+
+    /// Disable a remote, or every remote in a group
+    Disable {
+        /// Remote name
+        #[arg(required_unless_present = "group", conflicts_with = "group")]
+        name: Option<String>,
+
+        /// Disable every remote in this group
+        #[arg(long)]
+        group: Option<String>,
+    },
+
+    /// Set the groups of a remote (no groups clears them)
+    Group {
+        /// Remote name
+        name: String,
+
+        /// Group labels, e.g. "mirrors"
+        groups: Vec<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -398,6 +821,10 @@ enum BranchCommands {
         /// Show verbose information
         #[arg(short, long)]
         verbose: bool,
+
+        /// Only show branches matching this glob (e.g. "feature/*")
+        #[arg(long)]
+        matching: Option<String>,
     },
 
     /// Create a new branch
@@ -419,6 +846,16 @@ enum BranchCommands {
         #[arg(short, long)]
         force: bool,
     },
+
+    /// Recreate a deleted branch from the reflog (lists candidates without a name)
+    Recover {
+        /// Branch name
+        name: Option<String>,
+
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -439,11 +876,17 @@ enum TagCommands {
         #[arg(short, long)]
         message: Option<String>,
 
-        /// Sign the tag with GPG
+        /// Sign the tag with the configured GPG or SSH key (via `git tag -s`)
         #[arg(short, long)]
         sign: bool,
     },
 
+    /// Verify a signed tag
+    Verify {
+        /// Tag name
+        name: String,
+    },
+
     /// Delete a tag
     Delete {
         /// Tag name
@@ -457,58 +900,345 @@ enum ConflictCommands {
     List,
 
     /// Resolve conflicts interactively
-    Resolve,
+    Resolve {
+        /// Strategy: ours, theirs, primary, manual, force
+        #[arg(short, long)]
+        strategy: Option<String>,
+
+        /// Resolve every conflicted file with --strategy, without prompting
+        #[arg(long, requires = "strategy")]
+        non_interactive: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum AuthCommands {
+    /// Replace remote tokens with new ones, verifying each before it is stored
+    Rotate {
+        /// Only rotate this remote's token (default: every remote)
+        remote: Option<String>,
+
+        /// Read new tokens from MULTIGIT_<REMOTE>_TOKEN or MULTIGIT_<PROVIDER>_TOKEN
+        #[arg(long)]
+        no_interactive: bool,
+    },
+
+    /// Move stored credentials to another backend (keyring or encrypted)
+    Migrate {
+        /// Backend to read credentials from
+        #[arg(long)]
+        from: multigit::core::auth::AuthBackend,
+
+        /// Backend to write credentials to
+        #[arg(long)]
+        to: multigit::core::auth::AuthBackend,
+
+        /// Remove each credential from the source once its copy is verified
+        #[arg(long)]
+        purge: bool,
+
+        /// Read the encrypted store's passphrase from MULTIGIT_STORE_PASSPHRASE
+        #[arg(long)]
+        no_interactive: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProfileCommands {
+    /// List profiles
+    List,
+
+    /// Create a profile with an empty configuration
+    Create {
+        /// Profile name
+        name: String,
+    },
+
+    /// Make a profile the default ("default" for the plain user config)
+    Switch {
+        /// Profile name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum RoutingCommands {
+    /// Show which remotes a branch would be pushed to
+    Test {
+        /// Branch name
+        branch: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum WorktreeCommands {
+    /// List worktrees
+    List,
+
+    /// Add a linked worktree
+    Add {
+        /// Directory for the worktree; its name becomes the worktree name
+        path: String,
+
+        /// Branch to check out, created from HEAD if missing
+        /// (default: a new branch named after the worktree)
+        #[arg(short, long)]
+        branch: Option<String>,
+    },
+
+    /// Remove a linked worktree and its directory
+    Remove {
+        /// Worktree name
+        name: String,
+
+        /// Remove even with uncommitted changes or a lock
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum BisectCommands {
+    /// Start bisecting
+    Start {
+        /// Commit known to have the regression
+        bad: Option<String>,
+
+        /// Commits known to be free of it
+        good: Vec<String>,
+    },
+
+    /// Mark a commit as good (default: the checked-out one)
+    Good {
+        /// Commit to mark
+        rev: Option<String>,
+    },
+
+    /// Mark a commit as bad (default: the checked-out one)
+    Bad {
+        /// Commit to mark
+        rev: Option<String>,
+    },
+
+    /// Skip a commit that can't be tested (default: the checked-out one)
+    Skip {
+        /// Commit to skip
+        rev: Option<String>,
+    },
+
+    /// Test each candidate with a command: exit 0 is good, 125 skips, other
+    /// codes below 128 are bad
+    Run {
+        /// Command and its arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true, required = true)]
+        command: Vec<String>,
+    },
+
+    /// Finish bisecting and return to the original branch
+    Reset,
 }
 
 #[derive(Subcommand)]
 enum DaemonCommands {
     /// Start the daemon
     Start {
-        /// Sync interval in minutes
-        #[arg(short, long, default_value = "5")]
-        interval: u64,
+        /// Sync interval in minutes (default: from config, or 5)
+        #[arg(short, long, conflicts_with = "cron")]
+        interval: Option<u64>,
+
+        /// Cron expression for sync times, e.g. "0 2,14 * * *"
+        #[arg(long)]
+        cron: Option<String>,
+
+        /// Print the next run times and the remotes to sync, then exit
+        #[arg(long)]
+        dry_run: bool,
+
+        /// How many upcoming runs --dry-run lists
+        #[arg(long, value_name = "N", default_value = "5", requires = "dry_run")]
+        runs: usize,
+    },
+
+    /// Sync once in the foreground and exit, for systemd timers or cron
+    RunOnce,
+
+    /// Run `daemon run-once` for this repository from systemd, launchd or
+    /// Task Scheduler
+    InstallService {
+        /// Sync interval in minutes (default: from config, or 5)
+        #[arg(short, long, conflicts_with = "cron")]
+        interval: Option<u64>,
+
+        /// Cron expression for sync times (systemd only)
+        #[arg(long)]
+        cron: Option<String>,
+
+        /// Print the unit and commands instead of installing
+        #[arg(long)]
+        print_only: bool,
+    },
+
+    /// Remove the unit installed by install-service
+    UninstallService {
+        /// Print what would be removed instead of removing it
+        #[arg(long)]
+        print_only: bool,
     },
 
     /// Stop the daemon
     Stop,
 
     /// Show daemon status
-    Status,
+    Status {
+        /// Print status, last sync, next run, and per-remote results as JSON
+        #[arg(long)]
+        json: bool,
+    },
 
     /// Show daemon logs
     Logs {
         /// Number of lines to show
         #[arg(short, long, default_value = "50")]
         lines: usize,
+
+        /// Keep streaming new log lines (like `tail -f`)
+        #[arg(short, long)]
+        follow: bool,
     },
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    // Select the profile before anything reads the config
+    if let Some(name) = &cli.profile {
+        multigit::core::profile::select(name)?;
+    }
+
+    // Read once for the start-up settings below; commands load their own
+    let config = Config::load().unwrap_or_default();
+
     // Initialize logger based on verbosity
     let log_level = LogLevel::from_verbosity(cli.verbose);
+    // settings.log_filter and RUST_LOG refine the -v level per module
     let logger_config = LoggerConfig::new()
         .with_level(log_level)
-        .with_color(!cli.no_color);
+        .with_color(!cli.no_color)
+        .with_log_filter(config.settings.log_filter.clone());
+
+    // --json predates --log-format and still means JSON logs
+    let logger_config = match cli.log_format {
+        Some(format) => logger_config.with_format(format),
+        None if cli.json => logger_config.with_format(LogFormat::Json),
+        None => logger_config,
+    };
+
+    // The daemon also logs to a size-rotated file so background runs leave a trail;
+    // other commands write a JSON debug log when --log-file or settings.log_file is set
+    let logger_config = if let Commands::Daemon {
+        action: DaemonCommands::Start { dry_run: false, .. } | DaemonCommands::RunOnce,
+    } = &cli.command
+    {
+        use multigit::daemon::DaemonService;
+        logger_config
+            .with_file(DaemonService::default_log_path().to_string_lossy())
+            .with_rotation(config.daemon.log_rotation())
+    } else if let Some(path) = cli
+        .log_file
+        .clone()
+        .or_else(|| config.settings.log_file.clone())
+    {
+        logger_config
+            .with_file(path.to_string_lossy())
+            .with_file_json()
+            .with_file_level(LogLevel::Debug)
+    } else {
+        logger_config
+    };
 
-    let logger_config = if cli.json {
-        logger_config.json()
+    let logger_config = if cli.debug_timings {
+        logger_config.with_timings()
     } else {
         logger_config
     };
 
     init_logger(logger_config)?;
+    // Built lazily: the guard prints the report when dropped
+    let _timings = if cli.debug_timings {
+        Some(multigit::utils::logger::TimingReportGuard)
+    } else {
+        None
+    };
+
+    if cli.no_pager || cli.json {
+        multigit::utils::pager::set_enabled(false);
+    }
+
+    // Colored status icons follow --no-color, NO_COLOR and settings.colored_output
+    if cli.no_color || std::env::var_os("NO_COLOR").is_some() || !config.settings.colored_output {
+        multigit::ui::formatter::colors::set_enabled(false);
+    }
+
+    // Status icons follow --ascii, then settings.icons, then what the terminal can show
+    let icons = if cli.ascii {
+        multigit::models::IconStyle::Ascii
+    } else {
+        config
+            .settings
+            .icons
+            .unwrap_or_else(multigit::ui::formatter::icons::detect)
+    };
+    multigit::ui::formatter::icons::set_style(icons);
+
+    if cli.audit || cli.no_audit {
+        Config::set_audit_override(Some(cli.audit));
+    }
+
+    if let Some(secs) = cli.lock_timeout {
+        multigit::core::repo_lock::set_wait(std::time::Duration::from_secs(secs));
+    }
+    let concurrency = cli
+        .concurrency
+        .map(|concurrency| usize::try_from(concurrency).unwrap_or(usize::MAX));
+    if let Some(concurrency) = concurrency {
+        let remotes = config.enabled_remotes().len();
+        if remotes > 0 && concurrency > remotes {
+            tracing::warn!(
+                "--concurrency {} exceeds the {} enabled remote(s); the extra slots stay idle",
+                concurrency,
+                remotes
+            );
+        }
+    }
 
     tracing::debug!("MultiGit {} starting", multigit::VERSION);
 
     // Handle commands
     let runtime = tokio::runtime::Runtime::new()?;
+    // First Ctrl-C winds down pushes and fetches, a second one exits. The
+    // daemon handles SIGINT itself so it can remove its PID file first.
+    let daemon_running = matches!(
+        &cli.command,
+        Commands::Daemon {
+            action: DaemonCommands::Start { dry_run: false, .. } | DaemonCommands::RunOnce,
+        }
+    );
+    if !daemon_running {
+        runtime.spawn(multigit::utils::interrupt::watch_ctrl_c());
+    }
 
     match cli.command {
-        Commands::Init { no_interactive: _ } => {
+        Commands::Init {
+            no_interactive,
+            remotes,
+            from,
+        } => {
             use multigit::cli::commands::init;
-            init::execute(".")?;
+            if remotes.is_empty() && from.is_none() {
+                init::execute(".")?;
+            } else {
+                runtime.block_on(init::bootstrap(&remotes, from.as_deref(), !no_interactive))?;
+            }
         }
 
         Commands::Setup { provider, username } => {
@@ -522,63 +1252,171 @@ fn main() -> Result<()> {
             }
         }
 
-        Commands::Add => {
+        Commands::Add { patch } => {
             use multigit::cli::commands::add;
-            add::execute()?;
+            add::execute(patch)?;
         }
 
         Commands::Create {
             name,
             description,
             private,
+            public,
             interactive,
+            rollback,
+            dry_run,
         } => {
             use multigit::cli::commands::create;
             if interactive {
                 runtime.block_on(create::execute_interactive())?;
             } else {
-                runtime.block_on(create::execute(name, description, private))?;
+                let visibility = if public {
+                    Some(false)
+                } else {
+                    private.then_some(true)
+                };
+                runtime.block_on(create::execute(
+                    name,
+                    description,
+                    visibility,
+                    rollback,
+                    dry_run,
+                ))?;
             }
         }
 
         Commands::Remote { action } => {
-            handle_remote_command(action)?;
+            handle_remote_command(action, cli.json)?;
         }
 
         Commands::Push {
             branch,
             force,
             remotes,
+            group,
+            include_archived,
+            recurse_submodules,
+            set_upstream,
+            no_set_upstream,
+            tags,
+            follow_tags,
+            tags_only,
         } => {
-            use multigit::cli::commands::push;
-            runtime.block_on(push::execute(branch, force, remotes))?;
+            use multigit::cli::commands::push::{self, TagMode};
+            let set_upstream = if set_upstream {
+                Some(true)
+            } else if no_set_upstream {
+                Some(false)
+            } else {
+                None
+            };
+            let tags = if tags_only {
+                TagMode::Only
+            } else if tags {
+                TagMode::All
+            } else if follow_tags {
+                TagMode::Follow
+            } else {
+                TagMode::None
+            };
+            runtime.block_on(push::execute(
+                branch,
+                force,
+                remotes,
+                group,
+                include_archived,
+                recurse_submodules,
+                set_upstream,
+                tags,
+                concurrency,
+            ))?;
         }
 
-        Commands::Pull { from } => {
+        Commands::Pull { from, autostash } => {
             use multigit::cli::commands::pull;
-            pull::execute(Some(from), None)?;
+            pull::execute(Some(from), None, autostash)?;
         }
 
-        Commands::Fetch { remotes, all } => {
+        Commands::Fetch {
+            remotes,
+            all,
+            group,
+            recurse_submodules,
+        } => {
             use multigit::cli::commands::fetch;
-            runtime.block_on(fetch::execute(remotes, all))?;
+            runtime.block_on(fetch::execute(
+                remotes,
+                all,
+                group,
+                recurse_submodules,
+                concurrency,
+            ))?;
         }
 
-        Commands::Sync { branch, dry_run } => {
-            use multigit::cli::commands::sync;
-            runtime.block_on(sync::execute(branch, dry_run))?;
+        Commands::Sync {
+            branch,
+            dry_run,
+            remotes,
+            group,
+            all_branches,
+            include_branches,
+            exclude_branches,
+            json,
+            include_archived,
+            autostash,
+        } => {
+            use multigit::cli::commands::sync::{self, SyncOptions};
+            runtime.block_on(sync::execute(SyncOptions {
+                branch,
+                dry_run,
+                remotes,
+                group,
+                all_branches,
+                include_branches,
+                exclude_branches,
+                json,
+                include_archived,
+                autostash,
+                concurrency,
+            }))?;
         }
 
-        Commands::Status { detailed } => {
+        Commands::Clone {
+            url,
+            path,
+            mirrors,
+            depth,
+        } => {
+            use multigit::cli::commands::clone;
+            clone::execute(url, path, mirrors, depth)?;
+        }
+
+        Commands::Adopt { no_interactive } => {
+            use multigit::cli::commands::adopt;
+            runtime.block_on(adopt::execute(!no_interactive))?;
+        }
+
+        Commands::Status {
+            detailed,
+            no_interactive,
+            output,
+        } => {
             use multigit::cli::commands::status;
-            status::execute(detailed)?;
+            use std::io::IsTerminal;
+            let output = output.unwrap_or_default();
+            // Writing a report to a file is never interactive
+            let interactive = !no_interactive
+                && output.is_stdout()
+                && std::io::stdin().is_terminal()
+                && std::io::stdout().is_terminal();
+            runtime.block_on(status::execute(detailed, interactive, &output))?;
         }
 
         Commands::Branch { action } => {
             use multigit::cli::commands::branch::commands;
             match action {
-                BranchCommands::List { verbose } => {
-                    commands::list(verbose)?;
+                BranchCommands::List { verbose, matching } => {
+                    commands::list(verbose, matching)?;
                 }
                 BranchCommands::Create { name, from } => {
                     runtime.block_on(commands::create(name, from))?;
@@ -586,6 +1424,9 @@ fn main() -> Result<()> {
                 BranchCommands::Delete { name, force } => {
                     runtime.block_on(commands::delete(name, force))?;
                 }
+                BranchCommands::Recover { name, yes } => {
+                    commands::recover(name, yes)?;
+                }
             }
         }
 
@@ -602,38 +1443,64 @@ fn main() -> Result<()> {
                 } => {
                     commands::create(name, message, sign)?;
                 }
+                TagCommands::Verify { name } => {
+                    commands::verify(name)?;
+                }
                 TagCommands::Delete { name } => {
                     commands::delete(name)?;
                 }
             }
         }
 
+        Commands::Validate {
+            strict,
+            timeout,
+            json,
+        } => {
+            use multigit::cli::commands::validate;
+            runtime.block_on(validate::execute(strict, timeout, json || cli.json))?;
+        }
+
         Commands::Doctor { fix } => {
             use multigit::cli::commands::doctor;
-            doctor::execute(fix)?;
+            runtime.block_on(doctor::execute(fix))?;
         }
 
-        Commands::Cc => {
+        Commands::Cc { author, date } => {
             use multigit::cli::commands::conventional_commit;
-            conventional_commit::execute()?;
+            use multigit::git::identity::CommitOverrides;
+            conventional_commit::execute(&CommitOverrides { author, date })?;
         }
 
         Commands::Log {
             limit,
             branch,
             author,
+            since,
             graph,
         } => {
             use multigit::cli::commands::log;
             if graph {
                 log::show_graph(limit)?;
             } else {
-                log::execute(limit, branch, author)?;
+                log::execute(limit, branch, author, since)?;
             }
         }
 
+        Commands::Search {
+            query,
+            scope,
+            author,
+            since,
+            limit,
+        } => {
+            use multigit::cli::commands::search;
+            search::execute(&query, scope, author, since, limit)?;
+        }
+
         Commands::Switch {
             branch,
+            recent,
             create,
             from,
         } => {
@@ -641,7 +1508,7 @@ fn main() -> Result<()> {
             if create {
                 switch::create_and_switch(from)?;
             } else {
-                switch::execute(branch)?;
+                switch::execute(branch, recent)?;
             }
         }
 
@@ -655,24 +1522,106 @@ fn main() -> Result<()> {
             undo::execute()?;
         }
 
-        Commands::Amend { no_edit } => {
+        Commands::Abort { yes } => {
+            use multigit::cli::commands::abort;
+            abort::execute(yes)?;
+        }
+
+        Commands::Amend { no_edit, push } => {
             use multigit::cli::commands::amend;
-            amend::execute(no_edit)?;
+            runtime.block_on(amend::execute(no_edit, push, concurrency))?;
         }
 
-        Commands::Changelog { since, output } => {
+        Commands::Changelog {
+            since,
+            until,
+            output,
+            format,
+        } => {
             use multigit::cli::commands::changelog;
-            changelog::execute(since, output)?;
+            changelog::execute(since, until, output, format)?;
         }
 
-        Commands::Stats => {
+        Commands::Release {
+            version,
+            message,
+            sign,
+            changelog,
+            format,
+            no_changelog,
+            no_tag,
+            no_push,
+            publish,
+            prerelease,
+            assets,
+        } => {
+            use multigit::cli::commands::release::{self, ReleaseOptions};
+            let options = ReleaseOptions {
+                message,
+                sign,
+                changelog,
+                format,
+                no_changelog,
+                no_tag,
+                no_push,
+                publish: publish || !assets.is_empty(),
+                prerelease,
+                assets,
+            };
+            runtime.block_on(release::execute(version, options))?;
+        }
+
+        Commands::Auth { action } => {
+            use multigit::cli::commands::auth;
+            match action {
+                AuthCommands::Rotate {
+                    remote,
+                    no_interactive,
+                } => runtime.block_on(auth::rotate(remote, !no_interactive))?,
+                AuthCommands::Migrate {
+                    from,
+                    to,
+                    purge,
+                    no_interactive,
+                } => auth::migrate(from, to, purge, !no_interactive)?,
+            }
+        }
+
+        Commands::Audit {
+            event,
+            resource,
+            since,
+            failed,
+            limit,
+            json,
+        } => {
+            use multigit::cli::commands::audit;
+            use multigit::security::audit::AuditQuery;
+            let since = since
+                .map(|since| multigit::git::history::parse_since(&since, chrono::Utc::now()))
+                .transpose()?;
+            let query = AuditQuery {
+                event,
+                resource,
+                since,
+                failed_only: failed,
+                limit: Some(limit),
+            };
+            audit::execute(&query, json || cli.json)?;
+        }
+
+        Commands::Stats { output } => {
             use multigit::cli::commands::stats;
-            stats::execute()?;
+            stats::execute(&output.unwrap_or_default())?;
         }
 
-        Commands::Merge { from, branch } => {
+        Commands::Merge {
+            target,
+            from,
+            branch,
+        } => {
             use multigit::cli::commands::merge;
-            merge::execute(from, branch)?;
+            merge::execute(target, from, branch)?;
         }
 
         Commands::Backup { auto } => {
@@ -715,31 +1664,35 @@ fn main() -> Result<()> {
             message,
             amend,
             all,
+            author,
+            date,
             args,
         } => {
-            use multigit::cli::commands::git_passthrough;
-            let mut git_args = vec!["commit".to_string()];
-
-            if let Some(msg) = message {
-                git_args.push("-m".to_string());
-                git_args.push(msg);
-            }
-            if amend {
-                git_args.push("--amend".to_string());
-            }
-            if all {
-                git_args.push("-a".to_string());
-            }
-            git_args.extend(args);
-
-            git_passthrough::execute(git_args)?;
+            use multigit::cli::commands::commit;
+            use multigit::git::identity::CommitOverrides;
+            commit::execute(message, amend, all, args, &CommitOverrides { author, date })?;
         }
 
-        Commands::Diff { args } => {
-            use multigit::cli::commands::git_passthrough;
-            let mut git_args = vec!["diff".to_string()];
-            git_args.extend(args);
-            git_passthrough::execute(git_args)?;
+        Commands::Diff {
+            side_by_side,
+            mut args,
+        } => {
+            // `--side-by-side` after a revision lands in the trailing args
+            let side_by_side = side_by_side || {
+                let before = args.len();
+                args.retain(|arg| arg != "--side-by-side");
+                args.len() != before
+            };
+
+            if side_by_side {
+                use multigit::cli::commands::diff;
+                diff::execute_side_by_side(&args, cli.no_color)?;
+            } else {
+                use multigit::cli::commands::git_passthrough;
+                let mut git_args = vec!["diff".to_string()];
+                git_args.extend(args);
+                git_passthrough::execute(git_args)?;
+            }
         }
 
         Commands::Show { args } => {
@@ -763,11 +1716,21 @@ fn main() -> Result<()> {
             git_passthrough::execute(git_args)?;
         }
 
-        Commands::CherryPick { args } => {
-            use multigit::cli::commands::git_passthrough;
-            let mut git_args = vec!["cherry-pick".to_string()];
-            git_args.extend(args);
-            git_passthrough::execute(git_args)?;
+        Commands::CherryPick { push, mut args } => {
+            use multigit::cli::commands::{cherry_pick, git_passthrough};
+            // `--push` after a commit lands in the trailing args
+            let push = push || {
+                let before = args.len();
+                args.retain(|arg| arg != "--push");
+                args.len() != before
+            };
+            if push {
+                runtime.block_on(cherry_pick::execute(args))?;
+            } else {
+                let mut git_args = vec!["cherry-pick".to_string()];
+                git_args.extend(args);
+                git_passthrough::execute(git_args)?;
+            }
         }
 
         Commands::Rebase { args } => {
@@ -802,28 +1765,104 @@ fn main() -> Result<()> {
                 ConflictCommands::List => {
                     conflict::detect_conflicts()?;
                 }
-                ConflictCommands::Resolve => {
-                    // Use default fast-forward strategy
+                ConflictCommands::Resolve {
+                    strategy,
+                    non_interactive,
+                } => {
                     use multigit::core::conflict_resolver::ResolutionStrategy;
-                    conflict::resolve_conflicts(ResolutionStrategy::FastForwardOnly)?;
+                    // Default to the safe fast-forward strategy
+                    let strategy = strategy
+                        .as_deref()
+                        .map(conflict::parse_strategy)
+                        .transpose()?
+                        .unwrap_or(ResolutionStrategy::FastForwardOnly);
+                    if non_interactive {
+                        conflict::resolve_non_interactive(strategy)?;
+                    } else {
+                        conflict::resolve_conflicts(strategy)?;
+                    }
                 }
             }
         }
 
+        Commands::Profile { action } => {
+            use multigit::cli::commands::profile;
+            match action {
+                ProfileCommands::List => profile::list()?,
+                ProfileCommands::Create { name } => profile::create(&name)?,
+                ProfileCommands::Switch { name } => profile::switch(&name)?,
+            }
+        }
+
+        Commands::Routing { action } => {
+            use multigit::cli::commands::routing;
+            match action {
+                RoutingCommands::Test { branch } => routing::test(&branch)?,
+            }
+        }
+
+        Commands::Worktree { action } => {
+            use multigit::cli::commands::worktree;
+            match action {
+                WorktreeCommands::List => worktree::list()?,
+                WorktreeCommands::Add { path, branch } => {
+                    worktree::add(&path, branch.as_deref())?;
+                }
+                WorktreeCommands::Remove { name, force } => worktree::remove(&name, force)?,
+            }
+        }
+
+        Commands::Bisect { action } => {
+            use multigit::cli::commands::bisect;
+            match action {
+                BisectCommands::Start { bad, good } => bisect::start(bad.as_deref(), &good)?,
+                BisectCommands::Good { rev } => bisect::mark("good", rev.as_deref())?,
+                BisectCommands::Bad { rev } => bisect::mark("bad", rev.as_deref())?,
+                BisectCommands::Skip { rev } => bisect::mark("skip", rev.as_deref())?,
+                BisectCommands::Run { command } => bisect::run(command)?,
+                BisectCommands::Reset => bisect::reset()?,
+            }
+        }
+
         Commands::Daemon { action } => {
             use multigit::cli::commands::daemon;
             match action {
-                DaemonCommands::Start { interval } => {
-                    runtime.block_on(daemon::start(interval))?;
+                DaemonCommands::Start {
+                    interval,
+                    cron,
+                    dry_run,
+                    runs,
+                } => {
+                    let schedule = daemon::resolve_schedule(interval, cron)?;
+                    if dry_run {
+                        daemon::dry_run(&schedule, runs)?;
+                    } else {
+                        runtime.block_on(daemon::start(schedule))?;
+                    }
+                }
+                DaemonCommands::RunOnce => {
+                    let schedule = daemon::resolve_schedule(None, None)?;
+                    runtime.block_on(daemon::run_once(schedule))?;
+                }
+                DaemonCommands::InstallService {
+                    interval,
+                    cron,
+                    print_only,
+                } => {
+                    let schedule = daemon::resolve_schedule(interval, cron)?;
+                    daemon::install_service(&schedule, print_only)?;
+                }
+                DaemonCommands::UninstallService { print_only } => {
+                    daemon::uninstall_service(print_only)?;
                 }
                 DaemonCommands::Stop => {
                     daemon::stop()?;
                 }
-                DaemonCommands::Status => {
-                    daemon::status()?;
+                DaemonCommands::Status { json } => {
+                    daemon::status(json)?;
                 }
-                DaemonCommands::Logs { lines } => {
-                    daemon::logs(lines)?;
+                DaemonCommands::Logs { lines, follow } => {
+                    daemon::logs(lines, follow)?;
                 }
             }
         }
@@ -836,7 +1875,7 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn handle_remote_command(action: RemoteCommands) -> Result<()> {
+fn handle_remote_command(action: RemoteCommands, global_json: bool) -> Result<()> {
     use multigit::cli::commands::remote;
 
     let runtime = tokio::runtime::Runtime::new()?;
@@ -846,29 +1885,44 @@ fn handle_remote_command(action: RemoteCommands) -> Result<()> {
             provider,
             username,
             url,
+            flavor,
         } => {
-            runtime.block_on(remote::add_remote(provider, username, url, true))?;
+            runtime.block_on(remote::add_remote(
+                provider, username, url, flavor, true, None,
+            ))?;
         }
 
-        RemoteCommands::List { detailed } => {
-            remote::list_remotes(detailed)?;
+        RemoteCommands::List { detailed, json } => {
+            runtime.block_on(remote::list_remotes(detailed, json || global_json))?;
         }
 
         RemoteCommands::Remove { name } => {
             remote::remove_remote(name, true)?;
         }
 
-        RemoteCommands::Test { name } => {
+        RemoteCommands::Test { name, timeout } => {
             if let Some(remote_name) = name {
-                runtime.block_on(remote::test_remote(remote_name))?;
+                runtime.block_on(remote::test_remote(remote_name, timeout))?;
             } else {
-                runtime.block_on(remote::test_all_remotes())?;
+                runtime.block_on(remote::test_all_remotes(timeout))?;
             }
         }
 
         RemoteCommands::Update { name } => {
             runtime.block_on(remote::update_remote(name, true))?;
         }
+
+        RemoteCommands::Enable { name, group } => {
+            remote::set_enabled(name, group, true)?;
+        }
+
+        RemoteCommands::Disable { name, group } => {
+            remote::set_enabled(name, group, false)?;
+        }
+
+        RemoteCommands::Group { name, groups } => {
+            remote::set_groups(name, groups)?;
+        }
     }
 
     Ok(())
@@ -1,6 +1,7 @@
 //! Configuration models for `MultiGit` settings
 
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 /// Settings for general `MultiGit` behavior
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,9 +22,62 @@ pub struct Settings {
     #[serde(default = "default_true")]
     pub colored_output: bool,
 
+    /// Glyphs for status icons; unset picks ASCII on terminals that can't show emoji
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icons: Option<IconStyle>,
+
     /// Verbosity level (0-3: error, warn, info, debug)
     #[serde(default)]
     pub verbosity: u8,
+
+    /// Timeout in seconds for provider API requests
+    #[serde(default = "default_api_timeout")]
+    pub api_timeout_seconds: u64,
+
+    /// Proxy for API and git traffic (overrides `HTTPS_PROXY`/`HTTP_PROXY`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy_url: Option<String>,
+
+    /// Extra PEM root certificate to trust, e.g. an internal CA
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_cert_path: Option<PathBuf>,
+
+    /// Warn when a remote's token expires within this many days
+    #[serde(default = "default_token_expiry_warning")]
+    pub token_expiry_warning_days: u32,
+
+    /// Debug log written as JSON lines with secrets redacted (`--log-file` overrides it)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_file: Option<PathBuf>,
+
+    /// Default log filter directives, e.g. `multigit::providers=debug`, on top of
+    /// the `-v` level; `RUST_LOG` overrides them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_filter: Option<String>,
+
+    /// Fetch at most this many commits per ref (shallow); unset fetches full history
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fetch_depth: Option<u32>,
+
+    /// Attempts at each push or fetch that fails on a transient network error
+    #[serde(default = "default_network_attempts")]
+    pub network_attempts: u32,
+
+    /// Create repositories as private unless `--public` is given
+    #[serde(default = "default_true")]
+    pub default_private: bool,
+
+    /// This repository must be private on every remote (set in `.multigit/config.toml`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub expect_private: bool,
+
+    /// Profile used when `--profile` is not given (read from the plain user config)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+
+    /// Keys this version doesn't recognize, preserved on save
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 impl Default for Settings {
@@ -33,7 +87,20 @@ impl Default for Settings {
             parallel_push: true,
             max_parallel: default_parallel(),
             colored_output: true,
+            icons: None,
             verbosity: 1, // warn level
+            api_timeout_seconds: default_api_timeout(),
+            proxy_url: None,
+            ca_cert_path: None,
+            token_expiry_warning_days: default_token_expiry_warning(),
+            log_file: None,
+            log_filter: None,
+            fetch_depth: None,
+            network_attempts: default_network_attempts(),
+            default_private: true,
+            expect_private: false,
+            default_profile: None,
+            extra: toml::Table::new(),
         }
     }
 }
@@ -50,6 +117,18 @@ fn default_parallel() -> usize {
     4
 }
 
+fn default_api_timeout() -> u64 {
+    15
+}
+
+fn default_token_expiry_warning() -> u32 {
+    14
+}
+
+fn default_network_attempts() -> u32 {
+    crate::git::operations::DEFAULT_NETWORK_ATTEMPTS
+}
+
 /// Synchronization configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncConfig {
@@ -64,11 +143,11 @@ pub struct SyncConfig {
     #[serde(default = "default_strategy")]
     pub strategy: SyncStrategy,
 
-    /// Branches to include in sync (empty = all)
+    /// Branch globs to include in `sync --all-branches` (empty = all), e.g. `feature/*`
     #[serde(default)]
     pub include_branches: Vec<String>,
 
-    /// Branches to exclude from sync
+    /// Branch globs to exclude from sync (take precedence over includes), e.g. `release/**`
     #[serde(default)]
     pub exclude_branches: Vec<String>,
 
@@ -79,6 +158,14 @@ pub struct SyncConfig {
     /// Automatically resolve conflicts if possible
     #[serde(default)]
     pub auto_resolve: bool,
+
+    /// Stash uncommitted changes before `pull`/`sync` and restore them after
+    #[serde(default)]
+    pub autostash: bool,
+
+    /// Keys this version doesn't recognize, preserved on save
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 fn default_strategy() -> SyncStrategy {
@@ -95,10 +182,26 @@ impl Default for SyncConfig {
             exclude_branches: Vec::new(),
             detect_conflicts: true, // Default to true for safety
             auto_resolve: false,
+            autostash: false,
+            extra: toml::Table::new(),
         }
     }
 }
 
+/// Glyphs status icons are drawn with
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IconStyle {
+    /// Unicode symbols such as ✓ and ⚠
+    Emoji,
+
+    /// Plain ASCII such as `[ok]` and `[!]`, for terminals without emoji fonts
+    Ascii,
+
+    /// Icons from a patched Nerd Font
+    NerdFont,
+}
+
 /// Strategy for handling synchronization
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
@@ -158,6 +261,15 @@ pub struct SecurityConfig {
     /// Allow reading tokens from environment variables (default: false for security)
     #[serde(default)]
     pub allow_env_tokens: bool,
+
+    /// Key to sign commits and tags with: a GPG key id, or an SSH key path
+    /// when `gpg.format` is `ssh`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_key: Option<String>,
+
+    /// Keys this version doesn't recognize, preserved on save
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 impl Default for SecurityConfig {
@@ -169,10 +281,23 @@ impl Default for SecurityConfig {
             ssh_agent: true,
             allow_insecure_http: false,
             allow_env_tokens: false,
+            signing_key: None,
+            extra: toml::Table::new(),
         }
     }
 }
 
+impl SecurityConfig {
+    /// The configured signing key, if set to something
+    #[must_use]
+    pub fn signing_key(&self) -> Option<&str> {
+        self.signing_key
+            .as_deref()
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+    }
+}
+
 fn default_auth() -> AuthBackend {
     AuthBackend::Keyring
 }
@@ -201,8 +326,25 @@ impl std::fmt::Display for AuthBackend {
     }
 }
 
+/// Remotes the branches matching one `[routing]` glob may be pushed to
+///
+/// ```toml
+/// [routing."internal/**"]
+/// deny = ["public-mirror"]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RouteRule {
+    /// Only these remotes (empty = every remote not denied)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+
+    /// Never these remotes; wins over `allow`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+}
+
 /// Daemon configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DaemonConfig {
     /// Enable daemon on startup
     #[serde(default)]
@@ -212,6 +354,10 @@ pub struct DaemonConfig {
     #[serde(default = "default_interval")]
     pub interval_seconds: u64,
 
+    /// Cron expression for sync times (e.g. `"0 2,14 * * *"`); overrides `interval_seconds`
+    #[serde(default)]
+    pub cron: Option<String>,
+
     /// Run daemon in background
     #[serde(default = "default_true")]
     pub background: bool,
@@ -221,12 +367,78 @@ pub struct DaemonConfig {
 
     /// PID file path
     pub pid_file: Option<String>,
+
+    /// Roll the daemon log once it reaches this size (in megabytes)
+    #[serde(default = "default_max_log_size_mb")]
+    pub max_log_size_mb: u64,
+
+    /// Number of rolled daemon logs to keep (`daemon.log.1` .. `daemon.log.N`)
+    #[serde(default = "default_max_log_files")]
+    pub max_log_files: usize,
+
+    /// Upper bound (in seconds) for the backed-off interval of a failing remote
+    #[serde(default = "default_max_backoff_seconds")]
+    pub max_backoff_seconds: u64,
+
+    /// Keys this version doesn't recognize, preserved on save
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_seconds: default_interval(),
+            cron: None,
+            background: true,
+            log_file: None,
+            pid_file: None,
+            max_log_size_mb: default_max_log_size_mb(),
+            max_log_files: default_max_log_files(),
+            max_backoff_seconds: default_max_backoff_seconds(),
+            extra: toml::Table::new(),
+        }
+    }
+}
+
+impl DaemonConfig {
+    /// Schedule configured for the daemon (cron if set, otherwise the interval)
+    pub fn schedule(&self) -> Result<crate::daemon::Schedule, String> {
+        match &self.cron {
+            Some(expression) => crate::daemon::Schedule::from_cron(expression),
+            None => Ok(crate::daemon::Schedule::every_seconds(
+                self.interval_seconds,
+            )),
+        }
+    }
+
+    /// Rotation policy for the daemon log
+    #[must_use]
+    pub fn log_rotation(&self) -> crate::utils::log_file::LogRotation {
+        crate::utils::log_file::LogRotation {
+            max_bytes: self.max_log_size_mb.max(1) * 1024 * 1024,
+            max_files: self.max_log_files,
+        }
+    }
 }
 
 fn default_interval() -> u64 {
     300 // 5 minutes
 }
 
+fn default_max_log_size_mb() -> u64 {
+    10
+}
+
+fn default_max_log_files() -> usize {
+    5
+}
+
+fn default_max_backoff_seconds() -> u64 {
+    crate::daemon::backoff::DEFAULT_MAX_BACKOFF_SECONDS
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -237,6 +449,9 @@ mod tests {
         assert_eq!(settings.default_branch, "main");
         assert!(settings.parallel_push);
         assert_eq!(settings.max_parallel, 4);
+        assert_eq!(settings.network_attempts, 3);
+        assert!(settings.default_private);
+        assert!(!settings.expect_private);
     }
 
     #[test]
@@ -251,6 +466,14 @@ mod tests {
         assert_eq!(AuthBackend::EncryptedFile.to_string(), "encrypted-file");
     }
 
+    #[test]
+    fn test_daemon_config_log_rotation() {
+        let config = DaemonConfig::default();
+        let rotation = config.log_rotation();
+        assert_eq!(rotation.max_bytes, 10 * 1024 * 1024);
+        assert_eq!(rotation.max_files, 5);
+    }
+
     #[test]
     fn test_sync_config_default() {
         let config = SyncConfig::default();
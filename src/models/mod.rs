@@ -4,11 +4,15 @@
 //! including repository metadata, remote configurations, and sync state tracking.
 
 pub mod config;
+pub mod pull_request;
+pub mod release;
 pub mod remote;
 pub mod repository;
 pub mod sync_state;
 
 pub use config::*;
+pub use pull_request::*;
+pub use release::*;
 pub use remote::*;
 pub use repository::*;
 pub use sync_state::*;
@@ -0,0 +1,112 @@
+//! Pull request (merge request) models shared across hosting providers
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// State filter / state of a pull request
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PullRequestState {
+    /// Open and awaiting review or merge
+    #[default]
+    Open,
+    /// Closed without merging
+    Closed,
+    /// Merged into the target branch
+    Merged,
+    /// Any state (only meaningful as a filter)
+    All,
+}
+
+impl fmt::Display for PullRequestState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open => write!(f, "open"),
+            Self::Closed => write!(f, "closed"),
+            Self::Merged => write!(f, "merged"),
+            Self::All => write!(f, "all"),
+        }
+    }
+}
+
+/// A pull request on GitHub/Gitea/Bitbucket or a merge request on GitLab
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PullRequest {
+    /// Provider-specific number (`#12` on GitHub, `!12` on GitLab)
+    pub number: u64,
+
+    /// Title of the pull request
+    pub title: String,
+
+    /// Username of the author
+    pub author: String,
+
+    /// Web URL of the pull request
+    pub url: String,
+
+    /// Current state
+    pub state: PullRequestState,
+
+    /// Source branch name
+    pub source_branch: Option<String>,
+
+    /// Target branch name
+    pub target_branch: Option<String>,
+}
+
+impl PullRequest {
+    /// Create a new pull request with required fields
+    pub fn new(
+        number: u64,
+        title: impl Into<String>,
+        author: impl Into<String>,
+        url: impl Into<String>,
+        state: PullRequestState,
+    ) -> Self {
+        Self {
+            number,
+            title: title.into(),
+            author: author.into(),
+            url: url.into(),
+            state,
+            source_branch: None,
+            target_branch: None,
+        }
+    }
+
+    /// Set the source and target branches
+    pub fn with_branches(mut self, source: impl Into<String>, target: impl Into<String>) -> Self {
+        self.source_branch = Some(source.into());
+        self.target_branch = Some(target.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pull_request_creation() {
+        let pr = PullRequest::new(
+            42,
+            "Add feature",
+            "octocat",
+            "https://github.com/user/repo/pull/42",
+            PullRequestState::Open,
+        )
+        .with_branches("feature", "main");
+
+        assert_eq!(pr.number, 42);
+        assert_eq!(pr.author, "octocat");
+        assert_eq!(pr.source_branch.as_deref(), Some("feature"));
+        assert_eq!(pr.target_branch.as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn test_pull_request_state_display() {
+        assert_eq!(PullRequestState::Open.to_string(), "open");
+        assert_eq!(PullRequestState::Merged.to_string(), "merged");
+        assert_eq!(PullRequestState::default(), PullRequestState::Open);
+    }
+}
@@ -0,0 +1,66 @@
+//! Release models shared across hosting providers
+
+use serde::{Deserialize, Serialize};
+
+/// A release published on a hosting provider for a tag
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Release {
+    /// Provider-specific identifier (the numeric id on GitHub, the tag name
+    /// on GitLab), used to address the release afterwards
+    pub id: String,
+
+    /// Tag the release is for
+    pub tag: String,
+
+    /// Title of the release
+    pub name: String,
+
+    /// Web URL of the release
+    pub url: String,
+
+    /// Whether the release is marked as a pre-release
+    pub prerelease: bool,
+}
+
+impl Release {
+    /// Create a new release
+    pub fn new(
+        id: impl Into<String>,
+        tag: impl Into<String>,
+        name: impl Into<String>,
+        url: impl Into<String>,
+        prerelease: bool,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            tag: tag.into(),
+            name: name.into(),
+            url: url.into(),
+            prerelease,
+        }
+    }
+}
+
+/// A file attached to a release
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReleaseAsset {
+    /// File name the asset is listed under
+    pub name: String,
+
+    /// URL the asset can be downloaded from
+    pub url: String,
+
+    /// Size in bytes
+    pub size: u64,
+}
+
+impl ReleaseAsset {
+    /// Create a new release asset
+    pub fn new(name: impl Into<String>, url: impl Into<String>, size: u64) -> Self {
+        Self {
+            name: name.into(),
+            url: url.into(),
+            size,
+        }
+    }
+}
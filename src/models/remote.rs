@@ -1,5 +1,6 @@
 //! Remote configuration models for Git hosting platforms
 
+use crate::core::config::RemoteConfig;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -87,6 +88,43 @@ impl Remote {
     }
 }
 
+/// A configured remote, as `(name, config)` from `[remotes.<name>]`
+///
+/// The provider is the one [`RemoteConfig::provider_name`] resolves to; it
+/// fails to convert when it isn't one [`ProviderType`] knows.
+impl TryFrom<(&str, &RemoteConfig)> for Remote {
+    type Error = String;
+
+    fn try_from((name, config): (&str, &RemoteConfig)) -> Result<Self, Self::Error> {
+        let provider = config.provider_name(name).parse()?;
+        Ok(Self {
+            name: name.to_string(),
+            provider,
+            username: config.username.clone(),
+            api_url: config.api_url.clone(),
+            enabled: config.enabled,
+            use_ssh: config.use_ssh,
+            priority: config.priority,
+        })
+    }
+}
+
+/// The config entry for a remote; TLS, group and token settings, which a
+/// [`Remote`] doesn't carry, are left unset
+impl From<&Remote> for RemoteConfig {
+    fn from(remote: &Remote) -> Self {
+        Self {
+            username: remote.username.clone(),
+            api_url: remote.api_url.clone(),
+            enabled: remote.enabled,
+            provider: Some(remote.provider.as_str().to_string()),
+            use_ssh: remote.use_ssh,
+            priority: remote.priority,
+            ..Self::default()
+        }
+    }
+}
+
 /// Supported Git hosting providers
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
@@ -106,6 +144,29 @@ pub enum ProviderType {
 }
 
 impl ProviderType {
+    /// Every supported provider
+    pub const ALL: [Self; 6] = [
+        Self::GitHub,
+        Self::GitLab,
+        Self::Bitbucket,
+        Self::Codeberg,
+        Self::Gitea,
+        Self::Forgejo,
+    ];
+
+    /// The lowercase name used in config files, e.g. `github`
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::GitHub => "github",
+            Self::GitLab => "gitlab",
+            Self::Bitbucket => "bitbucket",
+            Self::Codeberg => "codeberg",
+            Self::Gitea => "gitea",
+            Self::Forgejo => "forgejo",
+        }
+    }
+
     /// Get the default API URL for this provider
     #[must_use]
     pub fn default_api_url(&self) -> &'static str {
@@ -247,6 +308,45 @@ impl RateLimit {
     }
 }
 
+/// What a provider reports about the token in use
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenInfo {
+    /// Token name or description, if the provider exposes one
+    pub name: Option<String>,
+
+    /// When the token expires; `None` if it never does (or isn't reported)
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl TokenInfo {
+    /// Whole days until expiry, negative once expired
+    #[must_use]
+    pub fn days_until_expiry(&self, now: chrono::DateTime<chrono::Utc>) -> Option<i64> {
+        self.expires_at.map(|at| (at - now).num_days())
+    }
+
+    /// Check whether the token expires within `days` of `now`
+    #[must_use]
+    pub fn expires_within(&self, days: u32, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.expires_at
+            .is_some_and(|at| at - now <= chrono::Duration::days(i64::from(days)))
+    }
+
+    /// Short description of the expiry, e.g. `expires in 5 day(s) (2024-06-01)`
+    #[must_use]
+    pub fn describe_expiry(&self, now: chrono::DateTime<chrono::Utc>) -> String {
+        match self.expires_at {
+            None => "does not expire".to_string(),
+            Some(at) if at <= now => format!("expired on {}", at.format("%Y-%m-%d")),
+            Some(at) => format!(
+                "expires in {} day(s) ({})",
+                (at - now).num_days(),
+                at.format("%Y-%m-%d")
+            ),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -291,6 +391,49 @@ mod tests {
         assert!("invalid".parse::<ProviderType>().is_err());
     }
 
+    fn remote_config(provider: Option<&str>) -> RemoteConfig {
+        RemoteConfig {
+            username: "user".to_string(),
+            api_url: Some("https://git.example.com".to_string()),
+            enabled: false,
+            provider: provider.map(str::to_string),
+            use_ssh: true,
+            priority: 3,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_remote_from_config() {
+        for provider in ProviderType::ALL {
+            let config = remote_config(Some(provider.as_str()));
+            let remote = Remote::try_from(("mirror", &config)).unwrap();
+            assert_eq!(remote.name, "mirror");
+            assert_eq!(remote.provider, provider);
+            assert_eq!(remote.api_url.as_deref(), Some("https://git.example.com"));
+            assert!(!remote.enabled);
+            assert!(remote.use_ssh);
+            assert_eq!(remote.priority, 3);
+
+            let back = RemoteConfig::from(&remote);
+            assert_eq!(back.provider.as_deref(), Some(provider.as_str()));
+            assert_eq!(back.api_url, config.api_url);
+            assert_eq!(back.priority, 3);
+        }
+    }
+
+    #[test]
+    fn test_remote_from_config_without_provider() {
+        // The remote's name stands in for a missing provider
+        let config = remote_config(None);
+        let remote = Remote::try_from(("gitlab", &config)).unwrap();
+        assert_eq!(remote.provider, ProviderType::GitLab);
+
+        assert!(Remote::try_from(("mirror", &config)).is_err());
+        let unknown = remote_config(Some("sourcehut"));
+        assert!(Remote::try_from(("mirror", &unknown)).is_err());
+    }
+
     #[test]
     fn test_provider_urls() {
         let github = ProviderType::GitHub;
@@ -321,4 +464,28 @@ mod tests {
         assert!(rate_limit.is_low());
         assert!(!rate_limit.is_exceeded());
     }
+
+    #[test]
+    fn test_token_expiry() {
+        let now = chrono::Utc::now();
+        let token = TokenInfo {
+            name: Some("ci".to_string()),
+            expires_at: Some(now + chrono::Duration::days(5) + chrono::Duration::hours(1)),
+        };
+
+        assert_eq!(token.days_until_expiry(now), Some(5));
+        assert!(token.expires_within(7, now));
+        assert!(!token.expires_within(3, now));
+        assert!(!TokenInfo::default().expires_within(7, now));
+        assert!(token
+            .describe_expiry(now)
+            .starts_with("expires in 5 day(s)"));
+
+        let expired = TokenInfo {
+            name: None,
+            expires_at: Some(now - chrono::Duration::days(1)),
+        };
+        assert!(expired.describe_expiry(now).starts_with("expired on"));
+        assert!(expired.expires_within(0, now));
+    }
 }
@@ -35,6 +35,22 @@ pub struct Repository {
 
     /// When the repository was last updated
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// Number of stars, if the provider reports it
+    #[serde(default)]
+    pub stars: Option<u32>,
+
+    /// Number of forks, if the provider reports it
+    #[serde(default)]
+    pub forks: Option<u32>,
+
+    /// Number of open issues, if the provider reports it
+    #[serde(default)]
+    pub open_issues: Option<u32>,
+
+    /// Whether the repository is archived (read-only), if the provider reports it
+    #[serde(default)]
+    pub archived: Option<bool>,
 }
 
 impl Repository {
@@ -57,6 +73,10 @@ impl Repository {
             html_url: None,
             created_at: None,
             updated_at: None,
+            stars: None,
+            forks: None,
+            open_issues: None,
+            archived: None,
         }
     }
 
@@ -77,6 +97,19 @@ impl Repository {
         self.html_url = Some(html_url.into());
         self
     }
+
+    /// Name used in the repository's URLs: the last part of `full_name`
+    ///
+    /// Differs from `name` on GitLab, where the display name may contain
+    /// spaces. Falls back to `name` when there is no full name.
+    #[must_use]
+    pub fn slug(&self) -> &str {
+        self.full_name
+            .as_deref()
+            .and_then(|full_name| full_name.rsplit('/').next())
+            .filter(|slug| !slug.is_empty())
+            .unwrap_or(&self.name)
+    }
 }
 
 /// Configuration for creating a new repository on a hosting platform
@@ -199,6 +232,16 @@ impl LocalRepo {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_repository_slug() {
+        let repo = Repository::new("My Project", "", "", false, "main");
+        assert_eq!(repo.slug(), "My Project");
+        assert_eq!(
+            repo.with_full_name("group/sub/my-project").slug(),
+            "my-project"
+        );
+    }
+
     #[test]
     fn test_repository_creation() {
         let repo = Repository::new(
@@ -232,6 +275,22 @@ mod tests {
         assert!(repo.private);
     }
 
+    #[test]
+    fn test_repository_metadata_is_optional() {
+        let repo = Repository::new("test", "", "", false, "main");
+        let json = serde_json::to_value(&repo).unwrap();
+        assert!(json["stars"].is_null());
+        assert!(json["archived"].is_null());
+
+        // Data serialized before the metadata fields existed still loads
+        let mut old = json;
+        for field in ["stars", "forks", "open_issues", "archived"] {
+            old.as_object_mut().unwrap().remove(field);
+        }
+        let loaded: Repository = serde_json::from_value(old).unwrap();
+        assert_eq!(loaded, repo);
+    }
+
     #[test]
     fn test_repo_config() {
         let config = RepoConfig::new("my-repo")
@@ -2,6 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 
 /// Overall synchronization state for a repository
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +25,14 @@ pub struct SyncState {
 
     /// Overall sync status
     pub status: SyncStatus,
+
+    /// HEAD commit the ahead/behind counts were computed at
+    #[serde(default)]
+    pub head_oid: Option<String>,
+
+    /// When the ahead/behind counts were computed
+    #[serde(default)]
+    pub computed_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 impl SyncState {
@@ -36,6 +45,8 @@ impl SyncState {
             conflicts: Vec::new(),
             last_sync: None,
             status: SyncStatus::Unknown,
+            head_oid: None,
+            computed_at: None,
         }
     }
 
@@ -82,6 +93,46 @@ impl SyncState {
             self.status = SyncStatus::Dirty;
         }
     }
+
+    /// Whether the cached ahead/behind counts still hold for `branch` of the
+    /// repository at `repo_path` with HEAD at `head_oid`
+    ///
+    /// A new commit (or any other move of HEAD) invalidates them at once;
+    /// otherwise they are reused for `ttl`, which bounds how long a fetch by
+    /// another process can go unnoticed.
+    #[must_use]
+    pub fn is_fresh(
+        &self,
+        repo_path: &str,
+        branch: &str,
+        head_oid: &str,
+        ttl: std::time::Duration,
+    ) -> bool {
+        let Some(computed_at) = self.computed_at else {
+            return false;
+        };
+        let age = chrono::Utc::now().signed_duration_since(computed_at);
+        self.repo_path == repo_path
+            && self.current_branch == branch
+            && self.head_oid.as_deref() == Some(head_oid)
+            && age.to_std().is_ok_and(|age| age < ttl)
+    }
+
+    /// Load a previously saved sync state from `path`
+    ///
+    /// Returns `None` if the file is missing or unreadable; the cache is
+    /// disposable and will be rebuilt on the next sync.
+    #[must_use]
+    pub fn load_from(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Atomically save this sync state to `path`, creating parent directories as needed
+    pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        crate::utils::atomic_write::write_atomic(path, content)
+    }
 }
 
 /// State of a specific remote
@@ -107,6 +158,10 @@ pub struct RemoteState {
 
     /// Error message if status is Error
     pub error_message: Option<String>,
+
+    /// Remote-tracking commit the ahead/behind counts were computed against
+    #[serde(default)]
+    pub tracking_oid: Option<String>,
 }
 
 impl RemoteState {
@@ -120,6 +175,7 @@ impl RemoteState {
             last_push: None,
             status: RemoteSyncStatus::Unknown,
             error_message: None,
+            tracking_oid: None,
         }
     }
 
@@ -341,6 +397,38 @@ impl PushResult {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sync_state_save_and_load() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("cache/sync_state.json");
+
+        let mut state = SyncState::new("/tmp/repo", "main");
+        state.add_remote("github", RemoteState::new("github"));
+        state.save_to(&path).unwrap();
+
+        let loaded = SyncState::load_from(&path).unwrap();
+        assert_eq!(loaded.current_branch, "main");
+        assert!(loaded.remotes.contains_key("github"));
+        assert!(SyncState::load_from(&temp.path().join("missing.json")).is_none());
+    }
+
+    #[test]
+    fn test_sync_state_freshness() {
+        let ttl = std::time::Duration::from_secs(30);
+        let mut state = SyncState::new("/repo", "main");
+        assert!(!state.is_fresh("/repo", "main", "abc", ttl));
+
+        state.head_oid = Some("abc".to_string());
+        state.computed_at = Some(chrono::Utc::now());
+        assert!(state.is_fresh("/repo", "main", "abc", ttl));
+        assert!(!state.is_fresh("/repo", "main", "def", ttl));
+        assert!(!state.is_fresh("/repo", "feature", "abc", ttl));
+        assert!(!state.is_fresh("/other", "main", "abc", ttl));
+
+        state.computed_at = Some(chrono::Utc::now() - chrono::Duration::seconds(60));
+        assert!(!state.is_fresh("/repo", "main", "abc", ttl));
+    }
+
     #[test]
     fn test_sync_state_creation() {
         let state = SyncState::new("/repo", "main");
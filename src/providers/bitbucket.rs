@@ -3,15 +3,21 @@
 //! Implements the Provider trait for Bitbucket using the REST API 2.0.
 
 use crate::api::{
-    client::build_api_client, rate_limiter::RateLimiter, retry::retry_async, retry::RetryConfig,
+    cache::{self, ResponseCache},
+    circuit_breaker::CircuitBreaker,
+    circuit_breaker::HostCircuit,
+    client::build_api_client,
+    rate_limiter::RateLimiter,
+    retry::retry_api,
+    retry::RetryConfig,
 };
-use crate::models::{RateLimit, Repository};
+use crate::models::{PullRequest, PullRequestState, RateLimit, Repository};
 use crate::providers::traits::{Protocol, Provider, RepoConfig};
 use crate::utils::error::{MultiGitError, Result};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde_json::{json, Value};
-use tracing::{debug, info};
+use tracing::{debug, info, instrument};
 
 /// Bitbucket API provider
 pub struct BitbucketProvider {
@@ -19,6 +25,8 @@ pub struct BitbucketProvider {
     username: String,
     password: String, // App password
     rate_limiter: RateLimiter,
+    circuit: HostCircuit,
+    cache: ResponseCache,
 }
 
 impl BitbucketProvider {
@@ -29,24 +37,67 @@ impl BitbucketProvider {
             username,
             password,
             rate_limiter: RateLimiter::bitbucket(),
+            circuit: CircuitBreaker::shared().for_host("bitbucket", "https://api.bitbucket.org"),
+            cache: ResponseCache::shared(),
         })
     }
 
+    /// Use a specific HTTP client (e.g. one built with per-remote TLS settings)
+    #[must_use]
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Use a specific response cache (e.g. `ResponseCache::disabled()` in tests)
+    #[must_use]
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Cache key prefix scoping entries to this provider and user
+    fn cache_prefix(&self) -> String {
+        cache::scope("bitbucket", &self.username, &self.password)
+    }
+
+    /// Make an authenticated GET request, answered from the response cache
+    /// when it is still fresh
     async fn get(&self, endpoint: &str) -> Result<Value> {
+        self.get_with_cache(endpoint, true).await
+    }
+
+    /// Make an authenticated GET request straight to the API, for answers
+    /// that must not be stale (credential checks, rate limits)
+    async fn get_fresh(&self, endpoint: &str) -> Result<Value> {
+        self.get_with_cache(endpoint, false).await
+    }
+
+    #[instrument(name = "bitbucket.get", level = "debug", skip_all, fields(endpoint = %endpoint))]
+    async fn get_with_cache(&self, endpoint: &str, use_cache: bool) -> Result<Value> {
+        let url = format!("https://api.bitbucket.org/2.0{endpoint}");
+
+        let cache_key = format!("{}{url}", self.cache_prefix());
+        if let Some(cached) = use_cache.then(|| self.cache.get(&cache_key)).flatten() {
+            debug!("Bitbucket GET (cached): {}", url);
+            return Ok(cached);
+        }
+
         self.rate_limiter
             .acquire()
             .await
             .map_err(MultiGitError::Other)?;
 
-        let url = format!("https://api.bitbucket.org/2.0{endpoint}");
         debug!("Bitbucket GET: {}", url);
 
-        retry_async(RetryConfig::for_api(), || async {
+        let data = retry_api(RetryConfig::for_api(), || async {
             let response = self
-                .client
-                .get(&url)
-                .basic_auth(&self.username, Some(&self.password))
-                .send()
+                .circuit
+                .send(
+                    self.client
+                        .get(&url)
+                        .basic_auth(&self.username, Some(&self.password)),
+                )
                 .await?;
 
             if !response.status().is_success() {
@@ -60,9 +111,13 @@ impl BitbucketProvider {
             let data: Value = response.json().await?;
             Ok(data)
         })
-        .await
+        .await?;
+
+        self.cache.insert(cache_key, data.clone());
+        Ok(data)
     }
 
+    #[instrument(name = "bitbucket.post", level = "debug", skip_all, fields(endpoint = %endpoint))]
     async fn post(&self, endpoint: &str, body: Value) -> Result<Value> {
         self.rate_limiter
             .acquire()
@@ -71,14 +126,17 @@ impl BitbucketProvider {
 
         let url = format!("https://api.bitbucket.org/2.0{endpoint}");
         debug!("Bitbucket POST: {}", url);
+        self.cache.invalidate_prefix(&self.cache_prefix());
 
-        retry_async(RetryConfig::for_api(), || async {
+        retry_api(RetryConfig::for_api(), || async {
             let response = self
-                .client
-                .post(&url)
-                .basic_auth(&self.username, Some(&self.password))
-                .json(&body)
-                .send()
+                .circuit
+                .send(
+                    self.client
+                        .post(&url)
+                        .basic_auth(&self.username, Some(&self.password))
+                        .json(&body),
+                )
                 .await?;
 
             if !response.status().is_success() {
@@ -94,6 +152,39 @@ impl BitbucketProvider {
         })
         .await
     }
+
+    async fn delete(&self, endpoint: &str) -> Result<()> {
+        self.rate_limiter
+            .acquire()
+            .await
+            .map_err(MultiGitError::Other)?;
+
+        let url = format!("https://api.bitbucket.org/2.0{endpoint}");
+        debug!("Bitbucket DELETE: {}", url);
+        self.cache.invalidate_prefix(&self.cache_prefix());
+
+        retry_api(RetryConfig::for_api(), || async {
+            let response = self
+                .circuit
+                .send(
+                    self.client
+                        .delete(&url)
+                        .basic_auth(&self.username, Some(&self.password)),
+                )
+                .await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(MultiGitError::Other(format!(
+                    "Bitbucket API error: {status} - {error_text}"
+                )));
+            }
+
+            Ok(())
+        })
+        .await
+    }
 }
 
 #[async_trait]
@@ -102,10 +193,13 @@ impl Provider for BitbucketProvider {
         "bitbucket"
     }
 
+    #[instrument(name = "bitbucket.test_connection", level = "debug", skip_all)]
     async fn test_connection(&self) -> anyhow::Result<bool> {
         info!("Testing Bitbucket connection");
-        match self.get(&format!("/users/{}", self.username)).await {
+        match self.get_fresh(&format!("/users/{}", self.username)).await {
             Ok(_) => Ok(true),
+            // Unreachable hosts and timeouts aren't credential problems
+            Err(MultiGitError::NetworkError(e)) => Err(e.into()),
             Err(_) => Ok(false),
         }
     }
@@ -157,6 +251,11 @@ impl Provider for BitbucketProvider {
                     .ok()
                     .map(std::convert::Into::into)
             }),
+            // Bitbucket's repository API has no star, fork or issue counts
+            stars: None,
+            forks: None,
+            open_issues: None,
+            archived: None,
         })
     }
 
@@ -199,6 +298,11 @@ impl Provider for BitbucketProvider {
                     .ok()
                     .map(std::convert::Into::into)
             }),
+            // Bitbucket's repository API has no star, fork or issue counts
+            stars: None,
+            forks: None,
+            open_issues: None,
+            archived: None,
         })
     }
 
@@ -220,6 +324,13 @@ impl Provider for BitbucketProvider {
         Ok(())
     }
 
+    async fn delete_repo(&self, name: &str) -> anyhow::Result<()> {
+        info!("Deleting Bitbucket repository: {}", name);
+        self.delete(&format!("/repositories/{}/{}", self.username, name))
+            .await?;
+        Ok(())
+    }
+
     async fn get_rate_limit(&self) -> anyhow::Result<RateLimit> {
         // Bitbucket doesn't expose rate limits via API
         Ok(RateLimit {
@@ -228,4 +339,63 @@ impl Provider for BitbucketProvider {
             reset_at: chrono::Utc::now() + chrono::Duration::hours(1),
         })
     }
+
+    fn supports_pull_requests(&self) -> bool {
+        true
+    }
+
+    async fn list_pull_requests(
+        &self,
+        repo: &str,
+        state: PullRequestState,
+    ) -> anyhow::Result<Vec<PullRequest>> {
+        debug!(
+            "Listing {} Bitbucket pull requests for {}/{}",
+            state, self.username, repo
+        );
+
+        let state_query = match state {
+            PullRequestState::Open => "state=OPEN",
+            PullRequestState::Closed => "state=DECLINED&state=SUPERSEDED",
+            PullRequestState::Merged => "state=MERGED",
+            PullRequestState::All => "state=OPEN&state=MERGED&state=DECLINED&state=SUPERSEDED",
+        };
+
+        let endpoint = format!(
+            "/repositories/{}/{}/pullrequests?{state_query}&pagelen=50",
+            self.username, repo
+        );
+        let data = self.get(&endpoint).await?;
+
+        Ok(data["values"]
+            .as_array()
+            .map(|pulls| pulls.iter().map(parse_pull_request).collect())
+            .unwrap_or_default())
+    }
+}
+
+/// Convert a Bitbucket pull request JSON object into a `PullRequest`
+fn parse_pull_request(data: &Value) -> PullRequest {
+    let state = match data["state"].as_str() {
+        Some("MERGED") => PullRequestState::Merged,
+        Some("DECLINED" | "SUPERSEDED") => PullRequestState::Closed,
+        _ => PullRequestState::Open,
+    };
+
+    let author = data["author"]["nickname"]
+        .as_str()
+        .or_else(|| data["author"]["display_name"].as_str())
+        .unwrap_or("");
+
+    PullRequest::new(
+        data["id"].as_u64().unwrap_or(0),
+        data["title"].as_str().unwrap_or(""),
+        author,
+        data["links"]["html"]["href"].as_str().unwrap_or(""),
+        state,
+    )
+    .with_branches(
+        data["source"]["branch"]["name"].as_str().unwrap_or(""),
+        data["destination"]["branch"]["name"].as_str().unwrap_or(""),
+    )
 }
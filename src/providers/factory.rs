@@ -2,6 +2,10 @@
 //!
 //! Centralizes provider creation logic to avoid duplication across commands.
 
+use crate::api::client::{build_api_client_with, ClientOptions};
+use crate::core::auth::{AuthBackend, AuthManager};
+use crate::core::config::{Config, RemoteConfig};
+use crate::git::remote::{url_utils, RemoteManager};
 use crate::providers::bitbucket::BitbucketProvider;
 use crate::providers::gitea::GiteaProvider;
 use crate::providers::github::GitHubProvider;
@@ -11,7 +15,14 @@ use crate::utils::error::{MultiGitError, Result};
 use crate::utils::validation::{extract_host_from_url, validate_https_url};
 use std::sync::Arc;
 
-/// Create a provider instance from configuration
+/// Provider name for a self-hosted forge without a dedicated module, served
+/// by the implementation its `api_flavor` names
+pub const CUSTOM_PROVIDER: &str = "custom";
+
+/// Providers whose API a `custom` provider can speak
+pub const CUSTOM_FLAVORS: &[&str] = &["gitea", "gitlab"];
+
+/// Create a provider instance with the default [`ClientOptions`]
 ///
 /// # Arguments
 /// * `provider` - Provider name (github, gitlab, bitbucket, codeberg, gitea)
@@ -29,9 +40,34 @@ pub fn create_provider(
     api_url: Option<&str>,
     allow_insecure: bool,
 ) -> Result<Arc<dyn Provider>> {
+    create_provider_with_options(
+        provider,
+        username,
+        token,
+        api_url,
+        allow_insecure,
+        &ClientOptions::default(),
+    )
+}
+
+/// Create a provider instance whose HTTP client uses `options`
+///
+/// Used for remotes with their own TLS settings (see
+/// `Config::client_options_for`); otherwise identical to [`create_provider`].
+pub fn create_provider_with_options(
+    provider: &str,
+    username: &str,
+    token: &str,
+    api_url: Option<&str>,
+    allow_insecure: bool,
+    options: &ClientOptions,
+) -> Result<Arc<dyn Provider>> {
+    let client = build_api_client_with(options)?;
     let provider_instance: Arc<dyn Provider> = match provider {
         "github" => {
-            let p = GitHubProvider::new(token.to_string(), username.to_string())?;
+            let p = GitHubProvider::new(token.to_string(), username.to_string())?
+                .with_client(client)
+                .with_api_timeout(options.timeout);
             Arc::new(p)
         }
         "gitlab" => {
@@ -40,11 +76,14 @@ pub fn create_provider(
             } else {
                 None
             };
-            let p = GitLabProvider::new(token.to_string(), username.to_string(), validated_url)?;
+            let p = GitLabProvider::new(token.to_string(), username.to_string(), validated_url)?
+                .with_client(client)
+                .with_api_timeout(options.timeout);
             Arc::new(p)
         }
         "bitbucket" => {
-            let p = BitbucketProvider::new(username.to_string(), token.to_string())?;
+            let p = BitbucketProvider::new(username.to_string(), token.to_string())?
+                .with_client(client);
             Arc::new(p)
         }
         "gitea" => {
@@ -52,7 +91,8 @@ pub fn create_provider(
                 MultiGitError::config("Gitea requires an API URL. Use --url flag".to_string())
             })?;
             let validated_url = validate_https_url(url, allow_insecure)?;
-            let p = GiteaProvider::new(token.to_string(), username.to_string(), validated_url)?;
+            let p = GiteaProvider::new(token.to_string(), username.to_string(), validated_url)?
+                .with_client(client);
             Arc::new(p)
         }
         "codeberg" => {
@@ -60,7 +100,8 @@ pub fn create_provider(
                 token.to_string(),
                 username.to_string(),
                 "https://codeberg.org".to_string(),
-            )?;
+            )?
+            .with_client(client);
             Arc::new(p)
         }
         _ => {
@@ -73,6 +114,48 @@ pub fn create_provider(
     Ok(provider_instance)
 }
 
+/// Build an authenticated provider for a configured remote
+pub(crate) fn connect_remote(
+    config: &Config,
+    name: &str,
+    remote_config: &RemoteConfig,
+) -> Result<Arc<dyn Provider>> {
+    let provider_name = remote_config.provider_name(name);
+    let allow_insecure = config.security.allow_insecure_http;
+    let host = get_provider_host(
+        provider_name,
+        remote_config.api_url.as_deref(),
+        allow_insecure,
+    )?;
+
+    let auth_manager = AuthManager::new(AuthBackend::Keyring, config.security.audit_log);
+    let token = auth_manager
+        .retrieve_remote_credential(
+            name,
+            provider_name,
+            &host,
+            &remote_config.username,
+            config.security.allow_env_tokens,
+        )
+        .map_err(|_| MultiGitError::auth(provider_name, "credentials not found"))?;
+
+    create_provider_with_options(
+        provider_name,
+        &remote_config.username,
+        &token,
+        remote_config.api_url.as_deref(),
+        allow_insecure,
+        &config.client_options_for(remote_config),
+    )
+}
+
+/// Repository name from the URL of the git remote `remote`
+pub(crate) fn remote_repo_name(remote: &str) -> Option<String> {
+    let repo = git2::Repository::open(".").ok()?;
+    let url = RemoteManager::new(&repo).get_url(remote).ok()?;
+    url_utils::extract_repo_name(&url)
+}
+
 /// Check if a provider name is supported
 ///
 /// # Arguments
@@ -3,15 +3,22 @@
 //! Implements the Provider trait for Gitea and Forgejo instances using the REST API.
 
 use crate::api::{
-    client::build_api_client, rate_limiter::RateLimiter, retry::retry_async, retry::RetryConfig,
+    cache::{self, ResponseCache},
+    circuit_breaker::CircuitBreaker,
+    circuit_breaker::HostCircuit,
+    client::build_api_client,
+    rate_limiter::RateLimiter,
+    retry::retry_api,
+    retry::RetryConfig,
 };
-use crate::models::{RateLimit, Repository};
+use crate::models::{PullRequest, PullRequestState, RateLimit, Repository};
+use crate::providers::json_count;
 use crate::providers::traits::{Protocol, Provider, RepoConfig};
 use crate::utils::error::{MultiGitError, Result};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde_json::{json, Value};
-use tracing::{debug, info};
+use tracing::{debug, info, instrument};
 
 /// Gitea/Forgejo API provider
 pub struct GiteaProvider {
@@ -21,6 +28,8 @@ pub struct GiteaProvider {
     api_url: String,
     base_url: String, // For clone URLs
     rate_limiter: RateLimiter,
+    circuit: HostCircuit,
+    cache: ResponseCache,
 }
 
 impl GiteaProvider {
@@ -32,27 +41,70 @@ impl GiteaProvider {
             client: build_api_client()?,
             token,
             username,
+            circuit: CircuitBreaker::shared().for_host("gitea", &api_url),
             api_url,
             base_url: base_url.trim_end_matches('/').to_string(),
             rate_limiter: RateLimiter::new(1000.0, 10.0), // Conservative defaults
+            cache: ResponseCache::shared(),
         })
     }
 
+    /// Use a specific HTTP client (e.g. one built with per-remote TLS settings)
+    #[must_use]
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Use a specific response cache (e.g. `ResponseCache::disabled()` in tests)
+    #[must_use]
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Cache key prefix scoping entries to this provider and user
+    fn cache_prefix(&self) -> String {
+        cache::scope("gitea", &self.username, &self.token)
+    }
+
+    /// Make an authenticated GET request, answered from the response cache
+    /// when it is still fresh
     async fn get(&self, endpoint: &str) -> Result<Value> {
+        self.get_with_cache(endpoint, true).await
+    }
+
+    /// Make an authenticated GET request straight to the API, for answers
+    /// that must not be stale (credential checks, rate limits)
+    async fn get_fresh(&self, endpoint: &str) -> Result<Value> {
+        self.get_with_cache(endpoint, false).await
+    }
+
+    #[instrument(name = "gitea.get", level = "debug", skip_all, fields(endpoint = %endpoint))]
+    async fn get_with_cache(&self, endpoint: &str, use_cache: bool) -> Result<Value> {
+        let url = format!("{}{}", self.api_url, endpoint);
+
+        let cache_key = format!("{}{url}", self.cache_prefix());
+        if let Some(cached) = use_cache.then(|| self.cache.get(&cache_key)).flatten() {
+            debug!("Gitea GET (cached): {}", url);
+            return Ok(cached);
+        }
+
         self.rate_limiter
             .acquire()
             .await
             .map_err(MultiGitError::Other)?;
 
-        let url = format!("{}{}", self.api_url, endpoint);
         debug!("Gitea GET: {}", url);
 
-        retry_async(RetryConfig::for_api(), || async {
+        let data = retry_api(RetryConfig::for_api(), || async {
             let response = self
-                .client
-                .get(&url)
-                .header("Authorization", format!("token {}", self.token))
-                .send()
+                .circuit
+                .send(
+                    self.client
+                        .get(&url)
+                        .header("Authorization", format!("token {}", self.token)),
+                )
                 .await?;
 
             if !response.status().is_success() {
@@ -66,9 +118,13 @@ impl GiteaProvider {
             let data: Value = response.json().await?;
             Ok(data)
         })
-        .await
+        .await?;
+
+        self.cache.insert(cache_key, data.clone());
+        Ok(data)
     }
 
+    #[instrument(name = "gitea.post", level = "debug", skip_all, fields(endpoint = %endpoint))]
     async fn post(&self, endpoint: &str, body: Value) -> Result<Value> {
         self.rate_limiter
             .acquire()
@@ -77,14 +133,17 @@ impl GiteaProvider {
 
         let url = format!("{}{}", self.api_url, endpoint);
         debug!("Gitea POST: {}", url);
+        self.cache.invalidate_prefix(&self.cache_prefix());
 
-        retry_async(RetryConfig::for_api(), || async {
+        retry_api(RetryConfig::for_api(), || async {
             let response = self
-                .client
-                .post(&url)
-                .header("Authorization", format!("token {}", self.token))
-                .json(&body)
-                .send()
+                .circuit
+                .send(
+                    self.client
+                        .post(&url)
+                        .header("Authorization", format!("token {}", self.token))
+                        .json(&body),
+                )
                 .await?;
 
             if !response.status().is_success() {
@@ -100,6 +159,39 @@ impl GiteaProvider {
         })
         .await
     }
+
+    async fn delete(&self, endpoint: &str) -> Result<()> {
+        self.rate_limiter
+            .acquire()
+            .await
+            .map_err(MultiGitError::Other)?;
+
+        let url = format!("{}{}", self.api_url, endpoint);
+        debug!("Gitea DELETE: {}", url);
+        self.cache.invalidate_prefix(&self.cache_prefix());
+
+        retry_api(RetryConfig::for_api(), || async {
+            let response = self
+                .circuit
+                .send(
+                    self.client
+                        .delete(&url)
+                        .header("Authorization", format!("token {}", self.token)),
+                )
+                .await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(MultiGitError::Other(format!(
+                    "Gitea API error: {status} - {error_text}"
+                )));
+            }
+
+            Ok(())
+        })
+        .await
+    }
 }
 
 #[async_trait]
@@ -108,10 +200,13 @@ impl Provider for GiteaProvider {
         "gitea"
     }
 
+    #[instrument(name = "gitea.test_connection", level = "debug", skip_all)]
     async fn test_connection(&self) -> anyhow::Result<bool> {
         info!("Testing Gitea connection");
-        match self.get("/user").await {
+        match self.get_fresh("/user").await {
             Ok(_) => Ok(true),
+            // Unreachable hosts and timeouts aren't credential problems
+            Err(MultiGitError::NetworkError(e)) => Err(e.into()),
             Err(_) => Ok(false),
         }
     }
@@ -150,6 +245,10 @@ impl Provider for GiteaProvider {
                     .ok()
                     .map(std::convert::Into::into)
             }),
+            stars: json_count(&data["stars_count"]),
+            forks: json_count(&data["forks_count"]),
+            open_issues: json_count(&data["open_issues_count"]),
+            archived: data["archived"].as_bool(),
         })
     }
 
@@ -157,29 +256,20 @@ impl Provider for GiteaProvider {
         let endpoint = format!("/repos/{}/{}", self.username, name);
         let data = self.get(&endpoint).await?;
 
-        Ok(Repository {
-            name: data["name"].as_str().unwrap_or("").to_string(),
-            full_name: Some(data["full_name"].as_str().unwrap_or("").to_string()),
-            url: data["clone_url"].as_str().unwrap_or("").to_string(),
-            ssh_url: data["ssh_url"].as_str().unwrap_or("").to_string(),
-            private: data["private"].as_bool().unwrap_or(false),
-            default_branch: data["default_branch"]
-                .as_str()
-                .unwrap_or("main")
-                .to_string(),
-            description: data["description"].as_str().map(String::from),
-            html_url: data["html_url"].as_str().map(String::from),
-            created_at: data["created_at"].as_str().and_then(|s| {
-                chrono::DateTime::parse_from_rfc3339(s)
-                    .ok()
-                    .map(std::convert::Into::into)
-            }),
-            updated_at: data["updated_at"].as_str().and_then(|s| {
-                chrono::DateTime::parse_from_rfc3339(s)
-                    .ok()
-                    .map(std::convert::Into::into)
-            }),
-        })
+        Ok(parse_repository(&data))
+    }
+
+    async fn list_repos(&self) -> anyhow::Result<Vec<Repository>> {
+        debug!("Listing Gitea repositories of {}", self.username);
+
+        // Gitea caps `limit` at 50 by default
+        let data = self.get("/user/repos?limit=50").await?;
+        let mut repos: Vec<Repository> = data
+            .as_array()
+            .map(|repos| repos.iter().map(parse_repository).collect())
+            .unwrap_or_default();
+        repos.sort_by_key(|repo| std::cmp::Reverse(repo.updated_at));
+        Ok(repos)
     }
 
     fn get_remote_url(&self, name: &str, protocol: Protocol) -> String {
@@ -199,8 +289,7 @@ impl Provider for GiteaProvider {
         info!("Creating branch '{}' in {}/{}", branch, self.username, repo);
 
         // Get the default branch reference
-        let repo_data = self.get_repo(repo).await?;
-        let default_branch = repo_data.default_branch;
+        let default_branch = self.default_branch(repo).await?;
 
         let body = json!({
             "new_branch_name": branch,
@@ -218,6 +307,13 @@ impl Provider for GiteaProvider {
         Ok(())
     }
 
+    async fn delete_repo(&self, name: &str) -> anyhow::Result<()> {
+        info!("Deleting Gitea repository: {}", name);
+        self.delete(&format!("/repos/{}/{}", self.username, name))
+            .await?;
+        Ok(())
+    }
+
     async fn get_rate_limit(&self) -> anyhow::Result<RateLimit> {
         // Gitea doesn't typically have strict rate limits
         Ok(RateLimit {
@@ -226,4 +322,94 @@ impl Provider for GiteaProvider {
             reset_at: chrono::Utc::now() + chrono::Duration::hours(1),
         })
     }
+
+    fn supports_pull_requests(&self) -> bool {
+        true
+    }
+
+    async fn list_pull_requests(
+        &self,
+        repo: &str,
+        state: PullRequestState,
+    ) -> anyhow::Result<Vec<PullRequest>> {
+        debug!(
+            "Listing {} Gitea pull requests for {}/{}",
+            state, self.username, repo
+        );
+
+        // Like GitHub, Gitea reports merged pull requests as closed with `merged: true`
+        let state_param = match state {
+            PullRequestState::Open => "open",
+            PullRequestState::Closed | PullRequestState::Merged => "closed",
+            PullRequestState::All => "all",
+        };
+
+        let endpoint = format!(
+            "/repos/{}/{}/pulls?state={state_param}&limit=50",
+            self.username, repo
+        );
+        let data = self.get(&endpoint).await?;
+
+        Ok(data
+            .as_array()
+            .map(|pulls| pulls.iter().map(parse_pull_request).collect::<Vec<_>>())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|pr| state == PullRequestState::All || pr.state == state)
+            .collect())
+    }
+}
+
+/// Convert a Gitea pull request JSON object into a `PullRequest`
+/// Convert a Gitea repository JSON object into a `Repository`
+fn parse_repository(data: &Value) -> Repository {
+    Repository {
+        name: data["name"].as_str().unwrap_or("").to_string(),
+        full_name: Some(data["full_name"].as_str().unwrap_or("").to_string()),
+        url: data["clone_url"].as_str().unwrap_or("").to_string(),
+        ssh_url: data["ssh_url"].as_str().unwrap_or("").to_string(),
+        private: data["private"].as_bool().unwrap_or(false),
+        default_branch: data["default_branch"]
+            .as_str()
+            .unwrap_or("main")
+            .to_string(),
+        description: data["description"].as_str().map(String::from),
+        html_url: data["html_url"].as_str().map(String::from),
+        created_at: data["created_at"].as_str().and_then(|s| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .ok()
+                .map(std::convert::Into::into)
+        }),
+        updated_at: data["updated_at"].as_str().and_then(|s| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .ok()
+                .map(std::convert::Into::into)
+        }),
+        stars: json_count(&data["stars_count"]),
+        forks: json_count(&data["forks_count"]),
+        open_issues: json_count(&data["open_issues_count"]),
+        archived: data["archived"].as_bool(),
+    }
+}
+
+fn parse_pull_request(data: &Value) -> PullRequest {
+    let state = if data["merged"].as_bool().unwrap_or(false) {
+        PullRequestState::Merged
+    } else if data["state"].as_str() == Some("closed") {
+        PullRequestState::Closed
+    } else {
+        PullRequestState::Open
+    };
+
+    PullRequest::new(
+        data["number"].as_u64().unwrap_or(0),
+        data["title"].as_str().unwrap_or(""),
+        data["user"]["login"].as_str().unwrap_or(""),
+        data["html_url"].as_str().unwrap_or(""),
+        state,
+    )
+    .with_branches(
+        data["head"]["ref"].as_str().unwrap_or(""),
+        data["base"]["ref"].as_str().unwrap_or(""),
+    )
 }
@@ -3,15 +3,30 @@
 //! Implements the Provider trait for GitHub using the REST API v3.
 
 use crate::api::{
-    client::build_api_client, rate_limiter::RateLimiter, retry::retry_async, retry::RetryConfig,
+    cache::{self, ResponseCache},
+    circuit_breaker::CircuitBreaker,
+    circuit_breaker::HostCircuit,
+    client::{build_api_client, DEFAULT_API_TIMEOUT},
+    rate_limiter::RateLimiter,
+    retry::retry_api,
+    retry::RetryConfig,
+};
+use crate::models::{
+    PullRequest, PullRequestState, RateLimit, Release, ReleaseAsset, Repository, TokenInfo,
 };
-use crate::models::{RateLimit, Repository};
 use crate::providers::traits::{Protocol, Provider, RepoConfig};
+use crate::providers::{asset_name, file_body, json_count, upload_timeout};
 use crate::utils::error::{MultiGitError, Result};
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use reqwest::Client;
 use serde_json::{json, Value};
-use tracing::{debug, info};
+use std::path::Path;
+use std::time::Duration;
+use tracing::{debug, info, instrument};
+
+/// Response header carrying the expiry of fine-grained and expiring tokens
+const TOKEN_EXPIRATION_HEADER: &str = "github-authentication-token-expiration";
 
 /// GitHub API provider
 pub struct GitHubProvider {
@@ -19,6 +34,10 @@ pub struct GitHubProvider {
     token: String,
     username: String,
     rate_limiter: RateLimiter,
+    circuit: HostCircuit,
+    cache: ResponseCache,
+    /// Request timeout of the client, the base of [`upload_timeout`]
+    api_timeout: Duration,
 }
 
 impl GitHubProvider {
@@ -29,31 +48,80 @@ impl GitHubProvider {
             token,
             username,
             rate_limiter: RateLimiter::github(),
+            circuit: CircuitBreaker::shared().for_host("github", "https://api.github.com"),
+            cache: ResponseCache::shared(),
+            api_timeout: DEFAULT_API_TIMEOUT,
         })
     }
 
-    /// Make an authenticated GET request
+    /// Use a specific HTTP client (e.g. one built with per-remote TLS settings)
+    #[must_use]
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// The request timeout the client was built with
+    #[must_use]
+    pub fn with_api_timeout(mut self, timeout: Duration) -> Self {
+        self.api_timeout = timeout;
+        self
+    }
+
+    /// Use a specific response cache (e.g. `ResponseCache::disabled()` in tests)
+    #[must_use]
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Cache key prefix scoping entries to this provider and user
+    fn cache_prefix(&self) -> String {
+        cache::scope("github", &self.username, &self.token)
+    }
+
+    /// Make an authenticated GET request, answered from the response cache
+    /// when it is still fresh
     async fn get(&self, endpoint: &str) -> Result<Value> {
-        self.rate_limiter
-            .acquire()
-            .await
-            .map_err(MultiGitError::Other)?;
+        self.get_with_cache(endpoint, true).await
+    }
+
+    /// Make an authenticated GET request straight to the API, for answers
+    /// that must not be stale (credential checks, rate limits)
+    async fn get_fresh(&self, endpoint: &str) -> Result<Value> {
+        self.get_with_cache(endpoint, false).await
+    }
 
+    #[instrument(name = "github.get", level = "debug", skip_all, fields(endpoint = %endpoint))]
+    async fn get_with_cache(&self, endpoint: &str, use_cache: bool) -> Result<Value> {
         let url = if endpoint.starts_with("https://") {
             endpoint.to_string()
         } else {
             format!("https://api.github.com{endpoint}")
         };
 
+        let cache_key = format!("{}{url}", self.cache_prefix());
+        if let Some(cached) = use_cache.then(|| self.cache.get(&cache_key)).flatten() {
+            debug!("GitHub GET (cached): {}", url);
+            return Ok(cached);
+        }
+
+        self.rate_limiter
+            .acquire()
+            .await
+            .map_err(MultiGitError::Other)?;
+
         debug!("GitHub GET: {}", url);
 
-        retry_async(RetryConfig::for_api(), || async {
+        let data = retry_api(RetryConfig::for_api(), || async {
             let response = self
-                .client
-                .get(&url)
-                .header("Authorization", format!("Bearer {}", self.token))
-                .header("Accept", "application/vnd.github.v3+json")
-                .send()
+                .circuit
+                .send(
+                    self.client
+                        .get(&url)
+                        .header("Authorization", format!("Bearer {}", self.token))
+                        .header("Accept", "application/vnd.github.v3+json"),
+                )
                 .await?;
 
             if !response.status().is_success() {
@@ -67,10 +135,14 @@ impl GitHubProvider {
             let data: Value = response.json().await?;
             Ok(data)
         })
-        .await
+        .await?;
+
+        self.cache.insert(cache_key, data.clone());
+        Ok(data)
     }
 
     /// Make an authenticated POST request
+    #[instrument(name = "github.post", level = "debug", skip_all, fields(endpoint = %endpoint))]
     async fn post(&self, endpoint: &str, body: Value) -> Result<Value> {
         self.rate_limiter
             .acquire()
@@ -79,15 +151,18 @@ impl GitHubProvider {
 
         let url = format!("https://api.github.com{endpoint}");
         debug!("GitHub POST: {}", url);
+        self.cache.invalidate_prefix(&self.cache_prefix());
 
-        retry_async(RetryConfig::for_api(), || async {
+        retry_api(RetryConfig::for_api(), || async {
             let response = self
-                .client
-                .post(&url)
-                .header("Authorization", format!("Bearer {}", self.token))
-                .header("Accept", "application/vnd.github.v3+json")
-                .json(&body)
-                .send()
+                .circuit
+                .send(
+                    self.client
+                        .post(&url)
+                        .header("Authorization", format!("Bearer {}", self.token))
+                        .header("Accept", "application/vnd.github.v3+json")
+                        .json(&body),
+                )
                 .await?;
 
             if !response.status().is_success() {
@@ -113,14 +188,17 @@ impl GitHubProvider {
 
         let url = format!("https://api.github.com{endpoint}");
         debug!("GitHub DELETE: {}", url);
+        self.cache.invalidate_prefix(&self.cache_prefix());
 
-        retry_async(RetryConfig::for_api(), || async {
+        retry_api(RetryConfig::for_api(), || async {
             let response = self
-                .client
-                .delete(&url)
-                .header("Authorization", format!("Bearer {}", self.token))
-                .header("Accept", "application/vnd.github.v3+json")
-                .send()
+                .circuit
+                .send(
+                    self.client
+                        .delete(&url)
+                        .header("Authorization", format!("Bearer {}", self.token))
+                        .header("Accept", "application/vnd.github.v3+json"),
+                )
                 .await?;
 
             if !response.status().is_success() {
@@ -135,6 +213,46 @@ impl GitHubProvider {
         })
         .await
     }
+
+    /// Read the token expiry header GitHub attaches to authenticated responses
+    ///
+    /// Bypasses the response cache since the header isn't part of the body.
+    async fn token_expiration(&self) -> Result<Option<String>> {
+        self.rate_limiter
+            .acquire()
+            .await
+            .map_err(MultiGitError::Other)?;
+
+        let url = "https://api.github.com/user";
+        debug!("GitHub GET (token expiry): {}", url);
+
+        retry_api(RetryConfig::for_api(), || async {
+            let response = self
+                .circuit
+                .send(
+                    self.client
+                        .get(url)
+                        .header("Authorization", format!("Bearer {}", self.token))
+                        .header("Accept", "application/vnd.github.v3+json"),
+                )
+                .await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(MultiGitError::Other(format!(
+                    "GitHub API error: {status} - {error_text}"
+                )));
+            }
+
+            Ok(response
+                .headers()
+                .get(TOKEN_EXPIRATION_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from))
+        })
+        .await
+    }
 }
 
 #[async_trait]
@@ -143,14 +261,17 @@ impl Provider for GitHubProvider {
         "github"
     }
 
+    #[instrument(name = "github.test_connection", level = "debug", skip_all)]
     async fn test_connection(&self) -> anyhow::Result<bool> {
         info!("Testing GitHub connection for user: {}", self.username);
 
-        match self.get("/user").await {
+        match self.get_fresh("/user").await {
             Ok(_) => {
                 info!("GitHub connection successful");
                 Ok(true)
             }
+            // Unreachable hosts and timeouts aren't credential problems
+            Err(MultiGitError::NetworkError(e)) => Err(e.into()),
             Err(e) => {
                 info!("GitHub connection failed: {}", e);
                 Ok(false)
@@ -170,29 +291,7 @@ impl Provider for GitHubProvider {
 
         let data = self.post("/user/repos", body).await?;
 
-        Ok(Repository {
-            name: data["name"].as_str().unwrap_or("").to_string(),
-            full_name: Some(data["full_name"].as_str().unwrap_or("").to_string()),
-            url: data["clone_url"].as_str().unwrap_or("").to_string(),
-            ssh_url: data["ssh_url"].as_str().unwrap_or("").to_string(),
-            private: data["private"].as_bool().unwrap_or(false),
-            default_branch: data["default_branch"]
-                .as_str()
-                .unwrap_or("main")
-                .to_string(),
-            description: data["description"].as_str().map(String::from),
-            html_url: data["html_url"].as_str().map(String::from),
-            created_at: data["created_at"].as_str().and_then(|s| {
-                chrono::DateTime::parse_from_rfc3339(s)
-                    .ok()
-                    .map(std::convert::Into::into)
-            }),
-            updated_at: data["updated_at"].as_str().and_then(|s| {
-                chrono::DateTime::parse_from_rfc3339(s)
-                    .ok()
-                    .map(std::convert::Into::into)
-            }),
-        })
+        Ok(parse_repository(&data))
     }
 
     async fn get_repo(&self, name: &str) -> anyhow::Result<Repository> {
@@ -201,29 +300,19 @@ impl Provider for GitHubProvider {
         let endpoint = format!("/repos/{}/{}", self.username, name);
         let data = self.get(&endpoint).await?;
 
-        Ok(Repository {
-            name: data["name"].as_str().unwrap_or("").to_string(),
-            full_name: Some(data["full_name"].as_str().unwrap_or("").to_string()),
-            url: data["clone_url"].as_str().unwrap_or("").to_string(),
-            ssh_url: data["ssh_url"].as_str().unwrap_or("").to_string(),
-            private: data["private"].as_bool().unwrap_or(false),
-            default_branch: data["default_branch"]
-                .as_str()
-                .unwrap_or("main")
-                .to_string(),
-            description: data["description"].as_str().map(String::from),
-            html_url: data["html_url"].as_str().map(String::from),
-            created_at: data["created_at"].as_str().and_then(|s| {
-                chrono::DateTime::parse_from_rfc3339(s)
-                    .ok()
-                    .map(std::convert::Into::into)
-            }),
-            updated_at: data["updated_at"].as_str().and_then(|s| {
-                chrono::DateTime::parse_from_rfc3339(s)
-                    .ok()
-                    .map(std::convert::Into::into)
-            }),
-        })
+        Ok(parse_repository(&data))
+    }
+
+    async fn list_repos(&self) -> anyhow::Result<Vec<Repository>> {
+        debug!("Listing GitHub repositories of {}", self.username);
+
+        let data = self
+            .get("/user/repos?affiliation=owner&sort=updated&per_page=100")
+            .await?;
+        Ok(data
+            .as_array()
+            .map(|repos| repos.iter().map(parse_repository).collect())
+            .unwrap_or_default())
     }
 
     fn get_remote_url(&self, name: &str, protocol: Protocol) -> String {
@@ -237,8 +326,7 @@ impl Provider for GitHubProvider {
         info!("Creating branch '{}' in {}/{}", branch, self.username, repo);
 
         // Get the default branch SHA
-        let repo_data = self.get_repo(repo).await?;
-        let default_branch = repo_data.default_branch;
+        let default_branch = self.default_branch(repo).await?;
 
         let endpoint = format!(
             "/repos/{}/{}/git/refs/heads/{}",
@@ -278,10 +366,17 @@ impl Provider for GitHubProvider {
         Ok(())
     }
 
+    async fn delete_repo(&self, name: &str) -> anyhow::Result<()> {
+        info!("Deleting GitHub repository: {}", name);
+        self.delete(&format!("/repos/{}/{}", self.username, name))
+            .await?;
+        Ok(())
+    }
+
     async fn get_rate_limit(&self) -> anyhow::Result<RateLimit> {
         debug!("Fetching GitHub rate limit info");
 
-        let data = self.get("/rate_limit").await?;
+        let data = self.get_fresh("/rate_limit").await?;
         let core = &data["resources"]["core"];
 
         Ok(RateLimit {
@@ -291,6 +386,230 @@ impl Provider for GitHubProvider {
                 .unwrap_or(chrono::Utc::now()),
         })
     }
+
+    async fn token_info(&self) -> anyhow::Result<Option<TokenInfo>> {
+        debug!("Fetching GitHub token expiry");
+
+        // Classic tokens without an expiry don't get the header at all
+        let expires_at = self
+            .token_expiration()
+            .await?
+            .and_then(|value| parse_token_expiration(&value));
+
+        Ok(Some(TokenInfo {
+            name: None,
+            expires_at,
+        }))
+    }
+
+    fn supports_pull_requests(&self) -> bool {
+        true
+    }
+
+    async fn list_pull_requests(
+        &self,
+        repo: &str,
+        state: PullRequestState,
+    ) -> anyhow::Result<Vec<PullRequest>> {
+        debug!(
+            "Listing {} GitHub pull requests for {}/{}",
+            state, self.username, repo
+        );
+
+        // GitHub has no "merged" filter; merged PRs are closed PRs with `merged_at` set
+        let state_param = match state {
+            PullRequestState::Open => "open",
+            PullRequestState::Closed | PullRequestState::Merged => "closed",
+            PullRequestState::All => "all",
+        };
+
+        let endpoint = format!(
+            "/repos/{}/{}/pulls?state={state_param}&per_page=100",
+            self.username, repo
+        );
+        let data = self.get(&endpoint).await?;
+
+        Ok(data
+            .as_array()
+            .map(|pulls| pulls.iter().map(parse_pull_request).collect::<Vec<_>>())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|pr| state == PullRequestState::All || pr.state == state)
+            .collect())
+    }
+
+    fn supports_releases(&self) -> bool {
+        true
+    }
+
+    #[instrument(skip(self, body))]
+    async fn create_release(
+        &self,
+        repo: &str,
+        tag: &str,
+        name: &str,
+        body: &str,
+        prerelease: bool,
+    ) -> anyhow::Result<Release> {
+        info!(
+            "Creating GitHub release {} for {}/{}",
+            tag, self.username, repo
+        );
+
+        let payload = json!({
+            "tag_name": tag,
+            "name": name,
+            "body": body,
+            "prerelease": prerelease,
+        });
+        let endpoint = format!("/repos/{}/{}/releases", self.username, repo);
+        let data = self.post(&endpoint, payload).await?;
+        Ok(parse_release(&data))
+    }
+
+    /// Uploads go to `uploads.github.com` rather than the API host
+    #[instrument(skip(self))]
+    async fn upload_release_asset(
+        &self,
+        repo: &str,
+        release_id: &str,
+        path: &Path,
+    ) -> anyhow::Result<ReleaseAsset> {
+        let name = asset_name(path)?;
+        info!(
+            "Uploading {} to GitHub release {} of {}/{}",
+            name, release_id, self.username, repo
+        );
+
+        self.rate_limiter
+            .acquire()
+            .await
+            .map_err(MultiGitError::Other)?;
+
+        let url = format!(
+            "https://uploads.github.com/repos/{}/{}/releases/{release_id}/assets?name={}",
+            self.username,
+            repo,
+            urlencoding::encode(&name)
+        );
+
+        // Not retried: the POST isn't idempotent once the body has started
+        // going out, and a retry could attach the asset twice
+        let (body, size) = file_body(path).await?;
+        let response = self
+            .circuit
+            .send(
+                self.client
+                    .post(&url)
+                    .timeout(upload_timeout(size, self.api_timeout))
+                    .header("Authorization", format!("Bearer {}", self.token))
+                    .header("Accept", "application/vnd.github.v3+json")
+                    .header("Content-Type", "application/octet-stream")
+                    .header("Content-Length", size)
+                    .body(body),
+            )
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(
+                MultiGitError::Other(format!("GitHub API error: {status} - {error_text}")).into(),
+            );
+        }
+
+        let data: Value = response.json().await?;
+        Ok(parse_release_asset(&data))
+    }
+}
+
+/// Convert a GitHub repository JSON object into a `Repository`
+fn parse_repository(data: &Value) -> Repository {
+    Repository {
+        name: data["name"].as_str().unwrap_or("").to_string(),
+        full_name: Some(data["full_name"].as_str().unwrap_or("").to_string()),
+        url: data["clone_url"].as_str().unwrap_or("").to_string(),
+        ssh_url: data["ssh_url"].as_str().unwrap_or("").to_string(),
+        private: data["private"].as_bool().unwrap_or(false),
+        default_branch: data["default_branch"]
+            .as_str()
+            .unwrap_or("main")
+            .to_string(),
+        description: data["description"].as_str().map(String::from),
+        html_url: data["html_url"].as_str().map(String::from),
+        created_at: data["created_at"].as_str().and_then(|s| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .ok()
+                .map(std::convert::Into::into)
+        }),
+        updated_at: data["updated_at"].as_str().and_then(|s| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .ok()
+                .map(std::convert::Into::into)
+        }),
+        stars: json_count(&data["stargazers_count"]),
+        forks: json_count(&data["forks_count"]),
+        open_issues: json_count(&data["open_issues_count"]),
+        archived: data["archived"].as_bool(),
+    }
+}
+
+/// Parse `github-authentication-token-expiration`, e.g. `2024-06-01 12:00:00 UTC`
+fn parse_token_expiration(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S %z")
+        .map(|at| at.with_timezone(&Utc))
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(value.trim_end_matches(" UTC"), "%Y-%m-%d %H:%M:%S")
+                .map(|at| at.and_utc())
+        })
+        .ok()
+}
+
+/// Convert a GitHub pull request JSON object into a `PullRequest`
+fn parse_pull_request(data: &Value) -> PullRequest {
+    let state = if !data["merged_at"].is_null() {
+        PullRequestState::Merged
+    } else if data["state"].as_str() == Some("closed") {
+        PullRequestState::Closed
+    } else {
+        PullRequestState::Open
+    };
+
+    PullRequest::new(
+        data["number"].as_u64().unwrap_or(0),
+        data["title"].as_str().unwrap_or(""),
+        data["user"]["login"].as_str().unwrap_or(""),
+        data["html_url"].as_str().unwrap_or(""),
+        state,
+    )
+    .with_branches(
+        data["head"]["ref"].as_str().unwrap_or(""),
+        data["base"]["ref"].as_str().unwrap_or(""),
+    )
+}
+
+/// Convert a GitHub release JSON object into a `Release`
+fn parse_release(data: &Value) -> Release {
+    Release::new(
+        data["id"]
+            .as_u64()
+            .map(|id| id.to_string())
+            .unwrap_or_default(),
+        data["tag_name"].as_str().unwrap_or(""),
+        data["name"].as_str().unwrap_or(""),
+        data["html_url"].as_str().unwrap_or(""),
+        data["prerelease"].as_bool().unwrap_or(false),
+    )
+}
+
+/// Convert a GitHub release asset JSON object into a `ReleaseAsset`
+fn parse_release_asset(data: &Value) -> ReleaseAsset {
+    ReleaseAsset::new(
+        data["name"].as_str().unwrap_or(""),
+        data["browser_download_url"].as_str().unwrap_or(""),
+        data["size"].as_u64().unwrap_or(0),
+    )
 }
 
 #[cfg(test)]
@@ -318,4 +637,66 @@ mod tests {
             "git@github.com:testuser/myrepo.git"
         );
     }
+
+    #[test]
+    fn test_parse_pull_request() {
+        let data = json!({
+            "number": 7,
+            "title": "Fix typo",
+            "user": { "login": "octocat" },
+            "html_url": "https://github.com/user/repo/pull/7",
+            "state": "closed",
+            "merged_at": "2024-01-01T00:00:00Z",
+            "head": { "ref": "typo" },
+            "base": { "ref": "main" },
+        });
+
+        let pr = parse_pull_request(&data);
+        assert_eq!(pr.number, 7);
+        assert_eq!(pr.author, "octocat");
+        assert_eq!(pr.state, PullRequestState::Merged);
+        assert_eq!(pr.source_branch.as_deref(), Some("typo"));
+    }
+
+    #[test]
+    fn test_parse_release() {
+        let release = parse_release(&json!({
+            "id": 1234,
+            "tag_name": "v1.0.0",
+            "name": "v1.0.0",
+            "html_url": "https://github.com/user/repo/releases/tag/v1.0.0",
+            "prerelease": true,
+        }));
+        assert_eq!(release.id, "1234");
+        assert_eq!(release.tag, "v1.0.0");
+        assert!(release.prerelease);
+    }
+
+    #[test]
+    fn test_parse_release_asset() {
+        let asset = parse_release_asset(&json!({
+            "name": "bin.tar.gz",
+            "size": 2048,
+            "browser_download_url":
+                "https://github.com/user/repo/releases/download/v1.0.0/bin.tar.gz",
+        }));
+        assert_eq!(asset.name, "bin.tar.gz");
+        assert_eq!(asset.size, 2048);
+        assert!(asset.url.ends_with("/v1.0.0/bin.tar.gz"));
+    }
+
+    #[test]
+    fn test_parse_token_expiration() {
+        let expected = chrono::TimeZone::with_ymd_and_hms(&Utc, 2024, 6, 1, 12, 0, 0).unwrap();
+
+        assert_eq!(
+            parse_token_expiration("2024-06-01 12:00:00 UTC"),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_token_expiration("2024-06-01 14:00:00 +0200"),
+            Some(expected)
+        );
+        assert_eq!(parse_token_expiration("soon"), None);
+    }
 }
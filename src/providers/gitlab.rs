@@ -3,15 +3,26 @@
 //! Implements the Provider trait for GitLab using the REST API v4.
 
 use crate::api::{
-    client::build_api_client, rate_limiter::RateLimiter, retry::retry_async, retry::RetryConfig,
+    cache::{self, ResponseCache},
+    circuit_breaker::CircuitBreaker,
+    circuit_breaker::HostCircuit,
+    client::{build_api_client, DEFAULT_API_TIMEOUT},
+    rate_limiter::RateLimiter,
+    retry::retry_api,
+    retry::RetryConfig,
+};
+use crate::models::{
+    PullRequest, PullRequestState, RateLimit, Release, ReleaseAsset, Repository, TokenInfo,
 };
-use crate::models::{RateLimit, Repository};
 use crate::providers::traits::{Protocol, Provider, RepoConfig};
+use crate::providers::{asset_name, file_body, json_count, upload_timeout};
 use crate::utils::error::{MultiGitError, Result};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde_json::{json, Value};
-use tracing::{debug, info};
+use std::path::Path;
+use std::time::Duration;
+use tracing::{debug, info, instrument};
 
 /// GitLab API provider
 pub struct GitLabProvider {
@@ -20,35 +31,88 @@ pub struct GitLabProvider {
     username: String,
     api_url: String,
     rate_limiter: RateLimiter,
+    circuit: HostCircuit,
+    cache: ResponseCache,
+    /// Request timeout of the client, the base of [`upload_timeout`]
+    api_timeout: Duration,
 }
 
 impl GitLabProvider {
     /// Create a new GitLab provider
     pub fn new(token: String, username: String, api_url: Option<String>) -> Result<Self> {
+        let api_url = api_url.unwrap_or_else(|| "https://gitlab.com/api/v4".to_string());
+
         Ok(Self {
             client: build_api_client()?,
             token,
             username,
-            api_url: api_url.unwrap_or_else(|| "https://gitlab.com/api/v4".to_string()),
+            circuit: CircuitBreaker::shared().for_host("gitlab", &api_url),
+            api_url,
             rate_limiter: RateLimiter::gitlab(),
+            cache: ResponseCache::shared(),
+            api_timeout: DEFAULT_API_TIMEOUT,
         })
     }
 
+    /// Use a specific HTTP client (e.g. one built with per-remote TLS settings)
+    #[must_use]
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// The request timeout the client was built with
+    #[must_use]
+    pub fn with_api_timeout(mut self, timeout: Duration) -> Self {
+        self.api_timeout = timeout;
+        self
+    }
+
+    /// Use a specific response cache (e.g. `ResponseCache::disabled()` in tests)
+    #[must_use]
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Cache key prefix scoping entries to this provider and user
+    fn cache_prefix(&self) -> String {
+        cache::scope("gitlab", &self.username, &self.token)
+    }
+
+    /// Make an authenticated GET request, answered from the response cache
+    /// when it is still fresh
     async fn get(&self, endpoint: &str) -> Result<Value> {
+        self.get_with_cache(endpoint, true).await
+    }
+
+    /// Make an authenticated GET request straight to the API, for answers
+    /// that must not be stale (credential checks, rate limits)
+    async fn get_fresh(&self, endpoint: &str) -> Result<Value> {
+        self.get_with_cache(endpoint, false).await
+    }
+
+    #[instrument(name = "gitlab.get", level = "debug", skip_all, fields(endpoint = %endpoint))]
+    async fn get_with_cache(&self, endpoint: &str, use_cache: bool) -> Result<Value> {
+        let url = format!("{}{}", self.api_url, endpoint);
+
+        let cache_key = format!("{}{url}", self.cache_prefix());
+        if let Some(cached) = use_cache.then(|| self.cache.get(&cache_key)).flatten() {
+            debug!("GitLab GET (cached): {}", url);
+            return Ok(cached);
+        }
+
         self.rate_limiter
             .acquire()
             .await
             .map_err(MultiGitError::Other)?;
 
-        let url = format!("{}{}", self.api_url, endpoint);
         debug!("GitLab GET: {}", url);
 
-        retry_async(RetryConfig::for_api(), || async {
+        let data = retry_api(RetryConfig::for_api(), || async {
             let response = self
-                .client
-                .get(&url)
-                .header("PRIVATE-TOKEN", &self.token)
-                .send()
+                .circuit
+                .send(self.client.get(&url).header("PRIVATE-TOKEN", &self.token))
                 .await?;
 
             if !response.status().is_success() {
@@ -62,9 +126,13 @@ impl GitLabProvider {
             let data: Value = response.json().await?;
             Ok(data)
         })
-        .await
+        .await?;
+
+        self.cache.insert(cache_key, data.clone());
+        Ok(data)
     }
 
+    #[instrument(name = "gitlab.post", level = "debug", skip_all, fields(endpoint = %endpoint))]
     async fn post(&self, endpoint: &str, body: Value) -> Result<Value> {
         self.rate_limiter
             .acquire()
@@ -73,14 +141,17 @@ impl GitLabProvider {
 
         let url = format!("{}{}", self.api_url, endpoint);
         debug!("GitLab POST: {}", url);
+        self.cache.invalidate_prefix(&self.cache_prefix());
 
-        retry_async(RetryConfig::for_api(), || async {
+        retry_api(RetryConfig::for_api(), || async {
             let response = self
-                .client
-                .post(&url)
-                .header("PRIVATE-TOKEN", &self.token)
-                .json(&body)
-                .send()
+                .circuit
+                .send(
+                    self.client
+                        .post(&url)
+                        .header("PRIVATE-TOKEN", &self.token)
+                        .json(&body),
+                )
                 .await?;
 
             if !response.status().is_success() {
@@ -96,6 +167,39 @@ impl GitLabProvider {
         })
         .await
     }
+
+    async fn delete(&self, endpoint: &str) -> Result<()> {
+        self.rate_limiter
+            .acquire()
+            .await
+            .map_err(MultiGitError::Other)?;
+
+        let url = format!("{}{}", self.api_url, endpoint);
+        debug!("GitLab DELETE: {}", url);
+        self.cache.invalidate_prefix(&self.cache_prefix());
+
+        retry_api(RetryConfig::for_api(), || async {
+            let response = self
+                .circuit
+                .send(
+                    self.client
+                        .delete(&url)
+                        .header("PRIVATE-TOKEN", &self.token),
+                )
+                .await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(MultiGitError::Other(format!(
+                    "GitLab API error: {status} - {error_text}"
+                )));
+            }
+
+            Ok(())
+        })
+        .await
+    }
 }
 
 #[async_trait]
@@ -104,10 +208,13 @@ impl Provider for GitLabProvider {
         "gitlab"
     }
 
+    #[instrument(name = "gitlab.test_connection", level = "debug", skip_all)]
     async fn test_connection(&self) -> anyhow::Result<bool> {
         info!("Testing GitLab connection");
-        match self.get("/user").await {
+        match self.get_fresh("/user").await {
             Ok(_) => Ok(true),
+            // Unreachable hosts and timeouts aren't credential problems
+            Err(MultiGitError::NetworkError(e)) => Err(e.into()),
             Err(_) => Ok(false),
         }
     }
@@ -150,6 +257,10 @@ impl Provider for GitLabProvider {
                     .ok()
                     .map(std::convert::Into::into)
             }),
+            stars: json_count(&data["star_count"]),
+            forks: json_count(&data["forks_count"]),
+            open_issues: json_count(&data["open_issues_count"]),
+            archived: data["archived"].as_bool(),
         })
     }
 
@@ -159,34 +270,19 @@ impl Provider for GitLabProvider {
         let endpoint = format!("/projects/{encoded_path}");
         let data = self.get(&endpoint).await?;
 
-        Ok(Repository {
-            name: data["name"].as_str().unwrap_or("").to_string(),
-            full_name: Some(
-                data["path_with_namespace"]
-                    .as_str()
-                    .unwrap_or("")
-                    .to_string(),
-            ),
-            url: data["http_url_to_repo"].as_str().unwrap_or("").to_string(),
-            ssh_url: data["ssh_url_to_repo"].as_str().unwrap_or("").to_string(),
-            private: data["visibility"].as_str() == Some("private"),
-            default_branch: data["default_branch"]
-                .as_str()
-                .unwrap_or("main")
-                .to_string(),
-            description: data["description"].as_str().map(String::from),
-            html_url: data["web_url"].as_str().map(String::from),
-            created_at: data["created_at"].as_str().and_then(|s| {
-                chrono::DateTime::parse_from_rfc3339(s)
-                    .ok()
-                    .map(std::convert::Into::into)
-            }),
-            updated_at: data["last_activity_at"].as_str().and_then(|s| {
-                chrono::DateTime::parse_from_rfc3339(s)
-                    .ok()
-                    .map(std::convert::Into::into)
-            }),
-        })
+        Ok(parse_repository(&data))
+    }
+
+    async fn list_repos(&self) -> anyhow::Result<Vec<Repository>> {
+        debug!("Listing GitLab projects of {}", self.username);
+
+        let data = self
+            .get("/projects?owned=true&order_by=last_activity_at&per_page=100")
+            .await?;
+        Ok(data
+            .as_array()
+            .map(|projects| projects.iter().map(parse_repository).collect())
+            .unwrap_or_default())
     }
 
     fn get_remote_url(&self, name: &str, protocol: Protocol) -> String {
@@ -202,11 +298,12 @@ impl Provider for GitLabProvider {
             branch, self.username, repo
         );
 
+        let default_branch = self.default_branch(repo).await?;
         let path_string = format!("{}/{}", self.username, repo);
         let encoded_path = urlencoding::encode(&path_string);
         let body = json!({
             "branch": branch,
-            "ref": "main",
+            "ref": default_branch,
         });
 
         let endpoint = format!("/projects/{encoded_path}/repository/branches");
@@ -221,6 +318,14 @@ impl Provider for GitLabProvider {
         Ok(())
     }
 
+    async fn delete_repo(&self, name: &str) -> anyhow::Result<()> {
+        info!("Deleting GitLab repository: {}", name);
+        let path_string = format!("{}/{}", self.username, name);
+        let encoded_path = urlencoding::encode(&path_string);
+        self.delete(&format!("/projects/{encoded_path}")).await?;
+        Ok(())
+    }
+
     async fn get_rate_limit(&self) -> anyhow::Result<RateLimit> {
         // GitLab doesn't have a dedicated rate limit endpoint like GitHub
         // Using sensible defaults based on GitLab's documented limits
@@ -230,4 +335,274 @@ impl Provider for GitLabProvider {
             reset_at: chrono::Utc::now() + chrono::Duration::minutes(1),
         })
     }
+
+    async fn token_info(&self) -> anyhow::Result<Option<TokenInfo>> {
+        debug!("Fetching GitLab token details");
+
+        let data = self.get_fresh("/personal_access_tokens/self").await?;
+        Ok(Some(parse_token_info(&data)))
+    }
+
+    fn supports_pull_requests(&self) -> bool {
+        true
+    }
+
+    async fn list_pull_requests(
+        &self,
+        repo: &str,
+        state: PullRequestState,
+    ) -> anyhow::Result<Vec<PullRequest>> {
+        debug!(
+            "Listing {} GitLab merge requests for {}/{}",
+            state, self.username, repo
+        );
+
+        let state_param = match state {
+            PullRequestState::Open => "opened",
+            PullRequestState::Closed => "closed",
+            PullRequestState::Merged => "merged",
+            PullRequestState::All => "all",
+        };
+
+        let path_string = format!("{}/{}", self.username, repo);
+        let encoded_path = urlencoding::encode(&path_string);
+        let endpoint =
+            format!("/projects/{encoded_path}/merge_requests?state={state_param}&per_page=100");
+        let data = self.get(&endpoint).await?;
+
+        Ok(data
+            .as_array()
+            .map(|mrs| mrs.iter().map(parse_merge_request).collect())
+            .unwrap_or_default())
+    }
+
+    fn supports_releases(&self) -> bool {
+        true
+    }
+
+    /// GitLab has no pre-release flag, so `prerelease` is only reflected in
+    /// the returned `Release`
+    #[instrument(skip(self, body))]
+    async fn create_release(
+        &self,
+        repo: &str,
+        tag: &str,
+        name: &str,
+        body: &str,
+        prerelease: bool,
+    ) -> anyhow::Result<Release> {
+        info!(
+            "Creating GitLab release {} for {}/{}",
+            tag, self.username, repo
+        );
+
+        let path_string = format!("{}/{}", self.username, repo);
+        let encoded_path = urlencoding::encode(&path_string);
+        let payload = json!({
+            "tag_name": tag,
+            "name": name,
+            "description": body,
+        });
+        let data = self
+            .post(&format!("/projects/{encoded_path}/releases"), payload)
+            .await?;
+        Ok(parse_release(&data, prerelease))
+    }
+
+    /// GitLab releases only hold links, so the file goes to the project's
+    /// generic package registry (package `<repo>`, version `<tag>`) and is
+    /// then linked from the release
+    #[instrument(skip(self))]
+    async fn upload_release_asset(
+        &self,
+        repo: &str,
+        release_id: &str,
+        path: &Path,
+    ) -> anyhow::Result<ReleaseAsset> {
+        let name = asset_name(path)?;
+        info!(
+            "Uploading {} to GitLab release {} of {}/{}",
+            name, release_id, self.username, repo
+        );
+
+        let path_string = format!("{}/{}", self.username, repo);
+        let encoded_path = urlencoding::encode(&path_string);
+        let package_url = format!(
+            "{}/projects/{encoded_path}/packages/generic/{}/{}/{}",
+            self.api_url,
+            urlencoding::encode(repo),
+            urlencoding::encode(release_id),
+            urlencoding::encode(&name)
+        );
+
+        self.rate_limiter
+            .acquire()
+            .await
+            .map_err(MultiGitError::Other)?;
+
+        // Sent once, like GitHub's: a retry after the body has started
+        // going out could leave a duplicate package file behind
+        let (body, size) = file_body(path).await?;
+        let response = self
+            .circuit
+            .send(
+                self.client
+                    .put(&package_url)
+                    .timeout(upload_timeout(size, self.api_timeout))
+                    .header("PRIVATE-TOKEN", &self.token)
+                    .header("Content-Length", size)
+                    .body(body),
+            )
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(
+                MultiGitError::Other(format!("GitLab API error: {status} - {error_text}")).into(),
+            );
+        }
+
+        let link = json!({
+            "name": name,
+            "url": package_url,
+            "link_type": "package",
+        });
+        let endpoint = format!(
+            "/projects/{encoded_path}/releases/{}/assets/links",
+            urlencoding::encode(release_id)
+        );
+        let data = self.post(&endpoint, link).await?;
+        Ok(ReleaseAsset::new(
+            data["name"].as_str().unwrap_or(&name),
+            data["direct_asset_url"]
+                .as_str()
+                .or_else(|| data["url"].as_str())
+                .unwrap_or(&package_url),
+            size,
+        ))
+    }
+}
+
+/// Convert a GitLab personal access token JSON object into a `TokenInfo`
+///
+/// `expires_at` is a plain date; the token stops working at the start of it.
+/// Convert a GitLab project JSON object into a `Repository`
+fn parse_repository(data: &Value) -> Repository {
+    Repository {
+        name: data["name"].as_str().unwrap_or("").to_string(),
+        full_name: Some(
+            data["path_with_namespace"]
+                .as_str()
+                .unwrap_or("")
+                .to_string(),
+        ),
+        url: data["http_url_to_repo"].as_str().unwrap_or("").to_string(),
+        ssh_url: data["ssh_url_to_repo"].as_str().unwrap_or("").to_string(),
+        private: data["visibility"].as_str() == Some("private"),
+        default_branch: data["default_branch"]
+            .as_str()
+            .unwrap_or("main")
+            .to_string(),
+        description: data["description"].as_str().map(String::from),
+        html_url: data["web_url"].as_str().map(String::from),
+        created_at: data["created_at"].as_str().and_then(|s| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .ok()
+                .map(std::convert::Into::into)
+        }),
+        updated_at: data["last_activity_at"].as_str().and_then(|s| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .ok()
+                .map(std::convert::Into::into)
+        }),
+        stars: json_count(&data["star_count"]),
+        forks: json_count(&data["forks_count"]),
+        open_issues: json_count(&data["open_issues_count"]),
+        archived: data["archived"].as_bool(),
+    }
+}
+
+fn parse_token_info(data: &Value) -> TokenInfo {
+    TokenInfo {
+        name: data["name"].as_str().map(String::from),
+        expires_at: data["expires_at"]
+            .as_str()
+            .and_then(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|at| at.and_utc()),
+    }
+}
+
+/// Convert a GitLab merge request JSON object into a `PullRequest`
+fn parse_merge_request(data: &Value) -> PullRequest {
+    let state = match data["state"].as_str() {
+        Some("merged") => PullRequestState::Merged,
+        Some("closed" | "locked") => PullRequestState::Closed,
+        _ => PullRequestState::Open,
+    };
+
+    PullRequest::new(
+        data["iid"].as_u64().unwrap_or(0),
+        data["title"].as_str().unwrap_or(""),
+        data["author"]["username"].as_str().unwrap_or(""),
+        data["web_url"].as_str().unwrap_or(""),
+        state,
+    )
+    .with_branches(
+        data["source_branch"].as_str().unwrap_or(""),
+        data["target_branch"].as_str().unwrap_or(""),
+    )
+}
+
+/// Convert a GitLab release JSON object into a `Release`
+///
+/// GitLab addresses releases by tag name, which doubles as the id.
+fn parse_release(data: &Value, prerelease: bool) -> Release {
+    let tag = data["tag_name"].as_str().unwrap_or("");
+    Release::new(
+        tag,
+        tag,
+        data["name"].as_str().unwrap_or(""),
+        data["_links"]["self"].as_str().unwrap_or(""),
+        prerelease,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_token_info() {
+        let token = parse_token_info(&json!({
+            "name": "multigit",
+            "expires_at": "2024-06-01",
+        }));
+        assert_eq!(token.name.as_deref(), Some("multigit"));
+        assert_eq!(
+            token.expires_at.map(|at| at.to_rfc3339()),
+            Some("2024-06-01T00:00:00+00:00".to_string())
+        );
+
+        let never = parse_token_info(&json!({ "name": "forever", "expires_at": null }));
+        assert_eq!(never.expires_at, None);
+    }
+
+    #[test]
+    fn test_parse_release() {
+        let release = parse_release(
+            &json!({
+                "tag_name": "v1.0.0",
+                "name": "v1.0.0",
+                "_links": { "self": "https://gitlab.com/user/repo/-/releases/v1.0.0" },
+            }),
+            false,
+        );
+        assert_eq!(release.id, "v1.0.0");
+        assert_eq!(
+            release.url,
+            "https://gitlab.com/user/repo/-/releases/v1.0.0"
+        );
+    }
 }
@@ -0,0 +1,358 @@
+//! In-memory provider for tests
+//!
+//! `MockProvider` implements [`Provider`] without touching the network.
+//! Responses are programmed up front with the `with_*` builders, and every
+//! call is recorded so tests can assert on what a command flow did. It is only
+//! built for this crate's tests, or for others with the `test-util` feature.
+
+use crate::models::{RateLimit, Release, ReleaseAsset, Repository, TokenInfo};
+use crate::providers::traits::{Protocol, Provider, RepoConfig};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Provider with programmable responses
+#[derive(Debug)]
+pub struct MockProvider {
+    name: String,
+    connection: std::result::Result<bool, String>,
+    repository: Option<Repository>,
+    repositories: Option<Vec<Repository>>,
+    get_error: Option<String>,
+    create_error: Option<String>,
+    delete_error: Option<String>,
+    rate_limit: Option<RateLimit>,
+    token_info: Option<TokenInfo>,
+    latency: Option<Duration>,
+    releases: bool,
+    calls: Mutex<Vec<String>>,
+}
+
+impl MockProvider {
+    /// Create a mock named `name` whose calls all succeed
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            connection: Ok(true),
+            repository: None,
+            repositories: None,
+            get_error: None,
+            create_error: None,
+            delete_error: None,
+            rate_limit: None,
+            token_info: None,
+            latency: None,
+            releases: false,
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Make `test_connection` return `Ok(authenticated)`
+    #[must_use]
+    pub fn with_connection(mut self, authenticated: bool) -> Self {
+        self.connection = Ok(authenticated);
+        self
+    }
+
+    /// Make `test_connection` fail with `message`
+    #[must_use]
+    pub fn with_connection_error(mut self, message: impl Into<String>) -> Self {
+        self.connection = Err(message.into());
+        self
+    }
+
+    /// Return `repository` from `create_repo` and `get_repo`
+    #[must_use]
+    pub fn with_repository(mut self, repository: Repository) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+
+    /// Return `repositories` from `list_repos` (otherwise it is unsupported)
+    #[must_use]
+    pub fn with_repositories(mut self, repositories: Vec<Repository>) -> Self {
+        self.repositories = Some(repositories);
+        self
+    }
+
+    /// Make `get_repo` fail with `message` (e.g. a 404 for a missing repository)
+    #[must_use]
+    pub fn with_get_repo_error(mut self, message: impl Into<String>) -> Self {
+        self.get_error = Some(message.into());
+        self
+    }
+
+    /// Make `create_repo` fail with `message`
+    #[must_use]
+    pub fn with_create_error(mut self, message: impl Into<String>) -> Self {
+        self.create_error = Some(message.into());
+        self
+    }
+
+    /// Make `delete_repo` fail with `message`
+    #[must_use]
+    pub fn with_delete_error(mut self, message: impl Into<String>) -> Self {
+        self.delete_error = Some(message.into());
+        self
+    }
+
+    /// Return `rate_limit` from `get_rate_limit` (otherwise it fails)
+    #[must_use]
+    pub fn with_rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Return `token_info` from `token_info` (otherwise `None`)
+    #[must_use]
+    pub fn with_token_info(mut self, token_info: TokenInfo) -> Self {
+        self.token_info = Some(token_info);
+        self
+    }
+
+    /// Delay `test_connection` by `latency`, like a slow or dead host
+    #[must_use]
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// Support releases: `create_release` and `upload_release_asset` succeed
+    /// instead of failing
+    #[must_use]
+    pub fn with_releases(mut self) -> Self {
+        self.releases = true;
+        self
+    }
+
+    /// Calls made so far, as `method:argument` strings in call order
+    ///
+    /// # Panics
+    /// Panics if the call log mutex is poisoned
+    #[must_use]
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn record(&self, call: String) {
+        self.calls.lock().unwrap().push(call);
+    }
+
+    fn repository_named(&self, name: &str, private: bool) -> Repository {
+        self.repository.clone().unwrap_or_else(|| {
+            Repository::new(
+                name,
+                self.get_remote_url(name, Protocol::Https),
+                self.get_remote_url(name, Protocol::Ssh),
+                private,
+                "main",
+            )
+        })
+    }
+}
+
+#[async_trait]
+impl Provider for MockProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn test_connection(&self) -> anyhow::Result<bool> {
+        self.record("test_connection".to_string());
+        if let Some(latency) = self.latency {
+            tokio::time::sleep(latency).await;
+        }
+        self.connection.clone().map_err(|e| anyhow!(e))
+    }
+
+    async fn create_repo(&self, config: RepoConfig) -> anyhow::Result<Repository> {
+        self.record(format!("create_repo:{}", config.name));
+        if let Some(ref message) = self.create_error {
+            return Err(anyhow!(message.clone()));
+        }
+        Ok(self.repository_named(&config.name, config.private))
+    }
+
+    async fn get_repo(&self, name: &str) -> anyhow::Result<Repository> {
+        self.record(format!("get_repo:{name}"));
+        if let Some(ref message) = self.get_error {
+            return Err(anyhow!(message.clone()));
+        }
+        Ok(self.repository_named(name, false))
+    }
+
+    async fn list_repos(&self) -> anyhow::Result<Vec<Repository>> {
+        self.record("list_repos".to_string());
+        self.repositories
+            .clone()
+            .ok_or_else(|| anyhow!("{} does not support listing repositories", self.name))
+    }
+
+    fn get_remote_url(&self, name: &str, protocol: Protocol) -> String {
+        match protocol {
+            Protocol::Https => format!("https://{}.example/mock/{name}.git", self.name),
+            Protocol::Ssh => format!("git@{}.example:mock/{name}.git", self.name),
+        }
+    }
+
+    async fn create_branch(&self, repo: &str, branch: &str) -> anyhow::Result<()> {
+        self.record(format!("create_branch:{repo}/{branch}"));
+        Ok(())
+    }
+
+    async fn delete_branch(&self, repo: &str, branch: &str) -> anyhow::Result<()> {
+        self.record(format!("delete_branch:{repo}/{branch}"));
+        Ok(())
+    }
+
+    async fn delete_repo(&self, name: &str) -> anyhow::Result<()> {
+        self.record(format!("delete_repo:{name}"));
+        match self.delete_error {
+            Some(ref message) => Err(anyhow!(message.clone())),
+            None => Ok(()),
+        }
+    }
+
+    async fn get_rate_limit(&self) -> anyhow::Result<RateLimit> {
+        self.record("get_rate_limit".to_string());
+        self.rate_limit
+            .clone()
+            .ok_or_else(|| anyhow!("rate limit not available"))
+    }
+
+    async fn token_info(&self) -> anyhow::Result<Option<TokenInfo>> {
+        self.record("token_info".to_string());
+        Ok(self.token_info.clone())
+    }
+
+    fn supports_releases(&self) -> bool {
+        self.releases
+    }
+
+    async fn create_release(
+        &self,
+        repo: &str,
+        tag: &str,
+        name: &str,
+        _body: &str,
+        prerelease: bool,
+    ) -> anyhow::Result<Release> {
+        self.record(format!("create_release:{repo}/{tag}"));
+        if !self.releases {
+            return Err(anyhow!("{} does not support creating releases", self.name));
+        }
+        let url = format!("https://{}.example/mock/{repo}/releases/{tag}", self.name);
+        Ok(Release::new(tag, tag, name, url, prerelease))
+    }
+
+    async fn upload_release_asset(
+        &self,
+        repo: &str,
+        release_id: &str,
+        path: &Path,
+    ) -> anyhow::Result<ReleaseAsset> {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        self.record(format!("upload_release_asset:{repo}/{release_id}/{name}"));
+        if !self.releases {
+            return Err(anyhow!("{} does not support uploading assets", self.name));
+        }
+        let size = std::fs::metadata(path)?.len();
+        let url = format!(
+            "https://{}.example/mock/{repo}/releases/{release_id}/{name}",
+            self.name
+        );
+        Ok(ReleaseAsset::new(name, url, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo_config(name: &str) -> RepoConfig {
+        RepoConfig {
+            name: name.to_string(),
+            description: String::new(),
+            private: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_defaults_succeed_and_record_calls() {
+        let provider = MockProvider::new("mock");
+
+        assert!(provider.test_connection().await.unwrap());
+        let repo = provider.create_repo(repo_config("demo")).await.unwrap();
+        assert_eq!(repo.name, "demo");
+        assert!(repo.private);
+        assert_eq!(repo.url, "https://mock.example/mock/demo.git");
+        assert!(provider.get_rate_limit().await.is_err());
+
+        assert_eq!(
+            provider.calls(),
+            vec!["test_connection", "create_repo:demo", "get_rate_limit"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_programmed_failures() {
+        let provider = MockProvider::new("mock")
+            .with_connection_error("connection refused")
+            .with_create_error("name already exists")
+            .with_delete_error("forbidden");
+
+        let err = provider.test_connection().await.unwrap_err();
+        assert_eq!(err.to_string(), "connection refused");
+        assert!(provider.create_repo(repo_config("demo")).await.is_err());
+        assert!(provider.delete_repo("demo").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_canned_repository() {
+        let canned = Repository::new(
+            "canned",
+            "https://x/canned.git",
+            "git@x:canned.git",
+            false,
+            "trunk",
+        );
+        let provider = MockProvider::new("mock").with_repository(canned);
+
+        let repo = provider.get_repo("anything").await.unwrap();
+        assert_eq!(repo.name, "canned");
+        assert_eq!(repo.default_branch, "trunk");
+    }
+
+    #[tokio::test]
+    async fn test_list_repos() {
+        assert!(MockProvider::new("mock").list_repos().await.is_err());
+
+        let listed = Repository::new("listed", "https://x/l.git", "git@x:l.git", true, "main");
+        let provider = MockProvider::new("mock").with_repositories(vec![listed]);
+        let repos = provider.list_repos().await.unwrap();
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].name, "listed");
+        assert_eq!(provider.calls(), vec!["list_repos"]);
+    }
+
+    #[tokio::test]
+    async fn test_create_release() {
+        let unsupported = MockProvider::new("mock");
+        assert!(!unsupported.supports_releases());
+        assert!(unsupported
+            .create_release("demo", "v1.0.0", "v1.0.0", "notes", false)
+            .await
+            .is_err());
+
+        let provider = MockProvider::new("mock").with_releases();
+        let release = provider
+            .create_release("demo", "v1.0.0", "v1.0.0", "notes", true)
+            .await
+            .unwrap();
+        assert_eq!(release.tag, "v1.0.0");
+        assert!(release.prerelease);
+        assert_eq!(provider.calls(), vec!["create_release:demo/v1.0.0"]);
+    }
+}
@@ -6,7 +6,46 @@ pub mod factory;
 pub mod gitea;
 pub mod github;
 pub mod gitlab;
+#[cfg(any(test, feature = "test-util"))]
+pub mod mock;
 pub mod traits;
 
-pub use factory::{create_provider, is_supported_provider, supported_providers};
+pub use factory::{
+    create_provider, create_provider_with_options, is_supported_provider, supported_providers,
+};
 pub use traits::{Protocol, Provider, RepoConfig};
+
+/// A count from an API response, if present and in range
+pub(crate) fn json_count(value: &serde_json::Value) -> Option<u32> {
+    value.as_u64().and_then(|count| u32::try_from(count).ok())
+}
+
+/// A streamed request body for the file at `path`, and its size in bytes
+///
+/// The file is read in chunks while the request is sent, so large release
+/// assets are never held in memory whole.
+pub(crate) async fn file_body(path: &std::path::Path) -> std::io::Result<(reqwest::Body, u64)> {
+    let file = tokio::fs::File::open(path).await?;
+    let size = file.metadata().await?.len();
+    let stream = tokio_util::io::ReaderStream::new(file);
+    Ok((reqwest::Body::wrap_stream(stream), size))
+}
+
+/// Slowest upload rate a release asset is given time for, in bytes per second
+const MIN_UPLOAD_RATE: u64 = 64 * 1024;
+
+/// Timeout for uploading a `size`-byte release asset
+///
+/// `api_timeout` only covers ordinary requests, so uploads get it plus the
+/// time needed to send the body at [`MIN_UPLOAD_RATE`].
+pub(crate) fn upload_timeout(size: u64, api_timeout: std::time::Duration) -> std::time::Duration {
+    api_timeout + std::time::Duration::from_secs(size / MIN_UPLOAD_RATE)
+}
+
+/// The name a release asset at `path` is uploaded under
+pub(crate) fn asset_name(path: &std::path::Path) -> anyhow::Result<String> {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(String::from)
+        .ok_or_else(|| anyhow::anyhow!("'{}' is not a file name", path.display()))
+}
@@ -2,8 +2,11 @@
 //!
 //! Defines the common interface that all Git hosting providers must implement.
 
-use crate::models::{RateLimit, Repository};
+use crate::models::{
+    PullRequest, PullRequestState, RateLimit, Release, ReleaseAsset, Repository, TokenInfo,
+};
 use async_trait::async_trait;
+use std::path::Path;
 
 /// Configuration for creating a repository
 #[derive(Debug, Clone)]
@@ -39,6 +42,26 @@ pub trait Provider: Send + Sync {
     /// Get repository information
     async fn get_repo(&self, name: &str) -> anyhow::Result<Repository>;
 
+    /// List repositories owned by the authenticated user, most recently
+    /// updated first
+    ///
+    /// Returns the first page only (up to 100). Providers that can't list
+    /// repositories return an error.
+    async fn list_repos(&self) -> anyhow::Result<Vec<Repository>> {
+        Err(anyhow::anyhow!(
+            "{} does not support listing repositories",
+            self.name()
+        ))
+    }
+
+    /// Get the repository's default branch (e.g. `main` or `master`)
+    ///
+    /// Read from the repository returned by `get_repo`, so providers that
+    /// cache GET responses answer repeated lookups without another request.
+    async fn default_branch(&self, repo: &str) -> anyhow::Result<String> {
+        Ok(self.get_repo(repo).await?.default_branch)
+    }
+
     /// Get the remote URL for a repository
     fn get_remote_url(&self, name: &str, protocol: Protocol) -> String;
 
@@ -48,8 +71,83 @@ pub trait Provider: Send + Sync {
     /// Delete a branch
     async fn delete_branch(&self, repo: &str, branch: &str) -> anyhow::Result<()>;
 
+    /// Delete a repository
+    ///
+    /// Used to roll back partially completed operations. Providers that
+    /// can't delete repositories return an error.
+    async fn delete_repo(&self, name: &str) -> anyhow::Result<()> {
+        Err(anyhow::anyhow!(
+            "{} does not support deleting repository '{name}'",
+            self.name()
+        ))
+    }
+
     /// Get rate limit information
     async fn get_rate_limit(&self) -> anyhow::Result<RateLimit>;
+
+    /// Describe the token in use, including its expiry
+    ///
+    /// Returns `None` for providers that can't report on their tokens.
+    async fn token_info(&self) -> anyhow::Result<Option<TokenInfo>> {
+        Ok(None)
+    }
+
+    /// Whether this provider can list pull/merge requests
+    fn supports_pull_requests(&self) -> bool {
+        false
+    }
+
+    /// List pull/merge requests for a repository, filtered by state
+    ///
+    /// Providers without pull request support return an empty list.
+    async fn list_pull_requests(
+        &self,
+        _repo: &str,
+        _state: PullRequestState,
+    ) -> anyhow::Result<Vec<PullRequest>> {
+        Ok(Vec::new())
+    }
+
+    /// Whether this provider can create releases
+    fn supports_releases(&self) -> bool {
+        false
+    }
+
+    /// Create a release for the existing tag `tag`, titled `name`, with
+    /// `body` (Markdown) as its notes
+    ///
+    /// Providers without release support return an error; check
+    /// [`Provider::supports_releases`] first to skip them.
+    async fn create_release(
+        &self,
+        _repo: &str,
+        tag: &str,
+        _name: &str,
+        _body: &str,
+        _prerelease: bool,
+    ) -> anyhow::Result<Release> {
+        Err(anyhow::anyhow!(
+            "{} does not support creating release '{tag}'",
+            self.name()
+        ))
+    }
+
+    /// Attach the file at `path` to the release `release_id` (see
+    /// [`Release::id`]), streaming it rather than reading it into memory
+    ///
+    /// Providers without release support return an error.
+    async fn upload_release_asset(
+        &self,
+        _repo: &str,
+        release_id: &str,
+        path: &Path,
+    ) -> anyhow::Result<ReleaseAsset> {
+        Err(anyhow::anyhow!(
+            "{} does not support uploading '{}' to release '{release_id}'",
+            self.name(),
+            path.display()
+        ))
+    }
 }
 
 // TODO: Implement this trait for each provider in Phase 3
@@ -4,13 +4,15 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::PathBuf;
+use std::str::FromStr;
 use tracing::{debug, warn};
 
 /// Audit event types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AuditEventType {
     /// Credential storage event
     CredentialStore,
@@ -24,10 +26,75 @@ pub enum AuditEventType {
     RemoteRemove,
     /// Push operation event
     Push,
+    /// Push that overwrote remote history
+    ForcePush,
     /// Pull operation event
     Pull,
     /// Sync operation event
     Sync,
+    /// Repository created on a provider
+    RepoCreate,
+    /// Repository deleted from a provider
+    RepoDelete,
+}
+
+impl AuditEventType {
+    /// Every event type, in declaration order
+    pub const ALL: [Self; 11] = [
+        Self::CredentialStore,
+        Self::CredentialRetrieve,
+        Self::CredentialDelete,
+        Self::RemoteAdd,
+        Self::RemoteRemove,
+        Self::Push,
+        Self::ForcePush,
+        Self::Pull,
+        Self::Sync,
+        Self::RepoCreate,
+        Self::RepoDelete,
+    ];
+
+    /// Kebab-case name, as accepted by `multigit audit --event`
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CredentialStore => "credential-store",
+            Self::CredentialRetrieve => "credential-retrieve",
+            Self::CredentialDelete => "credential-delete",
+            Self::RemoteAdd => "remote-add",
+            Self::RemoteRemove => "remote-remove",
+            Self::Push => "push",
+            Self::ForcePush => "force-push",
+            Self::Pull => "pull",
+            Self::Sync => "sync",
+            Self::RepoCreate => "repo-create",
+            Self::RepoDelete => "repo-delete",
+        }
+    }
+}
+
+impl fmt::Display for AuditEventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AuditEventType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let wanted = s.trim().to_lowercase().replace('_', "-");
+        Self::ALL
+            .into_iter()
+            .find(|event| event.as_str() == wanted)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|event| event.as_str()).collect();
+                format!(
+                    "unknown audit event '{s}' (expected one of: {})",
+                    names.join(", ")
+                )
+            })
+    }
 }
 
 /// An audit log entry
@@ -73,6 +140,35 @@ impl AuditEntry {
     }
 }
 
+/// Filter for [`AuditLogger::query`]; empty fields match everything
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    /// Only entries of this type
+    pub event: Option<AuditEventType>,
+    /// Only entries whose resource contains this text
+    pub resource: Option<String>,
+    /// Only entries at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only failed operations
+    pub failed_only: bool,
+    /// Keep only the most recent entries
+    pub limit: Option<usize>,
+}
+
+impl AuditQuery {
+    /// Whether `entry` passes every filter (`limit` aside)
+    #[must_use]
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        self.event.map_or(true, |event| entry.event_type == event)
+            && self
+                .resource
+                .as_deref()
+                .map_or(true, |resource| entry.resource.contains(resource))
+            && self.since.map_or(true, |since| entry.timestamp >= since)
+            && !(self.failed_only && entry.success)
+    }
+}
+
 /// Audit logger
 pub struct AuditLogger {
     log_path: PathBuf,
@@ -86,6 +182,12 @@ impl AuditLogger {
         Self { log_path, enabled }
     }
 
+    /// Logger writing to the default path, when `enabled` (`security.audit_log`)
+    #[must_use]
+    pub fn at_default_path(enabled: bool) -> Self {
+        Self::new(Self::default_path(), enabled)
+    }
+
     /// Get default audit log path
     #[must_use]
     pub fn default_path() -> PathBuf {
@@ -153,6 +255,20 @@ impl AuditLogger {
 
         Ok(entries)
     }
+
+    /// Entries matching `query`, oldest first
+    pub fn query(&self, query: &AuditQuery) -> std::io::Result<Vec<AuditEntry>> {
+        let mut entries: Vec<AuditEntry> = self
+            .read_entries()?
+            .into_iter()
+            .filter(|entry| query.matches(entry))
+            .collect();
+        if let Some(limit) = query.limit {
+            let skip = entries.len().saturating_sub(limit);
+            entries.drain(..skip);
+        }
+        Ok(entries)
+    }
 }
 
 impl Default for AuditLogger {
@@ -190,4 +306,48 @@ mod tests {
         assert_eq!(entries.len(), 1);
         assert!(entries[0].success);
     }
+
+    #[test]
+    fn test_event_type_names_round_trip() {
+        for event in AuditEventType::ALL {
+            assert_eq!(event.as_str().parse::<AuditEventType>(), Ok(event));
+        }
+        assert_eq!("FORCE_PUSH".parse(), Ok(AuditEventType::ForcePush));
+        assert!("deploy".parse::<AuditEventType>().is_err());
+    }
+
+    #[test]
+    fn test_query_filters_and_limits() {
+        let temp_dir = TempDir::new().unwrap();
+        let logger = AuditLogger::new(temp_dir.path().join("audit.log"), true);
+        logger.log(AuditEntry::new(AuditEventType::Push, "github:main", true));
+        logger.log(AuditEntry::new(AuditEventType::Push, "gitlab:main", false));
+        logger.log(AuditEntry::new(AuditEventType::Sync, "github:main", true));
+        logger.log(AuditEntry::new(AuditEventType::Push, "github:dev", true));
+
+        let pushes = AuditQuery {
+            event: Some(AuditEventType::Push),
+            ..AuditQuery::default()
+        };
+        assert_eq!(logger.query(&pushes).unwrap().len(), 3);
+
+        let github = AuditQuery {
+            resource: Some("github".to_string()),
+            limit: Some(2),
+            ..AuditQuery::default()
+        };
+        let resources: Vec<String> = logger
+            .query(&github)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.resource)
+            .collect();
+        assert_eq!(resources, vec!["github:main", "github:dev"]);
+
+        let failed = AuditQuery {
+            failed_only: true,
+            ..AuditQuery::default()
+        };
+        assert_eq!(logger.query(&failed).unwrap()[0].resource, "gitlab:main");
+    }
 }
@@ -2,24 +2,22 @@
 //!
 //! Provides a cross-platform interface to store credentials securely using the OS keyring.
 
+use crate::core::profile;
 use crate::utils::error::{MultiGitError, Result};
 use keyring::Entry;
 use tracing::{debug, info};
 
-/// Service name for keyring entries
-const SERVICE_NAME: &str = "multigit";
-
 /// Keyring manager for secure credential storage
 pub struct KeyringManager {
     service: String,
 }
 
 impl KeyringManager {
-    /// Create a new keyring manager
+    /// Create a keyring manager for the active profile's namespace
     #[must_use]
     pub fn new() -> Self {
         Self {
-            service: SERVICE_NAME.to_string(),
+            service: profile::keyring_service(),
         }
     }
 
@@ -138,6 +136,24 @@ impl KeyringManager {
 
         result
     }
+
+    /// Store the token of one configured remote
+    ///
+    /// Keyed by the remote's config name, so two remotes on the same provider
+    /// with the same username keep separate tokens.
+    pub fn store_remote_token(&self, remote: &str, token: &str) -> Result<()> {
+        self.store(&format!("remote:{remote}:token"), token)
+    }
+
+    /// Retrieve the token of one configured remote
+    pub fn retrieve_remote_token(&self, remote: &str) -> Result<String> {
+        self.retrieve(&format!("remote:{remote}:token"))
+    }
+
+    /// Delete the token of one configured remote
+    pub fn delete_remote_token(&self, remote: &str) -> Result<()> {
+        self.delete(&format!("remote:{remote}:token"))
+    }
 }
 
 impl Default for KeyringManager {
@@ -215,6 +231,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_remote_token_methods() {
+        let manager = KeyringManager::new();
+
+        if manager.store_remote_token("test-org-a", "token-a").is_ok() {
+            let _ = manager.store_remote_token("test-org-b", "token-b");
+            assert_eq!(
+                manager.retrieve_remote_token("test-org-a").unwrap(),
+                "token-a"
+            );
+            assert_eq!(
+                manager.retrieve_remote_token("test-org-b").unwrap(),
+                "token-b"
+            );
+
+            // Cleanup
+            let _ = manager.delete_remote_token("test-org-a");
+            let _ = manager.delete_remote_token("test-org-b");
+        }
+    }
+
     #[test]
     fn test_legacy_token_migration() {
         let manager = KeyringManager::new();
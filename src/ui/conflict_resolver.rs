@@ -3,13 +3,15 @@
 //! Provides a visual interface for resolving merge conflicts.
 
 use crate::core::config::Config;
+use crate::git::conflict_markers::{ConflictedFile, HunkChoice};
 use crate::ui::formatter::{colors, Status};
-use crate::utils::error::Result;
+use crate::utils::error::{MultiGitError, Result};
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use git2::{IndexEntry, Repository};
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Constraint, Direction, Layout, Margin, Rect},
@@ -21,6 +23,7 @@ use ratatui::{
     Frame, Terminal,
 };
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
@@ -42,6 +45,10 @@ pub struct Conflict {
     pub resolution: ResolutionChoice,
     /// Resolved content
     pub resolved_content: Option<String>,
+    /// Working-tree file split at its conflict markers, if it has any
+    pub hunks: Option<ConflictedFile>,
+    /// Choice per hunk, in file order
+    pub hunk_choices: Vec<HunkChoice>,
 }
 
 /// Types of conflicts
@@ -70,16 +77,22 @@ pub enum ResolutionChoice {
     Base,
     /// Manual merge
     Manual,
+    /// Ours/theirs/both chosen hunk by hunk
+    Hunks,
 }
 
 /// Conflict resolution application
 pub struct ConflictResolver {
     /// Configuration
     config: Config,
+    /// Repository working directory
+    workdir: PathBuf,
     /// List of conflicts
     conflicts: Vec<Conflict>,
     /// Currently selected conflict
     selected_conflict: usize,
+    /// Currently selected hunk in the hunk view
+    selected_hunk: usize,
     /// List state
     conflict_list_state: ListState,
     /// Current view mode
@@ -105,56 +118,114 @@ pub enum ViewMode {
     ManualEditor,
     /// Resolution preview
     ResolutionPreview,
+    /// Per-hunk selection
+    HunkView,
 }
 
 impl ConflictResolver {
-    /// Create new conflict resolver
+    /// Create new conflict resolver for the repository in the current directory
     pub fn new(config: Config) -> Result<Self> {
-        let conflicts = Self::detect_conflicts()?;
+        Self::open(config, ".")
+    }
+
+    /// Create new conflict resolver for the repository at `path`
+    pub fn open(config: Config, path: impl AsRef<Path>) -> Result<Self> {
+        let repo = Repository::open(path)?;
+        let workdir = repo
+            .workdir()
+            .ok_or_else(|| MultiGitError::other("Repository has no working directory"))?
+            .to_path_buf();
+        let conflicts = Self::detect_conflicts(&repo, &workdir)?;
+
+        Ok(Self::with_conflicts(config, workdir, conflicts))
+    }
 
-        Ok(Self {
+    fn with_conflicts(config: Config, workdir: PathBuf, conflicts: Vec<Conflict>) -> Self {
+        Self {
             config,
+            workdir,
             conflicts,
             selected_conflict: 0,
+            selected_hunk: 0,
             conflict_list_state: ListState::default(),
             view_mode: ViewMode::ConflictList,
             manual_editor_content: String::new(),
             editor_cursor: 0,
             running: true,
             theme: crate::ui::tui::Theme::default(),
-        })
-    }
-
-    /// Detect conflicts in the repository
-    fn detect_conflicts() -> Result<Vec<Conflict>> {
-        // This would use git to detect actual conflicts
-        // For now, return demo conflicts
-        Ok(vec![
-            Conflict {
-                file: "src/main.rs".to_string(),
-                conflict_type: ConflictType::Content,
-                local_content: "fn main() {\n    println!(\"Local version\");\n}".to_string(),
-                remote_content: "fn main() {\n    println!(\"Remote version\");\n}".to_string(),
-                base_content: Some("fn main() {\n    println!(\"Base version\");\n}".to_string()),
-                resolution: ResolutionChoice::Unresolved,
-                resolved_content: None,
-            },
-            Conflict {
-                file: "README.md".to_string(),
-                conflict_type: ConflictType::Content,
-                local_content: "# My Project\nLocal description".to_string(),
-                remote_content: "# My Project\nRemote description".to_string(),
-                base_content: Some("# My Project\nBase description".to_string()),
+        }
+    }
+
+    /// Detect conflicted files from the index
+    ///
+    /// The three sides come from the index stages; the working-tree copy is
+    /// split at its conflict markers for per-hunk resolution.
+    fn detect_conflicts(repo: &Repository, workdir: &Path) -> Result<Vec<Conflict>> {
+        let index = repo.index()?;
+        let blob_text = |entry: &Option<IndexEntry>| {
+            entry
+                .as_ref()
+                .and_then(|entry| repo.find_blob(entry.id).ok())
+                .map(|blob| String::from_utf8_lossy(blob.content()).into_owned())
+        };
+
+        let mut conflicts = Vec::new();
+        for entry in index.conflicts()? {
+            let entry = entry?;
+            let file = match entry
+                .our
+                .as_ref()
+                .or(entry.their.as_ref())
+                .or(entry.ancestor.as_ref())
+            {
+                Some(side) => String::from_utf8_lossy(&side.path).into_owned(),
+                None => continue,
+            };
+
+            let conflict_type = if entry.our.is_none() || entry.their.is_none() {
+                ConflictType::Deletion
+            } else if entry.ancestor.is_none() {
+                ConflictType::Addition
+            } else {
+                ConflictType::Content
+            };
+
+            let hunks = std::fs::read_to_string(workdir.join(&file))
+                .ok()
+                .and_then(|content| ConflictedFile::parse(&content).ok())
+                .filter(|parsed| parsed.hunk_count() > 0);
+            let hunk_choices =
+                vec![HunkChoice::Unresolved; hunks.as_ref().map_or(0, ConflictedFile::hunk_count)];
+
+            conflicts.push(Conflict {
+                file,
+                conflict_type,
+                local_content: blob_text(&entry.our).unwrap_or_default(),
+                remote_content: blob_text(&entry.their).unwrap_or_default(),
+                base_content: blob_text(&entry.ancestor),
                 resolution: ResolutionChoice::Unresolved,
                 resolved_content: None,
-            },
-        ])
+                hunks,
+                hunk_choices,
+            });
+        }
+
+        debug!("Found {} conflicted file(s)", conflicts.len());
+        Ok(conflicts)
     }
 
     /// Handle key event
     pub fn handle_key(&mut self, key: crossterm::event::KeyEvent) {
+        if self.view_mode == ViewMode::HunkView && self.handle_hunk_key(key.code) {
+            return;
+        }
+
         match key.code {
             KeyCode::Char('q') | KeyCode::Esc => {
+                if self.view_mode == ViewMode::ManualEditor {
+                    // Leaving the editor keeps what was typed
+                    self.set_resolution(ResolutionChoice::Manual);
+                }
                 if self.view_mode != ViewMode::ConflictList {
                     self.view_mode = ViewMode::ConflictList;
                 } else {
@@ -241,6 +312,17 @@ impl ConflictResolver {
                         .get_current_conflict()
                         .map(|c| c.local_content.clone())
                         .unwrap_or_default();
+                    self.editor_cursor = 0;
+                }
+            }
+            KeyCode::Char('5') => {
+                // Choose hunk by hunk
+                let has_hunks = self
+                    .get_current_conflict()
+                    .is_some_and(|c| c.hunks.is_some());
+                if self.view_mode == ViewMode::DiffView && has_hunks {
+                    self.selected_hunk = 0;
+                    self.view_mode = ViewMode::HunkView;
                 }
             }
             KeyCode::Char('m') => {
@@ -249,7 +331,7 @@ impl ConflictResolver {
                     ViewMode::ConflictList => ViewMode::DiffView,
                     ViewMode::DiffView => ViewMode::ResolutionPreview,
                     ViewMode::ResolutionPreview => ViewMode::ConflictList,
-                    ViewMode::ManualEditor => ViewMode::ConflictList,
+                    ViewMode::ManualEditor | ViewMode::HunkView => ViewMode::ConflictList,
                 };
             }
             KeyCode::Backspace | KeyCode::Delete => {
@@ -287,6 +369,12 @@ impl ConflictResolver {
                     // Keep manual editor content
                     conflict.resolved_content = Some(current_content);
                 }
+                ResolutionChoice::Hunks => {
+                    conflict.resolved_content = conflict
+                        .hunks
+                        .as_ref()
+                        .and_then(|hunks| hunks.resolve(&conflict.hunk_choices));
+                }
                 ResolutionChoice::Unresolved => {
                     conflict.resolved_content = None;
                 }
@@ -294,6 +382,72 @@ impl ConflictResolver {
         }
     }
 
+    /// Handle a key in the hunk view; returns whether it was consumed
+    fn handle_hunk_key(&mut self, code: KeyCode) -> bool {
+        let hunk_count = self
+            .get_current_conflict()
+            .map_or(0, |c| c.hunk_choices.len());
+
+        match code {
+            KeyCode::Up => {
+                self.selected_hunk = self.selected_hunk.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                if self.selected_hunk + 1 < hunk_count {
+                    self.selected_hunk += 1;
+                }
+            }
+            KeyCode::Char('o') => self.choose_hunk(HunkChoice::Ours),
+            KeyCode::Char('t') => self.choose_hunk(HunkChoice::Theirs),
+            KeyCode::Char('b') => self.choose_hunk(HunkChoice::Both),
+            KeyCode::Char('e') => {
+                // Fall back to editing by hand, starting from the choices so far
+                self.manual_editor_content = self
+                    .get_current_conflict()
+                    .and_then(|c| {
+                        c.hunks
+                            .as_ref()
+                            .map(|hunks| hunks.render_partial(&c.hunk_choices))
+                    })
+                    .unwrap_or_default();
+                self.editor_cursor = 0;
+                self.view_mode = ViewMode::ManualEditor;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Record `choice` for the selected hunk and move to the next one
+    ///
+    /// Once every hunk has a choice the file counts as resolved.
+    fn choose_hunk(&mut self, choice: HunkChoice) {
+        let selected = self.selected_hunk;
+        let all_chosen = match self.get_current_conflict_mut() {
+            Some(conflict) if selected < conflict.hunk_choices.len() => {
+                conflict.hunk_choices[selected] = choice;
+                conflict
+                    .hunk_choices
+                    .iter()
+                    .all(|c| *c != HunkChoice::Unresolved)
+            }
+            _ => return,
+        };
+
+        if all_chosen {
+            self.set_resolution(ResolutionChoice::Hunks);
+        } else {
+            self.set_resolution(ResolutionChoice::Unresolved);
+        }
+
+        let hunk_count = self
+            .get_current_conflict()
+            .map_or(0, |c| c.hunk_choices.len());
+        if self.selected_hunk + 1 < hunk_count {
+            self.selected_hunk += 1;
+        }
+    }
+
     /// Apply current resolution
     fn apply_current_resolution(&mut self) {
         if let Some(conflict) = self.get_current_conflict() {
@@ -427,6 +581,7 @@ impl ConflictResolver {
             ViewMode::DiffView => self.draw_diff_view(f, chunks[1]),
             ViewMode::ManualEditor => self.draw_manual_editor(f, chunks[1]),
             ViewMode::ResolutionPreview => self.draw_resolution_preview(f, chunks[1]),
+            ViewMode::HunkView => self.draw_hunk_view(f, chunks[1]),
         }
 
         // Draw status bar
@@ -469,10 +624,11 @@ impl ConflictResolver {
             .map(|(i, conflict)| {
                 let status = match conflict.resolution {
                     ResolutionChoice::Unresolved => "🔥",
-                    ResolutionChoice::Local => "✓",
-                    ResolutionChoice::Remote => "✓",
-                    ResolutionChoice::Base => "✓",
+                    ResolutionChoice::Local => Status::Success.icon(),
+                    ResolutionChoice::Remote => Status::Success.icon(),
+                    ResolutionChoice::Base => Status::Success.icon(),
                     ResolutionChoice::Manual => "✏️",
+                    ResolutionChoice::Hunks => Status::Success.icon(),
                 };
 
                 let style = if i == self.selected_conflict {
@@ -531,6 +687,84 @@ impl ConflictResolver {
         );
     }
 
+    /// Draw hunk view: the hunk list on top, both sides of the selected hunk below
+    fn draw_hunk_view(&self, f: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+            .split(area);
+
+        let conflict = match self.get_current_conflict() {
+            Some(conflict) => conflict,
+            None => return,
+        };
+        let hunks: Vec<_> = conflict
+            .hunks
+            .as_ref()
+            .map(|file| file.hunks().collect())
+            .unwrap_or_default();
+
+        let items: Vec<ListItem> = hunks
+            .iter()
+            .enumerate()
+            .map(|(i, hunk)| {
+                let choice = conflict.hunk_choices.get(i).copied().unwrap_or_default();
+                let status = match choice {
+                    HunkChoice::Unresolved => "🔥",
+                    HunkChoice::Ours => "← ours",
+                    HunkChoice::Theirs => "→ theirs",
+                    HunkChoice::Both => "↔ both",
+                };
+                let first_line = hunk.ours.lines().next().unwrap_or("").trim();
+
+                let style = if i == self.selected_hunk {
+                    Style::default()
+                        .fg(self.theme.primary)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(self.theme.foreground)
+                };
+                ListItem::new(format!("Hunk {} {status}  {first_line}", i + 1)).style(style)
+            })
+            .collect();
+
+        let list = List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(self.theme.border))
+                .title(format!(
+                    "{} (↑↓ select, o ours, t theirs, b both, e edit by hand)",
+                    conflict.file
+                )),
+        );
+        f.render_widget(list, chunks[0]);
+
+        let sides = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+        let selected = hunks.get(self.selected_hunk);
+
+        self.draw_version_panel(
+            f,
+            sides[0],
+            &format!(
+                "Ours: {} (o)",
+                selected.map_or("", |hunk| hunk.ours_label.as_str())
+            ),
+            selected.map_or("", |hunk| hunk.ours.as_str()),
+        );
+        self.draw_version_panel(
+            f,
+            sides[1],
+            &format!(
+                "Theirs: {} (t)",
+                selected.map_or("", |hunk| hunk.theirs_label.as_str())
+            ),
+            selected.map_or("", |hunk| hunk.theirs.as_str()),
+        );
+    }
+
     /// Draw version panel
     fn draw_version_panel(&self, f: &mut Frame, area: Rect, title: &str, content: &str) {
         let block = Block::default()
@@ -554,7 +788,7 @@ impl ConflictResolver {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(self.theme.border))
-            .title("Manual Editor (Esc to exit)");
+            .title("Manual Editor (Esc to save and exit)");
 
         let inner = block.inner(area);
         f.render_widget(block, area);
@@ -596,11 +830,17 @@ impl ConflictResolver {
 
     /// Draw status bar
     fn draw_status_bar(&self, f: &mut Frame, area: Rect) {
+        let hunk_hint = if self.view_mode == ViewMode::DiffView {
+            " | '5' per-hunk"
+        } else {
+            ""
+        };
         let status_text = format!(
-            "Unresolved: {} | Resolved: {} | Mode: {:?} | Press 'm' to toggle view | 'q' to quit",
+            "Unresolved: {} | Resolved: {} | Mode: {:?}{} | Press 'm' to toggle view | 'q' to quit",
             self.unresolved_conflicts(),
             self.resolved_conflicts(),
-            self.view_mode
+            self.view_mode,
+            hunk_hint
         );
 
         let status_bar = Paragraph::new(status_text)
@@ -619,23 +859,69 @@ impl ConflictResolver {
     }
 
     /// Apply all resolutions
-    pub fn apply_all_resolutions(&self) -> Result<()> {
+    ///
+    /// Writes each resolved file and stages it, replacing its conflict
+    /// stages in the index so git no longer sees it as conflicted.
+    pub fn apply_all_resolutions(&self) -> Result<AppliedResolutions> {
         info!(
             "Applying {} conflict resolutions",
             self.resolved_conflicts()
         );
 
+        let repo = Repository::open(&self.workdir)?;
+        let mut index = repo.index()?;
+        let mut staged = Vec::new();
+
         for conflict in &self.conflicts {
             if conflict.resolution != ResolutionChoice::Unresolved {
                 if let Some(resolved_content) = &conflict.resolved_content {
-                    // Write resolved content to file
-                    std::fs::write(&conflict.file, resolved_content)?;
+                    // Write resolved content to file and stage it
+                    let path = Path::new(&conflict.file);
+                    std::fs::write(self.workdir.join(path), resolved_content)?;
+                    index.conflict_remove(path)?;
+                    index.add_path(path)?;
                     info!("Resolved conflict in file: {}", conflict.file);
+                    staged.push(conflict.file.clone());
                 }
             }
         }
 
-        Ok(())
+        index.write()?;
+
+        let remaining = index.conflicts()?.count();
+        Ok(AppliedResolutions { staged, remaining })
+    }
+}
+
+/// Outcome of writing resolutions back to the repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedResolutions {
+    /// Files written and staged
+    pub staged: Vec<String>,
+    /// Files still conflicted in the index
+    pub remaining: usize,
+}
+
+impl AppliedResolutions {
+    /// Whether the index is free of conflicts, so the merge can be committed
+    #[must_use]
+    pub fn can_commit(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Print what was staged and what is left to do
+    pub fn print_summary(&self) {
+        for file in &self.staged {
+            println!("  {} {file} (staged)", Status::Success.icon());
+        }
+        if self.can_commit() {
+            println!("All conflicts resolved. Run `git commit` to conclude the merge.");
+        } else {
+            println!(
+                "{} file(s) still conflicted. Run `multigit conflict resolve` again to finish.",
+                self.remaining
+            );
+        }
     }
 }
 
@@ -694,9 +980,6 @@ pub async fn run_conflict_resolver(config: Config) -> Result<()> {
         tokio::time::sleep(Duration::from_millis(16)).await; // ~60 FPS
     }
 
-    // Apply resolutions
-    resolver.apply_all_resolutions()?;
-
     // Cleanup
     disable_raw_mode()?;
     execute!(
@@ -706,6 +989,123 @@ pub async fn run_conflict_resolver(config: Config) -> Result<()> {
     )?;
     terminal.show_cursor()?;
 
+    // Apply resolutions once the terminal is back to normal
+    let applied = resolver.apply_all_resolutions()?;
+    applied.print_summary();
+
     info!("Conflict resolver closed");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::{self, CONTENT_CONFLICT};
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    const MARKED: &str = "start\n\
+<<<<<<< HEAD\nours one\n=======\ntheirs one\n>>>>>>> feature\n\
+middle\n\
+<<<<<<< HEAD\nours two\n=======\ntheirs two\n>>>>>>> feature\n";
+
+    fn resolver() -> ConflictResolver {
+        let hunks = ConflictedFile::parse(MARKED).unwrap();
+        let conflict = Conflict {
+            file: "file.txt".to_string(),
+            conflict_type: ConflictType::Content,
+            local_content: String::new(),
+            remote_content: String::new(),
+            base_content: None,
+            resolution: ResolutionChoice::Unresolved,
+            resolved_content: None,
+            hunk_choices: vec![HunkChoice::Unresolved; hunks.hunk_count()],
+            hunks: Some(hunks),
+        };
+        ConflictResolver::with_conflicts(Config::default(), PathBuf::from("."), vec![conflict])
+    }
+
+    /// Repository whose index has `file.txt` conflicted at stages 1-3
+    fn conflicted_repo() -> (tempfile::TempDir, Repository) {
+        test_support::conflicted_repo(
+            &CONTENT_CONFLICT,
+            &[(
+                "file.txt",
+                "<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> feature\n",
+            )],
+        )
+    }
+
+    fn press(resolver: &mut ConflictResolver, code: KeyCode) {
+        resolver.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn test_hunk_choices_assemble_resolution() {
+        let mut resolver = resolver();
+        press(&mut resolver, KeyCode::Enter);
+        press(&mut resolver, KeyCode::Char('5'));
+        assert_eq!(resolver.view_mode, ViewMode::HunkView);
+
+        press(&mut resolver, KeyCode::Char('t'));
+        assert_eq!(resolver.unresolved_conflicts(), 1);
+        assert_eq!(resolver.selected_hunk, 1);
+
+        press(&mut resolver, KeyCode::Char('b'));
+        let conflict = &resolver.conflicts[0];
+        assert_eq!(conflict.resolution, ResolutionChoice::Hunks);
+        assert_eq!(
+            conflict.resolved_content.as_deref(),
+            Some("start\ntheirs one\nmiddle\nours two\ntheirs two\n")
+        );
+    }
+
+    #[test]
+    fn test_hunk_view_falls_back_to_manual_editor() {
+        let mut resolver = resolver();
+        press(&mut resolver, KeyCode::Enter);
+        press(&mut resolver, KeyCode::Char('5'));
+        press(&mut resolver, KeyCode::Char('o'));
+        press(&mut resolver, KeyCode::Char('e'));
+
+        assert_eq!(resolver.view_mode, ViewMode::ManualEditor);
+        assert!(resolver
+            .manual_editor_content
+            .starts_with("start\nours one\nmiddle\n<<<<<<< HEAD\n"));
+
+        press(&mut resolver, KeyCode::Esc);
+        assert_eq!(resolver.conflicts[0].resolution, ResolutionChoice::Manual);
+    }
+
+    #[test]
+    fn test_apply_resolutions_clears_index_conflicts() {
+        let (dir, repo) = conflicted_repo();
+        let mut resolver = ConflictResolver::open(Config::default(), dir.path()).unwrap();
+        assert_eq!(resolver.conflicts.len(), 1);
+        assert_eq!(resolver.conflicts[0].conflict_type, ConflictType::Content);
+        assert_eq!(resolver.conflicts[0].local_content, "ours\n");
+
+        // Nothing resolved yet: the file stays conflicted
+        let applied = resolver.apply_all_resolutions().unwrap();
+        assert!(applied.staged.is_empty());
+        assert!(!applied.can_commit());
+
+        press(&mut resolver, KeyCode::Enter);
+        press(&mut resolver, KeyCode::Char('5'));
+        press(&mut resolver, KeyCode::Char('t'));
+
+        let applied = resolver.apply_all_resolutions().unwrap();
+        assert_eq!(applied.staged, vec!["file.txt".to_string()]);
+        assert!(applied.can_commit());
+
+        let mut index = repo.index().unwrap();
+        index.read(true).unwrap();
+        assert!(!index.has_conflicts());
+        let entry = index.get_path(Path::new("file.txt"), 0).unwrap();
+        let blob = repo.find_blob(entry.id).unwrap();
+        assert_eq!(blob.content(), b"theirs\n");
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("file.txt")).unwrap(),
+            "theirs\n"
+        );
+    }
+}
@@ -0,0 +1,468 @@
+//! Side-by-side diff rendering
+//!
+//! Lays a git2 diff out in two columns, old on the left and new on the right,
+//! with removed/added lines paired up row by row and light keyword/comment
+//! highlighting on unchanged lines.
+
+use crate::ui::formatter::colors;
+use crate::utils::error::Result;
+use git2::{Diff, DiffFormat};
+use std::path::Path;
+
+/// Column separator
+const SEPARATOR: &str = " │ ";
+/// Width reserved for a line number and the space after it
+const GUTTER: usize = 5;
+/// Narrowest column still worth rendering
+const MIN_COLUMN: usize = 20;
+
+/// How a line changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    /// Present on both sides
+    Context,
+    /// Only on the old side
+    Removed,
+    /// Only on the new side
+    Added,
+}
+
+/// One line on one side of the diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SideLine {
+    /// Line number in its file
+    pub number: u32,
+    /// Line text, without the newline
+    pub text: String,
+    /// How the line changed
+    pub kind: LineKind,
+}
+
+/// One row of the rendered diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffRow {
+    /// Start of a file
+    File(String),
+    /// Hunk header (`@@ -1,3 +1,4 @@ ...`)
+    Hunk(String),
+    /// Old and new line side by side; either may be missing
+    Lines {
+        /// Old side
+        left: Option<SideLine>,
+        /// New side
+        right: Option<SideLine>,
+    },
+}
+
+/// A diff laid out for side-by-side display
+#[derive(Debug, Clone, Default)]
+pub struct SideBySideDiff {
+    rows: Vec<DiffRow>,
+    /// Language of the file each row belongs to, parallel to `rows`
+    languages: Vec<Option<Language>>,
+}
+
+impl SideBySideDiff {
+    /// Lay out a git2 diff
+    ///
+    /// Within each hunk, a run of removed lines followed by added lines is
+    /// paired up in order so edits line up across the columns.
+    pub fn from_diff(diff: &Diff<'_>) -> Result<Self> {
+        let mut layout = Self::default();
+        let mut removed: Vec<SideLine> = Vec::new();
+        let mut added: Vec<SideLine> = Vec::new();
+        let mut language = None;
+
+        diff.print(DiffFormat::Patch, |delta, _hunk, line| {
+            let text = || {
+                String::from_utf8_lossy(line.content())
+                    .trim_end_matches(['\n', '\r'])
+                    .replace('\t', "    ")
+            };
+
+            match line.origin() {
+                '-' => removed.push(SideLine {
+                    number: line.old_lineno().unwrap_or(0),
+                    text: text(),
+                    kind: LineKind::Removed,
+                }),
+                '+' => added.push(SideLine {
+                    number: line.new_lineno().unwrap_or(0),
+                    text: text(),
+                    kind: LineKind::Added,
+                }),
+                origin => {
+                    layout.flush(&mut removed, &mut added, language);
+                    match origin {
+                        'F' => {
+                            let path = delta
+                                .new_file()
+                                .path()
+                                .or_else(|| delta.old_file().path())
+                                .map(|p| p.display().to_string())
+                                .unwrap_or_default();
+                            language = Language::from_path(Path::new(&path));
+                            layout.push(DiffRow::File(path), language);
+                        }
+                        'H' => layout.push(DiffRow::Hunk(text()), language),
+                        ' ' => {
+                            let context = |number: Option<u32>| SideLine {
+                                number: number.unwrap_or(0),
+                                text: text(),
+                                kind: LineKind::Context,
+                            };
+                            layout.push(
+                                DiffRow::Lines {
+                                    left: Some(context(line.old_lineno())),
+                                    right: Some(context(line.new_lineno())),
+                                },
+                                language,
+                            );
+                        }
+                        'B' => {
+                            layout.push(DiffRow::Hunk("Binary files differ".to_string()), language);
+                        }
+                        // "\ No newline at end of file" markers and the like
+                        _ => {}
+                    }
+                }
+            }
+            true
+        })?;
+        layout.flush(&mut removed, &mut added, language);
+
+        Ok(layout)
+    }
+
+    /// Rows in display order
+    #[must_use]
+    pub fn rows(&self) -> &[DiffRow] {
+        &self.rows
+    }
+
+    /// Whether there is nothing to show
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Render to text `width` columns wide
+    #[must_use]
+    pub fn render(&self, width: usize, colors_enabled: bool) -> String {
+        let column = (width.saturating_sub(SEPARATOR.chars().count()) / 2).max(MIN_COLUMN);
+        let mut out = String::new();
+
+        for (row, language) in self.rows.iter().zip(&self.languages) {
+            match row {
+                DiffRow::File(path) => {
+                    if !out.is_empty() {
+                        out.push('\n');
+                    }
+                    out.push_str(&colors::colorize(
+                        &format!("━━ {path}"),
+                        colors::BOLD,
+                        colors_enabled,
+                    ));
+                }
+                DiffRow::Hunk(header) => {
+                    out.push_str(&colors::colorize(header, colors::CYAN, colors_enabled));
+                }
+                DiffRow::Lines { left, right } => {
+                    out.push_str(&render_cell(
+                        left.as_ref(),
+                        column,
+                        *language,
+                        colors_enabled,
+                    ));
+                    out.push_str(SEPARATOR);
+                    let right = render_cell(right.as_ref(), column, *language, colors_enabled);
+                    out.push_str(right.trim_end());
+                }
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+
+    fn push(&mut self, row: DiffRow, language: Option<Language>) {
+        self.rows.push(row);
+        self.languages.push(language);
+    }
+
+    /// Pair up pending removed and added lines
+    fn flush(
+        &mut self,
+        removed: &mut Vec<SideLine>,
+        added: &mut Vec<SideLine>,
+        language: Option<Language>,
+    ) {
+        let rows = removed.len().max(added.len());
+        let mut left = removed.drain(..);
+        let mut right = added.drain(..);
+        for _ in 0..rows {
+            self.rows.push(DiffRow::Lines {
+                left: left.next(),
+                right: right.next(),
+            });
+            self.languages.push(language);
+        }
+    }
+}
+
+/// One column of a row, padded to `column` characters
+fn render_cell(
+    line: Option<&SideLine>,
+    column: usize,
+    language: Option<Language>,
+    colors_enabled: bool,
+) -> String {
+    let line = match line {
+        Some(line) => line,
+        None => return " ".repeat(column),
+    };
+
+    let text_width = column.saturating_sub(GUTTER);
+    let mut text: String = line.text.chars().take(text_width).collect();
+    let padding = " ".repeat(text_width - text.chars().count());
+    let number = format!("{:>4} ", line.number);
+
+    if !colors_enabled {
+        text.push_str(&padding);
+        return format!("{number}{text}");
+    }
+
+    let body = match line.kind {
+        LineKind::Removed => colors::colorize(&text, colors::RED, true),
+        LineKind::Added => colors::colorize(&text, colors::GREEN, true),
+        LineKind::Context => match language {
+            Some(language) => language.highlight(&text),
+            None => text,
+        },
+    };
+    format!(
+        "{}{body}{padding}",
+        colors::colorize(&number, colors::DIM, true)
+    )
+}
+
+/// Languages with keyword and comment highlighting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    /// Rust
+    Rust,
+    /// Python
+    Python,
+    /// JavaScript and TypeScript
+    JavaScript,
+    /// Go
+    Go,
+    /// POSIX shell
+    Shell,
+}
+
+impl Language {
+    /// Language for a file, by extension
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "rs" => Some(Self::Rust),
+            "py" => Some(Self::Python),
+            "js" | "jsx" | "mjs" | "ts" | "tsx" => Some(Self::JavaScript),
+            "go" => Some(Self::Go),
+            "sh" | "bash" => Some(Self::Shell),
+            _ => None,
+        }
+    }
+
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            Self::Rust => &[
+                "as", "async", "await", "const", "crate", "else", "enum", "fn", "for", "if",
+                "impl", "let", "loop", "match", "mod", "mut", "pub", "return", "self", "Self",
+                "static", "struct", "trait", "type", "use", "where", "while",
+            ],
+            Self::Python => &[
+                "and", "as", "async", "await", "class", "def", "elif", "else", "except", "for",
+                "from", "if", "import", "in", "is", "lambda", "not", "or", "pass", "return", "try",
+                "while", "with", "yield",
+            ],
+            Self::JavaScript => &[
+                "async",
+                "await",
+                "class",
+                "const",
+                "else",
+                "export",
+                "for",
+                "function",
+                "if",
+                "import",
+                "interface",
+                "let",
+                "new",
+                "return",
+                "this",
+                "type",
+                "var",
+                "while",
+            ],
+            Self::Go => &[
+                "defer",
+                "else",
+                "for",
+                "func",
+                "go",
+                "if",
+                "import",
+                "interface",
+                "package",
+                "range",
+                "return",
+                "struct",
+                "type",
+                "var",
+            ],
+            Self::Shell => &[
+                "case", "do", "done", "elif", "else", "esac", "fi", "for", "function", "if", "in",
+                "local", "then", "while",
+            ],
+        }
+    }
+
+    fn comment_prefix(self) -> &'static str {
+        match self {
+            Self::Rust | Self::JavaScript | Self::Go => "//",
+            Self::Python | Self::Shell => "#",
+        }
+    }
+
+    /// Color keywords, string literals and trailing comments in one line
+    #[must_use]
+    pub fn highlight(self, line: &str) -> String {
+        let mut out = String::new();
+        let mut word = String::new();
+        let mut in_string = false;
+        let mut escaped = false;
+
+        let flush_word = |word: &mut String, out: &mut String| {
+            if self.keywords().contains(&word.as_str()) {
+                out.push_str(&colors::colorize(word, colors::BLUE, true));
+            } else {
+                out.push_str(word);
+            }
+            word.clear();
+        };
+
+        for (i, c) in line.char_indices() {
+            if in_string {
+                out.push(c);
+                if c == '"' && !escaped {
+                    in_string = false;
+                    out.push_str(colors::RESET);
+                }
+                escaped = c == '\\' && !escaped;
+                continue;
+            }
+
+            if c.is_alphanumeric() || c == '_' {
+                word.push(c);
+                continue;
+            }
+            flush_word(&mut word, &mut out);
+
+            if line[i..].starts_with(self.comment_prefix()) {
+                out.push_str(&colors::colorize(&line[i..], colors::DIM, true));
+                return out;
+            }
+            if c == '"' {
+                in_string = true;
+                out.push_str(colors::YELLOW);
+            }
+            out.push(c);
+        }
+
+        flush_word(&mut word, &mut out);
+        if in_string {
+            out.push_str(colors::RESET);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Repository;
+    use tempfile::TempDir;
+
+    /// Diff of `old` (committed to the index) against `new` (in the workdir)
+    fn layout(old: &str, new: &str) -> SideBySideDiff {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        std::fs::write(dir.path().join("main.rs"), old).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("main.rs")).unwrap();
+        index.write().unwrap();
+        std::fs::write(dir.path().join("main.rs"), new).unwrap();
+
+        let diff = repo.diff_index_to_workdir(None, None).unwrap();
+        SideBySideDiff::from_diff(&diff).unwrap()
+    }
+
+    #[test]
+    fn test_pairs_removed_with_added() {
+        let diff = layout("a\nb\nc\n", "a\nB\nc\nd\n");
+        let lines: Vec<_> = diff
+            .rows()
+            .iter()
+            .filter_map(|row| match row {
+                DiffRow::Lines { left, right } => Some((
+                    left.as_ref().map(|l| l.text.as_str()),
+                    right.as_ref().map(|r| r.text.as_str()),
+                )),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                (Some("a"), Some("a")),
+                (Some("b"), Some("B")),
+                (Some("c"), Some("c")),
+                (None, Some("d")),
+            ]
+        );
+        assert_eq!(diff.rows()[0], DiffRow::File("main.rs".to_string()));
+    }
+
+    #[test]
+    fn test_render_plain_columns() {
+        let diff = layout("one\n", "two\n");
+        let rendered = diff.render(43, false);
+
+        assert!(rendered.contains("━━ main.rs"));
+        assert!(rendered.contains(&format!("   1 {:<15} │    1 two\n", "one")));
+    }
+
+    #[test]
+    fn test_empty_diff() {
+        assert!(layout("same\n", "same\n").is_empty());
+    }
+
+    #[test]
+    fn test_highlight_keywords_strings_and_comments() {
+        let line = Language::Rust.highlight("let s = \"fn\"; // fn");
+
+        assert!(line.starts_with(&format!("{}let{}", colors::BLUE, colors::RESET)));
+        // Keywords inside strings and comments are left alone
+        assert!(line.contains(&format!("{}\"fn\"{}", colors::YELLOW, colors::RESET)));
+        assert!(line.ends_with(&format!("{}// fn{}", colors::DIM, colors::RESET)));
+        assert_eq!(
+            Language::from_path(Path::new("x.py")),
+            Some(Language::Python)
+        );
+        assert_eq!(Language::from_path(Path::new("README")), None);
+    }
+}
@@ -3,7 +3,9 @@
 //! Provides table formatting, colored output, and JSON serialization for CLI output.
 //! Supports both human-readable and machine-readable formats.
 
+use crate::models::IconStyle;
 use serde::Serialize;
+use std::io::IsTerminal;
 
 /// Output format type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,7 +18,29 @@ pub enum OutputFormat {
     Plain,
 }
 
+/// Width assumed when the terminal size can't be determined, e.g. when
+/// output is piped or written to a file
+pub const FALLBACK_WIDTH: usize = 80;
+
+/// Narrowest a column is shrunk to when a table doesn't fit
+const MIN_COLUMN_WIDTH: usize = 4;
+
+/// Width of the terminal stdout is attached to, or [`FALLBACK_WIDTH`]
+#[must_use]
+pub fn terminal_width() -> usize {
+    if !std::io::stdout().is_terminal() {
+        return FALLBACK_WIDTH;
+    }
+    match crossterm::terminal::size() {
+        Ok((columns, _)) if columns > 0 => usize::from(columns),
+        _ => FALLBACK_WIDTH,
+    }
+}
+
 /// Table formatter for aligned column output
+///
+/// Columns are sized to their content; when the table is wider than the
+/// terminal the widest columns are narrowed and their cells cut with `…`.
 pub struct Table {
     headers: Vec<String>,
     rows: Vec<Vec<String>>,
@@ -46,65 +70,138 @@ impl Table {
         self.rows.push(row);
     }
 
-    /// Print the table to stdout
+    /// Print the table to stdout, fitted to the terminal width
     pub fn print(&self) {
-        let mut column_widths = vec![0; self.headers.len()];
-
-        // Calculate column widths
-        for (i, header) in self.headers.iter().enumerate() {
-            column_widths[i] = header.len();
+        for line in self.render(terminal_width()) {
+            println!("{line}");
         }
+    }
+
+    /// Lines of the table, fitted to `width` columns
+    ///
+    /// Cells may contain ANSI colors; they don't count towards the width.
+    #[must_use]
+    pub fn render(&self, width: usize) -> Vec<String> {
+        let widths = self.column_widths(width);
+        let mut lines = Vec::with_capacity(self.rows.len() + 2);
+
+        let header = self.render_row(&self.headers, &widths);
+        lines.push(if self.colors_enabled {
+            format!("  {}", colors::bold(header.trim_start(), true))
+        } else {
+            header
+        });
+
+        let separator: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+        lines.push(format!("  {}", separator.join("  ")));
 
         for row in &self.rows {
-            for (i, cell) in row.iter().enumerate() {
-                if i < column_widths.len() {
-                    column_widths[i] = column_widths[i].max(cell.len());
-                }
-            }
+            lines.push(self.render_row(row, &widths));
         }
+        lines
+    }
 
-        // Print headers
-        print!("  ");
-        for (i, header) in self.headers.iter().enumerate() {
-            if self.colors_enabled {
-                print!("\x1b[1m{:width$}\x1b[0m", header, width = column_widths[i]);
-            } else {
-                print!("{:width$}", header, width = column_widths[i]);
-            }
-            if i < self.headers.len() - 1 {
-                print!("  ");
+    fn render_row(&self, row: &[String], widths: &[usize]) -> String {
+        let last = widths.len().saturating_sub(1);
+        let cells: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(i, width)| {
+                let cell = row.get(i).map_or("", String::as_str);
+                fit_cell(cell, *width, i < last)
+            })
+            .collect();
+        format!("  {}", cells.join("  ")).trim_end().to_string()
+    }
+
+    /// Content widths, narrowed from the widest column down until the table
+    /// fits in `width` or no column can shrink further
+    fn column_widths(&self, width: usize) -> Vec<usize> {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| display_width(h)).collect();
+        for row in &self.rows {
+            for (i, cell) in row.iter().enumerate().take(widths.len()) {
+                widths[i] = widths[i].max(display_width(cell));
             }
         }
-        println!();
-
-        // Print separator
-        print!("  ");
-        for (i, width) in column_widths.iter().enumerate() {
-            print!("{}", "-".repeat(*width));
-            if i < column_widths.len() - 1 {
-                print!("  ");
+
+        // Two spaces of indent, two between columns
+        let gaps = 2 + 2 * widths.len().saturating_sub(1);
+        let available = width.saturating_sub(gaps);
+        while widths.iter().sum::<usize>() > available {
+            let widest = widths
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, width)| **width)
+                .map(|(i, _)| i);
+            match widest {
+                Some(i) if widths[i] > MIN_COLUMN_WIDTH => widths[i] -= 1,
+                _ => break,
             }
         }
-        println!();
+        widths
+    }
+}
 
-        // Print rows
-        for row in &self.rows {
-            print!("  ");
-            for (i, cell) in row.iter().enumerate() {
-                if i < column_widths.len() {
-                    print!("{:width$}", cell, width = column_widths[i]);
-                    if i < row.len() - 1 {
-                        print!("  ");
-                    }
+/// Characters `text` takes up on screen, ignoring ANSI escape sequences
+#[must_use]
+pub fn display_width(text: &str) -> usize {
+    strip_ansi(text).chars().count()
+}
+
+fn strip_ansi(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip up to and including the final letter of the sequence
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
                 }
             }
-            println!();
+        } else {
+            plain.push(c);
         }
     }
+    plain
+}
+
+/// `cell` cut to `width` with a trailing `…` if too long, padded if `pad`
+fn fit_cell(cell: &str, width: usize, pad: bool) -> String {
+    let len = display_width(cell);
+    if len > width {
+        let mut cut: String = strip_ansi(cell)
+            .chars()
+            .take(width.saturating_sub(1))
+            .collect();
+        cut.push('…');
+        return cut;
+    }
+    if pad {
+        format!("{cell}{}", " ".repeat(width - len))
+    } else {
+        cell.to_string()
+    }
 }
 
 /// Colored text output
 pub mod colors {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    /// Cleared by `--no-color`, `NO_COLOR` or `settings.colored_output = false`
+    static ENABLED: AtomicBool = AtomicBool::new(true);
+
+    /// Turn colored output on or off for the rest of the process
+    pub fn set_enabled(enabled: bool) {
+        ENABLED.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Whether colored output is allowed in this process
+    #[must_use]
+    pub fn enabled() -> bool {
+        ENABLED.load(Ordering::Relaxed)
+    }
+
     /// Color codes
     pub const RESET: &str = "\x1b[0m";
     /// Bold text style
@@ -263,6 +360,57 @@ pub fn print_section(text: &str, color_enabled: bool) {
     }
 }
 
+/// Which glyphs status icons are drawn with
+pub mod icons {
+    use crate::models::IconStyle;
+    use std::sync::RwLock;
+
+    /// Set from `--ascii`, `settings.icons` or [`detect`]
+    static STYLE: RwLock<IconStyle> = RwLock::new(IconStyle::Emoji);
+
+    /// Draw status icons in `style` for the rest of the process
+    pub fn set_style(style: IconStyle) {
+        if let Ok(mut current) = STYLE.write() {
+            *current = style;
+        }
+    }
+
+    /// The style status icons are drawn in
+    #[must_use]
+    pub fn style() -> IconStyle {
+        STYLE.read().map_or(IconStyle::Emoji, |style| *style)
+    }
+
+    /// ASCII when `TERM` is dumb or the locale isn't UTF-8, emoji otherwise
+    #[must_use]
+    pub fn detect() -> IconStyle {
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty());
+        detect_from(std::env::var("TERM").ok().as_deref(), locale.as_deref())
+    }
+
+    /// [`detect`] for the given `TERM` and locale
+    ///
+    /// Windows consoles don't set a locale, so only `TERM` counts there.
+    #[must_use]
+    pub fn detect_from(term: Option<&str>, locale: Option<&str>) -> IconStyle {
+        if term == Some("dumb") {
+            return IconStyle::Ascii;
+        }
+        let utf8 = locale.map_or(cfg!(windows), |locale| {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        });
+        if utf8 {
+            IconStyle::Emoji
+        } else {
+            IconStyle::Ascii
+        }
+    }
+}
+
 /// Status indicator
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Status {
@@ -276,18 +424,48 @@ pub enum Status {
     Info,
     /// Pending status
     Pending,
+    /// Skipped, e.g. an archived or interrupted remote
+    Skipped,
 }
 
 impl Status {
-    /// Get the symbol for this status
+    /// Icon for this status
+    ///
+    /// The one place status icons are defined, so every command and the TUI
+    /// show the same ones, in the [`icons::style`] of this process.
     #[must_use]
-    pub fn symbol(&self) -> &'static str {
-        match self {
-            Status::Success => "✓",
-            Status::Error => "✗",
-            Status::Warning => "⚠",
-            Status::Info => "ℹ",
-            Status::Pending => "●",
+    pub fn icon(&self) -> &'static str {
+        self.icon_in(icons::style())
+    }
+
+    /// Icon for this status in `style`
+    #[must_use]
+    pub fn icon_in(&self, style: IconStyle) -> &'static str {
+        match style {
+            IconStyle::Emoji => match self {
+                Status::Success => "✓",
+                Status::Error => "✗",
+                Status::Warning => "⚠",
+                Status::Info => "ℹ",
+                Status::Pending => "●",
+                Status::Skipped => "⊘",
+            },
+            IconStyle::Ascii => match self {
+                Status::Success => "[ok]",
+                Status::Error => "[x]",
+                Status::Warning => "[!]",
+                Status::Info => "[i]",
+                Status::Pending => "[*]",
+                Status::Skipped => "[-]",
+            },
+            IconStyle::NerdFont => match self {
+                Status::Success => "\u{f00c}",
+                Status::Error => "\u{f00d}",
+                Status::Warning => "\u{f071}",
+                Status::Info => "\u{f05a}",
+                Status::Pending => "\u{f111}",
+                Status::Skipped => "\u{f05e}",
+            },
         }
     }
 
@@ -300,17 +478,40 @@ impl Status {
             Status::Warning => colors::YELLOW,
             Status::Info => colors::CYAN,
             Status::Pending => colors::BLUE,
+            Status::Skipped => colors::DIM,
         }
     }
 
+    /// Get the symbol for this status; same as [`Self::icon`]
+    #[must_use]
+    pub fn symbol(&self) -> &'static str {
+        self.icon()
+    }
+
+    /// Icon in this status's color, unless colors are turned off
+    #[must_use]
+    pub fn colored_icon(&self) -> String {
+        colors::colorize(self.icon(), self.color(), colors::enabled())
+    }
+
     /// Format a status line
     #[must_use]
     pub fn format(&self, message: &str, color_enabled: bool) -> String {
-        let symbol = self.symbol();
+        let icon = self.icon();
         if color_enabled {
-            format!("  {}{}{} {}", self.color(), symbol, colors::RESET, message)
+            format!("  {}{}{} {}", self.color(), icon, colors::RESET, message)
+        } else {
+            format!("  {icon} {message}")
+        }
+    }
+
+    /// `Success` for an enabled item, `Error` for a disabled one
+    #[must_use]
+    pub fn of_enabled(enabled: bool) -> Self {
+        if enabled {
+            Self::Success
         } else {
-            format!("  {symbol} {message}")
+            Self::Error
         }
     }
 }
@@ -333,6 +534,30 @@ mod tests {
         table.print();
     }
 
+    #[test]
+    fn test_table_fits_width() {
+        let mut table = Table::new(vec!["Name".to_string(), "URL".to_string()]).no_colors();
+        table.add_row(vec![
+            "github".to_string(),
+            "https://github.com/some-organisation/some-long-repository-name".to_string(),
+        ]);
+
+        let wide = table.render(120);
+        assert_eq!(wide[0], "  Name    URL");
+        assert!(wide[2].ends_with("some-long-repository-name"));
+
+        let narrow = table.render(40);
+        assert!(narrow.iter().all(|line| display_width(line) <= 40));
+        assert!(narrow[2].starts_with("  github  https://"));
+        assert!(narrow[2].ends_with('…'));
+    }
+
+    #[test]
+    fn test_display_width_ignores_colors() {
+        assert_eq!(display_width(&colors::success("✓ ok", true)), 4);
+        assert_eq!(fit_cell(&colors::error("failed", true), 4, true), "fai…");
+    }
+
     #[test]
     fn test_format_duration() {
         assert_eq!(format_duration(45), "45s");
@@ -358,8 +583,25 @@ mod tests {
     #[test]
     fn test_status() {
         let status = Status::Success;
-        assert_eq!(status.symbol(), "✓");
+        assert_eq!(status.icon(), "✓");
         let formatted = status.format("Test message", false);
         assert!(formatted.contains("Test message"));
+
+        assert_eq!(Status::of_enabled(false), Status::Error);
+        assert!(Status::Warning.colored_icon().contains("⚠"));
+    }
+
+    #[test]
+    fn test_icon_styles() {
+        assert_eq!(Status::Success.icon_in(IconStyle::Ascii), "[ok]");
+        assert_eq!(Status::Warning.icon_in(IconStyle::Ascii), "[!]");
+        assert_eq!(Status::Error.icon_in(IconStyle::Ascii), "[x]");
+        assert_eq!(Status::Skipped.icon_in(IconStyle::Emoji), "⊘");
+
+        let detect = icons::detect_from;
+        assert_eq!(detect(Some("xterm"), Some("en_US.UTF-8")), IconStyle::Emoji);
+        assert_eq!(detect(Some("xterm"), Some("C.utf8")), IconStyle::Emoji);
+        assert_eq!(detect(Some("dumb"), Some("en_US.UTF-8")), IconStyle::Ascii);
+        assert_eq!(detect(Some("xterm"), Some("C")), IconStyle::Ascii);
     }
 }
@@ -3,14 +3,16 @@
 //! Provides progress bars, output formatting, and terminal UI components.
 
 pub mod conflict_resolver;
+pub mod diff_view;
 pub mod formatter;
 pub mod progress;
+pub mod settings_editor;
 pub mod sync_monitor;
 pub mod tui;
 
 // Re-export commonly used items
 pub use conflict_resolver::ConflictResolver;
 pub use formatter::{colors, OutputFormat, Status, Table};
-pub use progress::{MultiRemoteProgress, ProgressCounter, Spinner};
+pub use progress::{MultiRemoteProgress, ProgressCounter, Spinner, TransferProgress};
 pub use sync_monitor::SyncMonitor;
 pub use tui::{start_dashboard, App, Theme};
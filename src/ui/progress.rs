@@ -3,14 +3,197 @@
 //! Provides multi-progress bar support for parallel operations using indicatif.
 //! Displays real-time progress for push/pull/sync operations across multiple remotes.
 
-use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
-use std::sync::Arc;
-use std::time::Duration;
+use crate::ui::formatter::Status;
+use indicatif::{HumanBytes, MultiProgress, ProgressBar, ProgressState, ProgressStyle};
+use std::io::IsTerminal;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Weight of the newest sample in the smoothed rate
+const RATE_SMOOTHING: f64 = 0.3;
+
+/// Samples closer together than this are merged into the next one
+const MIN_SAMPLE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Exponentially smoothed rate of a growing counter, and the ETA it implies
+#[derive(Debug, Clone, Default)]
+pub struct RateEstimator {
+    last: Option<(u64, Instant)>,
+    rate: Option<f64>,
+}
+
+impl RateEstimator {
+    /// Record that the counter reached `pos` at `now`
+    ///
+    /// A counter that goes backwards starts a fresh estimate.
+    pub fn observe(&mut self, pos: u64, now: Instant) {
+        let (last_pos, last_at) = match self.last {
+            Some(last) if pos >= last.0 => last,
+            _ => {
+                self.reset();
+                self.last = Some((pos, now));
+                return;
+            }
+        };
+
+        let elapsed = now.saturating_duration_since(last_at);
+        if elapsed < MIN_SAMPLE_INTERVAL {
+            return;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let sample = (pos - last_pos) as f64 / elapsed.as_secs_f64();
+        self.rate = Some(match self.rate {
+            Some(rate) => rate + RATE_SMOOTHING * (sample - rate),
+            None => sample,
+        });
+        self.last = Some((pos, now));
+    }
+
+    /// Smoothed rate in units per second, once there are two samples
+    #[must_use]
+    pub fn rate(&self) -> Option<f64> {
+        self.rate
+    }
+
+    /// Time to cover `remaining` units at the smoothed rate
+    #[must_use]
+    pub fn eta(&self, remaining: f64) -> Option<Duration> {
+        match self.rate {
+            Some(rate) if rate > 0.0 => Some(Duration::from_secs_f64(remaining.max(0.0) / rate)),
+            _ => None,
+        }
+    }
+
+    /// Forget all samples
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.rate = None;
+    }
+}
+
+/// Transfer counters as reported by libgit2 during fetch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransferProgress {
+    /// Objects downloaded so far
+    pub received_objects: u64,
+    /// Objects in the pack
+    pub total_objects: u64,
+    /// Bytes downloaded so far
+    pub received_bytes: u64,
+    /// Deltas resolved so far
+    pub indexed_deltas: u64,
+    /// Deltas in the pack
+    pub total_deltas: u64,
+}
+
+impl From<git2::Progress<'_>> for TransferProgress {
+    fn from(progress: git2::Progress<'_>) -> Self {
+        Self {
+            received_objects: progress.received_objects() as u64,
+            total_objects: progress.total_objects() as u64,
+            received_bytes: progress.received_bytes() as u64,
+            indexed_deltas: progress.indexed_deltas() as u64,
+            total_deltas: progress.total_deltas() as u64,
+        }
+    }
+}
+
+impl TransferProgress {
+    /// Which phase of the transfer these counters describe
+    #[must_use]
+    pub fn phase(&self) -> TransferPhase {
+        if self.total_deltas > 0 && self.received_objects >= self.total_objects {
+            TransferPhase::ResolvingDeltas
+        } else {
+            TransferPhase::Receiving
+        }
+    }
+
+    /// Projected bytes still to download, assuming remaining objects have
+    /// the average size of those received so far
+    #[must_use]
+    pub fn estimated_remaining_bytes(&self) -> Option<f64> {
+        if self.received_objects == 0 {
+            return None;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let per_object = self.received_bytes as f64 / self.received_objects as f64;
+        #[allow(clippy::cast_precision_loss)]
+        let remaining = self.total_objects.saturating_sub(self.received_objects) as f64;
+        Some(per_object * remaining)
+    }
+}
+
+/// What a remote's progress bar is currently counting
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransferPhase {
+    /// Generic units set through [`MultiRemoteProgress::update`]
+    #[default]
+    Units,
+    /// Downloading objects; the rate is measured in bytes
+    Receiving,
+    /// Resolving deltas locally; only counts move, no bytes
+    ResolvingDeltas,
+}
+
+/// Rate and ETA shown in a remote's bar
+#[derive(Debug, Default)]
+struct EtaState {
+    phase: TransferPhase,
+    estimator: RateEstimator,
+    eta: Option<Duration>,
+}
+
+impl EtaState {
+    /// Feed a sample; switching phase restarts the estimate so the ETA of
+    /// one phase doesn't bleed into the next
+    fn record(&mut self, phase: TransferPhase, pos: u64, remaining: Option<f64>, now: Instant) {
+        if phase != self.phase {
+            self.phase = phase;
+            self.estimator.reset();
+        }
+        self.estimator.observe(pos, now);
+        self.eta = remaining.and_then(|remaining| self.estimator.eta(remaining));
+    }
+
+    fn render(&self, state: &ProgressState, w: &mut dyn std::fmt::Write) {
+        if state.is_finished() {
+            return;
+        }
+
+        let eta = match self.eta {
+            Some(eta) => format!("ETA {}", format_eta(eta)),
+            None => "estimating...".to_string(),
+        };
+        let _ = match (self.phase, self.estimator.rate()) {
+            (TransferPhase::Receiving, Some(rate)) => {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let rate = HumanBytes(rate as u64);
+                write!(w, "{rate}/s, {eta}")
+            }
+            (TransferPhase::ResolvingDeltas, _) => write!(w, "resolving deltas, {eta}"),
+            _ => write!(w, "{eta}"),
+        };
+    }
+}
+
+/// Format an ETA as `m:ss`, or `h:mm:ss` past an hour
+#[must_use]
+pub fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
 
 /// Progress tracker for multi-remote operations
 pub struct MultiRemoteProgress {
     multi: Arc<MultiProgress>,
     bars: Vec<ProgressBar>,
+    etas: Vec<Arc<Mutex<EtaState>>>,
 }
 
 impl MultiRemoteProgress {
@@ -19,32 +202,96 @@ impl MultiRemoteProgress {
     pub fn new(remote_names: &[String]) -> Self {
         let multi = Arc::new(MultiProgress::new());
         let mut bars = Vec::new();
+        let mut etas = Vec::new();
 
         for remote_name in remote_names {
+            let eta = Arc::new(Mutex::new(EtaState::default()));
+            let render_eta = eta.clone();
+
             let pb = multi.add(ProgressBar::new(100));
             pb.set_style(
                 ProgressStyle::default_bar()
-                    .template("{prefix:>12} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
+                    .template("{prefix:>12} [{bar:40.cyan/blue}] {pos}/{len} {msg} {eta_info}")
                     .expect("Progress bar template should be valid")
+                    .with_key(
+                        "eta_info",
+                        move |state: &ProgressState, w: &mut dyn std::fmt::Write| {
+                            if let Ok(eta) = render_eta.lock() {
+                                eta.render(state, w);
+                            }
+                        },
+                    )
                     .progress_chars("█▓▒░ "),
             );
             pb.set_prefix(remote_name.clone());
             pb.set_message("Initializing...");
             bars.push(pb);
+            etas.push(eta);
         }
 
-        Self { multi, bars }
+        Self { multi, bars, etas }
     }
 
     /// Update progress for a specific remote
     pub fn update(&self, index: usize, pos: u64, total: u64, message: &str) {
         if let Some(pb) = self.bars.get(index) {
+            #[allow(clippy::cast_precision_loss)]
+            let remaining = total.saturating_sub(pos) as f64;
+            self.record(index, TransferPhase::Units, pos, Some(remaining));
+
             pb.set_length(total);
             pb.set_position(pos);
             pb.set_message(message.to_string());
         }
     }
 
+    /// Update a remote's bar from fetch transfer counters
+    ///
+    /// While objects download, the bar counts objects and the ETA follows
+    /// the byte rate. Once all objects are in, it counts resolved deltas
+    /// with a separate estimate, since no bytes move in that phase.
+    pub fn update_transfer(&self, index: usize, progress: TransferProgress) {
+        let pb = match self.bars.get(index) {
+            Some(pb) => pb,
+            None => return,
+        };
+
+        if progress.phase() == TransferPhase::ResolvingDeltas {
+            #[allow(clippy::cast_precision_loss)]
+            let remaining = progress
+                .total_deltas
+                .saturating_sub(progress.indexed_deltas) as f64;
+            self.record(
+                index,
+                TransferPhase::ResolvingDeltas,
+                progress.indexed_deltas,
+                Some(remaining),
+            );
+            pb.set_length(progress.total_deltas);
+            pb.set_position(progress.indexed_deltas);
+            pb.set_message("Resolving deltas");
+        } else {
+            self.record(
+                index,
+                TransferPhase::Receiving,
+                progress.received_bytes,
+                progress.estimated_remaining_bytes(),
+            );
+            pb.set_length(progress.total_objects);
+            pb.set_position(progress.received_objects);
+            pb.set_message(format!(
+                "Receiving objects ({})",
+                HumanBytes(progress.received_bytes)
+            ));
+        }
+    }
+
+    fn record(&self, index: usize, phase: TransferPhase, pos: u64, remaining: Option<f64>) {
+        if let Some(Ok(mut eta)) = self.etas.get(index).map(|eta| eta.lock()) {
+            eta.record(phase, pos, remaining, Instant::now());
+        }
+    }
+
     /// Mark a remote as complete
     pub fn finish(&self, index: usize, message: &str) {
         if let Some(pb) = self.bars.get(index) {
@@ -88,6 +335,15 @@ impl Spinner {
         Self { pb }
     }
 
+    /// A spinner when stderr, where it is drawn, is a terminal
+    ///
+    /// Redirected output gets no spinner, so logs and pipes stay free of
+    /// redraw sequences.
+    #[must_use]
+    pub fn for_terminal(message: &str) -> Option<Self> {
+        std::io::stderr().is_terminal().then(|| Self::new(message))
+    }
+
     /// Update the spinner message
     pub fn set_message(&self, message: &str) {
         self.pb.set_message(message.to_string());
@@ -174,22 +430,22 @@ pub fn show_message(message: &str) {
 
 /// Show a success message
 pub fn show_success(message: &str) {
-    println!("  ✅ {message}");
+    println!("  {} {message}", Status::Success.icon());
 }
 
 /// Show an error message
 pub fn show_error(message: &str) {
-    eprintln!("  ❌ {message}");
+    eprintln!("  {} {message}", Status::Error.icon());
 }
 
 /// Show a warning message
 pub fn show_warning(message: &str) {
-    println!("  ⚠️  {message}");
+    println!("  {} {message}", Status::Warning.icon());
 }
 
 /// Show an info message
 pub fn show_info(message: &str) {
-    println!("  ℹ️  {message}");
+    println!("  {} {message}", Status::Info.icon());
 }
 
 #[cfg(test)]
@@ -203,6 +459,109 @@ mod tests {
         assert_eq!(progress.bars.len(), 2);
     }
 
+    #[test]
+    fn test_rate_estimator_smooths_samples() {
+        let start = Instant::now();
+        let mut estimator = RateEstimator::default();
+
+        estimator.observe(0, start);
+        assert_eq!(estimator.rate(), None);
+
+        estimator.observe(1000, start + Duration::from_secs(1));
+        assert!((estimator.rate().unwrap() - 1000.0).abs() < 1e-6);
+
+        // A burst moves the smoothed rate only part of the way
+        estimator.observe(4000, start + Duration::from_secs(2));
+        assert!((estimator.rate().unwrap() - 1600.0).abs() < 1e-6);
+
+        let eta = estimator.eta(3200.0).unwrap();
+        assert_eq!(eta, Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_rate_estimator_ignores_close_samples_and_resets() {
+        let start = Instant::now();
+        let mut estimator = RateEstimator::default();
+
+        estimator.observe(0, start);
+        estimator.observe(500, start + Duration::from_millis(50));
+        assert_eq!(estimator.rate(), None);
+
+        estimator.observe(1000, start + Duration::from_secs(1));
+        assert!(estimator.rate().is_some());
+
+        // Going backwards means a new counter
+        estimator.observe(10, start + Duration::from_secs(2));
+        assert_eq!(estimator.rate(), None);
+        assert_eq!(estimator.eta(100.0), None);
+    }
+
+    #[test]
+    fn test_transfer_phases() {
+        let mut progress = TransferProgress {
+            received_objects: 50,
+            total_objects: 200,
+            received_bytes: 5000,
+            indexed_deltas: 0,
+            total_deltas: 0,
+        };
+        assert_eq!(progress.phase(), TransferPhase::Receiving);
+        assert_eq!(progress.estimated_remaining_bytes(), Some(15000.0));
+
+        progress.received_objects = 200;
+        progress.total_deltas = 80;
+        assert_eq!(progress.phase(), TransferPhase::ResolvingDeltas);
+    }
+
+    #[test]
+    fn test_phase_change_restarts_estimate() {
+        let start = Instant::now();
+        let mut eta = EtaState::default();
+
+        eta.record(TransferPhase::Receiving, 0, Some(1000.0), start);
+        eta.record(
+            TransferPhase::Receiving,
+            1000,
+            Some(1000.0),
+            start + Duration::from_secs(1),
+        );
+        assert_eq!(eta.eta, Some(Duration::from_secs(1)));
+
+        eta.record(
+            TransferPhase::ResolvingDeltas,
+            0,
+            Some(80.0),
+            start + Duration::from_secs(2),
+        );
+        assert_eq!(eta.eta, None);
+    }
+
+    #[test]
+    fn test_format_eta() {
+        assert_eq!(format_eta(Duration::from_secs(5)), "0:05");
+        assert_eq!(format_eta(Duration::from_secs(125)), "2:05");
+        assert_eq!(format_eta(Duration::from_secs(3725)), "1:02:05");
+    }
+
+    #[test]
+    fn test_multi_progress_transfer_updates() {
+        let progress = MultiRemoteProgress::new(&["github".to_string()]);
+        progress.update_transfer(
+            0,
+            TransferProgress {
+                received_objects: 10,
+                total_objects: 100,
+                received_bytes: 2048,
+                ..TransferProgress::default()
+            },
+        );
+        assert_eq!(progress.bars[0].position(), 10);
+        assert_eq!(progress.bars[0].length(), Some(100));
+
+        // Out-of-range indexes are ignored
+        progress.update_transfer(5, TransferProgress::default());
+    }
+
     #[test]
     fn test_spinner_creation() {
         let spinner = Spinner::new("Testing...");
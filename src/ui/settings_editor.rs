@@ -0,0 +1,392 @@
+//! Settings editor for the TUI Settings tab
+//!
+//! Edits a draft of `[settings]` and `[sync]`; nothing reaches the
+//! configuration until the draft passes [`SettingsEditor::validate`] and the
+//! TUI saves it.
+
+use crate::core::config::Config;
+use crate::models::config::{Settings, SyncConfig, SyncStrategy};
+use crate::utils::error::{MultiGitError, Result};
+use crossterm::event::KeyCode;
+
+/// Strategies offered by the strategy field, in display order
+const STRATEGIES: &[SyncStrategy] = &[
+    SyncStrategy::FastForward,
+    SyncStrategy::Merge,
+    SyncStrategy::Rebase,
+    SyncStrategy::Force,
+];
+
+/// An editable setting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingField {
+    /// `settings.max_parallel`
+    MaxParallel,
+    /// `settings.api_timeout_seconds`
+    ApiTimeout,
+    /// `settings.token_expiry_warning_days`
+    TokenExpiryWarning,
+    /// `settings.parallel_push`
+    ParallelPush,
+    /// `settings.colored_output`
+    ColoredOutput,
+    /// `settings.default_private`
+    DefaultPrivate,
+    /// `sync.auto_sync`
+    AutoSync,
+    /// `sync.strategy`
+    Strategy,
+    /// `sync.detect_conflicts`
+    DetectConflicts,
+    /// `sync.auto_resolve`
+    AutoResolve,
+}
+
+impl SettingField {
+    /// Every field, in display order
+    #[must_use]
+    pub fn all() -> &'static [SettingField] {
+        &[
+            Self::MaxParallel,
+            Self::ApiTimeout,
+            Self::TokenExpiryWarning,
+            Self::ParallelPush,
+            Self::ColoredOutput,
+            Self::DefaultPrivate,
+            Self::AutoSync,
+            Self::Strategy,
+            Self::DetectConflicts,
+            Self::AutoResolve,
+        ]
+    }
+
+    /// Label shown in the editor
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MaxParallel => "Max parallel",
+            Self::ApiTimeout => "API timeout (s)",
+            Self::TokenExpiryWarning => "Token expiry warning (days)",
+            Self::ParallelPush => "Parallel push",
+            Self::ColoredOutput => "Colored output",
+            Self::DefaultPrivate => "Create private repos",
+            Self::AutoSync => "Auto-sync",
+            Self::Strategy => "Sync strategy",
+            Self::DetectConflicts => "Detect conflicts",
+            Self::AutoResolve => "Auto-resolve conflicts",
+        }
+    }
+
+    /// Allowed range of a number field
+    #[must_use]
+    pub fn range(self) -> Option<(u64, u64)> {
+        match self {
+            Self::MaxParallel => Some((1, 64)),
+            Self::ApiTimeout => Some((1, 600)),
+            Self::TokenExpiryWarning => Some((0, 365)),
+            _ => None,
+        }
+    }
+}
+
+/// Draft of the editable settings and the state of the field being typed in
+#[derive(Debug, Clone)]
+pub struct SettingsEditor {
+    /// Draft of `[settings]`
+    pub settings: Settings,
+    /// Draft of `[sync]`
+    pub sync: SyncConfig,
+    /// Index of the selected field in [`SettingField::all`]
+    pub selected: usize,
+    /// Text typed into a number field, while editing it
+    pub input: Option<String>,
+    /// Last validation error
+    pub error: Option<String>,
+    /// Whether the draft differs from what was last saved
+    pub dirty: bool,
+}
+
+impl SettingsEditor {
+    /// Start editing the settings of `config`
+    #[must_use]
+    pub fn new(config: &Config) -> Self {
+        Self {
+            settings: config.settings.clone(),
+            sync: config.sync.clone(),
+            selected: 0,
+            input: None,
+            error: None,
+            dirty: false,
+        }
+    }
+
+    /// The selected field
+    #[must_use]
+    pub fn field(&self) -> SettingField {
+        SettingField::all()[self.selected]
+    }
+
+    /// Current value of `field` in the draft, as displayed
+    #[must_use]
+    pub fn value(&self, field: SettingField) -> String {
+        let toggle = |on: bool| if on { "on" } else { "off" }.to_string();
+        match field {
+            SettingField::MaxParallel => self.settings.max_parallel.to_string(),
+            SettingField::ApiTimeout => self.settings.api_timeout_seconds.to_string(),
+            SettingField::TokenExpiryWarning => self.settings.token_expiry_warning_days.to_string(),
+            SettingField::ParallelPush => toggle(self.settings.parallel_push),
+            SettingField::ColoredOutput => toggle(self.settings.colored_output),
+            SettingField::DefaultPrivate => toggle(self.settings.default_private),
+            SettingField::AutoSync => toggle(self.sync.auto_sync),
+            SettingField::Strategy => self.sync.strategy.to_string(),
+            SettingField::DetectConflicts => toggle(self.sync.detect_conflicts),
+            SettingField::AutoResolve => toggle(self.sync.auto_resolve),
+        }
+    }
+
+    /// Handle a key in the Settings tab, returning whether it was used
+    ///
+    /// While a number is being typed every key goes to the input.
+    pub fn handle_key(&mut self, code: KeyCode) -> bool {
+        if self.input.is_some() {
+            self.handle_input_key(code);
+            return true;
+        }
+
+        let count = SettingField::all().len();
+        match code {
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => self.selected = (self.selected + 1).min(count - 1),
+            KeyCode::Left => self.step(-1),
+            KeyCode::Right => self.step(1),
+            KeyCode::Enter => {
+                if self.field().range().is_some() {
+                    self.input = Some(self.value(self.field()));
+                    self.error = None;
+                } else {
+                    self.step(1);
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn handle_input_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                if let Some(input) = &mut self.input {
+                    input.push(c);
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(input) = &mut self.input {
+                    input.pop();
+                }
+            }
+            KeyCode::Esc => {
+                self.input = None;
+                self.error = None;
+            }
+            KeyCode::Enter => {
+                let input = self.input.clone().unwrap_or_default();
+                match self.parse_number(self.field(), &input) {
+                    Ok(value) => {
+                        self.set_number(self.field(), value);
+                        self.input = None;
+                        self.error = None;
+                    }
+                    Err(e) => self.error = Some(e.to_string()),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Move the selected field one step: numbers by one, toggles flip, the
+    /// strategy cycles through its options
+    fn step(&mut self, delta: i64) {
+        let field = self.field();
+        if let Some((min, max)) = field.range() {
+            let current = self.number(field);
+            let next = if delta < 0 {
+                current.saturating_sub(1).max(min)
+            } else {
+                current.saturating_add(1).min(max)
+            };
+            self.set_number(field, next);
+            return;
+        }
+
+        match field {
+            SettingField::ParallelPush => flip(&mut self.settings.parallel_push),
+            SettingField::ColoredOutput => flip(&mut self.settings.colored_output),
+            SettingField::DefaultPrivate => flip(&mut self.settings.default_private),
+            SettingField::AutoSync => flip(&mut self.sync.auto_sync),
+            SettingField::DetectConflicts => flip(&mut self.sync.detect_conflicts),
+            SettingField::AutoResolve => flip(&mut self.sync.auto_resolve),
+            SettingField::Strategy => {
+                let current = STRATEGIES
+                    .iter()
+                    .position(|s| *s == self.sync.strategy)
+                    .unwrap_or(0);
+                let next = if delta < 0 {
+                    current.checked_sub(1).unwrap_or(STRATEGIES.len() - 1)
+                } else {
+                    (current + 1) % STRATEGIES.len()
+                };
+                self.sync.strategy = STRATEGIES[next];
+            }
+            _ => return,
+        }
+        self.dirty = true;
+        self.error = None;
+    }
+
+    fn number(&self, field: SettingField) -> u64 {
+        match field {
+            SettingField::MaxParallel => u64::try_from(self.settings.max_parallel).unwrap_or(0),
+            SettingField::ApiTimeout => self.settings.api_timeout_seconds,
+            SettingField::TokenExpiryWarning => u64::from(self.settings.token_expiry_warning_days),
+            _ => 0,
+        }
+    }
+
+    fn set_number(&mut self, field: SettingField, value: u64) {
+        match field {
+            SettingField::MaxParallel => {
+                self.settings.max_parallel = usize::try_from(value).unwrap_or(usize::MAX);
+            }
+            SettingField::ApiTimeout => self.settings.api_timeout_seconds = value,
+            SettingField::TokenExpiryWarning => {
+                self.settings.token_expiry_warning_days = u32::try_from(value).unwrap_or(u32::MAX);
+            }
+            _ => return,
+        }
+        self.dirty = true;
+        self.error = None;
+    }
+
+    fn parse_number(&self, field: SettingField, input: &str) -> Result<u64> {
+        let (min, max) = field.range().unwrap_or((0, u64::MAX));
+        let out_of_range = || {
+            MultiGitError::InvalidInput(format!(
+                "{} must be between {min} and {max}",
+                field.label()
+            ))
+        };
+        let value: u64 = input.trim().parse().map_err(|_| out_of_range())?;
+        if (min..=max).contains(&value) {
+            Ok(value)
+        } else {
+            Err(out_of_range())
+        }
+    }
+
+    /// Check every number in the draft is within its range
+    ///
+    /// Catches out-of-range values loaded from a hand-edited config.
+    pub fn validate(&self) -> Result<()> {
+        for field in SettingField::all() {
+            if field.range().is_some() {
+                self.parse_number(*field, &self.number(*field).to_string())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Copy the draft into `config` after validating it
+    pub fn apply(&mut self, config: &mut Config) -> Result<()> {
+        if self.input.is_some() {
+            return Err(MultiGitError::InvalidInput(
+                "Finish editing the field with Enter, or cancel with Esc".to_string(),
+            ));
+        }
+        if let Err(e) = self.validate() {
+            self.error = Some(e.to_string());
+            return Err(e);
+        }
+
+        config.settings = self.settings.clone();
+        config.sync = self.sync.clone();
+        self.dirty = false;
+        self.error = None;
+        Ok(())
+    }
+}
+
+fn flip(value: &mut bool) {
+    *value = !*value;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn select(editor: &mut SettingsEditor, field: SettingField) {
+        editor.selected = SettingField::all()
+            .iter()
+            .position(|f| *f == field)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_number_input_is_validated() {
+        let mut editor = SettingsEditor::new(&Config::default());
+
+        editor.handle_key(KeyCode::Enter);
+        assert_eq!(editor.input.as_deref(), Some("4"));
+        editor.handle_key(KeyCode::Backspace);
+        for c in "100".chars() {
+            editor.handle_key(KeyCode::Char(c));
+        }
+        editor.handle_key(KeyCode::Enter);
+        assert!(editor
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("between 1 and 64"));
+        assert_eq!(editor.settings.max_parallel, 4);
+
+        editor.handle_key(KeyCode::Backspace);
+        editor.handle_key(KeyCode::Enter);
+        assert!(editor.input.is_none());
+        assert!(editor.error.is_none());
+        assert_eq!(editor.settings.max_parallel, 10);
+        assert!(editor.dirty);
+    }
+
+    #[test]
+    fn test_toggles_and_strategy() {
+        let mut editor = SettingsEditor::new(&Config::default());
+
+        select(&mut editor, SettingField::AutoSync);
+        editor.handle_key(KeyCode::Enter);
+        assert!(editor.sync.auto_sync);
+
+        select(&mut editor, SettingField::Strategy);
+        editor.handle_key(KeyCode::Right);
+        assert_eq!(editor.sync.strategy, SyncStrategy::Merge);
+        editor.handle_key(KeyCode::Left);
+        editor.handle_key(KeyCode::Left);
+        assert_eq!(editor.sync.strategy, SyncStrategy::Force);
+    }
+
+    #[test]
+    fn test_apply_rejects_invalid_draft() {
+        let mut config = Config::default();
+        config.settings.max_parallel = 0;
+        let mut editor = SettingsEditor::new(&config);
+        editor.sync.auto_sync = true;
+
+        assert!(editor.apply(&mut config).is_err());
+        assert!(editor.error.is_some());
+        assert!(!config.sync.auto_sync);
+
+        editor.settings.max_parallel = 8;
+        editor.apply(&mut config).unwrap();
+        assert_eq!(config.settings.max_parallel, 8);
+        assert!(config.sync.auto_sync);
+        assert!(!editor.dirty);
+    }
+}
@@ -7,6 +7,7 @@ use crate::core::config::Config;
 use crate::core::sync_manager::{FetchResult, PushResult, SyncManager};
 use crate::models::Remote;
 use crate::ui::formatter::Status;
+use crate::ui::settings_editor::{SettingField, SettingsEditor};
 use crate::ui::sync_monitor::SyncMonitor;
 use crate::utils::error::Result;
 use crossterm::{
@@ -68,6 +69,12 @@ pub struct App {
     pub reduced_motion: bool,
     /// Screen reader mode
     pub screen_reader_mode: bool,
+    /// Remote waiting for the user to confirm its removal
+    pub pending_delete: Option<String>,
+    /// Outcome of the last change, shown in the status bar
+    pub status_message: Option<String>,
+    /// Draft edited in the Settings tab
+    pub settings_editor: SettingsEditor,
 }
 
 /// Application tabs
@@ -208,26 +215,8 @@ pub enum TuiEvent {
 impl App {
     /// Create new application
     pub fn new(config: Config) -> Result<Self> {
-        let remotes = vec![
-            Remote {
-                name: "github".to_string(),
-                provider: crate::models::remote::ProviderType::GitHub,
-                username: "user".to_string(),
-                api_url: Some("https://api.github.com".to_string()),
-                enabled: true,
-                use_ssh: false,
-                priority: 1,
-            },
-            Remote {
-                name: "gitlab".to_string(),
-                provider: crate::models::remote::ProviderType::GitLab,
-                username: "user".to_string(),
-                api_url: Some("https://gitlab.com/api/v4".to_string()),
-                enabled: true,
-                use_ssh: false,
-                priority: 2,
-            },
-        ];
+        let remotes = remotes_from_config(&config);
+        let settings_editor = SettingsEditor::new(&config);
 
         let mut sync_states = HashMap::new();
         for remote in &remotes {
@@ -265,10 +254,15 @@ impl App {
             high_contrast: false,
             reduced_motion: false,
             screen_reader_mode: false,
+            pending_delete: None,
+            status_message: None,
+            settings_editor,
         };
 
         // Initialize list states
-        app.remote_table_state.select(Some(0));
+        if !app.remotes.is_empty() {
+            app.remote_table_state.select(Some(0));
+        }
         app.conflict_list_state.select(Some(0));
 
         Ok(app)
@@ -276,6 +270,20 @@ impl App {
 
     /// Handle key event
     pub fn handle_key(&mut self, key: KeyEvent) {
+        if let Some(name) = self.pending_delete.take() {
+            if matches!(key.code, KeyCode::Char('y' | 'Y')) {
+                if let Some(message) = self.delete_remote(&name) {
+                    self.persist(message, Some(&name));
+                }
+            } else {
+                self.status_message = Some(format!("Kept remote '{name}'"));
+            }
+            return;
+        }
+        if self.handle_tab_key(key.code) {
+            return;
+        }
+
         match key.code {
             KeyCode::Char('q') | KeyCode::Esc => {
                 if self.show_help {
@@ -411,6 +419,126 @@ impl App {
         }
     }
 
+    /// Handle keys specific to the active tab, returning whether `code` was one
+    fn handle_tab_key(&mut self, code: KeyCode) -> bool {
+        match self.active_tab {
+            Tab::Remotes => self.handle_remotes_key(code),
+            Tab::Settings if code == KeyCode::Char('w') && self.settings_editor.input.is_none() => {
+                self.save_settings();
+                true
+            }
+            Tab::Settings => self.settings_editor.handle_key(code),
+            _ => false,
+        }
+    }
+
+    /// Handle the keys that manage remotes, returning whether `code` was one
+    fn handle_remotes_key(&mut self, code: KeyCode) -> bool {
+        let remote = self.selected_remote().map(|remote| remote.name.clone());
+        let message = match code {
+            KeyCode::Char(' ') => self.toggle_selected_remote(),
+            KeyCode::Char('+' | '=') => self.change_selected_priority(1),
+            KeyCode::Char('-') => self.change_selected_priority(-1),
+            KeyCode::Char('d') => {
+                self.pending_delete = self.selected_remote().map(|remote| remote.name.clone());
+                None
+            }
+            _ => return false,
+        };
+
+        if let Some(message) = message {
+            self.persist(message, remote.as_deref());
+        }
+        true
+    }
+
+    /// Remote under the cursor in the Remotes tab
+    fn selected_remote(&self) -> Option<&Remote> {
+        self.remote_table_state
+            .selected()
+            .and_then(|selected| self.remotes.get(selected))
+    }
+
+    /// Enable or disable the selected remote, returning what changed
+    fn toggle_selected_remote(&mut self) -> Option<String> {
+        let name = self.selected_remote()?.name.clone();
+        let remote = self.config.remotes.get_mut(&name)?;
+        remote.enabled = !remote.enabled;
+        let enabled = remote.enabled;
+
+        self.reload_remotes(&name);
+        let action = if enabled { "Enabled" } else { "Disabled" };
+        Some(format!("{action} remote '{name}'"))
+    }
+
+    /// Raise or lower the priority of the selected remote by `delta`
+    ///
+    /// The list stays sorted by priority, so the cursor follows the remote.
+    fn change_selected_priority(&mut self, delta: i32) -> Option<String> {
+        let name = self.selected_remote()?.name.clone();
+        let remote = self.config.remotes.get_mut(&name)?;
+        remote.priority = remote.priority.saturating_add(delta);
+        let priority = remote.priority;
+
+        self.reload_remotes(&name);
+        Some(format!("Priority of '{name}' is now {priority}"))
+    }
+
+    /// Remove `name` from the configuration
+    fn delete_remote(&mut self, name: &str) -> Option<String> {
+        self.config.remotes.remove(name)?;
+        self.sync_states.remove(name);
+
+        let selected = self.remote_table_state.selected().unwrap_or(0);
+        self.remotes = remotes_from_config(&self.config);
+        self.remote_table_state.select(if self.remotes.is_empty() {
+            None
+        } else {
+            Some(selected.min(self.remotes.len() - 1))
+        });
+
+        Some(format!(
+            "Removed remote '{name}' (stored credentials are kept; 'multigit remote remove' deletes them)"
+        ))
+    }
+
+    /// Rebuild the remote list from the config, keeping `name` selected
+    fn reload_remotes(&mut self, name: &str) {
+        self.remotes = remotes_from_config(&self.config);
+        let selected = self.remotes.iter().position(|remote| remote.name == name);
+        self.remote_table_state.select(selected);
+    }
+
+    /// Validate the Settings tab draft and save it
+    fn save_settings(&mut self) {
+        match self.settings_editor.apply(&mut self.config) {
+            Ok(()) => self.persist("Saved settings".to_string(), None),
+            Err(e) => self.status_message = Some(e.to_string()),
+        }
+    }
+
+    /// Save the configuration after a change made in the TUI
+    ///
+    /// A change to `remote` goes to the files that define it, so a remote
+    /// from the repository config is updated (or deleted) there rather than
+    /// copied into the user config.
+    fn persist(&mut self, message: String, remote: Option<&str>) {
+        let result = match remote.map(Config::files_defining_remote) {
+            Some(Ok(paths)) if !paths.is_empty() => paths
+                .iter()
+                .try_for_each(|path| self.config.save_to_file(path)),
+            Some(Err(e)) => Err(e),
+            _ => self.config.save(),
+        };
+        self.status_message = Some(match result {
+            Ok(()) => message,
+            Err(e) => {
+                error!("Failed to save config: {}", e);
+                format!("Failed to save config: {e}")
+            }
+        });
+    }
+
     /// Trigger sync for a remote
     fn trigger_sync(&mut self, remote_name: &str) {
         if let Some(state) = self.sync_states.get_mut(remote_name) {
@@ -468,6 +596,7 @@ impl App {
     /// Refresh data
     fn refresh_data(&mut self) {
         self.last_update = Instant::now();
+        self.status_message = None;
         info!("Refreshing TUI data");
         // This would reload config and sync states
     }
@@ -596,10 +725,44 @@ impl App {
             }
         }
 
+        if let Some(name) = &self.pending_delete {
+            self.draw_confirm_delete(f, chunks[1], name);
+        }
+
         // Draw status bar
         self.draw_status_bar(f, chunks[2]);
     }
 
+    /// Draw the dialog asking to confirm removing `name`
+    fn draw_confirm_delete(&self, f: &mut Frame, area: Rect, name: &str) {
+        let width = area.width.min(60);
+        let height = area.height.min(5);
+        let dialog = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        let text = Paragraph::new(vec![
+            Line::from(format!("Remove remote '{name}' from the configuration?")),
+            Line::from(""),
+            Line::from("y - remove    any other key - cancel"),
+        ])
+        .style(Style::default().fg(self.theme.foreground))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(self.theme.warning))
+                .title("Confirm"),
+        )
+        .wrap(Wrap { trim: true });
+
+        f.render_widget(Clear, dialog);
+        f.render_widget(text, dialog);
+    }
+
     /// Draw tabs
     fn draw_tabs(&self, f: &mut Frame, area: Rect) {
         let tabs: Vec<Line> = Tab::all()
@@ -674,8 +837,13 @@ impl App {
         f.render_widget(repo_info, chunks[0]);
 
         // Quick stats
-        let stats = Paragraph::new("✅ Synced: 2 | ⚠️ Pending: 1 | ❌ Failed: 0")
-            .style(Style::default().fg(self.theme.foreground));
+        let stats = Paragraph::new(format!(
+            "{} Synced: 2 | {} Pending: 1 | {} Failed: 0",
+            Status::Success.icon(),
+            Status::Pending.icon(),
+            Status::Error.icon()
+        ))
+        .style(Style::default().fg(self.theme.foreground));
         f.render_widget(stats, chunks[1]);
 
         // Progress bars for active operations
@@ -706,11 +874,8 @@ impl App {
             .values()
             .map(|state| {
                 let icon = match state.status {
-                    Status::Success => "✓",
-                    Status::Error => "✗",
-                    Status::Warning => "⚠",
                     Status::Pending => Self::get_spinner_char(state.animation_frame),
-                    Status::Info => "ℹ",
+                    status => status.icon(),
                 };
 
                 let color = self.get_pulsing_color(
@@ -719,7 +884,7 @@ impl App {
                         Status::Error => self.theme.error,
                         Status::Warning => self.theme.warning,
                         Status::Pending => self.theme.primary,
-                        Status::Info => self.theme.secondary,
+                        Status::Info | Status::Skipped => self.theme.secondary,
                     },
                     state.pulse_phase,
                 );
@@ -749,7 +914,9 @@ impl App {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(self.theme.border))
-            .title("Remotes (↑↓ to navigate, Enter to sync)");
+            .title(
+                "Remotes (↑↓ navigate, Enter sync, Space enable/disable, +/- priority, d delete)",
+            );
 
         let inner = block.inner(area);
         f.render_widget(block, area);
@@ -784,6 +951,8 @@ impl App {
 
                 Row::new(vec![
                     remote.name.clone(),
+                    Status::of_enabled(remote.enabled).icon().to_string(),
+                    remote.priority.to_string(),
                     format!("{:?}", state.status),
                     state.operation.clone(),
                     format!("{}%", state.progress),
@@ -795,18 +964,25 @@ impl App {
         let table = Table::new(
             rows,
             &[
-                Constraint::Percentage(25),
+                Constraint::Percentage(20),
+                Constraint::Percentage(10),
+                Constraint::Percentage(10),
+                Constraint::Percentage(15),
+                Constraint::Percentage(30),
                 Constraint::Percentage(15),
-                Constraint::Percentage(35),
-                Constraint::Percentage(25),
             ],
         )
-        .widths(&[
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-            Constraint::Percentage(35),
-            Constraint::Percentage(15),
-        ])
+        .header(
+            Row::new(vec![
+                "Remote",
+                "Enabled",
+                "Priority",
+                "Status",
+                "Operation",
+                "Progress",
+            ])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
         .block(Block::default().padding(ratatui::widgets::Padding::uniform(1)))
         .style(Style::default().fg(self.theme.foreground));
 
@@ -843,15 +1019,54 @@ impl App {
 
     /// Draw settings panel
     fn draw_settings(&self, f: &mut Frame, area: Rect) {
+        let editor = &self.settings_editor;
+        let title = if editor.dirty {
+            "Settings * (↑↓ select, ←→/Enter change, w save)"
+        } else {
+            "Settings (↑↓ select, ←→/Enter change, w save)"
+        };
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(self.theme.border))
-            .title("Settings");
+            .title(title);
 
         let inner = block.inner(area);
         f.render_widget(block, area);
 
-        let settings_text = vec![
+        let mut settings_text: Vec<Line> = SettingField::all()
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let selected = i == editor.selected;
+                let value = match (&editor.input, selected) {
+                    (Some(input), true) => format!("[{input}_]"),
+                    _ if field.range().is_some() => editor.value(*field),
+                    _ => format!("< {} >", editor.value(*field)),
+                };
+                let style = if selected {
+                    Style::default()
+                        .fg(self.theme.primary)
+                        .add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::default().fg(self.theme.foreground)
+                };
+                Line::from(Span::styled(
+                    format!("{:<30} {value}", field.label()),
+                    style,
+                ))
+            })
+            .collect();
+
+        settings_text.push(Line::from(""));
+        if let Some(error) = &editor.error {
+            settings_text.push(Line::from(Span::styled(
+                format!("{} {error}", Status::Error.icon()),
+                Style::default().fg(self.theme.error),
+            )));
+            settings_text.push(Line::from(""));
+        }
+
+        settings_text.extend([
             Line::from("Theme: Press 't' to toggle"),
             Line::from(format!(
                 "High Contrast: {} (Press 'c')",
@@ -865,16 +1080,7 @@ impl App {
                 "Screen Reader: {} (Press F1)",
                 if self.screen_reader_mode { "ON" } else { "OFF" }
             )),
-            Line::from(""),
-            Line::from("Auto-sync: Enabled"),
-            Line::from("Max parallel: 4"),
-            Line::from("Update interval: 5min"),
-            Line::from(""),
-            Line::from("Press 'r' to refresh data"),
-            Line::from("Press 's' to sync all remotes"),
-            Line::from("Press 'h' to toggle help"),
-            Line::from("Press 'q' or Esc to quit"),
-        ];
+        ]);
 
         let settings = Paragraph::new(settings_text)
             .style(Style::default().fg(self.theme.foreground))
@@ -901,6 +1107,15 @@ impl App {
             Line::from("  ↑/↓ - Navigate lists"),
             Line::from("  Enter - Select/Execute"),
             Line::from(""),
+            Line::from("Remotes tab:"),
+            Line::from("  Space - Enable/disable remote"),
+            Line::from("  +/- - Raise/lower priority"),
+            Line::from("  d - Remove remote (asks to confirm)"),
+            Line::from(""),
+            Line::from("Settings tab:"),
+            Line::from("  ←/→ - Change value, Enter - Type a number or toggle"),
+            Line::from("  w - Validate and save settings"),
+            Line::from(""),
             Line::from("Actions:"),
             Line::from("  r - Refresh data"),
             Line::from("  s - Sync all remotes"),
@@ -929,16 +1144,20 @@ impl App {
 
     /// Draw status bar
     fn draw_status_bar(&self, f: &mut Frame, area: Rect) {
-        let status_text = format!(
-            "Last update: {}s ago | Theme: {} | Remotes: {} | Press 'h' for help",
-            self.last_update.elapsed().as_secs(),
-            if self.theme.background == Color::Black {
-                "Dark"
-            } else {
-                "Light"
-            },
-            self.remotes.len()
-        );
+        let status_text = if let Some(message) = &self.status_message {
+            message.clone()
+        } else {
+            format!(
+                "Last update: {}s ago | Theme: {} | Remotes: {} | Press 'h' for help",
+                self.last_update.elapsed().as_secs(),
+                if self.theme.background == Color::Black {
+                    "Dark"
+                } else {
+                    "Light"
+                },
+                self.remotes.len()
+            )
+        };
 
         let status_bar = Paragraph::new(status_text)
             .style(
@@ -956,6 +1175,26 @@ impl App {
     }
 }
 
+/// Configured remotes for the Remotes tab, highest priority first
+fn remotes_from_config(config: &Config) -> Vec<Remote> {
+    let mut remotes: Vec<Remote> = config
+        .remotes
+        .iter()
+        .filter_map(|(name, remote)| {
+            Remote::try_from((name.as_str(), remote))
+                .map_err(|e| debug!("Not showing remote '{}': {}", name, e))
+                .ok()
+        })
+        .collect();
+
+    remotes.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    remotes
+}
+
 /// Run the TUI application
 pub async fn run_tui(config: Config) -> Result<()> {
     info!("Starting MultiGit TUI");
@@ -1058,3 +1297,67 @@ pub fn start_dashboard(config: Config) -> Result<()> {
     let rt = tokio::runtime::Runtime::new()?;
     rt.block_on(run_tui(config))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::RemoteConfig;
+
+    fn app() -> App {
+        let mut config = Config::default();
+        for (name, priority) in [("github", 0), ("gitlab", 5)] {
+            config.add_remote(
+                name.to_string(),
+                RemoteConfig {
+                    username: "user".to_string(),
+                    provider: Some(name.to_string()),
+                    priority,
+                    ..Default::default()
+                },
+            );
+        }
+        App::new(config).unwrap()
+    }
+
+    #[test]
+    fn test_remotes_sorted_by_priority() {
+        let app = app();
+        let names: Vec<&str> = app.remotes.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["gitlab", "github"]);
+    }
+
+    #[test]
+    fn test_toggle_and_priority_follow_selection() {
+        let mut app = app();
+        app.remote_table_state.select(Some(1));
+
+        app.toggle_selected_remote().unwrap();
+        assert!(!app.config.remotes["github"].enabled);
+        assert!(!app.remotes[1].enabled);
+
+        // Raising github above gitlab moves it, and the cursor, to the top
+        for _ in 0..6 {
+            app.change_selected_priority(1).unwrap();
+        }
+        assert_eq!(app.config.remotes["github"].priority, 6);
+        assert_eq!(app.remotes[0].name, "github");
+        assert_eq!(app.remote_table_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_delete_needs_confirmation() {
+        let mut app = app();
+        app.active_tab = Tab::Remotes;
+
+        app.handle_key(KeyEvent::from(KeyCode::Char('d')));
+        assert_eq!(app.pending_delete.as_deref(), Some("gitlab"));
+        app.handle_key(KeyEvent::from(KeyCode::Char('n')));
+        assert!(app.pending_delete.is_none());
+        assert!(app.config.remotes.contains_key("gitlab"));
+
+        app.delete_remote("gitlab").unwrap();
+        assert!(!app.config.remotes.contains_key("gitlab"));
+        assert_eq!(app.remotes.len(), 1);
+        assert_eq!(app.remote_table_state.selected(), Some(0));
+    }
+}
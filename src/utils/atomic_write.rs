@@ -0,0 +1,124 @@
+//! Crash-safe file writes
+//!
+//! Contents are written to a temporary file in the target's directory,
+//! flushed to disk, and then renamed over the target. A crash or error
+//! mid-write leaves the previous file untouched instead of truncated.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Atomically replace `path` with `contents`
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    write_atomic_with(path, |file| file.write_all(contents.as_ref()))
+}
+
+/// Atomically replace `path` with whatever `write` writes to the temp file
+///
+/// If `write` fails, the temporary file is removed and `path` is left as it was.
+pub fn write_atomic_with<F>(path: &Path, write: F) -> io::Result<()>
+where
+    F: FnOnce(&mut File) -> io::Result<()>,
+{
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(parent)?;
+
+    let temp_path = temp_path_for(path);
+    let result = write_temp(path, &temp_path, write).and_then(|()| fs::rename(&temp_path, path));
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+        return result;
+    }
+
+    sync_dir(parent);
+    Ok(())
+}
+
+fn write_temp<F>(path: &Path, temp_path: &Path, write: F) -> io::Result<()>
+where
+    F: FnOnce(&mut File) -> io::Result<()>,
+{
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(temp_path)?;
+
+    // Keep the permissions of the file being replaced (e.g. 0600 credential stores)
+    if let Ok(metadata) = fs::metadata(path) {
+        file.set_permissions(metadata.permissions())?;
+    }
+
+    write(&mut file)?;
+    file.sync_all()
+}
+
+/// Hidden temp file next to `path`, unique per process
+fn temp_path_for(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map_or_else(|| "file".into(), |n| n.to_string_lossy().into_owned());
+    path.with_file_name(format!(".{name}.{}.tmp", std::process::id()))
+}
+
+/// Flush the directory entry so the rename itself survives a crash
+#[cfg(unix)]
+fn sync_dir(dir: &Path) {
+    if let Ok(dir) = File::open(dir) {
+        let _ = dir.sync_all();
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_atomic_replaces_file() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.toml");
+        fs::write(&path, "old").unwrap();
+
+        write_atomic(&path, "new").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(fs::read_dir(temp.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_write_atomic_creates_parent_dirs() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("nested/dir/state.json");
+
+        write_atomic(&path, "{}").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_partial_write_does_not_clobber() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.toml");
+        fs::write(&path, "[settings]\ndefault_branch = \"main\"\n").unwrap();
+
+        let result = write_atomic_with(&path, |file| {
+            file.write_all(b"[settings]\ndefault_br")?;
+            Err(io::Error::new(io::ErrorKind::Other, "killed mid-write"))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "[settings]\ndefault_branch = \"main\"\n"
+        );
+        // The temporary file is cleaned up
+        assert_eq!(fs::read_dir(temp.path()).unwrap().count(), 1);
+    }
+}
@@ -87,6 +87,17 @@ pub enum MultiGitError {
         message: String,
     },
 
+    /// Too many recent failures for a host; calls fail fast until the cooldown ends
+    #[error(
+        "Circuit open for {provider} after repeated failures; retrying in {retry_in_seconds}s"
+    )]
+    CircuitOpen {
+        /// Provider (and host, if not the public one) whose circuit is open
+        provider: String,
+        /// Seconds until a request is let through again
+        retry_in_seconds: u64,
+    },
+
     /// Daemon error
     #[error("Daemon error: {0}")]
     DaemonError(String),
@@ -129,6 +140,14 @@ impl MultiGitError {
         }
     }
 
+    /// Create an error for a host whose circuit breaker is open
+    pub fn circuit_open(provider: impl Into<String>, retry_in_seconds: u64) -> Self {
+        Self::CircuitOpen {
+            provider: provider.into(),
+            retry_in_seconds,
+        }
+    }
+
     /// Create a configuration error
     pub fn config(message: impl Into<String>) -> Self {
         Self::ConfigError(message.into())
@@ -212,6 +231,19 @@ impl MultiGitError {
         )
     }
 
+    /// Check if this error came from an open circuit breaker
+    #[must_use]
+    pub fn is_circuit_open(&self) -> bool {
+        matches!(self, Self::CircuitOpen { .. })
+    }
+
+    /// Check if this is a push the remote rejected because it is not a
+    /// fast-forward of the remote branch
+    #[must_use]
+    pub fn is_non_fast_forward(&self) -> bool {
+        matches!(self, Self::GitError(e) if e.code() == git2::ErrorCode::NotFastForward)
+    }
+
     /// Check if this is a authentication-related error
     #[must_use]
     pub fn is_auth_error(&self) -> bool {
@@ -0,0 +1,178 @@
+//! Git remote URL parsing
+//!
+//! Splits the URL forms git accepts for hosted repositories into host, owner,
+//! repository name and protocol, and maps well-known hosts to providers.
+
+use crate::utils::error::{MultiGitError, Result};
+use url::Url;
+
+/// Hosted services recognised from a remote's host
+pub const KNOWN_HOSTS: &[(&str, &str)] = &[
+    ("github.com", "github"),
+    ("gitlab.com", "gitlab"),
+    ("bitbucket.org", "bitbucket"),
+    ("codeberg.org", "codeberg"),
+];
+
+/// Transport used by a remote URL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UrlProtocol {
+    /// `https://`
+    Https,
+    /// `http://`
+    Http,
+    /// `ssh://` or scp-like `user@host:path`
+    Ssh,
+}
+
+/// A parsed git remote URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitUrl {
+    /// Host name, lowercased
+    pub host: String,
+    /// Owner path; may contain `/` for nested groups (GitLab subgroups)
+    pub owner: String,
+    /// Repository name without `.git`
+    pub repo: String,
+    /// Transport
+    pub protocol: UrlProtocol,
+}
+
+impl GitUrl {
+    /// Provider for the host, if it is a known hosted service
+    #[must_use]
+    pub fn provider(&self) -> Option<&'static str> {
+        provider_for_host(&self.host)
+    }
+
+    /// Whether the URL uses SSH
+    #[must_use]
+    pub fn is_ssh(&self) -> bool {
+        self.protocol == UrlProtocol::Ssh
+    }
+
+    /// Web page of the repository, e.g. `https://github.com/owner/repo`
+    #[must_use]
+    pub fn web_url(&self) -> String {
+        let scheme = if self.protocol == UrlProtocol::Http {
+            "http"
+        } else {
+            "https"
+        };
+        format!("{scheme}://{}/{}/{}", self.host, self.owner, self.repo)
+    }
+}
+
+/// Provider for a host name, if it is a known hosted service
+#[must_use]
+pub fn provider_for_host(host: &str) -> Option<&'static str> {
+    KNOWN_HOSTS
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(host))
+        .map(|(_, provider)| *provider)
+}
+
+/// Parse a git remote URL
+///
+/// Accepts `https://host/owner/repo.git`, `http://…`, `ssh://git@host[:port]/owner/repo.git`
+/// and the scp-like `git@host:owner/repo.git`. Local paths and `file://` URLs are
+/// rejected since they have no owner.
+pub fn parse(url: &str) -> Result<GitUrl> {
+    let url = url.trim();
+    let invalid = |reason: &str| MultiGitError::InvalidInput(format!("'{url}': {reason}"));
+
+    let (protocol, host, path) = if url.contains("://") {
+        let parsed = Url::parse(url).map_err(|e| invalid(&format!("unrecognised URL ({e})")))?;
+        let protocol = match parsed.scheme() {
+            "https" => UrlProtocol::Https,
+            "http" => UrlProtocol::Http,
+            "ssh" | "git+ssh" | "ssh+git" => UrlProtocol::Ssh,
+            scheme => return Err(invalid(&format!("unsupported scheme '{scheme}'"))),
+        };
+        let host = parsed
+            .host_str()
+            .ok_or_else(|| invalid("URL has no host"))?;
+        (protocol, host.to_string(), parsed.path().to_string())
+    } else if let Some((user_host, path)) = url.split_once(':') {
+        // scp-like: [user@]host:path, but not a Windows drive or a local path
+        if user_host.contains('/') || user_host.len() < 2 {
+            return Err(invalid("not a remote URL"));
+        }
+        let host = user_host.rsplit('@').next().unwrap_or(user_host);
+        (UrlProtocol::Ssh, host.to_string(), path.to_string())
+    } else {
+        return Err(invalid("not a remote URL"));
+    };
+
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, repo) = path
+        .rsplit_once('/')
+        .filter(|(owner, repo)| !owner.is_empty() && !repo.is_empty())
+        .ok_or_else(|| invalid("expected <owner>/<repository> in the path"))?;
+
+    Ok(GitUrl {
+        host: host.to_lowercase(),
+        owner: owner.to_string(),
+        repo: repo.to_string(),
+        protocol,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_https() {
+        let url = parse("https://github.com/alice/project.git").unwrap();
+        assert_eq!(
+            url,
+            GitUrl {
+                host: "github.com".to_string(),
+                owner: "alice".to_string(),
+                repo: "project".to_string(),
+                protocol: UrlProtocol::Https,
+            }
+        );
+        assert_eq!(url.provider(), Some("github"));
+        assert_eq!(url.web_url(), "https://github.com/alice/project");
+
+        let url = parse("https://user@GitLab.com/group/sub/project").unwrap();
+        assert_eq!(url.host, "gitlab.com");
+        assert_eq!(url.owner, "group/sub");
+        assert_eq!(url.repo, "project");
+    }
+
+    #[test]
+    fn test_parse_ssh_forms() {
+        let url = parse("git@gitlab.com:team/project.git").unwrap();
+        assert_eq!(url.protocol, UrlProtocol::Ssh);
+        assert_eq!(url.owner, "team");
+        assert_eq!(url.repo, "project");
+        assert_eq!(url.provider(), Some("gitlab"));
+
+        let url = parse("ssh://git@codeberg.org:2222/bob/project.git").unwrap();
+        assert!(url.is_ssh());
+        assert_eq!(url.host, "codeberg.org");
+        assert_eq!(url.owner, "bob");
+        assert_eq!(url.web_url(), "https://codeberg.org/bob/project");
+    }
+
+    #[test]
+    fn test_parse_unknown_host() {
+        let url = parse("http://git.example.com/team/project.git").unwrap();
+        assert_eq!(url.protocol, UrlProtocol::Http);
+        assert_eq!(url.provider(), None);
+        assert_eq!(url.web_url(), "http://git.example.com/team/project");
+    }
+
+    #[test]
+    fn test_parse_rejects_non_remote() {
+        assert!(parse("/srv/git/project.git").is_err());
+        assert!(parse("C:/repos/project").is_err());
+        assert!(parse("file:///srv/git/project.git").is_err());
+        assert!(parse("https://github.com/project").is_err());
+        assert!(parse("git@github.com:").is_err());
+    }
+}
@@ -0,0 +1,91 @@
+//! Ctrl-C handling for long-running commands
+//!
+//! The CLI runs [`watch_ctrl_c`] for its whole lifetime, except when running
+//! the daemon, which handles SIGINT itself. While a push or fetch holds an
+//! [`Interruptible`] guard, the first Ctrl-C cancels [`token`]: git transfers
+//! stop at their next callback and remotes that already finished are still
+//! reported. A second Ctrl-C, or one with nothing in flight, restores the
+//! terminal and exits straight away.
+
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use tokio_util::sync::CancellationToken;
+
+/// Exit status for a process stopped by SIGINT, as shells report it
+pub const EXIT_INTERRUPTED: i32 = 130;
+
+static TOKEN: OnceLock<CancellationToken> = OnceLock::new();
+
+/// Operations currently willing to wind down on Ctrl-C
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+/// Process-wide token cancelled by the first Ctrl-C during an operation
+#[must_use]
+pub fn token() -> CancellationToken {
+    TOKEN.get_or_init(CancellationToken::new).clone()
+}
+
+/// Whether Ctrl-C has cancelled in-flight operations
+#[must_use]
+pub fn is_interrupted() -> bool {
+    token().is_cancelled()
+}
+
+/// Marks an operation that stops cleanly when [`token`] is cancelled
+///
+/// Ctrl-C only waits for in-flight work while at least one guard is alive.
+pub struct Interruptible(());
+
+impl Interruptible {
+    /// Register an operation until the guard is dropped
+    #[must_use]
+    pub fn new() -> Self {
+        IN_FLIGHT.fetch_add(1, Ordering::SeqCst);
+        Self(())
+    }
+}
+
+impl Default for Interruptible {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Interruptible {
+    fn drop(&mut self) {
+        IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Handle Ctrl-C for the rest of the process
+///
+/// Spawn this on the CLI runtime once.
+pub async fn watch_ctrl_c() {
+    loop {
+        if tokio::signal::ctrl_c().await.is_err() {
+            // No signal handling on this platform; keep the default behavior
+            return;
+        }
+
+        let token = token();
+        if IN_FLIGHT.load(Ordering::SeqCst) == 0 || token.is_cancelled() {
+            restore_terminal();
+            std::process::exit(EXIT_INTERRUPTED);
+        }
+
+        token.cancel();
+        restore_terminal();
+        eprintln!("\nInterrupted: stopping transfers (Ctrl-C again to quit immediately)");
+    }
+}
+
+/// Put the terminal back into a usable state
+///
+/// Leaves raw mode and shows the cursor that progress bars hide.
+pub fn restore_terminal() {
+    let _ = crossterm::terminal::disable_raw_mode();
+    let mut stderr = std::io::stderr();
+    let _ = crossterm::execute!(stderr, crossterm::cursor::Show);
+    let _ = stderr.flush();
+}
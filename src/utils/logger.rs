@@ -1,15 +1,36 @@
 //! Logging configuration and utilities
 //!
 //! This module sets up tracing for structured logging across `MultiGit`.
-//! Supports multiple log levels, colored output, and JSON formatting.
-
+//! Supports multiple log levels, colored output, several console formats
+//! (plain, compact, pretty, JSON and logfmt), and an optional size-rotated log
+//! file with secrets redacted. With `--debug-timings` a span collector totals
+//! the time spent in each instrumented span.
+
+use crate::utils::log_file::{LogRotation, RedactingWriter, RotatingFileWriter};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::field::{Field, Visit};
+use tracing::{span, Event, Subscriber};
 use tracing_subscriber::{
-    fmt::{self, format::FmtSpan},
-    layer::SubscriberExt,
+    fmt::{
+        self,
+        format::{FmtSpan, Writer},
+        FmtContext, FormatEvent, FormatFields,
+    },
+    layer::{Context, SubscriberExt},
+    registry::LookupSpan,
     util::SubscriberInitExt,
-    EnvFilter, Layer,
+    EnvFilter, Layer, Registry,
 };
 
+/// A layer of the global subscriber
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+/// Span totals gathered by [`TimingLayer`], keyed by span name
+static SPAN_TIMINGS: Mutex<Option<HashMap<&'static str, SpanTiming>>> = Mutex::new(None);
+
 /// Log level configuration
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LogLevel {
@@ -57,6 +78,51 @@ impl std::fmt::Display for LogLevel {
     }
 }
 
+/// How log lines are written to the console
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// One line per event with level, optional target and fields (default)
+    #[default]
+    Plain,
+    /// One JSON object per line
+    Json,
+    /// Shorter lines, with span fields folded in
+    Compact,
+    /// Multi-line, human-friendly output with source locations
+    Pretty,
+    /// `key=value` pairs, e.g. for Loki
+    Logfmt,
+}
+
+impl std::fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Plain => "plain",
+            Self::Json => "json",
+            Self::Compact => "compact",
+            Self::Pretty => "pretty",
+            Self::Logfmt => "logfmt",
+        })
+    }
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "plain" => Ok(Self::Plain),
+            "json" => Ok(Self::Json),
+            "compact" => Ok(Self::Compact),
+            "pretty" => Ok(Self::Pretty),
+            "logfmt" => Ok(Self::Logfmt),
+            _ => Err(format!(
+                "Unknown log format '{s}': use plain, json, compact, pretty or logfmt"
+            )),
+        }
+    }
+}
+
 /// Logger configuration
 #[derive(Debug, Clone)]
 pub struct LoggerConfig {
@@ -66,8 +132,8 @@ pub struct LoggerConfig {
     /// Enable colored output
     pub colored: bool,
 
-    /// Use JSON format instead of pretty text
-    pub json: bool,
+    /// Console log format
+    pub format: LogFormat,
 
     /// Show timestamps
     pub timestamps: bool,
@@ -77,6 +143,21 @@ pub struct LoggerConfig {
 
     /// Log to file path (optional)
     pub file_path: Option<String>,
+
+    /// Size-based rotation policy for the log file
+    pub rotation: LogRotation,
+
+    /// Write the log file as JSON lines instead of plain text
+    pub file_json: bool,
+
+    /// Level recorded in the log file (default: the console level, at least info)
+    pub file_level: Option<LogLevel>,
+
+    /// Filter directives refining the level, from `settings.log_filter`
+    pub filter: Option<String>,
+
+    /// Collect per-span timings for [`timing_report`]
+    pub timings: bool,
 }
 
 impl Default for LoggerConfig {
@@ -84,10 +165,15 @@ impl Default for LoggerConfig {
         Self {
             level: LogLevel::Info,
             colored: true,
-            json: false,
+            format: LogFormat::Plain,
             timestamps: true,
             show_target: false,
             file_path: None,
+            rotation: LogRotation::default(),
+            file_json: false,
+            file_level: None,
+            filter: None,
+            timings: false,
         }
     }
 }
@@ -113,19 +199,61 @@ impl LoggerConfig {
         self
     }
 
-    /// Enable JSON output
+    /// Write console logs in `format`
     #[must_use]
-    pub fn json(mut self) -> Self {
-        self.json = true;
+    pub fn with_format(mut self, format: LogFormat) -> Self {
+        self.format = format;
         self
     }
 
+    /// Enable JSON output; same as `with_format(LogFormat::Json)`
+    #[must_use]
+    pub fn json(self) -> Self {
+        self.with_format(LogFormat::Json)
+    }
+
     /// Set log file path
     pub fn with_file(mut self, path: impl Into<String>) -> Self {
         self.file_path = Some(path.into());
         self
     }
 
+    /// Set the rotation policy for the log file
+    #[must_use]
+    pub fn with_rotation(mut self, rotation: LogRotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Write the log file as JSON lines
+    #[must_use]
+    pub fn with_file_json(mut self) -> Self {
+        self.file_json = true;
+        self
+    }
+
+    /// Record `level` in the log file regardless of the console level
+    #[must_use]
+    pub fn with_file_level(mut self, level: LogLevel) -> Self {
+        self.file_level = Some(level);
+        self
+    }
+
+    /// Refine the level with filter directives such as
+    /// `multigit::providers=debug`; `RUST_LOG` still overrides them
+    #[must_use]
+    pub fn with_log_filter(mut self, directives: Option<String>) -> Self {
+        self.filter = directives.filter(|directives| !directives.trim().is_empty());
+        self
+    }
+
+    /// Collect per-span timings for [`timing_report`]
+    #[must_use]
+    pub fn with_timings(mut self) -> Self {
+        self.timings = true;
+        self
+    }
+
     /// Hide timestamps
     #[must_use]
     pub fn without_timestamps(mut self) -> Self {
@@ -142,44 +270,331 @@ impl LoggerConfig {
 }
 
 /// Initialize the global logger
+///
+/// The level sets a baseline that `settings.log_filter` and then `RUST_LOG`
+/// refine, so `RUST_LOG=multigit::providers=debug` turns up one module while
+/// the rest stays at the `-v` level.
 pub fn init_logger(config: LoggerConfig) -> anyhow::Result<()> {
-    // Build the env filter
-    let env_filter = EnvFilter::try_from_default_env()
-        .or_else(|_| EnvFilter::try_new(config.level.as_filter()))?;
+    let rust_log = std::env::var("RUST_LOG").ok();
+    let (env_filter, mut invalid) =
+        build_filter(config.level, config.filter.as_deref(), rust_log.as_deref());
+
+    let mut layers: Vec<BoxedLayer> = Vec::new();
+    let (file_layer, file_invalid) = build_file_layer(&config, rust_log.as_deref())?;
+    layers.extend(file_layer);
+    invalid.extend(file_invalid);
+    if config.timings {
+        layers.push(TimingLayer.boxed());
+    }
+    layers.push(console_layer(&config).with_filter(env_filter).boxed());
+
+    tracing_subscriber::registry().with(layers).init();
 
-    if config.json {
-        // JSON formatted output
-        let json_layer = fmt::layer()
+    invalid.sort();
+    invalid.dedup();
+    for directive in invalid {
+        tracing::warn!("Ignoring invalid log filter directive '{}'", directive);
+    }
+    tracing::debug!("Logger initialized with level: {}", config.level);
+
+    Ok(())
+}
+
+/// The console layer in the configured [`LogFormat`]
+fn console_layer(config: &LoggerConfig) -> BoxedLayer {
+    let layer = fmt::layer()
+        .with_ansi(config.colored)
+        .with_target(config.show_target)
+        .with_span_events(FmtSpan::CLOSE);
+
+    match (config.format, config.timestamps) {
+        (LogFormat::Json, _) => layer.json().boxed(),
+        (LogFormat::Logfmt, timestamps) => layer
+            .with_ansi(false)
+            .event_format(Logfmt { timestamps })
+            .boxed(),
+        (LogFormat::Plain, true) => layer.boxed(),
+        (LogFormat::Plain, false) => layer.without_time().boxed(),
+        (LogFormat::Compact, true) => layer.compact().boxed(),
+        (LogFormat::Compact, false) => layer.compact().without_time().boxed(),
+        (LogFormat::Pretty, true) => layer.pretty().boxed(),
+        (LogFormat::Pretty, false) => layer.pretty().without_time().boxed(),
+    }
+}
+
+/// Event formatter for logfmt: `ts=… level=info target=… msg="…" key=value`
+struct Logfmt {
+    timestamps: bool,
+}
+
+impl<S, N> FormatEvent<S, N> for Logfmt
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> std::fmt::Result {
+        let metadata = event.metadata();
+        if self.timestamps {
+            let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+            write!(writer, "ts={now} ")?;
+        }
+        write!(
+            writer,
+            "level={} target={}",
+            metadata.level().as_str().to_lowercase(),
+            logfmt_value(metadata.target())
+        )?;
+        if let Some(scope) = ctx.event_scope() {
+            let spans: Vec<&str> = scope.from_root().map(|span| span.name()).collect();
+            write!(writer, " span={}", logfmt_value(&spans.join(":")))?;
+        }
+
+        let mut fields = LogfmtFields::default();
+        event.record(&mut fields);
+        writeln!(writer, "{}", fields.0)
+    }
+}
+
+/// An event's fields as logfmt pairs, with `message` written as `msg`
+#[derive(Default)]
+struct LogfmtFields(String);
+
+impl LogfmtFields {
+    fn push(&mut self, field: &Field, value: &str) {
+        let key = match field.name() {
+            "message" => "msg",
+            name => name,
+        };
+        let _ = write!(self.0, " {key}={}", logfmt_value(value));
+    }
+}
+
+impl Visit for LogfmtFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.push(field, value);
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.push(field, &format!("{value:?}"));
+    }
+}
+
+/// `value`, quoted and escaped when it is empty or has spaces, `=` or quotes
+fn logfmt_value(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value
+            .chars()
+            .any(|c| c.is_whitespace() || c == '=' || c == '"' || c.is_control());
+    if !needs_quotes {
+        return value.to_string();
+    }
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// `level`, refined by the comma-separated `directives` and then by
+/// `rust_log`, with the directives that didn't parse
+///
+/// Later directives win for the targets they name; one without a target
+/// replaces the level.
+fn build_filter(
+    level: LogLevel,
+    directives: Option<&str>,
+    rust_log: Option<&str>,
+) -> (EnvFilter, Vec<String>) {
+    let mut filter = EnvFilter::new(level.as_filter());
+    let mut invalid = Vec::new();
+    let directives = [directives, rust_log]
+        .into_iter()
+        .flatten()
+        .flat_map(|list| list.split(','))
+        .map(str::trim)
+        .filter(|directive| !directive.is_empty());
+    for directive in directives {
+        match directive.parse() {
+            Ok(directive) => filter = filter.add_directive(directive),
+            Err(_) => invalid.push(directive.to_string()),
+        }
+    }
+    (filter, invalid)
+}
+
+/// Build the file layer if a log file is configured
+///
+/// Unless [`LoggerConfig::file_level`] is set, the file records at least
+/// `info` so background runs leave a useful trail even when the console is
+/// quiet. Secrets are redacted before anything reaches the file.
+fn build_file_layer(
+    config: &LoggerConfig,
+    rust_log: Option<&str>,
+) -> anyhow::Result<(Option<BoxedLayer>, Vec<String>)> {
+    let path = match &config.file_path {
+        Some(path) => path,
+        None => return Ok((None, Vec::new())),
+    };
+
+    let writer = RedactingWriter::new(RotatingFileWriter::new(path, config.rotation)?);
+    let file_level = config.file_level.unwrap_or(match config.level {
+        LogLevel::Error | LogLevel::Warn => LogLevel::Info,
+        level => level,
+    });
+    let (file_filter, invalid) = build_filter(file_level, config.filter.as_deref(), rust_log);
+
+    let layer = fmt::layer()
+        .with_ansi(false)
+        .with_target(config.show_target)
+        .with_writer(move || writer.clone());
+
+    let layer = if config.file_json {
+        layer
             .json()
             .with_span_events(FmtSpan::CLOSE)
-            .with_filter(env_filter);
+            .with_filter(file_filter)
+            .boxed()
+    } else {
+        layer.with_filter(file_filter).boxed()
+    };
+    Ok((Some(layer), invalid))
+}
+
+/// Time spent in one span name
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpanTiming {
+    /// Number of spans closed
+    pub count: u64,
+    /// Total time from span creation to close
+    pub total: Duration,
+    /// Longest single span
+    pub max: Duration,
+}
+
+impl SpanTiming {
+    fn record(&mut self, elapsed: Duration) {
+        self.count += 1;
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+    }
+}
+
+/// Add one closed span to the totals
+pub fn record_span_timing(name: &'static str, elapsed: Duration) {
+    if let Ok(mut timings) = SPAN_TIMINGS.lock() {
+        timings
+            .get_or_insert_with(HashMap::new)
+            .entry(name)
+            .or_default()
+            .record(elapsed);
+    }
+}
+
+/// Collected span totals, longest total first
+#[must_use]
+pub fn span_timings() -> Vec<(&'static str, SpanTiming)> {
+    let mut timings: Vec<_> = SPAN_TIMINGS
+        .lock()
+        .ok()
+        .and_then(|timings| timings.clone())
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    timings.sort_by(|a, b| b.1.total.cmp(&a.1.total).then(a.0.cmp(b.0)));
+    timings
+}
+
+/// Table of `timings` as printed by `--debug-timings`
+#[must_use]
+pub fn format_timing_report(timings: &[(&'static str, SpanTiming)]) -> String {
+    if timings.is_empty() {
+        return "No timed operations ran\n".to_string();
+    }
+
+    let width = timings
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0)
+        .max("span".len());
+
+    let mut report = format!(
+        "{:<width$}  {:>5}  {:>10}  {:>10}\n",
+        "span", "count", "total", "max"
+    );
+    for (name, timing) in timings {
+        let _ = writeln!(
+            report,
+            "{name:<width$}  {:>5}  {:>10}  {:>10}",
+            timing.count,
+            format_duration(timing.total),
+            format_duration(timing.max)
+        );
+    }
+    report
+}
+
+/// Timing table for everything recorded so far (see [`LoggerConfig::with_timings`])
+#[must_use]
+pub fn timing_report() -> String {
+    format_timing_report(&span_timings())
+}
+
+/// Prints [`timing_report`] to stderr when dropped, however the command exits
+pub struct TimingReportGuard;
 
-        tracing_subscriber::registry().with(json_layer).init();
+impl Drop for TimingReportGuard {
+    fn drop(&mut self) {
+        eprintln!("\nTimings:\n{}", timing_report());
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let millis = duration.as_secs_f64() * 1000.0;
+    if millis >= 1000.0 {
+        format!("{:.2}s", millis / 1000.0)
     } else {
-        // Pretty formatted output
-        if config.timestamps {
-            let fmt_layer = fmt::layer()
-                .with_ansi(config.colored)
-                .with_target(config.show_target)
-                .with_span_events(FmtSpan::CLOSE)
-                .with_filter(env_filter);
-
-            tracing_subscriber::registry().with(fmt_layer).init();
-        } else {
-            let fmt_layer = fmt::layer()
-                .with_ansi(config.colored)
-                .with_target(config.show_target)
-                .with_span_events(FmtSpan::CLOSE)
-                .without_time()
-                .with_filter(env_filter);
-
-            tracing_subscriber::registry().with(fmt_layer).init();
-        }
+        format!("{millis:.1}ms")
     }
+}
 
-    tracing::debug!("Logger initialized with level: {}", config.level);
+/// Start time stored in a span's extensions
+struct SpanStart(Instant);
 
-    Ok(())
+/// Layer that times every span from creation to close
+struct TimingLayer;
+
+impl<S> Layer<S> for TimingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, _attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanStart(Instant::now()));
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(&id) {
+            if let Some(start) = span.extensions().get::<SpanStart>() {
+                record_span_timing(span.metadata().name(), start.0.elapsed());
+            }
+        }
+    }
 }
 
 /// Initialize a simple logger with default settings
@@ -205,7 +620,7 @@ pub fn logger_from_args(verbosity: u8, json: bool, no_color: bool) -> LoggerConf
         .with_color(!no_color);
 
     if json {
-        config = config.json();
+        config = config.with_format(LogFormat::Json);
     }
 
     config
@@ -241,16 +656,131 @@ mod tests {
 
         assert_eq!(config.level, LogLevel::Debug);
         assert!(!config.colored);
-        assert!(config.json);
+        assert_eq!(config.format, LogFormat::Json);
         assert_eq!(config.file_path, Some("/tmp/test.log".to_string()));
     }
 
+    #[test]
+    fn test_file_options() {
+        let config = LoggerConfig::new()
+            .with_file("/tmp/session.log")
+            .with_file_json()
+            .with_file_level(LogLevel::Debug);
+
+        assert!(config.file_json);
+        assert_eq!(config.file_level, Some(LogLevel::Debug));
+        assert_eq!(config.format, LogFormat::Plain);
+    }
+
+    #[test]
+    fn test_timing_report() {
+        let mut push = SpanTiming::default();
+        push.record(Duration::from_millis(1500));
+        push.record(Duration::from_millis(500));
+        let mut get = SpanTiming::default();
+        get.record(Duration::from_micros(2500));
+
+        let report = format_timing_report(&[("push_all", push), ("github.get", get)]);
+        let lines: Vec<&str> = report.lines().collect();
+
+        assert_eq!(push.count, 2);
+        assert_eq!(push.max, Duration::from_millis(1500));
+        assert!(lines[0].starts_with("span"));
+        assert!(lines[1].starts_with("push_all"));
+        assert!(lines[1].contains("2.00s"));
+        assert!(lines[1].ends_with("1.50s"));
+        assert!(lines[2].contains("2.5ms"));
+        assert_eq!(format_timing_report(&[]), "No timed operations ran\n");
+    }
+
     #[test]
     fn test_logger_default() {
         let config = LoggerConfig::default();
         assert_eq!(config.level, LogLevel::Info);
         assert!(config.colored);
-        assert!(!config.json);
+        assert_eq!(config.format, LogFormat::Plain);
         assert!(config.timestamps);
     }
+
+    #[test]
+    fn test_filter_layers_over_level() {
+        let enabled = |filter: EnvFilter, target: &str| {
+            let subscriber = tracing_subscriber::registry()
+                .with(fmt::layer().with_writer(std::io::sink).with_filter(filter));
+            tracing::subscriber::with_default(subscriber, || match target {
+                "providers" => {
+                    tracing::enabled!(target: "multigit::providers", tracing::Level::DEBUG)
+                }
+                _ => tracing::enabled!(target: "multigit::core", tracing::Level::DEBUG),
+            })
+        };
+
+        let config = Some("multigit::providers=debug");
+        let (filter, _) = build_filter(LogLevel::Warn, config, None);
+        assert!(enabled(filter, "providers"));
+        let (filter, _) = build_filter(LogLevel::Warn, config, None);
+        assert!(!enabled(filter, "core"));
+
+        // RUST_LOG overrides the config for the targets it names
+        let (filter, _) = build_filter(LogLevel::Warn, config, Some("multigit::providers=info"));
+        assert!(!enabled(filter, "providers"));
+        let (filter, _) = build_filter(LogLevel::Warn, None, Some("debug"));
+        assert!(enabled(filter, "core"));
+
+        let (_, invalid) = build_filter(LogLevel::Warn, Some("multigit=loud, ,info"), None);
+        assert_eq!(invalid, vec!["multigit=loud"]);
+    }
+
+    #[test]
+    fn test_log_format_parse() {
+        assert_eq!("logfmt".parse::<LogFormat>(), Ok(LogFormat::Logfmt));
+        assert_eq!("Pretty".parse::<LogFormat>(), Ok(LogFormat::Pretty));
+        assert!("xml".parse::<LogFormat>().is_err());
+        for format in [
+            LogFormat::Plain,
+            LogFormat::Json,
+            LogFormat::Compact,
+            LogFormat::Pretty,
+            LogFormat::Logfmt,
+        ] {
+            assert_eq!(format.to_string().parse::<LogFormat>(), Ok(format));
+        }
+    }
+
+    #[test]
+    fn test_logfmt_output() {
+        #[derive(Clone, Default)]
+        struct Capture(std::sync::Arc<Mutex<Vec<u8>>>);
+
+        impl std::io::Write for Capture {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let capture = Capture::default();
+        let writer = capture.clone();
+        let subscriber = tracing_subscriber::registry().with(
+            fmt::layer()
+                .event_format(Logfmt { timestamps: false })
+                .with_writer(move || writer.clone()),
+        );
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(remote = "github", commits = 2, "pushed to remote");
+        });
+
+        let output = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(
+            output,
+            "level=info target=multigit::utils::logger::tests \
+             msg=\"pushed to remote\" remote=github commits=2\n"
+        );
+        assert_eq!(logfmt_value("a\"b"), "\"a\\\"b\"");
+        assert_eq!(logfmt_value(""), "\"\"");
+    }
 }
@@ -1,10 +1,19 @@
 //! Utility modules for MultiGit
 //!
-//! This module contains utilities for error handling, logging, validation, and secret redaction.
+//! This module contains utilities for error handling, logging, validation, secret redaction,
+//! standard file locations, crash-safe file writes, TLS trust settings, paging, git
+//! remote URL parsing, and Ctrl-C handling.
 
+pub mod atomic_write;
 pub mod error;
+pub mod git_url;
+pub mod interrupt;
+pub mod log_file;
 pub mod logger;
+pub mod pager;
+pub mod paths;
 pub mod redact;
+pub mod tls;
 pub mod validation;
 
 pub use error::{MultiGitError, Result};
@@ -0,0 +1,222 @@
+//! Pager integration for long output
+//!
+//! Commands that can print more than a screenful collect their output and hand
+//! it to [`page`], which pipes it through the user's pager when stdout is a
+//! terminal and the text would not fit, and prints it directly otherwise.
+//! Commands with `-o/--output` send it to a [`Destination`] instead.
+
+use crate::ui::formatter::Status;
+use crate::utils::atomic_write::write_atomic;
+use crate::utils::error::Result;
+use std::io::{IsTerminal, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Pager used when neither `$GIT_PAGER` nor `$PAGER` is set
+pub const DEFAULT_PAGER: &str = "less -FRX";
+
+/// Cleared by `--no-pager` and `--json`
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Turn paging on or off for the rest of the process
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether paging is allowed in this process
+#[must_use]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Pager command line, following git: `$GIT_PAGER`, then `$PAGER`, then `less -FRX`
+///
+/// Returns `None` when the configured pager is empty or `cat`, which git also
+/// treats as "don't page".
+#[must_use]
+pub fn pager_command() -> Option<String> {
+    let pager = std::env::var("GIT_PAGER")
+        .or_else(|_| std::env::var("PAGER"))
+        .unwrap_or_else(|_| DEFAULT_PAGER.to_string());
+    let pager = pager.trim();
+
+    if pager.is_empty() || pager == "cat" {
+        None
+    } else {
+        Some(pager.to_string())
+    }
+}
+
+/// Whether `output` is taller than a terminal of `height` rows
+#[must_use]
+pub fn exceeds_height(output: &str, height: usize) -> bool {
+    output.lines().count() >= height
+}
+
+/// Whether `output` should be paged: paging is `enabled`, it goes to a
+/// terminal of known `height`, and it is taller than that
+fn wants_pager(enabled: bool, terminal: bool, height: usize, output: &str) -> bool {
+    enabled && terminal && height > 0 && exceeds_height(output, height)
+}
+
+/// Show `output`, through the pager if it would scroll off a terminal
+///
+/// Falls back to printing when paging is disabled, stdout is not a TTY, the
+/// terminal size is unknown, or the pager cannot be started.
+pub fn page(output: &str) -> Result<()> {
+    let height = crossterm::terminal::size().map_or(0, |(_, rows)| usize::from(rows));
+    let wanted = wants_pager(
+        is_enabled(),
+        std::io::stdout().is_terminal(),
+        height,
+        output,
+    );
+
+    match pager_command() {
+        Some(pager) if wanted => spawn_pager(&pager, output),
+        _ => {
+            print_all(output);
+            Ok(())
+        }
+    }
+}
+
+/// Page a list of lines; see [`page`]
+pub fn page_lines(lines: &[String]) -> Result<()> {
+    let mut output = lines.join("\n");
+    output.push('\n');
+    page(&output)
+}
+
+/// Where a command's output goes, as given to `-o/--output`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Destination {
+    /// The terminal, through the pager if needed (`-`)
+    #[default]
+    Stdout,
+    /// A file, replaced atomically
+    File(PathBuf),
+}
+
+impl Destination {
+    /// Whether output goes to the terminal
+    #[must_use]
+    pub fn is_stdout(&self) -> bool {
+        matches!(self, Self::Stdout)
+    }
+}
+
+impl std::str::FromStr for Destination {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim() {
+            "" => Err("Output path must not be empty; use - for stdout".to_string()),
+            "-" => Ok(Self::Stdout),
+            path => Ok(Self::File(PathBuf::from(path))),
+        }
+    }
+}
+
+/// Send `lines` to `destination`: paged on stdout, or written to a file
+///
+/// Parent directories are created, and a confirmation naming the file is printed.
+pub fn emit_lines(lines: &[String], destination: &Destination) -> Result<()> {
+    match destination {
+        Destination::Stdout => page_lines(lines),
+        Destination::File(path) => {
+            let mut output = lines.join("\n");
+            output.push('\n');
+            write_atomic(path, output)?;
+            println!(
+                "{} Output written to {}",
+                Status::Success.icon(),
+                path.display()
+            );
+            Ok(())
+        }
+    }
+}
+
+fn spawn_pager(pager: &str, output: &str) -> Result<()> {
+    let mut parts = pager.split_whitespace();
+    let program = parts.next().unwrap_or("less");
+
+    let spawned = Command::new(program)
+        .args(parts)
+        // Same hint git gives less, in case the user's pager is plain `less`
+        .env(
+            "LESS",
+            std::env::var("LESS").unwrap_or_else(|_| "FRX".to_string()),
+        )
+        .stdin(Stdio::piped())
+        .spawn();
+    let mut child = if let Ok(child) = spawned {
+        child
+    } else {
+        tracing::debug!("Could not start pager '{}', printing directly", pager);
+        print_all(output);
+        return Ok(());
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The user quitting the pager early closes the pipe; that's fine
+        let _ = stdin.write_all(output.as_bytes());
+    }
+    child.wait()?;
+    Ok(())
+}
+
+fn print_all(output: &str) {
+    let mut stdout = std::io::stdout().lock();
+    // Ignore a closed pipe, e.g. `multigit log | head`
+    let _ = stdout.write_all(output.as_bytes());
+    let _ = stdout.flush();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exceeds_height() {
+        assert!(!exceeds_height("one\ntwo\n", 3));
+        assert!(exceeds_height("one\ntwo\nthree\n", 3));
+        assert!(!exceeds_height("", 1));
+    }
+
+    #[test]
+    fn test_destination() {
+        assert_eq!("-".parse::<Destination>().unwrap(), Destination::Stdout);
+        assert_eq!(
+            "reports/status.txt".parse::<Destination>().unwrap(),
+            Destination::File(PathBuf::from("reports/status.txt"))
+        );
+        assert!("".parse::<Destination>().is_err());
+    }
+
+    #[test]
+    fn test_emit_lines_to_file() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("reports/stats.txt");
+
+        emit_lines(
+            &["one".to_string(), "two".to_string()],
+            &Destination::File(path.clone()),
+        )
+        .unwrap();
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "one\ntwo\n");
+    }
+
+    #[test]
+    fn test_wants_pager() {
+        let output = "a\nb\nc\n";
+        assert!(wants_pager(true, true, 2, output));
+        // --no-pager, a pipe, an unknown terminal size, or short output
+        assert!(!wants_pager(false, true, 2, output));
+        assert!(!wants_pager(true, false, 2, output));
+        assert!(!wants_pager(true, true, 0, output));
+        assert!(!wants_pager(true, true, 24, output));
+    }
+}
@@ -0,0 +1,125 @@
+//! Standard directory locations for runtime, state, and cache files
+//!
+//! Follows the XDG base directory layout (respecting `XDG_*` overrides on Linux):
+//! - Runtime files (PID files) go in `$XDG_RUNTIME_DIR/multigit`
+//! - State files (logs) go in `$XDG_STATE_HOME/multigit`
+//!
+//! Platforms without a runtime or state directory fall back to the next
+//! most appropriate location. Earlier versions wrote everything to the config
+//! directory; [`migrate_legacy_file`] moves those files to their new home.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+const APP_DIR: &str = "multigit";
+
+/// Directory for runtime files such as the daemon PID file
+#[must_use]
+pub fn runtime_dir() -> PathBuf {
+    dirs::runtime_dir()
+        .or_else(dirs::state_dir)
+        .or_else(dirs::cache_dir)
+        .map_or_else(fallback_dir, |dir| dir.join(APP_DIR))
+}
+
+/// Directory for persistent state such as daemon logs
+#[must_use]
+pub fn state_dir() -> PathBuf {
+    dirs::state_dir()
+        .or_else(dirs::cache_dir)
+        .map_or_else(fallback_dir, |dir| dir.join(APP_DIR))
+}
+
+/// Directory used for runtime files before the XDG layout was adopted
+#[must_use]
+pub fn legacy_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_DIR))
+}
+
+fn fallback_dir() -> PathBuf {
+    PathBuf::from(".multigit")
+}
+
+/// Move a file from its legacy location to `new_path` if needed
+///
+/// Does nothing when the legacy file is missing or the new file already
+/// exists. Returns `true` if a file was migrated.
+pub fn migrate_legacy_file(legacy_path: &Path, new_path: &Path) -> bool {
+    if legacy_path == new_path || !legacy_path.exists() || new_path.exists() {
+        return false;
+    }
+
+    match move_file(legacy_path, new_path) {
+        Ok(()) => {
+            info!(
+                "Migrated {} to {}",
+                legacy_path.display(),
+                new_path.display()
+            );
+            true
+        }
+        Err(e) => {
+            warn!("Failed to migrate {}: {}", legacy_path.display(), e);
+            false
+        }
+    }
+}
+
+/// Rename a file, falling back to copy + remove across filesystems
+fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    debug!("Rename failed, copying {} instead", from.display());
+    fs::copy(from, to)?;
+    fs::remove_file(from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_dirs_are_namespaced() {
+        assert!(runtime_dir().ends_with("multigit") || runtime_dir().ends_with(".multigit"));
+        assert!(state_dir().ends_with("multigit") || state_dir().ends_with(".multigit"));
+    }
+
+    #[test]
+    fn test_migrate_legacy_file() {
+        let temp = TempDir::new().unwrap();
+        let legacy = temp.path().join("config/daemon.log");
+        let new = temp.path().join("state/daemon.log");
+
+        fs::create_dir_all(legacy.parent().unwrap()).unwrap();
+        fs::write(&legacy, "old log").unwrap();
+
+        assert!(migrate_legacy_file(&legacy, &new));
+        assert!(!legacy.exists());
+        assert_eq!(fs::read_to_string(&new).unwrap(), "old log");
+
+        // Second run is a no-op
+        assert!(!migrate_legacy_file(&legacy, &new));
+    }
+
+    #[test]
+    fn test_migrate_does_not_overwrite() {
+        let temp = TempDir::new().unwrap();
+        let legacy = temp.path().join("old.pid");
+        let new = temp.path().join("new.pid");
+
+        fs::write(&legacy, "1").unwrap();
+        fs::write(&new, "2").unwrap();
+
+        assert!(!migrate_legacy_file(&legacy, &new));
+        assert_eq!(fs::read_to_string(&new).unwrap(), "2");
+    }
+}
@@ -0,0 +1,152 @@
+//! TLS trust settings for self-hosted instances
+//!
+//! Self-hosted servers often use certificates issued by an internal CA.
+//! `TlsOptions` adds such a CA to the trusted roots for API requests
+//! (reqwest) and git transfers (libgit2), or, for lab setups only, turns
+//! certificate verification off entirely.
+
+use crate::utils::error::{MultiGitError, Result};
+use git2::CertificateCheckStatus;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::{debug, warn};
+
+/// CA bundles already handed to libgit2 in this process
+static GIT_CA_FILES: Mutex<Option<HashSet<PathBuf>>> = Mutex::new(None);
+
+/// Certificate trust settings for one remote
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TlsOptions {
+    /// Extra PEM root certificate(s) to trust
+    pub ca_cert_path: Option<PathBuf>,
+    /// Accept any certificate; for lab environments only
+    pub insecure_skip_verify: bool,
+}
+
+impl TlsOptions {
+    /// Trust the PEM certificate(s) at `path` in addition to the system roots
+    #[must_use]
+    pub fn with_ca_cert(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ca_cert_path = Some(path.into());
+        self
+    }
+
+    /// Disable certificate verification
+    #[must_use]
+    pub fn with_insecure_skip_verify(mut self, skip: bool) -> Self {
+        self.insecure_skip_verify = skip;
+        self
+    }
+
+    /// Load the extra root certificate for reqwest, if one is configured
+    pub fn load_ca_certificate(&self) -> Result<Option<reqwest::Certificate>> {
+        let path = match self.ca_cert_path {
+            Some(ref path) => path,
+            None => return Ok(None),
+        };
+
+        let pem = std::fs::read(path).map_err(|e| {
+            MultiGitError::config(format!(
+                "Cannot read CA certificate {}: {e}",
+                path.display()
+            ))
+        })?;
+
+        reqwest::Certificate::from_pem(&pem).map(Some).map_err(|e| {
+            MultiGitError::config(format!("Invalid CA certificate {}: {e}", path.display()))
+        })
+    }
+
+    /// Make libgit2 trust the configured CA for HTTPS fetch/push
+    ///
+    /// libgit2 keeps one process-wide trust store, so each bundle is added
+    /// once and stays trusted for every remote. Call this before starting
+    /// transfers, not while other libgit2 operations are running.
+    pub fn trust_ca_for_git(&self) -> Result<()> {
+        let path = match self.ca_cert_path {
+            Some(ref path) => path,
+            None => return Ok(()),
+        };
+
+        check_readable(path)?;
+
+        let mut registered = GIT_CA_FILES
+            .lock()
+            .map_err(|_| MultiGitError::other("CA registry lock poisoned"))?;
+        let registered = registered.get_or_insert_with(HashSet::new);
+        if registered.contains(path) {
+            return Ok(());
+        }
+
+        debug!("Adding {} to libgit2 trusted certificates", path.display());
+        // SAFETY: libgit2 requires that no other libgit2 operation runs
+        // concurrently with setting SSL options. Callers register CAs before
+        // spawning transfers, and the lock above serializes registrations.
+        unsafe { git2::opts::set_ssl_cert_file(path) }.map_err(MultiGitError::GitError)?;
+
+        registered.insert(path.clone());
+        Ok(())
+    }
+
+    /// Decision for libgit2's certificate check callback
+    #[must_use]
+    pub fn certificate_check(&self, host: &str) -> CertificateCheckStatus {
+        if self.insecure_skip_verify {
+            warn!("Skipping TLS certificate verification for {}", host);
+            CertificateCheckStatus::CertificateOk
+        } else {
+            CertificateCheckStatus::CertificatePassthrough
+        }
+    }
+}
+
+fn check_readable(path: &Path) -> Result<()> {
+    std::fs::metadata(path).map(|_| ()).map_err(|e| {
+        MultiGitError::config(format!(
+            "Cannot read CA certificate {}: {e}",
+            path.display()
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_no_ca_configured() {
+        let tls = TlsOptions::default();
+        assert!(tls.load_ca_certificate().unwrap().is_none());
+        assert!(tls.trust_ca_for_git().is_ok());
+        assert!(matches!(
+            tls.certificate_check("gitea.internal"),
+            CertificateCheckStatus::CertificatePassthrough
+        ));
+    }
+
+    #[test]
+    fn test_missing_ca_file_is_config_error() {
+        let temp = TempDir::new().unwrap();
+        let tls = TlsOptions::default().with_ca_cert(temp.path().join("missing.pem"));
+
+        assert!(matches!(
+            tls.load_ca_certificate(),
+            Err(MultiGitError::ConfigError(_))
+        ));
+        assert!(matches!(
+            tls.trust_ca_for_git(),
+            Err(MultiGitError::ConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn test_insecure_accepts_any_certificate() {
+        let tls = TlsOptions::default().with_insecure_skip_verify(true);
+        assert!(matches!(
+            tls.certificate_check("lab.local"),
+            CertificateCheckStatus::CertificateOk
+        ));
+    }
+}
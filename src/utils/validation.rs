@@ -95,6 +95,65 @@ pub fn validate_branch_name(branch: &str) -> Result<()> {
     Ok(())
 }
 
+/// Validate a fetch refspec: `[+]<src>[:<dst>]`
+///
+/// `src` is a branch name, `HEAD` or a full ref such as `refs/pull/123/head`;
+/// `dst` is a full ref. A `*` glob must appear on both sides or neither.
+pub fn validate_refspec(refspec: &str) -> Result<()> {
+    let invalid = |reason: &str| {
+        MultiGitError::invalid_input(format!(
+            "Invalid refspec '{refspec}': {reason}. Examples: main, refs/pull/123/head, \
+             refs/heads/main:refs/remotes/origin/main"
+        ))
+    };
+
+    let spec = refspec.strip_prefix('+').unwrap_or(refspec);
+    let (src, dst) = match spec.split_once(':') {
+        Some((src, dst)) => (src, Some(dst)),
+        None => (spec, None),
+    };
+
+    if src.is_empty() {
+        return Err(invalid("the source ref is empty"));
+    }
+    if dst.is_some_and(|dst| dst.contains(':')) {
+        return Err(invalid("use a single ':' between source and destination"));
+    }
+
+    let globs = |side: &str| side.matches('*').count();
+    if globs(src) > 1 || dst.is_some_and(|dst| globs(dst) > 1) {
+        return Err(invalid("use at most one '*' per side"));
+    }
+    if let Some(dst) = dst {
+        if (globs(src) == 1) != (globs(dst) == 1) {
+            return Err(invalid("a '*' in one side needs a '*' in the other"));
+        }
+    }
+
+    // Check the names git would store, with any glob filled in
+    let is_valid = |name: &str, full_only: bool| {
+        let name = name.replace('*', "x");
+        if name == "HEAD" || name.starts_with("refs/") {
+            git2::Reference::is_valid_name(&name)
+        } else {
+            !full_only && git2::Reference::is_valid_name(&format!("refs/heads/{name}"))
+        }
+    };
+
+    if !is_valid(src, false) {
+        return Err(invalid(&format!("'{src}' is not a valid ref name")));
+    }
+    if let Some(dst) = dst.filter(|dst| !dst.is_empty()) {
+        if !is_valid(dst, true) {
+            return Err(invalid(&format!(
+                "'{dst}' is not a full ref name (refs/...)"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
 /// Validate a URL
 pub fn validate_url(url_str: &str) -> Result<()> {
     url::Url::parse(url_str)
@@ -216,6 +275,23 @@ mod tests {
         assert!(validate_branch_name("branch*name").is_err());
     }
 
+    #[test]
+    fn test_validate_refspec() {
+        assert!(validate_refspec("main").is_ok());
+        assert!(validate_refspec("refs/pull/123/head").is_ok());
+        assert!(validate_refspec("+refs/heads/*:refs/remotes/origin/*").is_ok());
+        assert!(validate_refspec("refs/heads/main:refs/remotes/origin/main").is_ok());
+        assert!(validate_refspec("HEAD").is_ok());
+
+        assert!(validate_refspec("").is_err());
+        assert!(validate_refspec(":refs/heads/main").is_err());
+        assert!(validate_refspec("a:b:c").is_err());
+        assert!(validate_refspec("refs/heads/*:refs/remotes/origin/main").is_err());
+        assert!(validate_refspec("main:other").is_err());
+        let err = validate_refspec("feature..x").unwrap_err().to_string();
+        assert!(err.contains("Examples"));
+    }
+
     #[test]
     fn test_validate_url() {
         assert!(validate_url("https://github.com").is_ok());
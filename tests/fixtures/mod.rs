@@ -11,11 +11,8 @@ pub fn create_test_config() -> Config {
         "github".to_string(),
         RemoteConfig {
             username: "testuser".to_string(),
-            api_url: None,
-            enabled: true,
             provider: Some("github".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         },
     );
 
@@ -24,10 +21,9 @@ pub fn create_test_config() -> Config {
         RemoteConfig {
             username: "testuser".to_string(),
             api_url: Some("https://gitlab.com".to_string()),
-            enabled: true,
             provider: Some("gitlab".to_string()),
-            use_ssh: false,
             priority: 1,
+            ..Default::default()
         },
     );
 
@@ -42,11 +38,8 @@ pub fn create_test_config_with_disabled_remotes() -> Config {
         "enabled".to_string(),
         RemoteConfig {
             username: "user1".to_string(),
-            api_url: None,
-            enabled: true,
             provider: Some("github".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         },
     );
 
@@ -54,11 +47,9 @@ pub fn create_test_config_with_disabled_remotes() -> Config {
         "disabled".to_string(),
         RemoteConfig {
             username: "user2".to_string(),
-            api_url: None,
             enabled: false,
             provider: Some("gitlab".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         },
     );
 
@@ -142,6 +133,104 @@ pub fn setup_test_repo(temp_dir: &std::path::Path) -> multigit::utils::error::Re
     Ok(())
 }
 
+/// A working repository wired to a bare repository acting as its remote
+///
+/// Everything lives in one temp dir, so push and fetch can be exercised
+/// end-to-end without network access:
+/// - `work`: non-bare repository with one initial commit
+/// - `remote`: bare repository, added to `work` as `remote_name`
+pub struct BareRemoteFixture {
+    /// Owns the temp dir; dropped with the fixture
+    pub dir: tempfile::TempDir,
+    /// Path of the working repository
+    pub work: std::path::PathBuf,
+    /// Path of the bare remote repository
+    pub remote: std::path::PathBuf,
+    /// Name of the remote in the working repository
+    pub remote_name: String,
+    /// Branch of the initial commit
+    pub branch: String,
+}
+
+impl BareRemoteFixture {
+    /// Create a working repo with an initial commit and a bare remote named `remote_name`
+    pub fn new(remote_name: &str) -> Self {
+        use multigit::git::operations::GitOperations;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let work = dir.path().join("work");
+        let remote = dir.path().join("remote.git");
+
+        git2::Repository::init_bare(&remote).unwrap();
+        let git_ops = GitOperations::init(&work).unwrap();
+        git_ops
+            .add_remote(remote_name, remote.to_str().unwrap())
+            .unwrap();
+
+        commit_file(&work, "README.md", "# Test Repository\n", "Initial commit");
+        let branch = git_ops.current_branch().unwrap();
+
+        Self {
+            dir,
+            work,
+            remote,
+            remote_name: remote_name.to_string(),
+            branch,
+        }
+    }
+
+    /// Commit a file directly on the remote, as another contributor would
+    ///
+    /// Clones the bare remote into a scratch dir, commits, and pushes back.
+    pub fn commit_on_remote(&self, file: &str, content: &str, message: &str) -> git2::Oid {
+        static CLONES: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        let n = CLONES.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        let scratch = self.dir.path().join(format!("scratch-{n}"));
+        let repo = git2::Repository::clone(self.remote.to_str().unwrap(), &scratch).unwrap();
+
+        let oid = commit_file(&scratch, file, content, message);
+
+        let refspec = format!("refs/heads/{0}:refs/heads/{0}", self.branch);
+        repo.find_remote("origin")
+            .unwrap()
+            .push(&[refspec.as_str()], None)
+            .unwrap();
+        oid
+    }
+
+    /// Commit id of `branch` in the bare remote, if it exists
+    pub fn remote_head(&self) -> Option<git2::Oid> {
+        let repo = git2::Repository::open_bare(&self.remote).unwrap();
+        repo.refname_to_id(&format!("refs/heads/{}", self.branch))
+            .ok()
+    }
+}
+
+/// Write `file` in the repository at `repo_path` and commit it on HEAD
+///
+/// Uses a fixed signature so tests don't depend on the user's git config.
+pub fn commit_file(
+    repo_path: &std::path::Path,
+    file: &str,
+    content: &str,
+    message: &str,
+) -> git2::Oid {
+    std::fs::write(repo_path.join(file), content).unwrap();
+
+    let repo = git2::Repository::open(repo_path).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(std::path::Path::new(file)).unwrap();
+    index.write().unwrap();
+
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = git2::Signature::now("MultiGit Test", "test@multigit.dev").unwrap();
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit<'_>> = parent.iter().collect();
+
+    repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+        .unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,6 +269,14 @@ mod tests {
         assert_eq!(repos[4].name, "repo4");
     }
 
+    #[test]
+    fn test_bare_remote_fixture() {
+        let fixture = BareRemoteFixture::new("origin");
+        assert!(fixture.work.join(".git").exists());
+        assert!(fixture.remote.join("HEAD").exists());
+        assert!(fixture.remote_head().is_none());
+    }
+
     #[test]
     fn test_create_test_config_toml() {
         let toml = create_test_config_toml();
@@ -40,11 +40,8 @@ fn test_config_remote_management() {
 
     let remote = RemoteConfig {
         username: "testuser".to_string(),
-        api_url: None,
-        enabled: true,
         provider: Some("github".to_string()),
-        use_ssh: false,
-        priority: 0,
+        ..Default::default()
     };
 
     config.add_remote("github".to_string(), remote);
@@ -64,11 +61,8 @@ fn test_enabled_remotes_filter() {
         "github".to_string(),
         RemoteConfig {
             username: "user".to_string(),
-            api_url: None,
-            enabled: true,
             provider: Some("github".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         },
     );
 
@@ -76,11 +70,9 @@ fn test_enabled_remotes_filter() {
         "gitlab".to_string(),
         RemoteConfig {
             username: "user".to_string(),
-            api_url: None,
             enabled: false,
             provider: Some("gitlab".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         },
     );
 
@@ -210,3 +202,759 @@ fn test_is_healthy() {
     // Note: The remote will be unreachable since it's a test URL
     // In tests, we don't fail the health check just because a test remote is unreachable
 }
+
+/// Run the `multigit` binary in `dir` with config and state kept inside it
+fn run_multigit(dir: &std::path::Path, args: &[&str]) -> String {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_multigit"))
+        .args(args)
+        .current_dir(dir)
+        .env("HOME", dir)
+        .env("XDG_CONFIG_HOME", dir.join(".config"))
+        .env("XDG_STATE_HOME", dir.join(".state"))
+        .env("XDG_CACHE_HOME", dir.join(".cache"))
+        .env("XDG_RUNTIME_DIR", dir.join(".run"))
+        .env("LC_ALL", "C.UTF-8")
+        .env_remove("TERM")
+        .env_remove("MULTIGIT_PROFILE")
+        .output()
+        .unwrap();
+    format!(
+        "{}{}",
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap()
+    )
+}
+
+#[test]
+fn test_remote_and_conflict_output_has_no_mojibake() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    let repo = Repository::init(path).unwrap();
+    {
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial", &tree, &[])
+            .unwrap();
+    }
+    repo.remote("github", "https://github.com/test/test.git")
+        .unwrap();
+    std::fs::create_dir(path.join(".multigit")).unwrap();
+    std::fs::write(
+        path.join(".multigit/config.toml"),
+        "[remotes.github]\nusername = \"test\"\nprovider = \"github\"\n\n\
+         [remotes.gitlab]\nusername = \"test\"\nprovider = \"gitlab\"\nenabled = false\n",
+    )
+    .unwrap();
+
+    for args in [
+        &["remote", "list"][..],
+        &["--no-color", "remote", "list"],
+        &["conflict", "list"],
+    ] {
+        let output = run_multigit(path, args);
+        assert!(output.contains("github"), "{args:?}: {output}");
+        // UTF-8 emoji decoded as Latin-1/Windows-1252 start with these
+        for garbage in ['\u{c2}', '\u{c3}', '\u{e2}', '\u{f0}', '\u{fffd}'] {
+            assert!(
+                !output.contains(garbage),
+                "{args:?} printed mojibake ({garbage:?}): {output}"
+            );
+        }
+    }
+
+    assert!(run_multigit(path, &["--no-color", "remote", "list"]).contains("✓ github"));
+}
+
+#[test]
+fn test_ascii_icons() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    let repo = Repository::init(path).unwrap();
+    repo.remote("github", "https://github.com/test/test.git")
+        .unwrap();
+    std::fs::create_dir(path.join(".multigit")).unwrap();
+    let remotes = "[remotes.github]\nusername = \"test\"\nprovider = \"github\"\n";
+    std::fs::write(path.join(".multigit/config.toml"), remotes).unwrap();
+
+    let output = run_multigit(path, &["--ascii", "--no-color", "remote", "list"]);
+    assert!(output.contains("[ok] github"), "{output}");
+    assert!(!output.contains('✓'), "{output}");
+
+    // settings.icons picks the style without the flag
+    std::fs::write(
+        path.join(".multigit/config.toml"),
+        format!("[settings]\nicons = \"ascii\"\n\n{remotes}"),
+    )
+    .unwrap();
+    let output = run_multigit(path, &["--no-color", "remote", "list"]);
+    assert!(output.contains("[ok] github"), "{output}");
+}
+
+#[test]
+fn test_init_from_copies_remotes() {
+    let temp_dir = TempDir::new().unwrap();
+    let source = temp_dir.path().join("source");
+    std::fs::create_dir_all(source.join(".multigit")).unwrap();
+    std::fs::write(
+        source.join(".multigit/config.toml"),
+        "[remotes.github]\nusername = \"test\"\nprovider = \"github\"\n",
+    )
+    .unwrap();
+    let target = temp_dir.path().join("target");
+    Repository::init(&target).unwrap();
+
+    let output = run_multigit(
+        &target,
+        &[
+            "init",
+            "--no-interactive",
+            "--from",
+            source.to_str().unwrap(),
+        ],
+    );
+    assert!(output.contains("Copied 1 remote(s)"), "{output}");
+
+    let config = Config::load_from_file(&target.join(".multigit/config.toml")).unwrap();
+    assert_eq!(config.remotes["github"].username, "test");
+
+    let missing = run_multigit(&target, &["init", "--from", "nowhere"]);
+    assert!(missing.contains("No MultiGit config found"), "{missing}");
+}
+
+#[test]
+fn test_template_remotes_are_inherited_and_snapshot_by_init() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    Repository::init(path).unwrap();
+    std::fs::create_dir_all(path.join(".config/multigit")).unwrap();
+    std::fs::write(
+        path.join(".config/multigit/template.toml"),
+        "[remotes.codeberg]\nusername = \"shared\"\nprovider = \"codeberg\"\n",
+    )
+    .unwrap();
+
+    let listed = run_multigit(path, &["remote", "list"]);
+    assert!(listed.contains("codeberg"), "{listed}");
+
+    let output = run_multigit(path, &["init"]);
+    assert!(output.contains("Using template"), "{output}");
+    let config = Config::load_from_file(&path.join(".multigit/config.toml")).unwrap();
+    assert_eq!(config.remotes["codeberg"].username, "shared");
+}
+
+/// A repository under `dir/work` with one commit and a `github` remote that is
+/// the bare repository `dir/mirror.git`; `extra_config` is appended to its
+/// `.multigit/config.toml`
+fn repo_with_bare_mirror(
+    dir: &std::path::Path,
+    extra_config: &str,
+) -> (std::path::PathBuf, Repository) {
+    let path = dir.join("work");
+    let bare = dir.join("mirror.git");
+    Repository::init_bare(&bare).unwrap();
+    let repo = Repository::init(&path).unwrap();
+    {
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial", &tree, &[])
+            .unwrap();
+    }
+    repo.remote("github", bare.to_str().unwrap()).unwrap();
+    std::fs::create_dir(path.join(".multigit")).unwrap();
+    std::fs::write(
+        path.join(".multigit/config.toml"),
+        format!("[remotes.github]\nusername = \"test\"\nprovider = \"github\"\n\n{extra_config}"),
+    )
+    .unwrap();
+    (path, repo)
+}
+
+#[test]
+fn test_push_sets_upstream_of_new_branch() {
+    let temp_dir = TempDir::new().unwrap();
+    let (path, repo) = repo_with_bare_mirror(temp_dir.path(), "");
+    let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+    let upstream = |repo: &Repository| {
+        repo.find_branch(&branch, git2::BranchType::Local)
+            .unwrap()
+            .upstream()
+            .ok()
+            .and_then(|b| b.name().unwrap().map(String::from))
+    };
+
+    let output = run_multigit(&path, &["push", "--include-archived", "--no-set-upstream"]);
+    assert!(output.contains("1 succeeded"), "{output}");
+    assert_eq!(upstream(&repo), None);
+
+    let output = run_multigit(&path, &["push", "--include-archived"]);
+    assert!(output.contains("now tracks github/"), "{output}");
+    assert_eq!(upstream(&repo), Some(format!("github/{branch}")));
+}
+
+#[test]
+fn test_push_rejected_as_diverged_until_forced() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("work");
+    let bare = temp_dir.path().join("mirror.git");
+    Repository::init_bare(&bare).unwrap();
+    let repo = Repository::init(&path).unwrap();
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let tree_id = repo.index().unwrap().write_tree().unwrap();
+    let tree = repo.find_tree(tree_id).unwrap();
+    let first = repo
+        .commit(Some("HEAD"), &sig, &sig, "Initial", &tree, &[])
+        .unwrap();
+    repo.remote("github", bare.to_str().unwrap()).unwrap();
+    std::fs::create_dir(path.join(".multigit")).unwrap();
+    std::fs::write(
+        path.join(".multigit/config.toml"),
+        "[remotes.github]\nusername = \"test\"\nprovider = \"github\"\n",
+    )
+    .unwrap();
+    let output = run_multigit(&path, &["push", "--include-archived"]);
+    assert!(output.contains("1 succeeded"), "{output}");
+
+    // Rewrite the pushed commit so the remote branch has diverged
+    let branch = repo.head().unwrap().name().unwrap().to_string();
+    let rewritten = repo
+        .commit(None, &sig, &sig, "Rewritten", &tree, &[])
+        .unwrap();
+    repo.reference(&branch, rewritten, true, "rewrite").unwrap();
+    assert_ne!(first, rewritten);
+
+    let output = run_multigit(&path, &["push", "--include-archived"]);
+    assert!(output.contains("diverged"), "{output}");
+    assert!(output.contains("1 failed"), "{output}");
+    let remote = Repository::open_bare(&bare).unwrap();
+    assert_eq!(remote.refname_to_id(&branch).unwrap(), first);
+
+    let output = run_multigit(&path, &["push", "--include-archived", "--force"]);
+    assert!(output.contains("1 succeeded"), "{output}");
+    assert_eq!(remote.refname_to_id(&branch).unwrap(), rewritten);
+}
+
+#[test]
+fn test_push_tags_reports_created_and_already_present() {
+    let temp_dir = TempDir::new().unwrap();
+    let (path, repo) = repo_with_bare_mirror(temp_dir.path(), "");
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    repo.tag("v1.0", head.as_object(), &sig, "Release 1.0", false)
+        .unwrap();
+    repo.tag_lightweight("scratch", head.as_object(), false)
+        .unwrap();
+
+    let output = run_multigit(&path, &["push", "--include-archived", "--follow-tags"]);
+    assert!(output.contains("1 succeeded"), "{output}");
+    assert!(output.contains("github - created: v1.0"), "{output}");
+    let mirror = Repository::open_bare(temp_dir.path().join("mirror.git")).unwrap();
+    assert!(mirror.find_reference("refs/tags/scratch").is_err());
+
+    let output = run_multigit(&path, &["push", "--include-archived", "--tags-only"]);
+    assert!(output.contains("Pushing 2 tag(s)"), "{output}");
+    assert!(
+        output.contains("github - created: scratch; already present: v1.0"),
+        "{output}"
+    );
+    assert_eq!(
+        mirror
+            .find_reference("refs/tags/v1.0")
+            .unwrap()
+            .peel_to_tag()
+            .unwrap()
+            .message(),
+        Some("Release 1.0")
+    );
+}
+
+#[test]
+fn test_pull_and_sync_autostash_keep_local_changes() {
+    let temp_dir = TempDir::new().unwrap();
+    let (path, repo) = repo_with_bare_mirror(temp_dir.path(), "");
+    let first = repo.head().unwrap().peel_to_commit().unwrap();
+    std::fs::write(path.join("upstream.txt"), "upstream\n").unwrap();
+    let mut index = repo.index().unwrap();
+    index
+        .add_path(std::path::Path::new("upstream.txt"))
+        .unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "Upstream", &tree, &[&first])
+        .unwrap();
+    let output = run_multigit(&path, &["push", "--include-archived"]);
+    assert!(output.contains("1 succeeded"), "{output}");
+    repo.reset(first.as_object(), git2::ResetType::Hard, None)
+        .unwrap();
+    std::fs::write(path.join("notes.txt"), "local\n").unwrap();
+
+    let output = run_multigit(&path, &["pull", "--from", "github"]);
+    assert!(output.contains("uncommitted changes"), "{output}");
+    assert!(!path.join("upstream.txt").exists());
+
+    let output = run_multigit(&path, &["pull", "--from", "github", "--autostash"]);
+    assert!(output.contains("Fast-forward merge successful"), "{output}");
+    assert!(
+        output.contains("Restored stashed local changes"),
+        "{output}"
+    );
+    assert!(path.join("upstream.txt").exists());
+    assert_eq!(
+        std::fs::read_to_string(path.join("notes.txt")).unwrap(),
+        "local\n"
+    );
+
+    let output = run_multigit(&path, &["sync", "--include-archived", "--autostash"]);
+    assert!(output.contains("Sync complete"), "{output}");
+    assert!(path.join("notes.txt").exists());
+    let mut repo = Repository::open(&path).unwrap();
+    let mut stashes = 0;
+    repo.stash_foreach(|_, _, _| {
+        stashes += 1;
+        true
+    })
+    .unwrap();
+    assert_eq!(stashes, 0);
+}
+
+#[test]
+fn test_routing_keeps_denied_branches_off_a_remote() {
+    let temp_dir = TempDir::new().unwrap();
+    let (path, repo) = repo_with_bare_mirror(
+        temp_dir.path(),
+        "[remotes.public]\nusername = \"test\"\nprovider = \"gitlab\"\n\n\
+         [routing.\"internal/**\"]\ndeny = [\"public\"]\n",
+    );
+    let public = temp_dir.path().join("public.git");
+    Repository::init_bare(&public).unwrap();
+    repo.remote("public", public.to_str().unwrap()).unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.branch("internal/plan", &head, false).unwrap();
+
+    let output = run_multigit(&path, &["routing", "test", "internal/plan"]);
+    assert!(output.contains("denied by \"internal/**\""), "{output}");
+    assert!(output.contains("pushed to 1 remote(s)"), "{output}");
+
+    let output = run_multigit(
+        &path,
+        &["push", "--include-archived", "--branch", "internal/plan"],
+    );
+    assert!(
+        output.contains("public - skipping 'internal/plan'"),
+        "{output}"
+    );
+    assert!(output.contains("1 succeeded"), "{output}");
+    let mirror = Repository::open_bare(temp_dir.path().join("mirror.git")).unwrap();
+    assert!(mirror.find_reference("refs/heads/internal/plan").is_ok());
+    let public = Repository::open_bare(&public).unwrap();
+    assert!(public.find_reference("refs/heads/internal/plan").is_err());
+
+    let output = run_multigit(
+        &path,
+        &[
+            "push",
+            "--include-archived",
+            "--branch",
+            "internal/plan",
+            "--remotes",
+            "public",
+        ],
+    );
+    assert!(output.contains("Routing rules keep"), "{output}");
+}
+
+#[test]
+fn test_push_is_audited_and_queryable() {
+    let temp_dir = TempDir::new().unwrap();
+    let (path, _repo) = repo_with_bare_mirror(temp_dir.path(), "[security]\naudit_log = true\n");
+
+    let output = run_multigit(&path, &["push", "--include-archived"]);
+    assert!(output.contains("1 succeeded"), "{output}");
+
+    let output = run_multigit(&path, &["audit", "--event", "push", "--json"]);
+    let entries: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(entries.as_array().unwrap().len(), 1, "{output}");
+    assert_eq!(entries[0]["event_type"], "Push");
+    assert_eq!(entries[0]["user"], "test");
+    assert!(entries[0]["resource"]
+        .as_str()
+        .unwrap()
+        .starts_with("github:"));
+
+    let output = run_multigit(&path, &["audit", "--event", "sync"]);
+    assert!(output.contains("No matching audit entries"), "{output}");
+}
+
+#[test]
+fn test_audit_flags_override_config() {
+    let audited = |path: &std::path::Path| {
+        let output = run_multigit(path, &["--json", "audit", "--event", "push"]);
+        serde_json::from_str::<serde_json::Value>(&output)
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .len()
+    };
+
+    let temp_dir = TempDir::new().unwrap();
+    let (path, _repo) = repo_with_bare_mirror(temp_dir.path(), "[security]\naudit_log = true\n");
+    run_multigit(&path, &["--no-audit", "push", "--include-archived"]);
+    assert_eq!(audited(&path), 0);
+
+    let temp_dir = TempDir::new().unwrap();
+    let (path, _repo) = repo_with_bare_mirror(temp_dir.path(), "");
+    run_multigit(&path, &["push", "--include-archived"]);
+    assert_eq!(audited(&path), 0);
+    run_multigit(&path, &["--audit", "push", "--include-archived"]);
+    assert_eq!(audited(&path), 1);
+}
+
+#[test]
+fn test_auth_rotate_skips_remotes_without_a_new_token() {
+    let temp_dir = TempDir::new().unwrap();
+    let (path, _repo) = repo_with_bare_mirror(temp_dir.path(), "");
+
+    let output = run_multigit(&path, &["auth", "rotate", "--no-interactive"]);
+    assert!(
+        output.contains("skipped: set MULTIGIT_GITHUB_TOKEN"),
+        "{output}"
+    );
+    assert!(output.contains("Rotated 0 of 1 account(s)"), "{output}");
+
+    let output = run_multigit(&path, &["auth", "rotate", "gitlab", "--no-interactive"]);
+    assert!(output.contains("Remote 'gitlab' not found"), "{output}");
+}
+
+#[test]
+fn test_auth_migrate_rejects_unusable_backends() {
+    let temp_dir = TempDir::new().unwrap();
+    let (path, _repo) = repo_with_bare_mirror(temp_dir.path(), "");
+
+    let output = run_multigit(
+        &path,
+        &["auth", "migrate", "--from", "keyring", "--to", "keyring"],
+    );
+    assert!(
+        output.contains("already in the keyring backend"),
+        "{output}"
+    );
+
+    let output = run_multigit(
+        &path,
+        &[
+            "auth",
+            "migrate",
+            "--from",
+            "encrypted",
+            "--to",
+            "keyring",
+            "--no-interactive",
+        ],
+    );
+    assert!(output.contains("Set MULTIGIT_STORE_PASSPHRASE"), "{output}");
+}
+
+#[test]
+fn test_validate_reports_every_failure_and_exits_non_zero() {
+    let temp_dir = TempDir::new().unwrap();
+    let (path, _repo) = repo_with_bare_mirror(
+        temp_dir.path(),
+        "[remotes.gitea]\nusername = \"test\"\nprovider = \"gitea\"\n",
+    );
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_multigit"))
+        .args(["--no-color", "validate"])
+        .current_dir(&path)
+        .env("HOME", &path)
+        .env("XDG_CONFIG_HOME", path.join(".config"))
+        .env("XDG_STATE_HOME", path.join(".state"))
+        .env("XDG_CACHE_HOME", path.join(".cache"))
+        .env("LC_ALL", "C.UTF-8")
+        .env_remove("TERM")
+        .env_remove("MULTIGIT_PROFILE")
+        .env_remove("MULTIGIT_GITHUB_TOKEN")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!output.status.success(), "{stdout}");
+    assert!(stdout.contains("Gitea requires an API URL"), "{stdout}");
+    assert!(stdout.contains("✓  branch"), "{stdout}");
+    assert!(stdout.contains("no credentials stored"), "{stdout}");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Validation failed"), "{stderr}");
+
+    let json = run_multigit(&path, &["validate", "--json"]);
+    assert!(json.contains("\"passed\": false"), "{json}");
+}
+
+#[test]
+fn test_release_updates_changelog_tags_and_pushes() {
+    let temp_dir = TempDir::new().unwrap();
+    let (path, repo) = repo_with_bare_mirror(temp_dir.path(), "");
+    {
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &sig,
+            &sig,
+            "feat: export",
+            &head.tree().unwrap(),
+            &[&head],
+        )
+        .unwrap();
+    }
+    // The config is committed so the tree is clean
+    std::fs::write(path.join(".gitignore"), ".multigit/\n").unwrap();
+    let output = run_multigit(&path, &["release", "v1.0.0"]);
+    assert!(output.contains("uncommitted changes"), "{output}");
+    {
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new(".gitignore")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "chore: ignore", &tree, &[&head])
+            .unwrap();
+    }
+
+    let output = run_multigit(&path, &["release", "v1.0.0", "--format", "keepachangelog"]);
+    assert!(output.contains("Released v1.0.0"), "{output}");
+    let changelog = std::fs::read_to_string(path.join("CHANGELOG.md")).unwrap();
+    assert!(changelog.contains("## [1.0.0] - "), "{changelog}");
+    assert!(changelog.contains("- export"), "{changelog}");
+
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.summary(), Some("chore(release): v1.0.0"));
+    let mirror = Repository::open_bare(temp_dir.path().join("mirror.git")).unwrap();
+    let tag = mirror
+        .find_reference("refs/tags/v1.0.0")
+        .unwrap()
+        .peel_to_commit()
+        .unwrap();
+    assert_eq!(tag.id(), head.id());
+
+    let output = run_multigit(&path, &["release", "v1.0.0"]);
+    assert!(output.contains("already exists"), "{output}");
+}
+
+#[test]
+fn test_commit_works_without_the_git_binary() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    let repo = Repository::init(path).unwrap();
+    std::fs::write(path.join("notes.txt"), "first").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(std::path::Path::new("notes.txt")).unwrap();
+    index.write().unwrap();
+
+    // An empty PATH hides the git binary
+    let commit = |args: &[&str]| {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_multigit"))
+            .args(args)
+            .current_dir(path)
+            .env("HOME", path)
+            .env("PATH", "")
+            .output()
+            .unwrap();
+        format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        )
+    };
+
+    let output = commit(&["commit", "-m", "Add notes"]);
+    assert!(output.contains("Add notes"), "{output}");
+    assert_eq!(
+        repo.head().unwrap().peel_to_commit().unwrap().message(),
+        Some("Add notes")
+    );
+
+    std::fs::write(path.join("notes.txt"), "second").unwrap();
+    commit(&["commit", "-a", "-m", "Update notes"]);
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.message(), Some("Update notes"));
+    assert_eq!(head.parent_count(), 1);
+
+    let output = commit(&["commit", "-m", "Signed"]);
+    assert!(output.contains("Nothing to commit"), "{output}");
+    repo.config()
+        .unwrap()
+        .set_bool("commit.gpgsign", true)
+        .unwrap();
+    std::fs::write(path.join("notes.txt"), "third").unwrap();
+    let output = commit(&["commit", "-a", "-m", "Signed"]);
+    assert!(output.contains("git binary is needed"), "{output}");
+}
+
+#[test]
+fn test_commit_author_and_date_overrides() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    let repo = Repository::init(path).unwrap();
+    std::fs::write(path.join("notes.txt"), "first").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(std::path::Path::new("notes.txt")).unwrap();
+    index.write().unwrap();
+
+    let output = run_multigit(
+        path,
+        &[
+            "commit",
+            "-m",
+            "Imported",
+            "--author",
+            "Jane Doe <jane@example.com>",
+            "--date",
+            "2024-05-01T12:00:00+02:00",
+        ],
+    );
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.message(), Some("Imported"), "{output}");
+    assert_eq!(head.author().name(), Some("Jane Doe"));
+    assert_eq!(head.author().when().seconds(), 1_714_557_600);
+    assert_eq!(head.author().when().offset_minutes(), 120);
+
+    let output = run_multigit(path, &["commit", "--amend", "-m", "x", "--date", "soon"]);
+    assert!(output.contains("Invalid date 'soon'"), "{output}");
+}
+
+#[test]
+fn test_daemon_dry_run_previews_schedule() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+    Repository::init(path).unwrap();
+
+    let output = run_multigit(
+        path,
+        &[
+            "daemon",
+            "start",
+            "--dry-run",
+            "--cron",
+            "30 2 * * *",
+            "--runs",
+            "3",
+        ],
+    );
+    assert!(output.contains("cron '30 2 * * *'"), "{output}");
+    assert_eq!(output.matches("02:30:00").count(), 3, "{output}");
+    assert!(output.contains("none; add one"), "{output}");
+
+    let output = run_multigit(
+        path,
+        &["daemon", "start", "--dry-run", "--cron", "61 * * * *"],
+    );
+    assert!(output.contains("Invalid cron expression"), "{output}");
+}
+
+#[test]
+fn test_daemon_run_once_syncs_and_records_status() {
+    let temp_dir = TempDir::new().unwrap();
+    let (path, repo) = repo_with_bare_mirror(temp_dir.path(), "");
+    std::fs::write(
+        path.join(".git/info/exclude"),
+        ".multigit\n.config\n.state\n.cache\n.run\n",
+    )
+    .unwrap();
+    let run_once = || {
+        std::process::Command::new(env!("CARGO_BIN_EXE_multigit"))
+            .args(["daemon", "run-once"])
+            .current_dir(&path)
+            .env("HOME", &path)
+            .env("XDG_CONFIG_HOME", path.join(".config"))
+            .env("XDG_STATE_HOME", path.join(".state"))
+            .env("XDG_CACHE_HOME", path.join(".cache"))
+            .env("XDG_RUNTIME_DIR", path.join(".run"))
+            .env_remove("MULTIGIT_PROFILE")
+            .output()
+            .unwrap()
+    };
+
+    let output = run_once();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("Daemon run finished"), "{stdout}");
+    let mirror = Repository::open_bare(temp_dir.path().join("mirror.git")).unwrap();
+    assert_eq!(mirror.branches(None).unwrap().count(), 1);
+
+    let status = run_multigit(&path, &["daemon", "status", "--json"]);
+    assert!(status.contains("\"last_sync\""), "{status}");
+
+    repo.remote_set_url("github", "/nonexistent/mirror.git")
+        .unwrap();
+    let output = run_once();
+    assert!(!output.status.success());
+    let status = run_multigit(&path, &["daemon", "status", "--json"]);
+    assert!(status.contains("\"success\": false"), "{status}");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_daemon_install_service_print_only() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("webapp");
+    Repository::init(&path).unwrap();
+
+    let output = run_multigit(
+        &path,
+        &[
+            "daemon",
+            "install-service",
+            "--cron",
+            "0 2 * * 1-5",
+            "--print-only",
+        ],
+    );
+    let timer = output
+        .split_whitespace()
+        .find(|word| word.ends_with(".timer"))
+        .unwrap_or_default();
+    assert!(timer.contains("multigit-sync-webapp-"), "{output}");
+    assert!(
+        output.contains("OnCalendar=Mon..Fri *-*-* 02:00:00"),
+        "{output}"
+    );
+    assert!(output.contains("daemon run-once"), "{output}");
+    assert!(output.contains("systemctl --user enable --now"), "{output}");
+    assert!(!path.join(".config/systemd").exists());
+}
+
+#[test]
+fn test_mutating_commands_respect_the_repository_lock() {
+    use fs2::FileExt;
+    use std::io::Write;
+
+    let temp_dir = TempDir::new().unwrap();
+    let (path, _repo) = repo_with_bare_mirror(temp_dir.path(), "");
+
+    // Another multigit process holding the lock
+    let mut holder = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(true)
+        .write(true)
+        .open(path.join(".multigit/lock"))
+        .unwrap();
+    FileExt::lock_exclusive(&holder).unwrap();
+    writeln!(holder, "4242 multigit sync").unwrap();
+
+    let output = run_multigit(&path, &["push", "--include-archived"]);
+    assert!(
+        output.contains("locked by 'multigit sync' (pid 4242)"),
+        "{output}"
+    );
+    let output = run_multigit(&path, &["--lock-timeout", "1", "pull", "--from", "github"]);
+    assert!(output.contains("after waiting 1s"), "{output}");
+
+    // Read-only commands don't need it
+    let output = run_multigit(&path, &["status"]);
+    assert!(!output.contains("locked"), "{output}");
+
+    FileExt::unlock(&holder).unwrap();
+    let output = run_multigit(&path, &["push", "--include-archived"]);
+    assert!(output.contains("1 succeeded"), "{output}");
+}
@@ -162,3 +162,114 @@ fn test_git_list_local_branches() {
     let branches = git_ops.list_local_branches().unwrap();
     assert!(!branches.is_empty());
 }
+
+#[test]
+fn test_git_fetch_refspec_writes_fetch_head() {
+    let upstream_dir = TempDir::new().unwrap();
+    let upstream = git2::Repository::init(upstream_dir.path()).unwrap();
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let tree_id = upstream.index().unwrap().write_tree().unwrap();
+    let tree = upstream.find_tree(tree_id).unwrap();
+    let commit = upstream
+        .commit(Some("HEAD"), &sig, &sig, "Initial", &tree, &[])
+        .unwrap();
+    // A ref outside refs/heads, like a pull request head
+    upstream
+        .reference("refs/pull/1/head", commit, true, "test")
+        .unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let git_ops = GitOperations::init(temp_dir.path()).unwrap();
+    git_ops
+        .add_remote("origin", upstream_dir.path().to_str().unwrap())
+        .unwrap();
+
+    git_ops.fetch("origin", &["refs/pull/1/head"]).unwrap();
+
+    let fetch_head = fs::read_to_string(git_ops.path().join("FETCH_HEAD")).unwrap();
+    assert!(fetch_head.starts_with(&commit.to_string()));
+    assert!(git_ops.inner().refname_to_id("FETCH_HEAD").is_ok());
+}
+
+fn commit_file(repo: &git2::Repository, path: &str, content: &str, message: &str) -> git2::Oid {
+    fs::write(repo.workdir().unwrap().join(path), content).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new(path)).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit<'_>> = parent.iter().collect();
+    repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+        .unwrap()
+}
+
+/// Repository with `a.txt` on the default branch and one more commit on
+/// `feature`; HEAD is back on the default branch
+fn repo_with_feature(feature_path: &str, feature_content: &str) -> (TempDir, GitOperations) {
+    let temp_dir = TempDir::new().unwrap();
+    let git_ops = GitOperations::init(temp_dir.path()).unwrap();
+    {
+        let repo = git_ops.inner();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+
+        commit_file(repo, "a.txt", "base\n", "Initial");
+        let main = repo.head().unwrap().name().unwrap().to_string();
+        let base = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("feature", &base, false).unwrap();
+        repo.set_head("refs/heads/feature").unwrap();
+        commit_file(repo, feature_path, feature_content, "Fix on feature");
+        repo.set_head(&main).unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+    }
+
+    (temp_dir, git_ops)
+}
+
+#[test]
+fn test_git_cherry_pick_clean() {
+    use multigit::git::operations::CherryPickOutcome;
+
+    let (temp_dir, git_ops) = repo_with_feature("b.txt", "fix\n");
+
+    let oid = match git_ops.cherry_pick("feature").unwrap() {
+        CherryPickOutcome::Committed(oid) => oid,
+        outcome => panic!("expected a clean pick, got {outcome:?}"),
+    };
+    let commit = git_ops.find_commit(oid).unwrap();
+    assert_eq!(commit.summary(), Some("Fix on feature"));
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("b.txt")).unwrap(),
+        "fix\n"
+    );
+    assert!(git_ops.operation_in_progress().unwrap().is_none());
+}
+
+#[test]
+fn test_git_cherry_pick_conflict_then_conclude() {
+    use multigit::git::operations::CherryPickOutcome;
+
+    let (temp_dir, git_ops) = repo_with_feature("a.txt", "feature\n");
+    commit_file(git_ops.inner(), "a.txt", "main\n", "Change on main");
+
+    assert_eq!(
+        git_ops.cherry_pick("feature").unwrap(),
+        CherryPickOutcome::Conflicts(vec!["a.txt".to_string()])
+    );
+    assert!(git_ops.conclude_cherry_pick().is_err());
+
+    fs::write(temp_dir.path().join("a.txt"), "merged\n").unwrap();
+    let mut index = git_ops.inner().index().unwrap();
+    index.add_path(Path::new("a.txt")).unwrap();
+    index.write().unwrap();
+
+    let oid = git_ops.conclude_cherry_pick().unwrap();
+    assert_eq!(
+        git_ops.find_commit(oid).unwrap().summary(),
+        Some("Fix on feature")
+    );
+    assert!(git_ops.operation_in_progress().unwrap().is_none());
+}
@@ -2,4 +2,5 @@
 
 mod cli_tests;
 mod git_operations_tests;
+mod sync_tests;
 mod workflow_tests;
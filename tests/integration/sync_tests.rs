@@ -0,0 +1,87 @@
+//! End-to-end push/fetch tests against a bare local remote
+
+use crate::fixtures::{commit_file, BareRemoteFixture};
+use multigit::core::sync_manager::SyncManager;
+use multigit::git::operations::GitOperations;
+
+#[tokio::test]
+async fn test_push_all_to_bare_remote() {
+    let fixture = BareRemoteFixture::new("origin");
+    let manager = SyncManager::new(&fixture.work).unwrap();
+
+    let results = manager
+        .push_all(&fixture.branch, std::slice::from_ref(&fixture.remote_name))
+        .await
+        .unwrap();
+
+    assert_eq!(results.len(), 1);
+    assert!(results[0].success, "push failed: {}", results[0].message);
+
+    let local_head = GitOperations::open(&fixture.work)
+        .unwrap()
+        .head_commit()
+        .unwrap()
+        .id();
+    assert_eq!(fixture.remote_head(), Some(local_head));
+}
+
+#[tokio::test]
+async fn test_push_rejected_when_diverged() {
+    let fixture = BareRemoteFixture::new("origin");
+    let manager = SyncManager::new(&fixture.work).unwrap();
+    let remotes = vec![fixture.remote_name.clone()];
+
+    manager.push_all(&fixture.branch, &remotes).await.unwrap();
+
+    // Both sides commit on top of the shared history
+    let remote_commit = fixture.commit_on_remote("remote.txt", "remote", "Remote change");
+    commit_file(&fixture.work, "local.txt", "local", "Local change");
+
+    let results = manager.push_all(&fixture.branch, &remotes).await.unwrap();
+
+    assert_eq!(results.len(), 1);
+    assert!(!results[0].success);
+    assert!(results[0].rejected);
+    assert!(results[0].message.contains("diverged"));
+    // The remote keeps its own history
+    assert_eq!(fixture.remote_head(), Some(remote_commit));
+
+    let results = manager
+        .force_push_all(&fixture.branch, &remotes)
+        .await
+        .unwrap();
+    assert!(
+        results[0].success,
+        "force push failed: {}",
+        results[0].message
+    );
+    assert_ne!(fixture.remote_head(), Some(remote_commit));
+}
+
+#[tokio::test]
+async fn test_ahead_behind_after_remote_commit() {
+    let fixture = BareRemoteFixture::new("origin");
+    let manager = SyncManager::new(&fixture.work).unwrap();
+    let remotes = vec![fixture.remote_name.clone()];
+
+    manager.push_all(&fixture.branch, &remotes).await.unwrap();
+    fixture.commit_on_remote("remote.txt", "remote", "Remote change");
+
+    let fetch_results = manager.fetch_all(&remotes).await.unwrap();
+    assert!(fetch_results[0].success, "{}", fetch_results[0].message);
+    assert!(fetch_results[0].commits_fetched > 0);
+
+    let git_ops = GitOperations::open(&fixture.work).unwrap();
+    assert_eq!(
+        git_ops
+            .get_ahead_behind(&fixture.branch, &fixture.remote_name)
+            .unwrap(),
+        (0, 1)
+    );
+
+    commit_file(&fixture.work, "local.txt", "local", "Local change");
+    let status = manager.get_sync_status(&fixture.branch, &remotes).unwrap();
+    let origin = &status[&fixture.remote_name];
+    assert_eq!((origin.ahead, origin.behind), (1, 1));
+    assert!(!origin.in_sync);
+}
@@ -19,11 +19,8 @@ fn test_init_and_add_remote_workflow() {
         "github".to_string(),
         RemoteConfig {
             username: "testuser".to_string(),
-            api_url: None,
-            enabled: true,
             provider: Some("github".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         },
     );
 
@@ -99,11 +96,8 @@ fn test_config_merge_workflow() {
         "github".to_string(),
         RemoteConfig {
             username: "user1".to_string(),
-            api_url: None,
-            enabled: true,
             provider: Some("github".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         },
     );
 
@@ -113,11 +107,8 @@ fn test_config_merge_workflow() {
         "gitlab".to_string(),
         RemoteConfig {
             username: "user2".to_string(),
-            api_url: None,
-            enabled: true,
             provider: Some("gitlab".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         },
     );
 
@@ -136,11 +127,8 @@ fn test_enabled_remotes_filtering_workflow() {
         "github".to_string(),
         RemoteConfig {
             username: "user1".to_string(),
-            api_url: None,
-            enabled: true,
             provider: Some("github".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         },
     );
 
@@ -148,11 +136,9 @@ fn test_enabled_remotes_filtering_workflow() {
         "gitlab".to_string(),
         RemoteConfig {
             username: "user2".to_string(),
-            api_url: None,
             enabled: false,
             provider: Some("gitlab".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         },
     );
 
@@ -160,11 +146,8 @@ fn test_enabled_remotes_filtering_workflow() {
         "bitbucket".to_string(),
         RemoteConfig {
             username: "user3".to_string(),
-            api_url: None,
-            enabled: true,
             provider: Some("bitbucket".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         },
     );
 
@@ -22,11 +22,8 @@ fn test_config_save_and_load() {
         "github".to_string(),
         RemoteConfig {
             username: "testuser".to_string(),
-            api_url: None,
-            enabled: true,
             provider: Some("github".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         },
     );
 
@@ -52,11 +49,8 @@ fn test_remote_config_enabled_filtering() {
         "github".to_string(),
         RemoteConfig {
             username: "user1".to_string(),
-            api_url: None,
-            enabled: true,
             provider: Some("github".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         },
     );
 
@@ -64,11 +58,9 @@ fn test_remote_config_enabled_filtering() {
         "gitlab".to_string(),
         RemoteConfig {
             username: "user2".to_string(),
-            api_url: None,
             enabled: false,
             provider: Some("gitlab".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         },
     );
 
@@ -87,11 +79,8 @@ fn test_config_add_and_remove_remote() {
         "test".to_string(),
         RemoteConfig {
             username: "testuser".to_string(),
-            api_url: None,
-            enabled: true,
             provider: Some("github".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         },
     );
 
@@ -111,11 +100,8 @@ fn test_config_get_remote() {
         "github".to_string(),
         RemoteConfig {
             username: "testuser".to_string(),
-            api_url: None,
-            enabled: true,
             provider: Some("github".to_string()),
-            use_ssh: false,
-            priority: 0,
+            ..Default::default()
         },
     );
 
@@ -1,8 +1,12 @@
 //! Provider module tests
 
 use multigit::models::remote::ProviderType;
+use multigit::providers::bitbucket::BitbucketProvider;
+use multigit::providers::gitea::GiteaProvider;
 use multigit::providers::github::GitHubProvider;
+use multigit::providers::gitlab::GitLabProvider;
 use multigit::providers::traits::{Protocol, Provider};
+use multigit::utils::git_url::{self, UrlProtocol};
 
 #[test]
 fn test_github_provider_creation() {
@@ -121,3 +125,51 @@ fn test_self_hosted_urls() {
         ProviderType::Gitea.ssh_url_template("user", "repo", Some("https://git.example.com"));
     assert_eq!(gitea_ssh, "git@git.example.com:user/repo.git");
 }
+
+#[test]
+fn test_remote_urls_round_trip() {
+    let providers: Vec<(Box<dyn Provider>, &str, Option<&str>)> = vec![
+        (
+            Box::new(GitHubProvider::new("token".into(), "alice".into()).unwrap()),
+            "github.com",
+            Some("github"),
+        ),
+        (
+            Box::new(GitLabProvider::new("token".into(), "alice".into(), None).unwrap()),
+            "gitlab.com",
+            Some("gitlab"),
+        ),
+        (
+            Box::new(BitbucketProvider::new("alice".into(), "password".into()).unwrap()),
+            "bitbucket.org",
+            Some("bitbucket"),
+        ),
+        (
+            Box::new(
+                GiteaProvider::new(
+                    "token".into(),
+                    "alice".into(),
+                    "https://git.example.com".into(),
+                )
+                .unwrap(),
+            ),
+            "git.example.com",
+            None,
+        ),
+    ];
+
+    for (provider, host, known) in providers {
+        for (protocol, expected) in [
+            (Protocol::Https, UrlProtocol::Https),
+            (Protocol::Ssh, UrlProtocol::Ssh),
+        ] {
+            let url = provider.get_remote_url("project", protocol);
+            let parsed = git_url::parse(&url).unwrap();
+            assert_eq!(parsed.host, host, "{url}");
+            assert_eq!(parsed.owner, "alice", "{url}");
+            assert_eq!(parsed.repo, "project", "{url}");
+            assert_eq!(parsed.protocol, expected, "{url}");
+            assert_eq!(parsed.provider(), known, "{url}");
+        }
+    }
+}
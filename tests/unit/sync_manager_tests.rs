@@ -57,3 +57,91 @@ fn test_is_clean() {
     let is_clean = git_ops.is_clean();
     assert!(is_clean.is_ok());
 }
+
+#[test]
+fn test_refuses_during_merge() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo = Repository::init(temp_dir.path()).unwrap();
+
+    let manager = SyncManager::new(temp_dir.path()).unwrap();
+    assert!(manager.ensure_no_operation_in_progress("sync").is_ok());
+
+    std::fs::write(
+        repo.path().join("MERGE_HEAD"),
+        "0000000000000000000000000000000000000000\n",
+    )
+    .unwrap();
+
+    let err = manager
+        .ensure_no_operation_in_progress("sync")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("merge in progress"));
+    assert!(err.contains("multigit abort"));
+}
+
+#[tokio::test]
+async fn test_cancelled_manager_reports_interrupted_remotes() {
+    let temp_dir = TempDir::new().unwrap();
+    Repository::init(temp_dir.path()).unwrap();
+
+    let cancel = tokio_util::sync::CancellationToken::new();
+    cancel.cancel();
+    let manager = SyncManager::new(temp_dir.path())
+        .unwrap()
+        .with_cancellation(cancel);
+
+    let remotes = vec!["origin".to_string(), "mirror".to_string()];
+    let results = manager.push_all("main", &remotes).await.unwrap();
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|r| r.cancelled && !r.success));
+
+    let results = manager.fetch_all(&remotes).await.unwrap();
+    assert!(results.iter().all(|r| r.cancelled));
+    assert!(manager.is_cancelled());
+}
+
+#[test]
+fn test_fast_forward_from_tracking_ref() {
+    let temp_dir = TempDir::new().unwrap();
+    let repo = Repository::init(temp_dir.path()).unwrap();
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+
+    let tree_id = repo.index().unwrap().write_tree().unwrap();
+    let tree = repo.find_tree(tree_id).unwrap();
+    let first = repo
+        .commit(Some("HEAD"), &sig, &sig, "Initial", &tree, &[])
+        .unwrap();
+    let first = repo.find_commit(first).unwrap();
+    let second = repo
+        .commit(None, &sig, &sig, "Remote work", &tree, &[&first])
+        .unwrap();
+
+    let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+    repo.reference(
+        &format!("refs/remotes/origin/{branch}"),
+        second,
+        true,
+        "test",
+    )
+    .unwrap();
+
+    let manager = SyncManager::new(temp_dir.path()).unwrap();
+    assert_eq!(manager.ahead_behind(&branch, "origin").unwrap(), (0, 1));
+    assert_eq!(manager.fast_forward(&branch, "origin").unwrap(), 1);
+    assert_eq!(manager.ahead_behind(&branch, "origin").unwrap(), (0, 0));
+    assert_eq!(repo.head().unwrap().target(), Some(second));
+
+    // Local commits block a fast-forward
+    let head = repo.find_commit(second).unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "Local work", &tree, &[&head])
+        .unwrap();
+    repo.reference(
+        &format!("refs/remotes/origin/{branch}"),
+        first.id(),
+        true,
+        "test",
+    )
+    .unwrap();
+    assert!(manager.fast_forward(&branch, "origin").is_err());
+}